@@ -274,6 +274,10 @@ const UNSAFE_OBJECT_METHODS: &[(&str, &str)] = &[
     ("Object", "call_deferred"),
 ];
 
+/// `String` arguments with these names are method, signal or property names, which are taken as
+/// `impl NameArg` to allow passing cached `StringName`s.
+const NAME_ARGUMENTS: &[&str] = &["method", "signal", "property"];
+
 pub(crate) fn generate_methods(
     class: &GodotClass,
     icalls: &mut HashMap<String, MethodSig>,
@@ -334,10 +338,17 @@ pub(crate) fn generate_methods(
         let mut params_use = TokenStream::new();
         for argument in &method.arguments {
             let ty = argument.get_type();
-            let rust_ty = ty.to_rust_arg();
             let name = rust_safe_name(&argument.name);
 
-            let arg_erased = arg_erase(&ty, &name);
+            let (rust_ty, arg_erased) =
+                if ty == Ty::String && NAME_ARGUMENTS.contains(&argument.name.as_str()) {
+                    (
+                        syn::parse_quote! { impl NameArg },
+                        quote! { #name.into_name() },
+                    )
+                } else {
+                    (ty.to_rust_arg(), arg_erase(&ty, &name))
+                };
 
             params_decl.extend(quote! {
                 , #name: #rust_ty
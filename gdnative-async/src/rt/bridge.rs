@@ -7,8 +7,8 @@ use gdnative_bindings::{Object, Reference};
 use gdnative_core::core_types::{GodotError, Variant, VariantArray};
 use gdnative_core::export::user_data::{ArcData, Map};
 use gdnative_core::export::{ClassBuilder, Method, NativeClass, NativeClassMethods, Varargs};
-use gdnative_core::godot_site;
 use gdnative_core::object::{Instance, TInstance, TRef};
+use gdnative_core::{godot_site, string_name};
 
 use crate::future::Resume;
 
@@ -71,7 +71,7 @@ impl SignalBridge {
         source.connect(
             signal,
             bridge.base(),
-            "_on_signal",
+            string_name!("_on_signal"),
             VariantArray::new_shared(),
             Object::CONNECT_ONESHOT,
         )?;
@@ -4,9 +4,9 @@ use gdnative_core::export::user_data::{LocalCellData, Map, MapMut};
 use gdnative_core::export::{
     ClassBuilder, NativeClass, NativeClassMethods, StaticArgs, StaticArgsMethod,
 };
-use gdnative_core::godot_site;
 use gdnative_core::object::ownership::Unique;
 use gdnative_core::object::{Instance, TInstance};
+use gdnative_core::{godot_site, string_name};
 use gdnative_derive::FromVarargs;
 
 use crate::future::Resume;
@@ -63,7 +63,7 @@ pub(super) fn resolve(this: TInstance<'_, FuncState>, value: Variant) {
         })
         .expect("no reentrancy");
 
-    this.base().emit_signal("completed", &[value]);
+    this.base().emit_signal(string_name!("completed"), &[value]);
 }
 
 pub(super) fn make_resumable(this: TInstance<'_, FuncState>, resume: Resume<Variant>) {
@@ -83,7 +83,7 @@ pub(super) fn make_resumable(this: TInstance<'_, FuncState>, resume: Resume<Vari
             );
         }
         Kind::Pending => {
-            this.base().emit_signal("resumable", &[]);
+            this.base().emit_signal(string_name!("resumable"), &[]);
         }
     }
 }
@@ -2,7 +2,10 @@ use crate::private::get_api;
 use crate::sys;
 use std::mem::transmute;
 
-use crate::core_types::GodotString;
+use crate::core_types::{GodotString, RealConv};
+
+mod names;
+mod ok_color;
 
 /// RGBA color with 32-bit floating point components.
 #[repr(C)]
@@ -39,7 +42,15 @@ impl Color {
             b: 0.0,
             a: 0.0,
         };
-        Color::from_sys(unsafe { (get_api().godot_color_from_hsv)(color.sys(), h, s, v, a) })
+        Color::from_sys(unsafe {
+            (get_api().godot_color_from_hsv)(
+                color.sys(),
+                sys::godot_real::from_f32(h),
+                sys::godot_real::from_f32(s),
+                sys::godot_real::from_f32(v),
+                sys::godot_real::from_f32(a),
+            )
+        })
     }
 
     /// Parses from a HTML color code, or `None` on parse error.
@@ -129,17 +140,17 @@ impl Color {
 
     #[inline]
     pub fn h(&self) -> f32 {
-        unsafe { (get_api().godot_color_get_h)(self.sys()) }
+        unsafe { (get_api().godot_color_get_h)(self.sys()) }.as_f32()
     }
 
     #[inline]
     pub fn s(&self) -> f32 {
-        unsafe { (get_api().godot_color_get_s)(self.sys()) }
+        unsafe { (get_api().godot_color_get_s)(self.sys()) }.as_f32()
     }
 
     #[inline]
     pub fn v(&self) -> f32 {
-        unsafe { (get_api().godot_color_get_v)(self.sys()) }
+        unsafe { (get_api().godot_color_get_v)(self.sys()) }.as_f32()
     }
 
     #[inline]
@@ -164,7 +175,9 @@ impl Color {
 
     #[inline]
     pub fn darkened(&self, amount: f32) -> Color {
-        Color::from_sys(unsafe { (get_api().godot_color_darkened)(self.sys(), amount) })
+        Color::from_sys(unsafe {
+            (get_api().godot_color_darkened)(self.sys(), sys::godot_real::from_f32(amount))
+        })
     }
 
     #[inline]
@@ -184,6 +197,119 @@ impl Color {
         }
     }
 
+    /// Returns the color converted from sRGB to linear encoding, as needed for blending and
+    /// lighting calculations. Alpha is unchanged.
+    ///
+    /// Colors in the engine, like those picked in the editor, are sRGB-encoded.
+    #[inline]
+    pub fn to_linear(self) -> Color {
+        Color {
+            r: ok_color::srgb_transfer_inv(self.r),
+            g: ok_color::srgb_transfer_inv(self.g),
+            b: ok_color::srgb_transfer_inv(self.b),
+            a: self.a,
+        }
+    }
+
+    /// Returns the color converted from linear to sRGB encoding. This is the inverse of
+    /// [`to_linear`][Self::to_linear]. Alpha is unchanged.
+    #[inline]
+    pub fn to_srgb(self) -> Color {
+        Color {
+            r: ok_color::srgb_transfer(self.r),
+            g: ok_color::srgb_transfer(self.g),
+            b: ok_color::srgb_transfer(self.b),
+            a: self.a,
+        }
+    }
+
+    /// Returns the relative luminance of the color, from 0.0 for black to 1.0 for white.
+    ///
+    /// The color must be linear, so sRGB-encoded colors should be converted with
+    /// [`to_linear`][Self::to_linear] first.
+    #[inline]
+    pub fn luminance(self) -> f32 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+
+    /// Constructs a sRGB-encoded color from the lightness `l` and the green-red and blue-yellow
+    /// axes `a` and `b` of the perceptual [OKLab](https://bottosson.github.io/posts/oklab/)
+    /// color space.
+    ///
+    /// The result is not clamped, so it can be outside of the 0.0-1.0 range if the OKLab color
+    /// is outside of the sRGB gamut.
+    #[inline]
+    pub fn from_oklab(l: f32, a: f32, b: f32, alpha: f32) -> Color {
+        let [r, g, b] = ok_color::oklab_to_linear_srgb([l, a, b]);
+        Color::from_rgba(r, g, b, alpha).to_srgb()
+    }
+
+    /// Returns the OKLab coordinates `(l, a, b)` of this sRGB-encoded color. See
+    /// [`from_oklab`][Self::from_oklab].
+    ///
+    /// Distances in OKLab match perceived color differences well, and interpolating in it avoids
+    /// the muddy midpoints of RGB interpolation.
+    #[inline]
+    pub fn to_oklab(self) -> (f32, f32, f32) {
+        let linear = self.to_linear();
+        let [l, a, b] = ok_color::linear_srgb_to_oklab([linear.r, linear.g, linear.b]);
+        (l, a, b)
+    }
+
+    /// Constructs a sRGB-encoded color from hue, saturation and lightness in the
+    /// [OKHSL](https://bottosson.github.io/posts/colorpicker/) color space, each from 0.0 to 1.0.
+    ///
+    /// Unlike HSV, the lightness of OKHSL colors matches their perceived brightness, independent of
+    /// the hue. This corresponds to `Color.from_ok_hsl` in Godot 4, and clamps the result to the
+    /// 0.0-1.0 range like it.
+    #[inline]
+    pub fn from_ok_hsl(h: f32, s: f32, l: f32, alpha: f32) -> Color {
+        let [r, g, b] = ok_color::okhsl_to_srgb([h, s, l]);
+        let clamp = |x: f32| x.clamp(0.0, 1.0);
+        Color::from_rgba(clamp(r), clamp(g), clamp(b), clamp(alpha))
+    }
+
+    /// Returns the OKHSL hue, saturation and lightness `(h, s, l)` of this sRGB-encoded color. See
+    /// [`from_ok_hsl`][Self::from_ok_hsl].
+    ///
+    /// Hue and saturation are 0.0 for black, and close to 0.0 for other grays.
+    #[inline]
+    pub fn to_ok_hsl(self) -> (f32, f32, f32) {
+        let zero_nan = |x: f32| if x.is_nan() { 0.0 } else { x };
+        let [h, s, l] = ok_color::srgb_to_okhsl([self.r, self.g, self.b]);
+        (zero_nan(h), zero_nan(s), zero_nan(l))
+    }
+
+    /// Looks up a color by name in the engine's table of named colors, or returns `None` if there
+    /// is no such color.
+    ///
+    /// Like the engine, this ignores case, spaces and the characters `-`, `_`, `'` and `.`.
+    ///
+    /// ```
+    /// use gdnative::prelude::Color;
+    ///
+    /// let color = Color::named("Alice Blue").unwrap();
+    /// assert_eq!(Color::named("aliceblue"), Some(color));
+    /// assert_eq!(Color::named("not a color"), None);
+    /// ```
+    ///
+    /// This corresponds to the
+    /// [GDScript method `ColorN`](https://docs.godotengine.org/en/3.5/classes/class_@gdscript.html#class-gdscript-method-colorn).
+    #[inline]
+    pub fn named(name: &str) -> Option<Color> {
+        let name = names::normalize(name);
+        names::NAMED_COLORS
+            .binary_search_by_key(&name.as_str(), |&(name, _)| name)
+            .ok()
+            .map(|index| names::NAMED_COLORS[index].1)
+    }
+
+    /// Returns the names and values of all colors known to [`named`][Self::named], sorted by name.
+    #[inline]
+    pub fn named_colors() -> &'static [(&'static str, Color)] {
+        &names::NAMED_COLORS
+    }
+
     #[inline]
     pub fn to_html(self, with_alpha: bool) -> GodotString {
         GodotString::from_sys(unsafe { (get_api().godot_color_to_html)(self.sys(), with_alpha) })
@@ -303,6 +429,121 @@ fn color_repr() {
     assert_eq!(size_of::<Color>(), size_of::<sys::godot_color>());
 }
 
+#[test]
+fn color_srgb_linear() {
+    // Values computed with the sRGB transfer functions.
+    let color = Color::from_rgba(0.5, 0.0, 1.0, 0.25);
+    let linear = color.to_linear();
+    assert!((linear.r - 0.214_041_14).abs() < 1e-6);
+    assert_eq!((linear.g, linear.b, linear.a), (0.0, 1.0, 0.25));
+    assert!((0.0_f32.to_bits()..=1.0_f32.to_bits())
+        .step_by(1 << 16)
+        .map(f32::from_bits)
+        .all(|x| {
+            let c = Color::from_rgb(x, x, x);
+            (c.to_linear().to_srgb().r - x).abs() < 1e-5
+                && (c.to_srgb().to_linear().r - x).abs() < 1e-5
+        }));
+
+    assert!((Color::from_rgb(1.0, 1.0, 1.0).luminance() - 1.0).abs() < 1e-6);
+    assert!(
+        Color::from_rgb(0.0, 1.0, 0.0).luminance() > Color::from_rgb(1.0, 0.0, 0.0).luminance()
+    );
+}
+
+#[test]
+fn color_oklab() {
+    let near = |a: f32, b: f32| (a - b).abs() < 1e-4;
+
+    // Reference values from https://bottosson.github.io/posts/oklab/.
+    let (l, a, b) = Color::from_rgb(1.0, 1.0, 1.0).to_oklab();
+    assert!(near(l, 1.0) && near(a, 0.0) && near(b, 0.0));
+    let (l, a, b) = Color::from_rgb(1.0, 0.0, 0.0).to_oklab();
+    assert!(near(l, 0.627_955) && near(a, 0.224_863) && near(b, 0.125_846));
+
+    for color in [
+        Color::from_rgba(1.0, 0.0, 0.0, 0.5),
+        Color::from_rgb(0.2, 0.6, 0.9),
+        Color::from_rgb(0.0, 0.0, 0.0),
+    ] {
+        let (l, a, b) = color.to_oklab();
+        let back = Color::from_oklab(l, a, b, color.a);
+        assert!(near(back.r, color.r) && near(back.g, color.g) && near(back.b, color.b));
+        assert_eq!(back.a, color.a);
+    }
+}
+
+#[test]
+fn color_ok_hsl() {
+    let near = |a: f32, b: f32| (a - b).abs() < 1e-3;
+
+    assert_eq!(Color::from_rgb(0.0, 0.0, 0.0).to_ok_hsl(), (0.0, 0.0, 0.0));
+    let (_, s, l) = Color::from_rgb(0.5, 0.5, 0.5).to_ok_hsl();
+    assert!(near(s, 0.0));
+    assert!(near(l, 0.533_76));
+    assert_eq!(
+        Color::from_ok_hsl(0.3, 0.5, 1.0, 1.0),
+        Color::from_rgb(1.0, 1.0, 1.0)
+    );
+    assert_eq!(
+        Color::from_ok_hsl(0.3, 0.5, 0.0, 1.0),
+        Color::from_rgb(0.0, 0.0, 0.0)
+    );
+
+    // Fully saturated colors are on the sRGB gamut boundary.
+    let (h, s, _) = Color::from_rgb(1.0, 0.0, 0.0).to_ok_hsl();
+    assert!(near(h, 29.233_885 / 360.0));
+    assert!((s - 1.0).abs() < 1e-2);
+
+    for color in [
+        Color::from_rgb(1.0, 0.0, 0.0),
+        Color::from_rgb(0.2, 0.6, 0.9),
+        Color::from_rgba(0.9, 0.8, 0.1, 0.5),
+        Color::from_rgb(0.3, 0.25, 0.2),
+    ] {
+        let (h, s, l) = color.to_ok_hsl();
+        let back = Color::from_ok_hsl(h, s, l, color.a);
+        assert!(
+            near(back.r, color.r) && near(back.g, color.g) && near(back.b, color.b),
+            "{color:?} -> ({h}, {s}, {l}) -> {back:?}"
+        );
+        assert_eq!(back.a, color.a);
+    }
+}
+
+#[test]
+fn color_named() {
+    let names = Color::named_colors();
+    assert!(names.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert!(names
+        .iter()
+        .all(|&(name, _)| names::normalize(name) == name));
+
+    let alice_blue = Color::from_rgb(0.94, 0.97, 1.0);
+    assert_eq!(Color::named("aliceblue"), Some(alice_blue));
+    assert_eq!(Color::named("Alice Blue"), Some(alice_blue));
+    assert_eq!(Color::named("ALICE_BLUE"), Some(alice_blue));
+    assert_eq!(Color::named("alice-blue."), Some(alice_blue));
+    assert_eq!(
+        Color::named("yellowgreen"),
+        Some(Color::from_rgb(0.6, 0.8, 0.2))
+    );
+    assert_eq!(Color::named("transparent").map(|c| c.a), Some(0.0));
+
+    // The engine uses the X11 values, with separate web colors.
+    assert_eq!(
+        Color::named("gray"),
+        Some(Color::from_rgb(0.75, 0.75, 0.75))
+    );
+    assert_eq!(
+        Color::named("web gray"),
+        Some(Color::from_rgb(0.5, 0.5, 0.5))
+    );
+
+    assert_eq!(Color::named(""), None);
+    assert_eq!(Color::named("blurple"), None);
+}
+
 #[test]
 fn color_to_pixel_color_formats() {
     let color = Color::from_rgba(1.0, 0.5, 1.0, 0.0);
@@ -0,0 +1,170 @@
+use super::Color;
+
+/// The engine's named colors, as used by `ColorN` and `Color::named` in `core/color_names.inc`.
+///
+/// Sorted by name, so it can be searched with a binary search. The engine stores the components
+/// rounded to two decimals, which is kept here to produce identical values.
+pub(super) static NAMED_COLORS: [(&str, Color); 146] = [
+    ("aliceblue", rgb(0.94, 0.97, 1.00)),
+    ("antiquewhite", rgb(0.98, 0.92, 0.84)),
+    ("aqua", rgb(0.00, 1.00, 1.00)),
+    ("aquamarine", rgb(0.50, 1.00, 0.83)),
+    ("azure", rgb(0.94, 1.00, 1.00)),
+    ("beige", rgb(0.96, 0.96, 0.86)),
+    ("bisque", rgb(1.00, 0.89, 0.77)),
+    ("black", rgb(0.00, 0.00, 0.00)),
+    ("blanchedalmond", rgb(1.00, 0.92, 0.80)),
+    ("blue", rgb(0.00, 0.00, 1.00)),
+    ("blueviolet", rgb(0.54, 0.17, 0.89)),
+    ("brown", rgb(0.65, 0.16, 0.16)),
+    ("burlywood", rgb(0.87, 0.72, 0.53)),
+    ("cadetblue", rgb(0.37, 0.62, 0.63)),
+    ("chartreuse", rgb(0.50, 1.00, 0.00)),
+    ("chocolate", rgb(0.82, 0.41, 0.12)),
+    ("coral", rgb(1.00, 0.50, 0.31)),
+    ("cornflower", rgb(0.39, 0.58, 0.93)),
+    ("cornsilk", rgb(1.00, 0.97, 0.86)),
+    ("crimson", rgb(0.86, 0.08, 0.24)),
+    ("cyan", rgb(0.00, 1.00, 1.00)),
+    ("darkblue", rgb(0.00, 0.00, 0.55)),
+    ("darkcyan", rgb(0.00, 0.55, 0.55)),
+    ("darkgoldenrod", rgb(0.72, 0.53, 0.04)),
+    ("darkgray", rgb(0.66, 0.66, 0.66)),
+    ("darkgreen", rgb(0.00, 0.39, 0.00)),
+    ("darkkhaki", rgb(0.74, 0.72, 0.42)),
+    ("darkmagenta", rgb(0.55, 0.00, 0.55)),
+    ("darkolivegreen", rgb(0.33, 0.42, 0.18)),
+    ("darkorange", rgb(1.00, 0.55, 0.00)),
+    ("darkorchid", rgb(0.60, 0.20, 0.80)),
+    ("darkred", rgb(0.55, 0.00, 0.00)),
+    ("darksalmon", rgb(0.91, 0.59, 0.48)),
+    ("darkseagreen", rgb(0.56, 0.74, 0.56)),
+    ("darkslateblue", rgb(0.28, 0.24, 0.55)),
+    ("darkslategray", rgb(0.18, 0.31, 0.31)),
+    ("darkturquoise", rgb(0.00, 0.81, 0.82)),
+    ("darkviolet", rgb(0.58, 0.00, 0.83)),
+    ("deeppink", rgb(1.00, 0.08, 0.58)),
+    ("deepskyblue", rgb(0.00, 0.75, 1.00)),
+    ("dimgray", rgb(0.41, 0.41, 0.41)),
+    ("dodgerblue", rgb(0.12, 0.56, 1.00)),
+    ("firebrick", rgb(0.70, 0.13, 0.13)),
+    ("floralwhite", rgb(1.00, 0.98, 0.94)),
+    ("forestgreen", rgb(0.13, 0.55, 0.13)),
+    ("fuchsia", rgb(1.00, 0.00, 1.00)),
+    ("gainsboro", rgb(0.86, 0.86, 0.86)),
+    ("ghostwhite", rgb(0.97, 0.97, 1.00)),
+    ("gold", rgb(1.00, 0.84, 0.00)),
+    ("goldenrod", rgb(0.85, 0.65, 0.13)),
+    ("gray", rgb(0.75, 0.75, 0.75)),
+    ("green", rgb(0.00, 1.00, 0.00)),
+    ("greenyellow", rgb(0.68, 1.00, 0.18)),
+    ("honeydew", rgb(0.94, 1.00, 0.94)),
+    ("hotpink", rgb(1.00, 0.41, 0.71)),
+    ("indianred", rgb(0.80, 0.36, 0.36)),
+    ("indigo", rgb(0.29, 0.00, 0.51)),
+    ("ivory", rgb(1.00, 1.00, 0.94)),
+    ("khaki", rgb(0.94, 0.90, 0.55)),
+    ("lavender", rgb(0.90, 0.90, 0.98)),
+    ("lavenderblush", rgb(1.00, 0.94, 0.96)),
+    ("lawngreen", rgb(0.49, 0.99, 0.00)),
+    ("lemonchiffon", rgb(1.00, 0.98, 0.80)),
+    ("lightblue", rgb(0.68, 0.85, 0.90)),
+    ("lightcoral", rgb(0.94, 0.50, 0.50)),
+    ("lightcyan", rgb(0.88, 1.00, 1.00)),
+    ("lightgoldenrod", rgb(0.98, 0.98, 0.82)),
+    ("lightgray", rgb(0.83, 0.83, 0.83)),
+    ("lightgreen", rgb(0.56, 0.93, 0.56)),
+    ("lightpink", rgb(1.00, 0.71, 0.76)),
+    ("lightsalmon", rgb(1.00, 0.63, 0.48)),
+    ("lightseagreen", rgb(0.13, 0.70, 0.67)),
+    ("lightskyblue", rgb(0.53, 0.81, 0.98)),
+    ("lightslategray", rgb(0.47, 0.53, 0.60)),
+    ("lightsteelblue", rgb(0.69, 0.77, 0.87)),
+    ("lightyellow", rgb(1.00, 1.00, 0.88)),
+    ("lime", rgb(0.00, 1.00, 0.00)),
+    ("limegreen", rgb(0.20, 0.80, 0.20)),
+    ("linen", rgb(0.98, 0.94, 0.90)),
+    ("magenta", rgb(1.00, 0.00, 1.00)),
+    ("maroon", rgb(0.69, 0.19, 0.38)),
+    ("mediumaquamarine", rgb(0.40, 0.80, 0.67)),
+    ("mediumblue", rgb(0.00, 0.00, 0.80)),
+    ("mediumorchid", rgb(0.73, 0.33, 0.83)),
+    ("mediumpurple", rgb(0.58, 0.44, 0.86)),
+    ("mediumseagreen", rgb(0.24, 0.70, 0.44)),
+    ("mediumslateblue", rgb(0.48, 0.41, 0.93)),
+    ("mediumspringgreen", rgb(0.00, 0.98, 0.60)),
+    ("mediumturquoise", rgb(0.28, 0.82, 0.80)),
+    ("mediumvioletred", rgb(0.78, 0.08, 0.52)),
+    ("midnightblue", rgb(0.10, 0.10, 0.44)),
+    ("mintcream", rgb(0.96, 1.00, 0.98)),
+    ("mistyrose", rgb(1.00, 0.89, 0.88)),
+    ("moccasin", rgb(1.00, 0.89, 0.71)),
+    ("navajowhite", rgb(1.00, 0.87, 0.68)),
+    ("navyblue", rgb(0.00, 0.00, 0.50)),
+    ("oldlace", rgb(0.99, 0.96, 0.90)),
+    ("olive", rgb(0.50, 0.50, 0.00)),
+    ("olivedrab", rgb(0.42, 0.56, 0.14)),
+    ("orange", rgb(1.00, 0.65, 0.00)),
+    ("orangered", rgb(1.00, 0.27, 0.00)),
+    ("orchid", rgb(0.85, 0.44, 0.84)),
+    ("palegoldenrod", rgb(0.93, 0.91, 0.67)),
+    ("palegreen", rgb(0.60, 0.98, 0.60)),
+    ("paleturquoise", rgb(0.69, 0.93, 0.93)),
+    ("palevioletred", rgb(0.86, 0.44, 0.58)),
+    ("papayawhip", rgb(1.00, 0.94, 0.84)),
+    ("peachpuff", rgb(1.00, 0.85, 0.73)),
+    ("peru", rgb(0.80, 0.52, 0.25)),
+    ("pink", rgb(1.00, 0.75, 0.80)),
+    ("plum", rgb(0.87, 0.63, 0.87)),
+    ("powderblue", rgb(0.69, 0.88, 0.90)),
+    ("purple", rgb(0.63, 0.13, 0.94)),
+    ("rebeccapurple", rgb(0.40, 0.20, 0.60)),
+    ("red", rgb(1.00, 0.00, 0.00)),
+    ("rosybrown", rgb(0.74, 0.56, 0.56)),
+    ("royalblue", rgb(0.25, 0.41, 0.88)),
+    ("saddlebrown", rgb(0.55, 0.27, 0.07)),
+    ("salmon", rgb(0.98, 0.50, 0.45)),
+    ("sandybrown", rgb(0.96, 0.64, 0.38)),
+    ("seagreen", rgb(0.18, 0.55, 0.34)),
+    ("seashell", rgb(1.00, 0.96, 0.93)),
+    ("sienna", rgb(0.63, 0.32, 0.18)),
+    ("silver", rgb(0.75, 0.75, 0.75)),
+    ("skyblue", rgb(0.53, 0.81, 0.92)),
+    ("slateblue", rgb(0.42, 0.35, 0.80)),
+    ("slategray", rgb(0.44, 0.50, 0.56)),
+    ("snow", rgb(1.00, 0.98, 0.98)),
+    ("springgreen", rgb(0.00, 1.00, 0.50)),
+    ("steelblue", rgb(0.27, 0.51, 0.71)),
+    ("tan", rgb(0.82, 0.71, 0.55)),
+    ("teal", rgb(0.00, 0.50, 0.50)),
+    ("thistle", rgb(0.85, 0.75, 0.85)),
+    ("tomato", rgb(1.00, 0.39, 0.28)),
+    ("transparent", rgba(1.00, 1.00, 1.00, 0.00)),
+    ("turquoise", rgb(0.25, 0.88, 0.82)),
+    ("violet", rgb(0.93, 0.51, 0.93)),
+    ("webgray", rgb(0.50, 0.50, 0.50)),
+    ("webgreen", rgb(0.00, 0.50, 0.00)),
+    ("webmaroon", rgb(0.50, 0.00, 0.00)),
+    ("webpurple", rgb(0.50, 0.00, 0.50)),
+    ("wheat", rgb(0.96, 0.87, 0.70)),
+    ("white", rgb(1.00, 1.00, 1.00)),
+    ("whitesmoke", rgb(0.96, 0.96, 0.96)),
+    ("yellow", rgb(1.00, 1.00, 0.00)),
+    ("yellowgreen", rgb(0.60, 0.80, 0.20)),
+];
+
+const fn rgb(r: f32, g: f32, b: f32) -> Color {
+    rgba(r, g, b, 1.0)
+}
+
+const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Color {
+    Color { r, g, b, a }
+}
+
+/// Normalizes a color name like the engine does, ignoring case, spaces and punctuation.
+pub(super) fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_' | '\'' | '.'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
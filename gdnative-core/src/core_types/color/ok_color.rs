@@ -0,0 +1,345 @@
+//! Conversions between sRGB and the OKLab and OKHSL color spaces.
+//!
+//! Port of Björn Ottosson's reference implementation (`ok_color.h`, MIT licensed), which is also
+//! used by the engine from Godot 4 on. See <https://bottosson.github.io/posts/oklab/> and
+//! <https://bottosson.github.io/posts/colorpicker/>.
+
+// The constants are kept as in the reference implementation.
+#![allow(clippy::excessive_precision)]
+
+use std::f32::consts::PI;
+
+/// Lightness and chroma.
+#[derive(Copy, Clone)]
+struct Lc {
+    l: f32,
+    c: f32,
+}
+
+/// Chroma relative to lightness (`s`), and to the distance from white (`t`).
+#[derive(Copy, Clone)]
+struct St {
+    s: f32,
+    t: f32,
+}
+
+/// sRGB transfer function, converting a linear component to its sRGB encoding.
+///
+/// The thresholds are those of the engine's `Color::to_srgb`.
+#[inline]
+pub(super) fn srgb_transfer(a: f32) -> f32 {
+    if a < 0.003_130_8 {
+        12.92 * a
+    } else {
+        1.055 * a.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Inverse of the sRGB transfer function, converting an sRGB component to linear.
+///
+/// The thresholds are those of the engine's `Color::to_linear`.
+#[inline]
+pub(super) fn srgb_transfer_inv(a: f32) -> f32 {
+    if a < 0.040_45 {
+        a / 12.92
+    } else {
+        ((a + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub(super) fn linear_srgb_to_oklab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = 0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b;
+    let m = 0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b;
+    let s = 0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b;
+
+    let (l, m, s) = (l.cbrt(), m.cbrt(), s.cbrt());
+
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+pub(super) fn oklab_to_linear_srgb([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let l_ = l + 0.3963377774 * a + 0.2158037573 * b;
+    let m_ = l - 0.1055613458 * a - 0.0638541728 * b;
+    let s_ = l - 0.0894841775 * a - 1.2914855480 * b;
+
+    let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+
+    [
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    ]
+}
+
+/// Finds the maximum saturation (`C / L`) for a hue that fits in sRGB. `a` and `b` must be
+/// normalized.
+fn compute_max_saturation(a: f32, b: f32) -> f32 {
+    // Select the sRGB component which goes below zero first.
+    let (k, w) = if -1.88170328 * a - 0.80936493 * b > 1.0 {
+        // Red
+        (
+            [1.19086277, 1.76576728, 0.59662641, 0.75515197, 0.56771245],
+            [4.0767416621, -3.3077115913, 0.2309699292],
+        )
+    } else if 1.81444104 * a - 1.19445276 * b > 1.0 {
+        // Green
+        (
+            [0.73956515, -0.45954404, 0.08285427, 0.12541070, 0.14503204],
+            [-1.2684380046, 2.6097574011, -0.3413193965],
+        )
+    } else {
+        // Blue
+        (
+            [
+                1.35733652,
+                -0.00915799,
+                -1.15130210,
+                -0.50559606,
+                0.00692167,
+            ],
+            [-0.0041960863, -0.7034186147, 1.7076147010],
+        )
+    };
+
+    // Polynomial approximation, followed by one step of Halley's method.
+    let s = k[0] + k[1] * a + k[2] * b + k[3] * a * a + k[4] * a * b;
+
+    let k_l = 0.3963377774 * a + 0.2158037573 * b;
+    let k_m = -0.1055613458 * a - 0.0638541728 * b;
+    let k_s = -0.0894841775 * a - 1.2914855480 * b;
+
+    let l_ = 1.0 + s * k_l;
+    let m_ = 1.0 + s * k_m;
+    let s_ = 1.0 + s * k_s;
+
+    let l = l_ * l_ * l_;
+    let m = m_ * m_ * m_;
+    let s3 = s_ * s_ * s_;
+
+    let l_ds = 3.0 * k_l * l_ * l_;
+    let m_ds = 3.0 * k_m * m_ * m_;
+    let s_ds = 3.0 * k_s * s_ * s_;
+
+    let l_ds2 = 6.0 * k_l * k_l * l_;
+    let m_ds2 = 6.0 * k_m * k_m * m_;
+    let s_ds2 = 6.0 * k_s * k_s * s_;
+
+    let f = w[0] * l + w[1] * m + w[2] * s3;
+    let f1 = w[0] * l_ds + w[1] * m_ds + w[2] * s_ds;
+    let f2 = w[0] * l_ds2 + w[1] * m_ds2 + w[2] * s_ds2;
+
+    s - f * f1 / (f1 * f1 - 0.5 * f * f2)
+}
+
+/// Finds the lightness and chroma of the most saturated color of a hue.
+fn find_cusp(a: f32, b: f32) -> Lc {
+    let s_cusp = compute_max_saturation(a, b);
+
+    let [r, g, b] = oklab_to_linear_srgb([1.0, s_cusp * a, s_cusp * b]);
+    let l_cusp = (1.0 / r.max(g).max(b)).cbrt();
+
+    Lc {
+        l: l_cusp,
+        c: l_cusp * s_cusp,
+    }
+}
+
+/// Finds the intersection of the line `L = L0 * (1 - t) + t * L1; C = t * C1` with the sRGB
+/// gamut.
+fn find_gamut_intersection(a: f32, b: f32, l1: f32, c1: f32, l0: f32, cusp: Lc) -> f32 {
+    if (l1 - l0) * cusp.c - (cusp.l - l0) * c1 <= 0.0 {
+        // Lower half, where the gamut is a triangle.
+        return cusp.c * l0 / (c1 * cusp.l + cusp.c * (l0 - l1));
+    }
+
+    // Upper half: intersect with the triangle, then refine with one step of Halley's method.
+    let mut t = cusp.c * (l0 - 1.0) / (c1 * (cusp.l - 1.0) + cusp.c * (l0 - l1));
+
+    let dl = l1 - l0;
+    let dc = c1;
+
+    let k_l = 0.3963377774 * a + 0.2158037573 * b;
+    let k_m = -0.1055613458 * a - 0.0638541728 * b;
+    let k_s = -0.0894841775 * a - 1.2914855480 * b;
+
+    let l_dt = dl + dc * k_l;
+    let m_dt = dl + dc * k_m;
+    let s_dt = dl + dc * k_s;
+
+    let lightness = l0 * (1.0 - t) + t * l1;
+    let chroma = t * c1;
+
+    let l_ = lightness + chroma * k_l;
+    let m_ = lightness + chroma * k_m;
+    let s_ = lightness + chroma * k_s;
+
+    let lms = [l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_];
+    let lms_dt = [
+        3.0 * l_dt * l_ * l_,
+        3.0 * m_dt * m_ * m_,
+        3.0 * s_dt * s_ * s_,
+    ];
+    let lms_dt2 = [
+        6.0 * l_dt * l_dt * l_,
+        6.0 * m_dt * m_dt * m_,
+        6.0 * s_dt * s_dt * s_,
+    ];
+
+    // Step for the component reaching 1, separately for red, green and blue.
+    let step = |w: [f32; 3]| {
+        let dot = |v: [f32; 3]| w[0] * v[0] + w[1] * v[1] + w[2] * v[2];
+        let (f, f1, f2) = (dot(lms) - 1.0, dot(lms_dt), dot(lms_dt2));
+        let u = f1 / (f1 * f1 - 0.5 * f * f2);
+        if u >= 0.0 {
+            -f * u
+        } else {
+            f32::MAX
+        }
+    };
+
+    let t_r = step([4.0767416621, -3.3077115913, 0.2309699292]);
+    let t_g = step([-1.2684380046, 2.6097574011, -0.3413193965]);
+    let t_b = step([-0.0041960863, -0.7034186147, 1.7076147010]);
+
+    t += t_r.min(t_g.min(t_b));
+    t
+}
+
+const TOE_K1: f32 = 0.206;
+const TOE_K2: f32 = 0.03;
+const TOE_K3: f32 = (1.0 + TOE_K1) / (1.0 + TOE_K2);
+
+/// Maps OKLab lightness to a lightness estimate closer to CIELab.
+fn toe(x: f32) -> f32 {
+    let y = TOE_K3 * x - TOE_K1;
+    0.5 * (y + (y * y + 4.0 * TOE_K2 * TOE_K3 * x).sqrt())
+}
+
+fn toe_inv(x: f32) -> f32 {
+    (x * x + TOE_K1 * x) / (TOE_K3 * (x + TOE_K2))
+}
+
+fn to_st(cusp: Lc) -> St {
+    St {
+        s: cusp.c / cusp.l,
+        t: cusp.c / (1.0 - cusp.l),
+    }
+}
+
+/// Returns a smooth approximation of the location of the cusp.
+fn get_st_mid(a: f32, b: f32) -> St {
+    let s = 0.11516993
+        + 1.0
+            / (7.44778970
+                + 4.15901240 * b
+                + a * (-2.19557347
+                    + 1.75198401 * b
+                    + a * (-2.13704948 - 10.02301043 * b
+                        + a * (-4.24894561 + 5.38770819 * b + 4.69891013 * a))));
+
+    let t = 0.11239642
+        + 1.0
+            / (1.61320320 - 0.68124379 * b
+                + a * (0.40370612
+                    + 0.90148123 * b
+                    + a * (-0.27087943
+                        + 0.61223990 * b
+                        + a * (0.00299215 - 0.45399568 * b - 0.14661872 * a))));
+
+    St { s, t }
+}
+
+/// Returns the chroma at saturation 0.0 (roughly), 0.8 and 1.0 for a lightness and hue.
+fn get_cs(l: f32, a: f32, b: f32) -> [f32; 3] {
+    let cusp = find_cusp(a, b);
+
+    let c_max = find_gamut_intersection(a, b, l, 1.0, l, cusp);
+    let st_max = to_st(cusp);
+
+    // Scale factor to compensate for the curved part of the gamut shape.
+    let k = c_max / (l * st_max.s).min((1.0 - l) * st_max.t);
+
+    // Soft minimum functions instead of sharp triangle shapes, to get smooth chroma values.
+    let c_mid = {
+        let st_mid = get_st_mid(a, b);
+        let c_a = l * st_mid.s;
+        let c_b = (1.0 - l) * st_mid.t;
+        0.9 * k
+            * (1.0 / (1.0 / (c_a * c_a * c_a * c_a) + 1.0 / (c_b * c_b * c_b * c_b)))
+                .sqrt()
+                .sqrt()
+    };
+
+    // The shape is independent of the hue here, so use constants roughly averaging the above.
+    let c_0 = {
+        let c_a = l * 0.4;
+        let c_b = (1.0 - l) * 0.8;
+        (1.0 / (1.0 / (c_a * c_a) + 1.0 / (c_b * c_b))).sqrt()
+    };
+
+    [c_0, c_mid, c_max]
+}
+
+const MID: f32 = 0.8;
+const MID_INV: f32 = 1.25;
+
+pub(super) fn okhsl_to_srgb([h, s, l]: [f32; 3]) -> [f32; 3] {
+    if l == 1.0 {
+        return [1.0, 1.0, 1.0];
+    } else if l == 0.0 {
+        return [0.0, 0.0, 0.0];
+    }
+
+    let a = (2.0 * PI * h).cos();
+    let b = (2.0 * PI * h).sin();
+    let lightness = toe_inv(l);
+
+    let [c_0, c_mid, c_max] = get_cs(lightness, a, b);
+
+    let chroma = if s < MID {
+        let t = MID_INV * s;
+        let k_1 = MID * c_0;
+        let k_2 = 1.0 - k_1 / c_mid;
+        t * k_1 / (1.0 - k_2 * t)
+    } else {
+        let t = (s - MID) / (1.0 - MID);
+        let k_0 = c_mid;
+        let k_1 = (1.0 - MID) * c_mid * c_mid * MID_INV * MID_INV / c_0;
+        let k_2 = 1.0 - k_1 / (c_max - c_mid);
+        k_0 + t * k_1 / (1.0 - k_2 * t)
+    };
+
+    oklab_to_linear_srgb([lightness, chroma * a, chroma * b]).map(srgb_transfer)
+}
+
+/// Converts to OKHSL. Hue and saturation are NaN for grays, like in the reference implementation.
+pub(super) fn srgb_to_okhsl(rgb: [f32; 3]) -> [f32; 3] {
+    let [lightness, lab_a, lab_b] = linear_srgb_to_oklab(rgb.map(srgb_transfer_inv));
+
+    let chroma = (lab_a * lab_a + lab_b * lab_b).sqrt();
+    let a = lab_a / chroma;
+    let b = lab_b / chroma;
+
+    let h = 0.5 + 0.5 * (-lab_b).atan2(-lab_a) / PI;
+
+    let [c_0, c_mid, c_max] = get_cs(lightness, a, b);
+
+    let s = if chroma < c_mid {
+        let k_1 = MID * c_0;
+        let k_2 = 1.0 - k_1 / c_mid;
+        let t = chroma / (k_1 + k_2 * chroma);
+        t * MID
+    } else {
+        let k_0 = c_mid;
+        let k_1 = (1.0 - MID) * c_mid * c_mid * MID_INV * MID_INV / c_0;
+        let k_2 = 1.0 - k_1 / (c_max - c_mid);
+        let t = (chroma - k_0) / (k_1 + k_2 * (chroma - k_0));
+        MID + (1.0 - MID) * t
+    };
+
+    [h, s, toe(lightness)]
+}
@@ -1,4 +1,4 @@
-use crate::core_types::{Axis, Plane, Vector3};
+use crate::core_types::{glam_real, real, Axis, Plane, Vector3};
 
 /// Axis-aligned bounding box.
 ///
@@ -26,6 +26,26 @@ impl Aabb {
         Self { position, size }
     }
 
+    /// Returns the smallest `Aabb` containing all `points`, or `None` if there are none.
+    ///
+    /// `PoolArray` read guards dereference to slices, so they can be passed directly:
+    /// `Aabb::from_points(&array.read())`.
+    #[inline]
+    pub fn from_points(points: &[Vector3]) -> Option<Self> {
+        let (first, rest) = points.split_first()?;
+        let (min, max) = rest
+            .iter()
+            .fold((first.glam(), first.glam()), |(min, max), point| {
+                let point = point.glam();
+                (min.min(point), max.max(point))
+            });
+
+        Some(Self {
+            position: Vector3::gd(min),
+            size: Vector3::gd(max - min),
+        })
+    }
+
     /// Ending corner. This is calculated as `position + size`.
     #[inline]
     pub fn end(self) -> Vector3 {
@@ -42,7 +62,7 @@ impl Aabb {
     /// corner is the origin and the size is positive.
     #[inline]
     pub fn abs(self) -> Self {
-        let position = self.position + Vector3::gd(self.size.glam().min(glam::Vec3A::ZERO));
+        let position = self.position + Vector3::gd(self.size.glam().min(glam_real::Vec3A::ZERO));
         let size = self.size.abs();
 
         Self { position, size }
@@ -54,7 +74,7 @@ impl Aabb {
     ///
     /// [`get_area`]: https://docs.godotengine.org/en/stable/classes/class_aabb.html#class-aabb-method-get-area
     #[inline]
-    pub fn volume(self) -> f32 {
+    pub fn volume(self) -> real {
         self.size.x * self.size.y * self.size.z
     }
 
@@ -130,7 +150,7 @@ impl Aabb {
     /// let axis = index.to_unit_vector();
     /// ```
     #[inline]
-    pub fn longest_axis(self) -> (Axis, f32) {
+    pub fn longest_axis(self) -> (Axis, real) {
         let Vector3 { x, y, z } = self.size;
 
         (self.size.max_axis(), x.max(y).max(z))
@@ -148,7 +168,7 @@ impl Aabb {
     /// let axis = index.to_unit_vector();
     /// ```
     #[inline]
-    pub fn shortest_axis(self) -> (Axis, f32) {
+    pub fn shortest_axis(self) -> (Axis, real) {
         let Vector3 { x, y, z } = self.size;
 
         (self.size.min_axis(), x.min(y).min(z))
@@ -181,7 +201,7 @@ impl Aabb {
     /// It is possible to specify a negative amount to shrink the AABB (note that this can invert the AABB).
     #[inline]
     #[must_use]
-    pub fn grow(self, by: f32) -> Self {
+    pub fn grow(self, by: real) -> Self {
         let position = self.position - Vector3::new(by, by, by);
         let size = self.size + Vector3::new(by, by, by) * 2.0;
 
@@ -225,8 +245,8 @@ impl Aabb {
     /// Returns true if the bounding box intersects the line segment between `from` and `to`.
     #[inline]
     pub fn intersects_segment(self, from: Vector3, to: Vector3) -> bool {
-        let mut min: f32 = 0.0;
-        let mut max: f32 = 1.0;
+        let mut min: real = 0.0;
+        let mut max: real = 1.0;
 
         for i in 0..3 {
             let from = from.as_ref()[i];
@@ -496,4 +516,23 @@ mod tests {
         assert_ne!(a.merge(c), expected);
         assert_eq!(a.merge(c.abs()), expected);
     }
+
+    #[test]
+    fn test_from_points() {
+        assert_eq!(Aabb::from_points(&[]), None);
+
+        let point = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(
+            Aabb::from_points(&[point]),
+            Some(Aabb::new(point, Vector3::ZERO))
+        );
+
+        let points = [
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(-1.0, 5.0, 0.0),
+            Vector3::new(4.0, -2.0, 1.0),
+        ];
+        let expected = Aabb::new(Vector3::new(-1.0, -2.0, 0.0), Vector3::new(5.0, 7.0, 3.0));
+        assert_eq!(Aabb::from_points(&points), Some(expected));
+    }
 }
@@ -1,6 +1,7 @@
-use crate::core_types::{IsEqualApprox, Quat, Vector3};
+use crate::core_types::glam_real::{Mat3, Mat3A};
+use crate::core_types::{real, real_consts, Axis, IsEqualApprox, Quat, Vector3, CMP_EPSILON};
 use crate::globalscope::lerp;
-use glam::Mat3;
+use glam::EulerRot;
 use std::ops::Mul;
 
 /// A 3x3 matrix, typically used as an orthogonal basis for [`Transform`][crate::core_types::Transform].
@@ -8,6 +9,9 @@ use std::ops::Mul;
 /// The basis vectors are the column vectors of the matrix, while the [`elements`][Self::elements]
 /// field represents the row vectors.
 ///
+/// Like in the engine, the coordinate system is right-handed, with +Y up and -Z forward. Positive
+/// rotation angles are counter-clockwise when looking along the rotation axis towards the origin.
+///
 /// See also [Basis](https://docs.godotengine.org/en/stable/classes/class_basis.html) in the Godot API doc.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -28,6 +32,53 @@ impl Default for Basis {
     }
 }
 
+/// Order in which Euler angles are applied, for [`Basis::from_euler_with_order`] and related
+/// methods.
+///
+/// The rotation matrix is the product of the per-axis rotations in the named order, so the last
+/// axis is applied first. For example, `YXZ` rotates around **Z** first, then **X**, and **Y** last.
+/// The names match Godot 4's `EulerOrder`.
+#[allow(clippy::exhaustive_enums)] // explicitly exhaustive since there are only 6 orders of 3 axes
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EulerOrder {
+    XYZ,
+    XZY,
+    /// The only order supported by Godot 3, used by [`Basis::from_euler`] and the `rotation`
+    /// property of `Spatial`.
+    #[default]
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+}
+
+impl EulerOrder {
+    /// Returns the axes in the order of the name.
+    #[inline]
+    pub fn axes(self) -> [Axis; 3] {
+        match self {
+            EulerOrder::XYZ => [Axis::X, Axis::Y, Axis::Z],
+            EulerOrder::XZY => [Axis::X, Axis::Z, Axis::Y],
+            EulerOrder::YXZ => [Axis::Y, Axis::X, Axis::Z],
+            EulerOrder::YZX => [Axis::Y, Axis::Z, Axis::X],
+            EulerOrder::ZXY => [Axis::Z, Axis::X, Axis::Y],
+            EulerOrder::ZYX => [Axis::Z, Axis::Y, Axis::X],
+        }
+    }
+
+    #[inline]
+    pub(super) fn glam(self) -> EulerRot {
+        match self {
+            EulerOrder::XYZ => EulerRot::XYZ,
+            EulerOrder::XZY => EulerRot::XZY,
+            EulerOrder::YXZ => EulerRot::YXZ,
+            EulerOrder::YZX => EulerRot::YZX,
+            EulerOrder::ZXY => EulerRot::ZXY,
+            EulerOrder::ZYX => EulerRot::ZYX,
+        }
+    }
+}
+
 impl Basis {
     /// The identity basis. Basis vectors are unit vectors along each axis X, Y and Z.
     ///
@@ -146,6 +197,48 @@ impl Basis {
         *self = ymat * xmat * zmat;
     }
 
+    /// Creates a rotation matrix from Euler angles, applied in the given order.
+    ///
+    /// The angle vector has XYZ components, regardless of the order. With [`EulerOrder::YXZ`], this
+    /// is the same as [`from_euler`][Self::from_euler].
+    #[inline]
+    pub fn from_euler_with_order(euler_angles: Vector3, order: EulerOrder) -> Self {
+        if order == EulerOrder::YXZ {
+            return Self::from_euler(euler_angles);
+        }
+
+        let rotation = |axis: Axis| {
+            Self::from_axis_angle(axis.to_unit_vector(), euler_angles.as_ref()[axis as usize])
+        };
+        let [first, second, third] = order.axes();
+        rotation(first) * rotation(second) * rotation(third)
+    }
+
+    /// Creates a rotation matrix with the -Z axis pointing in `direction`, and the Y axis as close
+    /// to `up` as possible.
+    ///
+    /// Returns `None` if `direction` is zero or parallel to `up`.
+    #[inline]
+    pub fn looking_at(direction: Vector3, up: Vector3) -> Option<Self> {
+        let v_z = -direction.normalized();
+        let v_x = up.cross(v_z);
+        if !v_x.length_squared().is_finite() || v_x.length_squared() < CMP_EPSILON as real {
+            return None;
+        }
+
+        let v_x = v_x.normalized();
+        let v_y = v_z.cross(v_x);
+        Some(Self::from_basis_vectors(v_x, v_y, v_z))
+    }
+
+    /// Creates a matrix which scales by `scale`, then rotates by `quat`.
+    ///
+    /// This is the inverse of extracting [`to_quat`][Self::to_quat] and [`scale`][Self::scale].
+    #[inline]
+    pub fn from_quat_scale(quat: Quat, scale: Vector3) -> Self {
+        Self::from_quat(quat) * Self::from_diagonal(scale)
+    }
+
     /// Constructs a pure rotation basis matrix from the given quaternion.
     #[inline]
     pub fn from_quat(quat: Quat) -> Self {
@@ -166,7 +259,7 @@ impl Basis {
     ///
     /// If `axis` is not normalized.
     #[inline]
-    pub fn from_axis_angle(axis: Vector3, phi: f32) -> Self {
+    pub fn from_axis_angle(axis: Vector3, phi: real) -> Self {
         assert!(
             axis.length().is_equal_approx(1.0),
             "The axis Vector3 must be normalized."
@@ -218,10 +311,10 @@ impl Basis {
             y.x * z.y - y.y * z.x,
         ];
 
-        let det: f32 = x.x * co[0] + x.y * co[1] + x.z * co[2];
+        let det: real = x.x * co[0] + x.y * co[1] + x.z * co[2];
         assert!(!det.is_equal_approx(0.0), "Determinant was zero");
 
-        let s: f32 = 1.0 / det;
+        let s: real = 1.0 / det;
 
         self.set_a(Vector3::new(co[0] * s, co[1] * s, co[2] * s));
         self.set_b(Vector3::new(
@@ -256,7 +349,7 @@ impl Basis {
 
     /// Returns linear interpolation on a sphere between two basis by weight amount (on the range of 0.0 to 1.0).
     #[inline]
-    pub fn slerp(&self, other: &Basis, weight: f32) -> Self {
+    pub fn slerp(&self, other: &Basis, weight: real) -> Self {
         let from = self.to_quat();
         let to = other.to_quat();
         let mut result = Basis::from_quat(from.slerp(to, weight));
@@ -273,7 +366,7 @@ impl Basis {
 
     /// Returns linear interpolation between two basis by weight amount (on the range of 0.0 to 1.0).
     #[inline]
-    pub fn lerp(&self, other: &Basis, weight: f32) -> Self {
+    pub fn lerp(&self, other: &Basis, weight: real) -> Self {
         // this is how godot is doing it at https://github.com/godotengine/godot/blob/master/core/math/basis.cpp#L964
         // but Godot engine output for me differs than godot-rust
         let a = self.elements[0].linear_interpolate(other.elements[0], weight);
@@ -300,7 +393,7 @@ impl Basis {
 
     /// Returns the determinant of the matrix.
     #[inline]
-    pub fn determinant(&self) -> f32 {
+    pub fn determinant(&self) -> real {
         let [x, y, z] = &self.elements;
         x.x * (y.y * z.z - z.y * y.z) // x
             - y.x * (x.y * z.z - z.y * x.z) // y
@@ -379,7 +472,7 @@ impl Basis {
     /// The main use of `Basis` is as a `Transform.basis`, which is used as the transformation matrix
     /// of the 3D object. `rotated()` here refers to rotation of the object (which is `R * self`), not the matrix itself.
     #[inline]
-    pub fn rotated(&self, axis: Vector3, phi: f32) -> Self {
+    pub fn rotated(&self, axis: Vector3, phi: real) -> Self {
         let mut copy = *self;
         copy.rotate(axis, phi);
         copy
@@ -389,7 +482,7 @@ impl Basis {
     ///
     /// If object rotation is needed, see [`Basis::rotated()`]
     #[inline]
-    fn rotate(&mut self, axis: Vector3, phi: f32) {
+    fn rotate(&mut self, axis: Vector3, phi: real) {
         let rot = Self::from_axis_angle(axis, phi);
         *self = rot * *self;
     }
@@ -464,7 +557,7 @@ impl Basis {
 
         // Allow getting a quaternion from an unnormalized transform
         let trace = matrix.elements[0].x + matrix.elements[1].y + matrix.elements[2].z;
-        let mut temp = [0_f32; 4];
+        let mut temp: [real; 4] = [0.0; 4];
 
         if trace > 0.0 {
             let mut s = (trace + 1.0).sqrt();
@@ -490,7 +583,7 @@ impl Basis {
             let j = (i + 1) % 3;
             let k = (i + 2) % 3;
 
-            let elements_arr: [[f32; 3]; 3] = [
+            let elements_arr: [[real; 3]; 3] = [
                 *matrix.elements[0].as_ref(),
                 *matrix.elements[1].as_ref(),
                 *matrix.elements[2].as_ref(),
@@ -521,8 +614,9 @@ impl Basis {
         let mut euler = Vector3::ZERO;
 
         let m12 = self.elements[1].z;
-        if m12 < 1.0 {
-            if m12 > -1.0 {
+        let threshold = 1.0 - CMP_EPSILON as real;
+        if m12 < threshold {
+            if m12 > -threshold {
                 // is this a pure X rotation?
                 if self.elements[1].x.is_equal_approx(0.0)
                     && self.elements[0].y.is_equal_approx(0.0)
@@ -541,20 +635,127 @@ impl Basis {
                 }
             } else {
                 // m12 == -1
-                euler.x = core::f32::consts::PI * 0.5;
+                euler.x = real_consts::PI * 0.5;
                 euler.y = -(-self.elements[0].y).atan2(self.elements[0].x);
                 euler.z = 0.0;
             }
         } else {
             // m12 == 1
-            euler.x = -core::f32::consts::PI * 0.5;
-            euler.y = -(-self.elements[0].y).atan2(self.elements[0].x);
+            euler.x = -real_consts::PI * 0.5;
+            euler.y = -self.elements[0].y.atan2(self.elements[0].x);
             euler.z = 0.0;
         }
 
         euler
     }
 
+    /// Returns the `Basis`'s rotation in the form of Euler angles, applied in the given order.
+    ///
+    /// The returned `Vector3` contains the rotation angles in the format (**X** angle, **Y** angle,
+    /// **Z** angle), regardless of the order. With [`EulerOrder::YXZ`], this is the same as
+    /// [`to_euler`][Self::to_euler].
+    ///
+    /// When the middle rotation is ±90 degrees (gimbal lock), the first angle absorbs the
+    /// rotation, and the last one is zero.
+    #[inline]
+    pub fn to_euler_with_order(&self, order: EulerOrder) -> Vector3 {
+        let m = |row: usize, col: usize| self.elements[row].as_ref()[col];
+        let threshold = 1.0 - CMP_EPSILON as real;
+        let half_pi = real_consts::FRAC_PI_2;
+
+        // Each order extracts the sine of the middle angle from one element of the matrix. The
+        // comments show the matrix, with c/s for the cosine/sine of each axis's angle.
+        let (x, y, z) = match order {
+            EulerOrder::YXZ => return self.to_euler(),
+            EulerOrder::XYZ => {
+                //  cy*cz            -cy*sz            sy
+                //  cz*sx*sy+cx*sz    cx*cz-sx*sy*sz  -cy*sx
+                // -cx*cz*sy+sx*sz    cz*sx+cx*sy*sz   cx*cy
+                let sy = m(0, 2);
+                if sy >= threshold {
+                    (m(2, 1).atan2(m(1, 1)), half_pi, 0.0)
+                } else if sy <= -threshold {
+                    (m(2, 1).atan2(m(1, 1)), -half_pi, 0.0)
+                } else {
+                    (
+                        (-m(1, 2)).atan2(m(2, 2)),
+                        sy.asin(),
+                        (-m(0, 1)).atan2(m(0, 0)),
+                    )
+                }
+            }
+            EulerOrder::XZY => {
+                //  cy*cz             -sz      cz*sy
+                //  sx*sy+cx*cy*sz     cx*cz   cx*sz*sy-cy*sx
+                //  cy*sx*sz-cx*sy     cz*sx   cx*cy+sx*sz*sy
+                let sz = -m(0, 1);
+                if sz >= threshold {
+                    ((-m(1, 2)).atan2(m(2, 2)), 0.0, half_pi)
+                } else if sz <= -threshold {
+                    ((-m(1, 2)).atan2(m(2, 2)), 0.0, -half_pi)
+                } else {
+                    (m(2, 1).atan2(m(1, 1)), m(0, 2).atan2(m(0, 0)), sz.asin())
+                }
+            }
+            EulerOrder::YZX => {
+                //  cy*cz    sy*sx-cy*cx*sz    cx*sy+cy*sz*sx
+                //  sz       cz*cx            -cz*sx
+                // -cz*sy    cy*sx+cx*sy*sz    cy*cx-sy*sz*sx
+                let sz = m(1, 0);
+                if sz >= threshold {
+                    (m(2, 1).atan2(m(2, 2)), 0.0, half_pi)
+                } else if sz <= -threshold {
+                    (m(2, 1).atan2(m(2, 2)), 0.0, -half_pi)
+                } else {
+                    (
+                        (-m(1, 2)).atan2(m(1, 1)),
+                        (-m(2, 0)).atan2(m(0, 0)),
+                        sz.asin(),
+                    )
+                }
+            }
+            EulerOrder::ZXY => {
+                //  cz*cy-sz*sx*sy   -cx*sz    cz*sy+cy*sz*sx
+                //  cy*sz+cz*sx*sy    cz*cx    sz*sy-cz*cy*sx
+                // -cx*sy             sx       cx*cy
+                let sx = m(2, 1);
+                if sx >= threshold {
+                    (half_pi, m(0, 2).atan2(m(0, 0)), 0.0)
+                } else if sx <= -threshold {
+                    (-half_pi, m(0, 2).atan2(m(0, 0)), 0.0)
+                } else {
+                    (
+                        sx.asin(),
+                        (-m(2, 0)).atan2(m(2, 2)),
+                        (-m(0, 1)).atan2(m(1, 1)),
+                    )
+                }
+            }
+            EulerOrder::ZYX => {
+                //  cz*cy    cz*sy*sx-cx*sz    sz*sx+cz*cx*sy
+                //  cy*sz    cz*cx+sz*sy*sx    cx*sz*sy-cz*sx
+                // -sy       cy*sx             cy*cx
+                let sy = -m(2, 0);
+                if sy >= threshold {
+                    (0.0, half_pi, (-m(0, 1)).atan2(m(1, 1)))
+                } else if sy <= -threshold {
+                    (0.0, -half_pi, (-m(0, 1)).atan2(m(1, 1)))
+                } else {
+                    (m(2, 1).atan2(m(2, 2)), sy.asin(), m(1, 0).atan2(m(0, 0)))
+                }
+            }
+        };
+
+        Vector3::new(x, y, z)
+    }
+
+    /// Returns the rotation of the matrix as a normalized axis and an angle in radians, from 0 to
+    /// π. See [`Quat::to_axis_angle`].
+    #[inline]
+    pub fn to_axis_angle(&self) -> (Vector3, real) {
+        self.to_quat().to_axis_angle()
+    }
+
     /// Returns a vector transformed (multiplied) by the matrix.
     #[inline]
     pub fn xform(&self, v: Vector3) -> Vector3 {
@@ -573,21 +774,40 @@ impl Basis {
         Vector3::new(self.a().dot(v), self.b().dot(v), self.c().dot(v))
     }
 
+    /// Transforms (multiplies) each vector in `vectors` by the matrix, in place.
+    ///
+    /// This gives the same results as calling [`xform`][Self::xform] on each vector, up to
+    /// rounding, but uses SIMD instructions where available. `PoolArray` write guards dereference
+    /// to slices, so they can be passed directly: `basis.xform_slice(&mut array.write())`.
+    #[inline]
+    pub fn xform_slice(&self, vectors: &mut [Vector3]) {
+        let matrix = self.glam_a();
+        for v in vectors {
+            *v = Vector3::gd(matrix * v.glam());
+        }
+    }
+
+    /// Returns the matrix as a `glam` matrix with aligned columns.
+    #[inline]
+    pub(super) fn glam_a(&self) -> Mat3A {
+        Mat3A::from_cols(self.a().glam(), self.b().glam(), self.c().glam())
+    }
+
     /// Transposed dot product with the **X basis vector** of the matrix.
     #[inline]
-    pub(crate) fn tdotx(&self, v: Vector3) -> f32 {
+    pub(crate) fn tdotx(&self, v: Vector3) -> real {
         self.a().dot(v)
     }
 
     /// Transposed dot product with the **Y basis vector** of the matrix.
     #[inline]
-    pub(crate) fn tdoty(&self, v: Vector3) -> f32 {
+    pub(crate) fn tdoty(&self, v: Vector3) -> real {
         self.b().dot(v)
     }
 
     /// Transposed dot product with the **Z basis vector** of the matrix.
     #[inline]
-    pub(crate) fn tdotz(&self, v: Vector3) -> f32 {
+    pub(crate) fn tdotz(&self, v: Vector3) -> real {
         self.c().dot(v)
     }
 
@@ -697,9 +917,9 @@ mod tests {
 
         let vector = Vector3::new(4.0, 5.0, 6.0);
 
-        assert!((basis.tdotx(vector) - 32.0).abs() < std::f32::EPSILON);
-        assert!((basis.tdoty(vector) - 47.0).abs() < std::f32::EPSILON);
-        assert!((basis.tdotz(vector) - 62.0).abs() < std::f32::EPSILON);
+        assert!((basis.tdotx(vector) - 32.0).abs() < real::EPSILON);
+        assert!((basis.tdoty(vector) - 47.0).abs() < real::EPSILON);
+        assert!((basis.tdotz(vector) - 62.0).abs() < real::EPSILON);
     }
 
     #[test]
@@ -757,6 +977,23 @@ mod tests {
         assert!(Vector3::new(0.57079, 0.310283, 0.760213).is_equal_approx(bn.to_euler()));
     }
 
+    #[test]
+    fn euler_gimbal_lock() {
+        let half_pi = real_consts::FRAC_PI_2;
+
+        // With X at +-90 degrees, Y and Z rotate around the same axis. Like the engine, the
+        // combined angle is returned as Y.
+        let basis = Basis::from_euler(Vector3::new(half_pi, 0.3, 0.4));
+        assert!(Vector3::new(half_pi, -0.1, 0.0).is_equal_approx(basis.to_euler()));
+
+        let basis = Basis::from_euler(Vector3::new(-half_pi, 0.3, 0.4));
+        assert!(Vector3::new(-half_pi, 0.7, 0.0).is_equal_approx(basis.to_euler()));
+
+        // Within `CMP_EPSILON` of gimbal lock, X snaps to +-90 degrees.
+        let basis = Basis::from_euler(Vector3::new(half_pi - 0.002, 0.3, 0.4));
+        assert!(Vector3::new(half_pi, -0.1, 0.0).is_equal_approx(basis.to_euler()));
+    }
+
     #[test]
     fn orthonormalized() {
         let (b, _bn) = test_inputs();
@@ -808,6 +1045,18 @@ mod tests {
         assert!(Vector3::new(1.0, 1.0, 1.0).is_equal_approx(b.scale()));
     }
 
+    #[test]
+    fn xform_slice() {
+        let (b, _bn) = test_inputs();
+        let mut vectors = [Vector3::ZERO, Vector3::new(1.0, -2.0, 3.0), Vector3::ONE];
+        let expected = vectors.map(|v| b.xform(v));
+
+        b.xform_slice(&mut vectors);
+        for (v, e) in vectors.iter().zip(&expected) {
+            assert!(v.is_equal_approx(*e));
+        }
+    }
+
     #[test]
     fn approx_eq() {
         let (b, _bn) = test_inputs();
@@ -852,4 +1101,99 @@ mod tests {
         );
         assert!(expected.is_equal_approx(&b.inverse()));
     }
+
+    const ORDERS: [EulerOrder; 6] = [
+        EulerOrder::XYZ,
+        EulerOrder::XZY,
+        EulerOrder::YXZ,
+        EulerOrder::YZX,
+        EulerOrder::ZXY,
+        EulerOrder::ZYX,
+    ];
+
+    #[test]
+    fn euler_with_order() {
+        let half_pi = real_consts::FRAC_PI_2;
+
+        // Rotating RIGHT by 90 degrees around X and Y: applying Y first turns it to FORWARD, which
+        // X then turns to UP. Applying X first doesn't change it.
+        let angles = Vector3::new(half_pi, half_pi, 0.0);
+        let xyz = Basis::from_euler_with_order(angles, EulerOrder::XYZ);
+        let zyx = Basis::from_euler_with_order(angles, EulerOrder::ZYX);
+        assert!(xyz.xform(Vector3::RIGHT).is_equal_approx(Vector3::UP));
+        assert!(zyx.xform(Vector3::RIGHT).is_equal_approx(Vector3::FORWARD));
+
+        let (_b, bn) = test_inputs();
+        assert_eq!(bn.to_euler_with_order(EulerOrder::YXZ), bn.to_euler());
+        let euler = Vector3::new(0.3, -0.8, 1.2);
+        assert_eq!(
+            Basis::from_euler_with_order(euler, EulerOrder::YXZ),
+            Basis::from_euler(euler)
+        );
+
+        for order in ORDERS {
+            let [first, second, third] = order.axes();
+            let expected = Basis::from_axis_angle(first.to_unit_vector(), 0.4)
+                * Basis::from_axis_angle(second.to_unit_vector(), 0.5)
+                * Basis::from_axis_angle(third.to_unit_vector(), 0.6);
+
+            let mut euler = [0.0; 3];
+            euler[first as usize] = 0.4;
+            euler[second as usize] = 0.5;
+            euler[third as usize] = 0.6;
+            let euler = Vector3::new(euler[0], euler[1], euler[2]);
+
+            let basis = Basis::from_euler_with_order(euler, order);
+            assert!(basis.is_equal_approx(&expected), "{order:?}");
+            assert!(
+                basis.to_euler_with_order(order).is_equal_approx(euler),
+                "{order:?}"
+            );
+
+            // In gimbal lock, the angles differ but must describe the same rotation.
+            for middle in [half_pi, -half_pi] {
+                let mut locked = [0.3; 3];
+                locked[second as usize] = middle;
+                let locked = Basis::from_euler_with_order(
+                    Vector3::new(locked[0], locked[1], locked[2]),
+                    order,
+                );
+                let recovered =
+                    Basis::from_euler_with_order(locked.to_euler_with_order(order), order);
+                assert!(recovered.is_equal_approx(&locked), "{order:?} {middle}");
+            }
+        }
+    }
+
+    #[test]
+    fn looking_at() {
+        let basis = Basis::looking_at(Vector3::new(2.0, 0.0, 0.0), Vector3::UP).unwrap();
+        assert!(basis
+            .xform(Vector3::FORWARD)
+            .is_equal_approx(Vector3::RIGHT));
+        assert!(basis.xform(Vector3::UP).is_equal_approx(Vector3::UP));
+        assert!(basis.determinant().is_equal_approx(1.0));
+
+        let tilted = Basis::looking_at(Vector3::FORWARD, Vector3::new(1.0, 1.0, 0.0)).unwrap();
+        assert!(tilted
+            .b()
+            .is_equal_approx(Vector3::new(1.0, 1.0, 0.0).normalized()));
+
+        assert_eq!(Basis::looking_at(Vector3::DOWN, Vector3::UP), None);
+        assert_eq!(Basis::looking_at(Vector3::ZERO, Vector3::UP), None);
+    }
+
+    #[test]
+    fn quat_scale() {
+        let quat = Quat::from_axis_angle(Vector3::new(1.0, 2.0, -1.0).normalized(), 0.7);
+        let scale = Vector3::new(2.0, 0.5, 3.0);
+        let basis = Basis::from_quat_scale(quat, scale);
+
+        assert!(basis.to_quat().is_equal_approx(quat));
+        assert!(basis.scale().is_equal_approx(scale));
+
+        let (axis, angle) = basis.to_axis_angle();
+        assert!(axis.is_equal_approx(Vector3::new(1.0, 2.0, -1.0).normalized()));
+        assert!(angle.is_equal_approx(0.7));
+    }
 }
@@ -0,0 +1,1112 @@
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use crate::core_types::{real, IsEqualApprox, Vector2, Vector3, CMP_EPSILON};
+
+/// Default distance between baked points of a [`Curve2D`], in pixels.
+pub const CURVE2D_DEFAULT_BAKE_INTERVAL: real = 5.0;
+
+/// Default distance between baked points of a [`Curve3D`], in meters.
+pub const CURVE3D_DEFAULT_BAKE_INTERVAL: real = 0.2;
+
+/// Control point of a [`Curve2D`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurvePoint2D {
+    /// Position of the point.
+    pub position: Vector2,
+    /// Handle controlling the curve arriving at this point, relative to `position`.
+    pub point_in: Vector2,
+    /// Handle controlling the curve leaving this point, relative to `position`.
+    pub point_out: Vector2,
+}
+
+impl CurvePoint2D {
+    /// Creates a control point from its position and handles.
+    #[inline]
+    pub const fn new(position: Vector2, point_in: Vector2, point_out: Vector2) -> Self {
+        Self {
+            position,
+            point_in,
+            point_out,
+        }
+    }
+}
+
+/// Control point of a [`Curve3D`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurvePoint3D {
+    /// Position of the point.
+    pub position: Vector3,
+    /// Handle controlling the curve arriving at this point, relative to `position`.
+    pub point_in: Vector3,
+    /// Handle controlling the curve leaving this point, relative to `position`.
+    pub point_out: Vector3,
+    /// Rotation around the curve at this point, in radians.
+    pub tilt: real,
+}
+
+impl CurvePoint3D {
+    /// Creates a control point from its position and handles, without tilt.
+    #[inline]
+    pub const fn new(position: Vector3, point_in: Vector3, point_out: Vector3) -> Self {
+        Self {
+            position,
+            point_in,
+            point_out,
+            tilt: 0.0,
+        }
+    }
+}
+
+/// Error returned when converting malformed curve data, as stored in the `_data` property of
+/// curve resources.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CurveDataError {
+    /// The number of vectors is not a multiple of three.
+    PointCount { len: usize },
+    /// The number of tilts doesn't match the number of points.
+    TiltCount { expected: usize, actual: usize },
+}
+
+impl fmt::Display for CurveDataError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurveDataError::PointCount { len } => {
+                write!(f, "expected 3 vectors per point, found {len} vectors")
+            }
+            CurveDataError::TiltCount { expected, actual } => {
+                write!(f, "expected {expected} tilts, found {actual}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CurveDataError {}
+
+/// Bézier path in 2D space, equivalent to the engine's `Curve2D` resource.
+///
+/// Points along the curve can be computed from the control points with [`interpolate`][Self::interpolate],
+/// which is not uniform in distance. For movement at constant speed, [`bake`][Self::bake] the curve
+/// into evenly spaced points once, and query the resulting [`BakedCurve2D`] instead.
+///
+/// All algorithms match those of the engine, so results are the same as when querying the
+/// resource through the API.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Curve2D {
+    /// The control points.
+    pub points: Vec<CurvePoint2D>,
+    /// Distance between two adjacent baked points.
+    pub bake_interval: real,
+}
+
+impl Default for Curve2D {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Curve2D {
+    /// Creates an empty curve with the default bake interval.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            points: Vec::new(),
+            bake_interval: CURVE2D_DEFAULT_BAKE_INTERVAL,
+        }
+    }
+
+    /// Creates a curve from the `points` array of its serialized data, which contains the
+    /// in handle, out handle and position of each point, in that order.
+    ///
+    /// A `PoolVector2Array` can be converted with `Curve2D::from_data(&array.read())`.
+    ///
+    /// # Errors
+    ///
+    /// If the length of `data` is not a multiple of three.
+    #[inline]
+    pub fn from_data(data: &[Vector2]) -> Result<Self, CurveDataError> {
+        if data.len() % 3 != 0 {
+            return Err(CurveDataError::PointCount { len: data.len() });
+        }
+
+        let points = data
+            .chunks_exact(3)
+            .map(|point| CurvePoint2D::new(point[2], point[0], point[1]))
+            .collect();
+
+        Ok(Self {
+            points,
+            ..Self::new()
+        })
+    }
+
+    /// Returns the `points` array of the curve's serialized data. This is the inverse of
+    /// [`from_data`][Self::from_data].
+    #[inline]
+    pub fn to_data(&self) -> Vec<Vector2> {
+        self.points
+            .iter()
+            .flat_map(|point| [point.point_in, point.point_out, point.position])
+            .collect()
+    }
+
+    /// Returns the position between the control points `index` and `index + 1`, at `t` from 0.0
+    /// to 1.0 along the segment.
+    ///
+    /// Indices out of bounds return the first or last point. Returns `None` if the curve is empty.
+    #[inline]
+    pub fn interpolate(&self, index: isize, t: real) -> Option<Vector2> {
+        let points = &self.points;
+        let segment = segment(points.len(), index)?;
+        Some(match segment {
+            Ok(i) => bezier(&points[i], &points[i + 1], t),
+            Err(i) => points[i].position,
+        })
+    }
+
+    /// Returns the position at `findex`, where the integer part is the index of the segment and the
+    /// fractional part is the offset along it. See [`interpolate`][Self::interpolate].
+    #[inline]
+    pub fn interpolatef(&self, findex: real) -> Option<Vector2> {
+        let findex = findex.clamp(0.0, self.points.len() as real);
+        self.interpolate(findex as isize, findex % 1.0)
+    }
+
+    /// Returns points along the curve, denser where it bends more.
+    ///
+    /// Each segment is subdivided up to `max_stages` times, adding points where the angle between
+    /// consecutive directions exceeds `tolerance_degrees`. The engine uses 5 stages and 4 degrees
+    /// by default.
+    #[inline]
+    pub fn tessellate(&self, max_stages: u32, tolerance_degrees: real) -> Vec<Vector2> {
+        tessellate(&self.points, max_stages, tolerance_degrees)
+    }
+
+    /// Computes points along the curve separated by [`bake_interval`][Self::bake_interval].
+    ///
+    /// Returns `None` if the curve is empty.
+    #[inline]
+    pub fn bake(&self) -> Option<BakedCurve2D> {
+        let (samples, length) = bake(&self.points, self.bake_interval)?;
+        Some(BakedCurve2D {
+            points: samples.into_iter().map(|sample| sample.position).collect(),
+            interval: self.bake_interval,
+            length,
+        })
+    }
+}
+
+/// Points sampled at regular distances along a [`Curve2D`].
+///
+/// Created with [`Curve2D::bake`]. Queries are relative to the distance from the start of the
+/// curve, like the `*_baked` methods of the engine's `Curve2D`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BakedCurve2D {
+    points: Vec<Vector2>,
+    interval: real,
+    length: real,
+}
+
+impl BakedCurve2D {
+    /// Returns the baked points. There is always at least one.
+    #[inline]
+    pub fn points(&self) -> &[Vector2] {
+        &self.points
+    }
+
+    /// Returns the distance between two adjacent points.
+    #[inline]
+    pub fn interval(&self) -> real {
+        self.interval
+    }
+
+    /// Returns the approximate length of the curve.
+    #[inline]
+    pub fn length(&self) -> real {
+        self.length
+    }
+
+    /// Returns the position at `offset` along the curve, equivalent to the engine's
+    /// `interpolate_baked`.
+    ///
+    /// If `cubic` is `true`, positions between baked points are interpolated cubically, which is
+    /// smoother but slower.
+    #[inline]
+    pub fn interpolate(&self, offset: real, cubic: bool) -> Vector2 {
+        let r = &self.points;
+        match baked_index(r.len(), self.interval, self.length, offset) {
+            Err(i) => r[i],
+            Ok((i, frac)) if cubic => {
+                let pre = if i > 0 { r[i - 1] } else { r[i] };
+                let post = r.get(i + 2).copied().unwrap_or(r[i + 1]);
+                r[i].cubic_interpolate(r[i + 1], pre, post, frac)
+            }
+            Ok((i, frac)) => r[i].linear_interpolate(r[i + 1], frac),
+        }
+    }
+
+    /// Returns the offset of the point on the curve closest to `to_point`.
+    #[inline]
+    pub fn closest_offset(&self, to_point: Vector2) -> real {
+        closest(&self.points, self.interval, to_point).0
+    }
+
+    /// Returns the point on the curve closest to `to_point`.
+    #[inline]
+    pub fn closest_point(&self, to_point: Vector2) -> Vector2 {
+        closest(&self.points, self.interval, to_point).1
+    }
+}
+
+/// Bézier path in 3D space, equivalent to the engine's `Curve3D` resource.
+///
+/// In addition to [`Curve2D`]'s features, each point has a tilt, and baking can compute up vectors
+/// which rotate minimally along the curve, like those used by `PathFollow` nodes.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Curve3D {
+    /// The control points.
+    pub points: Vec<CurvePoint3D>,
+    /// Distance between two adjacent baked points.
+    pub bake_interval: real,
+    /// Whether baking computes up vectors.
+    pub up_vector_enabled: bool,
+}
+
+impl Default for Curve3D {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Curve3D {
+    /// Creates an empty curve with the default bake interval and up vectors enabled.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            points: Vec::new(),
+            bake_interval: CURVE3D_DEFAULT_BAKE_INTERVAL,
+            up_vector_enabled: true,
+        }
+    }
+
+    /// Creates a curve from the `points` and `tilts` arrays of its serialized data. `points`
+    /// contains the in handle, out handle and position of each point, in that order.
+    ///
+    /// # Errors
+    ///
+    /// If the length of `points` is not a multiple of three, or if there isn't one tilt per point.
+    #[inline]
+    pub fn from_data(points: &[Vector3], tilts: &[real]) -> Result<Self, CurveDataError> {
+        if points.len() % 3 != 0 {
+            return Err(CurveDataError::PointCount { len: points.len() });
+        }
+        if tilts.len() != points.len() / 3 {
+            return Err(CurveDataError::TiltCount {
+                expected: points.len() / 3,
+                actual: tilts.len(),
+            });
+        }
+
+        let points = points
+            .chunks_exact(3)
+            .zip(tilts)
+            .map(|(point, &tilt)| CurvePoint3D {
+                tilt,
+                ..CurvePoint3D::new(point[2], point[0], point[1])
+            })
+            .collect();
+
+        Ok(Self {
+            points,
+            ..Self::new()
+        })
+    }
+
+    /// Returns the `points` and `tilts` arrays of the curve's serialized data. This is the inverse
+    /// of [`from_data`][Self::from_data].
+    #[inline]
+    pub fn to_data(&self) -> (Vec<Vector3>, Vec<real>) {
+        let points = self
+            .points
+            .iter()
+            .flat_map(|point| [point.point_in, point.point_out, point.position])
+            .collect();
+        let tilts = self.points.iter().map(|point| point.tilt).collect();
+        (points, tilts)
+    }
+
+    /// Returns the position between the control points `index` and `index + 1`, at `t` from 0.0
+    /// to 1.0 along the segment.
+    ///
+    /// Indices out of bounds return the first or last point. Returns `None` if the curve is empty.
+    #[inline]
+    pub fn interpolate(&self, index: isize, t: real) -> Option<Vector3> {
+        let points = &self.points;
+        let segment = segment(points.len(), index)?;
+        Some(match segment {
+            Ok(i) => bezier(&points[i], &points[i + 1], t),
+            Err(i) => points[i].position,
+        })
+    }
+
+    /// Returns the position at `findex`, where the integer part is the index of the segment and the
+    /// fractional part is the offset along it. See [`interpolate`][Self::interpolate].
+    #[inline]
+    pub fn interpolatef(&self, findex: real) -> Option<Vector3> {
+        let findex = findex.clamp(0.0, self.points.len() as real);
+        self.interpolate(findex as isize, findex % 1.0)
+    }
+
+    /// Returns points along the curve, denser where it bends more. See [`Curve2D::tessellate`].
+    #[inline]
+    pub fn tessellate(&self, max_stages: u32, tolerance_degrees: real) -> Vec<Vector3> {
+        tessellate(&self.points, max_stages, tolerance_degrees)
+    }
+
+    /// Computes points along the curve separated by [`bake_interval`][Self::bake_interval], with
+    /// their tilts and up vectors.
+    ///
+    /// Returns `None` if the curve is empty.
+    #[inline]
+    pub fn bake(&self) -> Option<BakedCurve3D> {
+        let (samples, length) = bake(&self.points, self.bake_interval)?;
+
+        let tilts = samples
+            .iter()
+            .map(|sample| match self.points.get(sample.segment + 1) {
+                Some(next) => {
+                    let tilt = self.points[sample.segment].tilt;
+                    tilt + (next.tilt - tilt) * sample.t
+                }
+                None => self.points[sample.segment].tilt,
+            })
+            .collect();
+
+        let points: Vec<Vector3> = samples.into_iter().map(|sample| sample.position).collect();
+
+        let up_vectors = if self.up_vector_enabled {
+            Some(up_vectors(&points))
+        } else {
+            None
+        };
+
+        Some(BakedCurve3D {
+            points,
+            tilts,
+            up_vectors,
+            interval: self.bake_interval,
+            length,
+        })
+    }
+}
+
+/// Points sampled at regular distances along a [`Curve3D`].
+///
+/// Created with [`Curve3D::bake`]. Queries are relative to the distance from the start of the
+/// curve, like the `*_baked` methods of the engine's `Curve3D`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BakedCurve3D {
+    points: Vec<Vector3>,
+    tilts: Vec<real>,
+    up_vectors: Option<Vec<Vector3>>,
+    interval: real,
+    length: real,
+}
+
+impl BakedCurve3D {
+    /// Returns the baked points. There is always at least one.
+    #[inline]
+    pub fn points(&self) -> &[Vector3] {
+        &self.points
+    }
+
+    /// Returns the tilt at each baked point.
+    #[inline]
+    pub fn tilts(&self) -> &[real] {
+        &self.tilts
+    }
+
+    /// Returns the up vector at each baked point, or `None` if the curve was baked without them.
+    #[inline]
+    pub fn up_vectors(&self) -> Option<&[Vector3]> {
+        self.up_vectors.as_deref()
+    }
+
+    /// Returns the distance between two adjacent points.
+    #[inline]
+    pub fn interval(&self) -> real {
+        self.interval
+    }
+
+    /// Returns the approximate length of the curve.
+    #[inline]
+    pub fn length(&self) -> real {
+        self.length
+    }
+
+    /// Returns the position at `offset` along the curve, equivalent to the engine's
+    /// `interpolate_baked`.
+    ///
+    /// If `cubic` is `true`, positions between baked points are interpolated cubically, which is
+    /// smoother but slower.
+    #[inline]
+    pub fn interpolate(&self, offset: real, cubic: bool) -> Vector3 {
+        let r = &self.points;
+        match baked_index(r.len(), self.interval, self.length, offset) {
+            Err(i) => r[i],
+            Ok((i, frac)) if cubic => {
+                let pre = if i > 0 { r[i - 1] } else { r[i] };
+                let post = r.get(i + 2).copied().unwrap_or(r[i + 1]);
+                r[i].cubic_interpolate(r[i + 1], pre, post, frac)
+            }
+            Ok((i, frac)) => r[i].linear_interpolate(r[i + 1], frac),
+        }
+    }
+
+    /// Returns the tilt at `offset` along the curve.
+    #[inline]
+    pub fn interpolate_tilt(&self, offset: real) -> real {
+        let r = &self.tilts;
+        match baked_index(r.len(), self.interval, self.length, offset) {
+            Err(i) => r[i],
+            Ok((i, frac)) => r[i] + (r[i + 1] - r[i]) * frac,
+        }
+    }
+
+    /// Returns the up vector at `offset` along the curve, optionally rotated by the tilt.
+    ///
+    /// Returns `None` if the curve was baked without up vectors.
+    #[inline]
+    pub fn interpolate_up_vector(&self, offset: real, apply_tilt: bool) -> Option<Vector3> {
+        let r = self.up_vectors.as_deref()?;
+        let (rp, rt) = (&self.points, &self.tilts);
+        let count = r.len();
+        if count == 1 {
+            return Some(r[0]);
+        }
+
+        let offset = offset.clamp(0.0, self.length);
+        let idx = ((offset / self.interval).floor() as usize).min(count - 1);
+        let frac = (offset % self.interval) / self.interval;
+
+        if idx == count - 1 {
+            return Some(if apply_tilt {
+                rotated_or_same(r[idx], normalized_or_zero(rp[idx] - rp[idx - 1]), rt[idx])
+            } else {
+                r[idx]
+            });
+        }
+
+        let forward = normalized_or_zero(rp[idx + 1] - rp[idx]);
+        let mut up = r[idx];
+        let mut up1 = r[idx + 1];
+
+        if apply_tilt {
+            up = rotated_or_same(up, forward, rt[idx]);
+            let next_forward = match rp.get(idx + 2) {
+                Some(&next) => normalized_or_zero(next - rp[idx + 1]),
+                None => forward,
+            };
+            up1 = rotated_or_same(up1, next_forward, rt[idx + 1]);
+        }
+
+        let axis = up.cross(up1);
+        let axis = if axis.length_squared() < (CMP_EPSILON * CMP_EPSILON) as real {
+            forward
+        } else {
+            axis.normalized()
+        };
+
+        Some(rotated_or_same(up, axis, up.angle_to(up1) * frac))
+    }
+
+    /// Returns the offset of the point on the curve closest to `to_point`.
+    #[inline]
+    pub fn closest_offset(&self, to_point: Vector3) -> real {
+        closest(&self.points, self.interval, to_point).0
+    }
+
+    /// Returns the point on the curve closest to `to_point`.
+    #[inline]
+    pub fn closest_point(&self, to_point: Vector3) -> Vector3 {
+        closest(&self.points, self.interval, to_point).1
+    }
+}
+
+/// Vector operations shared by the 2D and 3D curve algorithms.
+trait CurveVector: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<real, Output = Self> {
+    fn dot(self, other: Self) -> real;
+    fn length_squared(self) -> real;
+}
+
+impl CurveVector for Vector2 {
+    #[inline]
+    fn dot(self, other: Self) -> real {
+        Vector2::dot(self, other)
+    }
+
+    #[inline]
+    fn length_squared(self) -> real {
+        Vector2::length_squared(self)
+    }
+}
+
+impl CurveVector for Vector3 {
+    #[inline]
+    fn dot(self, other: Self) -> real {
+        Vector3::dot(self, other)
+    }
+
+    #[inline]
+    fn length_squared(self) -> real {
+        Vector3::length_squared(self)
+    }
+}
+
+/// Control points which delimit a cubic Bézier segment.
+trait ControlPoint {
+    type Vector: CurveVector;
+
+    fn handles(&self) -> (Self::Vector, Self::Vector, Self::Vector);
+
+    #[inline]
+    fn position(&self) -> Self::Vector {
+        self.handles().0
+    }
+}
+
+impl ControlPoint for CurvePoint2D {
+    type Vector = Vector2;
+
+    #[inline]
+    fn handles(&self) -> (Vector2, Vector2, Vector2) {
+        (self.position, self.point_in, self.point_out)
+    }
+}
+
+impl ControlPoint for CurvePoint3D {
+    type Vector = Vector3;
+
+    #[inline]
+    fn handles(&self) -> (Vector3, Vector3, Vector3) {
+        (self.position, self.point_in, self.point_out)
+    }
+}
+
+/// Like `normalized`, but returns zero for zero vectors, like the engine does.
+#[inline]
+fn normalized_or_zero<V: CurveVector>(v: V) -> V {
+    let length_squared = v.length_squared();
+    if length_squared == 0.0 {
+        v
+    } else {
+        v * (1.0 / length_squared.sqrt())
+    }
+}
+
+/// Rotates `v` around `axis`, or returns it unchanged if `axis` is not normalized, e.g. because
+/// it is the zero direction between coincident points. The engine logs an error in this case.
+#[inline]
+fn rotated_or_same(v: Vector3, axis: Vector3, angle: real) -> Vector3 {
+    if axis.length().is_equal_approx(1.0) {
+        v.rotated(axis, angle)
+    } else {
+        v
+    }
+}
+
+#[inline]
+fn distance<V: CurveVector>(a: V, b: V) -> real {
+    (b - a).length_squared().sqrt()
+}
+
+/// Evaluates the segment from `a` to `b` at `t`.
+#[inline]
+fn bezier<P: ControlPoint>(a: &P, b: &P, t: real) -> P::Vector {
+    let (start, _, out) = a.handles();
+    let (end, point_in, _) = b.handles();
+    bezier_interp(t, start, start + out, end + point_in, end)
+}
+
+#[inline]
+fn bezier_interp<V: CurveVector>(t: real, start: V, control_1: V, control_2: V, end: V) -> V {
+    let omt = 1.0 - t;
+    let omt2 = omt * omt;
+    let omt3 = omt2 * omt;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    start * omt3 + control_1 * (omt2 * t * 3.0) + control_2 * (omt * t2 * 3.0) + end * t3
+}
+
+/// Returns `Ok(index)` of the segment starting at `index`, or `Err(index)` of the first or last
+/// point if `index` is out of bounds. Returns `None` for empty curves.
+#[inline]
+fn segment(len: usize, index: isize) -> Option<Result<usize, usize>> {
+    if len == 0 {
+        None
+    } else if index < 0 {
+        Some(Err(0))
+    } else if index as usize >= len - 1 {
+        Some(Err(len - 1))
+    } else {
+        Some(Ok(index as usize))
+    }
+}
+
+fn tessellate<P: ControlPoint>(
+    points: &[P],
+    max_stages: u32,
+    tolerance_degrees: real,
+) -> Vec<P::Vector> {
+    let first = match points.first() {
+        Some(first) => first.position(),
+        None => return Vec::new(),
+    };
+
+    let min_dot = tolerance_degrees.to_radians().cos();
+    let mut tess = vec![first];
+    let mut midpoints = Vec::new();
+
+    for pair in points.windows(2) {
+        let eval = |t| bezier(&pair[0], &pair[1], t);
+        midpoints.clear();
+        bake_segment(&mut midpoints, 0.0, 1.0, &eval, 0, max_stages, min_dot);
+
+        midpoints.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        tess.extend(midpoints.iter().map(|&(_, point)| point));
+        tess.push(pair[1].position());
+    }
+
+    tess
+}
+
+/// Recursively subdivides the range from `begin` to `end`, recording midpoints where the curve
+/// bends by more than the tolerance.
+fn bake_segment<V: CurveVector>(
+    midpoints: &mut Vec<(real, V)>,
+    begin: real,
+    end: real,
+    eval: &impl Fn(real) -> V,
+    depth: u32,
+    max_depth: u32,
+    min_dot: real,
+) {
+    let mp = begin + (end - begin) * 0.5;
+    let beg = eval(begin);
+    let mid = eval(mp);
+    let end_point = eval(end);
+
+    let na = normalized_or_zero(mid - beg);
+    let nb = normalized_or_zero(end_point - mid);
+    if na.dot(nb) < min_dot {
+        midpoints.push((mp, mid));
+    }
+
+    if depth < max_depth {
+        bake_segment(midpoints, begin, mp, eval, depth + 1, max_depth, min_dot);
+        bake_segment(midpoints, mp, end, eval, depth + 1, max_depth, min_dot);
+    }
+}
+
+/// Point computed while baking, with the segment and parameter it was found at.
+struct Sample<V> {
+    position: V,
+    segment: usize,
+    t: real,
+}
+
+/// Samples points separated by `interval` along the curve, and returns them with the curve's
+/// length.
+fn bake<P: ControlPoint>(points: &[P], interval: real) -> Option<(Vec<Sample<P::Vector>>, real)> {
+    let first = points.first()?;
+    let last = points.last()?;
+
+    let mut pos = first.position();
+    let mut samples = vec![Sample {
+        position: pos,
+        segment: 0,
+        t: 0.0,
+    }];
+
+    if points.len() == 1 {
+        return Some((samples, 0.0));
+    }
+
+    for (i, pair) in points.windows(2).enumerate() {
+        // Step in tenths of the segment, searching for the point at `interval` from the last one
+        // whenever a step goes beyond it.
+        let step: real = 0.1;
+        let mut p: real = 0.0;
+
+        while p < 1.0 {
+            let np = (p + step).min(1.0);
+            let mut npp = bezier(&pair[0], &pair[1], np);
+
+            if distance(pos, npp) > interval {
+                let mut low = p;
+                let mut hi = np;
+                let mut mid = low + (hi - low) * 0.5;
+
+                for _ in 0..10 {
+                    npp = bezier(&pair[0], &pair[1], mid);
+                    if interval < distance(pos, npp) {
+                        hi = mid;
+                    } else {
+                        low = mid;
+                    }
+                    mid = low + (hi - low) * 0.5;
+                }
+
+                pos = npp;
+                p = mid;
+                samples.push(Sample {
+                    position: pos,
+                    segment: i,
+                    t: mid,
+                });
+            } else {
+                p = np;
+            }
+        }
+    }
+
+    let last_pos = last.position();
+    let length = (samples.len() - 1) as real * interval + distance(pos, last_pos);
+    samples.push(Sample {
+        position: last_pos,
+        segment: points.len() - 1,
+        t: 0.0,
+    });
+
+    Some((samples, length))
+}
+
+/// Computes up vectors which rotate as little as possible from one point to the next.
+fn up_vectors(points: &[Vector3]) -> Vec<Vector3> {
+    let mut up_vectors = Vec::with_capacity(points.len());
+
+    let mut prev_sideways = Vector3::RIGHT;
+    let mut prev_up = Vector3::UP;
+    let mut prev_forward = Vector3::BACK;
+
+    for (idx, &point) in points.iter().enumerate() {
+        let forward = if idx > 0 {
+            normalized_or_zero(point - points[idx - 1])
+        } else {
+            prev_forward
+        };
+
+        let y_dot = prev_up.dot(forward);
+        let threshold = 1.0 - CMP_EPSILON as real;
+
+        let (sideways, up) = if y_dot > threshold {
+            (prev_sideways, -prev_forward)
+        } else if y_dot < -threshold {
+            (prev_sideways, prev_forward)
+        } else {
+            let sideways = normalized_or_zero(prev_up.cross(forward));
+            (sideways, normalized_or_zero(forward.cross(sideways)))
+        };
+
+        // The first point has no direction of its own, it uses the second one's.
+        if idx == 1 {
+            up_vectors[0] = up;
+        }
+        up_vectors.push(up);
+
+        prev_sideways = sideways;
+        prev_up = up;
+        prev_forward = forward;
+    }
+
+    up_vectors
+}
+
+/// Returns `Ok((index, fraction))` of the baked segment containing `offset`, or `Err(index)` if
+/// `offset` is at or beyond the ends of the curve.
+fn baked_index(
+    count: usize,
+    interval: real,
+    length: real,
+    offset: real,
+) -> Result<(usize, real), usize> {
+    if count == 1 || offset < 0.0 {
+        return Err(0);
+    }
+    if offset >= length {
+        return Err(count - 1);
+    }
+
+    let idx = (offset / interval).floor() as usize;
+    let mut frac = offset % interval;
+
+    if idx >= count - 1 {
+        return Err(count - 1);
+    } else if idx == count - 2 {
+        // The last segment is shorter than the others.
+        let last = length % interval;
+        if frac > 0.0 && last > 0.0 {
+            frac /= last;
+        }
+    } else {
+        frac /= interval;
+    }
+
+    Ok((idx, frac))
+}
+
+/// Returns the offset and position of the point on the baked curve closest to `to_point`.
+fn closest<V: CurveVector>(points: &[V], interval: real, to_point: V) -> (real, V) {
+    if points.len() == 1 {
+        return (0.0, points[0]);
+    }
+
+    let mut nearest = (0.0, points[0]);
+    let mut nearest_dist = -1.0;
+
+    for (i, pair) in points.windows(2).enumerate() {
+        let origin = pair[0];
+        let direction = (pair[1] - origin) * (1.0 / interval);
+
+        let d = (to_point - origin).dot(direction).clamp(0.0, interval);
+        let proj = origin + direction * d;
+        let dist = (proj - to_point).length_squared();
+
+        if nearest_dist < 0.0 || dist < nearest_dist {
+            nearest = (i as real * interval + d, proj);
+            nearest_dist = dist;
+        }
+    }
+
+    nearest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Baked points are found by bisection, so they are only accurate to about 1e-3.
+    fn near<V: CurveVector>(actual: V, expected: V) -> bool {
+        distance(actual, expected) < 1e-2
+    }
+
+    fn v2(x: real, y: real) -> Vector2 {
+        Vector2::new(x, y)
+    }
+
+    fn line_2d(length: real) -> Curve2D {
+        Curve2D {
+            points: vec![
+                CurvePoint2D::new(Vector2::ZERO, Vector2::ZERO, Vector2::ZERO),
+                CurvePoint2D::new(v2(length, 0.0), Vector2::ZERO, Vector2::ZERO),
+            ],
+            ..Curve2D::new()
+        }
+    }
+
+    #[test]
+    fn interpolate() {
+        let curve = Curve2D {
+            points: vec![
+                CurvePoint2D::new(Vector2::ZERO, Vector2::ZERO, v2(0.0, 10.0)),
+                CurvePoint2D::new(v2(10.0, 0.0), v2(0.0, 10.0), Vector2::ZERO),
+            ],
+            ..Curve2D::new()
+        };
+
+        assert_eq!(curve.interpolate(0, 0.0), Some(Vector2::ZERO));
+        assert_eq!(curve.interpolate(0, 1.0), Some(v2(10.0, 0.0)));
+        // 3 * 0.25 * 10 for both handles.
+        assert!(curve
+            .interpolate(0, 0.5)
+            .unwrap()
+            .is_equal_approx(v2(5.0, 7.5)));
+        assert_eq!(curve.interpolate(-1, 0.5), Some(Vector2::ZERO));
+        assert_eq!(curve.interpolate(5, 0.5), Some(v2(10.0, 0.0)));
+        assert_eq!(curve.interpolatef(0.5), curve.interpolate(0, 0.5));
+        assert_eq!(curve.interpolatef(7.0), Some(v2(10.0, 0.0)));
+
+        assert_eq!(Curve2D::new().interpolate(0, 0.5), None);
+    }
+
+    #[test]
+    fn data() {
+        let data = [
+            v2(-1.0, 0.0),
+            v2(1.0, 0.0),
+            v2(0.0, 0.0),
+            v2(0.0, -1.0),
+            v2(0.0, 1.0),
+            v2(5.0, 5.0),
+        ];
+        let curve = Curve2D::from_data(&data).unwrap();
+        assert_eq!(
+            curve.points[1],
+            CurvePoint2D::new(v2(5.0, 5.0), v2(0.0, -1.0), v2(0.0, 1.0))
+        );
+        assert_eq!(curve.to_data(), data);
+        assert_eq!(
+            Curve2D::from_data(&data[..4]),
+            Err(CurveDataError::PointCount { len: 4 })
+        );
+
+        let points = [Vector3::ZERO; 6];
+        assert_eq!(
+            Curve3D::from_data(&points, &[0.5]),
+            Err(CurveDataError::TiltCount {
+                expected: 2,
+                actual: 1
+            })
+        );
+        let curve = Curve3D::from_data(&points, &[0.5, 1.0]).unwrap();
+        assert_eq!(curve.points[1].tilt, 1.0);
+        assert_eq!(curve.to_data(), (points.to_vec(), vec![0.5, 1.0]));
+    }
+
+    #[test]
+    fn bake_line() {
+        let baked = line_2d(12.0).bake().unwrap();
+        assert_eq!(baked.points().len(), 4);
+        assert!(near(baked.points()[1], v2(5.0, 0.0)));
+        assert!(near(baked.points()[2], v2(10.0, 0.0)));
+        assert!((baked.length() - 12.0).abs() < 1e-2);
+
+        assert!(near(baked.interpolate(2.5, false), v2(2.5, 0.0)));
+        // The last segment is only 2 long.
+        assert!(near(baked.interpolate(11.0, false), v2(11.0, 0.0)));
+        assert_eq!(baked.interpolate(-1.0, false), Vector2::ZERO);
+        assert_eq!(baked.interpolate(20.0, false), v2(12.0, 0.0));
+
+        // Cubic interpolation between evenly spaced points on a line stays on the line.
+        let long = line_2d(22.0).bake().unwrap();
+        assert!(near(long.interpolate(7.5, true), v2(7.5, 0.0)));
+
+        assert!((baked.closest_offset(v2(7.0, 3.0)) - 7.0).abs() < 1e-2);
+        assert!(near(baked.closest_point(v2(7.0, 3.0)), v2(7.0, 0.0)));
+
+        assert_eq!(Curve2D::new().bake(), None);
+        let single = Curve2D {
+            points: vec![CurvePoint2D::new(
+                v2(1.0, 2.0),
+                Vector2::ZERO,
+                Vector2::ZERO,
+            )],
+            ..Curve2D::new()
+        };
+        let baked = single.bake().unwrap();
+        assert_eq!(baked.interpolate(3.0, false), v2(1.0, 2.0));
+        assert_eq!(baked.closest_offset(v2(5.0, 5.0)), 0.0);
+    }
+
+    #[test]
+    fn bake_arc() {
+        // Quarter circle approximation with radius 100.
+        let k = 55.228475;
+        let curve = Curve2D {
+            points: vec![
+                CurvePoint2D::new(v2(100.0, 0.0), Vector2::ZERO, v2(0.0, k)),
+                CurvePoint2D::new(v2(0.0, 100.0), v2(k, 0.0), Vector2::ZERO),
+            ],
+            ..Curve2D::new()
+        };
+
+        let baked = curve.bake().unwrap();
+        let quarter = std::f64::consts::FRAC_PI_2 as real * 100.0;
+        assert!((baked.length() - quarter).abs() < 0.1);
+
+        // Baked points are evenly spaced, and on the circle.
+        for pair in baked.points()[..baked.points().len() - 1].windows(2) {
+            assert!((pair[0].distance_to(pair[1]) - 5.0).abs() < 0.05);
+            assert!((pair[1].length() - 100.0).abs() < 0.1);
+        }
+
+        let tess = curve.tessellate(5, 4.0);
+        assert_eq!(tess.first(), Some(&v2(100.0, 0.0)));
+        assert_eq!(tess.last(), Some(&v2(0.0, 100.0)));
+        assert!(tess.len() > 3);
+        for pair in tess.windows(2) {
+            // Sorted along the curve.
+            assert!(pair[0].x > pair[1].x && pair[0].y < pair[1].y);
+        }
+
+        assert_eq!(line_2d(10.0).tessellate(5, 4.0).len(), 2);
+    }
+
+    #[test]
+    fn bake_3d() {
+        let curve = Curve3D {
+            points: vec![
+                CurvePoint3D {
+                    tilt: 0.0,
+                    ..CurvePoint3D::new(Vector3::ZERO, Vector3::ZERO, Vector3::ZERO)
+                },
+                CurvePoint3D {
+                    tilt: 1.0,
+                    ..CurvePoint3D::new(Vector3::new(0.0, 0.0, -1.0), Vector3::ZERO, Vector3::ZERO)
+                },
+            ],
+            bake_interval: 0.3,
+            ..Curve3D::new()
+        };
+
+        let baked = curve.bake().unwrap();
+        assert_eq!(baked.points().len(), 5);
+        assert!((baked.length() - 1.0).abs() < 1e-2);
+        assert!(near(
+            baked.interpolate(0.45, false),
+            Vector3::new(0.0, 0.0, -0.45)
+        ));
+
+        let tilts = baked.tilts();
+        assert_eq!((tilts[0], tilts[4]), (0.0, 1.0));
+        assert!(tilts.windows(2).all(|pair| pair[0] < pair[1]));
+        let tilt = (tilts[1] + tilts[2]) / 2.0;
+        assert!(baked.interpolate_tilt(0.45).is_equal_approx(tilt));
+
+        // Going straight forward, the up vector is UP, and the tilt rotates it towards RIGHT.
+        let up_vectors = baked.up_vectors().unwrap();
+        assert!(up_vectors.iter().all(|up| up.is_equal_approx(Vector3::UP)));
+        assert!(baked
+            .interpolate_up_vector(0.45, false)
+            .unwrap()
+            .is_equal_approx(Vector3::UP));
+        let (sin, cos) = tilt.sin_cos();
+        assert!(baked
+            .interpolate_up_vector(0.45, true)
+            .unwrap()
+            .is_equal_approx(Vector3::new(sin, cos, 0.0)));
+
+        // Coincident points have no direction to tilt around.
+        let point = CurvePoint3D {
+            tilt: 0.5,
+            ..CurvePoint3D::new(Vector3::ONE, Vector3::ZERO, Vector3::ZERO)
+        };
+        let coincident = Curve3D {
+            points: vec![point, point],
+            ..Curve3D::new()
+        };
+        let baked = coincident.bake().unwrap();
+        for offset in [0.0, 0.5] {
+            let up = baked.interpolate_up_vector(offset, true).unwrap();
+            assert!(!up.x.is_nan() && !up.y.is_nan() && !up.z.is_nan());
+        }
+
+        let without_up = Curve3D {
+            up_vector_enabled: false,
+            ..curve
+        };
+        assert_eq!(
+            without_up.bake().unwrap().interpolate_up_vector(0.5, false),
+            None
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::core_types::geometry3d;
+use crate::core_types::{real, Aabb, Plane, Transform, Vector3};
+
+/// Triangle in 3D space.
+///
+/// This is the equivalent of the engine's internal `Face3` type, as used for collision faces of
+/// meshes and navigation data.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Face3 {
+    /// The triangle's corners.
+    pub vertices: [Vector3; 3],
+}
+
+impl Face3 {
+    /// Creates a `Face3` from its three corners.
+    #[inline]
+    pub const fn new(a: Vector3, b: Vector3, c: Vector3) -> Self {
+        Self {
+            vertices: [a, b, c],
+        }
+    }
+
+    /// Returns the area of the triangle.
+    #[inline]
+    pub fn area(self) -> real {
+        let [a, b, c] = self.vertices;
+        (a - b).cross(a - c).length() * 0.5
+    }
+
+    /// Returns the centroid of the triangle.
+    #[inline]
+    pub fn median_point(self) -> Vector3 {
+        let [a, b, c] = self.vertices;
+        (a + b + c) / 3.0
+    }
+
+    /// Returns the plane of the triangle, with the vertices in clockwise order when seen from the
+    /// front, or `None` if the triangle is degenerate.
+    #[inline]
+    pub fn plane(self) -> Option<Plane> {
+        let [a, b, c] = self.vertices;
+        Plane::from_points(a, b, c)
+    }
+
+    /// Returns `true` if the triangle has no area, within a small tolerance.
+    #[inline]
+    pub fn is_degenerate(self) -> bool {
+        let [a, b, c] = self.vertices;
+        geometry3d::is_zero_area(a, b, c)
+    }
+
+    /// Returns the smallest bounding box containing the triangle.
+    #[inline]
+    pub fn aabb(self) -> Aabb {
+        let [a, b, c] = self.vertices;
+        let min = Vector3::new(
+            a.x.min(b.x).min(c.x),
+            a.y.min(b.y).min(c.y),
+            a.z.min(b.z).min(c.z),
+        );
+        let max = Vector3::new(
+            a.x.max(b.x).max(c.x),
+            a.y.max(b.y).max(c.y),
+            a.z.max(b.z).max(c.z),
+        );
+        Aabb::new(min, max - min)
+    }
+
+    /// Returns the point where the ray starting at `from` in direction `dir` hits the triangle, or
+    /// `None` if it doesn't. See [`geometry3d::ray_intersects_triangle`].
+    #[inline]
+    pub fn intersects_ray(self, from: Vector3, dir: Vector3) -> Option<Vector3> {
+        let [a, b, c] = self.vertices;
+        geometry3d::ray_intersects_triangle(from, dir, a, b, c)
+    }
+
+    /// Returns the point where the segment `from`-`to` hits the triangle, or `None` if it doesn't.
+    /// See [`geometry3d::segment_intersects_triangle`].
+    #[inline]
+    pub fn intersects_segment(self, from: Vector3, to: Vector3) -> Option<Vector3> {
+        let [a, b, c] = self.vertices;
+        geometry3d::segment_intersects_triangle(from, to, a, b, c)
+    }
+
+    /// Returns the point of the triangle that is closest to `point`.
+    #[inline]
+    pub fn closest_point_to(self, point: Vector3) -> Vector3 {
+        let [a, b, c] = self.vertices;
+        let edge0 = b - a;
+        let edge1 = c - a;
+        let v0 = a - point;
+
+        let a = edge0.dot(edge0);
+        let b = edge0.dot(edge1);
+        let c = edge1.dot(edge1);
+        let d = edge0.dot(v0);
+        let e = edge1.dot(v0);
+
+        let det = a * c - b * b;
+        let s = b * e - c * d;
+        let t = b * d - a * e;
+
+        // Barycentric coordinates of the closest point, depending on which region around the
+        // triangle the projection of `point` falls into.
+        let (s, t) = if s + t < det {
+            if s < 0.0 {
+                if t < 0.0 && d < 0.0 {
+                    ((-d / a).clamp(0.0, 1.0), 0.0)
+                } else {
+                    (0.0, (-e / c).clamp(0.0, 1.0))
+                }
+            } else if t < 0.0 {
+                ((-d / a).clamp(0.0, 1.0), 0.0)
+            } else {
+                (s / det, t / det)
+            }
+        } else if s < 0.0 {
+            let tmp0 = b + d;
+            let tmp1 = c + e;
+            if tmp1 > tmp0 {
+                let s = ((tmp1 - tmp0) / (a - 2.0 * b + c)).clamp(0.0, 1.0);
+                (s, 1.0 - s)
+            } else {
+                (0.0, (-e / c).clamp(0.0, 1.0))
+            }
+        } else if t < 0.0 {
+            if a + d > b + e {
+                let s = ((c + e - b - d) / (a - 2.0 * b + c)).clamp(0.0, 1.0);
+                (s, 1.0 - s)
+            } else {
+                ((-d / a).clamp(0.0, 1.0), 0.0)
+            }
+        } else {
+            let s = ((c + e - b - d) / (a - 2.0 * b + c)).clamp(0.0, 1.0);
+            (s, 1.0 - s)
+        };
+
+        self.vertices[0] + edge0 * s + edge1 * t
+    }
+
+    /// Returns the triangle transformed by `transform`.
+    #[inline]
+    pub fn transformed(self, transform: &Transform) -> Self {
+        let [a, b, c] = self.vertices;
+        Self::new(transform.xform(a), transform.xform(b), transform.xform(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_types::IsEqualApprox;
+
+    fn v(x: real, y: real, z: real) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    fn face() -> Face3 {
+        Face3::new(v(0.0, 0.0, 0.0), v(2.0, 0.0, 0.0), v(0.0, 2.0, 0.0))
+    }
+
+    #[test]
+    fn properties() {
+        let face = face();
+        assert!(face.area().is_equal_approx(2.0));
+        assert!(face
+            .median_point()
+            .is_equal_approx(v(2.0 / 3.0, 2.0 / 3.0, 0.0)));
+        assert!(face
+            .plane()
+            .unwrap()
+            .is_equal_approx(Plane::new(Vector3::FORWARD, 0.0)));
+        assert_eq!(face.aabb(), Aabb::new(Vector3::ZERO, v(2.0, 2.0, 0.0)));
+        assert!(!face.is_degenerate());
+
+        let line = Face3::new(v(0.0, 0.0, 0.0), v(1.0, 1.0, 1.0), v(2.0, 2.0, 2.0));
+        assert!(line.is_degenerate());
+        assert_eq!(line.plane(), None);
+    }
+
+    #[test]
+    fn intersections() {
+        let face = face();
+        assert_eq!(
+            face.intersects_ray(v(0.5, 0.5, 1.0), Vector3::FORWARD),
+            Some(v(0.5, 0.5, 0.0))
+        );
+        assert_eq!(
+            face.intersects_segment(v(0.5, 0.5, 2.0), v(0.5, 0.5, 1.0)),
+            None
+        );
+
+        let moved = face.transformed(&Transform::IDENTITY.translated_global(v(0.0, 0.0, 1.0)));
+        assert_eq!(
+            moved.intersects_segment(v(0.5, 0.5, 2.0), v(0.5, 0.5, 0.0)),
+            Some(v(0.5, 0.5, 1.0))
+        );
+    }
+
+    #[test]
+    fn closest_point() {
+        let face = face();
+        let cases = [
+            // Inside, above the face.
+            (v(0.5, 0.5, 3.0), v(0.5, 0.5, 0.0)),
+            // Beyond each vertex.
+            (v(-1.0, -1.0, 0.0), v(0.0, 0.0, 0.0)),
+            (v(3.0, -1.0, 0.0), v(2.0, 0.0, 0.0)),
+            (v(-1.0, 3.0, 0.0), v(0.0, 2.0, 0.0)),
+            // Beyond each edge.
+            (v(1.0, -1.0, 1.0), v(1.0, 0.0, 0.0)),
+            (v(-1.0, 1.0, 0.0), v(0.0, 1.0, 0.0)),
+            (v(2.0, 2.0, 0.0), v(1.0, 1.0, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            assert!(
+                face.closest_point_to(point).is_equal_approx(expected),
+                "closest point to {point:?}: {:?} != {expected:?}",
+                face.closest_point_to(point),
+            );
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use crate::core_types::{real, Aabb, Plane, Transform, Vector3};
+
+/// The six planes bounding the space visible to a camera.
+///
+/// Created with [`Projection::frustum_planes`][crate::core_types::Projection::frustum_planes].
+/// The plane normals point outwards, so points are inside the frustum if they are not over any
+/// of its planes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Frustum {
+    /// Plane closest to the camera.
+    pub near: Plane,
+    /// Plane furthest from the camera.
+    pub far: Plane,
+    /// Left side.
+    pub left: Plane,
+    /// Top side.
+    pub top: Plane,
+    /// Right side.
+    pub right: Plane,
+    /// Bottom side.
+    pub bottom: Plane,
+}
+
+impl Frustum {
+    /// Returns the planes in the engine's order: near, far, left, top, right, bottom.
+    ///
+    /// This is the order of `Camera.get_frustum()`, and can be passed on to functions like
+    /// [`geometry3d::segment_intersects_convex`][crate::core_types::geometry3d::segment_intersects_convex].
+    #[inline]
+    pub fn planes(&self) -> [Plane; 6] {
+        [
+            self.near,
+            self.far,
+            self.left,
+            self.top,
+            self.right,
+            self.bottom,
+        ]
+    }
+
+    /// Returns the frustum transformed by `transform`.
+    #[inline]
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        let xform = |plane: Plane| {
+            let point = transform.xform(plane.normal * plane.d);
+            let normal =
+                (transform.xform(plane.normal * plane.d + plane.normal) - point).normalized();
+            Plane {
+                normal,
+                d: normal.dot(point),
+            }
+        };
+
+        Self {
+            near: xform(self.near),
+            far: xform(self.far),
+            left: xform(self.left),
+            top: xform(self.top),
+            right: xform(self.right),
+            bottom: xform(self.bottom),
+        }
+    }
+
+    /// Returns the eight corners of the frustum: first the far ones, then the near ones, each as
+    /// top-left, bottom-left, top-right, bottom-right.
+    ///
+    /// Returns `None` if the frustum is not bounded.
+    #[inline]
+    pub fn endpoints(&self) -> Option<[Vector3; 8]> {
+        let corner = |plane: Plane, side: Plane, vertical: Plane| plane.intersect_3(side, vertical);
+
+        Some([
+            corner(self.far, self.left, self.top)?,
+            corner(self.far, self.left, self.bottom)?,
+            corner(self.far, self.right, self.top)?,
+            corner(self.far, self.right, self.bottom)?,
+            corner(self.near, self.left, self.top)?,
+            corner(self.near, self.left, self.bottom)?,
+            corner(self.near, self.right, self.top)?,
+            corner(self.near, self.right, self.bottom)?,
+        ])
+    }
+
+    /// Returns `true` if `point` is inside the frustum or on its boundary.
+    #[inline]
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.planes()
+            .iter()
+            .all(|plane| !plane.is_point_over(point))
+    }
+
+    /// Returns `true` if the sphere may be visible.
+    ///
+    /// Like all plane-based tests, this is conservative: spheres close to the frustum's edges
+    /// but outside of it may be reported as intersecting.
+    #[inline]
+    pub fn intersects_sphere(&self, center: Vector3, radius: real) -> bool {
+        self.planes()
+            .iter()
+            .all(|plane| plane.distance_to(center) <= radius)
+    }
+
+    /// Returns `true` if the bounding box may be visible.
+    ///
+    /// Boxes which are behind one of the planes, or which are entirely beyond the frustum's
+    /// corners along one of the axes, are rejected. Like in the engine, boxes close to the
+    /// frustum's edges but outside of it may still be reported as intersecting.
+    #[inline]
+    pub fn intersects_aabb(&self, aabb: Aabb) -> bool {
+        let half_extents = aabb.size * 0.5;
+        let center = aabb.position + half_extents;
+
+        // Test the corner furthest behind each plane.
+        let behind_all = self.planes().iter().all(|plane| {
+            let corner = center + select(plane.normal, -half_extents, half_extents);
+            !plane.is_point_over(corner)
+        });
+        if !behind_all {
+            return false;
+        }
+
+        // Make sure the corners of the frustum aren't all on one side of the box on any axis.
+        let endpoints = match self.endpoints() {
+            Some(endpoints) => endpoints,
+            None => return true,
+        };
+
+        let (min, max) = (aabb.position, aabb.end());
+        (0..3).all(|axis| {
+            let below = endpoints
+                .iter()
+                .all(|p| p.as_ref()[axis] < min.as_ref()[axis]);
+            let above = endpoints
+                .iter()
+                .all(|p| p.as_ref()[axis] > max.as_ref()[axis]);
+            !below && !above
+        })
+    }
+
+    /// Returns `true` if the bounding box is entirely inside the frustum.
+    #[inline]
+    pub fn encloses_aabb(&self, aabb: Aabb) -> bool {
+        let half_extents = aabb.size * 0.5;
+        let center = aabb.position + half_extents;
+
+        // Test the corner furthest in front of each plane.
+        self.planes().iter().all(|plane| {
+            let corner = center + select(plane.normal, half_extents, -half_extents);
+            !plane.is_point_over(corner)
+        })
+    }
+}
+
+/// Picks the components of `positive` where `normal` is positive, and of `otherwise` elsewhere.
+fn select(normal: Vector3, positive: Vector3, otherwise: Vector3) -> Vector3 {
+    let pick = |n: real, p: real, o: real| if n > 0.0 { p } else { o };
+    Vector3::new(
+        pick(normal.x, positive.x, otherwise.x),
+        pick(normal.y, positive.y, otherwise.y),
+        pick(normal.z, positive.z, otherwise.z),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_types::Projection;
+
+    fn camera() -> (Projection, Transform) {
+        let projection = Projection::perspective(90.0, 1.0, 1.0, 100.0, false);
+        // At (0, 0, 10), looking along -Z.
+        let transform = Transform::IDENTITY.translated_global(Vector3::new(0.0, 0.0, 10.0));
+        (projection, transform)
+    }
+
+    #[test]
+    fn planes() {
+        let (projection, transform) = camera();
+        let frustum = projection.frustum_planes(&Transform::IDENTITY);
+
+        assert!(frustum
+            .near
+            .is_equal_approx(Plane::new(Vector3::BACK, -1.0)));
+        // The far plane loses precision in single-precision builds.
+        assert!(frustum.far.normal.is_equal_approx(Vector3::FORWARD));
+        assert!((frustum.far.d - 100.0).abs() < 1e-3);
+        let side = std::f64::consts::FRAC_1_SQRT_2 as real;
+        assert!(frustum
+            .left
+            .is_equal_approx(Plane::new(Vector3::new(-side, 0.0, side), 0.0)));
+        assert!(frustum
+            .top
+            .is_equal_approx(Plane::new(Vector3::new(0.0, side, side), 0.0)));
+
+        let moved = projection.frustum_planes(&transform);
+        assert!(moved.near.is_equal_approx(Plane::new(Vector3::BACK, 9.0)));
+        assert!(moved
+            .left
+            .is_equal_approx(Plane::new(Vector3::new(-side, 0.0, side), 10.0 * side)));
+    }
+
+    #[test]
+    fn endpoints() {
+        let (projection, _) = camera();
+        let endpoints = projection
+            .frustum_planes(&Transform::IDENTITY)
+            .endpoints()
+            .unwrap();
+
+        assert!((endpoints[0] / 100.0).is_equal_approx(Vector3::new(-1.0, 1.0, -1.0)));
+        assert!((endpoints[3] / 100.0).is_equal_approx(Vector3::new(1.0, -1.0, -1.0)));
+        assert!(endpoints[4].is_equal_approx(Vector3::new(-1.0, 1.0, -1.0)));
+        assert!(endpoints[7].is_equal_approx(Vector3::new(1.0, -1.0, -1.0)));
+    }
+
+    #[test]
+    fn culling() {
+        let (projection, transform) = camera();
+        let frustum = projection.frustum_planes(&transform);
+
+        assert!(frustum.contains_point(Vector3::ZERO));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, 20.0)));
+        assert!(!frustum.contains_point(Vector3::new(20.0, 0.0, 0.0)));
+
+        assert!(frustum.intersects_sphere(Vector3::new(11.0, 0.0, 0.0), 1.0));
+        assert!(!frustum.intersects_sphere(Vector3::new(13.0, 0.0, 0.0), 1.0));
+
+        let unit = |x: real, y: real, z: real| Aabb::new(Vector3::new(x, y, z), Vector3::ONE);
+        assert!(frustum.intersects_aabb(unit(0.0, 0.0, 0.0)));
+        assert!(frustum.encloses_aabb(unit(0.0, 0.0, 0.0)));
+
+        // Straddling the right plane.
+        assert!(frustum.intersects_aabb(unit(9.5, 0.0, 0.0)));
+        assert!(!frustum.encloses_aabb(unit(9.5, 0.0, 0.0)));
+
+        // Behind the camera, beyond the far plane, and to the side.
+        assert!(!frustum.intersects_aabb(unit(0.0, 0.0, 12.0)));
+        assert!(!frustum.intersects_aabb(unit(0.0, 0.0, -100.0)));
+        assert!(!frustum.intersects_aabb(unit(20.0, 0.0, 0.0)));
+    }
+}
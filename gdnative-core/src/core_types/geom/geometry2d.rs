@@ -0,0 +1,794 @@
+//! Pure-Rust ports of the 2D algorithms of the engine's `Geometry` singleton.
+//!
+//! Unlike the singleton, these functions do not call into the engine, so they can be used on any
+//! thread, and outside of the engine altogether. The algorithms, tolerances and corner cases follow
+//! the engine's.
+//!
+//! Polygons are passed as slices of [`Vector2`]. A [`PoolArray<Vector2>`][crate::core_types::PoolArray]
+//! can be passed through its read guard, and results converted back with
+//! [`PoolArray::from_vec`][crate::core_types::PoolArray::from_vec]:
+//!
+//! ```no_run
+//! # use gdnative::prelude::*;
+//! use gdnative::core_types::geometry2d;
+//!
+//! # fn outline(points: PoolArray<Vector2>) -> PoolArray<Vector2> {
+//! let hull = geometry2d::convex_hull(&points.read());
+//! PoolArray::from_vec(hull)
+//! # }
+//! ```
+//!
+//! ## Polygon operations
+//!
+//! Like the engine, [`merge_polygons`], [`clip_polygons`], [`intersect_polygons`],
+//! [`exclude_polygons`] and [`offset_polygon`] work on a fixed-point grid with a resolution of
+//! `0.00001`, to which inputs are truncated and outputs are snapped. They return outer boundaries
+//! and holes as separate polygons. Holes can be told apart with [`is_polygon_clockwise`]:
+//! outer boundaries are counter-clockwise, holes clockwise. Collinear and duplicate vertices are
+//! removed from the results. Both the polygons and their vertices come in the same order as the
+//! engine's, as the operations are done by a port of the Clipper library the engine uses.
+
+use crate::core_types::{real, IsEqualApprox, RealConv, Vector2, CMP_EPSILON};
+
+use self::clipper::{ClipType, Clipper, ClipperOffset, IntPoint, PolyFillType, PolyType};
+
+mod clipper;
+
+/// Returns `true` if the polygon's vertices are ordered in clockwise order.
+///
+/// Polygons with less than three vertices are never clockwise.
+#[inline]
+pub fn is_polygon_clockwise(polygon: &[Vector2]) -> bool {
+    if polygon.len() < 3 {
+        return false;
+    }
+
+    let mut sum = 0.0;
+    for (i, &v1) in polygon.iter().enumerate() {
+        let v2 = polygon[(i + 1) % polygon.len()];
+        sum += (v2.x - v1.x) * (v2.y + v1.y);
+    }
+
+    sum > 0.0
+}
+
+/// Returns `true` if `point` is inside `polygon`, using the even-odd rule.
+///
+/// Polygons with less than three vertices contain no points.
+#[inline]
+pub fn is_point_in_polygon(point: Vector2, polygon: &[Vector2]) -> bool {
+    if polygon.len() < 3 {
+        return false;
+    }
+
+    let mut further_away = Vector2::new(-1e20, -1e20);
+    let mut further_away_opposite = Vector2::new(1e20, 1e20);
+    for v in polygon {
+        further_away.x = further_away.x.max(v.x);
+        further_away.y = further_away.y.max(v.y);
+        further_away_opposite.x = further_away_opposite.x.min(v.x);
+        further_away_opposite.y = further_away_opposite.y.min(v.y);
+    }
+
+    // A point outside the polygon that won't be collinear with any of its edges.
+    further_away += (further_away - further_away_opposite) * Vector2::new(1.221313, 1.512312);
+
+    let intersections = polygon
+        .iter()
+        .enumerate()
+        .filter(|&(i, &v1)| {
+            let v2 = polygon[(i + 1) % polygon.len()];
+            segment_intersects_segment(v1, v2, point, further_away).is_some()
+        })
+        .count();
+
+    intersections % 2 == 1
+}
+
+/// Returns the point where the segments `from_a`-`to_a` and `from_b`-`to_b` intersect, or `None`
+/// if they don't.
+///
+/// Parallel and collinear segments are never considered to intersect.
+#[inline]
+pub fn segment_intersects_segment(
+    from_a: Vector2,
+    to_a: Vector2,
+    from_b: Vector2,
+    to_b: Vector2,
+) -> Option<Vector2> {
+    let b = to_a - from_a;
+    let c = from_b - from_a;
+    let d = to_b - from_a;
+
+    let ab_len = b.dot(b);
+    if ab_len <= 0.0 {
+        return None;
+    }
+
+    // Rotate and scale `b` to the X axis.
+    let bn = b / ab_len;
+    let c = Vector2::new(c.x * bn.x + c.y * bn.y, c.y * bn.x - c.x * bn.y);
+    let d = Vector2::new(d.x * bn.x + d.y * bn.y, d.y * bn.x - d.x * bn.y);
+
+    let epsilon = CMP_EPSILON as real;
+    if (c.y < -epsilon && d.y < -epsilon) || (c.y > epsilon && d.y > epsilon) {
+        return None;
+    }
+
+    if c.y.is_equal_approx(d.y) {
+        return None;
+    }
+
+    let ab_pos = d.x + (c.x - d.x) * d.y / (d.y - c.y);
+    if !(0.0..=1.0).contains(&ab_pos) {
+        return None;
+    }
+
+    Some(from_a + b * ab_pos)
+}
+
+/// Returns the position along the segment `from`-`to` where it first enters or leaves the circle,
+/// as a fraction between 0 and 1, or `None` if it does neither.
+#[inline]
+pub fn segment_intersects_circle(
+    from: Vector2,
+    to: Vector2,
+    circle_position: Vector2,
+    circle_radius: real,
+) -> Option<real> {
+    let line_vec = to - from;
+    let vec_to_line = from - circle_position;
+
+    let a = line_vec.dot(line_vec);
+    let b = 2.0 * vec_to_line.dot(line_vec);
+    let c = circle_position.dot(circle_position) + from.dot(from)
+        - 2.0 * circle_position.dot(from)
+        - circle_radius * circle_radius;
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let root = discriminant.sqrt();
+    [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+        .into_iter()
+        .find(|t| (0.0..=1.0).contains(t))
+}
+
+/// Returns the point on the segment `from`-`to` that is closest to `point`.
+#[inline]
+pub fn closest_point_to_segment(point: Vector2, from: Vector2, to: Vector2) -> Vector2 {
+    let p = point - from;
+    let n = to - from;
+    let l2 = n.length_squared();
+    if l2 < 1e-20 {
+        return from;
+    }
+
+    let d = n.dot(p) / l2;
+    if d <= 0.0 {
+        from
+    } else if d >= 1.0 {
+        to
+    } else {
+        from + n * d
+    }
+}
+
+/// Returns the point on the line through `from` and `to` that is closest to `point`.
+#[inline]
+pub fn closest_point_to_segment_uncapped(point: Vector2, from: Vector2, to: Vector2) -> Vector2 {
+    let p = point - from;
+    let n = to - from;
+    let l2 = n.length_squared();
+    if l2 < 1e-20 {
+        return from;
+    }
+
+    from + n * (n.dot(p) / l2)
+}
+
+/// Returns the pair of closest points between the segments `p1`-`q1` and `p2`-`q2`, the first
+/// one on `p1`-`q1` and the second one on `p2`-`q2`.
+#[inline]
+pub fn closest_points_between_segments(
+    p1: Vector2,
+    q1: Vector2,
+    p2: Vector2,
+    q2: Vector2,
+) -> (Vector2, Vector2) {
+    let epsilon = CMP_EPSILON as real;
+
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.dot(d1);
+    let e = d2.dot(d2);
+    let f = d2.dot(r);
+
+    if a <= epsilon && e <= epsilon {
+        return (p1, p2);
+    }
+
+    let (s, t) = if a <= epsilon {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(r);
+        if e <= epsilon {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+            let s = if denom != 0.0 {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+
+            let t = (b * s + f) / e;
+            if t < 0.0 {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else if t > 1.0 {
+                (((b - c) / a).clamp(0.0, 1.0), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
+/// Returns the convex hull of `points`, in counter-clockwise order.
+///
+/// Like in the engine, the first point is repeated at the end of the hull.
+#[inline]
+pub fn convex_hull(points: &[Vector2]) -> Vec<Vector2> {
+    fn cross(o: Vector2, a: Vector2, b: Vector2) -> real {
+        (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+    }
+
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .and_then(|ord| Some(ord.then(a.y.partial_cmp(&b.y)?)))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    // Andrew's monotone chain: lower hull, then upper hull.
+    let mut hull: Vec<Vector2> = Vec::with_capacity(points.len() * 2);
+    for &p in &points {
+        while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    let lower_len = hull.len() + 1;
+    for &p in points.iter().rev().skip(1) {
+        while hull.len() >= lower_len && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    hull
+}
+
+/// Triangulates a simple polygon by ear clipping.
+///
+/// Returns the vertex indices of the triangles, three per triangle, or `None` if the polygon
+/// could not be triangulated, for example because it intersects itself.
+#[inline]
+pub fn triangulate_polygon(polygon: &[Vector2]) -> Option<Vec<usize>> {
+    let n = polygon.len();
+    if n < 3 {
+        return None;
+    }
+
+    // Work on a counter-clockwise polygon.
+    let area: real = (0..n)
+        .map(|q| polygon[(q + n - 1) % n].cross(polygon[q]))
+        .sum();
+    let mut vertices: Vec<usize> = if area > 0.0 {
+        (0..n).collect()
+    } else {
+        (0..n).rev().collect()
+    };
+
+    let mut result = Vec::with_capacity((n - 2) * 3);
+    let mut relaxed = false;
+    let mut count = 2 * n;
+    let mut v = n - 1;
+
+    while vertices.len() > 2 {
+        let nv = vertices.len();
+
+        // Looping means the polygon is probably not simple. Try again once, accepting aligned
+        // vertices which the strict check rejects.
+        if count == 0 {
+            if relaxed {
+                return None;
+            }
+            count = 2 * nv;
+            relaxed = true;
+        } else {
+            count -= 1;
+        }
+
+        let u = if v < nv { v } else { 0 };
+        v = if u + 1 < nv { u + 1 } else { 0 };
+        let w = if v + 1 < nv { v + 1 } else { 0 };
+
+        if is_ear(polygon, &vertices, u, v, w, relaxed) {
+            result.extend_from_slice(&[vertices[u], vertices[v], vertices[w]]);
+            vertices.remove(v);
+            count = 2 * vertices.len();
+        }
+    }
+
+    Some(result)
+}
+
+fn is_ear(
+    polygon: &[Vector2],
+    vertices: &[usize],
+    u: usize,
+    v: usize,
+    w: usize,
+    relaxed: bool,
+) -> bool {
+    let a = polygon[vertices[u]];
+    let b = polygon[vertices[v]];
+    let c = polygon[vertices[w]];
+
+    if !relaxed && (CMP_EPSILON as real) > (b - a).cross(c - a) {
+        return false;
+    }
+
+    vertices
+        .iter()
+        .enumerate()
+        .filter(|&(p, _)| p != u && p != v && p != w)
+        .all(|(_, &p)| !is_inside_triangle(a, b, c, polygon[p], relaxed))
+}
+
+fn is_inside_triangle(a: Vector2, b: Vector2, c: Vector2, p: Vector2, relaxed: bool) -> bool {
+    let a_cross_bp = (c - b).cross(p - b);
+    let c_cross_ap = (b - a).cross(p - a);
+    let b_cross_cp = (a - c).cross(p - c);
+
+    if relaxed {
+        a_cross_bp > 0.0 && b_cross_cp > 0.0 && c_cross_ap > 0.0
+    } else {
+        a_cross_bp >= 0.0 && b_cross_cp >= 0.0 && c_cross_ap >= 0.0
+    }
+}
+
+/// Returns the union of two polygons.
+#[inline]
+pub fn merge_polygons(polygon_a: &[Vector2], polygon_b: &[Vector2]) -> Vec<Vec<Vector2>> {
+    boolean_operation(ClipType::Union, polygon_a, polygon_b)
+}
+
+/// Returns the parts of `polygon_a` which are not covered by `polygon_b`.
+#[inline]
+pub fn clip_polygons(polygon_a: &[Vector2], polygon_b: &[Vector2]) -> Vec<Vec<Vector2>> {
+    boolean_operation(ClipType::Difference, polygon_a, polygon_b)
+}
+
+/// Returns the intersection of two polygons.
+#[inline]
+pub fn intersect_polygons(polygon_a: &[Vector2], polygon_b: &[Vector2]) -> Vec<Vec<Vector2>> {
+    boolean_operation(ClipType::Intersection, polygon_a, polygon_b)
+}
+
+/// Returns the parts covered by exactly one of the polygons.
+#[inline]
+pub fn exclude_polygons(polygon_a: &[Vector2], polygon_b: &[Vector2]) -> Vec<Vec<Vector2>> {
+    boolean_operation(ClipType::Xor, polygon_a, polygon_b)
+}
+
+/// How the corners of a polygon are joined when it is inflated or deflated by [`offset_polygon`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PolyJoinType {
+    /// Corners are squared off at exactly the offset distance.
+    #[default]
+    Square,
+    /// Corners are rounded, approximating an arc at the offset distance.
+    Round,
+    /// Corners are extended to a point, unless the point would be further than twice the offset
+    /// distance away, in which case they are squared off.
+    Miter,
+}
+
+/// Inflates (positive `delta`) or deflates (negative `delta`) `polygon` by `delta` units, joining
+/// corners as specified by `join_type`.
+///
+/// Deflating may split the polygon into several ones, or make it disappear entirely. Inflating
+/// may produce holes.
+#[inline]
+pub fn offset_polygon(
+    polygon: &[Vector2],
+    delta: real,
+    join_type: PolyJoinType,
+) -> Vec<Vec<Vector2>> {
+    let mut offset = ClipperOffset::new(MITER_LIMIT, ARC_TOLERANCE);
+    offset.add_path(&to_path(polygon), join_type);
+    offset
+        .execute(delta.as_f64() * SCALE_FACTOR)
+        .iter()
+        .map(|path| from_path(path))
+        .collect()
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Polygon operations on the fixed-point grid
+
+/// Number of grid points per unit.
+const SCALE_FACTOR: f64 = 100_000.0;
+
+/// Arc tolerance and miter limit of round and miter joins, in grid points and offset distances.
+const ARC_TOLERANCE: f64 = 0.25 * SCALE_FACTOR;
+const MITER_LIMIT: f64 = 2.0;
+
+fn boolean_operation(
+    clip_type: ClipType,
+    polygon_a: &[Vector2],
+    polygon_b: &[Vector2],
+) -> Vec<Vec<Vector2>> {
+    let mut clipper = Clipper::new();
+    clipper.add_path(&to_path(polygon_a), PolyType::Subject);
+    clipper.add_path(&to_path(polygon_b), PolyType::Clip);
+    clipper
+        .execute(clip_type, PolyFillType::EvenOdd, PolyFillType::EvenOdd)
+        .iter()
+        .map(|path| from_path(path))
+        .collect()
+}
+
+/// Converts to grid points, truncating like the engine.
+fn to_path(polygon: &[Vector2]) -> Vec<IntPoint> {
+    polygon
+        .iter()
+        .map(|v| {
+            IntPoint::new(
+                (v.x.as_f64() * SCALE_FACTOR) as i64,
+                (v.y.as_f64() * SCALE_FACTOR) as i64,
+            )
+        })
+        .collect()
+}
+
+/// Converts from grid points, rounding to `real` before scaling like the engine.
+fn from_path(path: &[IntPoint]) -> Vec<Vector2> {
+    path.iter()
+        .map(|pt| {
+            Vector2::new(
+                real::from_f64((pt.x as real).as_f64() / SCALE_FACTOR),
+                real::from_f64((pt.y as real).as_f64() / SCALE_FACTOR),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: real, y: real) -> Vector2 {
+        Vector2::new(x, y)
+    }
+
+    fn square(x: real, y: real, size: real) -> Vec<Vector2> {
+        vec![
+            v(x, y),
+            v(x + size, y),
+            v(x + size, y + size),
+            v(x, y + size),
+        ]
+    }
+
+    /// Compares polygons regardless of their starting vertex.
+    fn assert_polygon_eq(actual: &[Vector2], expected: &[Vector2]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+        let matches = (0..actual.len()).any(|shift| {
+            (0..actual.len())
+                .all(|i| actual[(i + shift) % actual.len()].is_equal_approx(expected[i]))
+        });
+        assert!(matches, "{actual:?} != {expected:?}");
+    }
+
+    #[test]
+    fn polygon_orientation() {
+        let mut polygon = square(0.0, 0.0, 10.0);
+        assert!(!is_polygon_clockwise(&polygon));
+        polygon.reverse();
+        assert!(is_polygon_clockwise(&polygon));
+        assert!(!is_polygon_clockwise(&polygon[..2]));
+    }
+
+    #[test]
+    fn point_in_polygon() {
+        let polygon = [
+            v(0.0, 0.0),
+            v(10.0, 0.0),
+            v(10.0, 10.0),
+            v(5.0, 4.0),
+            v(0.0, 10.0),
+        ];
+        assert!(is_point_in_polygon(v(2.0, 2.0), &polygon));
+        assert!(is_point_in_polygon(v(8.0, 6.0), &polygon));
+        assert!(!is_point_in_polygon(v(5.0, 6.0), &polygon));
+        assert!(!is_point_in_polygon(v(-1.0, 5.0), &polygon));
+        assert!(!is_point_in_polygon(v(2.0, 2.0), &polygon[..2]));
+    }
+
+    #[test]
+    fn segment_intersections() {
+        assert_eq!(
+            segment_intersects_segment(v(0.0, 0.0), v(10.0, 10.0), v(0.0, 10.0), v(10.0, 0.0)),
+            Some(v(5.0, 5.0)),
+        );
+        assert_eq!(
+            segment_intersects_segment(v(0.0, 0.0), v(10.0, 0.0), v(0.0, 1.0), v(10.0, 1.0)),
+            None,
+        );
+        assert_eq!(
+            segment_intersects_segment(v(0.0, 0.0), v(10.0, 0.0), v(2.0, 0.0), v(12.0, 0.0)),
+            None,
+        );
+        assert_eq!(
+            segment_intersects_segment(v(0.0, 0.0), v(10.0, 0.0), v(11.0, -1.0), v(11.0, 1.0)),
+            None,
+        );
+
+        let t = segment_intersects_circle(v(0.0, 0.0), v(10.0, 0.0), v(5.0, 0.0), 2.0);
+        assert!(t.unwrap().is_equal_approx(0.3));
+        let t = segment_intersects_circle(v(5.0, 0.0), v(10.0, 0.0), v(5.0, 0.0), 2.0);
+        assert!(t.unwrap().is_equal_approx(0.4));
+        assert_eq!(
+            segment_intersects_circle(v(0.0, 5.0), v(10.0, 5.0), v(5.0, 0.0), 2.0),
+            None,
+        );
+        assert_eq!(
+            segment_intersects_circle(v(4.0, 0.0), v(6.0, 0.0), v(5.0, 0.0), 2.0),
+            None,
+        );
+    }
+
+    #[test]
+    fn closest_points() {
+        let (from, to) = (v(0.0, 0.0), v(10.0, 0.0));
+        assert_eq!(closest_point_to_segment(v(5.0, 5.0), from, to), v(5.0, 0.0));
+        assert_eq!(closest_point_to_segment(v(-5.0, 5.0), from, to), from);
+        assert_eq!(closest_point_to_segment(v(15.0, 5.0), from, to), to);
+        assert_eq!(
+            closest_point_to_segment_uncapped(v(15.0, 5.0), from, to),
+            v(15.0, 0.0)
+        );
+
+        assert_eq!(
+            closest_points_between_segments(from, to, v(5.0, 5.0), v(5.0, 1.0)),
+            (v(5.0, 0.0), v(5.0, 1.0)),
+        );
+        assert_eq!(
+            closest_points_between_segments(from, to, v(12.0, 3.0), v(14.0, 1.0)),
+            (to, v(12.5, 2.5)),
+        );
+        assert_eq!(
+            closest_points_between_segments(from, to, v(1.0, 1.0), v(1.0, 1.0)),
+            (v(1.0, 0.0), v(1.0, 1.0)),
+        );
+    }
+
+    #[test]
+    fn convex_hull_of_points() {
+        let points = [
+            v(0.0, 0.0),
+            v(5.0, 5.0),
+            v(10.0, 10.0),
+            v(0.0, 10.0),
+            v(10.0, 0.0),
+            v(5.0, 0.0),
+            v(3.0, 7.0),
+        ];
+        assert_eq!(
+            convex_hull(&points),
+            vec![
+                v(0.0, 0.0),
+                v(10.0, 0.0),
+                v(10.0, 10.0),
+                v(0.0, 10.0),
+                v(0.0, 0.0)
+            ],
+        );
+    }
+
+    #[test]
+    fn triangulation() {
+        assert_eq!(
+            triangulate_polygon(&square(0.0, 0.0, 10.0)),
+            Some(vec![3, 0, 1, 1, 2, 3])
+        );
+
+        let mut clockwise = square(0.0, 0.0, 10.0);
+        clockwise.reverse();
+        assert_eq!(
+            triangulate_polygon(&clockwise),
+            Some(vec![0, 3, 2, 2, 1, 0])
+        );
+
+        // Concave, with a collinear vertex.
+        let polygon = [
+            v(0.0, 0.0),
+            v(5.0, 0.0),
+            v(10.0, 0.0),
+            v(10.0, 10.0),
+            v(5.0, 2.0),
+            v(0.0, 10.0),
+        ];
+        let triangles = triangulate_polygon(&polygon).unwrap();
+        assert_eq!(triangles.len(), 12);
+        let area: real = triangles
+            .chunks(3)
+            .map(|t| (polygon[t[1]] - polygon[t[0]]).cross(polygon[t[2]] - polygon[t[0]]) / 2.0)
+            .sum();
+        assert!(area.is_equal_approx(60.0));
+
+        assert_eq!(triangulate_polygon(&polygon[..2]), None);
+    }
+
+    #[test]
+    fn boolean_operations() {
+        let a = square(0.0, 0.0, 10.0);
+        let b = square(5.0, 5.0, 10.0);
+
+        let merged = merge_polygons(&a, &b);
+        assert_eq!(merged.len(), 1);
+        assert_polygon_eq(
+            &merged[0],
+            &[
+                v(0.0, 0.0),
+                v(10.0, 0.0),
+                v(10.0, 5.0),
+                v(15.0, 5.0),
+                v(15.0, 15.0),
+                v(5.0, 15.0),
+                v(5.0, 10.0),
+                v(0.0, 10.0),
+            ],
+        );
+
+        let intersected = intersect_polygons(&a, &b);
+        assert_eq!(intersected.len(), 1);
+        assert_polygon_eq(&intersected[0], &square(5.0, 5.0, 5.0));
+
+        let clipped = clip_polygons(&a, &b);
+        assert_eq!(clipped.len(), 1);
+        assert_polygon_eq(
+            &clipped[0],
+            &[
+                v(0.0, 0.0),
+                v(10.0, 0.0),
+                v(10.0, 5.0),
+                v(5.0, 5.0),
+                v(5.0, 10.0),
+                v(0.0, 10.0),
+            ],
+        );
+
+        let excluded = exclude_polygons(&a, &b);
+        assert_eq!(excluded.len(), 2);
+        assert!(excluded
+            .iter()
+            .all(|p| p.len() == 6 && !is_polygon_clockwise(p)));
+
+        // Orientation of the inputs doesn't matter.
+        let mut reversed = b.clone();
+        reversed.reverse();
+        assert_polygon_eq(&intersect_polygons(&a, &reversed)[0], &intersected[0]);
+
+        assert!(intersect_polygons(&a, &square(20.0, 0.0, 5.0)).is_empty());
+        assert!(clip_polygons(&square(2.0, 2.0, 5.0), &a).is_empty());
+    }
+
+    #[test]
+    fn boolean_operations_with_shared_edges() {
+        let a = square(0.0, 0.0, 10.0);
+        let b = square(10.0, 0.0, 10.0);
+
+        let merged = merge_polygons(&a, &b);
+        assert_eq!(merged.len(), 1);
+        assert_polygon_eq(
+            &merged[0],
+            &[v(0.0, 0.0), v(20.0, 0.0), v(20.0, 10.0), v(0.0, 10.0)],
+        );
+
+        assert!(intersect_polygons(&a, &b).is_empty());
+        assert_polygon_eq(&clip_polygons(&a, &b)[0], &a);
+        assert_polygon_eq(&intersect_polygons(&a, &a)[0], &a);
+    }
+
+    #[test]
+    fn boolean_operations_with_holes() {
+        let outer = square(0.0, 0.0, 30.0);
+        let inner = square(10.0, 10.0, 10.0);
+
+        let clipped = clip_polygons(&outer, &inner);
+        assert_eq!(clipped.len(), 2);
+
+        let (holes, boundaries): (Vec<_>, Vec<_>) =
+            clipped.iter().partition(|p| is_polygon_clockwise(p));
+        assert_polygon_eq(boundaries[0], &outer);
+        let mut hole = inner.clone();
+        hole.reverse();
+        assert_polygon_eq(holes[0], &hole);
+    }
+
+    #[test]
+    fn offset() {
+        let polygon = square(0.0, 0.0, 10.0);
+
+        let inflated = offset_polygon(&polygon, 1.0, PolyJoinType::Miter);
+        assert_eq!(inflated.len(), 1);
+        assert_polygon_eq(&inflated[0], &square(-1.0, -1.0, 12.0));
+
+        let deflated = offset_polygon(&polygon, -1.0, PolyJoinType::Miter);
+        assert_eq!(deflated.len(), 1);
+        assert_polygon_eq(&deflated[0], &square(1.0, 1.0, 8.0));
+
+        let mut clockwise = polygon.clone();
+        clockwise.reverse();
+        assert_polygon_eq(
+            &offset_polygon(&clockwise, 1.0, PolyJoinType::Miter)[0],
+            &inflated[0],
+        );
+
+        assert!(offset_polygon(&polygon, -6.0, PolyJoinType::Miter).is_empty());
+        assert_polygon_eq(
+            &offset_polygon(&polygon, 0.0, PolyJoinType::Square)[0],
+            &polygon,
+        );
+    }
+
+    #[test]
+    fn offset_joins() {
+        let polygon = square(0.0, 0.0, 10.0);
+
+        // Corners are cut at tan(pi / 8) from the corner's offset edges.
+        let squared = offset_polygon(&polygon, 1.0, PolyJoinType::Square);
+        assert_eq!(squared.len(), 1);
+        assert_eq!(squared[0].len(), 8);
+        for corner in [v(-1.0, -0.41421), v(-0.41421, -1.0)] {
+            assert!(squared[0].iter().any(|p| p.is_equal_approx(corner)));
+        }
+
+        let rounded = offset_polygon(&polygon, 10.0, PolyJoinType::Round);
+        assert_eq!(rounded.len(), 1);
+        assert!(rounded[0].len() > 8);
+        assert!(rounded[0]
+            .iter()
+            .all(|p| closest_point_on_square(*p).distance_to(*p) <= 10.0 + 1e-4));
+
+        // Concave corners are filled in.
+        let notched = [
+            v(0.0, 0.0),
+            v(10.0, 0.0),
+            v(10.0, 10.0),
+            v(5.0, 5.0),
+            v(0.0, 10.0),
+        ];
+        let inflated = offset_polygon(&notched, 1.0, PolyJoinType::Miter);
+        assert_eq!(inflated.len(), 1);
+        assert!(!is_polygon_clockwise(&inflated[0]));
+        assert!(!inflated[0].contains(&v(5.0, 5.0)));
+    }
+
+    fn closest_point_on_square(p: Vector2) -> Vector2 {
+        v(p.x.clamp(0.0, 10.0), p.y.clamp(0.0, 10.0))
+    }
+}
@@ -0,0 +1,3445 @@
+//! Port of Clipper 6.4.2, the version bundled with Godot 3.
+//!
+//! Only what the engine uses is ported: boolean operations on two closed paths with the default
+//! options, and offsetting of closed polygons. Both follow the original step by step, since the
+//! order of the resulting polygons, their orientation and the vertex each of them starts at all
+//! depend on the exact course of the sweep. Coordinates beyond the range Clipper supports, which
+//! make the original throw, produce no polygons.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::f64::consts::{PI, TAU};
+
+use super::PolyJoinType;
+
+/// Largest coordinate Clipper accepts.
+const HI_RANGE: i64 = 0x3FFF_FFFF_FFFF_FFFF;
+
+/// `Dx` of horizontal edges.
+const HORIZONTAL: f64 = -1.0e40;
+const TOLERANCE: f64 = 1.0e-20;
+const DEF_ARC_TOLERANCE: f64 = 0.25;
+
+/// `OutIdx` of edges which don't contribute to an output polygon.
+const UNASSIGNED: i32 = -1;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct IntPoint {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl IntPoint {
+    pub(super) fn new(x: i64, y: i64) -> Self {
+        IntPoint { x, y }
+    }
+}
+
+pub(super) type Path = Vec<IntPoint>;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum ClipType {
+    Intersection,
+    Union,
+    Difference,
+    Xor,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum PolyType {
+    Subject,
+    Clip,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum PolyFillType {
+    EvenOdd,
+    Positive,
+    Negative,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum EdgeSide {
+    Left,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Direction {
+    RightToLeft,
+    LeftToRight,
+}
+
+/// Execution stopped where the original gives up or throws.
+#[derive(Debug)]
+struct Failed;
+
+/// `TEdge`. Links to other edges are indices into [`Clipper::edges`].
+#[derive(Clone, Debug)]
+struct Edge {
+    bot: IntPoint,
+    curr: IntPoint,
+    top: IntPoint,
+    dx: f64,
+    poly_type: PolyType,
+    side: EdgeSide,
+    wind_delta: i32,
+    wind_cnt: i32,
+    wind_cnt2: i32,
+    out_idx: i32,
+    next: usize,
+    prev: usize,
+    next_in_lml: Option<usize>,
+    next_in_ael: Option<usize>,
+    prev_in_ael: Option<usize>,
+    next_in_sel: Option<usize>,
+    prev_in_sel: Option<usize>,
+}
+
+impl Edge {
+    fn is_horizontal(&self) -> bool {
+        self.dx == HORIZONTAL
+    }
+
+    fn top_x(&self, current_y: i64) -> i64 {
+        if current_y == self.top.y {
+            self.top.x
+        } else {
+            self.bot.x + round(self.dx * (current_y - self.bot.y) as f64)
+        }
+    }
+
+    fn horz_direction(&self) -> (Direction, i64, i64) {
+        if self.bot.x < self.top.x {
+            (Direction::LeftToRight, self.bot.x, self.top.x)
+        } else {
+            (Direction::RightToLeft, self.top.x, self.bot.x)
+        }
+    }
+
+    fn reverse_horizontal(&mut self) {
+        std::mem::swap(&mut self.top.x, &mut self.bot.x);
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct LocalMinimum {
+    y: i64,
+    left_bound: usize,
+    right_bound: usize,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct IntersectNode {
+    edge1: usize,
+    edge2: usize,
+    pt: IntPoint,
+}
+
+/// A polygon of the solution. Links to points are indices into [`Clipper::out_pts`].
+#[derive(Clone, Debug)]
+struct OutRec {
+    idx: usize,
+    is_hole: bool,
+    first_left: Option<usize>,
+    pts: Option<usize>,
+    bottom_pt: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct OutPt {
+    idx: usize,
+    pt: IntPoint,
+    next: usize,
+    prev: usize,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Join {
+    out_pt1: usize,
+    out_pt2: usize,
+    off_pt: IntPoint,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct IntRect {
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+}
+
+pub(super) struct Clipper {
+    edges: Vec<Edge>,
+    minima_list: Vec<LocalMinimum>,
+    current_lm: usize,
+    scanbeam: BinaryHeap<i64>,
+    active_edges: Option<usize>,
+    sorted_edges: Option<usize>,
+    intersect_list: Vec<IntersectNode>,
+    poly_outs: Vec<OutRec>,
+    out_pts: Vec<OutPt>,
+    joins: Vec<Join>,
+    ghost_joins: Vec<Join>,
+    clip_type: ClipType,
+    subj_fill_type: PolyFillType,
+    clip_fill_type: PolyFillType,
+    reverse_output: bool,
+    out_of_range: bool,
+}
+
+impl Clipper {
+    pub(super) fn new() -> Self {
+        Clipper {
+            edges: Vec::new(),
+            minima_list: Vec::new(),
+            current_lm: 0,
+            scanbeam: BinaryHeap::new(),
+            active_edges: None,
+            sorted_edges: None,
+            intersect_list: Vec::new(),
+            poly_outs: Vec::new(),
+            out_pts: Vec::new(),
+            joins: Vec::new(),
+            ghost_joins: Vec::new(),
+            clip_type: ClipType::Union,
+            subj_fill_type: PolyFillType::EvenOdd,
+            clip_fill_type: PolyFillType::EvenOdd,
+            reverse_output: false,
+            out_of_range: false,
+        }
+    }
+
+    /// `ClipperBase::AddPath` for closed paths.
+    pub(super) fn add_path(&mut self, path: &[IntPoint], poly_type: PolyType) -> bool {
+        let mut high_i = path.len() as isize - 1;
+        while high_i > 0 && path[high_i as usize] == path[0] {
+            high_i -= 1;
+        }
+        while high_i > 0 && path[high_i as usize] == path[high_i as usize - 1] {
+            high_i -= 1;
+        }
+        if high_i < 2 {
+            return false;
+        }
+
+        let len = high_i as usize + 1;
+        if path[..len]
+            .iter()
+            .any(|pt| pt.x > HI_RANGE || pt.y > HI_RANGE || -pt.x > HI_RANGE || -pt.y > HI_RANGE)
+        {
+            self.out_of_range = true;
+            return false;
+        }
+
+        // 1. Basic (first) edge initialization.
+        let base = self.edges.len();
+        for (i, &pt) in path[..len].iter().enumerate() {
+            self.edges.push(Edge {
+                bot: IntPoint::default(),
+                curr: pt,
+                top: IntPoint::default(),
+                dx: 0.0,
+                poly_type: PolyType::Subject,
+                side: EdgeSide::Left,
+                wind_delta: 0,
+                wind_cnt: 0,
+                wind_cnt2: 0,
+                out_idx: UNASSIGNED,
+                next: base + (i + 1) % len,
+                prev: base + (i + len - 1) % len,
+                next_in_lml: None,
+                next_in_ael: None,
+                prev_in_ael: None,
+                next_in_sel: None,
+                prev_in_sel: None,
+            });
+        }
+
+        // 2. Remove duplicate vertices and collinear edges.
+        let mut e_start = base;
+        let mut e = e_start;
+        let mut e_loop_stop = e_start;
+        loop {
+            let (prev, next) = (self.edges[e].prev, self.edges[e].next);
+            if self.edges[e].curr == self.edges[next].curr {
+                if e == next {
+                    break;
+                }
+                if e == e_start {
+                    e_start = next;
+                }
+                e = self.remove_edge(e);
+                e_loop_stop = e;
+                continue;
+            }
+            if prev == next {
+                break;
+            } else if slopes_equal3(
+                self.edges[prev].curr,
+                self.edges[e].curr,
+                self.edges[next].curr,
+            ) {
+                if e == e_start {
+                    e_start = next;
+                }
+                e = self.remove_edge(e);
+                e = self.edges[e].prev;
+                e_loop_stop = e;
+                continue;
+            }
+            e = next;
+            if e == e_loop_stop {
+                break;
+            }
+        }
+
+        if self.edges[e].prev == self.edges[e].next {
+            self.edges.truncate(base);
+            return false;
+        }
+
+        // 3. Second stage of edge initialization.
+        let mut is_flat = true;
+        e = e_start;
+        loop {
+            self.init_edge2(e, poly_type);
+            e = self.edges[e].next;
+            if is_flat && self.edges[e].curr.y != self.edges[e_start].curr.y {
+                is_flat = false;
+            }
+            if e == e_start {
+                break;
+            }
+        }
+
+        if is_flat {
+            self.edges.truncate(base);
+            return false;
+        }
+
+        // 4. Add the bounds to the list of local minima.
+        let mut e_min = None;
+        loop {
+            e = self.find_next_loc_min(e);
+            if Some(e) == e_min {
+                break;
+            } else if e_min.is_none() {
+                e_min = Some(e);
+            }
+
+            // `e` and its predecessor share a local minimum. Compare their slopes to find which
+            // starts which bound.
+            let prev = self.edges[e].prev;
+            let y = self.edges[e].bot.y;
+            let (left_bound, right_bound, left_bound_is_forward) =
+                if self.edges[e].dx < self.edges[prev].dx {
+                    (prev, e, false)
+                } else {
+                    (e, prev, true)
+                };
+
+            let wind_delta = if self.edges[left_bound].next == right_bound {
+                -1
+            } else {
+                1
+            };
+            self.edges[left_bound].wind_delta = wind_delta;
+            self.edges[right_bound].wind_delta = -wind_delta;
+
+            e = self.process_bound(left_bound, left_bound_is_forward);
+            let e2 = self.process_bound(right_bound, !left_bound_is_forward);
+
+            self.minima_list.push(LocalMinimum {
+                y,
+                left_bound,
+                right_bound,
+            });
+            if !left_bound_is_forward {
+                e = e2;
+            }
+        }
+
+        true
+    }
+
+    /// `Clipper::Execute`, returning the solution's polygons.
+    pub(super) fn execute(
+        &mut self,
+        clip_type: ClipType,
+        subj_fill_type: PolyFillType,
+        clip_fill_type: PolyFillType,
+    ) -> Vec<Path> {
+        self.clip_type = clip_type;
+        self.subj_fill_type = subj_fill_type;
+        self.clip_fill_type = clip_fill_type;
+
+        if self.out_of_range || self.execute_internal().is_err() {
+            return Vec::new();
+        }
+
+        self.build_result()
+    }
+
+    fn remove_edge(&mut self, e: usize) -> usize {
+        let (prev, next) = (self.edges[e].prev, self.edges[e].next);
+        self.edges[prev].next = next;
+        self.edges[next].prev = prev;
+        next
+    }
+
+    fn init_edge2(&mut self, e: usize, poly_type: PolyType) {
+        let next_curr = self.edges[self.edges[e].next].curr;
+        let edge = &mut self.edges[e];
+        if edge.curr.y >= next_curr.y {
+            edge.bot = edge.curr;
+            edge.top = next_curr;
+        } else {
+            edge.top = edge.curr;
+            edge.bot = next_curr;
+        }
+        let dy = edge.top.y - edge.bot.y;
+        edge.dx = if dy == 0 {
+            HORIZONTAL
+        } else {
+            (edge.top.x - edge.bot.x) as f64 / dy as f64
+        };
+        edge.poly_type = poly_type;
+    }
+
+    fn find_next_loc_min(&self, mut e: usize) -> usize {
+        let edges = &self.edges;
+        loop {
+            while edges[e].bot != edges[edges[e].prev].bot || edges[e].curr == edges[e].top {
+                e = edges[e].next;
+            }
+            if !edges[e].is_horizontal() && !edges[edges[e].prev].is_horizontal() {
+                break;
+            }
+            while edges[edges[e].prev].is_horizontal() {
+                e = edges[e].prev;
+            }
+            let e2 = e;
+            while edges[e].is_horizontal() {
+                e = edges[e].next;
+            }
+            if edges[e].top.y == edges[edges[e].prev].bot.y {
+                // Just an intermediate horizontal.
+                continue;
+            }
+            if edges[edges[e2].prev].bot.x < edges[e].bot.x {
+                e = e2;
+            }
+            break;
+        }
+        e
+    }
+
+    fn process_bound(&mut self, mut e: usize, next_is_forward: bool) -> usize {
+        let edges = &mut self.edges;
+        let mut result = e;
+
+        if edges[e].is_horizontal() {
+            // Consecutive horizontals may start heading left before going right.
+            let e_start = if next_is_forward {
+                edges[e].prev
+            } else {
+                edges[e].next
+            };
+            if edges[e_start].is_horizontal() {
+                if edges[e_start].bot.x != edges[e].bot.x && edges[e_start].top.x != edges[e].bot.x
+                {
+                    edges[e].reverse_horizontal();
+                }
+            } else if edges[e_start].bot.x != edges[e].bot.x {
+                edges[e].reverse_horizontal();
+            }
+        }
+
+        let e_start = e;
+        if next_is_forward {
+            while edges[result].top.y == edges[edges[result].next].bot.y {
+                result = edges[result].next;
+            }
+            if edges[result].is_horizontal() {
+                // At the top of a bound, horizontals are added to the bound only when the
+                // preceding edge attaches to the horizontal's left vertex.
+                let mut horz = result;
+                while edges[edges[horz].prev].is_horizontal() {
+                    horz = edges[horz].prev;
+                }
+                if edges[edges[horz].prev].top.x > edges[edges[result].next].top.x {
+                    result = edges[horz].prev;
+                }
+            }
+            while e != result {
+                edges[e].next_in_lml = Some(edges[e].next);
+                if edges[e].is_horizontal()
+                    && e != e_start
+                    && edges[e].bot.x != edges[edges[e].prev].top.x
+                {
+                    edges[e].reverse_horizontal();
+                }
+                e = edges[e].next;
+            }
+            if edges[e].is_horizontal()
+                && e != e_start
+                && edges[e].bot.x != edges[edges[e].prev].top.x
+            {
+                edges[e].reverse_horizontal();
+            }
+            // Move to the edge just beyond the current bound.
+            edges[result].next
+        } else {
+            while edges[result].top.y == edges[edges[result].prev].bot.y {
+                result = edges[result].prev;
+            }
+            if edges[result].is_horizontal() {
+                let mut horz = result;
+                while edges[edges[horz].next].is_horizontal() {
+                    horz = edges[horz].next;
+                }
+                if edges[edges[horz].next].top.x >= edges[edges[result].prev].top.x {
+                    result = edges[horz].next;
+                }
+            }
+            while e != result {
+                edges[e].next_in_lml = Some(edges[e].prev);
+                if edges[e].is_horizontal()
+                    && e != e_start
+                    && edges[e].bot.x != edges[edges[e].next].top.x
+                {
+                    edges[e].reverse_horizontal();
+                }
+                e = edges[e].prev;
+            }
+            if edges[e].is_horizontal()
+                && e != e_start
+                && edges[e].bot.x != edges[edges[e].next].top.x
+            {
+                edges[e].reverse_horizontal();
+            }
+            edges[result].prev
+        }
+    }
+
+    /// `ClipperBase::GetBounds`.
+    fn get_bounds(&self) -> IntRect {
+        let edges = &self.edges;
+        let first = match self.minima_list.first() {
+            Some(lm) => edges[lm.left_bound].bot,
+            None => {
+                return IntRect {
+                    left: 0,
+                    top: 0,
+                    right: 0,
+                    bottom: 0,
+                }
+            }
+        };
+
+        let mut result = IntRect {
+            left: first.x,
+            top: first.y,
+            right: first.x,
+            bottom: first.y,
+        };
+        for lm in &self.minima_list {
+            result.bottom = result.bottom.max(edges[lm.left_bound].bot.y);
+            let mut e = lm.left_bound;
+            loop {
+                let bottom_e = e;
+                while let Some(next) = edges[e].next_in_lml {
+                    result.left = result.left.min(edges[e].bot.x);
+                    result.right = result.right.max(edges[e].bot.x);
+                    e = next;
+                }
+                result.left = result.left.min(edges[e].bot.x);
+                result.right = result.right.max(edges[e].bot.x);
+                result.left = result.left.min(edges[e].top.x);
+                result.right = result.right.max(edges[e].top.x);
+                result.top = result.top.min(edges[e].top.y);
+                if bottom_e == lm.left_bound {
+                    e = lm.right_bound;
+                } else {
+                    break;
+                }
+            }
+        }
+        result
+    }
+
+    fn reset(&mut self) {
+        self.current_lm = 0;
+        if self.minima_list.is_empty() {
+            return;
+        }
+        std_sort(&mut self.minima_list, |a, b| b.y < a.y);
+
+        self.scanbeam.clear();
+        for i in 0..self.minima_list.len() {
+            let lm = self.minima_list[i];
+            self.scanbeam.push(lm.y);
+            for (e, side) in [
+                (lm.left_bound, EdgeSide::Left),
+                (lm.right_bound, EdgeSide::Right),
+            ] {
+                let edge = &mut self.edges[e];
+                edge.curr = edge.bot;
+                edge.side = side;
+                edge.out_idx = UNASSIGNED;
+            }
+        }
+        self.active_edges = None;
+        self.current_lm = 0;
+    }
+
+    fn execute_internal(&mut self) -> Result<(), Failed> {
+        self.reset();
+        self.sorted_edges = None;
+
+        let bot_y = self.pop_scanbeam().ok_or(Failed)?;
+        self.insert_local_minima_into_ael(bot_y);
+        while let Some(top_y) = self.pop_scanbeam() {
+            self.process_horizontals();
+            self.ghost_joins.clear();
+            self.process_intersections(top_y)?;
+            self.process_edges_at_top_of_scanbeam(top_y)?;
+            self.insert_local_minima_into_ael(top_y);
+        }
+
+        // Fix orientations.
+        for i in 0..self.poly_outs.len() {
+            if let Some(pts) = self.poly_outs[i].pts {
+                if (self.poly_outs[i].is_hole ^ self.reverse_output) == (self.area(pts) > 0.0) {
+                    self.reverse_poly_pt_links(pts);
+                }
+            }
+        }
+
+        if !self.joins.is_empty() {
+            self.join_common_edges();
+        }
+
+        // FixupOutPolygon() must be done after JoinCommonEdges().
+        for i in 0..self.poly_outs.len() {
+            if self.poly_outs[i].pts.is_some() {
+                self.fixup_out_polygon(i);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pop_scanbeam(&mut self) -> Option<i64> {
+        let y = self.scanbeam.pop()?;
+        while self.scanbeam.peek() == Some(&y) {
+            self.scanbeam.pop();
+        }
+        Some(y)
+    }
+
+    fn pop_local_minima(&mut self, y: i64) -> Option<LocalMinimum> {
+        let lm = *self.minima_list.get(self.current_lm)?;
+        if lm.y != y {
+            return None;
+        }
+        self.current_lm += 1;
+        Some(lm)
+    }
+
+    fn insert_local_minima_into_ael(&mut self, bot_y: i64) {
+        while let Some(lm) = self.pop_local_minima(bot_y) {
+            let (lb, rb) = (lm.left_bound, lm.right_bound);
+
+            self.insert_edge_into_ael(lb, None);
+            self.insert_edge_into_ael(rb, Some(lb));
+            self.set_winding_count(lb);
+            self.edges[rb].wind_cnt = self.edges[lb].wind_cnt;
+            self.edges[rb].wind_cnt2 = self.edges[lb].wind_cnt2;
+
+            let mut op1 = None;
+            if self.is_contributing(lb) {
+                op1 = Some(self.add_local_min_poly(lb, rb, self.edges[lb].bot));
+            }
+            self.scanbeam.push(self.edges[lb].top.y);
+
+            if self.edges[rb].is_horizontal() {
+                self.add_edge_to_sel(rb);
+                if let Some(next) = self.edges[rb].next_in_lml {
+                    self.scanbeam.push(self.edges[next].top.y);
+                }
+            } else {
+                self.scanbeam.push(self.edges[rb].top.y);
+            }
+
+            let op1 = match op1 {
+                Some(op1) => op1,
+                None => {
+                    // Neither bound contributes, but they may still cross the edges between them.
+                    self.intersect_bounds(lb, rb);
+                    continue;
+                }
+            };
+
+            // If output polygons share an edge, they'll need joining later.
+            if self.edges[rb].is_horizontal() {
+                for i in 0..self.ghost_joins.len() {
+                    let jr = self.ghost_joins[i];
+                    // Convert ghost joins overlapping the horizontal into real ones.
+                    if horz_segments_overlap(
+                        self.out_pts[jr.out_pt1].pt.x,
+                        jr.off_pt.x,
+                        self.edges[rb].bot.x,
+                        self.edges[rb].top.x,
+                    ) {
+                        self.add_join(jr.out_pt1, op1, jr.off_pt);
+                    }
+                }
+            }
+
+            if let Some(prev) = self.edges[lb].prev_in_ael {
+                let (lb_edge, prev_edge) = (&self.edges[lb], &self.edges[prev]);
+                if lb_edge.out_idx >= 0
+                    && prev_edge.curr.x == lb_edge.bot.x
+                    && prev_edge.out_idx >= 0
+                    && slopes_equal4(prev_edge.bot, prev_edge.top, lb_edge.curr, lb_edge.top)
+                {
+                    let (bot, top) = (lb_edge.bot, lb_edge.top);
+                    let op2 = self.add_out_pt(prev, bot);
+                    self.add_join(op1, op2, top);
+                }
+            }
+
+            if self.edges[lb].next_in_ael != Some(rb) {
+                if let Some(prev) = self.edges[rb].prev_in_ael {
+                    let (rb_edge, prev_edge) = (&self.edges[rb], &self.edges[prev]);
+                    if rb_edge.out_idx >= 0
+                        && prev_edge.out_idx >= 0
+                        && slopes_equal4(prev_edge.curr, prev_edge.top, rb_edge.curr, rb_edge.top)
+                    {
+                        let (bot, top) = (rb_edge.bot, rb_edge.top);
+                        let op2 = self.add_out_pt(prev, bot);
+                        self.add_join(op1, op2, top);
+                    }
+                }
+
+                self.intersect_bounds(lb, rb);
+            }
+        }
+    }
+
+    /// Intersects the right bound of a new local minimum with the edges between its bounds.
+    fn intersect_bounds(&mut self, lb: usize, rb: usize) {
+        let pt = self.edges[lb].curr;
+        let mut e = self.edges[lb].next_in_ael;
+        while let Some(edge) = e {
+            if edge == rb {
+                break;
+            }
+            // IntersectEdges() assumes that its first edge is to the right of the second one
+            // above the intersection.
+            self.intersect_edges(rb, edge, pt);
+            e = self.edges[edge].next_in_ael;
+        }
+    }
+
+    fn insert_edge_into_ael(&mut self, edge: usize, start_edge: Option<usize>) {
+        let active = match self.active_edges {
+            Some(active) => active,
+            None => {
+                self.edges[edge].prev_in_ael = None;
+                self.edges[edge].next_in_ael = None;
+                self.active_edges = Some(edge);
+                return;
+            }
+        };
+
+        if start_edge.is_none() && self.e2_inserts_before_e1(active, edge) {
+            self.edges[edge].prev_in_ael = None;
+            self.edges[edge].next_in_ael = Some(active);
+            self.edges[active].prev_in_ael = Some(edge);
+            self.active_edges = Some(edge);
+        } else {
+            let mut start = start_edge.unwrap_or(active);
+            while let Some(next) = self.edges[start].next_in_ael {
+                if self.e2_inserts_before_e1(next, edge) {
+                    break;
+                }
+                start = next;
+            }
+            let next = self.edges[start].next_in_ael;
+            self.edges[edge].next_in_ael = next;
+            if let Some(next) = next {
+                self.edges[next].prev_in_ael = Some(edge);
+            }
+            self.edges[edge].prev_in_ael = Some(start);
+            self.edges[start].next_in_ael = Some(edge);
+        }
+    }
+
+    fn e2_inserts_before_e1(&self, e1: usize, e2: usize) -> bool {
+        let (e1, e2) = (&self.edges[e1], &self.edges[e2]);
+        if e2.curr.x == e1.curr.x {
+            if e2.top.y > e1.top.y {
+                e2.top.x < e1.top_x(e2.top.y)
+            } else {
+                e1.top.x > e2.top_x(e1.top.y)
+            }
+        } else {
+            e2.curr.x < e1.curr.x
+        }
+    }
+
+    fn fill_types(&self, poly_type: PolyType) -> (PolyFillType, PolyFillType) {
+        match poly_type {
+            PolyType::Subject => (self.subj_fill_type, self.clip_fill_type),
+            PolyType::Clip => (self.clip_fill_type, self.subj_fill_type),
+        }
+    }
+
+    fn set_winding_count(&mut self, edge: usize) {
+        let poly_type = self.edges[edge].poly_type;
+        let (fill_type, alt_fill_type) = self.fill_types(poly_type);
+
+        // Find the edge of the same poly type that immediately precedes `edge` in the AEL.
+        let mut e = self.edges[edge].prev_in_ael;
+        while let Some(prev) = e {
+            if self.edges[prev].poly_type == poly_type {
+                break;
+            }
+            e = self.edges[prev].prev_in_ael;
+        }
+
+        let wind_delta = self.edges[edge].wind_delta;
+        let mut e = match e {
+            None => {
+                self.edges[edge].wind_cnt = wind_delta;
+                self.edges[edge].wind_cnt2 = 0;
+                self.active_edges
+            }
+            Some(e) => {
+                let prev = &self.edges[e];
+                let wind_cnt = if fill_type == PolyFillType::EvenOdd {
+                    wind_delta
+                } else if prev.wind_cnt * prev.wind_delta < 0 {
+                    // The previous edge decreases the winding count towards zero, so we're
+                    // outside of its polygon.
+                    if prev.wind_cnt.abs() > 1 {
+                        if prev.wind_delta * wind_delta < 0 {
+                            prev.wind_cnt
+                        } else {
+                            prev.wind_cnt + wind_delta
+                        }
+                    } else {
+                        wind_delta
+                    }
+                } else if prev.wind_delta * wind_delta < 0 {
+                    // Inside the previous polygon, but reversing its direction.
+                    prev.wind_cnt
+                } else {
+                    prev.wind_cnt + wind_delta
+                };
+                let wind_cnt2 = prev.wind_cnt2;
+                let next = prev.next_in_ael;
+                self.edges[edge].wind_cnt = wind_cnt;
+                self.edges[edge].wind_cnt2 = wind_cnt2;
+                next
+            }
+        };
+
+        // Update the winding count of the other poly type.
+        while let Some(other) = e {
+            if other == edge {
+                break;
+            }
+            let other_wind_delta = self.edges[other].wind_delta;
+            let current = &mut self.edges[edge];
+            if alt_fill_type == PolyFillType::EvenOdd {
+                current.wind_cnt2 = if current.wind_cnt2 == 0 { 1 } else { 0 };
+            } else {
+                current.wind_cnt2 += other_wind_delta;
+            }
+            e = self.edges[other].next_in_ael;
+        }
+    }
+
+    fn is_contributing(&self, edge: usize) -> bool {
+        let edge = &self.edges[edge];
+        let (fill_type, alt_fill_type) = self.fill_types(edge.poly_type);
+
+        match fill_type {
+            PolyFillType::EvenOdd => {}
+            PolyFillType::Positive => {
+                if edge.wind_cnt != 1 {
+                    return false;
+                }
+            }
+            PolyFillType::Negative => {
+                if edge.wind_cnt != -1 {
+                    return false;
+                }
+            }
+        }
+
+        let inside_other = match alt_fill_type {
+            PolyFillType::EvenOdd => edge.wind_cnt2 != 0,
+            PolyFillType::Positive => edge.wind_cnt2 > 0,
+            PolyFillType::Negative => edge.wind_cnt2 < 0,
+        };
+        let outside_other = match alt_fill_type {
+            PolyFillType::EvenOdd => edge.wind_cnt2 == 0,
+            PolyFillType::Positive => edge.wind_cnt2 <= 0,
+            PolyFillType::Negative => edge.wind_cnt2 >= 0,
+        };
+
+        match self.clip_type {
+            ClipType::Intersection => inside_other,
+            ClipType::Union => outside_other,
+            ClipType::Difference => match edge.poly_type {
+                PolyType::Subject => outside_other,
+                PolyType::Clip => inside_other,
+            },
+            ClipType::Xor => true,
+        }
+    }
+
+    fn add_edge_to_sel(&mut self, edge: usize) {
+        // The SEL links are reused to build a list of horizontal edges, whose order doesn't
+        // matter.
+        self.edges[edge].prev_in_sel = None;
+        self.edges[edge].next_in_sel = self.sorted_edges;
+        if let Some(sorted) = self.sorted_edges {
+            self.edges[sorted].prev_in_sel = Some(edge);
+        }
+        self.sorted_edges = Some(edge);
+    }
+
+    fn pop_edge_from_sel(&mut self) -> Option<usize> {
+        let edge = self.sorted_edges?;
+        self.delete_from_sel(edge);
+        Some(edge)
+    }
+
+    fn copy_ael_to_sel(&mut self) {
+        let mut e = self.active_edges;
+        self.sorted_edges = e;
+        while let Some(edge) = e {
+            let edge = &mut self.edges[edge];
+            edge.prev_in_sel = edge.prev_in_ael;
+            edge.next_in_sel = edge.next_in_ael;
+            e = edge.next_in_ael;
+        }
+    }
+
+    fn delete_from_ael(&mut self, e: usize) {
+        let (prev, next) = (self.edges[e].prev_in_ael, self.edges[e].next_in_ael);
+        if prev.is_none() && next.is_none() && Some(e) != self.active_edges {
+            // Already deleted.
+            return;
+        }
+        match prev {
+            Some(prev) => self.edges[prev].next_in_ael = next,
+            None => self.active_edges = next,
+        }
+        if let Some(next) = next {
+            self.edges[next].prev_in_ael = prev;
+        }
+        self.edges[e].next_in_ael = None;
+        self.edges[e].prev_in_ael = None;
+    }
+
+    fn delete_from_sel(&mut self, e: usize) {
+        let (prev, next) = (self.edges[e].prev_in_sel, self.edges[e].next_in_sel);
+        if prev.is_none() && next.is_none() && Some(e) != self.sorted_edges {
+            // Already deleted.
+            return;
+        }
+        match prev {
+            Some(prev) => self.edges[prev].next_in_sel = next,
+            None => self.sorted_edges = next,
+        }
+        if let Some(next) = next {
+            self.edges[next].prev_in_sel = prev;
+        }
+        self.edges[e].next_in_sel = None;
+        self.edges[e].prev_in_sel = None;
+    }
+
+    fn swap_positions_in_ael(&mut self, edge1: usize, edge2: usize) {
+        // Check that neither edge has already been removed from the AEL.
+        if self.edges[edge1].next_in_ael == self.edges[edge1].prev_in_ael
+            || self.edges[edge2].next_in_ael == self.edges[edge2].prev_in_ael
+        {
+            return;
+        }
+
+        let edges = &mut self.edges;
+        if edges[edge1].next_in_ael == Some(edge2) {
+            let next = edges[edge2].next_in_ael;
+            if let Some(next) = next {
+                edges[next].prev_in_ael = Some(edge1);
+            }
+            let prev = edges[edge1].prev_in_ael;
+            if let Some(prev) = prev {
+                edges[prev].next_in_ael = Some(edge2);
+            }
+            edges[edge2].prev_in_ael = prev;
+            edges[edge2].next_in_ael = Some(edge1);
+            edges[edge1].prev_in_ael = Some(edge2);
+            edges[edge1].next_in_ael = next;
+        } else if edges[edge2].next_in_ael == Some(edge1) {
+            let next = edges[edge1].next_in_ael;
+            if let Some(next) = next {
+                edges[next].prev_in_ael = Some(edge2);
+            }
+            let prev = edges[edge2].prev_in_ael;
+            if let Some(prev) = prev {
+                edges[prev].next_in_ael = Some(edge1);
+            }
+            edges[edge1].prev_in_ael = prev;
+            edges[edge1].next_in_ael = Some(edge2);
+            edges[edge2].prev_in_ael = Some(edge1);
+            edges[edge2].next_in_ael = next;
+        } else {
+            let next = edges[edge1].next_in_ael;
+            let prev = edges[edge1].prev_in_ael;
+            edges[edge1].next_in_ael = edges[edge2].next_in_ael;
+            if let Some(n) = edges[edge1].next_in_ael {
+                edges[n].prev_in_ael = Some(edge1);
+            }
+            edges[edge1].prev_in_ael = edges[edge2].prev_in_ael;
+            if let Some(p) = edges[edge1].prev_in_ael {
+                edges[p].next_in_ael = Some(edge1);
+            }
+            edges[edge2].next_in_ael = next;
+            if let Some(n) = next {
+                edges[n].prev_in_ael = Some(edge2);
+            }
+            edges[edge2].prev_in_ael = prev;
+            if let Some(p) = prev {
+                edges[p].next_in_ael = Some(edge2);
+            }
+        }
+
+        if edges[edge1].prev_in_ael.is_none() {
+            self.active_edges = Some(edge1);
+        } else if edges[edge2].prev_in_ael.is_none() {
+            self.active_edges = Some(edge2);
+        }
+    }
+
+    fn swap_positions_in_sel(&mut self, edge1: usize, edge2: usize) {
+        let edges = &mut self.edges;
+        if edges[edge1].next_in_sel.is_none() && edges[edge1].prev_in_sel.is_none() {
+            return;
+        }
+        if edges[edge2].next_in_sel.is_none() && edges[edge2].prev_in_sel.is_none() {
+            return;
+        }
+
+        if edges[edge1].next_in_sel == Some(edge2) {
+            let next = edges[edge2].next_in_sel;
+            if let Some(next) = next {
+                edges[next].prev_in_sel = Some(edge1);
+            }
+            let prev = edges[edge1].prev_in_sel;
+            if let Some(prev) = prev {
+                edges[prev].next_in_sel = Some(edge2);
+            }
+            edges[edge2].prev_in_sel = prev;
+            edges[edge2].next_in_sel = Some(edge1);
+            edges[edge1].prev_in_sel = Some(edge2);
+            edges[edge1].next_in_sel = next;
+        } else if edges[edge2].next_in_sel == Some(edge1) {
+            let next = edges[edge1].next_in_sel;
+            if let Some(next) = next {
+                edges[next].prev_in_sel = Some(edge2);
+            }
+            let prev = edges[edge2].prev_in_sel;
+            if let Some(prev) = prev {
+                edges[prev].next_in_sel = Some(edge1);
+            }
+            edges[edge1].prev_in_sel = prev;
+            edges[edge1].next_in_sel = Some(edge2);
+            edges[edge2].prev_in_sel = Some(edge1);
+            edges[edge2].next_in_sel = next;
+        } else {
+            let next = edges[edge1].next_in_sel;
+            let prev = edges[edge1].prev_in_sel;
+            edges[edge1].next_in_sel = edges[edge2].next_in_sel;
+            if let Some(n) = edges[edge1].next_in_sel {
+                edges[n].prev_in_sel = Some(edge1);
+            }
+            edges[edge1].prev_in_sel = edges[edge2].prev_in_sel;
+            if let Some(p) = edges[edge1].prev_in_sel {
+                edges[p].next_in_sel = Some(edge1);
+            }
+            edges[edge2].next_in_sel = next;
+            if let Some(n) = next {
+                edges[n].prev_in_sel = Some(edge2);
+            }
+            edges[edge2].prev_in_sel = prev;
+            if let Some(p) = prev {
+                edges[p].next_in_sel = Some(edge2);
+            }
+        }
+
+        if edges[edge1].prev_in_sel.is_none() {
+            self.sorted_edges = Some(edge1);
+        } else if edges[edge2].prev_in_sel.is_none() {
+            self.sorted_edges = Some(edge2);
+        }
+    }
+
+    /// Replaces `e` in the AEL with the next edge of its bound, and returns that edge.
+    fn update_edge_into_ael(&mut self, e: usize) -> usize {
+        let next = self.edges[e]
+            .next_in_lml
+            .expect("edge should not be the last of its bound");
+        let edge = self.edges[e].clone();
+
+        match edge.prev_in_ael {
+            Some(prev) => self.edges[prev].next_in_ael = Some(next),
+            None => self.active_edges = Some(next),
+        }
+        if let Some(ael_next) = edge.next_in_ael {
+            self.edges[ael_next].prev_in_ael = Some(next);
+        }
+
+        let next_edge = &mut self.edges[next];
+        next_edge.out_idx = edge.out_idx;
+        next_edge.side = edge.side;
+        next_edge.wind_delta = edge.wind_delta;
+        next_edge.wind_cnt = edge.wind_cnt;
+        next_edge.wind_cnt2 = edge.wind_cnt2;
+        next_edge.curr = next_edge.bot;
+        next_edge.prev_in_ael = edge.prev_in_ael;
+        next_edge.next_in_ael = edge.next_in_ael;
+        if !next_edge.is_horizontal() {
+            let top_y = next_edge.top.y;
+            self.scanbeam.push(top_y);
+        }
+        next
+    }
+
+    fn get_next_in_ael(&self, e: usize, dir: Direction) -> Option<usize> {
+        match dir {
+            Direction::LeftToRight => self.edges[e].next_in_ael,
+            Direction::RightToLeft => self.edges[e].prev_in_ael,
+        }
+    }
+
+    fn get_maxima_pair(&self, e: usize) -> Option<usize> {
+        let edges = &self.edges;
+        let (next, prev) = (edges[e].next, edges[e].prev);
+        if edges[next].top == edges[e].top && edges[next].next_in_lml.is_none() {
+            Some(next)
+        } else if edges[prev].top == edges[e].top && edges[prev].next_in_lml.is_none() {
+            Some(prev)
+        } else {
+            None
+        }
+    }
+
+    /// Like `get_maxima_pair`, but ignores pairs which aren't in the AEL, unless horizontal.
+    fn get_maxima_pair_ex(&self, e: usize) -> Option<usize> {
+        self.get_maxima_pair(e).filter(|&pair| {
+            let pair = &self.edges[pair];
+            pair.next_in_ael != pair.prev_in_ael || pair.is_horizontal()
+        })
+    }
+
+    fn is_maxima(&self, e: usize, y: i64) -> bool {
+        self.edges[e].top.y == y && self.edges[e].next_in_lml.is_none()
+    }
+
+    fn is_intermediate(&self, e: usize, y: i64) -> bool {
+        self.edges[e].top.y == y && self.edges[e].next_in_lml.is_some()
+    }
+
+    fn create_out_rec(&mut self) -> usize {
+        let idx = self.poly_outs.len();
+        self.poly_outs.push(OutRec {
+            idx,
+            is_hole: false,
+            first_left: None,
+            pts: None,
+            bottom_pt: None,
+        });
+        idx
+    }
+
+    fn add_out_pt(&mut self, e: usize, pt: IntPoint) -> usize {
+        if self.edges[e].out_idx < 0 {
+            let out_rec = self.create_out_rec();
+            let new_op = self.out_pts.len();
+            self.out_pts.push(OutPt {
+                idx: out_rec,
+                pt,
+                next: new_op,
+                prev: new_op,
+            });
+            self.poly_outs[out_rec].pts = Some(new_op);
+            self.set_hole_state(e, out_rec);
+            self.edges[e].out_idx = out_rec as i32;
+            new_op
+        } else {
+            let out_rec = &self.poly_outs[self.edges[e].out_idx as usize];
+            // `pts` is the left-most point, and `pts.prev` the right-most.
+            let op = out_rec
+                .pts
+                .expect("contributing edge should have output points");
+            let idx = out_rec.idx;
+            let prev = self.out_pts[op].prev;
+
+            let to_front = self.edges[e].side == EdgeSide::Left;
+            if to_front && pt == self.out_pts[op].pt {
+                return op;
+            } else if !to_front && pt == self.out_pts[prev].pt {
+                return prev;
+            }
+
+            let new_op = self.out_pts.len();
+            self.out_pts.push(OutPt {
+                idx,
+                pt,
+                next: op,
+                prev,
+            });
+            self.out_pts[prev].next = new_op;
+            self.out_pts[op].prev = new_op;
+            if to_front {
+                self.poly_outs[self.edges[e].out_idx as usize].pts = Some(new_op);
+            }
+            new_op
+        }
+    }
+
+    fn get_last_out_pt(&self, e: usize) -> usize {
+        let pts = self.poly_outs[self.edges[e].out_idx as usize]
+            .pts
+            .expect("contributing edge should have output points");
+        match self.edges[e].side {
+            EdgeSide::Left => pts,
+            EdgeSide::Right => self.out_pts[pts].prev,
+        }
+    }
+
+    fn set_hole_state(&mut self, e: usize, out_rec: usize) {
+        let mut e2 = self.edges[e].prev_in_ael;
+        let mut e_tmp: Option<usize> = None;
+        while let Some(edge) = e2 {
+            if self.edges[edge].out_idx >= 0 {
+                match e_tmp {
+                    None => e_tmp = Some(edge),
+                    Some(tmp) if self.edges[tmp].out_idx == self.edges[edge].out_idx => {
+                        e_tmp = None
+                    }
+                    Some(_) => {}
+                }
+            }
+            e2 = self.edges[edge].prev_in_ael;
+        }
+
+        match e_tmp {
+            None => {
+                self.poly_outs[out_rec].first_left = None;
+                self.poly_outs[out_rec].is_hole = false;
+            }
+            Some(tmp) => {
+                let first_left = self.edges[tmp].out_idx as usize;
+                self.poly_outs[out_rec].first_left = Some(first_left);
+                self.poly_outs[out_rec].is_hole = !self.poly_outs[first_left].is_hole;
+            }
+        }
+    }
+
+    fn add_local_min_poly(&mut self, e1: usize, e2: usize, pt: IntPoint) -> usize {
+        let result;
+        let e;
+        let prev_e;
+        if self.edges[e2].is_horizontal() || self.edges[e1].dx > self.edges[e2].dx {
+            result = self.add_out_pt(e1, pt);
+            self.edges[e2].out_idx = self.edges[e1].out_idx;
+            self.edges[e1].side = EdgeSide::Left;
+            self.edges[e2].side = EdgeSide::Right;
+            e = e1;
+            prev_e = if self.edges[e].prev_in_ael == Some(e2) {
+                self.edges[e2].prev_in_ael
+            } else {
+                self.edges[e].prev_in_ael
+            };
+        } else {
+            result = self.add_out_pt(e2, pt);
+            self.edges[e1].out_idx = self.edges[e2].out_idx;
+            self.edges[e1].side = EdgeSide::Right;
+            self.edges[e2].side = EdgeSide::Left;
+            e = e2;
+            prev_e = if self.edges[e].prev_in_ael == Some(e1) {
+                self.edges[e1].prev_in_ael
+            } else {
+                self.edges[e].prev_in_ael
+            };
+        }
+
+        if let Some(prev_e) = prev_e {
+            let (prev_edge, edge) = (&self.edges[prev_e], &self.edges[e]);
+            if prev_edge.out_idx >= 0 && prev_edge.top.y < pt.y && edge.top.y < pt.y {
+                let x_prev = prev_edge.top_x(pt.y);
+                let x_e = edge.top_x(pt.y);
+                if x_prev == x_e
+                    && slopes_equal4(
+                        IntPoint::new(x_prev, pt.y),
+                        prev_edge.top,
+                        IntPoint::new(x_e, pt.y),
+                        edge.top,
+                    )
+                {
+                    let top = edge.top;
+                    let out_pt = self.add_out_pt(prev_e, pt);
+                    self.add_join(result, out_pt, top);
+                }
+            }
+        }
+        result
+    }
+
+    fn add_local_max_poly(&mut self, e1: usize, e2: usize, pt: IntPoint) {
+        self.add_out_pt(e1, pt);
+        let (idx1, idx2) = (self.edges[e1].out_idx, self.edges[e2].out_idx);
+        if idx1 == idx2 {
+            self.edges[e1].out_idx = UNASSIGNED;
+            self.edges[e2].out_idx = UNASSIGNED;
+        } else if idx1 < idx2 {
+            self.append_polygon(e1, e2);
+        } else {
+            self.append_polygon(e2, e1);
+        }
+    }
+
+    fn add_join(&mut self, out_pt1: usize, out_pt2: usize, off_pt: IntPoint) {
+        self.joins.push(Join {
+            out_pt1,
+            out_pt2,
+            off_pt,
+        });
+    }
+
+    fn add_ghost_join(&mut self, out_pt: usize, off_pt: IntPoint) {
+        self.ghost_joins.push(Join {
+            out_pt1: out_pt,
+            out_pt2: out_pt,
+            off_pt,
+        });
+    }
+
+    fn append_polygon(&mut self, e1: usize, e2: usize) {
+        let out_rec1 = self.edges[e1].out_idx as usize;
+        let out_rec2 = self.edges[e2].out_idx as usize;
+
+        let hole_state_rec = if self.out_rec1_right_of_out_rec2(out_rec1, out_rec2) {
+            out_rec2
+        } else if self.out_rec1_right_of_out_rec2(out_rec2, out_rec1) {
+            out_rec1
+        } else {
+            self.get_lowermost_rec(out_rec1, out_rec2)
+        };
+
+        // Join the second polygon onto the first one.
+        let p1_lft = self.poly_outs[out_rec1]
+            .pts
+            .expect("polygon should have points");
+        let p1_rt = self.out_pts[p1_lft].prev;
+        let p2_lft = self.poly_outs[out_rec2]
+            .pts
+            .expect("polygon should have points");
+        let p2_rt = self.out_pts[p2_lft].prev;
+
+        let side1 = self.edges[e1].side;
+        let side2 = self.edges[e2].side;
+        match (side1, side2) {
+            (EdgeSide::Left, EdgeSide::Left) => {
+                // z y x a b c
+                self.reverse_poly_pt_links(p2_lft);
+                self.out_pts[p2_lft].next = p1_lft;
+                self.out_pts[p1_lft].prev = p2_lft;
+                self.out_pts[p1_rt].next = p2_rt;
+                self.out_pts[p2_rt].prev = p1_rt;
+                self.poly_outs[out_rec1].pts = Some(p2_rt);
+            }
+            (EdgeSide::Left, EdgeSide::Right) => {
+                // x y z a b c
+                self.out_pts[p2_rt].next = p1_lft;
+                self.out_pts[p1_lft].prev = p2_rt;
+                self.out_pts[p2_lft].prev = p1_rt;
+                self.out_pts[p1_rt].next = p2_lft;
+                self.poly_outs[out_rec1].pts = Some(p2_lft);
+            }
+            (EdgeSide::Right, EdgeSide::Right) => {
+                // a b c z y x
+                self.reverse_poly_pt_links(p2_lft);
+                self.out_pts[p1_rt].next = p2_rt;
+                self.out_pts[p2_rt].prev = p1_rt;
+                self.out_pts[p2_lft].next = p1_lft;
+                self.out_pts[p1_lft].prev = p2_lft;
+            }
+            (EdgeSide::Right, EdgeSide::Left) => {
+                // a b c x y z
+                self.out_pts[p1_rt].next = p2_lft;
+                self.out_pts[p2_lft].prev = p1_rt;
+                self.out_pts[p1_lft].prev = p2_rt;
+                self.out_pts[p2_rt].next = p1_lft;
+            }
+        }
+
+        self.poly_outs[out_rec1].bottom_pt = None;
+        if hole_state_rec == out_rec2 {
+            if self.poly_outs[out_rec2].first_left != Some(out_rec1) {
+                self.poly_outs[out_rec1].first_left = self.poly_outs[out_rec2].first_left;
+            }
+            self.poly_outs[out_rec1].is_hole = self.poly_outs[out_rec2].is_hole;
+        }
+        self.poly_outs[out_rec2].pts = None;
+        self.poly_outs[out_rec2].bottom_pt = None;
+        self.poly_outs[out_rec2].first_left = Some(out_rec1);
+
+        let ok_idx = self.edges[e1].out_idx;
+        let obsolete_idx = self.edges[e2].out_idx;
+
+        self.edges[e1].out_idx = UNASSIGNED;
+        self.edges[e2].out_idx = UNASSIGNED;
+
+        let mut e = self.active_edges;
+        while let Some(edge) = e {
+            if self.edges[edge].out_idx == obsolete_idx {
+                self.edges[edge].out_idx = ok_idx;
+                self.edges[edge].side = side1;
+                break;
+            }
+            e = self.edges[edge].next_in_ael;
+        }
+
+        self.poly_outs[out_rec2].idx = self.poly_outs[out_rec1].idx;
+    }
+
+    fn out_rec1_right_of_out_rec2(&self, out_rec1: usize, out_rec2: usize) -> bool {
+        let mut out_rec1 = self.poly_outs[out_rec1].first_left;
+        while let Some(out_rec) = out_rec1 {
+            if out_rec == out_rec2 {
+                return true;
+            }
+            out_rec1 = self.poly_outs[out_rec].first_left;
+        }
+        false
+    }
+
+    /// Returns whichever polygon has the correct hole state.
+    fn get_lowermost_rec(&mut self, out_rec1: usize, out_rec2: usize) -> usize {
+        for out_rec in [out_rec1, out_rec2] {
+            if self.poly_outs[out_rec].bottom_pt.is_none() {
+                let pts = self.poly_outs[out_rec]
+                    .pts
+                    .expect("polygon should have points");
+                self.poly_outs[out_rec].bottom_pt = Some(self.get_bottom_pt(pts));
+            }
+        }
+
+        let out_pt1 = self.poly_outs[out_rec1].bottom_pt.unwrap();
+        let out_pt2 = self.poly_outs[out_rec2].bottom_pt.unwrap();
+        let (pt1, pt2) = (self.out_pts[out_pt1].pt, self.out_pts[out_pt2].pt);
+        let first_is_lowermost = match (pt1.y.cmp(&pt2.y), pt1.x.cmp(&pt2.x)) {
+            (Ordering::Greater, _) => true,
+            (Ordering::Less, _) => false,
+            (_, Ordering::Less) => true,
+            (_, Ordering::Greater) => false,
+            _ => {
+                self.out_pts[out_pt1].next != out_pt1
+                    && (self.out_pts[out_pt2].next == out_pt2
+                        || self.first_is_bottom_pt(out_pt1, out_pt2))
+            }
+        };
+        if first_is_lowermost {
+            out_rec1
+        } else {
+            out_rec2
+        }
+    }
+
+    fn get_bottom_pt(&self, mut pp: usize) -> usize {
+        let ops = &self.out_pts;
+        let mut dups = None;
+        let mut p = ops[pp].next;
+        while p != pp {
+            if ops[p].pt.y > ops[pp].pt.y {
+                pp = p;
+                dups = None;
+            } else if ops[p].pt.y == ops[pp].pt.y && ops[p].pt.x <= ops[pp].pt.x {
+                if ops[p].pt.x < ops[pp].pt.x {
+                    dups = None;
+                    pp = p;
+                } else if ops[p].next != pp && ops[p].prev != pp {
+                    dups = Some(p);
+                }
+            }
+            p = ops[p].next;
+        }
+
+        if let Some(mut dups) = dups {
+            // There are at least two vertices at the bottom point.
+            while dups != p {
+                if !self.first_is_bottom_pt(p, dups) {
+                    pp = dups;
+                }
+                dups = ops[dups].next;
+                while ops[dups].pt != ops[pp].pt {
+                    dups = ops[dups].next;
+                }
+            }
+        }
+        pp
+    }
+
+    fn first_is_bottom_pt(&self, btm_pt1: usize, btm_pt2: usize) -> bool {
+        let ops = &self.out_pts;
+        let dx = |btm_pt: usize, step: fn(&OutPt) -> usize| {
+            let mut p = step(&ops[btm_pt]);
+            while ops[p].pt == ops[btm_pt].pt && p != btm_pt {
+                p = step(&ops[p]);
+            }
+            get_dx(ops[btm_pt].pt, ops[p].pt).abs()
+        };
+
+        let dx1p = dx(btm_pt1, |op| op.prev);
+        let dx1n = dx(btm_pt1, |op| op.next);
+        let dx2p = dx(btm_pt2, |op| op.prev);
+        let dx2n = dx(btm_pt2, |op| op.next);
+
+        if dx1p.max(dx1n) == dx2p.max(dx2n) && dx1p.min(dx1n) == dx2p.min(dx2n) {
+            // Otherwise identical, so use the orientation.
+            self.area(btm_pt1) > 0.0
+        } else {
+            (dx1p >= dx2p && dx1p >= dx2n) || (dx1n >= dx2p && dx1n >= dx2n)
+        }
+    }
+
+    fn get_out_rec(&self, idx: usize) -> usize {
+        let mut out_rec = idx;
+        while out_rec != self.poly_outs[out_rec].idx {
+            out_rec = self.poly_outs[out_rec].idx;
+        }
+        out_rec
+    }
+
+    fn intersect_edges(&mut self, e1: usize, e2: usize, pt: IntPoint) {
+        let e1_contributing = self.edges[e1].out_idx >= 0;
+        let e2_contributing = self.edges[e2].out_idx >= 0;
+
+        // Update the winding counts, assuming that `e1` will be to the right of `e2` above the
+        // intersection.
+        let (e1_fill_type, e1_fill_type2) = self.fill_types(self.edges[e1].poly_type);
+        let (e2_fill_type, e2_fill_type2) = self.fill_types(self.edges[e2].poly_type);
+        let same_poly_type = self.edges[e1].poly_type == self.edges[e2].poly_type;
+        {
+            let (e1_delta, e2_delta) = (self.edges[e1].wind_delta, self.edges[e2].wind_delta);
+            let (e1_cnt, e2_cnt) = (self.edges[e1].wind_cnt, self.edges[e2].wind_cnt);
+            let (e1_cnt2, e2_cnt2) = (self.edges[e1].wind_cnt2, self.edges[e2].wind_cnt2);
+            if same_poly_type {
+                if e1_fill_type == PolyFillType::EvenOdd {
+                    self.edges[e1].wind_cnt = e2_cnt;
+                    self.edges[e2].wind_cnt = e1_cnt;
+                } else {
+                    self.edges[e1].wind_cnt = if e1_cnt + e2_delta == 0 {
+                        -e1_cnt
+                    } else {
+                        e1_cnt + e2_delta
+                    };
+                    self.edges[e2].wind_cnt = if e2_cnt - e1_delta == 0 {
+                        -e2_cnt
+                    } else {
+                        e2_cnt - e1_delta
+                    };
+                }
+            } else {
+                self.edges[e1].wind_cnt2 = if e2_fill_type != PolyFillType::EvenOdd {
+                    e1_cnt2 + e2_delta
+                } else if e1_cnt2 == 0 {
+                    1
+                } else {
+                    0
+                };
+                self.edges[e2].wind_cnt2 = if e1_fill_type != PolyFillType::EvenOdd {
+                    e2_cnt2 - e1_delta
+                } else if e2_cnt2 == 0 {
+                    1
+                } else {
+                    0
+                };
+            }
+        }
+
+        let fill_count = |fill_type: PolyFillType, count: i32| match fill_type {
+            PolyFillType::Positive => count,
+            PolyFillType::Negative => -count,
+            PolyFillType::EvenOdd => count.abs(),
+        };
+        let e1_wc = fill_count(e1_fill_type, self.edges[e1].wind_cnt);
+        let e2_wc = fill_count(e2_fill_type, self.edges[e2].wind_cnt);
+
+        if e1_contributing && e2_contributing {
+            if (e1_wc != 0 && e1_wc != 1)
+                || (e2_wc != 0 && e2_wc != 1)
+                || (!same_poly_type && self.clip_type != ClipType::Xor)
+            {
+                self.add_local_max_poly(e1, e2, pt);
+            } else {
+                self.add_out_pt(e1, pt);
+                self.add_out_pt(e2, pt);
+                self.swap_sides(e1, e2);
+                self.swap_poly_indexes(e1, e2);
+            }
+        } else if e1_contributing {
+            if e2_wc == 0 || e2_wc == 1 {
+                self.add_out_pt(e1, pt);
+                self.swap_sides(e1, e2);
+                self.swap_poly_indexes(e1, e2);
+            }
+        } else if e2_contributing {
+            if e1_wc == 0 || e1_wc == 1 {
+                self.add_out_pt(e2, pt);
+                self.swap_sides(e1, e2);
+                self.swap_poly_indexes(e1, e2);
+            }
+        } else if (e1_wc == 0 || e1_wc == 1) && (e2_wc == 0 || e2_wc == 1) {
+            // Neither edge is currently contributing.
+            let e1_wc2 = fill_count(e1_fill_type2, self.edges[e1].wind_cnt2);
+            let e2_wc2 = fill_count(e2_fill_type2, self.edges[e2].wind_cnt2);
+
+            if !same_poly_type {
+                self.add_local_min_poly(e1, e2, pt);
+            } else if e1_wc == 1 && e2_wc == 1 {
+                let starts_polygon = match self.clip_type {
+                    ClipType::Intersection => e1_wc2 > 0 && e2_wc2 > 0,
+                    ClipType::Union => e1_wc2 <= 0 && e2_wc2 <= 0,
+                    ClipType::Difference => match self.edges[e1].poly_type {
+                        PolyType::Clip => e1_wc2 > 0 && e2_wc2 > 0,
+                        PolyType::Subject => e1_wc2 <= 0 && e2_wc2 <= 0,
+                    },
+                    ClipType::Xor => true,
+                };
+                if starts_polygon {
+                    self.add_local_min_poly(e1, e2, pt);
+                }
+            } else {
+                self.swap_sides(e1, e2);
+            }
+        }
+    }
+
+    fn swap_sides(&mut self, e1: usize, e2: usize) {
+        let side = self.edges[e1].side;
+        self.edges[e1].side = self.edges[e2].side;
+        self.edges[e2].side = side;
+    }
+
+    fn swap_poly_indexes(&mut self, e1: usize, e2: usize) {
+        let out_idx = self.edges[e1].out_idx;
+        self.edges[e1].out_idx = self.edges[e2].out_idx;
+        self.edges[e2].out_idx = out_idx;
+    }
+
+    fn process_horizontals(&mut self) {
+        while let Some(horz_edge) = self.pop_edge_from_sel() {
+            self.process_horizontal(horz_edge);
+        }
+    }
+
+    /// Adds joins between the last point added for `horz_edge` and the overlapping horizontals
+    /// waiting in the SEL, and a ghost join for horizontals still to come.
+    fn join_horizontals(&mut self, horz_edge: usize, op1: usize, ghost_pt: IntPoint) {
+        let (horz_bot, horz_top) = (self.edges[horz_edge].bot, self.edges[horz_edge].top);
+        let mut e_next_horz = self.sorted_edges;
+        while let Some(next_horz) = e_next_horz {
+            let edge = &self.edges[next_horz];
+            if edge.out_idx >= 0
+                && horz_segments_overlap(horz_bot.x, horz_top.x, edge.bot.x, edge.top.x)
+            {
+                let top = edge.top;
+                let op2 = self.get_last_out_pt(next_horz);
+                self.add_join(op2, op1, top);
+            }
+            e_next_horz = self.edges[next_horz].next_in_sel;
+        }
+        self.add_ghost_join(op1, ghost_pt);
+    }
+
+    fn process_horizontal(&mut self, mut horz_edge: usize) {
+        let (mut dir, mut horz_left, mut horz_right) = self.edges[horz_edge].horz_direction();
+
+        let mut e_last_horz = horz_edge;
+        while let Some(next) = self.edges[e_last_horz].next_in_lml {
+            if !self.edges[next].is_horizontal() {
+                break;
+            }
+            e_last_horz = next;
+        }
+        let e_max_pair = if self.edges[e_last_horz].next_in_lml.is_none() {
+            self.get_maxima_pair(e_last_horz)
+        } else {
+            None
+        };
+
+        let mut op1 = None;
+
+        // Loop through consecutive horizontal edges.
+        loop {
+            let is_last_horz = horz_edge == e_last_horz;
+            let mut e = self.get_next_in_ael(horz_edge, dir);
+            while let Some(edge) = e {
+                let curr = self.edges[edge].curr;
+                if (dir == Direction::LeftToRight && curr.x > horz_right)
+                    || (dir == Direction::RightToLeft && curr.x < horz_left)
+                {
+                    break;
+                }
+
+                // Also break at the end of an intermediate horizontal edge. Smaller dx's are to
+                // the right of larger ones above the horizontal.
+                if curr.x == self.edges[horz_edge].top.x {
+                    if let Some(next) = self.edges[horz_edge].next_in_lml {
+                        if self.edges[edge].dx < self.edges[next].dx {
+                            break;
+                        }
+                    }
+                }
+
+                // This may be done multiple times.
+                if self.edges[horz_edge].out_idx >= 0 {
+                    let op = self.add_out_pt(horz_edge, curr);
+                    op1 = Some(op);
+                    let bot = self.edges[horz_edge].bot;
+                    self.join_horizontals(horz_edge, op, bot);
+                }
+
+                // Make sure we're at the last of consecutive horizontals when matching with the
+                // maxima pair.
+                if Some(edge) == e_max_pair && is_last_horz {
+                    if self.edges[horz_edge].out_idx >= 0 {
+                        let top = self.edges[horz_edge].top;
+                        self.add_local_max_poly(horz_edge, edge, top);
+                    }
+                    self.delete_from_ael(horz_edge);
+                    self.delete_from_ael(edge);
+                    return;
+                }
+
+                let pt = IntPoint::new(curr.x, self.edges[horz_edge].curr.y);
+                match dir {
+                    Direction::LeftToRight => self.intersect_edges(horz_edge, edge, pt),
+                    Direction::RightToLeft => self.intersect_edges(edge, horz_edge, pt),
+                }
+                let e_next = self.get_next_in_ael(edge, dir);
+                self.swap_positions_in_ael(horz_edge, edge);
+                e = e_next;
+            }
+
+            // Stop unless the next edge of the bound is also horizontal.
+            match self.edges[horz_edge].next_in_lml {
+                Some(next) if self.edges[next].is_horizontal() => {}
+                _ => break,
+            }
+
+            horz_edge = self.update_edge_into_ael(horz_edge);
+            if self.edges[horz_edge].out_idx >= 0 {
+                let bot = self.edges[horz_edge].bot;
+                self.add_out_pt(horz_edge, bot);
+            }
+            (dir, horz_left, horz_right) = self.edges[horz_edge].horz_direction();
+        }
+
+        if self.edges[horz_edge].out_idx >= 0 && op1.is_none() {
+            let op = self.get_last_out_pt(horz_edge);
+            let top = self.edges[horz_edge].top;
+            self.join_horizontals(horz_edge, op, top);
+        }
+
+        if self.edges[horz_edge].next_in_lml.is_some() {
+            if self.edges[horz_edge].out_idx >= 0 {
+                let top = self.edges[horz_edge].top;
+                let op1 = self.add_out_pt(horz_edge, top);
+                horz_edge = self.update_edge_into_ael(horz_edge);
+
+                // The edge is no longer horizontal here.
+                let horz = &self.edges[horz_edge];
+                let (bot, top) = (horz.bot, horz.top);
+                let joins_edge = |other: Option<usize>| {
+                    other.filter(|&other| {
+                        let other = &self.edges[other];
+                        other.curr == bot
+                            && other.out_idx >= 0
+                            && other.curr.y > other.top.y
+                            && slopes_equal_edges(horz, other)
+                    })
+                };
+                if let Some(other) =
+                    joins_edge(horz.prev_in_ael).or_else(|| joins_edge(horz.next_in_ael))
+                {
+                    let op2 = self.add_out_pt(other, bot);
+                    self.add_join(op1, op2, top);
+                }
+            } else {
+                self.update_edge_into_ael(horz_edge);
+            }
+        } else {
+            if self.edges[horz_edge].out_idx >= 0 {
+                let top = self.edges[horz_edge].top;
+                self.add_out_pt(horz_edge, top);
+            }
+            self.delete_from_ael(horz_edge);
+        }
+    }
+
+    fn process_intersections(&mut self, top_y: i64) -> Result<(), Failed> {
+        if self.active_edges.is_none() {
+            return Ok(());
+        }
+
+        self.build_intersect_list(top_y);
+        match self.intersect_list.len() {
+            0 => return Ok(()),
+            1 => {}
+            _ => {
+                if !self.fixup_intersection_order() {
+                    return Err(Failed);
+                }
+            }
+        }
+        self.process_intersect_list();
+        self.sorted_edges = None;
+        Ok(())
+    }
+
+    fn build_intersect_list(&mut self, top_y: i64) {
+        // Prepare for sorting.
+        let mut e = self.active_edges;
+        self.sorted_edges = e;
+        while let Some(edge) = e {
+            let edge = &mut self.edges[edge];
+            edge.prev_in_sel = edge.prev_in_ael;
+            edge.next_in_sel = edge.next_in_ael;
+            edge.curr.x = edge.top_x(top_y);
+            e = edge.next_in_ael;
+        }
+
+        // Bubble sort.
+        while let Some(mut e) = self.sorted_edges {
+            let mut is_modified = false;
+            while let Some(e_next) = self.edges[e].next_in_sel {
+                if self.edges[e].curr.x > self.edges[e_next].curr.x {
+                    let mut pt = intersect_point(&self.edges[e], &self.edges[e_next]);
+                    if pt.y < top_y {
+                        pt = IntPoint::new(self.edges[e].top_x(top_y), top_y);
+                    }
+                    self.intersect_list.push(IntersectNode {
+                        edge1: e,
+                        edge2: e_next,
+                        pt,
+                    });
+                    self.swap_positions_in_sel(e, e_next);
+                    is_modified = true;
+                } else {
+                    e = e_next;
+                }
+            }
+
+            match self.edges[e].prev_in_sel {
+                Some(prev) => self.edges[prev].next_in_sel = None,
+                None => break,
+            }
+            if !is_modified {
+                break;
+            }
+        }
+        self.sorted_edges = None;
+    }
+
+    fn process_intersect_list(&mut self) {
+        for node in std::mem::take(&mut self.intersect_list) {
+            self.intersect_edges(node.edge1, node.edge2, node.pt);
+            self.swap_positions_in_ael(node.edge1, node.edge2);
+        }
+    }
+
+    fn edges_adjacent(&self, node: &IntersectNode) -> bool {
+        self.edges[node.edge1].next_in_sel == Some(node.edge2)
+            || self.edges[node.edge1].prev_in_sel == Some(node.edge2)
+    }
+
+    /// Reorders the intersections, sorted bottom-most first, so that they're only made between
+    /// adjacent edges.
+    fn fixup_intersection_order(&mut self) -> bool {
+        self.copy_ael_to_sel();
+        std_sort(&mut self.intersect_list, |node1, node2| {
+            node2.pt.y < node1.pt.y
+        });
+
+        let cnt = self.intersect_list.len();
+        for i in 0..cnt {
+            if !self.edges_adjacent(&self.intersect_list[i]) {
+                let mut j = i + 1;
+                while j < cnt && !self.edges_adjacent(&self.intersect_list[j]) {
+                    j += 1;
+                }
+                if j == cnt {
+                    return false;
+                }
+                self.intersect_list.swap(i, j);
+            }
+            let node = self.intersect_list[i];
+            self.swap_positions_in_sel(node.edge1, node.edge2);
+        }
+        true
+    }
+
+    fn do_maxima(&mut self, e: usize) -> Result<(), Failed> {
+        let e_max_pair = match self.get_maxima_pair_ex(e) {
+            Some(pair) => pair,
+            None => {
+                if self.edges[e].out_idx >= 0 {
+                    let top = self.edges[e].top;
+                    self.add_out_pt(e, top);
+                }
+                self.delete_from_ael(e);
+                return Ok(());
+            }
+        };
+
+        let mut e_next = self.edges[e].next_in_ael;
+        while let Some(next) = e_next {
+            if next == e_max_pair {
+                break;
+            }
+            let top = self.edges[e].top;
+            self.intersect_edges(e, next, top);
+            self.swap_positions_in_ael(e, next);
+            e_next = self.edges[e].next_in_ael;
+        }
+
+        let (idx, pair_idx) = (self.edges[e].out_idx, self.edges[e_max_pair].out_idx);
+        if idx == UNASSIGNED && pair_idx == UNASSIGNED {
+            self.delete_from_ael(e);
+            self.delete_from_ael(e_max_pair);
+        } else if idx >= 0 && pair_idx >= 0 {
+            let top = self.edges[e].top;
+            self.add_local_max_poly(e, e_max_pair, top);
+            self.delete_from_ael(e);
+            self.delete_from_ael(e_max_pair);
+        } else {
+            return Err(Failed);
+        }
+        Ok(())
+    }
+
+    fn process_edges_at_top_of_scanbeam(&mut self, top_y: i64) -> Result<(), Failed> {
+        let mut e = self.active_edges;
+        while let Some(mut edge) = e {
+            // 1. Process maxima, treating them as if they're bent horizontal edges, but exclude
+            // maxima with horizontal edges.
+            let mut is_maxima_edge = self.is_maxima(edge, top_y);
+            if is_maxima_edge {
+                is_maxima_edge = match self.get_maxima_pair_ex(edge) {
+                    Some(pair) => !self.edges[pair].is_horizontal(),
+                    None => true,
+                };
+            }
+
+            if is_maxima_edge {
+                let e_prev = self.edges[edge].prev_in_ael;
+                self.do_maxima(edge)?;
+                e = match e_prev {
+                    Some(prev) => self.edges[prev].next_in_ael,
+                    None => self.active_edges,
+                };
+            } else {
+                // 2. Promote horizontal edges, otherwise update the current position.
+                let next_is_horizontal = self.is_intermediate(edge, top_y)
+                    && self.edges[edge]
+                        .next_in_lml
+                        .map_or(false, |next| self.edges[next].is_horizontal());
+                if next_is_horizontal {
+                    edge = self.update_edge_into_ael(edge);
+                    if self.edges[edge].out_idx >= 0 {
+                        let bot = self.edges[edge].bot;
+                        self.add_out_pt(edge, bot);
+                    }
+                    self.add_edge_to_sel(edge);
+                } else {
+                    let edge = &mut self.edges[edge];
+                    edge.curr.x = edge.top_x(top_y);
+                    edge.curr.y = top_y;
+                }
+                e = self.edges[edge].next_in_ael;
+            }
+        }
+
+        // 3. Process horizontals at the top of the scanbeam.
+        self.process_horizontals();
+
+        // 4. Promote intermediate vertices.
+        let mut e = self.active_edges;
+        while let Some(mut edge) = e {
+            if self.is_intermediate(edge, top_y) {
+                let op = if self.edges[edge].out_idx >= 0 {
+                    let top = self.edges[edge].top;
+                    Some(self.add_out_pt(edge, top))
+                } else {
+                    None
+                };
+                edge = self.update_edge_into_ael(edge);
+
+                // If output polygons share an edge, they'll need joining later.
+                if let Some(op) = op {
+                    let current = &self.edges[edge];
+                    let (bot, top) = (current.bot, current.top);
+                    let joins_edge = |other: Option<usize>| {
+                        other.filter(|&other| {
+                            let other = &self.edges[other];
+                            other.curr == bot
+                                && other.out_idx >= 0
+                                && other.curr.y > other.top.y
+                                && slopes_equal4(current.curr, current.top, other.curr, other.top)
+                        })
+                    };
+                    if let Some(other) =
+                        joins_edge(current.prev_in_ael).or_else(|| joins_edge(current.next_in_ael))
+                    {
+                        let op2 = self.add_out_pt(other, bot);
+                        self.add_join(op, op2, top);
+                    }
+                }
+            }
+            e = self.edges[edge].next_in_ael;
+        }
+
+        Ok(())
+    }
+
+    fn reverse_poly_pt_links(&mut self, pp: usize) {
+        let mut pp1 = pp;
+        loop {
+            let op = &mut self.out_pts[pp1];
+            let pp2 = op.next;
+            std::mem::swap(&mut op.next, &mut op.prev);
+            pp1 = pp2;
+            if pp1 == pp {
+                break;
+            }
+        }
+    }
+
+    /// Area of the polygon starting at `op`, positive for counter-clockwise polygons when
+    /// traversed through their `prev` links.
+    fn area(&self, op: usize) -> f64 {
+        let start = op;
+        let mut op = op;
+        let mut a = 0.0;
+        loop {
+            let prev = &self.out_pts[self.out_pts[op].prev];
+            let pt = self.out_pts[op].pt;
+            a += (prev.pt.x + pt.x) as f64 * (prev.pt.y - pt.y) as f64;
+            op = self.out_pts[op].next;
+            if op == start {
+                break;
+            }
+        }
+        a * 0.5
+    }
+
+    /// Returns 0 if `pt` is outside of the polygon starting at `op`, 1 if it's inside, and -1 if
+    /// it's on its boundary.
+    fn point_in_polygon(&self, pt: IntPoint, op: usize) -> i32 {
+        let start = op;
+        let mut op = op;
+        let mut result = 0;
+        loop {
+            let p = self.out_pts[op].pt;
+            let next = self.out_pts[self.out_pts[op].next].pt;
+            if next.y == pt.y
+                && (next.x == pt.x || (p.y == pt.y && ((next.x > pt.x) == (p.x < pt.x))))
+            {
+                return -1;
+            }
+            if (p.y < pt.y) != (next.y < pt.y) {
+                let d = || {
+                    (p.x - pt.x) as f64 * (next.y - pt.y) as f64
+                        - (next.x - pt.x) as f64 * (p.y - pt.y) as f64
+                };
+                if p.x >= pt.x {
+                    if next.x > pt.x {
+                        result = 1 - result;
+                    } else {
+                        let d = d();
+                        if d == 0.0 {
+                            return -1;
+                        }
+                        if (d > 0.0) == (next.y > p.y) {
+                            result = 1 - result;
+                        }
+                    }
+                } else if next.x > pt.x {
+                    let d = d();
+                    if d == 0.0 {
+                        return -1;
+                    }
+                    if (d > 0.0) == (next.y > p.y) {
+                        result = 1 - result;
+                    }
+                }
+            }
+            op = self.out_pts[op].next;
+            if op == start {
+                break;
+            }
+        }
+        result
+    }
+
+    fn poly2_contains_poly1(&self, out_pt1: usize, out_pt2: usize) -> bool {
+        let mut op = out_pt1;
+        loop {
+            let res = self.point_in_polygon(self.out_pts[op].pt, out_pt2);
+            if res >= 0 {
+                return res > 0;
+            }
+            op = self.out_pts[op].next;
+            if op == out_pt1 {
+                break;
+            }
+        }
+        true
+    }
+
+    fn dup_out_pt(&mut self, out_pt: usize, insert_after: bool) -> usize {
+        let result = self.out_pts.len();
+        let OutPt {
+            idx,
+            pt,
+            next,
+            prev,
+        } = self.out_pts[out_pt];
+        if insert_after {
+            self.out_pts.push(OutPt {
+                idx,
+                pt,
+                next,
+                prev: out_pt,
+            });
+            self.out_pts[next].prev = result;
+            self.out_pts[out_pt].next = result;
+        } else {
+            self.out_pts.push(OutPt {
+                idx,
+                pt,
+                next: out_pt,
+                prev,
+            });
+            self.out_pts[prev].next = result;
+            self.out_pts[out_pt].prev = result;
+        }
+        result
+    }
+
+    /// Moves along a horizontal run of points from `op` towards `pt`, and inserts a duplicate of
+    /// `pt` there, returning it and its duplicate.
+    fn prepare_horz_join(
+        &mut self,
+        mut op: usize,
+        dir: Direction,
+        pt: IntPoint,
+        discard_left: bool,
+    ) -> (usize, usize) {
+        let ops = &self.out_pts;
+        let insert_after = match dir {
+            Direction::LeftToRight => {
+                while ops[ops[op].next].pt.x <= pt.x
+                    && ops[ops[op].next].pt.x >= ops[op].pt.x
+                    && ops[ops[op].next].pt.y == pt.y
+                {
+                    op = ops[op].next;
+                }
+                if discard_left && ops[op].pt.x != pt.x {
+                    op = ops[op].next;
+                }
+                !discard_left
+            }
+            Direction::RightToLeft => {
+                while ops[ops[op].next].pt.x >= pt.x
+                    && ops[ops[op].next].pt.x <= ops[op].pt.x
+                    && ops[ops[op].next].pt.y == pt.y
+                {
+                    op = ops[op].next;
+                }
+                if !discard_left && ops[op].pt.x != pt.x {
+                    op = ops[op].next;
+                }
+                discard_left
+            }
+        };
+
+        let mut op_b = self.dup_out_pt(op, insert_after);
+        if self.out_pts[op_b].pt != pt {
+            op = op_b;
+            self.out_pts[op].pt = pt;
+            op_b = self.dup_out_pt(op, insert_after);
+        }
+        (op, op_b)
+    }
+
+    fn join_horz(
+        &mut self,
+        op1: usize,
+        op1b: usize,
+        op2: usize,
+        op2b: usize,
+        pt: IntPoint,
+        discard_left: bool,
+    ) -> bool {
+        let direction = |a: usize, b: usize| {
+            if self.out_pts[a].pt.x > self.out_pts[b].pt.x {
+                Direction::RightToLeft
+            } else {
+                Direction::LeftToRight
+            }
+        };
+        let dir1 = direction(op1, op1b);
+        let dir2 = direction(op2, op2b);
+        if dir1 == dir2 {
+            return false;
+        }
+
+        // When discarding the left side, `op1b` should be to the left of `op1`, otherwise to its
+        // right, and likewise with `op2` and `op2b`.
+        let (op1, op1b) = self.prepare_horz_join(op1, dir1, pt, discard_left);
+        let (op2, op2b) = self.prepare_horz_join(op2, dir2, pt, discard_left);
+
+        let ops = &mut self.out_pts;
+        if (dir1 == Direction::LeftToRight) == discard_left {
+            ops[op1].prev = op2;
+            ops[op2].next = op1;
+            ops[op1b].next = op2b;
+            ops[op2b].prev = op1b;
+        } else {
+            ops[op1].next = op2;
+            ops[op2].prev = op1;
+            ops[op1b].prev = op2b;
+            ops[op2b].next = op1b;
+        }
+        true
+    }
+
+    /// Joins the points of `j`, updating them to the points the polygons split at.
+    fn join_points(&mut self, j: &mut Join, out_rec1: usize, out_rec2: usize) -> bool {
+        let mut op1 = j.out_pt1;
+        let mut op2 = j.out_pt2;
+
+        // Horizontal joins are between points anywhere along collinear horizontal edges.
+        // Non-horizontal joins are between points at the bottom of the overlapping segment, with
+        // the offset point above. Strictly simple joins aren't made without StrictlySimple.
+        let is_horizontal = self.out_pts[op1].pt.y == j.off_pt.y;
+
+        if is_horizontal && j.off_pt == self.out_pts[op1].pt && j.off_pt == self.out_pts[op2].pt {
+            // Strictly simple join.
+            if out_rec1 != out_rec2 {
+                return false;
+            }
+            let off_pt = j.off_pt;
+            let after = |start: usize| {
+                let mut op = self.out_pts[start].next;
+                while op != start && self.out_pts[op].pt == off_pt {
+                    op = self.out_pts[op].next;
+                }
+                self.out_pts[op].pt.y > off_pt.y
+            };
+            let reverse1 = after(op1);
+            let reverse2 = after(op2);
+            if reverse1 == reverse2 {
+                return false;
+            }
+            let op1b = self.link_joined(op1, op2, reverse1);
+            j.out_pt1 = op1;
+            j.out_pt2 = op1b;
+            true
+        } else if is_horizontal {
+            // Since the overlap isn't yet known, the points may be anywhere along the edges.
+            let ops = &self.out_pts;
+            let mut op1b = op1;
+            while ops[ops[op1].prev].pt.y == ops[op1].pt.y
+                && ops[op1].prev != op1b
+                && ops[op1].prev != op2
+            {
+                op1 = ops[op1].prev;
+            }
+            while ops[ops[op1b].next].pt.y == ops[op1b].pt.y
+                && ops[op1b].next != op1
+                && ops[op1b].next != op2
+            {
+                op1b = ops[op1b].next;
+            }
+            if ops[op1b].next == op1 || ops[op1b].next == op2 {
+                // A flat polygon.
+                return false;
+            }
+
+            let mut op2b = op2;
+            while ops[ops[op2].prev].pt.y == ops[op2].pt.y
+                && ops[op2].prev != op2b
+                && ops[op2].prev != op1b
+            {
+                op2 = ops[op2].prev;
+            }
+            while ops[ops[op2b].next].pt.y == ops[op2b].pt.y
+                && ops[op2b].next != op2
+                && ops[op2b].next != op1
+            {
+                op2b = ops[op2b].next;
+            }
+            if ops[op2b].next == op2 || ops[op2b].next == op1 {
+                // A flat polygon.
+                return false;
+            }
+
+            // `op1` to `op1b` and `op2` to `op2b` are the ends of the horizontal edges.
+            let (left, right) =
+                match get_overlap(ops[op1].pt.x, ops[op1b].pt.x, ops[op2].pt.x, ops[op2b].pt.x) {
+                    Some(overlap) => overlap,
+                    None => return false,
+                };
+
+            // Joining overlapping edges creates a spike which is cleaned up later. Neither `op1`
+            // nor `op2` should be on the discarded side, as they may still be needed for other
+            // joins.
+            let (pt1, pt1b, pt2, pt2b) = (ops[op1].pt, ops[op1b].pt, ops[op2].pt, ops[op2b].pt);
+            let (pt, discard_left_side) = if pt1.x >= left && pt1.x <= right {
+                (pt1, pt1.x > pt1b.x)
+            } else if pt2.x >= left && pt2.x <= right {
+                (pt2, pt2.x > pt2b.x)
+            } else if pt1b.x >= left && pt1b.x <= right {
+                (pt1b, pt1b.x > pt1.x)
+            } else {
+                (pt2b, pt2b.x > pt2.x)
+            };
+            j.out_pt1 = op1;
+            j.out_pt2 = op2;
+            self.join_horz(op1, op1b, op2, op2b, pt, discard_left_side)
+        } else {
+            // The points are at the same height, above the offset point. Make sure the polygons
+            // are correctly oriented.
+            let off_pt = j.off_pt;
+            let other_end = |op: usize| -> Option<(usize, bool)> {
+                let ops = &self.out_pts;
+                let walk = |step: fn(&OutPt) -> usize| {
+                    let mut b = step(&ops[op]);
+                    while ops[b].pt == ops[op].pt && b != op {
+                        b = step(&ops[b]);
+                    }
+                    b
+                };
+                let overlaps = |b: usize| {
+                    ops[b].pt.y <= ops[op].pt.y && slopes_equal3(ops[op].pt, ops[b].pt, off_pt)
+                };
+
+                let b = walk(|op| op.next);
+                if overlaps(b) {
+                    return Some((b, false));
+                }
+                let b = walk(|op| op.prev);
+                if overlaps(b) {
+                    Some((b, true))
+                } else {
+                    None
+                }
+            };
+
+            let (op1b, reverse1) = match other_end(op1) {
+                Some(end) => end,
+                None => return false,
+            };
+            let (op2b, reverse2) = match other_end(op2) {
+                Some(end) => end,
+                None => return false,
+            };
+
+            if op1b == op1
+                || op2b == op2
+                || op1b == op2b
+                || (out_rec1 == out_rec2 && reverse1 == reverse2)
+            {
+                return false;
+            }
+
+            let op1b = self.link_joined(op1, op2, reverse1);
+            j.out_pt1 = op1;
+            j.out_pt2 = op1b;
+            true
+        }
+    }
+
+    /// Splices the polygons of `op1` and `op2` together at these points, duplicating them, and
+    /// returns the duplicate of `op1`.
+    fn link_joined(&mut self, op1: usize, op2: usize, reverse1: bool) -> usize {
+        let op1b = self.dup_out_pt(op1, !reverse1);
+        let op2b = self.dup_out_pt(op2, reverse1);
+        let ops = &mut self.out_pts;
+        if reverse1 {
+            ops[op1].prev = op2;
+            ops[op2].next = op1;
+            ops[op1b].next = op2b;
+            ops[op2b].prev = op1b;
+        } else {
+            ops[op1].next = op2;
+            ops[op2].prev = op1;
+            ops[op1b].prev = op2b;
+            ops[op2b].next = op1b;
+        }
+        op1b
+    }
+
+    fn update_out_pt_idxs(&mut self, out_rec: usize) {
+        let idx = self.poly_outs[out_rec].idx;
+        let start = self.poly_outs[out_rec]
+            .pts
+            .expect("polygon should have points");
+        let mut op = start;
+        loop {
+            self.out_pts[op].idx = idx;
+            op = self.out_pts[op].prev;
+            if op == start {
+                break;
+            }
+        }
+    }
+
+    fn join_common_edges(&mut self) {
+        for i in 0..self.joins.len() {
+            let mut join = self.joins[i];
+
+            let out_rec1 = self.get_out_rec(self.out_pts[join.out_pt1].idx);
+            let mut out_rec2 = self.get_out_rec(self.out_pts[join.out_pt2].idx);
+
+            if self.poly_outs[out_rec1].pts.is_none() || self.poly_outs[out_rec2].pts.is_none() {
+                continue;
+            }
+
+            // Get the polygon fragment with the correct hole state before joining.
+            let hole_state_rec = if out_rec1 == out_rec2 {
+                out_rec1
+            } else if self.out_rec1_right_of_out_rec2(out_rec1, out_rec2) {
+                out_rec2
+            } else if self.out_rec1_right_of_out_rec2(out_rec2, out_rec1) {
+                out_rec1
+            } else {
+                self.get_lowermost_rec(out_rec1, out_rec2)
+            };
+
+            if !self.join_points(&mut join, out_rec1, out_rec2) {
+                continue;
+            }
+
+            if out_rec1 == out_rec2 {
+                // Instead of joining two polygons, one has been split into two.
+                self.poly_outs[out_rec1].pts = Some(join.out_pt1);
+                self.poly_outs[out_rec1].bottom_pt = None;
+                out_rec2 = self.create_out_rec();
+                self.poly_outs[out_rec2].pts = Some(join.out_pt2);
+
+                self.update_out_pt_idxs(out_rec2);
+
+                if self.poly2_contains_poly1(join.out_pt2, join.out_pt1) {
+                    // The first polygon contains the second one.
+                    let is_hole = !self.poly_outs[out_rec1].is_hole;
+                    self.poly_outs[out_rec2].is_hole = is_hole;
+                    self.poly_outs[out_rec2].first_left = Some(out_rec1);
+
+                    if (is_hole ^ self.reverse_output) == (self.area(join.out_pt2) > 0.0) {
+                        self.reverse_poly_pt_links(join.out_pt2);
+                    }
+                } else if self.poly2_contains_poly1(join.out_pt1, join.out_pt2) {
+                    // The second polygon contains the first one.
+                    let is_hole = self.poly_outs[out_rec1].is_hole;
+                    self.poly_outs[out_rec2].is_hole = is_hole;
+                    self.poly_outs[out_rec1].is_hole = !is_hole;
+                    self.poly_outs[out_rec2].first_left = self.poly_outs[out_rec1].first_left;
+                    self.poly_outs[out_rec1].first_left = Some(out_rec2);
+
+                    if (!is_hole ^ self.reverse_output) == (self.area(join.out_pt1) > 0.0) {
+                        self.reverse_poly_pt_links(join.out_pt1);
+                    }
+                } else {
+                    // The two polygons are completely separate.
+                    self.poly_outs[out_rec2].is_hole = self.poly_outs[out_rec1].is_hole;
+                    self.poly_outs[out_rec2].first_left = self.poly_outs[out_rec1].first_left;
+                }
+            } else {
+                // Two polygons have been joined.
+                self.poly_outs[out_rec2].pts = None;
+                self.poly_outs[out_rec2].bottom_pt = None;
+                self.poly_outs[out_rec2].idx = self.poly_outs[out_rec1].idx;
+
+                self.poly_outs[out_rec1].is_hole = self.poly_outs[hole_state_rec].is_hole;
+                if hole_state_rec == out_rec2 {
+                    self.poly_outs[out_rec1].first_left = self.poly_outs[out_rec2].first_left;
+                }
+                self.poly_outs[out_rec2].first_left = Some(out_rec1);
+            }
+        }
+    }
+
+    /// Removes duplicate points, and the middle point of collinear edges.
+    fn fixup_out_polygon(&mut self, out_rec: usize) {
+        let mut last_ok = None;
+        self.poly_outs[out_rec].bottom_pt = None;
+        let mut pp = self.poly_outs[out_rec]
+            .pts
+            .expect("polygon should have points");
+
+        loop {
+            let (prev, next) = (self.out_pts[pp].prev, self.out_pts[pp].next);
+            if prev == pp || prev == next {
+                self.poly_outs[out_rec].pts = None;
+                return;
+            }
+
+            let (pt, prev_pt, next_pt) = (
+                self.out_pts[pp].pt,
+                self.out_pts[prev].pt,
+                self.out_pts[next].pt,
+            );
+            if pt == next_pt || pt == prev_pt || slopes_equal3(prev_pt, pt, next_pt) {
+                last_ok = None;
+                self.out_pts[prev].next = next;
+                self.out_pts[next].prev = prev;
+                pp = prev;
+            } else if Some(pp) == last_ok {
+                break;
+            } else {
+                if last_ok.is_none() {
+                    last_ok = Some(pp);
+                }
+                pp = next;
+            }
+        }
+        self.poly_outs[out_rec].pts = Some(pp);
+    }
+
+    fn build_result(&self) -> Vec<Path> {
+        let mut polys = Vec::with_capacity(self.poly_outs.len());
+        for out_rec in &self.poly_outs {
+            let pts = match out_rec.pts {
+                Some(pts) => pts,
+                None => continue,
+            };
+
+            let mut p = self.out_pts[pts].prev;
+            let mut cnt = 0;
+            let mut q = p;
+            loop {
+                cnt += 1;
+                q = self.out_pts[q].next;
+                if q == p {
+                    break;
+                }
+            }
+            if cnt < 2 {
+                continue;
+            }
+
+            let mut path = Vec::with_capacity(cnt);
+            for _ in 0..cnt {
+                path.push(self.out_pts[p].pt);
+                p = self.out_pts[p].prev;
+            }
+            polys.push(path);
+        }
+        polys
+    }
+}
+
+/// `ClipperOffset`, for closed polygons.
+pub(super) struct ClipperOffset {
+    miter_limit: f64,
+    arc_tolerance: f64,
+    polygons: Vec<(Path, PolyJoinType)>,
+    lowest: Option<(usize, usize)>,
+
+    src_poly: Path,
+    dest_poly: Path,
+    normals: Vec<(f64, f64)>,
+    delta: f64,
+    sin_a: f64,
+    sin: f64,
+    cos: f64,
+    miter_lim: f64,
+    steps_per_rad: f64,
+}
+
+impl ClipperOffset {
+    pub(super) fn new(miter_limit: f64, arc_tolerance: f64) -> Self {
+        ClipperOffset {
+            miter_limit,
+            arc_tolerance,
+            polygons: Vec::new(),
+            lowest: None,
+            src_poly: Vec::new(),
+            dest_poly: Vec::new(),
+            normals: Vec::new(),
+            delta: 0.0,
+            sin_a: 0.0,
+            sin: 0.0,
+            cos: 0.0,
+            miter_lim: 0.0,
+            steps_per_rad: 0.0,
+        }
+    }
+
+    pub(super) fn add_path(&mut self, path: &[IntPoint], join_type: PolyJoinType) {
+        if path.is_empty() {
+            return;
+        }
+
+        // Strip duplicate points, and find the lowest point.
+        let mut high_i = path.len() - 1;
+        while high_i > 0 && path[0] == path[high_i] {
+            high_i -= 1;
+        }
+        let mut contour = vec![path[0]];
+        let (mut j, mut k) = (0, 0);
+        for &pt in &path[1..=high_i] {
+            if contour[j] != pt {
+                j += 1;
+                contour.push(pt);
+                if pt.y > contour[k].y || (pt.y == contour[k].y && pt.x < contour[k].x) {
+                    k = j;
+                }
+            }
+        }
+        if j < 2 {
+            return;
+        }
+
+        let lowest_pt = contour[k];
+        self.polygons.push((contour, join_type));
+
+        // Remember whether this path's lowest point is lower than all the others.
+        let is_lowest = match self.lowest {
+            None => true,
+            Some((i, k)) => {
+                let ip = self.polygons[i].0[k];
+                lowest_pt.y > ip.y || (lowest_pt.y == ip.y && lowest_pt.x < ip.x)
+            }
+        };
+        if is_lowest {
+            self.lowest = Some((self.polygons.len() - 1, k));
+        }
+    }
+
+    /// Offsets the polygons by `delta`, returning the solution's polygons.
+    pub(super) fn execute(&mut self, delta: f64) -> Vec<Path> {
+        self.fix_orientations();
+        let dest_polys = self.do_offset(delta);
+
+        // Clean up the corners.
+        let mut clipper = Clipper::new();
+        for path in &dest_polys {
+            clipper.add_path(path, PolyType::Subject);
+        }
+        if delta > 0.0 {
+            clipper.execute(
+                ClipType::Union,
+                PolyFillType::Positive,
+                PolyFillType::Positive,
+            )
+        } else {
+            let r = clipper.get_bounds();
+            let outer = [
+                IntPoint::new(r.left - 10, r.bottom + 10),
+                IntPoint::new(r.right + 10, r.bottom + 10),
+                IntPoint::new(r.right + 10, r.top - 10),
+                IntPoint::new(r.left - 10, r.top - 10),
+            ];
+            clipper.add_path(&outer, PolyType::Subject);
+            clipper.reverse_output = true;
+            let mut solution = clipper.execute(
+                ClipType::Union,
+                PolyFillType::Negative,
+                PolyFillType::Negative,
+            );
+            if !solution.is_empty() {
+                solution.remove(0);
+            }
+            solution
+        }
+    }
+
+    /// Reverses all polygons if the one with the lowest vertex is oriented clockwise.
+    fn fix_orientations(&mut self) {
+        if let Some((i, _)) = self.lowest {
+            if area(&self.polygons[i].0) < 0.0 {
+                for (contour, _) in &mut self.polygons {
+                    contour.reverse();
+                }
+            }
+        }
+    }
+
+    fn do_offset(&mut self, delta: f64) -> Vec<Path> {
+        self.delta = delta;
+
+        // Without an offset, just copy the polygons.
+        if delta > -TOLERANCE && delta < TOLERANCE {
+            return self
+                .polygons
+                .iter()
+                .map(|(contour, _)| contour.clone())
+                .collect();
+        }
+
+        self.miter_lim = if self.miter_limit > 2.0 {
+            2.0 / (self.miter_limit * self.miter_limit)
+        } else {
+            0.5
+        };
+
+        let y = if self.arc_tolerance <= 0.0 {
+            DEF_ARC_TOLERANCE
+        } else if self.arc_tolerance > delta.abs() * DEF_ARC_TOLERANCE {
+            delta.abs() * DEF_ARC_TOLERANCE
+        } else {
+            self.arc_tolerance
+        };
+        let mut steps = PI / (1.0 - y / delta.abs()).acos();
+        if steps > delta.abs() * PI {
+            // Excessive precision check.
+            steps = delta.abs() * PI;
+        }
+        self.sin = (TAU / steps).sin();
+        self.cos = (TAU / steps).cos();
+        self.steps_per_rad = steps / TAU;
+        if delta < 0.0 {
+            self.sin = -self.sin;
+        }
+
+        let mut dest_polys = Vec::with_capacity(self.polygons.len() * 2);
+        for i in 0..self.polygons.len() {
+            let (src_poly, join_type) = self.polygons[i].clone();
+            let len = src_poly.len();
+            self.src_poly = src_poly;
+            self.dest_poly = Vec::new();
+
+            self.normals = (0..len)
+                .map(|j| get_unit_normal(self.src_poly[j], self.src_poly[(j + 1) % len]))
+                .collect();
+
+            let mut k = len - 1;
+            for j in 0..len {
+                self.offset_point(j, &mut k, join_type);
+            }
+            dest_polys.push(std::mem::take(&mut self.dest_poly));
+        }
+        dest_polys
+    }
+
+    fn push_offset(&mut self, j: usize, x: f64, y: f64) {
+        let src = self.src_poly[j];
+        self.dest_poly.push(IntPoint::new(
+            round(src.x as f64 + x),
+            round(src.y as f64 + y),
+        ));
+    }
+
+    fn offset_point(&mut self, j: usize, k: &mut usize, join_type: PolyJoinType) {
+        let (nj, nk) = (self.normals[j], self.normals[*k]);
+        let delta = self.delta;
+
+        // Cross product.
+        self.sin_a = nk.0 * nj.1 - nj.0 * nk.1;
+        if (self.sin_a * delta).abs() < 1.0 {
+            // Dot product.
+            let cos_a = nk.0 * nj.0 + nj.1 * nk.1;
+            if cos_a > 0.0 {
+                // The angle is close to 0 degrees. Like the original, `k` is not updated.
+                self.push_offset(j, nk.0 * delta, nk.1 * delta);
+                return;
+            }
+            // Otherwise, the angle is close to 180 degrees.
+        } else {
+            self.sin_a = self.sin_a.clamp(-1.0, 1.0);
+        }
+
+        if self.sin_a * delta < 0.0 {
+            self.push_offset(j, nk.0 * delta, nk.1 * delta);
+            self.dest_poly.push(self.src_poly[j]);
+            self.push_offset(j, nj.0 * delta, nj.1 * delta);
+        } else {
+            match join_type {
+                PolyJoinType::Miter => {
+                    let r = 1.0 + (nj.0 * nk.0 + nj.1 * nk.1);
+                    if r >= self.miter_lim {
+                        self.do_miter(j, *k, r);
+                    } else {
+                        self.do_square(j, *k);
+                    }
+                }
+                PolyJoinType::Square => self.do_square(j, *k),
+                PolyJoinType::Round => self.do_round(j, *k),
+            }
+        }
+        *k = j;
+    }
+
+    fn do_square(&mut self, j: usize, k: usize) {
+        let (nj, nk) = (self.normals[j], self.normals[k]);
+        let delta = self.delta;
+        let dx = (self.sin_a.atan2(nk.0 * nj.0 + nk.1 * nj.1) / 4.0).tan();
+        self.push_offset(j, delta * (nk.0 - nk.1 * dx), delta * (nk.1 + nk.0 * dx));
+        self.push_offset(j, delta * (nj.0 + nj.1 * dx), delta * (nj.1 - nj.0 * dx));
+    }
+
+    fn do_miter(&mut self, j: usize, k: usize, r: f64) {
+        let (nj, nk) = (self.normals[j], self.normals[k]);
+        let q = self.delta / r;
+        self.push_offset(j, (nk.0 + nj.0) * q, (nk.1 + nj.1) * q);
+    }
+
+    fn do_round(&mut self, j: usize, k: usize) {
+        let (nj, nk) = (self.normals[j], self.normals[k]);
+        let delta = self.delta;
+        let a = self.sin_a.atan2(nk.0 * nj.0 + nk.1 * nj.1);
+        let steps = (round(self.steps_per_rad * a.abs()) as i32).max(1);
+
+        let (mut x, mut y) = nk;
+        for _ in 0..steps {
+            self.push_offset(j, x * delta, y * delta);
+            let x2 = x;
+            x = x * self.cos - self.sin * y;
+            y = x2 * self.sin + y * self.cos;
+        }
+        self.push_offset(j, nj.0 * delta, nj.1 * delta);
+    }
+}
+
+/// Rounds half away from zero, truncating the way the original does.
+fn round(val: f64) -> i64 {
+    if val < 0.0 {
+        (val - 0.5) as i64
+    } else {
+        (val + 0.5) as i64
+    }
+}
+
+fn area(path: &[IntPoint]) -> f64 {
+    let size = path.len();
+    if size < 3 {
+        return 0.0;
+    }
+
+    let mut a = 0.0;
+    let mut j = size - 1;
+    for i in 0..size {
+        a += (path[j].x as f64 + path[i].x as f64) * (path[j].y as f64 - path[i].y as f64);
+        j = i;
+    }
+    -a * 0.5
+}
+
+fn get_dx(pt1: IntPoint, pt2: IntPoint) -> f64 {
+    if pt1.y == pt2.y {
+        HORIZONTAL
+    } else {
+        (pt2.x - pt1.x) as f64 / (pt2.y - pt1.y) as f64
+    }
+}
+
+fn get_unit_normal(pt1: IntPoint, pt2: IntPoint) -> (f64, f64) {
+    if pt1 == pt2 {
+        return (0.0, 0.0);
+    }
+
+    let dx = (pt2.x - pt1.x) as f64;
+    let dy = (pt2.y - pt1.y) as f64;
+    let f = 1.0 / (dx * dx + dy * dy).sqrt();
+    (dy * f, -dx * f)
+}
+
+fn slopes_equal3(pt1: IntPoint, pt2: IntPoint, pt3: IntPoint) -> bool {
+    i128::from(pt1.y - pt2.y) * i128::from(pt2.x - pt3.x)
+        == i128::from(pt1.x - pt2.x) * i128::from(pt2.y - pt3.y)
+}
+
+fn slopes_equal4(pt1: IntPoint, pt2: IntPoint, pt3: IntPoint, pt4: IntPoint) -> bool {
+    i128::from(pt1.y - pt2.y) * i128::from(pt3.x - pt4.x)
+        == i128::from(pt1.x - pt2.x) * i128::from(pt3.y - pt4.y)
+}
+
+fn slopes_equal_edges(e1: &Edge, e2: &Edge) -> bool {
+    i128::from(e1.top.y - e1.bot.y) * i128::from(e2.top.x - e2.bot.x)
+        == i128::from(e1.top.x - e1.bot.x) * i128::from(e2.top.y - e2.bot.y)
+}
+
+fn horz_segments_overlap(mut seg1a: i64, mut seg1b: i64, mut seg2a: i64, mut seg2b: i64) -> bool {
+    if seg1a > seg1b {
+        std::mem::swap(&mut seg1a, &mut seg1b);
+    }
+    if seg2a > seg2b {
+        std::mem::swap(&mut seg2a, &mut seg2b);
+    }
+    seg1a < seg2b && seg2a < seg1b
+}
+
+fn get_overlap(a1: i64, a2: i64, b1: i64, b2: i64) -> Option<(i64, i64)> {
+    let (left, right) = if a1 < a2 {
+        if b1 < b2 {
+            (a1.max(b1), a2.min(b2))
+        } else {
+            (a1.max(b2), a2.min(b1))
+        }
+    } else if b1 < b2 {
+        (a2.max(b1), a1.min(b2))
+    } else {
+        (a2.max(b2), a1.min(b1))
+    };
+    (left < right).then_some((left, right))
+}
+
+fn intersect_point(e1: &Edge, e2: &Edge) -> IntPoint {
+    let mut ip = IntPoint::default();
+    if e1.dx == e2.dx {
+        ip.y = e1.curr.y;
+        ip.x = e1.top_x(ip.y);
+        return ip;
+    } else if e1.dx == 0.0 {
+        ip.x = e1.bot.x;
+        if e2.is_horizontal() {
+            ip.y = e2.bot.y;
+        } else {
+            let b2 = e2.bot.y as f64 - (e2.bot.x as f64 / e2.dx);
+            ip.y = round(ip.x as f64 / e2.dx + b2);
+        }
+    } else if e2.dx == 0.0 {
+        ip.x = e2.bot.x;
+        if e1.is_horizontal() {
+            ip.y = e1.bot.y;
+        } else {
+            let b1 = e1.bot.y as f64 - (e1.bot.x as f64 / e1.dx);
+            ip.y = round(ip.x as f64 / e1.dx + b1);
+        }
+    } else {
+        let b1 = e1.bot.x as f64 - e1.bot.y as f64 * e1.dx;
+        let b2 = e2.bot.x as f64 - e2.bot.y as f64 * e2.dx;
+        let q = (b2 - b1) / (e1.dx - e2.dx);
+        ip.y = round(q);
+        ip.x = if e1.dx.abs() < e2.dx.abs() {
+            round(e1.dx * q + b1)
+        } else {
+            round(e2.dx * q + b2)
+        };
+    }
+
+    if ip.y < e1.top.y || ip.y < e2.top.y {
+        ip.y = e1.top.y.max(e2.top.y);
+        ip.x = if e1.dx.abs() < e2.dx.abs() {
+            e1.top_x(ip.y)
+        } else {
+            e2.top_x(ip.y)
+        };
+    }
+    // Don't allow the point to be below the bottom of the scanbeam.
+    if ip.y > e1.curr.y {
+        ip.y = e1.curr.y;
+        // Use the more vertical edge to derive X.
+        ip.x = if e1.dx.abs() > e2.dx.abs() {
+            e2.top_x(ip.y)
+        } else {
+            e1.top_x(ip.y)
+        };
+    }
+    ip
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Sorting
+
+/// Below this length, libstdc++ sorts by insertion.
+const SORT_THRESHOLD: usize = 16;
+
+/// Sorts `v` exactly like libstdc++'s `std::sort`, which the engine's Linux and Windows builds use.
+/// The sort is not stable, and local minima and intersections at the same height are processed
+/// in the order it leaves them in.
+fn std_sort<T: Copy>(v: &mut [T], less: impl Fn(&T, &T) -> bool) {
+    if v.len() > 1 {
+        let depth_limit = 2 * (usize::BITS - 1 - v.len().leading_zeros()) as usize;
+        introsort_loop(v, depth_limit, &less);
+        final_insertion_sort(v, &less);
+    }
+}
+
+fn introsort_loop<T: Copy>(
+    mut v: &mut [T],
+    mut depth_limit: usize,
+    less: &impl Fn(&T, &T) -> bool,
+) {
+    while v.len() > SORT_THRESHOLD {
+        if depth_limit == 0 {
+            heap_sort(v, less);
+            return;
+        }
+        depth_limit -= 1;
+
+        // Move the median of three to the front as the pivot, and partition behind it.
+        let (mid, last) = (v.len() / 2, v.len() - 1);
+        let median = if less(&v[1], &v[mid]) {
+            if less(&v[mid], &v[last]) {
+                mid
+            } else if less(&v[1], &v[last]) {
+                last
+            } else {
+                1
+            }
+        } else if less(&v[1], &v[last]) {
+            1
+        } else if less(&v[mid], &v[last]) {
+            last
+        } else {
+            mid
+        };
+        v.swap(0, median);
+
+        let (mut first, mut last) = (1, v.len());
+        let cut = loop {
+            while less(&v[first], &v[0]) {
+                first += 1;
+            }
+            last -= 1;
+            while less(&v[0], &v[last]) {
+                last -= 1;
+            }
+            if first >= last {
+                break first;
+            }
+            v.swap(first, last);
+            first += 1;
+        };
+
+        let (left, right) = std::mem::take(&mut v).split_at_mut(cut);
+        introsort_loop(right, depth_limit, less);
+        v = left;
+    }
+}
+
+fn final_insertion_sort<T: Copy>(v: &mut [T], less: &impl Fn(&T, &T) -> bool) {
+    if v.len() > SORT_THRESHOLD {
+        insertion_sort(&mut v[..SORT_THRESHOLD], less);
+        for i in SORT_THRESHOLD..v.len() {
+            unguarded_linear_insert(v, i, less);
+        }
+    } else {
+        insertion_sort(v, less);
+    }
+}
+
+fn insertion_sort<T: Copy>(v: &mut [T], less: &impl Fn(&T, &T) -> bool) {
+    for i in 1..v.len() {
+        if less(&v[i], &v[0]) {
+            let val = v[i];
+            v.copy_within(0..i, 1);
+            v[0] = val;
+        } else {
+            unguarded_linear_insert(v, i, less);
+        }
+    }
+}
+
+/// Inserts `v[last]` into the sorted elements before it, which must include one not greater.
+fn unguarded_linear_insert<T: Copy>(v: &mut [T], mut last: usize, less: &impl Fn(&T, &T) -> bool) {
+    let val = v[last];
+    while less(&val, &v[last - 1]) {
+        v[last] = v[last - 1];
+        last -= 1;
+    }
+    v[last] = val;
+}
+
+fn heap_sort<T: Copy>(v: &mut [T], less: &impl Fn(&T, &T) -> bool) {
+    let len = v.len();
+    if len >= 2 {
+        let mut parent = (len - 2) / 2;
+        loop {
+            adjust_heap(v, parent, len, v[parent], less);
+            if parent == 0 {
+                break;
+            }
+            parent -= 1;
+        }
+    }
+
+    for last in (1..len).rev() {
+        let val = v[last];
+        v[last] = v[0];
+        adjust_heap(v, 0, last, val, less);
+    }
+}
+
+fn adjust_heap<T: Copy>(
+    v: &mut [T],
+    mut hole: usize,
+    len: usize,
+    val: T,
+    less: &impl Fn(&T, &T) -> bool,
+) {
+    let top = hole;
+    let mut second_child = hole;
+    while second_child < (len - 1) / 2 {
+        second_child = 2 * (second_child + 1);
+        if less(&v[second_child], &v[second_child - 1]) {
+            second_child -= 1;
+        }
+        v[hole] = v[second_child];
+        hole = second_child;
+    }
+    if len & 1 == 0 && second_child == (len - 2) / 2 {
+        second_child = 2 * (second_child + 1);
+        v[hole] = v[second_child - 1];
+        hole = second_child - 1;
+    }
+
+    while hole > top {
+        let parent = (hole - 1) / 2;
+        if !less(&v[parent], &val) {
+            break;
+        }
+        v[hole] = v[parent];
+        hole = parent;
+    }
+    v[hole] = val;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(points: &[(i64, i64)]) -> Path {
+        points.iter().map(|&(x, y)| IntPoint::new(x, y)).collect()
+    }
+
+    fn square(x: i64, y: i64, size: i64) -> Path {
+        path(&[(x, y), (x + size, y), (x + size, y + size), (x, y + size)])
+    }
+
+    fn execute(
+        clip_type: ClipType,
+        fill_type: PolyFillType,
+        subject: &Path,
+        clip: &Path,
+    ) -> Vec<Path> {
+        let mut clipper = Clipper::new();
+        clipper.add_path(subject, PolyType::Subject);
+        clipper.add_path(clip, PolyType::Clip);
+        clipper.execute(clip_type, fill_type, fill_type)
+    }
+
+    fn offset(polygon: &Path, join_type: PolyJoinType, delta: f64) -> Vec<Path> {
+        let mut offset = ClipperOffset::new(2.0, 25.0);
+        offset.add_path(polygon, join_type);
+        offset.execute(delta)
+    }
+
+    // The expected polygons include their order, orientation and starting vertex, which the
+    // engine passes on unchanged.
+
+    #[test]
+    fn boolean_operations() {
+        let a = square(0, 0, 10);
+        let b = square(5, 5, 10);
+        let run = |clip_type| execute(clip_type, PolyFillType::EvenOdd, &a, &b);
+
+        assert_eq!(
+            run(ClipType::Union),
+            vec![path(&[
+                (10, 5),
+                (15, 5),
+                (15, 15),
+                (5, 15),
+                (5, 10),
+                (0, 10),
+                (0, 0),
+                (10, 0),
+            ])],
+        );
+        assert_eq!(
+            run(ClipType::Intersection),
+            vec![path(&[(10, 10), (5, 10), (5, 5), (10, 5)])],
+        );
+        assert_eq!(
+            run(ClipType::Difference),
+            vec![path(&[(10, 5), (5, 5), (5, 10), (0, 10), (0, 0), (10, 0)])],
+        );
+        assert_eq!(
+            run(ClipType::Xor),
+            vec![
+                path(&[(15, 15), (5, 15), (5, 10), (10, 10), (10, 5), (15, 5)]),
+                path(&[(10, 5), (5, 5), (5, 10), (0, 10), (0, 0), (10, 0)]),
+            ],
+        );
+    }
+
+    #[test]
+    fn boolean_operations_with_holes() {
+        let outer = square(0, 0, 30);
+        let inner = square(10, 10, 10);
+        let run = |clip_type| execute(clip_type, PolyFillType::EvenOdd, &outer, &inner);
+
+        assert_eq!(
+            run(ClipType::Union),
+            vec![path(&[(30, 30), (0, 30), (0, 0), (30, 0)])],
+        );
+        assert_eq!(
+            run(ClipType::Intersection),
+            vec![path(&[(20, 20), (10, 20), (10, 10), (20, 10)])],
+        );
+
+        // Holes are oriented opposite to their outer polygon.
+        let with_hole = vec![
+            path(&[(30, 30), (0, 30), (0, 0), (30, 0)]),
+            path(&[(10, 10), (10, 20), (20, 20), (20, 10)]),
+        ];
+        assert_eq!(run(ClipType::Difference), with_hole);
+        assert_eq!(run(ClipType::Xor), with_hole);
+    }
+
+    #[test]
+    fn rounded_intersections() {
+        // The triangle's edges cross y = 15 at x = 7.5 and x = 12.5, which are rounded along
+        // each edge from its bottom.
+        let triangle = path(&[(0, 0), (20, 0), (10, 20)]);
+        let square = square(5, 5, 10);
+        let run = |clip_type| execute(clip_type, PolyFillType::EvenOdd, &triangle, &square);
+
+        assert_eq!(
+            run(ClipType::Union),
+            vec![path(&[
+                (15, 10),
+                (15, 15),
+                (13, 15),
+                (10, 20),
+                (7, 15),
+                (5, 15),
+                (5, 10),
+                (0, 0),
+                (20, 0),
+            ])],
+        );
+        assert_eq!(
+            run(ClipType::Intersection),
+            vec![path(&[
+                (15, 10),
+                (13, 15),
+                (7, 15),
+                (5, 10),
+                (5, 5),
+                (15, 5)
+            ])],
+        );
+        assert_eq!(
+            run(ClipType::Difference),
+            vec![
+                path(&[(10, 20), (7, 15), (13, 15)]),
+                path(&[(15, 10), (15, 5), (5, 5), (5, 10), (0, 0), (20, 0)]),
+            ],
+        );
+    }
+
+    #[test]
+    fn fill_types() {
+        // The left lobe winds once counter-clockwise, the right lobe once clockwise.
+        let bowtie = path(&[(0, 0), (10, 10), (10, 0), (0, 10)]);
+        let square = square(0, 0, 5);
+        let run = |clip_type, fill_type| execute(clip_type, fill_type, &bowtie, &square);
+
+        assert_eq!(
+            run(ClipType::Union, PolyFillType::EvenOdd),
+            vec![
+                path(&[(10, 10), (5, 5), (10, 0)]),
+                path(&[(5, 5), (0, 10), (0, 0), (5, 0)]),
+            ],
+        );
+        assert_eq!(
+            run(ClipType::Union, PolyFillType::Positive),
+            vec![path(&[(5, 5), (0, 10), (0, 0), (5, 0)])],
+        );
+        assert_eq!(
+            run(ClipType::Intersection, PolyFillType::Positive),
+            vec![path(&[(5, 5), (0, 5), (0, 0)])],
+        );
+        assert_eq!(
+            run(ClipType::Difference, PolyFillType::EvenOdd),
+            vec![
+                path(&[(10, 10), (5, 5), (10, 0)]),
+                path(&[(0, 10), (0, 5), (5, 5)]),
+            ],
+        );
+        assert_eq!(
+            run(ClipType::Difference, PolyFillType::Positive),
+            vec![path(&[(0, 10), (0, 5), (5, 5)])],
+        );
+        assert!(run(ClipType::Intersection, PolyFillType::Negative).is_empty());
+    }
+
+    #[test]
+    fn invalid_paths() {
+        let mut clipper = Clipper::new();
+        assert!(!clipper.add_path(&path(&[(0, 0), (10, 0), (0, 0)]), PolyType::Subject));
+        assert!(!clipper.add_path(&path(&[(0, 0), (10, 0), (20, 0)]), PolyType::Subject));
+        assert!(clipper.add_path(&square(0, 0, 10), PolyType::Subject));
+        assert!(!clipper.add_path(&square(HI_RANGE, 0, 10), PolyType::Clip));
+        assert!(clipper
+            .execute(
+                ClipType::Union,
+                PolyFillType::EvenOdd,
+                PolyFillType::EvenOdd
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn offset_joins() {
+        let polygon = square(0, 0, 1000);
+
+        assert_eq!(
+            offset(&polygon, PolyJoinType::Miter, 100.0),
+            vec![path(&[
+                (1100, 1100),
+                (-100, 1100),
+                (-100, -100),
+                (1100, -100)
+            ])],
+        );
+        // Corners are cut at tan(pi / 8) from the corner's offset edges.
+        assert_eq!(
+            offset(&polygon, PolyJoinType::Square, 100.0),
+            vec![path(&[
+                (1100, -41),
+                (1100, 1041),
+                (1041, 1100),
+                (-41, 1100),
+                (-100, 1041),
+                (-100, -41),
+                (-41, -100),
+                (1041, -100),
+            ])],
+        );
+        // An arc tolerance of a quarter of the offset leaves a single step for right angles.
+        assert_eq!(
+            offset(&polygon, PolyJoinType::Round, 100.0),
+            vec![path(&[
+                (1100, 0),
+                (1100, 1000),
+                (1000, 1100),
+                (0, 1100),
+                (-100, 1000),
+                (-100, 0),
+                (0, -100),
+                (1000, -100),
+            ])],
+        );
+
+        for join_type in [
+            PolyJoinType::Square,
+            PolyJoinType::Round,
+            PolyJoinType::Miter,
+        ] {
+            assert_eq!(
+                offset(&polygon, join_type, -100.0),
+                vec![path(&[(900, 900), (100, 900), (100, 100), (900, 100)])],
+            );
+        }
+    }
+
+    #[test]
+    fn offset_concave() {
+        let notched = path(&[(0, 0), (1000, 0), (1000, 1000), (500, 500), (0, 1000)]);
+
+        // The corners next to the notch exceed the miter limit, and are squared off instead.
+        let inflated = vec![path(&[
+            (1100, 1067),
+            (977, 1118),
+            (500, 642),
+            (23, 1118),
+            (-100, 1067),
+            (-100, -100),
+            (1100, -100),
+        ])];
+        assert_eq!(offset(&notched, PolyJoinType::Miter, 100.0), inflated);
+
+        // Orientation of the input doesn't matter.
+        let mut reversed = notched.clone();
+        reversed.reverse();
+        assert_eq!(offset(&reversed, PolyJoinType::Miter, 100.0), inflated);
+
+        assert_eq!(
+            offset(&notched, PolyJoinType::Round, 100.0),
+            vec![path(&[
+                (1100, 0),
+                (1100, 1000),
+                (1013, 1099),
+                (929, 1071),
+                (500, 642),
+                (71, 1071),
+                (-61, 1079),
+                (-100, 1000),
+                (-100, 0),
+                (0, -100),
+                (1000, -100),
+            ])],
+        );
+        assert_eq!(
+            offset(&notched, PolyJoinType::Miter, -100.0),
+            vec![path(&[
+                (900, 758),
+                (500, 359),
+                (100, 758),
+                (100, 100),
+                (900, 100)
+            ])],
+        );
+        assert_eq!(
+            offset(&notched, PolyJoinType::Square, -100.0),
+            vec![path(&[
+                (900, 758),
+                (541, 400),
+                (459, 400),
+                (100, 758),
+                (100, 100),
+                (900, 100),
+            ])],
+        );
+    }
+
+    #[test]
+    fn offset_splits_and_vanishes() {
+        let notched = path(&[(0, 0), (1000, 0), (1000, 1000), (500, 100), (0, 1000)]);
+        assert_eq!(
+            offset(&notched, PolyJoinType::Miter, -150.0),
+            vec![
+                path(&[(150, 421), (150, 150), (301, 150)]),
+                path(&[(850, 421), (699, 150), (850, 150)]),
+            ],
+        );
+
+        assert!(offset(&square(0, 0, 1000), PolyJoinType::Miter, -600.0).is_empty());
+        assert!(offset(
+            &path(&[(0, 0), (1000, 0), (0, 0)]),
+            PolyJoinType::Miter,
+            100.0
+        )
+        .is_empty());
+    }
+}
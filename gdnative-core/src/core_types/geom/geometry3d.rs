@@ -0,0 +1,817 @@
+//! Pure-Rust ports of the 3D algorithms of the engine's `Geometry` singleton.
+//!
+//! Unlike the singleton, these functions do not call into the engine, so they can be used on any
+//! thread. The algorithms, tolerances and corner cases follow the engine's.
+//!
+//! Shapes like cylinders and capsules are centered at the origin, along one of the coordinate
+//! axes. To test against a shape with a [`Transform`][crate::core_types::Transform], convert the
+//! segment into the shape's local space first with
+//! [`Transform::xform_inv`][crate::core_types::Transform::xform_inv], and the results back with
+//! [`Transform::xform`][crate::core_types::Transform::xform].
+//!
+//! Plane lists, as accepted by [`segment_intersects_convex`] and [`compute_convex_mesh_points`]
+//! and returned by the `build_*_planes` functions, describe convex shapes as the space behind all
+//! of their planes. Their normals point outwards.
+
+use crate::core_types::{real, real_consts, Axis, Plane, Vector3, CMP_EPSILON};
+
+/// Intersection of a segment with the surface of a shape.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct SurfaceHit {
+    /// Point where the segment enters the shape.
+    pub position: Vector3,
+    /// Normal of the surface at `position`.
+    pub normal: Vector3,
+}
+
+/// Returns the point where the ray starting at `from` in direction `dir` hits the triangle `a`,
+/// `b`, `c`, or `None` if it doesn't. Both sides of the triangle are hit.
+#[inline]
+pub fn ray_intersects_triangle(
+    from: Vector3,
+    dir: Vector3,
+    a: Vector3,
+    b: Vector3,
+    c: Vector3,
+) -> Option<Vector3> {
+    let t = triangle_intersection(from, dir, a, b, c)?;
+    if t > CMP_EPSILON as real {
+        Some(from + dir * t)
+    } else {
+        None
+    }
+}
+
+/// Returns the point where the segment `from`-`to` hits the triangle `a`, `b`, `c`, or `None` if
+/// it doesn't. Both sides of the triangle are hit.
+#[inline]
+pub fn segment_intersects_triangle(
+    from: Vector3,
+    to: Vector3,
+    a: Vector3,
+    b: Vector3,
+    c: Vector3,
+) -> Option<Vector3> {
+    let rel = to - from;
+    let t = triangle_intersection(from, rel, a, b, c)?;
+    if t > CMP_EPSILON as real && t <= 1.0 {
+        Some(from + rel * t)
+    } else {
+        None
+    }
+}
+
+/// Möller–Trumbore intersection, returning the position along `dir` where the line hits.
+fn triangle_intersection(
+    from: Vector3,
+    dir: Vector3,
+    v0: Vector3,
+    v1: Vector3,
+    v2: Vector3,
+) -> Option<real> {
+    let e1 = v1 - v0;
+    let e2 = v2 - v0;
+    let h = dir.cross(e2);
+    let a = e1.dot(h);
+    if a.abs() < CMP_EPSILON as real {
+        // Parallel to the triangle.
+        return None;
+    }
+
+    let f = 1.0 / a;
+    let s = from - v0;
+    let u = f * s.dot(h);
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = s.cross(e1);
+    let v = f * dir.dot(q);
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    Some(f * e2.dot(q))
+}
+
+/// Returns where the segment `from`-`to` enters the sphere at `sphere_position`, or `None` if it
+/// doesn't.
+#[inline]
+pub fn segment_intersects_sphere(
+    from: Vector3,
+    to: Vector3,
+    sphere_position: Vector3,
+    sphere_radius: real,
+) -> Option<SurfaceHit> {
+    let sphere_pos = sphere_position - from;
+    let rel = to - from;
+    let rel_len = rel.length();
+    if rel_len < CMP_EPSILON as real {
+        return None;
+    }
+
+    let normal = rel / rel_len;
+    let sphere_d = normal.dot(sphere_pos);
+    let ray_distance = sphere_pos.distance_to(normal * sphere_d);
+    if ray_distance >= sphere_radius {
+        return None;
+    }
+
+    let inters_d2 = sphere_radius * sphere_radius - ray_distance * ray_distance;
+    let mut inters_d = sphere_d;
+    if inters_d2 >= CMP_EPSILON as real {
+        inters_d -= inters_d2.sqrt();
+    }
+
+    if inters_d < 0.0 || inters_d > rel_len {
+        return None;
+    }
+
+    let position = from + normal * inters_d;
+    Some(SurfaceHit {
+        position,
+        normal: (position - sphere_position).normalized(),
+    })
+}
+
+/// Returns where the segment `from`-`to` enters the cylinder of the given `height` and `radius`,
+/// centered at the origin along `axis`, or `None` if it doesn't.
+///
+/// Segments starting inside the cylinder hit it at `from`.
+#[inline]
+pub fn segment_intersects_cylinder(
+    from: Vector3,
+    to: Vector3,
+    height: real,
+    radius: real,
+    axis: Axis,
+) -> Option<SurfaceHit> {
+    let rel = to - from;
+    let rel_len = rel.length();
+    if rel_len < CMP_EPSILON as real {
+        return None;
+    }
+
+    let axis = axis as usize;
+    let cylinder_axis = unit(axis);
+
+    let normal = rel / rel_len;
+    let crs = normal.cross(cylinder_axis);
+    let crs_len = crs.length();
+    let axis_dir = if crs_len < CMP_EPSILON as real {
+        // Parallel to the axis, any side axis will do.
+        unit((axis + 1) % 3)
+    } else {
+        crs / crs_len
+    };
+
+    let dist = axis_dir.dot(from);
+    if dist >= radius {
+        return None;
+    }
+
+    // Continue in 2D, on the plane through the segment and parallel to the axis.
+    let w2 = radius * radius - dist * dist;
+    if w2 < CMP_EPSILON as real {
+        return None;
+    }
+
+    let size = [w2.sqrt(), height * 0.5];
+    let side_dir = axis_dir.cross(cylinder_axis).normalized();
+    let from_2d = [side_dir.dot(from), from.as_ref()[axis]];
+    let to_2d = [side_dir.dot(to), to.as_ref()[axis]];
+
+    let mut min: real = 0.0;
+    let mut max: real = 1.0;
+    let mut hit_side = false;
+
+    for i in 0..2 {
+        let (seg_from, seg_to) = (from_2d[i], to_2d[i]);
+        let (box_begin, box_end) = (-size[i], size[i]);
+        let length = seg_to - seg_from;
+
+        let (cmin, cmax) = if seg_from < seg_to {
+            if seg_from > box_end || seg_to < box_begin {
+                return None;
+            }
+            (
+                if seg_from < box_begin {
+                    (box_begin - seg_from) / length
+                } else {
+                    0.0
+                },
+                if seg_to > box_end {
+                    (box_end - seg_from) / length
+                } else {
+                    1.0
+                },
+            )
+        } else {
+            if seg_to > box_end || seg_from < box_begin {
+                return None;
+            }
+            (
+                if seg_from > box_end {
+                    (box_end - seg_from) / length
+                } else {
+                    0.0
+                },
+                if seg_to < box_begin {
+                    (box_begin - seg_from) / length
+                } else {
+                    1.0
+                },
+            )
+        };
+
+        if cmin > min {
+            min = cmin;
+            hit_side = i == 0;
+        }
+        if cmax < max {
+            max = cmax;
+        }
+        if max < min {
+            return None;
+        }
+    }
+
+    let position = from + rel * min;
+    let mut normal = *position.as_ref();
+    if hit_side {
+        normal[axis] = 0.0;
+    } else {
+        normal[(axis + 1) % 3] = 0.0;
+        normal[(axis + 2) % 3] = 0.0;
+    }
+
+    Some(SurfaceHit {
+        position,
+        normal: Vector3::new(normal[0], normal[1], normal[2]).normalized(),
+    })
+}
+
+/// Returns where the segment `from`-`to` enters the capsule with a cylinder of the given `height`
+/// and `radius`, centered at the origin along `axis`, or `None` if it doesn't.
+///
+/// `height` excludes the hemispherical caps, like the `height` of the engine's `CapsuleShape`.
+#[inline]
+pub fn segment_intersects_capsule(
+    from: Vector3,
+    to: Vector3,
+    height: real,
+    radius: real,
+    axis: Axis,
+) -> Option<SurfaceHit> {
+    let dir = (to - from).normalized();
+    let cap = axis.to_unit_vector() * (height * 0.5);
+
+    [
+        segment_intersects_cylinder(from, to, height, radius, axis),
+        segment_intersects_sphere(from, to, cap, radius),
+        segment_intersects_sphere(from, to, -cap, radius),
+    ]
+    .into_iter()
+    .flatten()
+    .min_by(|a, b| {
+        dir.dot(a.position)
+            .partial_cmp(&dir.dot(b.position))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    })
+}
+
+/// Returns where the segment `from`-`to` enters the convex shape bounded by `planes`, or `None`
+/// if it doesn't.
+///
+/// Segments starting inside the shape don't hit it.
+///
+/// # Panics
+///
+/// If the normal of any plane doesn't have unit length.
+#[inline]
+pub fn segment_intersects_convex(
+    from: Vector3,
+    to: Vector3,
+    planes: &[Plane],
+) -> Option<SurfaceHit> {
+    let rel = to - from;
+    let rel_len = rel.length();
+    if rel_len < CMP_EPSILON as real {
+        return None;
+    }
+
+    let dir = rel / rel_len;
+    let mut min: real = -1e20;
+    let mut max: real = 1e20;
+    let mut min_plane = None;
+
+    for plane in planes {
+        let den = plane.normal.dot(dir);
+        if den.abs() <= CMP_EPSILON as real {
+            // Parallel to the plane.
+            continue;
+        }
+
+        let dist = -plane.distance_to(from) / den;
+        if den > 0.0 {
+            // Leaving through this plane.
+            max = max.min(dist);
+        } else if dist > min {
+            min = dist;
+            min_plane = Some(plane);
+        }
+    }
+
+    let plane = min_plane?;
+    if max <= min || min < 0.0 || min > rel_len {
+        return None;
+    }
+
+    Some(SurfaceHit {
+        position: from + dir * min,
+        normal: plane.normal,
+    })
+}
+
+/// Returns the point on the segment `from`-`to` that is closest to `point`.
+#[inline]
+pub fn closest_point_to_segment(point: Vector3, from: Vector3, to: Vector3) -> Vector3 {
+    let p = point - from;
+    let n = to - from;
+    let l2 = n.length_squared();
+    if l2 < 1e-20 {
+        return from;
+    }
+
+    let d = n.dot(p) / l2;
+    if d <= 0.0 {
+        from
+    } else if d >= 1.0 {
+        to
+    } else {
+        from + n * d
+    }
+}
+
+/// Returns the point on the line through `from` and `to` that is closest to `point`.
+#[inline]
+pub fn closest_point_to_segment_uncapped(point: Vector3, from: Vector3, to: Vector3) -> Vector3 {
+    let p = point - from;
+    let n = to - from;
+    let l2 = n.length_squared();
+    if l2 < 1e-20 {
+        return from;
+    }
+
+    from + n * (n.dot(p) / l2)
+}
+
+/// Returns the pair of closest points between the segments `p1`-`q1` and `p2`-`q2`, the first
+/// one on `p1`-`q1` and the second one on `p2`-`q2`.
+///
+/// For parallel segments, the pair closest to `p1` is returned.
+#[inline]
+pub fn closest_points_between_segments(
+    p1: Vector3,
+    q1: Vector3,
+    p2: Vector3,
+    q2: Vector3,
+) -> (Vector3, Vector3) {
+    let epsilon = CMP_EPSILON as real;
+
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.dot(d1);
+    let e = d2.dot(d2);
+    let f = d2.dot(r);
+
+    if a <= epsilon && e <= epsilon {
+        return (p1, p2);
+    }
+
+    let (s, t) = if a <= epsilon {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(r);
+        if e <= epsilon {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+            let s = if denom != 0.0 {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+
+            let t = (b * s + f) / e;
+            if t < 0.0 {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else if t > 1.0 {
+                (((b - c) / a).clamp(0.0, 1.0), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
+/// Returns the distance between the closest points of the segments `p1`-`q1` and `p2`-`q2`.
+#[inline]
+pub fn closest_distance_between_segments(
+    p1: Vector3,
+    q1: Vector3,
+    p2: Vector3,
+    q2: Vector3,
+) -> real {
+    let (c1, c2) = closest_points_between_segments(p1, q1, p2, q2);
+    c1.distance_to(c2)
+}
+
+/// Returns the vertices of the convex shape bounded by `planes`.
+///
+/// Every point where three planes intersect and which is not in front of any other plane is a
+/// vertex. Vertices shared by more than three planes are returned several times.
+///
+/// # Panics
+///
+/// If the normal of any plane doesn't have unit length.
+#[inline]
+pub fn compute_convex_mesh_points(planes: &[Plane]) -> Vec<Vector3> {
+    let mut points = Vec::new();
+
+    for i in (0..planes.len()).rev() {
+        for j in (0..i).rev() {
+            for k in (0..j).rev() {
+                let point = match planes[i].intersect_3(planes[j], planes[k]) {
+                    Some(point) => point,
+                    None => continue,
+                };
+
+                let excluded = planes.iter().enumerate().any(|(n, plane)| {
+                    n != i
+                        && n != j
+                        && n != k
+                        && plane.normal.dot(point) - plane.d > CMP_EPSILON as real
+                });
+
+                if !excluded {
+                    points.push(point);
+                }
+            }
+        }
+    }
+
+    points
+}
+
+/// Returns the 6 planes of a box centered at the origin, with the given half-extents.
+#[inline]
+pub fn build_box_planes(extents: Vector3) -> Vec<Plane> {
+    vec![
+        Plane {
+            normal: Vector3::RIGHT,
+            d: extents.x,
+        },
+        Plane {
+            normal: Vector3::LEFT,
+            d: extents.x,
+        },
+        Plane {
+            normal: Vector3::UP,
+            d: extents.y,
+        },
+        Plane {
+            normal: Vector3::DOWN,
+            d: extents.y,
+        },
+        Plane {
+            normal: Vector3::BACK,
+            d: extents.z,
+        },
+        Plane {
+            normal: Vector3::FORWARD,
+            d: extents.z,
+        },
+    ]
+}
+
+/// Returns the planes of a prism with `sides` faces approximating a cylinder, centered at the
+/// origin along `axis`, followed by its two caps.
+#[inline]
+pub fn build_cylinder_planes(radius: real, height: real, sides: usize, axis: Axis) -> Vec<Plane> {
+    let axis_vec = axis.to_unit_vector();
+
+    let mut planes: Vec<Plane> = (0..sides)
+        .map(|i| Plane {
+            normal: side_normal(axis, i, sides),
+            d: radius,
+        })
+        .collect();
+
+    planes.push(Plane {
+        normal: axis_vec,
+        d: height * 0.5,
+    });
+    planes.push(Plane {
+        normal: -axis_vec,
+        d: height * 0.5,
+    });
+
+    planes
+}
+
+/// Returns the planes of a polyhedron approximating a sphere centered at the origin, with `lons`
+/// meridians and `lats` parallels per hemisphere around `axis`.
+#[inline]
+pub fn build_sphere_planes(radius: real, lats: usize, lons: usize, axis: Axis) -> Vec<Plane> {
+    build_rounded_planes(radius, 0.0, lats, lons, axis)
+}
+
+/// Returns the planes of a polyhedron approximating a capsule centered at the origin along
+/// `axis`, with `sides` faces around its cylinder and `lats` parallels per cap.
+///
+/// `height` excludes the hemispherical caps, like the `height` of the engine's `CapsuleShape`.
+#[inline]
+pub fn build_capsule_planes(
+    radius: real,
+    height: real,
+    sides: usize,
+    lats: usize,
+    axis: Axis,
+) -> Vec<Plane> {
+    build_rounded_planes(radius, height, lats, sides, axis)
+}
+
+fn build_rounded_planes(
+    radius: real,
+    height: real,
+    lats: usize,
+    lons: usize,
+    axis: Axis,
+) -> Vec<Plane> {
+    let index = axis as usize;
+    let axis_vec = axis.to_unit_vector();
+
+    // Mirrors points and normals to the opposite hemisphere.
+    let mut mirror = [1.0; 3];
+    mirror[index] = -1.0;
+    let mirror = Vector3::new(mirror[0], mirror[1], mirror[2]);
+
+    let mut planes = Vec::with_capacity(lons * (1 + 2 * lats));
+    for i in 0..lons {
+        let normal = side_normal(axis, i, lons);
+        planes.push(Plane { normal, d: radius });
+
+        for j in 1..=lats {
+            let angle = normal
+                .linear_interpolate(axis_vec, j as real / lats as real)
+                .normalized();
+            let pos = axis_vec * (height * 0.5) + angle * radius;
+
+            planes.push(plane_through(pos, angle));
+            planes.push(plane_through(pos * mirror, angle * mirror));
+        }
+    }
+
+    planes
+}
+
+/// Normal of side `i` of `sides` around `axis`.
+fn side_normal(axis: Axis, i: usize, sides: usize) -> Vector3 {
+    let index = axis as usize;
+    let (sin, cos) = (i as real * real_consts::TAU / sides as real).sin_cos();
+
+    let mut normal = [0.0; 3];
+    normal[(index + 1) % 3] = cos;
+    normal[(index + 2) % 3] = sin;
+    Vector3::new(normal[0], normal[1], normal[2])
+}
+
+fn plane_through(point: Vector3, normal: Vector3) -> Plane {
+    Plane {
+        normal,
+        d: normal.dot(point),
+    }
+}
+
+fn unit(index: usize) -> Vector3 {
+    let mut v = [0.0; 3];
+    v[index] = 1.0;
+    Vector3::new(v[0], v[1], v[2])
+}
+
+// Used by `Face3`.
+pub(crate) fn is_zero_area(a: Vector3, b: Vector3, c: Vector3) -> bool {
+    (a - b).cross(a - c).length_squared() < (CMP_EPSILON * CMP_EPSILON) as real
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_types::IsEqualApprox;
+
+    fn v(x: real, y: real, z: real) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+
+    impl SurfaceHit {
+        fn is_equal_approx(self, position: Vector3, normal: Vector3) -> bool {
+            self.position.is_equal_approx(position) && self.normal.is_equal_approx(normal)
+        }
+    }
+
+    #[test]
+    fn triangle_intersections() {
+        let (a, b, c) = (v(0.0, 0.0, 0.0), v(2.0, 0.0, 0.0), v(0.0, 2.0, 0.0));
+
+        assert_eq!(
+            ray_intersects_triangle(v(0.5, 0.5, 1.0), v(0.0, 0.0, -1.0), a, b, c),
+            Some(v(0.5, 0.5, 0.0)),
+        );
+        // Back faces are hit too.
+        assert_eq!(
+            ray_intersects_triangle(v(0.5, 0.5, -1.0), v(0.0, 0.0, 1.0), a, b, c),
+            Some(v(0.5, 0.5, 0.0)),
+        );
+        assert_eq!(
+            ray_intersects_triangle(v(0.5, 0.5, 1.0), v(0.0, 0.0, 1.0), a, b, c),
+            None,
+        );
+        assert_eq!(
+            ray_intersects_triangle(v(1.5, 1.5, 1.0), v(0.0, 0.0, -1.0), a, b, c),
+            None,
+        );
+        assert_eq!(
+            ray_intersects_triangle(v(0.5, 0.5, 1.0), v(1.0, 0.0, 0.0), a, b, c),
+            None,
+        );
+
+        assert_eq!(
+            segment_intersects_triangle(v(0.5, 0.5, 1.0), v(0.5, 0.5, -1.0), a, b, c),
+            Some(v(0.5, 0.5, 0.0)),
+        );
+        assert_eq!(
+            segment_intersects_triangle(v(0.5, 0.5, 2.0), v(0.5, 0.5, 1.0), a, b, c),
+            None,
+        );
+    }
+
+    #[test]
+    fn sphere_intersection() {
+        let hit =
+            segment_intersects_sphere(v(-5.0, 0.0, 0.0), v(5.0, 0.0, 0.0), v(1.0, 0.0, 0.0), 2.0)
+                .unwrap();
+        assert!(hit.is_equal_approx(v(-1.0, 0.0, 0.0), v(-1.0, 0.0, 0.0)));
+
+        assert_eq!(
+            segment_intersects_sphere(v(-5.0, 3.0, 0.0), v(5.0, 3.0, 0.0), v(1.0, 0.0, 0.0), 2.0),
+            None,
+        );
+        assert_eq!(
+            segment_intersects_sphere(v(-5.0, 0.0, 0.0), v(-4.0, 0.0, 0.0), v(1.0, 0.0, 0.0), 2.0),
+            None,
+        );
+        // Starting inside.
+        assert_eq!(
+            segment_intersects_sphere(v(1.0, 0.0, 0.0), v(5.0, 0.0, 0.0), v(1.0, 0.0, 0.0), 2.0),
+            None,
+        );
+    }
+
+    #[test]
+    fn cylinder_intersection() {
+        let side =
+            segment_intersects_cylinder(v(-5.0, 0.0, 0.5), v(5.0, 0.0, 0.5), 4.0, 1.0, Axis::Z)
+                .unwrap();
+        assert!(side.is_equal_approx(v(-1.0, 0.0, 0.5), v(-1.0, 0.0, 0.0)));
+
+        let cap =
+            segment_intersects_cylinder(v(0.5, 0.0, 5.0), v(0.5, 0.0, -5.0), 4.0, 1.0, Axis::Z)
+                .unwrap();
+        assert!(cap.is_equal_approx(v(0.5, 0.0, 2.0), v(0.0, 0.0, 1.0)));
+
+        let along_y =
+            segment_intersects_cylinder(v(0.0, -5.0, 0.0), v(0.0, 5.0, 0.0), 4.0, 1.0, Axis::Y)
+                .unwrap();
+        assert!(along_y.is_equal_approx(v(0.0, -2.0, 0.0), v(0.0, -1.0, 0.0)));
+
+        assert_eq!(
+            segment_intersects_cylinder(v(-5.0, 2.0, 0.0), v(5.0, 2.0, 0.0), 4.0, 1.0, Axis::Z),
+            None,
+        );
+        assert_eq!(
+            segment_intersects_cylinder(v(-5.0, 0.0, 3.0), v(5.0, 0.0, 3.0), 4.0, 1.0, Axis::Z),
+            None,
+        );
+    }
+
+    #[test]
+    fn capsule_intersection() {
+        // Through the cap.
+        let hit =
+            segment_intersects_capsule(v(0.0, 0.0, 5.0), v(0.0, 0.0, -5.0), 2.0, 1.0, Axis::Z)
+                .unwrap();
+        assert!(hit.is_equal_approx(v(0.0, 0.0, 2.0), v(0.0, 0.0, 1.0)));
+
+        // Through the side.
+        let hit =
+            segment_intersects_capsule(v(5.0, 0.0, 0.0), v(-5.0, 0.0, 0.0), 2.0, 1.0, Axis::Z)
+                .unwrap();
+        assert!(hit.is_equal_approx(v(1.0, 0.0, 0.0), v(1.0, 0.0, 0.0)));
+
+        // Beside the cap's rounded edge.
+        let hit =
+            segment_intersects_capsule(v(5.0, 0.0, 1.9), v(-5.0, 0.0, 1.9), 2.0, 1.0, Axis::Z)
+                .unwrap();
+        assert!(hit.position.is_equal_approx(v(0.43589, 0.0, 1.9)));
+        assert!(hit.normal.is_equal_approx(v(0.43589, 0.0, 0.9)));
+        assert_eq!(
+            segment_intersects_capsule(v(5.0, 0.0, 2.5), v(-5.0, 0.0, 2.5), 2.0, 1.0, Axis::Z),
+            None,
+        );
+    }
+
+    #[test]
+    fn convex_intersection() {
+        let planes = build_box_planes(v(1.0, 2.0, 3.0));
+
+        let hit = segment_intersects_convex(v(-5.0, 0.5, 0.5), v(5.0, 0.5, 0.5), &planes).unwrap();
+        assert!(hit.is_equal_approx(v(-1.0, 0.5, 0.5), v(-1.0, 0.0, 0.0)));
+
+        let hit = segment_intersects_convex(v(0.0, 0.0, 10.0), v(0.0, 0.0, 0.0), &planes).unwrap();
+        assert!(hit.is_equal_approx(v(0.0, 0.0, 3.0), v(0.0, 0.0, 1.0)));
+
+        assert_eq!(
+            segment_intersects_convex(v(-5.0, 5.0, 0.0), v(5.0, 4.0, 0.0), &planes),
+            None,
+        );
+        assert_eq!(
+            segment_intersects_convex(v(0.0, 0.0, 0.0), v(5.0, 0.0, 0.0), &planes),
+            None,
+        );
+    }
+
+    #[test]
+    fn closest_points() {
+        let (from, to) = (v(0.0, 0.0, 0.0), v(10.0, 0.0, 0.0));
+        assert_eq!(
+            closest_point_to_segment(v(5.0, 5.0, 5.0), from, to),
+            v(5.0, 0.0, 0.0)
+        );
+        assert_eq!(closest_point_to_segment(v(-5.0, 5.0, 0.0), from, to), from);
+        assert_eq!(
+            closest_point_to_segment_uncapped(v(-5.0, 5.0, 0.0), from, to),
+            v(-5.0, 0.0, 0.0)
+        );
+
+        // Skew segments.
+        let (c1, c2) =
+            closest_points_between_segments(from, to, v(3.0, -1.0, 2.0), v(3.0, 1.0, 2.0));
+        assert!(c1.is_equal_approx(v(3.0, 0.0, 0.0)));
+        assert!(c2.is_equal_approx(v(3.0, 0.0, 2.0)));
+        assert!(
+            closest_distance_between_segments(from, to, v(3.0, -1.0, 2.0), v(3.0, 1.0, 2.0))
+                .is_equal_approx(2.0)
+        );
+
+        // Parallel segments.
+        let (c1, c2) =
+            closest_points_between_segments(from, to, v(2.0, 1.0, 0.0), v(4.0, 1.0, 0.0));
+        assert!(c1.distance_to(c2).is_equal_approx(1.0));
+    }
+
+    #[test]
+    fn plane_shapes() {
+        let mut points = compute_convex_mesh_points(&build_box_planes(v(1.0, 2.0, 3.0)));
+        assert_eq!(points.len(), 8);
+        points.sort_by(|a, b| a.as_ref().partial_cmp(b.as_ref()).unwrap());
+        points.dedup();
+        assert_eq!(points.len(), 8);
+        assert!(points
+            .iter()
+            .all(|p| p.abs().is_equal_approx(v(1.0, 2.0, 3.0))));
+
+        let cylinder = build_cylinder_planes(1.0, 4.0, 8, Axis::Y);
+        assert_eq!(cylinder.len(), 10);
+        assert!(cylinder.iter().all(|p| p.normal.is_normalized()));
+        assert!(cylinder[0].normal.is_equal_approx(v(0.0, 0.0, 1.0)));
+        assert!(cylinder[8].is_equal_approx(Plane::new(Vector3::UP, 2.0)));
+        assert_eq!(compute_convex_mesh_points(&cylinder).len(), 16);
+
+        let sphere = build_sphere_planes(1.0, 2, 4, Axis::Z);
+        assert_eq!(sphere.len(), 4 * 5);
+        assert!(sphere.iter().all(|p| p.normal.is_normalized()));
+        assert!(sphere.iter().all(|p| p.d.is_equal_approx(1.0)));
+
+        let capsule = build_capsule_planes(1.0, 2.0, 4, 2, Axis::Z);
+        assert_eq!(capsule.len(), 4 * 5);
+        // The top of the caps are at the capsule's full height.
+        assert!(capsule[3].is_equal_approx(Plane::new(Vector3::BACK, 2.0)));
+        assert!(capsule[4].is_equal_approx(Plane::new(Vector3::FORWARD, 2.0)));
+    }
+}
@@ -2,15 +2,26 @@
 
 mod aabb;
 mod basis;
+mod curve;
+mod face3;
+mod frustum;
 mod plane;
+mod projection;
 mod quat;
 mod rect2;
 mod transform;
 mod transform2d;
 
+pub mod geometry2d;
+pub mod geometry3d;
+
 pub use aabb::*;
 pub use basis::*;
+pub use curve::*;
+pub use face3::*;
+pub use frustum::*;
 pub use plane::*;
+pub use projection::*;
 pub use quat::*;
 pub use rect2::*;
 pub use transform::*;
@@ -1,4 +1,4 @@
-use crate::core_types::{IsEqualApprox, Vector3};
+use crate::core_types::{real, IsEqualApprox, Vector3};
 
 // TODO(#994) enforce invariants via setters, make fields private
 // Otherwise almost all methods need to panic
@@ -22,7 +22,7 @@ pub struct Plane {
     /// Distance from the coordinate system origin (in the direction of `normal`).
     ///
     /// This value is typically non-negative. It can however be negative, which behaves as if `normal` changed direction.
-    pub d: f32,
+    pub d: real,
 }
 
 impl Plane {
@@ -32,7 +32,7 @@ impl Plane {
     /// In contrast to construction via `Plane { normal, d }`, this verifies that `normal` has unit length, and will
     /// panic if this is not the case.
     #[inline]
-    pub fn new(normal: Vector3, d: f32) -> Self {
+    pub fn new(normal: Vector3, d: real) -> Self {
         // Design: we could call normalize() here, however that suggests to the user that vectors with non-unit
         // length are valid normals, and tempts users to assign those directly to the field. It's also confusing
         // if Plane { normal, d } and Plane::new(normal, d) have fundamentally different behaviors.
@@ -51,7 +51,7 @@ impl Plane {
     /// # Panics
     /// See [`Self::new()`].
     #[inline]
-    pub fn from_coordinates(a: f32, b: f32, c: f32, d: f32) -> Self {
+    pub fn from_coordinates(a: real, b: real, c: real, d: real) -> Self {
         Self::new(Vector3::new(a, b, c), d)
     }
 
@@ -96,7 +96,7 @@ impl Plane {
     ///
     /// This value is negative, if `self.is_point_over(point)` is false.
     #[inline]
-    pub fn distance_to(self, point: Vector3) -> f32 {
+    pub fn distance_to(self, point: Vector3) -> real {
         self.ensure_normalized();
 
         (self.normal.dot(point)) - self.d
@@ -109,14 +109,14 @@ impl Plane {
     pub fn contains_point(self, point: Vector3) -> bool {
         // Note: invariant check in distance_to()
 
-        self.contains_point_eps(point, crate::core_types::CMP_EPSILON as f32)
+        self.contains_point_eps(point, crate::core_types::CMP_EPSILON as real)
     }
 
     /// Returns `true` if `point` is inside the `Plane`.
     ///
     /// `epsilon` specifies the minimum distance, at and below which a point is considered inside the `Plane`.
     #[inline]
-    pub fn contains_point_eps(self, point: Vector3, epsilon: f32) -> bool {
+    pub fn contains_point_eps(self, point: Vector3, epsilon: real) -> bool {
         // Note: invariant check in distance_to()
 
         let dist = self.distance_to(point).abs();
@@ -160,7 +160,7 @@ impl Plane {
         }
 
         let dist = (self.normal.dot(from) - self.d) / denom;
-        if dist > f32::EPSILON {
+        if dist > real::EPSILON {
             return None;
         }
 
@@ -184,7 +184,7 @@ impl Plane {
         let dist = (self.normal.dot(begin) - self.d) / denom;
 
         // check that dist is not in -EPSILON..(EPSILON+1)
-        if (-f32::EPSILON..=(f32::EPSILON + 1.0)).contains(&dist) {
+        if (-real::EPSILON..=(real::EPSILON + 1.0)).contains(&dist) {
             Some(begin + segment * -dist)
         } else {
             None
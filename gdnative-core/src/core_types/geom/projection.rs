@@ -0,0 +1,431 @@
+use std::ops::{Mul, MulAssign};
+
+use crate::core_types::glam_real::Mat4;
+use crate::core_types::{real, Frustum, Plane, Transform, Vector2, Vector3};
+
+/// A 4x4 projection matrix, as used by cameras to map view space to clip space.
+///
+/// This is the equivalent of the engine's internal `CameraMatrix` type, and follows the same
+/// conventions: the camera looks along -Z, and depth is mapped to the range -1 to 1.
+///
+/// Angles are in degrees, like the properties of the engine's `Camera` node.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Projection {
+    /// Matrix columns. `matrix[3]` is the translation column.
+    pub matrix: [[real; 4]; 4],
+}
+
+impl Default for Projection {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Projection {
+    /// Identity matrix, which leaves points unchanged.
+    pub const IDENTITY: Self = Self {
+        matrix: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    /// Creates a perspective projection with the vertical field of view `fovy_degrees`.
+    ///
+    /// If `flip_fov` is `true`, `fovy_degrees` is the horizontal field of view instead, like with
+    /// the `KEEP_WIDTH` aspect mode of cameras. Returns the identity matrix if the aspect ratio,
+    /// the field of view or the depth range is zero.
+    #[inline]
+    pub fn perspective(
+        fovy_degrees: real,
+        aspect: real,
+        z_near: real,
+        z_far: real,
+        flip_fov: bool,
+    ) -> Self {
+        let fovy_degrees = if flip_fov {
+            Self::fovy(fovy_degrees, 1.0 / aspect)
+        } else {
+            fovy_degrees
+        };
+
+        let radians = (fovy_degrees / 2.0).to_radians();
+        let delta_z = z_far - z_near;
+        let (sine, cosine) = radians.sin_cos();
+        if delta_z == 0.0 || sine == 0.0 || aspect == 0.0 {
+            return Self::IDENTITY;
+        }
+
+        let cotangent = cosine / sine;
+        let mut result = Self::IDENTITY;
+        result.matrix[0][0] = cotangent / aspect;
+        result.matrix[1][1] = cotangent;
+        result.matrix[2][2] = -(z_far + z_near) / delta_z;
+        result.matrix[2][3] = -1.0;
+        result.matrix[3][2] = -2.0 * z_near * z_far / delta_z;
+        result.matrix[3][3] = 0.0;
+        result
+    }
+
+    /// Creates an orthogonal projection showing `size` units vertically.
+    ///
+    /// If `flip_fov` is `true`, `size` is the horizontal extent instead.
+    #[inline]
+    pub fn orthogonal(size: real, aspect: real, z_near: real, z_far: real, flip_fov: bool) -> Self {
+        let size = if flip_fov { size } else { size * aspect };
+        Self::orthogonal_bounds(
+            -size / 2.0,
+            size / 2.0,
+            -size / aspect / 2.0,
+            size / aspect / 2.0,
+            z_near,
+            z_far,
+        )
+    }
+
+    /// Creates an orthogonal projection of the given box.
+    #[inline]
+    pub fn orthogonal_bounds(
+        left: real,
+        right: real,
+        bottom: real,
+        top: real,
+        z_near: real,
+        z_far: real,
+    ) -> Self {
+        let mut result = Self::IDENTITY;
+        result.matrix[0][0] = 2.0 / (right - left);
+        result.matrix[3][0] = -((right + left) / (right - left));
+        result.matrix[1][1] = 2.0 / (top - bottom);
+        result.matrix[3][1] = -((top + bottom) / (top - bottom));
+        result.matrix[2][2] = -2.0 / (z_far - z_near);
+        result.matrix[3][2] = -((z_far + z_near) / (z_far - z_near));
+        result.matrix[3][3] = 1.0;
+        result
+    }
+
+    /// Creates a perspective projection whose near plane shows `size` units vertically, shifted
+    /// by `offset`, like cameras in frustum mode.
+    ///
+    /// If `flip_fov` is `true`, `size` is the horizontal extent instead.
+    #[inline]
+    pub fn frustum(
+        size: real,
+        aspect: real,
+        offset: Vector2,
+        z_near: real,
+        z_far: real,
+        flip_fov: bool,
+    ) -> Self {
+        let size = if flip_fov { size } else { size * aspect };
+        Self::frustum_bounds(
+            -size / 2.0 + offset.x,
+            size / 2.0 + offset.x,
+            -size / aspect / 2.0 + offset.y,
+            size / aspect / 2.0 + offset.y,
+            z_near,
+            z_far,
+        )
+    }
+
+    /// Creates a perspective projection whose near plane spans the given rectangle.
+    #[inline]
+    pub fn frustum_bounds(
+        left: real,
+        right: real,
+        bottom: real,
+        top: real,
+        z_near: real,
+        z_far: real,
+    ) -> Self {
+        let x = 2.0 * z_near / (right - left);
+        let y = 2.0 * z_near / (top - bottom);
+        let a = (right + left) / (right - left);
+        let b = (top + bottom) / (top - bottom);
+        let c = -(z_far + z_near) / (z_far - z_near);
+        let d = -2.0 * z_far * z_near / (z_far - z_near);
+
+        Self {
+            matrix: [
+                [x, 0.0, 0.0, 0.0],
+                [0.0, y, 0.0, 0.0],
+                [a, b, c, -1.0],
+                [0.0, 0.0, d, 0.0],
+            ],
+        }
+    }
+
+    /// Returns the vertical field of view matching the horizontal field of view `fovx_degrees`
+    /// for the given aspect ratio.
+    #[inline]
+    pub fn fovy(fovx_degrees: real, aspect: real) -> real {
+        ((aspect * (fovx_degrees.to_radians() * 0.5).tan()).atan() * 2.0).to_degrees()
+    }
+
+    /// Returns the distance of the near plane.
+    #[inline]
+    pub fn z_near(&self) -> real {
+        -self.plane(2, 1.0).d
+    }
+
+    /// Returns the distance of the far plane.
+    #[inline]
+    pub fn z_far(&self) -> real {
+        self.plane(2, -1.0).d
+    }
+
+    /// Returns the width divided by the height of the near plane.
+    #[inline]
+    pub fn aspect(&self) -> real {
+        let extents = self.viewport_half_extents();
+        extents.x / extents.y
+    }
+
+    /// Returns the horizontal field of view.
+    #[inline]
+    pub fn fov(&self) -> real {
+        let m = &self.matrix;
+        let right = self.plane(0, -1.0);
+        if m[2][0] == 0.0 && m[2][1] == 0.0 {
+            right.normal.x.abs().acos().to_degrees() * 2.0
+        } else {
+            // Asymmetrical frustum, the left plane's angle differs.
+            let left = self.plane(0, 1.0);
+            left.normal.x.abs().acos().to_degrees() + right.normal.x.abs().acos().to_degrees()
+        }
+    }
+
+    /// Returns `true` if this is an orthogonal projection.
+    #[inline]
+    pub fn is_orthogonal(&self) -> bool {
+        self.matrix[3][3] == 1.0
+    }
+
+    /// Returns the half width and half height of the near plane, in view space.
+    ///
+    /// Returns zero if the near plane is not bounded.
+    #[inline]
+    pub fn viewport_half_extents(&self) -> Vector2 {
+        let near = self.plane(2, 1.0);
+        let right = self.plane(0, -1.0);
+        let top = self.plane(1, -1.0);
+
+        near.intersect_3(right, top)
+            .map_or(Vector2::ZERO, |corner| Vector2::new(corner.x, corner.y))
+    }
+
+    /// Returns the planes bounding the visible space, for a camera with the given transform.
+    ///
+    /// The plane normals point outwards.
+    #[inline]
+    pub fn frustum_planes(&self, camera: &Transform) -> Frustum {
+        Frustum {
+            near: self.plane(2, 1.0),
+            far: self.plane(2, -1.0),
+            left: self.plane(0, 1.0),
+            top: self.plane(1, -1.0),
+            right: self.plane(0, -1.0),
+            bottom: self.plane(1, 1.0),
+        }
+        .transformed(camera)
+    }
+
+    /// Returns the inverse of the matrix.
+    #[inline]
+    pub fn inverse(&self) -> Self {
+        Self::gd(self.glam().inverse())
+    }
+
+    /// Transforms `v` by the matrix, including the perspective divide.
+    #[inline]
+    pub fn xform(&self, v: Vector3) -> Vector3 {
+        let m = &self.matrix;
+        let row = |i: usize| m[0][i] * v.x + m[1][i] * v.y + m[2][i] * v.z + m[3][i];
+        Vector3::new(row(0), row(1), row(2)) / row(3)
+    }
+
+    /// Extracts a clipping plane, as the fourth row of the matrix plus or minus the given row.
+    ///
+    /// See "Fast Extraction of Viewing Frustum Planes from the World-View-Projection Matrix" by
+    /// Gil Gribb and Klaus Hartmann.
+    fn plane(&self, row: usize, sign: real) -> Plane {
+        let m = &self.matrix;
+        let coefficient = |col: usize| m[col][3] + sign * m[col][row];
+
+        Plane {
+            normal: -Vector3::new(coefficient(0), coefficient(1), coefficient(2)),
+            d: coefficient(3),
+        }
+        .normalized()
+    }
+
+    #[inline]
+    fn glam(&self) -> Mat4 {
+        Mat4::from_cols_array_2d(&self.matrix)
+    }
+
+    #[inline]
+    fn gd(m: Mat4) -> Self {
+        Self {
+            matrix: m.to_cols_array_2d(),
+        }
+    }
+}
+
+impl From<Transform> for Projection {
+    #[inline]
+    fn from(transform: Transform) -> Self {
+        let [a, b, c] = [
+            transform.basis.a(),
+            transform.basis.b(),
+            transform.basis.c(),
+        ];
+        let o = transform.origin;
+
+        Self {
+            matrix: [
+                [a.x, a.y, a.z, 0.0],
+                [b.x, b.y, b.z, 0.0],
+                [c.x, c.y, c.z, 0.0],
+                [o.x, o.y, o.z, 1.0],
+            ],
+        }
+    }
+}
+
+impl Mul<Projection> for Projection {
+    type Output = Projection;
+
+    #[inline]
+    fn mul(self, rhs: Projection) -> Self::Output {
+        Self::gd(self.glam() * rhs.glam())
+    }
+}
+
+impl MulAssign<Projection> for Projection {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Projection) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_types::IsEqualApprox;
+
+    fn assert_matrix_eq(actual: &Projection, expected: [[real; 4]; 4]) {
+        for (col, expected_col) in expected.iter().enumerate() {
+            for (row, expected) in expected_col.iter().enumerate() {
+                assert!(
+                    actual.matrix[col][row].is_equal_approx(*expected),
+                    "[{col}][{row}]: {:?} != {expected:?}",
+                    actual.matrix
+                );
+            }
+        }
+    }
+
+    /// Far plane values lose precision in single-precision builds, compare them relatively.
+    fn assert_far_eq(actual: real, expected: real) {
+        assert!(
+            (actual - expected).abs() <= expected.abs() * 1e-5,
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn perspective() {
+        let p = Projection::perspective(90.0, 2.0, 1.0, 101.0, false);
+        assert_matrix_eq(
+            &p,
+            [
+                [0.5, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, -1.02, -1.0],
+                [0.0, 0.0, -2.02, 0.0],
+            ],
+        );
+
+        assert!(p.z_near().is_equal_approx(1.0));
+        assert_far_eq(p.z_far(), 101.0);
+        assert!(p.aspect().is_equal_approx(2.0));
+        assert!(p
+            .viewport_half_extents()
+            .is_equal_approx(Vector2::new(2.0, 1.0)));
+        assert!(p.fov().is_equal_approx(Projection::fovy(90.0, 2.0)));
+        assert!(!p.is_orthogonal());
+
+        // Near and far plane centers map to the ends of the depth range.
+        assert!(p
+            .xform(Vector3::new(0.0, 0.0, -1.0))
+            .is_equal_approx(Vector3::new(0.0, 0.0, -1.0)));
+        assert!(p
+            .xform(Vector3::new(2.0, 1.0, -1.0))
+            .is_equal_approx(Vector3::new(1.0, 1.0, -1.0)));
+        assert!(p
+            .xform(Vector3::new(0.0, 0.0, -101.0))
+            .is_equal_approx(Vector3::new(0.0, 0.0, 1.0)));
+
+        let flipped = Projection::perspective(90.0, 2.0, 1.0, 101.0, true);
+        assert!(flipped.fov().is_equal_approx(90.0));
+
+        assert_eq!(
+            Projection::perspective(90.0, 0.0, 1.0, 101.0, false),
+            Projection::IDENTITY
+        );
+    }
+
+    #[test]
+    fn orthogonal() {
+        let p = Projection::orthogonal(10.0, 2.0, 0.5, 100.5, false);
+        assert!(p.is_orthogonal());
+        assert!(p.z_near().is_equal_approx(0.5));
+        assert_far_eq(p.z_far(), 100.5);
+        assert!(p
+            .viewport_half_extents()
+            .is_equal_approx(Vector2::new(10.0, 5.0)));
+        assert!(p
+            .xform(Vector3::new(10.0, 5.0, -0.5))
+            .is_equal_approx(Vector3::new(1.0, 1.0, -1.0)));
+    }
+
+    #[test]
+    fn frustum() {
+        let p = Projection::frustum(2.0, 1.0, Vector2::new(1.0, 0.0), 1.0, 10.0, false);
+        assert_eq!(
+            p,
+            Projection::frustum_bounds(0.0, 2.0, -1.0, 1.0, 1.0, 10.0)
+        );
+        assert!(p.z_near().is_equal_approx(1.0));
+        assert_far_eq(p.z_far(), 10.0);
+        // The left plane is straight ahead, the right one at atan(2) to it.
+        assert!(p.fov().is_equal_approx(63.434948));
+    }
+
+    #[test]
+    fn inverse_and_transform() {
+        let p = Projection::perspective(70.0, 1.5, 0.1, 50.0, false);
+        let point = Vector3::new(1.0, -2.0, -7.0);
+        assert!(p.inverse().xform(p.xform(point)).is_equal_approx(point));
+
+        let identity = p * p.inverse();
+        for (col, values) in identity.matrix.iter().enumerate() {
+            for (row, value) in values.iter().enumerate() {
+                let expected = if col == row { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-4);
+            }
+        }
+
+        let transform = Transform::IDENTITY
+            .rotated(Vector3::UP, 0.5)
+            .translated_global(Vector3::new(1.0, 2.0, 3.0));
+        assert!(Projection::from(transform)
+            .xform(point)
+            .is_equal_approx(transform.xform(point)));
+    }
+}
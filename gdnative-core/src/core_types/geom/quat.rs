@@ -1,4 +1,6 @@
-use crate::core_types::{Basis, IsEqualApprox, Vector3, CMP_EPSILON};
+use crate::core_types::{
+    glam_real, real, Axis, Basis, EulerOrder, IsEqualApprox, Vector3, CMP_EPSILON,
+};
 use glam::EulerRot;
 use std::ops::{Mul, Neg};
 
@@ -6,15 +8,19 @@ use std::ops::{Mul, Neg};
 ///
 /// Quaternions need to be [normalized][Self::normalized()] before all operations.
 ///
+/// Rotations follow the same conventions as [`Basis`]: the coordinate system is right-handed, and
+/// positive angles are counter-clockwise when looking along the rotation axis towards the origin.
+/// Multiplying `a * b` gives the rotation which applies `b` first, then `a`.
+///
 /// See also [Quat](https://docs.godotengine.org/en/stable/classes/class_quat.html) in the Godot API doc.
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Quat {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
-    pub w: f32,
+    pub x: real,
+    pub y: real,
+    pub z: real,
+    pub w: real,
 }
 
 /// Helper methods for `Quat`.
@@ -27,7 +33,7 @@ impl Quat {
 
     /// Constructs a quaternion defined by the given values.
     #[inline]
-    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+    pub const fn new(x: real, y: real, z: real, w: real) -> Self {
         Self { x, y, z, w }
     }
 
@@ -42,7 +48,7 @@ impl Quat {
     /// (X angle, Y angle, Z angle).
     #[inline]
     pub fn from_euler(euler: Vector3) -> Self {
-        Self::gd(glam::Quat::from_euler(
+        Self::gd(glam_real::Quat::from_euler(
             EulerRot::YXZ,
             euler.y,
             euler.x,
@@ -50,18 +56,75 @@ impl Quat {
         ))
     }
 
+    /// Constructs a quaternion from Euler angles, applied in the given order. See
+    /// [`Basis::from_euler_with_order`].
+    #[inline]
+    pub fn from_euler_with_order(euler: Vector3, order: EulerOrder) -> Self {
+        if order == EulerOrder::YXZ {
+            return Self::from_euler(euler);
+        }
+
+        let angle = |axis: Axis| euler.as_ref()[axis as usize];
+        let [first, second, third] = order.axes();
+        Self::gd(glam_real::Quat::from_euler(
+            order.glam(),
+            angle(first),
+            angle(second),
+            angle(third),
+        ))
+    }
+
+    /// Constructs the shortest rotation which turns the direction `from` into the direction `to`.
+    ///
+    /// The vectors don't need to be normalized, but must not be zero. If they point in opposite
+    /// directions, the result is a half turn around an arbitrary perpendicular axis.
+    #[inline]
+    pub fn shortest_arc(from: Vector3, to: Vector3) -> Self {
+        let from = from.normalized();
+        let to = to.normalized();
+        let d = from.dot(to);
+
+        if d < -1.0 + CMP_EPSILON as real {
+            // Any axis perpendicular to `from` works, pick one which isn't parallel to it.
+            let other = if from.x.abs() < 0.9 {
+                Vector3::RIGHT
+            } else {
+                Vector3::UP
+            };
+            let axis = from.cross(other).normalized();
+            return Self::new(axis.x, axis.y, axis.z, 0.0);
+        }
+
+        let c = from.cross(to);
+        let s = ((1.0 + d) * 2.0).sqrt();
+        let rs = 1.0 / s;
+        Self::new(c.x * rs, c.y * rs, c.z * rs, s * 0.5)
+    }
+
+    /// Constructs a rotation with the -Z axis pointing in `direction`, and the Y axis as close to
+    /// `up` as possible. See [`Basis::looking_at`].
+    ///
+    /// Returns `None` if `direction` is zero or parallel to `up`.
+    #[inline]
+    pub fn look_rotation(direction: Vector3, up: Vector3) -> Option<Self> {
+        Basis::looking_at(direction, up).map(|basis| basis.to_quat())
+    }
+
     /// Constructs a quaternion that will rotate around the given axis by the specified angle. The
     /// axis must be a normalized vector.
     #[inline]
-    pub fn from_axis_angle(axis: Vector3, angle: f32) -> Self {
+    pub fn from_axis_angle(axis: Vector3, angle: real) -> Self {
         debug_assert!(axis.is_normalized(), "Axis is not normalized");
-        Self::gd(glam::Quat::from_axis_angle(axis.glam().into(), angle))
+        Self::gd(glam_real::Quat::from_axis_angle(
+            glam_real::Vec3::new(axis.x, axis.y, axis.z),
+            angle,
+        ))
     }
 
     /// Performs a cubic spherical interpolation between quaternions `pre_a`, this quaternion, `b`,
     /// and `post_b`, by the given amount `t`.
     #[inline]
-    pub fn cubic_slerp(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self {
+    pub fn cubic_slerp(self, b: Self, pre_a: Self, post_b: Self, t: real) -> Self {
         let t2 = (1.0 - t) * t * 2.0;
         let sp = self.slerp(b, t);
         let sq = pre_a.slerpni(post_b, t);
@@ -70,7 +133,7 @@ impl Quat {
 
     /// Returns the dot product of two quaternions.
     #[inline]
-    pub fn dot(self, b: Self) -> f32 {
+    pub fn dot(self, b: Self) -> real {
         self.glam().dot(b.glam())
     }
 
@@ -82,6 +145,67 @@ impl Quat {
         Basis::from_quat(self).to_euler()
     }
 
+    /// Returns Euler angles corresponding to the rotation, applied in the given order. See
+    /// [`Basis::to_euler_with_order`].
+    #[inline]
+    pub fn to_euler_with_order(self, order: EulerOrder) -> Vector3 {
+        Basis::from_quat(self).to_euler_with_order(order)
+    }
+
+    /// Returns the rotation as a normalized axis and an angle in radians, from 0 to π.
+    ///
+    /// For rotations by a negligible angle, the axis is [`Vector3::RIGHT`].
+    ///
+    /// **Note:** The quaternion must be normalized.
+    #[inline]
+    pub fn to_axis_angle(self) -> (Vector3, real) {
+        // q and -q are the same rotation, pick the one with the smaller angle.
+        let q = if self.w < 0.0 { -self } else { self };
+        let v = Vector3::new(q.x, q.y, q.z);
+        let s = v.length();
+
+        if s < CMP_EPSILON as real {
+            (Vector3::RIGHT, 0.0)
+        } else {
+            (v / s, 2.0 * s.atan2(q.w))
+        }
+    }
+
+    /// Returns the angle in radians of the smallest rotation between this quaternion and `to`,
+    /// from 0 to π.
+    ///
+    /// **Note:** Both quaternions must be normalized.
+    #[inline]
+    pub fn angle_to(self, to: Self) -> real {
+        let d = self.dot(to);
+        (d * d * 2.0 - 1.0).clamp(-1.0, 1.0).acos()
+    }
+
+    /// Decomposes the rotation into a swing and a twist, such that `self == swing * twist`.
+    ///
+    /// The twist is the part of the rotation around `twist_axis`, and the swing is the remaining
+    /// rotation around an axis perpendicular to it. This is commonly used to limit the rotation of
+    /// joints separately around the bone and sideways. `twist_axis` must be normalized.
+    ///
+    /// If the rotation turns `twist_axis` by half a turn, the twist is not defined, and the
+    /// identity is returned as twist.
+    #[inline]
+    pub fn swing_twist(self, twist_axis: Vector3) -> (Self, Self) {
+        debug_assert!(twist_axis.is_normalized(), "Axis is not normalized");
+
+        let v = Vector3::new(self.x, self.y, self.z);
+        let p = twist_axis * v.dot(twist_axis);
+        let twist = Self::new(p.x, p.y, p.z, self.w);
+
+        let twist = if twist.length_squared() < CMP_EPSILON as real {
+            Self::IDENTITY
+        } else {
+            twist.normalized()
+        };
+
+        (self * twist.inverse(), twist)
+    }
+
     /// Returns the inverse of the quaternion.
     #[inline]
     pub fn inverse(self) -> Self {
@@ -106,13 +230,13 @@ impl Quat {
 
     /// Returns the length of the quaternion.
     #[inline]
-    pub fn length(self) -> f32 {
+    pub fn length(self) -> real {
         self.glam().length()
     }
 
     /// Returns the length of the quaternion, squared.
     #[inline]
-    pub fn length_squared(self) -> f32 {
+    pub fn length_squared(self) -> real {
         self.glam().length_squared()
     }
 
@@ -129,7 +253,7 @@ impl Quat {
     ///
     /// **Note:** Both quaternions must be normalized.
     #[inline]
-    pub fn slerp(self, b: Self, t: f32) -> Self {
+    pub fn slerp(self, b: Self, t: real) -> Self {
         debug_assert!(self.is_normalized(), "Quaternion `self` is not normalized");
         debug_assert!(b.is_normalized(), "Quaternion `b` is not normalized");
 
@@ -142,7 +266,7 @@ impl Quat {
         let (cos, b) = if cos < 0.0 { (-cos, -b) } else { (cos, b) };
 
         // calculate coefficients
-        let scale = if 1.0 - cos > CMP_EPSILON as f32 {
+        let scale = if 1.0 - cos > CMP_EPSILON as real {
             // standard case (slerp)
             let omega = cos.acos();
             let sin = omega.sin();
@@ -165,7 +289,7 @@ impl Quat {
     /// Returns the result of the spherical linear interpolation between this quaternion and `t` by
     /// amount `t`, but without checking if the rotation path is not bigger than 90 degrees.
     #[inline]
-    pub fn slerpni(self, b: Self, t: f32) -> Self {
+    pub fn slerpni(self, b: Self, t: real) -> Self {
         debug_assert!(self.is_normalized(), "Quaternion `self` is not normalized");
         debug_assert!(b.is_normalized(), "Quaternion `b` is not normalized");
 
@@ -197,13 +321,13 @@ impl Quat {
     }
 
     #[inline]
-    pub(super) fn gd(quat: glam::Quat) -> Self {
+    pub(super) fn gd(quat: glam_real::Quat) -> Self {
         Self::new(quat.x, quat.y, quat.z, quat.w)
     }
 
     #[inline]
-    pub(super) fn glam(self) -> glam::Quat {
-        glam::Quat::from_xyzw(self.x, self.y, self.z, self.w)
+    pub(super) fn glam(self) -> glam_real::Quat {
+        glam_real::Quat::from_xyzw(self.x, self.y, self.z, self.w)
     }
 }
 
@@ -313,4 +437,91 @@ mod test {
         let e = Quat::new(-0.768253, -0.490687, 0.341836, -0.22839);
         assert!(e.is_equal_approx(a.cubic_slerp(b, c, d, t)));
     }
+
+    #[test]
+    fn euler_with_order() {
+        let euler = Vector3::new(0.25, 5.24, 3.0);
+        for order in [
+            EulerOrder::XYZ,
+            EulerOrder::XZY,
+            EulerOrder::YXZ,
+            EulerOrder::YZX,
+            EulerOrder::ZXY,
+            EulerOrder::ZYX,
+        ] {
+            let quat = Quat::from_euler_with_order(euler, order);
+            let basis = Basis::from_euler_with_order(euler, order);
+            assert!(Basis::from_quat(quat).is_equal_approx(&basis), "{order:?}");
+            assert!(
+                Basis::from_euler_with_order(quat.to_euler_with_order(order), order)
+                    .is_equal_approx(&basis),
+                "{order:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn shortest_arc() {
+        let s = std::f64::consts::FRAC_1_SQRT_2 as real;
+        let quat = Quat::shortest_arc(Vector3::RIGHT, Vector3::new(0.0, 3.0, 0.0));
+        assert!(quat.is_equal_approx(Quat::new(0.0, 0.0, s, s)));
+
+        let from = Vector3::new(1.0, -2.0, 0.5);
+        let to = Vector3::new(-3.0, 0.1, 2.0);
+        let quat = Quat::shortest_arc(from, to);
+        assert!((quat * from.normalized()).is_equal_approx(to.normalized()));
+
+        assert!(Quat::shortest_arc(from, from * 2.0).is_equal_approx(Quat::IDENTITY));
+        for v in [Vector3::RIGHT, Vector3::UP, from] {
+            let quat = Quat::shortest_arc(v, -v);
+            assert!(quat.is_normalized());
+            assert!((quat * v.normalized()).is_equal_approx(-v.normalized()));
+        }
+    }
+
+    #[test]
+    fn look_rotation() {
+        let direction = Vector3::new(1.0, 0.5, -2.0);
+        let quat = Quat::look_rotation(direction, Vector3::UP).unwrap();
+        assert!((quat * Vector3::FORWARD).is_equal_approx(direction.normalized()));
+        assert_eq!(Quat::look_rotation(Vector3::UP, Vector3::UP), None);
+    }
+
+    #[test]
+    fn axis_angle() {
+        let axis = Vector3::new(0.485489, 0.142796, -0.862501).normalized();
+
+        let (a, angle) = Quat::from_axis_angle(axis, 2.0).to_axis_angle();
+        assert!(a.is_equal_approx(axis));
+        assert!(angle.is_equal_approx(2.0));
+
+        // Angles beyond a half turn are the smaller rotation in the other direction.
+        let (a, angle) = Quat::from_axis_angle(axis, 4.0).to_axis_angle();
+        assert!(a.is_equal_approx(-axis));
+        assert!(angle.is_equal_approx(std::f64::consts::TAU as real - 4.0));
+
+        assert_eq!(Quat::IDENTITY.to_axis_angle(), (Vector3::RIGHT, 0.0));
+
+        let a = Quat::from_axis_angle(axis, 0.5);
+        let b = Quat::from_axis_angle(axis, 1.75);
+        assert!(a.angle_to(b).is_equal_approx(1.25));
+        assert!(b.angle_to(-a).is_equal_approx(1.25));
+    }
+
+    #[test]
+    fn swing_twist() {
+        let swing = Quat::from_axis_angle(Vector3::new(1.0, 0.0, 1.0).normalized(), 0.5);
+        let twist = Quat::from_axis_angle(Vector3::UP, 0.7);
+        let quat = swing * twist;
+
+        let (s, t) = quat.swing_twist(Vector3::UP);
+        assert!(s.is_equal_approx(swing));
+        assert!(t.is_equal_approx(twist));
+        assert!((s * t).is_equal_approx(quat));
+
+        // A pure swing has no twist.
+        let (s, t) = swing.swing_twist(Vector3::UP);
+        assert!(s.is_equal_approx(swing));
+        assert!(t.is_equal_approx(Quat::IDENTITY));
+    }
 }
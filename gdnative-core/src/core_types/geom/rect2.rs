@@ -1,4 +1,4 @@
-use crate::core_types::Vector2;
+use crate::core_types::{real, Vector2};
 use std::convert::TryFrom;
 
 /// 2D axis-aligned bounding box.
@@ -26,7 +26,7 @@ impl Rect2 {
 
     /// Creates a `Rect2` by x, y, width, and height.
     #[inline]
-    pub fn from_components(x: f32, y: f32, width: f32, height: f32) -> Self {
+    pub fn from_components(x: real, y: real, width: real, height: real) -> Self {
         let position = Vector2::new(x, y);
         let size = Vector2::new(width, height);
 
@@ -57,7 +57,7 @@ impl Rect2 {
 
     /// Returns the area of the rectangle. See also [`has_no_area`][Self::has_no_area].
     #[inline]
-    pub fn area(self) -> f32 {
+    pub fn area(self) -> real {
         self.size.x * self.size.y
     }
 
@@ -221,7 +221,7 @@ impl Rect2 {
     /// Returns a copy of this rectangle grown by a given amount of units on all the sides.
     #[inline]
     #[must_use]
-    pub fn grow(self, by: f32) -> Self {
+    pub fn grow(self, by: real) -> Self {
         let position = self.position - Vector2::new(by, by);
         let size = self.size + Vector2::new(by, by) * 2.0;
 
@@ -232,7 +232,7 @@ impl Rect2 {
     /// individually.
     #[inline]
     #[must_use]
-    pub fn grow_individual(mut self, left: f32, top: f32, right: f32, bottom: f32) -> Self {
+    pub fn grow_individual(mut self, left: real, top: real, right: real, bottom: real) -> Self {
         self.position.x -= left;
         self.position.y -= top;
         self.size.x += left + right;
@@ -245,7 +245,7 @@ impl Rect2 {
     /// direction.
     #[inline]
     #[must_use]
-    pub fn grow_margin(self, margin: Margin, amount: f32) -> Self {
+    pub fn grow_margin(self, margin: Margin, amount: real) -> Self {
         let left = if margin == Margin::Left { amount } else { 0.0 };
         let top = if margin == Margin::Top { amount } else { 0.0 };
         let right = if margin == Margin::Right { amount } else { 0.0 };
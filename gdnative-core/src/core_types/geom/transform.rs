@@ -1,6 +1,6 @@
 use std::ops::{Mul, MulAssign};
 
-use crate::core_types::{Basis, Vector3};
+use crate::core_types::{real, Basis, Quat, Vector3};
 
 // Note regarding naming of interpolation: there are 3 namings in Godot.
 // * `lerp` + `slerp` for simple types
@@ -121,6 +121,20 @@ impl Transform {
         self.basis.xform(v) + self.origin
     }
 
+    /// Transforms (multiplies) each vector in `vectors` by the matrix, in place.
+    ///
+    /// This gives the same results as calling [`xform`][Self::xform] on each vector, up to
+    /// rounding, but uses SIMD instructions where available. `PoolArray` write guards dereference
+    /// to slices, so they can be passed directly: `transform.xform_slice(&mut array.write())`.
+    #[inline]
+    pub fn xform_slice(&self, vectors: &mut [Vector3]) {
+        let matrix = self.basis.glam_a();
+        let origin = self.origin.glam();
+        for v in vectors {
+            *v = Vector3::gd(matrix * v.glam() + origin);
+        }
+    }
+
     /// Returns a vector transformed (multiplied) by the transposed basis
     /// matrix.
     ///
@@ -156,12 +170,37 @@ impl Transform {
         }
     }
 
+    /// Creates a transform which scales by `scale`, then rotates by `rotation`, then translates by
+    /// `translation`.
+    ///
+    /// This is the inverse of [`decompose`][Self::decompose].
+    #[inline]
+    pub fn from_translation_rotation_scale(
+        translation: Vector3,
+        rotation: Quat,
+        scale: Vector3,
+    ) -> Self {
+        Transform {
+            basis: Basis::from_quat_scale(rotation, scale),
+            origin: translation,
+        }
+    }
+
+    /// Splits the transform into its translation, rotation and scale, in that order.
+    ///
+    /// If the basis mirrors (has a negative determinant), all scale components are negative.
+    /// Shear can't be represented and is lost.
+    #[inline]
+    pub fn decompose(&self) -> (Vector3, Quat, Vector3) {
+        (self.origin, self.basis.to_quat(), self.basis.scale())
+    }
+
     /// In-place rotation of the transform around the given axis by the given
     /// angle (in radians), using matrix multiplication. The axis must be a
     /// normalized vector.
     /// Due to nature of the operation, a new transform is created first.
     #[inline]
-    pub fn rotated(&self, axis: Vector3, phi: f32) -> Self {
+    pub fn rotated(&self, axis: Vector3, phi: real) -> Self {
         Transform {
             basis: Basis::from_axis_angle(axis, phi),
             origin: Vector3::default(),
@@ -172,7 +211,7 @@ impl Transform {
     /// Returns the rotated transform around the given axis by the given angle (in radians),
     /// using matrix multiplication. The axis must be a normalized vector.
     #[inline]
-    fn rotate(&mut self, axis: Vector3, phi: f32) {
+    fn rotate(&mut self, axis: Vector3, phi: real) {
         *self = self.rotated(axis, phi);
     }
     */
@@ -257,14 +296,14 @@ impl Transform {
     /// Assuming the two transforms are located on a sphere surface.
     #[inline]
     #[deprecated = "This is the Godot 4 rename of `interpolate_with`. It will be removed in favor of the original Godot 3 naming in a future version."]
-    pub fn sphere_interpolate_with(&self, other: &Transform, weight: f32) -> Self {
+    pub fn sphere_interpolate_with(&self, other: &Transform, weight: real) -> Self {
         self.interpolate_with(other, weight)
     }
 
     /// Interpolates the transform to other Transform by weight amount (on the range of 0.0 to 1.0).
     /// Assuming the two transforms are located on a sphere surface.
     #[inline]
-    pub fn interpolate_with(&self, other: &Transform, weight: f32) -> Self {
+    pub fn interpolate_with(&self, other: &Transform, weight: real) -> Self {
         let src_scale = self.basis.scale();
         let src_rot = self.basis.to_quat();
         let src_loc = self.origin;
@@ -447,4 +486,48 @@ mod tests {
         );
         assert!(expected.is_equal_approx(&result))
     }
+
+    #[test]
+    fn decompose_is_sane() {
+        let rotation = Quat::from_axis_angle(Vector3::new(0.0, 1.0, 1.0).normalized(), 1.2);
+        let translation = Vector3::new(0.5, -1.0, 0.25);
+        let scale = Vector3::new(1.5, 2.0, 0.5);
+
+        let t = Transform::from_translation_rotation_scale(translation, rotation, scale);
+        assert!(t
+            .xform(Vector3::RIGHT)
+            .is_equal_approx(translation + rotation * Vector3::new(1.5, 0.0, 0.0)));
+
+        let (tr, rot, sc) = t.decompose();
+        assert!(tr.is_equal_approx(translation));
+        assert!(rot.is_equal_approx(rotation));
+        assert!(sc.is_equal_approx(scale));
+
+        // Mirrored transforms have a negative scale.
+        let mirrored = Transform::from_translation_rotation_scale(translation, rotation, -scale);
+        let (_, rot, sc) = mirrored.decompose();
+        assert!(sc.is_equal_approx(-scale));
+        assert!(
+            Transform::from_translation_rotation_scale(translation, rot, sc)
+                .is_equal_approx(&mirrored)
+        );
+    }
+
+    #[test]
+    fn xform_slice() {
+        let t = Transform::from_translation_rotation_scale(
+            Vector3::new(0.5, -1.0, 0.25),
+            Quat::from_axis_angle(Vector3::new(0.0, 1.0, 1.0).normalized(), 1.2),
+            Vector3::new(1.5, 2.0, 0.5),
+        );
+        let mut vectors = [Vector3::ZERO, Vector3::new(1.0, -2.0, 3.0), Vector3::ONE];
+        let expected = vectors.map(|v| t.xform(v));
+
+        t.xform_slice(&mut vectors);
+        for (v, e) in vectors.iter().zip(&expected) {
+            assert!(v.is_equal_approx(*e));
+        }
+
+        t.xform_slice(&mut []);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::core_types::Vector2;
+use crate::core_types::{real, Vector2};
 
 /// Affine 2D transform (2x3 matrix).
 ///
@@ -89,7 +89,7 @@ impl Transform2D {
     /// [`scaled`]: Self::scaled
     /// [`translated`]: Self::translated
     #[inline]
-    pub fn from_scale_rotation_origin(scale: Vector2, rotation: f32, origin: Vector2) -> Self {
+    pub fn from_scale_rotation_origin(scale: Vector2, rotation: real, origin: Vector2) -> Self {
         let mut tr = Self::IDENTITY;
         tr.set_scale(scale);
         tr.set_rotation(rotation);
@@ -137,6 +137,18 @@ impl Transform2D {
         Vector2::new(self.tdotx(v), self.tdoty(v)) + self.origin
     }
 
+    /// Transforms each vector in `vectors` by this transform, in place.
+    ///
+    /// This is equivalent to calling [`xform`][Self::xform] on each vector. `PoolArray` write
+    /// guards dereference to slices, so they can be passed directly:
+    /// `transform.xform_slice(&mut array.write())`.
+    #[inline]
+    pub fn xform_slice(&self, vectors: &mut [Vector2]) {
+        for v in vectors {
+            *v = self.xform(*v);
+        }
+    }
+
     /// Inverse-transforms the given Vector2, Rect2, or PoolVector2Array by this transform.
     #[inline]
     pub fn xform_inv(&self, v: Vector2) -> Vector2 {
@@ -157,16 +169,16 @@ impl Transform2D {
 
     /// Returns the transform's rotation (in radians).
     #[inline]
-    pub fn rotation(&self) -> f32 {
-        f32::atan2(self.a.y, self.a.x)
+    pub fn rotation(&self) -> real {
+        real::atan2(self.a.y, self.a.x)
     }
 
     /// Sets the transform's rotation (argument `rotation` in radians).
     #[inline]
-    pub fn set_rotation(&mut self, rotation: f32) {
+    pub fn set_rotation(&mut self, rotation: real) {
         let scale = self.scale();
-        let cr = f32::cos(rotation);
-        let sr = f32::sin(rotation);
+        let cr = real::cos(rotation);
+        let sr = real::sin(rotation);
         self.a.x = cr;
         self.a.y = sr;
         self.b.x = -sr;
@@ -176,7 +188,7 @@ impl Transform2D {
 
     /// Rotates the transform by the given angle (in radians), using matrix multiplication. This will modify the transform's origin.
     #[inline]
-    pub fn rotated(&self, rotation: f32) -> Self {
+    pub fn rotated(&self, rotation: real) -> Self {
         let mut tr = Self::IDENTITY;
         tr.set_rotation(rotation);
         tr * *self
@@ -208,7 +220,7 @@ impl Transform2D {
     /// Returns a transform interpolated between this transform and another by a given weight (on the range of 0.0 to 1.0).
     /// NOTE: This method assumes both Transform2Ds are affine transformations.
     #[inline]
-    pub fn interpolate_with(&self, other: Self, weight: f32) -> Self {
+    pub fn interpolate_with(&self, other: Self, weight: real) -> Self {
         // extract parameters
         let p1 = self.origin;
         let p2 = other.origin;
@@ -220,22 +232,22 @@ impl Transform2D {
         let s2 = other.scale();
 
         // slerp rotation
-        let v1 = Vector2::new(f32::cos(r1), f32::sin(r1));
-        let v2 = Vector2::new(f32::cos(r2), f32::sin(r2));
+        let v1 = Vector2::new(real::cos(r1), real::sin(r1));
+        let v2 = Vector2::new(real::cos(r2), real::sin(r2));
         let dot = v1.dot(v2).clamp(-1.0, 1.0);
 
         let v = if dot > 0.9995 {
             //linearly interpolate to avoid numerical precision issues
             v1.linear_interpolate(v2, weight).normalized()
         } else {
-            let angle = weight * f32::cos(dot);
+            let angle = weight * real::cos(dot);
             let v3 = (v2 - v1 * dot).normalized();
-            v1 * f32::cos(angle) + v3 * f32::sin(angle)
+            v1 * real::cos(angle) + v3 * real::sin(angle)
         };
 
         // construct matrix
         let mut result = Self::IDENTITY
-            .rotated(f32::atan2(v.y, v.x))
+            .rotated(real::atan2(v.y, v.x))
             .translated(p1.linear_interpolate(p2, weight));
         result.scale_basis(s1.linear_interpolate(s2, weight));
         result
@@ -271,15 +283,15 @@ impl Transform2D {
         unsafe { std::mem::transmute::<sys::godot_transform2d, Self>(c) }
     }
 
-    fn basis_determinant(&self) -> f32 {
+    fn basis_determinant(&self) -> real {
         self.a.x * self.b.y - self.a.y * self.b.x
     }
 
-    fn tdotx(&self, v: Vector2) -> f32 {
+    fn tdotx(&self, v: Vector2) -> real {
         self.a.x * v.x + self.b.x * v.y
     }
 
-    fn tdoty(&self, v: Vector2) -> f32 {
+    fn tdoty(&self, v: Vector2) -> real {
         self.a.y * v.x + self.b.y * v.y
     }
 
@@ -365,7 +377,7 @@ fn test_transform2d_behavior_impl() {
     // Translation, rotation, scale
 
     let translation_vector = Vector2::new(3.0, 6.0);
-    let rotation_angle = std::f32::consts::FRAC_PI_2;
+    let rotation_angle = crate::core_types::real_consts::FRAC_PI_2;
     let scale_vector = Vector2::new(7.0, 9.0);
 
     let transformed_rust = new_transform_rust
@@ -458,7 +470,7 @@ godot_test!(
 
 #[test]
 fn test_transform2d_constructor() {
-    use std::f32::consts::PI;
+    use crate::core_types::real_consts::PI;
 
     let scale = Vector2::new(2.0, 0.5);
     let rotation = PI / 4.0;
@@ -475,3 +487,17 @@ fn test_transform2d_constructor() {
         "{actual_local_right:?} != {expected_local_right:?}"
     );
 }
+
+#[test]
+fn test_transform2d_xform_slice() {
+    let tr = Transform2D::from_scale_rotation_origin(
+        Vector2::new(2.0, 0.5),
+        0.7,
+        Vector2::new(250.0, 150.0),
+    );
+    let mut vectors = [Vector2::ZERO, Vector2::new(1.0, -2.0), Vector2::ONE];
+    let expected = vectors.map(|v| tr.xform(v));
+
+    tr.xform_slice(&mut vectors);
+    assert_eq!(vectors, expected);
+}
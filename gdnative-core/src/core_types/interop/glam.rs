@@ -0,0 +1,189 @@
+use crate::core_types::glam_real::{Affine2, Affine3A, Mat3, Quat as GlamQuat, Vec2, Vec3, Vec3A};
+use crate::core_types::{Basis, Color, Quat, Transform, Transform2D, Vector2, Vector3};
+
+impl From<Vec2> for Vector2 {
+    #[inline]
+    fn from(v: Vec2) -> Self {
+        Vector2::new(v.x, v.y)
+    }
+}
+
+impl From<Vector2> for Vec2 {
+    #[inline]
+    fn from(v: Vector2) -> Self {
+        Vec2::new(v.x, v.y)
+    }
+}
+
+impl From<Vec3> for Vector3 {
+    #[inline]
+    fn from(v: Vec3) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3> for Vec3 {
+    #[inline]
+    fn from(v: Vector3) -> Self {
+        Vec3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<GlamQuat> for Quat {
+    #[inline]
+    fn from(q: GlamQuat) -> Self {
+        Quat::new(q.x, q.y, q.z, q.w)
+    }
+}
+
+impl From<Quat> for GlamQuat {
+    #[inline]
+    fn from(q: Quat) -> Self {
+        GlamQuat::from_xyzw(q.x, q.y, q.z, q.w)
+    }
+}
+
+impl From<Mat3> for Basis {
+    #[inline]
+    fn from(m: Mat3) -> Self {
+        Basis::from_basis_vectors(m.x_axis.into(), m.y_axis.into(), m.z_axis.into())
+    }
+}
+
+impl From<Basis> for Mat3 {
+    #[inline]
+    fn from(b: Basis) -> Self {
+        Mat3::from_cols(b.a().into(), b.b().into(), b.c().into())
+    }
+}
+
+impl From<Affine3A> for Transform {
+    #[inline]
+    fn from(t: Affine3A) -> Self {
+        let m = t.matrix3;
+        let vector = |v: Vec3A| Vector3::new(v.x, v.y, v.z);
+        Transform {
+            basis: Basis::from_basis_vectors(vector(m.x_axis), vector(m.y_axis), vector(m.z_axis)),
+            origin: vector(t.translation),
+        }
+    }
+}
+
+impl From<Transform> for Affine3A {
+    #[inline]
+    fn from(t: Transform) -> Self {
+        let vector = |v: Vector3| Vec3A::new(v.x, v.y, v.z);
+        Affine3A::from_cols(
+            vector(t.basis.a()),
+            vector(t.basis.b()),
+            vector(t.basis.c()),
+            vector(t.origin),
+        )
+    }
+}
+
+impl From<Affine2> for Transform2D {
+    #[inline]
+    fn from(t: Affine2) -> Self {
+        Transform2D {
+            a: t.matrix2.x_axis.into(),
+            b: t.matrix2.y_axis.into(),
+            origin: t.translation.into(),
+        }
+    }
+}
+
+impl From<Transform2D> for Affine2 {
+    #[inline]
+    fn from(t: Transform2D) -> Self {
+        Affine2::from_cols(t.a.into(), t.b.into(), t.origin.into())
+    }
+}
+
+impl From<glam::Vec4> for Color {
+    #[inline]
+    fn from(v: glam::Vec4) -> Self {
+        Color::from_rgba(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Color> for glam::Vec4 {
+    #[inline]
+    fn from(c: Color) -> Self {
+        glam::Vec4::new(c.r, c.g, c.b, c.a)
+    }
+}
+
+impl_variant_via!(
+    Vec2 => Vector2,
+    Vec3 => Vector3,
+    GlamQuat => Quat,
+    Mat3 => Basis,
+    Affine3A => Transform,
+    Affine2 => Transform2D,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors() {
+        let v = Vector2::new(1.0, 2.0);
+        assert_eq!(Vec2::from(v), Vec2::new(1.0, 2.0));
+        assert_eq!(Vector2::from(Vec2::from(v)), v);
+
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(Vec3::from(v), Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(Vector3::from(Vec3::from(v)), v);
+
+        let c = Color::from_rgba(0.1, 0.2, 0.3, 0.4);
+        assert_eq!(glam::Vec4::from(c), glam::Vec4::new(0.1, 0.2, 0.3, 0.4));
+        assert_eq!(Color::from(glam::Vec4::from(c)), c);
+    }
+
+    #[test]
+    fn rotations() {
+        let axis = Vector3::new(1.0, 2.0, 3.0).normalized();
+        let q = Quat::from_axis_angle(axis, 0.5);
+        let glam_q = GlamQuat::from_axis_angle(axis.into(), 0.5);
+        assert!(Quat::from(glam_q).is_equal_approx(q));
+        assert_eq!(Quat::from(GlamQuat::from(q)), q);
+
+        let basis = Basis::from_quat(q);
+        let mat = Mat3::from(basis);
+        assert!(Basis::from(Mat3::from_quat(glam_q)).is_equal_approx(&basis));
+        assert_eq!(Basis::from(mat), basis);
+
+        // Both libraries use column vectors, so the products must agree.
+        let v = Vector3::new(-1.0, 0.5, 2.0);
+        assert!(Vector3::from(mat * Vec3::from(v)).is_equal_approx(basis.xform(v)));
+    }
+
+    #[test]
+    fn transforms() {
+        let basis =
+            Basis::from_euler(Vector3::new(0.3, -0.2, 1.1)).scaled(Vector3::new(2.0, 1.0, 0.5));
+        let t = Transform {
+            basis,
+            origin: Vector3::new(4.0, 5.0, 6.0),
+        };
+        let affine = Affine3A::from(t);
+        assert_eq!(Transform::from(affine), t);
+
+        let v = Vector3::new(-1.0, 0.5, 2.0);
+        let p = affine.transform_point3(v.into());
+        assert!(Vector3::from(p).is_equal_approx(t.xform(v)));
+
+        let t = Transform2D::from_scale_rotation_origin(
+            Vector2::new(3.0, 2.0),
+            0.7,
+            Vector2::new(1.0, -2.0),
+        );
+        let affine = Affine2::from(t);
+        assert_eq!(Transform2D::from(affine), t);
+
+        let v = Vector2::new(-1.0, 0.5);
+        assert!(Vector2::from(affine.transform_point2(v.into())).is_equal_approx(t.xform(v)));
+    }
+}
@@ -0,0 +1,231 @@
+use mint::IntoMint;
+
+use crate::core_types::{real, Basis, Color, Quat, Transform, Transform2D, Vector2, Vector3};
+
+impl From<mint::Vector2<real>> for Vector2 {
+    #[inline]
+    fn from(v: mint::Vector2<real>) -> Self {
+        Vector2::new(v.x, v.y)
+    }
+}
+
+impl From<Vector2> for mint::Vector2<real> {
+    #[inline]
+    fn from(v: Vector2) -> Self {
+        mint::Vector2 { x: v.x, y: v.y }
+    }
+}
+
+impl IntoMint for Vector2 {
+    type MintType = mint::Vector2<real>;
+}
+
+impl From<mint::Vector3<real>> for Vector3 {
+    #[inline]
+    fn from(v: mint::Vector3<real>) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3> for mint::Vector3<real> {
+    #[inline]
+    fn from(v: Vector3) -> Self {
+        mint::Vector3 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl IntoMint for Vector3 {
+    type MintType = mint::Vector3<real>;
+}
+
+impl From<mint::Quaternion<real>> for Quat {
+    #[inline]
+    fn from(q: mint::Quaternion<real>) -> Self {
+        Quat::new(q.v.x, q.v.y, q.v.z, q.s)
+    }
+}
+
+impl From<Quat> for mint::Quaternion<real> {
+    #[inline]
+    fn from(q: Quat) -> Self {
+        mint::Quaternion {
+            v: mint::Vector3 {
+                x: q.x,
+                y: q.y,
+                z: q.z,
+            },
+            s: q.w,
+        }
+    }
+}
+
+impl IntoMint for Quat {
+    type MintType = mint::Quaternion<real>;
+}
+
+impl From<mint::ColumnMatrix3<real>> for Basis {
+    #[inline]
+    fn from(m: mint::ColumnMatrix3<real>) -> Self {
+        Basis::from_basis_vectors(m.x.into(), m.y.into(), m.z.into())
+    }
+}
+
+impl From<Basis> for mint::ColumnMatrix3<real> {
+    #[inline]
+    fn from(b: Basis) -> Self {
+        mint::ColumnMatrix3 {
+            x: b.a().into(),
+            y: b.b().into(),
+            z: b.c().into(),
+        }
+    }
+}
+
+impl IntoMint for Basis {
+    type MintType = mint::ColumnMatrix3<real>;
+}
+
+impl From<mint::ColumnMatrix3x4<real>> for Transform {
+    #[inline]
+    fn from(m: mint::ColumnMatrix3x4<real>) -> Self {
+        Transform {
+            basis: Basis::from_basis_vectors(m.x.into(), m.y.into(), m.z.into()),
+            origin: m.w.into(),
+        }
+    }
+}
+
+impl From<Transform> for mint::ColumnMatrix3x4<real> {
+    #[inline]
+    fn from(t: Transform) -> Self {
+        mint::ColumnMatrix3x4 {
+            x: t.basis.a().into(),
+            y: t.basis.b().into(),
+            z: t.basis.c().into(),
+            w: t.origin.into(),
+        }
+    }
+}
+
+impl IntoMint for Transform {
+    type MintType = mint::ColumnMatrix3x4<real>;
+}
+
+impl From<mint::ColumnMatrix2x3<real>> for Transform2D {
+    #[inline]
+    fn from(m: mint::ColumnMatrix2x3<real>) -> Self {
+        Transform2D {
+            a: m.x.into(),
+            b: m.y.into(),
+            origin: m.z.into(),
+        }
+    }
+}
+
+impl From<Transform2D> for mint::ColumnMatrix2x3<real> {
+    #[inline]
+    fn from(t: Transform2D) -> Self {
+        mint::ColumnMatrix2x3 {
+            x: t.a.into(),
+            y: t.b.into(),
+            z: t.origin.into(),
+        }
+    }
+}
+
+impl IntoMint for Transform2D {
+    type MintType = mint::ColumnMatrix2x3<real>;
+}
+
+impl From<mint::Vector4<f32>> for Color {
+    #[inline]
+    fn from(v: mint::Vector4<f32>) -> Self {
+        Color::from_rgba(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Color> for mint::Vector4<f32> {
+    #[inline]
+    fn from(c: Color) -> Self {
+        mint::Vector4 {
+            x: c.r,
+            y: c.g,
+            z: c.b,
+            w: c.a,
+        }
+    }
+}
+
+impl IntoMint for Color {
+    type MintType = mint::Vector4<f32>;
+}
+
+impl_variant_via!(
+    mint::Vector2<real> => Vector2,
+    mint::Vector3<real> => Vector3,
+    mint::Quaternion<real> => Quat,
+    mint::ColumnMatrix3<real> => Basis,
+    mint::ColumnMatrix3x4<real> => Transform,
+    mint::ColumnMatrix2x3<real> => Transform2D,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors() {
+        let v = Vector2::new(1.0, 2.0);
+        assert_eq!(mint::Vector2::from(v), mint::Vector2 { x: 1.0, y: 2.0 });
+        assert_eq!(Vector2::from(mint::Vector2::from(v)), v);
+
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(Vector3::from(mint::Vector3::from(v)), v);
+
+        let c = Color::from_rgba(0.1, 0.2, 0.3, 0.4);
+        let m = mint::Vector4::from(c);
+        assert_eq!([m.x, m.y, m.z, m.w], [0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(Color::from(m), c);
+    }
+
+    #[test]
+    fn rotations() {
+        let q = Quat::from_axis_angle(Vector3::new(1.0, 2.0, 3.0).normalized(), 0.5);
+        let m = mint::Quaternion::from(q);
+        assert_eq!(m.s, q.w);
+        assert_eq!(Quat::from(m), q);
+
+        let basis = Basis::from_quat(q);
+        let m = mint::ColumnMatrix3::from(basis);
+        assert_eq!(Vector3::from(m.x), basis.a());
+        assert_eq!(Basis::from(m), basis);
+
+        // The engine stores the rows, which matches mint's row-major layout.
+        let rows = mint::RowMatrix3::from(m);
+        assert_eq!(Vector3::from(rows.x), basis.elements[0]);
+    }
+
+    #[test]
+    fn transforms() {
+        let t = Transform {
+            basis: Basis::from_euler(Vector3::new(0.3, -0.2, 1.1)),
+            origin: Vector3::new(4.0, 5.0, 6.0),
+        };
+        let m = mint::ColumnMatrix3x4::from(t);
+        assert_eq!(Vector3::from(m.w), t.origin);
+        assert_eq!(Transform::from(m), t);
+
+        let t = Transform2D::from_scale_rotation_origin(
+            Vector2::new(3.0, 2.0),
+            0.7,
+            Vector2::new(1.0, -2.0),
+        );
+        let m = mint::ColumnMatrix2x3::from(t);
+        assert_eq!(Vector2::from(m.z), t.origin);
+        assert_eq!(Transform2D::from(m), t);
+    }
+}
@@ -0,0 +1,36 @@
+//! Conversions between the math types and those of other math libraries.
+//!
+//! The vector and matrix types convert to the library's types with the same precision as
+//! [`real`][super::real], e.g. `Vector2` converts to `glam::Vec2` by default and to `glam::DVec2`
+//! with the `double-precision` feature. `Color` always converts to 4-component `f32` vectors.
+//!
+//! Types converting losslessly to a core type also implement `ToVariant` and `FromVariant` by
+//! going through that type, so they can be passed to and from the engine directly.
+
+/// Implements `ToVariant` and `FromVariant` for foreign types that convert to and from a core type.
+macro_rules! impl_variant_via {
+    ($($Type:ty => $Core:ty),* $(,)?) => {
+        $(
+            impl $crate::core_types::ToVariant for $Type {
+                #[inline]
+                fn to_variant(&self) -> $crate::core_types::Variant {
+                    <$Core>::from(*self).to_variant()
+                }
+            }
+
+            impl $crate::core_types::FromVariant for $Type {
+                #[inline]
+                fn from_variant(
+                    variant: &$crate::core_types::Variant,
+                ) -> Result<Self, $crate::core_types::FromVariantError> {
+                    <$Core>::from_variant(variant).map(Self::from)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "mint")]
+mod mint;
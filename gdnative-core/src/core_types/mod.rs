@@ -4,6 +4,9 @@
 //! are the counterparts to built-in types in GDScript.
 //!
 //! godot-rust provides optional serialization support for many core types.  Enable the feature `serde` to make use of it.
+//!
+//! The math types can be converted to and from their [`glam`](https://docs.rs/glam) and [`mint`](https://docs.rs/mint)
+//! counterparts with `From`/`Into`.  Enable the features of the same names to make use of it.
 
 mod geom;
 
@@ -11,31 +14,177 @@ mod access;
 mod color;
 mod dictionary;
 mod error;
+mod name;
 mod node_path;
+mod node_path_buf;
 mod pool_array;
+pub mod pool_vec;
 mod rid;
 mod string;
-mod variant;
+mod typed_array;
+mod typed_dictionary;
+pub mod variant;
 mod variant_array;
 mod vector2;
 mod vector3;
 
+#[cfg(any(feature = "glam", feature = "mint"))]
+mod interop;
+
 pub use access::*;
 pub use color::*;
 pub use dictionary::*;
 pub use error::{GodotError, GodotResult};
 pub use geom::*;
+pub use name::*;
 pub use node_path::*;
+pub use node_path_buf::*;
 pub use pool_array::*;
+pub use pool_vec::PoolVec;
 pub use rid::*;
 pub use string::*;
+pub use typed_array::*;
+pub use typed_dictionary::*;
 pub use variant::*;
 pub use variant_array::*;
 pub use vector2::*;
 pub use vector3::*;
 
+/// Re-export of the `glam` version the conversions are implemented for.
+#[cfg(feature = "glam")]
+pub use glam;
+
+/// Re-export of the `mint` version the conversions are implemented for.
+#[cfg(feature = "mint")]
+pub use mint;
+
 use approx::relative_eq;
 
+/// Floating-point type used by the geometry types, matching Godot's `real_t`.
+///
+/// This is `f32` by default, and `f64` when the `double-precision` feature is enabled, for
+/// engine builds with `float=64`.
+#[cfg(not(feature = "double-precision"))]
+#[allow(non_camel_case_types)]
+pub type real = f32;
+
+/// Floating-point type used by the geometry types, matching Godot's `real_t`.
+///
+/// This is `f32` by default, and `f64` when the `double-precision` feature is enabled, for
+/// engine builds with `float=64`.
+#[cfg(feature = "double-precision")]
+#[allow(non_camel_case_types)]
+pub type real = f64;
+
+/// Mathematical constants of type [`real`].
+#[cfg(not(feature = "double-precision"))]
+pub use std::f32::consts as real_consts;
+
+/// Mathematical constants of type [`real`].
+#[cfg(feature = "double-precision")]
+pub use std::f64::consts as real_consts;
+
+/// Conversions between floating-point types that behave the same with and without the
+/// `double-precision` feature, where [`real`] is either `f32` or `f64`.
+///
+/// This avoids casts like `x as f64`, which are redundant in one of the modes.
+pub trait RealConv: Copy {
+    /// Converts to `f32`, rounding if `Self` is `f64`.
+    fn as_f32(self) -> f32;
+
+    /// Converts to `f64`, which is lossless.
+    fn as_f64(self) -> f64;
+
+    /// Converts from `f32`, which is lossless.
+    fn from_f32(f: f32) -> Self;
+
+    /// Converts from `f64`, rounding if `Self` is `f32`.
+    fn from_f64(f: f64) -> Self;
+}
+
+impl RealConv for f32 {
+    #[inline]
+    fn as_f32(self) -> f32 {
+        self
+    }
+
+    #[inline]
+    fn as_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    #[inline]
+    fn from_f32(f: f32) -> Self {
+        f
+    }
+
+    #[inline]
+    fn from_f64(f: f64) -> Self {
+        f as f32
+    }
+}
+
+impl RealConv for f64 {
+    #[inline]
+    fn as_f32(self) -> f32 {
+        self as f32
+    }
+
+    #[inline]
+    fn as_f64(self) -> f64 {
+        self
+    }
+
+    #[inline]
+    fn from_f32(f: f32) -> Self {
+        f64::from(f)
+    }
+
+    #[inline]
+    fn from_f64(f: f64) -> Self {
+        f
+    }
+}
+
+/// `glam` types with the same precision as [`real`].
+#[cfg(not(feature = "double-precision"))]
+mod glam_real {
+    pub(crate) use glam::{Mat3, Mat3A, Mat4, Quat, Vec2, Vec3, Vec3A};
+
+    #[cfg(feature = "glam")]
+    pub(crate) use glam::{Affine2, Affine3A};
+}
+
+/// `glam` types with the same precision as [`real`].
+#[cfg(feature = "double-precision")]
+mod glam_real {
+    pub(crate) use glam::{
+        DMat3 as Mat3, DMat3 as Mat3A, DMat4 as Mat4, DQuat as Quat, DVec2 as Vec2, DVec3 as Vec3,
+        DVec3 as Vec3A,
+    };
+
+    #[cfg(feature = "glam")]
+    pub(crate) use glam::{DAffine2 as Affine2, DAffine3 as Affine3A};
+}
+
+// The math types are passed to the engine by pointer or transmuted, so their size must match the
+// engine's, which depends on `real_t`.
+const _: () = {
+    use crate::sys;
+    use std::mem::size_of;
+
+    assert!(size_of::<Vector2>() == size_of::<sys::godot_vector2>());
+    assert!(size_of::<Vector3>() == size_of::<sys::godot_vector3>());
+    assert!(size_of::<Quat>() == size_of::<sys::godot_quat>());
+    assert!(size_of::<Basis>() == size_of::<sys::godot_basis>());
+    assert!(size_of::<Transform>() == size_of::<sys::godot_transform>());
+    assert!(size_of::<Transform2D>() == size_of::<sys::godot_transform2d>());
+    assert!(size_of::<Plane>() == size_of::<sys::godot_plane>());
+    assert!(size_of::<Aabb>() == size_of::<sys::godot_aabb>());
+    assert!(size_of::<Rect2>() == size_of::<sys::godot_rect2>());
+    assert!(size_of::<Color>() == size_of::<sys::godot_color>());
+};
+
 const CMP_EPSILON: f64 = 0.00001;
 
 // This trait is intended for internal use
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+use crate::core_types::{GodotString, NodePath, StringName};
+
+/// Trait for arguments that name a method, signal or property. This is a sealed trait with no
+/// public interface.
+///
+/// Godot looks these names up by value, so they are accepted in several forms:
+///
+/// - [`StringName`]s, preferably `&'static` ones created with the [`string_name!`] macro. These
+///   are converted to a `GodotString` without copying the characters.
+/// - `GodotString`s, which are passed as they are.
+/// - `&str`, `String` and anything else that implements `AsRef<str>`. The `GodotString` for each
+///   distinct name is cached, so that names used on every frame are only converted once.
+/// - [`NodePath`]s, which are converted like `GodotString::from(path)`.
+///
+/// This covers every `Into<GodotString>` conversion provided by this crate, so code written for the
+/// former `impl Into<GodotString>` parameters of the generated API keeps compiling.
+///
+/// [`string_name!`]: crate::string_name
+pub trait NameArg: private::Sealed {
+    #[doc(hidden)]
+    fn into_name(self) -> GodotString;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+impl<S: AsRef<str>> private::Sealed for S {}
+impl private::Sealed for GodotString {}
+impl private::Sealed for &GodotString {}
+impl private::Sealed for StringName {}
+impl private::Sealed for &StringName {}
+impl private::Sealed for NodePath {}
+
+impl<S: AsRef<str>> NameArg for S {
+    #[inline]
+    fn into_name(self) -> GodotString {
+        cached_name(self.as_ref())
+    }
+}
+
+impl NameArg for GodotString {
+    #[inline]
+    fn into_name(self) -> GodotString {
+        self
+    }
+}
+
+impl NameArg for &GodotString {
+    #[inline]
+    fn into_name(self) -> GodotString {
+        self.clone()
+    }
+}
+
+impl NameArg for StringName {
+    #[inline]
+    fn into_name(self) -> GodotString {
+        self.to_godot_string()
+    }
+}
+
+impl NameArg for &StringName {
+    #[inline]
+    fn into_name(self) -> GodotString {
+        self.to_godot_string()
+    }
+}
+
+impl NameArg for NodePath {
+    #[inline]
+    fn into_name(self) -> GodotString {
+        self.into()
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Name cache
+
+/// Upper bound for the number of cached names, in case names are generated dynamically.
+const NAME_CACHE_CAPACITY: usize = 4096;
+
+static NAME_CACHE: Lazy<RwLock<HashMap<Box<str>, GodotString>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Returns the `GodotString` for `name`, converting it only the first time it is used.
+fn cached_name(name: &str) -> GodotString {
+    if let Some(cached) = NAME_CACHE.read().get(name) {
+        return cached.clone();
+    }
+
+    let converted = GodotString::from_str(name);
+    let mut cache = NAME_CACHE.write();
+    if cache.len() < NAME_CACHE_CAPACITY {
+        cache.insert(name.into(), converted.clone());
+    }
+    converted
+}
+
+/// Clears the name cache, releasing the cached strings while the API is still bound.
+pub(crate) fn cleanup_names() {
+    NAME_CACHE.write().clear();
+}
+
+godot_test!(test_name_arg {
+    use crate::core_types::{FromVariant, ToVariant, VariantType};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn cached() -> &'static StringName {
+        crate::string_name!("test_name_arg")
+    }
+
+    assert!(std::ptr::eq(cached(), cached()));
+    assert_eq!(cached().to_godot_string(), GodotString::from("test_name_arg"));
+
+    let expected = GodotString::from("test_name_arg");
+    assert_eq!("test_name_arg".into_name(), expected);
+    assert_eq!(String::from("test_name_arg").into_name(), expected);
+    assert_eq!((&String::from("test_name_arg")).into_name(), expected);
+    assert_eq!(expected.clone().into_name(), expected);
+    assert_eq!("test_name_arg".into_name(), expected, "cached names should stay the same");
+    assert_eq!((&expected).into_name(), expected);
+    assert_eq!(cached().into_name(), expected);
+    assert_eq!(NodePath::from_str("test_name_arg").into_name(), expected);
+
+    let variant = cached().to_variant();
+    assert_eq!(VariantType::GodotString, variant.get_type());
+    let name = StringName::from_variant(&variant).unwrap();
+    assert_eq!(&name, cached());
+    assert!(StringName::from_variant(&1.to_variant()).is_err());
+
+    let hash = |name: &StringName| {
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(&name), hash(cached()));
+    assert_ne!(hash(&StringName::from_str("other")), hash(cached()));
+});
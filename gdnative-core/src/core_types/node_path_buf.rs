@@ -0,0 +1,474 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::core_types::{FromVariant, FromVariantError, NodePath, ToVariant, Variant};
+
+/// A node path as a plain Rust value, consisting of node names, subnames and an absolute flag.
+///
+/// Unlike [`NodePath`], which is owned by the engine, `NodePathBuf` can be parsed, inspected and
+/// edited without any FFI calls, and doesn't require the engine to be running. It converts
+/// losslessly to and from `NodePath` with `From`, and it can be passed wherever bindings take
+/// `impl Into<NodePath>`.
+///
+/// Paths follow the syntax of the engine: names are separated by `/`, a leading `/` makes the path
+/// absolute, and subnames (resources and properties) follow the node names, each introduced by a
+/// `:`. For example, `"Path2D/PathFollow2D/Sprite:texture:size"` has the names `Path2D`,
+/// `PathFollow2D` and `Sprite`, and the subnames `texture` and `size`.
+///
+/// Use the [`node_path!`][crate::node_path] macro to check paths known at compile time.
+///
+/// # Examples
+///
+/// ```
+/// # use gdnative::core_types::NodePathBuf;
+/// let path: NodePathBuf = "Player/Sprite:modulate".parse().unwrap();
+/// assert_eq!(path.names(), ["Player", "Sprite"]);
+/// assert_eq!(path.subnames(), ["modulate"]);
+/// assert_eq!(path.to_string(), "Player/Sprite:modulate");
+///
+/// let level: NodePathBuf = "/root/Level".parse().unwrap();
+/// let player = level.join(&"Player".parse().unwrap());
+/// assert_eq!(player.to_string(), "/root/Level/Player");
+///
+/// let hud: NodePathBuf = "/root/Hud".parse().unwrap();
+/// assert_eq!(hud.relative_to(&player).unwrap().to_string(), "../../Hud");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodePathBuf {
+    absolute: bool,
+    names: Vec<String>,
+    subnames: Vec<String>,
+}
+
+/// Error returned when parsing a malformed node path.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NodePathError {
+    /// A node name is empty, like in `"Player//Sprite"` or `"Player/"`. `position` is the byte
+    /// offset where the name should start.
+    EmptyName { position: usize },
+    /// A subname is empty, like in `"Sprite::modulate"`. `position` is the byte offset where the
+    /// subname should start.
+    EmptySubname { position: usize },
+}
+
+impl fmt::Display for NodePathError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodePathError::EmptyName { position } => {
+                write!(f, "empty node name at byte {position}")
+            }
+            NodePathError::EmptySubname { position } => {
+                write!(f, "empty subname at byte {position}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NodePathError {}
+
+impl NodePathBuf {
+    /// Creates an empty, relative path.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a path like `"../Player/Sprite:texture:size"`.
+    ///
+    /// The syntax is the same as the engine's, except that empty node names, which the engine
+    /// skips silently, are rejected. A single `:` at the end is allowed and ignored, like in the
+    /// engine.
+    ///
+    /// # Errors
+    ///
+    /// If a node name or subname is empty.
+    #[inline]
+    pub fn parse(path: &str) -> Result<Self, NodePathError> {
+        Self::validate(path)?;
+
+        let (names, subnames) = path.split_once(':').unwrap_or((path, ""));
+        let (absolute, names) = match names.strip_prefix('/') {
+            Some(names) => (true, names),
+            None => (false, names),
+        };
+        let segments = |text: &str, separator: char| -> Vec<String> {
+            text.split(separator)
+                .filter(|segment| !segment.is_empty())
+                .map(String::from)
+                .collect()
+        };
+
+        Ok(NodePathBuf {
+            absolute,
+            names: segments(names, '/'),
+            subnames: segments(subnames, ':'),
+        })
+    }
+
+    /// Checks whether [`Self::parse()`] would succeed for `path`, without allocating.
+    ///
+    /// This is a `const fn`, so it can be used to check paths at compile time.
+    ///
+    /// # Errors
+    ///
+    /// If a node name or subname is empty.
+    #[inline]
+    pub const fn validate(path: &str) -> Result<(), NodePathError> {
+        let bytes = path.as_bytes();
+        let len = bytes.len();
+
+        let mut colon = 0;
+        while colon < len && bytes[colon] != b':' {
+            colon += 1;
+        }
+
+        // Node names, unless there are none at all like in "", "/" or ":property"
+        let mut start = if colon > 0 && bytes[0] == b'/' { 1 } else { 0 };
+        if start < colon {
+            let mut i = start;
+            while i <= colon {
+                if i == colon || bytes[i] == b'/' {
+                    if i == start {
+                        return Err(NodePathError::EmptyName { position: start });
+                    }
+                    start = i + 1;
+                }
+                i += 1;
+            }
+        }
+
+        // Subnames, where a trailing ':' is allowed
+        let mut start = colon + 1;
+        let mut i = start;
+        while i < len {
+            if bytes[i] == b':' {
+                if i == start {
+                    return Err(NodePathError::EmptySubname { position: start });
+                }
+                start = i + 1;
+            }
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the path is absolute, i.e. starts with `/`.
+    #[inline]
+    pub fn is_absolute(&self) -> bool {
+        self.absolute
+    }
+
+    /// Returns `true` if the path is relative and has neither names nor subnames, like the
+    /// engine's `NodePath.is_empty()`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.absolute && self.names.is_empty() && self.subnames.is_empty()
+    }
+
+    /// Returns the node names, from the outermost to the innermost node.
+    #[inline]
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the subnames, i.e. the names of resources and properties after the last node.
+    #[inline]
+    pub fn subnames(&self) -> &[String] {
+        &self.subnames
+    }
+
+    /// Resolves `path` relative to the node `self` points to.
+    ///
+    /// If `path` is absolute, it is returned as it is. Otherwise, its names are appended to the
+    /// names of `self`, and its subnames replace the subnames of `self`.
+    ///
+    /// The result is not simplified, see [`Self::simplified()`].
+    #[inline]
+    pub fn join(&self, path: &NodePathBuf) -> NodePathBuf {
+        if path.absolute {
+            return path.clone();
+        }
+
+        NodePathBuf {
+            absolute: self.absolute,
+            names: self.names.iter().chain(&path.names).cloned().collect(),
+            subnames: path.subnames.clone(),
+        }
+    }
+
+    /// Returns the path with `.` names removed and `..` names collapsed with the preceding name,
+    /// like the engine's `NodePath::simplify()`.
+    ///
+    /// A path that would become empty this way is simplified to `.` instead.
+    #[inline]
+    pub fn simplified(&self) -> NodePathBuf {
+        let mut names = self.names.clone();
+        let mut i = 0;
+        while i < names.len() && names.len() > 1 {
+            if names[i] == "." {
+                names.remove(i);
+            } else if i > 0 && names[i] == ".." && names[i - 1] != "." && names[i - 1] != ".." {
+                names.drain(i - 1..=i);
+                i -= 1;
+                if names.is_empty() {
+                    names.push(".".into());
+                }
+            } else {
+                i += 1;
+            }
+        }
+
+        NodePathBuf {
+            absolute: self.absolute,
+            names,
+            subnames: self.subnames.clone(),
+        }
+    }
+
+    /// Returns a relative path from the node `base` points to, to the node `self` points to,
+    /// like the engine's `Node::get_path_to()`. The subnames of `self` are kept.
+    ///
+    /// Returns `None` if only one of the paths is absolute, or if `base` goes up through a
+    /// parent that `self` doesn't share, since its name is unknown.
+    #[inline]
+    pub fn relative_to(&self, base: &NodePathBuf) -> Option<NodePathBuf> {
+        if self.absolute != base.absolute {
+            return None;
+        }
+
+        let names = |path: &NodePathBuf| -> Vec<String> {
+            let simplified = path.simplified();
+            if simplified.names == ["."] {
+                Vec::new()
+            } else {
+                simplified.names
+            }
+        };
+        let target = names(self);
+        let base = names(base);
+
+        let common = target
+            .iter()
+            .zip(&base)
+            .take_while(|(target, base)| target == base)
+            .count();
+        if base[common..].iter().any(|name| name == "..") {
+            return None;
+        }
+
+        let mut names: Vec<String> = std::iter::repeat("..".to_string())
+            .take(base.len() - common)
+            .chain(target[common..].iter().cloned())
+            .collect();
+        if names.is_empty() {
+            names.push(".".into());
+        }
+
+        Some(NodePathBuf {
+            absolute: false,
+            names,
+            subnames: self.subnames.clone(),
+        })
+    }
+}
+
+impl fmt::Display for NodePathBuf {
+    /// Formats the path the same way as the engine.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.absolute {
+            f.write_str("/")?;
+        }
+        for (i, name) in self.names.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            f.write_str(name)?;
+        }
+        for subname in &self.subnames {
+            write!(f, ":{subname}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for NodePathBuf {
+    type Err = NodePathError;
+
+    #[inline]
+    fn from_str(path: &str) -> Result<Self, NodePathError> {
+        Self::parse(path)
+    }
+}
+
+impl From<&NodePathBuf> for NodePath {
+    #[inline]
+    fn from(path: &NodePathBuf) -> NodePath {
+        NodePath::from_str(&path.to_string())
+    }
+}
+
+impl From<NodePathBuf> for NodePath {
+    #[inline]
+    fn from(path: NodePathBuf) -> NodePath {
+        NodePath::from(&path)
+    }
+}
+
+impl From<&NodePath> for NodePathBuf {
+    #[inline]
+    fn from(path: &NodePath) -> NodePathBuf {
+        NodePathBuf {
+            absolute: path.is_absolute(),
+            names: (0..path.name_count())
+                .map(|i| path.get_name(i).to_string())
+                .collect(),
+            subnames: (0..path.get_subname_count())
+                .map(|i| path.get_subname(i).to_string())
+                .collect(),
+        }
+    }
+}
+
+impl From<NodePath> for NodePathBuf {
+    #[inline]
+    fn from(path: NodePath) -> NodePathBuf {
+        NodePathBuf::from(&path)
+    }
+}
+
+impl ToVariant for NodePathBuf {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        NodePath::from(self).to_variant()
+    }
+}
+
+impl FromVariant for NodePathBuf {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        NodePath::from_variant(variant).map(NodePathBuf::from)
+    }
+}
+
+godot_test!(test_node_path_buf_conversion {
+    use crate::core_types::VariantType;
+
+    for text in ["", "/", "/root/Level/Player", "../Sprite:texture:size", ":modulate", "."] {
+        let path = NodePathBuf::parse(text).unwrap();
+        let engine = NodePath::from(&path);
+        assert_eq!(engine.to_string(), text);
+        assert_eq!(engine.is_absolute(), path.is_absolute());
+        assert_eq!(engine.is_empty(), path.is_empty());
+        assert_eq!(NodePathBuf::from(&engine), path);
+
+        let variant = path.to_variant();
+        assert_eq!(VariantType::NodePath, variant.get_type());
+        assert_eq!(NodePathBuf::from_variant(&variant), Ok(path));
+    }
+
+    assert_eq!(
+        crate::node_path!("Player/Sprite:modulate"),
+        NodePathBuf::from(NodePath::from_str("Player/Sprite:modulate")),
+    );
+    assert!(NodePathBuf::from_variant(&1.to_variant()).is_err());
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(text: &str) -> NodePathBuf {
+        NodePathBuf::parse(text).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let p = path("/root/Level/Player:position:x");
+        assert!(p.is_absolute());
+        assert_eq!(p.names(), ["root", "Level", "Player"]);
+        assert_eq!(p.subnames(), ["position", "x"]);
+
+        let p = path(":modulate");
+        assert!(!p.is_absolute());
+        assert!(p.names().is_empty());
+        assert_eq!(p.subnames(), ["modulate"]);
+
+        assert!(path("").is_empty());
+        assert!(!path("/").is_empty());
+        assert_eq!(path("/").names().len(), 0);
+        assert_eq!(path("Sprite:texture:"), path("Sprite:texture"));
+        assert_eq!(path("../A/./B").names(), ["..", "A", ".", "B"]);
+        assert_eq!(path("A:b/c").subnames(), ["b/c"]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let empty_name = |position| Err(NodePathError::EmptyName { position });
+        let empty_subname = |position| Err(NodePathError::EmptySubname { position });
+
+        assert_eq!(NodePathBuf::validate("A//B"), empty_name(2));
+        assert_eq!(NodePathBuf::validate("//A"), empty_name(1));
+        assert_eq!(NodePathBuf::validate("A/"), empty_name(2));
+        assert_eq!(NodePathBuf::validate("A/:b"), empty_name(2));
+        assert_eq!(NodePathBuf::validate("/A/"), empty_name(3));
+        assert_eq!(NodePathBuf::validate("A::b"), empty_subname(2));
+        assert_eq!(NodePathBuf::validate("::"), empty_subname(1));
+        assert_eq!(
+            NodePathBuf::parse("A:b::c"),
+            Err(NodePathError::EmptySubname { position: 4 })
+        );
+        assert_eq!(
+            "A//B".parse::<NodePathBuf>(),
+            Err(NodePathError::EmptyName { position: 2 })
+        );
+    }
+
+    #[test]
+    fn display() {
+        for text in ["", "/", "A", "/root/A/B", "../A:b:c", ":b", "/:b", "./A"] {
+            assert_eq!(path(text).to_string(), text);
+        }
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(
+            path("/root/A:x").join(&path("B/C:y")),
+            path("/root/A/B/C:y")
+        );
+        assert_eq!(path("A").join(&path(":y")), path("A:y"));
+        assert_eq!(path("A").join(&path("/root")), path("/root"));
+        assert_eq!(path("A/B").join(&path("../C")), path("A/B/../C"));
+    }
+
+    #[test]
+    fn simplified() {
+        assert_eq!(path("A/B/../C").simplified(), path("A/C"));
+        assert_eq!(path("./A/./B").simplified(), path("A/B"));
+        assert_eq!(path("A/..").simplified(), path("."));
+        assert_eq!(path("../../A").simplified(), path("../../A"));
+        assert_eq!(path("/root/A/../B:x").simplified(), path("/root/B:x"));
+        assert_eq!(path(".").simplified(), path("."));
+    }
+
+    #[test]
+    fn relative_to() {
+        let relative =
+            |target: &str, base: &str| path(target).relative_to(&path(base)).map(|p| p.to_string());
+
+        assert_eq!(
+            relative("/root/Hud", "/root/Level/Player").as_deref(),
+            Some("../../Hud")
+        );
+        assert_eq!(relative("/root/A/B:x", "/root").as_deref(), Some("A/B:x"));
+        assert_eq!(relative("/root/A", "/root/A").as_deref(), Some("."));
+        assert_eq!(relative("../X", "../B").as_deref(), Some("../X"));
+        assert_eq!(relative("../../X", "../B").as_deref(), Some("../../X"));
+        assert_eq!(relative("/root/A", "A"), None);
+        assert_eq!(relative("X", "../B"), None);
+    }
+}
@@ -4,12 +4,16 @@ use std::iter::{Extend, FromIterator};
 use gdnative_impl_proc_macros as macros;
 
 use crate::core_types::access::{Aligned, MaybeUnaligned};
-use crate::core_types::{Color, GodotString, VariantArray, Vector2, Vector3};
+use crate::core_types::{real, Color, GodotString, VariantArray, Vector2, Vector3};
 use crate::object::NewRef;
 use crate::private::get_api;
 
+mod byte_array;
+mod fastlz;
+
 #[cfg(feature = "gd-test")]
 mod godot_tests;
+pub use byte_array::{Base64Error, CompressionMode, DecompressError};
 #[cfg(feature = "gd-test")]
 pub use godot_tests::*;
 
@@ -496,7 +500,7 @@ macros::impl_typed_array_element! {
     impl PoolElement for i32 => int { .. }
 }
 macros::impl_typed_array_element! {
-    impl PoolElement for f32 => real { .. }
+    impl PoolElement for real => real { .. }
 }
 macros::impl_typed_array_element! {
     impl PoolElement for GodotString
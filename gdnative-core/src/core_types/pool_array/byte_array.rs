@@ -0,0 +1,424 @@
+use std::fmt;
+
+use crate::core_types::GodotString;
+use crate::digest::{hex, sha256};
+
+use super::{fastlz, PoolArray};
+
+/// Compression modes for [`PoolArray<u8>::compress`], corresponding to the
+/// `File.COMPRESSION_*` constants in GDScript.
+///
+/// FastLZ is always available. The other modes are enabled by the `compression` feature, which
+/// links the zlib and Zstandard libraries that Godot itself uses.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CompressionMode {
+    /// FastLZ, corresponding to `File.COMPRESSION_FASTLZ`.
+    FastLz,
+    /// DEFLATE in a zlib container, corresponding to `File.COMPRESSION_DEFLATE`.
+    #[cfg(feature = "compression")]
+    Deflate,
+    /// Zstandard, corresponding to `File.COMPRESSION_ZSTD`.
+    #[cfg(feature = "compression")]
+    Zstd,
+    /// DEFLATE in a gzip container, corresponding to `File.COMPRESSION_GZIP`.
+    #[cfg(feature = "compression")]
+    Gzip,
+}
+
+/// Error returned by [`PoolArray<u8>::decompress`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecompressError {
+    /// The input is not valid compressed data for the mode, or is truncated.
+    InvalidData,
+    /// The decompressed data does not fit in the given buffer size.
+    BufferTooSmall,
+    /// The mode can't be used to decompress data of unknown size.
+    UnsupportedMode(CompressionMode),
+}
+
+impl fmt::Display for DecompressError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecompressError::InvalidData => write!(f, "invalid or truncated compressed data"),
+            DecompressError::BufferTooSmall => {
+                write!(f, "decompressed data does not fit in the buffer")
+            }
+            DecompressError::UnsupportedMode(mode) => {
+                write!(f, "{mode:?} does not support decompression of unknown size")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecompressError {}
+
+/// Error returned by [`PoolArray<u8>::from_base64`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Base64Error {
+    position: usize,
+}
+
+impl Base64Error {
+    /// Returns the byte offset in the input, at which decoding failed.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for Base64Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid base64 at byte {}", self.position)
+    }
+}
+
+impl std::error::Error for Base64Error {}
+
+/// Operations of GDScript's `PoolByteArray`, as well as the base64 marshalling functions of the
+/// `Marshalls` singleton.
+///
+/// These are implemented in Rust and work on the [`read()`][Self::read] and
+/// [`write()`][Self::write] views directly, without going through `Variant` calls. Compressed
+/// data is byte-identical to what Godot produces with its default project settings, so it can
+/// be exchanged freely between GDScript and Rust.
+impl PoolArray<u8> {
+    /// Returns a compressed copy of the array, like `PoolByteArray.compress()`.
+    ///
+    /// An empty array stays empty. Deflate and gzip use zlib's default compression level, and
+    /// Zstandard uses level 3 without long-distance matching, which are the defaults of Godot's
+    /// `compression/formats` project settings.
+    #[inline]
+    pub fn compress(&self, mode: CompressionMode) -> Self {
+        let read = self.read();
+        let mut compressed = PoolArray::new();
+        if read.is_empty() {
+            return compressed;
+        }
+
+        compressed.resize(to_len(max_compressed_len(read.len(), mode)));
+        let len = {
+            let mut write = compressed.write();
+            match mode {
+                CompressionMode::FastLz => fastlz::compress(&read, &mut write),
+                #[cfg(feature = "compression")]
+                CompressionMode::Deflate => codecs::deflate(&read, &mut write, false),
+                #[cfg(feature = "compression")]
+                CompressionMode::Gzip => codecs::deflate(&read, &mut write, true),
+                #[cfg(feature = "compression")]
+                CompressionMode::Zstd => codecs::zstd_compress(&read, &mut write),
+            }
+        };
+        compressed.resize(to_len(len));
+        compressed
+    }
+
+    /// Decompresses the array, like `PoolByteArray.decompress()`.
+    ///
+    /// `buffer_size` is the maximum size of the decompressed data, usually the length of the
+    /// array before compression. The returned array may be shorter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BufferTooSmall` if the decompressed data is larger than `buffer_size`, or
+    /// `InvalidData` if the array doesn't hold data compressed with `mode`.
+    #[inline]
+    pub fn decompress(
+        &self,
+        buffer_size: usize,
+        mode: CompressionMode,
+    ) -> Result<Self, DecompressError> {
+        let read = self.read();
+        let mut decompressed = PoolArray::new();
+        decompressed.resize(to_len(buffer_size));
+        let len = {
+            let mut write = decompressed.write();
+            match mode {
+                CompressionMode::FastLz => fastlz::decompress(&read, &mut write),
+                #[cfg(feature = "compression")]
+                CompressionMode::Deflate => codecs::inflate(&read, &mut write, false),
+                #[cfg(feature = "compression")]
+                CompressionMode::Gzip => codecs::inflate(&read, &mut write, true),
+                #[cfg(feature = "compression")]
+                CompressionMode::Zstd => codecs::zstd_decompress(&read, &mut write),
+            }
+        }?;
+        decompressed.resize(to_len(len));
+        Ok(decompressed)
+    }
+
+    /// Decompresses the array without knowing the decompressed size in advance, like
+    /// `PoolByteArray.decompress_dynamic()`.
+    ///
+    /// The output grows as needed, up to `max_output_size` bytes if given. Like in Godot, only
+    /// `Deflate` and `Gzip` are supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedMode` for other modes, `BufferTooSmall` if the decompressed data is
+    /// larger than `max_output_size`, or `InvalidData` if the array doesn't hold data compressed
+    /// with `mode`.
+    #[cfg(feature = "compression")]
+    #[inline]
+    pub fn decompress_dynamic(
+        &self,
+        max_output_size: Option<usize>,
+        mode: CompressionMode,
+    ) -> Result<Self, DecompressError> {
+        let gzip = match mode {
+            CompressionMode::Deflate => false,
+            CompressionMode::Gzip => true,
+            _ => return Err(DecompressError::UnsupportedMode(mode)),
+        };
+        codecs::inflate_dynamic(&self.read(), max_output_size, gzip)
+    }
+
+    /// Returns the SHA-256 digest of the array.
+    #[inline]
+    pub fn sha256(&self) -> [u8; 32] {
+        sha256(&self.read())
+    }
+
+    /// Returns the SHA-256 digest of the array as a lowercase hexadecimal string, like
+    /// `PoolByteArray.sha256_string()`.
+    #[inline]
+    pub fn sha256_string(&self) -> GodotString {
+        GodotString::from(hex(&self.sha256()))
+    }
+
+    /// Returns the array as a lowercase hexadecimal string, like `PoolByteArray.hex_encode()`.
+    #[inline]
+    pub fn hex_encode(&self) -> GodotString {
+        GodotString::from(hex(&self.read()))
+    }
+
+    /// Encodes the array in base64 with padding, like `Marshalls.raw_to_base64()`.
+    #[inline]
+    pub fn to_base64(&self) -> GodotString {
+        let read = self.read();
+        let mut encoded = String::with_capacity((read.len() + 2) / 3 * 4);
+        for chunk in read.chunks(3) {
+            let bits = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |bits, (i, &b)| bits | u32::from(b) << (16 - 8 * i));
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    let index = (bits >> (18 - 6 * i)) & 63;
+                    encoded.push(char::from(BASE64_ALPHABET[index as usize]));
+                } else {
+                    encoded.push('=');
+                }
+            }
+        }
+        GodotString::from(encoded)
+    }
+
+    /// Decodes a base64 string, like `Marshalls.base64_to_raw()`.
+    ///
+    /// ASCII whitespace is ignored, and the trailing padding may be omitted.
+    ///
+    /// # Errors
+    ///
+    /// If `encoded` contains a character outside of the standard base64 alphabet, misplaced
+    /// padding, or a final group that is too short to hold a byte.
+    #[inline]
+    pub fn from_base64(encoded: &str) -> Result<Self, Base64Error> {
+        let mut decoded = PoolArray::new();
+        decoded.resize(to_len(encoded.len() / 4 * 3 + 2));
+
+        let len = {
+            let mut write = decoded.write();
+            let mut len = 0;
+            let mut bits = 0u32;
+            let mut count = 0;
+            let mut padding = 0;
+
+            for (position, byte) in encoded.bytes().enumerate() {
+                if byte.is_ascii_whitespace() {
+                    continue;
+                }
+
+                // Padding may only complete a group of two or three characters
+                if byte == b'=' {
+                    if count < 2 || count + padding >= 4 {
+                        return Err(Base64Error { position });
+                    }
+                    padding += 1;
+                    continue;
+                }
+
+                let value = match BASE64_ALPHABET.iter().position(|&c| c == byte) {
+                    Some(value) if padding == 0 => value as u32,
+                    _ => return Err(Base64Error { position }),
+                };
+                bits = bits << 6 | value;
+                count += 1;
+                if count == 4 {
+                    write[len..len + 3].copy_from_slice(&bits.to_be_bytes()[1..]);
+                    len += 3;
+                    bits = 0;
+                    count = 0;
+                }
+            }
+
+            let end = Base64Error {
+                position: encoded.len(),
+            };
+            if count == 1 || (padding > 0 && count + padding != 4) {
+                return Err(end);
+            }
+            if count > 1 {
+                let bytes = (bits << (6 * (4 - count))).to_be_bytes();
+                write[len..len + count - 1].copy_from_slice(&bytes[1..count]);
+                len += count - 1;
+            }
+            len
+        };
+
+        decoded.resize(to_len(len));
+        Ok(decoded)
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn to_len(len: usize) -> i32 {
+    i32::try_from(len).expect("length should fit in i32")
+}
+
+fn max_compressed_len(len: usize, mode: CompressionMode) -> usize {
+    match mode {
+        CompressionMode::FastLz => fastlz::max_compressed_len(len),
+        #[cfg(feature = "compression")]
+        CompressionMode::Deflate | CompressionMode::Gzip => codecs::deflate_bound(len),
+        #[cfg(feature = "compression")]
+        CompressionMode::Zstd => codecs::zstd_bound(len),
+    }
+}
+
+#[cfg(feature = "compression")]
+mod codecs {
+    use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress, Status};
+
+    use super::{to_len, DecompressError, PoolArray};
+
+    /// Window size used by Godot for both zlib and gzip streams.
+    const WINDOW_BITS: u8 = 15;
+
+    /// zlib's bound for stored blocks, which holds for every compression level. The header
+    /// and trailer of gzip are the larger of the two containers.
+    pub(super) fn deflate_bound(len: usize) -> usize {
+        len + (len >> 5) + (len >> 7) + (len >> 11) + 7 + 18
+    }
+
+    pub(super) fn zstd_bound(len: usize) -> usize {
+        zstd_safe::compress_bound(len)
+    }
+
+    pub(super) fn deflate(input: &[u8], output: &mut [u8], gzip: bool) -> usize {
+        let mut stream = if gzip {
+            Compress::new_gzip(Compression::default(), WINDOW_BITS)
+        } else {
+            Compress::new(Compression::default(), true)
+        };
+
+        match stream.compress(input, output, FlushCompress::Finish) {
+            Ok(Status::StreamEnd) => stream.total_out() as usize,
+            status => panic!("deflate should finish within its bound, got {status:?}"),
+        }
+    }
+
+    pub(super) fn inflate(
+        input: &[u8],
+        output: &mut [u8],
+        gzip: bool,
+    ) -> Result<usize, DecompressError> {
+        let mut stream = new_inflate(gzip);
+        match stream.decompress(input, output, FlushDecompress::Finish) {
+            Ok(Status::StreamEnd) => Ok(stream.total_out() as usize),
+            Ok(_) if stream.total_out() as usize == output.len() => {
+                Err(DecompressError::BufferTooSmall)
+            }
+            _ => Err(DecompressError::InvalidData),
+        }
+    }
+
+    pub(super) fn inflate_dynamic(
+        input: &[u8],
+        max_len: Option<usize>,
+        gzip: bool,
+    ) -> Result<PoolArray<u8>, DecompressError> {
+        let mut stream = new_inflate(gzip);
+        let mut output = PoolArray::new();
+        let mut capacity = 0;
+
+        loop {
+            let (in_pos, out_pos) = (stream.total_in() as usize, stream.total_out() as usize);
+            if out_pos == capacity {
+                if max_len == Some(capacity) {
+                    return Err(DecompressError::BufferTooSmall);
+                }
+                capacity = (capacity * 2).max(input.len()).max(1024);
+                if let Some(max_len) = max_len {
+                    capacity = capacity.min(max_len);
+                }
+                output.resize(to_len(capacity));
+            }
+
+            let status = stream.decompress(
+                &input[in_pos..],
+                &mut output.write()[out_pos..],
+                FlushDecompress::None,
+            );
+            let progress =
+                stream.total_in() as usize != in_pos || stream.total_out() as usize != out_pos;
+
+            match status {
+                Ok(Status::StreamEnd) => break,
+                Ok(_) if progress => {}
+                _ => return Err(DecompressError::InvalidData),
+            }
+        }
+
+        output.resize(to_len(stream.total_out() as usize));
+        Ok(output)
+    }
+
+    fn new_inflate(gzip: bool) -> Decompress {
+        if gzip {
+            Decompress::new_gzip(WINDOW_BITS)
+        } else {
+            Decompress::new(true)
+        }
+    }
+
+    pub(super) fn zstd_compress(input: &[u8], output: &mut [u8]) -> usize {
+        /// `compression/formats/zstd/compression_level`
+        const LEVEL: i32 = 3;
+
+        zstd_safe::compress(output, input, LEVEL)
+            .unwrap_or_else(|code| panic!("{}", zstd_safe::get_error_name(code)))
+    }
+
+    pub(super) fn zstd_decompress(
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<usize, DecompressError> {
+        /// `ZSTD_CONTENTSIZE_ERROR`, the largest value is `ZSTD_CONTENTSIZE_UNKNOWN`.
+        const CONTENT_SIZE_ERROR: u64 = u64::MAX - 1;
+
+        zstd_safe::decompress(output, input).map_err(|_| {
+            match zstd_safe::get_frame_content_size(input) {
+                size if size < CONTENT_SIZE_ERROR && size > output.len() as u64 => {
+                    DecompressError::BufferTooSmall
+                }
+                _ => DecompressError::InvalidData,
+            }
+        })
+    }
+}
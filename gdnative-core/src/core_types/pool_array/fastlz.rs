@@ -0,0 +1,327 @@
+//! Port of FastLZ 0.5.0, the version bundled with Godot 3.
+//!
+//! The compressor follows the original step by step, since `PoolByteArray.compress()` output
+//! depends on its exact choice of matches. The decompressor accepts any valid FastLZ stream of
+//! level 1 or 2, with bounds checks in place of the original's debug assertions.
+
+use super::byte_array::DecompressError;
+
+const MAX_COPY: usize = 32;
+const MAX_LEN: usize = 264;
+const MAX_L1_DISTANCE: usize = 8192;
+const MAX_L2_DISTANCE: usize = 8191;
+const MAX_FAR_DISTANCE: usize = 65535 + MAX_L2_DISTANCE - 1;
+
+const HASH_LOG: u32 = 13;
+const HASH_SIZE: usize = 1 << HASH_LOG;
+const HASH_MASK: u32 = HASH_SIZE as u32 - 1;
+
+/// Godot pads inputs shorter than this with zeros before compressing them.
+const MIN_BLOCK_LEN: usize = 16;
+
+/// Returns the output size Godot allocates for compressing `len` bytes.
+pub(super) fn max_compressed_len(len: usize) -> usize {
+    (len + len * 6 / 100).max(66)
+}
+
+/// Compresses `input` into `output` the way Godot's `Compression::compress` does, returning the
+/// compressed length. `output` must be at least `max_compressed_len(input.len())` long.
+pub(super) fn compress(input: &[u8], output: &mut [u8]) -> usize {
+    if input.len() < MIN_BLOCK_LEN {
+        let mut padded = [0; MIN_BLOCK_LEN];
+        padded[..input.len()].copy_from_slice(input);
+        compress_block(&padded, output)
+    } else {
+        compress_block(input, output)
+    }
+}
+
+/// Decompresses `input` into `output` the way Godot's `Compression::decompress` does, returning
+/// the decompressed length.
+pub(super) fn decompress(input: &[u8], output: &mut [u8]) -> Result<usize, DecompressError> {
+    if output.len() < MIN_BLOCK_LEN {
+        let mut padded = [0; MIN_BLOCK_LEN];
+        let len = decompress_block(input, &mut padded)?.min(output.len());
+        output[..len].copy_from_slice(&padded[..len]);
+        Ok(len)
+    } else {
+        decompress_block(input, output)
+    }
+}
+
+struct Output<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> Output<'a> {
+    fn push(&mut self, byte: u8) {
+        self.buf[self.pos] = byte;
+        self.pos += 1;
+    }
+
+    fn extend(&mut self, bytes: &[u8]) {
+        self.buf[self.pos..self.pos + bytes.len()].copy_from_slice(bytes);
+        self.pos += bytes.len();
+    }
+}
+
+fn read_u32(input: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([input[pos], input[pos + 1], input[pos + 2], input[pos + 3]])
+}
+
+fn hash(v: u32) -> usize {
+    let h = (u64::from(v) * 2_654_435_769) >> (32 - HASH_LOG);
+    (h as u32 & HASH_MASK) as usize
+}
+
+/// Returns the length of the match at `p` and `q`, biased the same way as the original: one
+/// more than the number of equal bytes, unless `q` reaches `bound` first.
+fn match_len(input: &[u8], mut p: usize, mut q: usize, bound: usize) -> usize {
+    let start = p;
+    if read_u32(input, p) == read_u32(input, q) {
+        p += 4;
+        q += 4;
+    }
+    while q < bound {
+        let equal = input[p] == input[q];
+        p += 1;
+        q += 1;
+        if !equal {
+            break;
+        }
+    }
+    p - start
+}
+
+fn literals(mut run: &[u8], out: &mut Output<'_>) {
+    while run.len() >= MAX_COPY {
+        out.push(MAX_COPY as u8 - 1);
+        out.extend(&run[..MAX_COPY]);
+        run = &run[MAX_COPY..];
+    }
+    if !run.is_empty() {
+        out.push(run.len() as u8 - 1);
+        out.extend(run);
+    }
+}
+
+fn match_level1(mut len: usize, distance: usize, out: &mut Output<'_>) {
+    let distance = distance - 1;
+    let (high, low) = ((distance >> 8) as u8, distance as u8);
+
+    while len > MAX_LEN - 2 {
+        out.push((7 << 5) + high);
+        out.push((MAX_LEN - 2 - 7 - 2) as u8);
+        out.push(low);
+        len -= MAX_LEN - 2;
+    }
+
+    if len < 7 {
+        out.push(((len as u8) << 5) + high);
+        out.push(low);
+    } else {
+        out.push((7 << 5) + high);
+        out.push((len - 7) as u8);
+        out.push(low);
+    }
+}
+
+fn match_level2(len: usize, distance: usize, out: &mut Output<'_>) {
+    let mut distance = distance - 1;
+    let far = distance >= MAX_L2_DISTANCE;
+    if far {
+        distance -= MAX_L2_DISTANCE;
+    }
+
+    let high = if far { 31 } else { (distance >> 8) as u8 };
+    if len < 7 {
+        out.push(((len as u8) << 5) + high);
+    } else {
+        out.push((7 << 5) + high);
+        let mut len = len - 7;
+        while len >= 255 {
+            out.push(255);
+            len -= 255;
+        }
+        out.push(len as u8);
+    }
+
+    if far {
+        out.push(255);
+        out.push((distance >> 8) as u8);
+    }
+    out.push(distance as u8);
+}
+
+/// `fastlz_compress`: level 1 for blocks shorter than 64 KiB, level 2 otherwise.
+fn compress_block(input: &[u8], output: &mut [u8]) -> usize {
+    let length = input.len();
+    let level2 = length >= 65536;
+    let max_distance = if level2 {
+        MAX_FAR_DISTANCE
+    } else {
+        MAX_L1_DISTANCE
+    };
+
+    // Positions `ip` with `ip + 13 >= length` are past `ip_limit` in the original.
+    let past_limit = |ip: usize| ip + 13 >= length;
+    let bound = length.saturating_sub(4);
+
+    let mut out = Output {
+        buf: output,
+        pos: 0,
+    };
+    let mut htab = [0u32; HASH_SIZE];
+
+    let mut anchor = 0;
+    let mut ip = 2;
+
+    while !past_limit(ip) {
+        let mut reference;
+        let mut distance;
+
+        // find potential match
+        loop {
+            let seq = read_u32(input, ip) & 0xff_ffff;
+            let h = hash(seq);
+            reference = htab[h] as usize;
+            htab[h] = ip as u32;
+            distance = ip - reference;
+            let cmp = if distance < max_distance {
+                read_u32(input, reference) & 0xff_ffff
+            } else {
+                0x100_0000
+            };
+            if past_limit(ip) {
+                break;
+            }
+            ip += 1;
+            if seq == cmp {
+                break;
+            }
+        }
+
+        if past_limit(ip) {
+            break;
+        }
+        ip -= 1;
+
+        // far, needs at least 5-byte match
+        if level2
+            && distance >= MAX_L2_DISTANCE
+            && (input[reference + 3] != input[ip + 3] || input[reference + 4] != input[ip + 4])
+        {
+            ip += 1;
+            continue;
+        }
+
+        if ip > anchor {
+            literals(&input[anchor..ip], &mut out);
+        }
+
+        let len = match_len(input, reference + 3, ip + 3, bound);
+        if level2 {
+            match_level2(len, distance, &mut out);
+        } else {
+            match_level1(len, distance, &mut out);
+        }
+
+        // update the hash at match boundary
+        ip += len;
+        let seq = read_u32(input, ip);
+        htab[hash(seq & 0xff_ffff)] = ip as u32;
+        ip += 1;
+        htab[hash(seq >> 8)] = ip as u32;
+        ip += 1;
+
+        anchor = ip;
+    }
+
+    literals(&input[anchor..], &mut out);
+
+    if level2 {
+        out.buf[0] |= 1 << 5;
+    }
+
+    out.pos
+}
+
+/// `fastlz_decompress` for both levels.
+fn decompress_block(input: &[u8], output: &mut [u8]) -> Result<usize, DecompressError> {
+    let first = *input.first().ok_or(DecompressError::InvalidData)?;
+    let level2 = match first >> 5 {
+        0 => false,
+        1 => true,
+        _ => return Err(DecompressError::InvalidData),
+    };
+
+    let byte_at = |ip: usize| input.get(ip).copied().ok_or(DecompressError::InvalidData);
+
+    let mut ip = 1;
+    let mut op = 0;
+    let mut ctrl = usize::from(first & 31);
+
+    loop {
+        if ctrl >= 32 {
+            let mut len = (ctrl >> 5) - 1;
+            let ofs = (ctrl & 31) << 8;
+
+            if len == 7 - 1 {
+                loop {
+                    let code = byte_at(ip)?;
+                    ip += 1;
+                    len += usize::from(code);
+                    if !level2 || code != 255 {
+                        break;
+                    }
+                }
+            }
+
+            let code = byte_at(ip)?;
+            ip += 1;
+            let mut distance = ofs + usize::from(code) + 1;
+
+            // match from 16-bit distance
+            if level2 && code == 255 && ofs == 31 << 8 {
+                let far = usize::from(byte_at(ip)?) << 8 | usize::from(byte_at(ip + 1)?);
+                ip += 2;
+                distance = far + MAX_L2_DISTANCE + 1;
+            }
+
+            len += 3;
+            if distance > op {
+                return Err(DecompressError::InvalidData);
+            }
+            if op + len > output.len() {
+                return Err(DecompressError::BufferTooSmall);
+            }
+
+            // Byte by byte, since the source may overlap the destination
+            for i in op..op + len {
+                output[i] = output[i - distance];
+            }
+            op += len;
+        } else {
+            let len = ctrl + 1;
+            let literal = input
+                .get(ip..ip + len)
+                .ok_or(DecompressError::InvalidData)?;
+            output
+                .get_mut(op..op + len)
+                .ok_or(DecompressError::BufferTooSmall)?
+                .copy_from_slice(literal);
+            ip += len;
+            op += len;
+        }
+
+        // The shortest instruction is two bytes long
+        if ip + 2 > input.len() {
+            break;
+        }
+        ctrl = usize::from(input[ip]);
+        ip += 1;
+    }
+
+    Ok(op)
+}
@@ -1,9 +1,9 @@
 use approx::relative_eq;
 
-use crate::core_types::{Color, GodotString, Vector2, Vector3};
+use crate::core_types::{real, Color, GodotString, ToVariant, Variant, Vector2, Vector3};
 use crate::object::NewRef as _;
 
-use super::{PoolArray, PoolElement};
+use super::{CompressionMode, DecompressError, PoolArray, PoolElement};
 
 fn test_array_access<T: PoolElement + Clone>(
     elements: impl IntoIterator<Item = T>,
@@ -58,7 +58,7 @@ godot_test!(
     }
 
     test_float32_array_access {
-        test_array_access((0..8).map(|i| i as f32), |i| *i *= 2.0, |a, b| relative_eq!(a, b));
+        test_array_access((0..8).map(|i| i as real), |i| *i *= 2.0, |a, b| relative_eq!(a, b));
     }
 
     test_int32_array_access {
@@ -103,4 +103,156 @@ godot_test!(
             |a, b| a == b,
         );
     }
+
+    test_byte_array_compression {
+        let mut modes = vec![CompressionMode::FastLz];
+        #[cfg(feature = "compression")]
+        modes.extend([CompressionMode::Deflate, CompressionMode::Zstd, CompressionMode::Gzip]);
+
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(100);
+        let arr = text.bytes().collect::<PoolArray<u8>>();
+
+        for mode in modes {
+            let compressed = arr.compress(mode);
+            assert!(compressed.len() < arr.len());
+
+            let decompressed = compressed.decompress(arr.len() as usize, mode).unwrap();
+            assert_eq!(arr, decompressed);
+
+            assert_eq!(
+                Err(DecompressError::BufferTooSmall),
+                compressed.decompress(100, mode)
+            );
+            assert!(PoolArray::<u8>::new().compress(mode).is_empty());
+        }
+
+        // Level 1 stream: a literal run of "ab", then a match of three bytes at distance 2
+        let fastlz = PoolArray::from_vec(vec![0x01, b'a', b'b', 0x20, 0x01]);
+        let decompressed = fastlz.decompress(16, CompressionMode::FastLz).unwrap();
+        assert_eq!(b"ababa", decompressed.read().as_slice());
+
+        #[cfg(feature = "compression")]
+        {
+            let compressed = arr.compress(CompressionMode::Gzip);
+            assert_eq!(&[0x1f, 0x8b], &compressed.read()[..2]);
+            assert_eq!(
+                Ok(arr.new_ref()),
+                compressed.decompress_dynamic(None, CompressionMode::Gzip)
+            );
+            assert_eq!(
+                Err(DecompressError::UnsupportedMode(CompressionMode::Zstd)),
+                compressed.decompress_dynamic(None, CompressionMode::Zstd)
+            );
+            assert_eq!(
+                Err(DecompressError::InvalidData),
+                compressed.decompress(arr.len() as usize, CompressionMode::Deflate)
+            );
+        }
+    }
+
+    test_byte_array_matches_engine {
+        /// Calls a `PoolByteArray` method of the engine.
+        fn call(arr: &PoolArray<u8>, method: &str, args: &[Variant]) -> Variant {
+            unsafe { arr.to_variant().call(method, args) }
+                .unwrap_or_else(|err| panic!("{method} should be callable: {err:?}"))
+        }
+
+        fn engine_bytes(arr: &PoolArray<u8>, method: &str, args: &[Variant]) -> PoolArray<u8> {
+            call(arr, method, args).to().expect("should return PoolByteArray")
+        }
+
+        // Values of the `File.COMPRESSION_*` constants
+        let mut modes = vec![(CompressionMode::FastLz, 0)];
+        #[cfg(feature = "compression")]
+        modes.extend([
+            (CompressionMode::Deflate, 1),
+            (CompressionMode::Zstd, 2),
+            (CompressionMode::Gzip, 3),
+        ]);
+
+        // Short inputs are padded by FastLZ, long and incompressible ones span several blocks
+        let mut state = 1_u32;
+        let noise = (0..5000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (state >> 16) as u8
+            })
+            .collect::<Vec<_>>();
+        let inputs = [
+            Vec::new(),
+            b"abc".to_vec(),
+            "The quick brown fox jumps over the lazy dog. ".repeat(100).into_bytes(),
+            noise,
+            vec![0; 70000],
+        ];
+
+        for input in inputs {
+            let arr = PoolArray::from_vec(input);
+            let len = arr.len().to_variant();
+
+            for &(mode, engine_mode) in &modes {
+                let engine_compressed = engine_bytes(&arr, "compress", &[engine_mode.to_variant()]);
+                let compressed = arr.compress(mode);
+                assert_eq!(engine_compressed, compressed, "{mode:?}, {} bytes", arr.len());
+
+                if arr.is_empty() {
+                    continue;
+                }
+
+                let decompressed = engine_compressed.decompress(arr.len() as usize, mode);
+                assert_eq!(Ok(arr.new_ref()), decompressed, "{mode:?}");
+                let engine_decompressed =
+                    engine_bytes(&compressed, "decompress", &[len.clone(), engine_mode.to_variant()]);
+                assert_eq!(arr, engine_decompressed, "{mode:?}");
+            }
+
+            #[cfg(feature = "compression")]
+            for (mode, engine_mode) in [(CompressionMode::Deflate, 1), (CompressionMode::Gzip, 3)] {
+                if arr.is_empty() {
+                    continue;
+                }
+                let engine_compressed = engine_bytes(&arr, "compress", &[engine_mode.to_variant()]);
+                let decompressed = engine_compressed.decompress_dynamic(None, mode);
+                assert_eq!(Ok(arr.new_ref()), decompressed, "{mode:?}");
+                let engine_decompressed = engine_bytes(
+                    &arr.compress(mode),
+                    "decompress_dynamic",
+                    &[len.clone(), engine_mode.to_variant()],
+                );
+                assert_eq!(arr, engine_decompressed, "{mode:?}");
+            }
+
+            assert_eq!(
+                call(&arr, "sha256_string", &[]).to::<GodotString>(),
+                Some(arr.sha256_string())
+            );
+            assert_eq!(
+                call(&arr, "hex_encode", &[]).to::<GodotString>(),
+                Some(arr.hex_encode())
+            );
+        }
+    }
+
+    test_byte_array_encoding {
+        let arr = b"abc".iter().copied().collect::<PoolArray<u8>>();
+        assert_eq!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            arr.sha256_string().to_string()
+        );
+        assert_eq!(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            PoolArray::<u8>::new().sha256_string().to_string()
+        );
+        assert_eq!("616263", arr.hex_encode().to_string());
+
+        for (raw, encoded) in [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foob", "Zm9vYg==")] {
+            let arr = raw.bytes().collect::<PoolArray<u8>>();
+            assert_eq!(encoded, arr.to_base64().to_string());
+            assert_eq!(Ok(arr), PoolArray::from_base64(encoded));
+        }
+
+        assert_eq!(b"foob", PoolArray::from_base64("Zm9v\nYg").unwrap().read().as_slice());
+        assert_eq!(3, PoolArray::from_base64("Zm9*").unwrap_err().position());
+        assert_eq!(5, PoolArray::from_base64("Zm9vY").unwrap_err().position());
+    }
 );
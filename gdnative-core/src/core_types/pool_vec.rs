@@ -0,0 +1,206 @@
+//! Conversions between `Vec`s of pool array elements and `PoolArray`s.
+//!
+//! `Vec<T>` and `&[T]` are converted to and from [`VariantArray`]s element by element, which
+//! allocates a `Variant` for every element. For the element types of [`PoolArray`] (`u8`,
+//! `i32`, [`real`], [`GodotString`], [`Vector2`], [`Vector3`] and [`Color`]), the functions in
+//! this module and the [`PoolVec`] wrapper convert to the corresponding `Pool*Array` instead,
+//! copying all elements at once through a single [`write()`][PoolArray::write] or
+//! [`read()`][PoolArray::read] guard.
+//!
+//! On the way in, both the matching `Pool*Array` type and generic `Array`s are accepted, so
+//! GDScript code can pass either.
+//!
+//! The functions can be used with the `with` attribute of the `ToVariant` and `FromVariant`
+//! derive macros:
+//!
+//! ```ignore
+//! #[derive(ToVariant, FromVariant)]
+//! struct MeshData {
+//!     #[variant(with = "gdnative::core_types::pool_vec")]
+//!     vertices: Vec<Vector3>,
+//!     #[variant(with = "gdnative::core_types::pool_vec")]
+//!     indices: Vec<i32>,
+//! }
+//! ```
+
+use std::ops::{Deref, DerefMut};
+
+use crate::core_types::*;
+use crate::export::{Export, ExportInfo};
+
+/// Converts `slice` to the `PoolArray` of its element type, wrapped in a `Variant`.
+///
+/// # Panics
+///
+/// If the length of `slice` does not fit in `i32`.
+#[inline]
+pub fn to_variant<T: PoolElement + Clone>(slice: &[T]) -> Variant {
+    to_pool_array(slice).to_variant()
+}
+
+/// Converts a `PoolArray` of type `T`, or a `VariantArray` of values convertible to `T`, into a
+/// `Vec<T>`.
+///
+/// # Errors
+///
+/// Returns `FromVariantError::InvalidVariantType` if the variant is neither, or
+/// `FromVariantError::InvalidItem` if an element of a `VariantArray` can't be converted.
+#[inline]
+pub fn from_variant<T: PoolElement + FromVariant + Clone>(
+    variant: &Variant,
+) -> Result<Vec<T>, FromVariantError> {
+    if let Ok(arr) = PoolArray::<T>::from_variant(variant) {
+        return Ok(arr.read().to_vec());
+    }
+
+    match VariantArray::from_variant(variant) {
+        Ok(arr) => arr
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                T::from_variant(&item).map_err(|e| FromVariantError::InvalidItem {
+                    index,
+                    error: Box::new(e),
+                })
+            })
+            .collect(),
+        Err(_) => Err(FromVariantError::InvalidVariantType {
+            variant_type: variant.get_type(),
+            expected: VariantType::from_sys(T::SYS_VARIANT_TYPE),
+        }),
+    }
+}
+
+/// Copies `slice` into a new `PoolArray` through a single write guard.
+fn to_pool_array<T: PoolElement + Clone>(slice: &[T]) -> PoolArray<T> {
+    let mut arr = PoolArray::<T>::new();
+    arr.resize(i32::try_from(slice.len()).expect("length should fit in i32"));
+
+    let mut write = arr.write();
+    for (dst, src) in write.iter_mut().zip(slice) {
+        dst.clone_from(src);
+    }
+    drop(write);
+
+    arr
+}
+
+/// A `Vec` that is converted to and from the `PoolArray` of its element type.
+///
+/// This is a thin wrapper that dereferences to `Vec<T>`, for use as method parameters, return
+/// values and exported properties. See the [module documentation](self) for details.
+///
+/// # Example
+///
+/// ```ignore
+/// #[method]
+/// fn vertices(&self) -> PoolVec<Vector3> {
+///     // Sent to GDScript as a PoolVector3Array
+///     PoolVec(self.vertices.clone())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PoolVec<T: PoolElement>(pub Vec<T>);
+
+impl<T: PoolElement> PoolVec<T> {
+    /// Returns the wrapped `Vec`.
+    #[inline]
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T: PoolElement> Deref for PoolVec<T> {
+    type Target = Vec<T>;
+
+    #[inline]
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T: PoolElement> DerefMut for PoolVec<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.0
+    }
+}
+
+impl<T: PoolElement> From<Vec<T>> for PoolVec<T> {
+    #[inline]
+    fn from(vec: Vec<T>) -> Self {
+        PoolVec(vec)
+    }
+}
+
+impl<T: PoolElement> From<PoolVec<T>> for Vec<T> {
+    #[inline]
+    fn from(vec: PoolVec<T>) -> Self {
+        vec.0
+    }
+}
+
+impl<T: PoolElement> FromIterator<T> for PoolVec<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        PoolVec(iter.into_iter().collect())
+    }
+}
+
+impl<T: PoolElement + Clone> ToVariant for PoolVec<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        to_variant(&self.0)
+    }
+}
+
+impl<T: PoolElement + FromVariant + Clone> FromVariant for PoolVec<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        from_variant(variant).map(PoolVec)
+    }
+}
+
+impl<T: PoolElement + Clone> Export for PoolVec<T>
+where
+    PoolArray<T>: Export,
+{
+    type Hint = <PoolArray<T> as Export>::Hint;
+
+    #[inline]
+    fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+        PoolArray::<T>::export_info(hint)
+    }
+}
+
+godot_test!(test_pool_vec {
+    let vertices = vec![Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0)];
+    let variant = PoolVec(vertices.clone()).to_variant();
+    assert_eq!(VariantType::Vector3Array, variant.get_type());
+    assert_eq!(Ok(PoolVec(vertices.clone())), PoolVec::from_variant(&variant));
+
+    let names = vec![GodotString::from("a"), GodotString::from("b")];
+    let variant = to_variant(&names);
+    assert_eq!(VariantType::StringArray, variant.get_type());
+    assert_eq!(Ok(names), from_variant::<GodotString>(&variant));
+
+    // Generic arrays are accepted too
+    let variant = vec![1, 2, 3].to_variant();
+    assert_eq!(VariantType::VariantArray, variant.get_type());
+    assert_eq!(Ok(vec![1, 2, 3]), from_variant::<i32>(&variant));
+
+    let variant = vec![1.to_variant(), "two".to_variant()].to_variant();
+    assert!(matches!(
+        from_variant::<i32>(&variant),
+        Err(FromVariantError::InvalidItem { index: 1, .. })
+    ));
+
+    assert_eq!(
+        Err(FromVariantError::InvalidVariantType {
+            variant_type: VariantType::I64,
+            expected: VariantType::ByteArray,
+        }),
+        from_variant::<u8>(&1.to_variant())
+    );
+    assert_eq!(Ok(Vec::<real>::new()), from_variant(&to_variant::<real>(&[])));
+});
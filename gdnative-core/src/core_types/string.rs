@@ -1,4 +1,5 @@
-use crate::core_types::Variant;
+use crate::core_types::{PoolArray, RealConv, Variant, VariantArray};
+use crate::object::ownership::Unique;
 use crate::object::NewRef;
 use crate::private::get_api;
 use crate::sys;
@@ -7,7 +8,7 @@ use std::cmp::Ordering;
 use std::ffi::CStr;
 use std::fmt;
 use std::mem::forget;
-use std::ops::{Add, AddAssign, Index, Range};
+use std::ops::{Add, AddAssign, Deref, Index, Range};
 use std::slice;
 use std::str;
 
@@ -30,9 +31,10 @@ use std::str;
 /// Godot through the `#[export]` macro, both types can be used as parameters and return types, and any
 /// conversions are done transparently.
 /// For auto-generated binding APIs in `gdnative::api`, return types are `GodotString`, but parameters
-/// are declared `impl Into<GodotString>`, allowing `String` or `&str` to be passed. In addition, the
-/// two types can always be explicitly converted using `GodotString::from_str()` and
-/// `GodotString::display/to_string()`.
+/// are declared `impl Into<GodotString>`, allowing `String` or `&str` to be passed. Method, signal
+/// and property names are declared [`impl NameArg`](crate::core_types::NameArg), which additionally
+/// accepts [`StringName`]. In addition, the two types can always be explicitly converted using
+/// `GodotString::from_str()` and `GodotString::display/to_string()`.
 ///
 /// As a general guideline, use `GodotString` if:
 /// * your strings are very large, so you can avoid copying them
@@ -113,7 +115,6 @@ impl GodotString {
         pub fn is_resource_file(&self) -> bool : godot_string_is_resource_file;
         pub fn is_absolute_path(&self) -> bool : godot_string_is_abs_path;
         pub fn is_relative_path(&self) -> bool : godot_string_is_rel_path;
-        pub fn to_f32(&self) -> f32 : godot_string_to_float;
         pub fn to_f64(&self) -> f64 : godot_string_to_double;
         pub fn to_i32(&self) -> i32 : godot_string_to_int;
         pub fn u32_hash(&self) -> u32 : godot_string_hash;
@@ -146,8 +147,14 @@ impl GodotString {
         pub fn xml_unescape(&self) -> Self: godot_string_xml_unescape;
         pub fn percent_decode(&self) -> Self : godot_string_percent_decode;
         pub fn percent_encode(&self) -> Self : godot_string_percent_encode;
+        pub fn strip_escapes(&self) -> Self : godot_string_strip_escapes;
     );
 
+    #[inline]
+    pub fn to_f32(&self) -> f32 {
+        unsafe { (get_api().godot_string_to_float)(&self.0) }.as_f32()
+    }
+
     #[inline]
     pub fn is_valid_hex_number(&self, with_prefix: bool) -> bool {
         unsafe { (get_api().godot_string_is_valid_hex_number)(&self.0, with_prefix) }
@@ -180,12 +187,45 @@ impl GodotString {
         }
     }
 
-    #[doc(hidden)]
+    /// Encodes the string as UTF-8.
+    ///
+    /// The result can be borrowed as `&str` without another copy, unlike `to_string()`, which
+    /// copies it into a Rust `String`.
     #[inline]
     pub fn to_utf8(&self) -> Utf8String {
         unsafe { Utf8String((get_api().godot_string_utf8)(&self.0)) }
     }
 
+    /// Returns the characters of the string in Godot's native encoding, without copying them.
+    ///
+    /// This is the engine's `wchar_t` buffer, which holds UTF-32 code points on most platforms,
+    /// and UTF-16 code units on Windows. Unlike indexing, which goes through FFI for every
+    /// character, the whole buffer is fetched with a single call, so this is the cheapest way to
+    /// inspect a string in hot paths.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use gdnative::prelude::*;
+    /// let path = GodotString::from("res://icon.png");
+    /// let slashes = path.as_chars().iter().filter(|&&c| c == '/').count();
+    /// assert_eq!(slashes, 2);
+    /// ```
+    #[inline]
+    pub fn as_chars(&self) -> &[GodotChar] {
+        let len = self.len();
+        if len == 0 {
+            return &[];
+        }
+
+        // SAFETY: `GodotString` is immutable, so the buffer lives as long as `self` and holds
+        // `len` characters before the nul terminator. `GodotChar` is a transparent `wchar_t`.
+        unsafe {
+            let data = (get_api().godot_string_wide_str)(&self.0);
+            slice::from_raw_parts(data as *const GodotChar, len)
+        }
+    }
+
     #[inline]
     pub fn find(&self, what: &GodotString) -> i32 {
         unsafe { (get_api().godot_string_find)(&self.0, what.0) }
@@ -201,6 +241,225 @@ impl GodotString {
         unsafe { (get_api().godot_string_find_last)(&self.0, what.0) }
     }
 
+    /// Returns the number of non-overlapping occurrences of `what` in the string.
+    #[inline]
+    pub fn count(&self, what: &GodotString) -> usize {
+        unsafe { (get_api().godot_string_count)(&self.0, what.0, 0, 0) as usize }
+    }
+
+    /// Returns the number of non-overlapping occurrences of `what` in the string, ignoring case.
+    #[inline]
+    pub fn count_ignore_case(&self, what: &GodotString) -> usize {
+        unsafe { (get_api().godot_string_countn)(&self.0, what.0, 0, 0) as usize }
+    }
+
+    /// Returns `true` if the whole string matches `pattern`, where `*` matches any sequence of
+    /// characters, including an empty one, and `?` matches any single character except `.`.
+    ///
+    /// An empty string or pattern never matches.
+    #[inline]
+    pub fn matches_glob(&self, pattern: &GodotString) -> bool {
+        unsafe { (get_api().godot_string_match)(&self.0, &pattern.0) }
+    }
+
+    /// Like [`Self::matches_glob()`], but ignoring case.
+    #[inline]
+    pub fn matches_glob_ignore_case(&self, pattern: &GodotString) -> bool {
+        unsafe { (get_api().godot_string_matchn)(&self.0, &pattern.0) }
+    }
+
+    /// Returns the similarity of the two strings as a value from `0.0` to `1.0`, using the
+    /// Sørensen–Dice coefficient of their character pairs.
+    ///
+    /// Equal strings have a similarity of `1.0`, and strings shorter than two characters have a
+    /// similarity of `0.0` to any other string.
+    #[inline]
+    pub fn similarity(&self, other: &GodotString) -> f32 {
+        unsafe { (get_api().godot_string_similarity)(&self.0, &other.0) }.as_f32()
+    }
+
+    /// Splits the string at each occurrence of `delimiter`.
+    ///
+    /// If `allow_empty` is `false`, empty parts (between adjacent delimiters, or at the start or
+    /// end of the string) are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use gdnative::prelude::*;
+    /// let csv = GodotString::from("a,b,,c");
+    /// let delimiter = GodotString::from(",");
+    /// assert_eq!(csv.split(&delimiter, false), vec!["a".into(), "b".into(), "c".into()]);
+    /// assert_eq!(csv.split(&delimiter, true).len(), 4);
+    /// ```
+    #[inline]
+    pub fn split(&self, delimiter: &GodotString, allow_empty: bool) -> Vec<GodotString> {
+        let array = unsafe {
+            let api = get_api();
+            let split = if allow_empty {
+                api.godot_string_split_allow_empty
+            } else {
+                api.godot_string_split
+            };
+            VariantArray::<Unique>::from_sys(split(&self.0, &delimiter.0))
+        };
+
+        array
+            .iter()
+            .map(|part| part.to().expect("split should return strings"))
+            .collect()
+    }
+
+    /// Splits the string at each occurrence of `delimiter`, starting from the end.
+    ///
+    /// At most `max_split` splits are made, so the first part contains the rest of the string.
+    /// A `max_split` of 0 means no limit, in which case the result is the same as
+    /// [`Self::split()`].
+    #[inline]
+    pub fn rsplit(
+        &self,
+        delimiter: &GodotString,
+        allow_empty: bool,
+        max_split: usize,
+    ) -> Vec<GodotString> {
+        let parts = unsafe {
+            PoolArray::<GodotString>::from_sys((get_api().godot_string_rsplit)(
+                &self.0,
+                &delimiter.0,
+                allow_empty,
+                max_split as i32,
+            ))
+        };
+        let read = parts.read();
+        read.to_vec()
+    }
+
+    /// Returns a copy of the string with all occurrences of `what` replaced by `with`.
+    #[inline]
+    pub fn replace(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_replace)(&self.0, what.0, with.0)) }
+    }
+
+    /// Returns a copy of the string with the first occurrence of `what` replaced by `with`.
+    #[inline]
+    pub fn replace_first(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_replace_first)(
+                &self.0, what.0, with.0,
+            ))
+        }
+    }
+
+    /// Returns a copy of the string with all occurrences of `what` replaced by `with`, ignoring
+    /// case when searching.
+    #[inline]
+    pub fn replace_ignore_case(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_replacen)(&self.0, what.0, with.0)) }
+    }
+
+    /// Returns a copy of the string with `what` inserted before the character at `position`,
+    /// or at the end if `position` is past it.
+    #[inline]
+    pub fn insert(&self, position: usize, what: &GodotString) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_insert)(
+                &self.0,
+                position as i32,
+                what.0,
+            ))
+        }
+    }
+
+    /// Returns a copy of the string with whitespace and control characters removed from the
+    /// start if `left` is `true`, and from the end if `right` is `true`.
+    #[inline]
+    pub fn strip_edges(&self, left: bool, right: bool) -> Self {
+        unsafe { GodotString((get_api().godot_string_strip_edges)(&self.0, left, right)) }
+    }
+
+    /// Returns a copy of the string with all characters contained in `chars` removed from the
+    /// start.
+    #[inline]
+    pub fn lstrip(&self, chars: &GodotString) -> Self {
+        let chars = chars.as_chars();
+        let start = self
+            .as_chars()
+            .iter()
+            .take_while(|c| chars.contains(c))
+            .count();
+
+        if start == 0 {
+            self.clone()
+        } else {
+            self.sub_string(start..self.len())
+        }
+    }
+
+    /// Returns a copy of the string with all characters contained in `chars` removed from the
+    /// end.
+    #[inline]
+    pub fn rstrip(&self, chars: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_rstrip)(&self.0, &chars.0)) }
+    }
+
+    /// Returns a copy of the string with `prefix` removed from the start, if present.
+    #[inline]
+    pub fn trim_prefix(&self, prefix: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_trim_prefix)(&self.0, &prefix.0)) }
+    }
+
+    /// Returns a copy of the string with `suffix` removed from the end, if present.
+    #[inline]
+    pub fn trim_suffix(&self, suffix: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_trim_suffix)(&self.0, &suffix.0)) }
+    }
+
+    /// Formats a number by prepending zeros to its integer part until it has at least `digits`
+    /// digits.
+    ///
+    /// For example, `"3.14"` padded to 3 digits is `"003.14"`.
+    #[inline]
+    pub fn pad_zeros(&self, digits: usize) -> Self {
+        unsafe { GodotString((get_api().godot_string_pad_zeros)(&self.0, digits as i32)) }
+    }
+
+    /// Formats a number to exactly `digits` decimals, truncating extra ones or appending zeros.
+    ///
+    /// For example, `"3.14159"` padded to 2 decimals is `"3.14"`, and `"3"` is `"3.00"`.
+    #[inline]
+    pub fn pad_decimals(&self, digits: usize) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_pad_decimals)(
+                &self.0,
+                digits as i32,
+            ))
+        }
+    }
+
+    /// Prepends `padding` to the string until it is at least `min_length` characters long.
+    #[inline]
+    pub fn lpad(&self, min_length: usize, padding: &GodotString) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_lpad_with_custom_character)(
+                &self.0,
+                min_length as i32,
+                &padding.0,
+            ))
+        }
+    }
+
+    /// Appends `padding` to the string until it is at least `min_length` characters long.
+    #[inline]
+    pub fn rpad(&self, min_length: usize, padding: &GodotString) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_rpad_with_custom_character)(
+                &self.0,
+                min_length as i32,
+                &padding.0,
+            ))
+        }
+    }
+
     /// Formats the string by replacing all occurrences of a key in the string with the
     /// corresponding value. The method can handle arrays or dictionaries for the key/value pairs.
     ///
@@ -453,9 +712,10 @@ impl Index<usize> for GodotString {
     }
 }
 
-// TODO(#993): Is it useful to expose this type?
-// Could just make it an internal detail of how to convert to a rust string.
-#[doc(hidden)]
+/// A UTF-8 encoded copy of a [`GodotString`], as returned by [`GodotString::to_utf8()`].
+///
+/// The bytes are owned by the engine. `Utf8String` dereferences to `str`, so it can be used as a
+/// string slice directly, without copying it into a `String` first.
 pub struct Utf8String(pub(crate) sys::godot_char_string);
 
 impl Utf8String {
@@ -502,10 +762,26 @@ impl Utf8String {
     }
 }
 
-impl ToString for Utf8String {
+impl Deref for Utf8String {
+    type Target = str;
+
     #[inline]
-    fn to_string(&self) -> String {
-        String::from(self.as_str())
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Utf8String {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Utf8String {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
     }
 }
 
@@ -624,6 +900,13 @@ impl Ord for StringName {
     }
 }
 
+impl std::hash::Hash for StringName {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u32(self.get_hash());
+    }
+}
+
 impl fmt::Debug for StringName {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
@@ -757,6 +1040,74 @@ godot_test!(test_string {
     assert_eq!(fmt_string2, GodotString::from("{0} {1}"));
 });
 
+godot_test!(test_string_ops {
+    use crate::core_types::{GodotChar, GodotString};
+
+    let s = |text: &str| GodotString::from(text);
+
+    let csv = s("a,b,,c,");
+    assert_eq!(csv.split(&s(","), false), vec![s("a"), s("b"), s("c")]);
+    assert_eq!(csv.split(&s(","), true), vec![s("a"), s("b"), s(""), s("c"), s("")]);
+    assert_eq!(s("a.b.c").rsplit(&s("."), false, 1), vec![s("a.b"), s("c")]);
+    assert_eq!(s("a.b.c").rsplit(&s("."), false, 0), vec![s("a"), s("b"), s("c")]);
+
+    let text = s("One fish, two Fish");
+    assert_eq!(text.replace(&s("fish"), &s("cat")), s("One cat, two Fish"));
+    assert_eq!(text.replace_first(&s("o"), &s("0")), s("One fish, tw0 Fish"));
+    assert_eq!(text.replace_ignore_case(&s("FISH"), &s("cat")), s("One cat, two cat"));
+    assert_eq!(text.count(&s("fish")), 1);
+    assert_eq!(text.count_ignore_case(&s("fish")), 2);
+    assert_eq!(s("aaaa").count(&s("aa")), 2);
+    assert_eq!(s("ac").insert(1, &s("b")), s("abc"));
+    assert_eq!(s("ab").insert(10, &s("c")), s("abc"));
+
+    assert_eq!(s(" \t x \n").strip_edges(true, true), s("x"));
+    assert_eq!(s(" \t x \n").strip_edges(true, false), s("x \n"));
+    assert_eq!(s("a\tb\nc").strip_escapes(), s("abc"));
+    assert_eq!(s("xxyhixy").lstrip(&s("xy")), s("hixy"));
+    assert_eq!(s("xxyhixy").rstrip(&s("xy")), s("xxyhi"));
+    assert_eq!(s("hi").lstrip(&s("")), s("hi"));
+    assert_eq!(s("xy").lstrip(&s("xy")), s(""));
+    assert_eq!(s("res://icon.png").trim_prefix(&s("res://")), s("icon.png"));
+    assert_eq!(s("icon.png").trim_suffix(&s(".svg")), s("icon.png"));
+
+    assert_eq!(s("3.14").pad_zeros(3), s("003.14"));
+    assert_eq!(s("-7").pad_zeros(2), s("-07"));
+    assert_eq!(s("3.14159").pad_decimals(2), s("3.14"));
+    assert_eq!(s("3").pad_decimals(2), s("3.00"));
+    assert_eq!(s("7").lpad(3, &s("0")), s("007"));
+    assert_eq!(s("ab").rpad(4, &s(".")), s("ab.."));
+    assert_eq!(s("abcd").rpad(2, &s(".")), s("abcd"));
+
+    assert!(s("icon.png").matches_glob(&s("*.png")));
+    assert!(s("icon.png").matches_glob(&s("ic?n*")));
+    assert!(!s("icon.png").matches_glob(&s("icon?png")));
+    assert!(!s("Icon.PNG").matches_glob(&s("*.png")));
+    assert!(s("Icon.PNG").matches_glob_ignore_case(&s("*.png")));
+    assert!(!s("").matches_glob(&s("*")));
+
+    assert_eq!(s("godot").similarity(&s("godot")), 1.0);
+    assert_eq!(s("a").similarity(&s("b")), 0.0);
+    assert!((s("night").similarity(&s("nacht")) - 0.25).abs() < 1e-6);
+
+    let chars = s("héllo");
+    assert_eq!(chars.as_chars().len(), 5);
+    assert_eq!(chars.as_chars()[1], 'é');
+    assert!(chars.as_chars().iter().zip("héllo".chars()).all(|(c, expected)| *c == expected));
+    assert_eq!(GodotString::new().as_chars(), &[] as &[GodotChar]);
+
+    let utf8 = chars.to_utf8();
+    assert_eq!(&*utf8, "héllo");
+    assert!(utf8.starts_with("hé"));
+    assert_eq!(utf8.to_string(), String::from("héllo"));
+
+    let name = "Ferris";
+    let formatted = crate::godot_format!("{name} has {count} {things}", name, count = 3, things = s("claws"));
+    assert_eq!(formatted, s("Ferris has 3 claws"));
+    assert_eq!(crate::godot_format!("{missing}"), s("{missing}"));
+    assert_eq!(crate::godot_format!(s("{0}"), x = 1,), s("{0}"));
+});
+
 godot_test!(test_string_name_eq {
     use crate::core_types::{GodotString, StringName};
 
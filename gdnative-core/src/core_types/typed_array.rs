@@ -0,0 +1,522 @@
+use std::fmt;
+use std::iter::{Extend, FromIterator};
+use std::marker::PhantomData;
+
+use crate::core_types::{
+    FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant, VariantArray,
+};
+use crate::object::NewRef;
+
+use crate::object::ownership::*;
+
+/// A statically typed view of a [`VariantArray`] whose elements are all convertible to `T`.
+///
+/// `Array` shares its storage with the underlying `VariantArray`, and follows the same
+/// ownership model: only `Unique` and `ThreadLocal` arrays can change their size. Elements are
+/// validated when an `Array` is constructed from an untyped container, either explicitly with
+/// [`Array::from_variant_array`], or implicitly through `FromVariant` when it is used as a method
+/// argument or property.
+///
+/// Since the storage is shared, code outside Rust (or other references to the same
+/// `VariantArray`) can still insert elements of the wrong type after validation. Typed getters
+/// panic in that case, while [`Array::try_get`] reports the error instead.
+///
+/// # Example
+///
+/// ```ignore
+/// let scores = Array::<i64, Unique>::new();
+/// scores.push(10);
+/// scores.push(20);
+/// assert_eq!(30, scores.iter().sum::<i64>());
+/// ```
+pub struct Array<T: ToVariant + FromVariant, Own: Ownership = Shared> {
+    inner: VariantArray<Own>,
+
+    /// Marker for the element type. `fn() -> T` keeps `Send` and `Sync` dependent on `Own` only.
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Operations allowed on all typed arrays at any point in time.
+impl<T: ToVariant + FromVariant, Own: Ownership> Array<T, Own> {
+    /// Creates a typed view of `array`, checking that all of its elements can be converted
+    /// to `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FromVariantError::InvalidItem` with the index of the first element that could
+    /// not be converted.
+    #[inline]
+    pub fn from_variant_array(array: VariantArray<Own>) -> Result<Self, FromVariantError> {
+        for (index, item) in array.iter().enumerate() {
+            T::from_variant(&item).map_err(|error| FromVariantError::InvalidItem {
+                index,
+                error: Box::new(error),
+            })?;
+        }
+
+        // All elements have been validated above.
+        Ok(Self::assume_typed(array))
+    }
+
+    /// Creates a typed view of `array` without checking its elements.
+    ///
+    /// This is useful when the elements are known to be valid, e.g. when they were inserted by
+    /// the caller. If an element cannot be converted to `T`, typed getters like [`Array::get`]
+    /// panic when they encounter it, while [`Array::try_get`] reports the error instead.
+    #[inline]
+    pub fn assume_typed(array: VariantArray<Own>) -> Self {
+        Array {
+            inner: array,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying untyped array.
+    #[inline]
+    pub fn as_variant_array(&self) -> &VariantArray<Own> {
+        &self.inner
+    }
+
+    /// Returns the underlying untyped array, consuming the typed view.
+    #[inline]
+    pub fn into_variant_array(self) -> VariantArray<Own> {
+        self.inner
+    }
+
+    /// Sets the value of the element at the given offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    #[inline]
+    pub fn set(&self, idx: i32, val: T) {
+        self.inner.set(idx, val.to_variant())
+    }
+
+    /// Returns a copy of the element at the given offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds, or if the element cannot be converted to `T`.
+    #[inline]
+    pub fn get(&self, idx: i32) -> T {
+        expect_element(idx, self.try_get(idx))
+    }
+
+    /// Returns a copy of the element at the given offset, or an error if it cannot be
+    /// converted to `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    #[inline]
+    pub fn try_get(&self, idx: i32) -> Result<T, FromVariantError> {
+        T::from_variant(&self.inner.get(idx))
+    }
+
+    /// Returns `true` if the array contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the number of elements in the array.
+    #[inline]
+    pub fn len(&self) -> i32 {
+        self.inner.len()
+    }
+
+    /// Returns the number of times `val` appears in the array.
+    #[inline]
+    pub fn count(&self, val: &T) -> i32 {
+        self.inner.count(val)
+    }
+
+    /// Returns true if the array contains the specified value.
+    #[inline]
+    pub fn contains(&self, what: &T) -> bool {
+        self.inner.contains(what)
+    }
+
+    /// Searches the array for a value and returns its index.
+    /// Pass an initial search index as the second argument.
+    /// Returns `-1` if value is not found.
+    #[inline]
+    pub fn find(&self, what: &T, from: i32) -> i32 {
+        self.inner.find(what, from)
+    }
+
+    /// Searches the array in reverse order for a value.
+    /// Returns its index or `-1` if not found.
+    #[inline]
+    pub fn find_last(&self, what: &T) -> i32 {
+        self.inner.find_last(what)
+    }
+
+    /// Inverts the order of the elements in the array.
+    #[inline]
+    pub fn invert(&self) {
+        self.inner.invert()
+    }
+
+    /// Sorts the array using Godot's `Variant` ordering.
+    #[inline]
+    pub fn sort(&self) {
+        self.inner.sort()
+    }
+
+    /// Create a copy of the array.
+    ///
+    /// This creates a new array and is **not** a cheap reference count
+    /// increment.
+    #[inline]
+    pub fn duplicate(&self) -> Array<T, Unique> {
+        // The copy contains the same elements as `self`.
+        Array::assume_typed(self.inner.duplicate())
+    }
+
+    /// Returns an iterator through all values in the array, converted to `T`.
+    ///
+    /// See [`VariantArray::iter`] for notes on modifying the array during iteration.
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics if an element cannot be converted to `T`.
+    #[inline]
+    pub fn iter(&self) -> ArrayIter<'_, T, Own> {
+        self.into_iter()
+    }
+
+    /// Copies all elements into a `Vec`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an element cannot be converted to `T`.
+    #[inline]
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+}
+
+/// Operations allowed on typed arrays that can only be referenced to from the current thread.
+impl<T: ToVariant + FromVariant, Own: LocalThreadOwnership> Array<T, Own> {
+    /// Clears the array, resizing to 0.
+    #[inline]
+    pub fn clear(&self) {
+        self.inner.clear()
+    }
+
+    /// Removes the element at `idx`.
+    #[inline]
+    pub fn remove(&self, idx: i32) {
+        self.inner.remove(idx)
+    }
+
+    /// Appends an element at the end of the array.
+    #[inline]
+    pub fn push(&self, val: T) {
+        self.inner.push(val.to_variant())
+    }
+
+    /// Removes and returns the element at the end of the array, or `None` if it is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element cannot be converted to `T`.
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let idx = self.len() - 1;
+        Some(expect_element(idx, T::from_variant(&self.inner.pop())))
+    }
+
+    /// Appends an element to the front of the array.
+    #[inline]
+    pub fn push_front(&self, val: T) {
+        self.inner.push_front(val.to_variant())
+    }
+
+    /// Removes and returns the element at the front of the array, or `None` if it is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element cannot be converted to `T`.
+    #[inline]
+    pub fn pop_front(&self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(expect_element(0, T::from_variant(&self.inner.pop_front())))
+    }
+
+    /// Inserts a new element at a given position in the array.
+    #[inline]
+    pub fn insert(&self, at: i32, val: T) {
+        self.inner.insert(at, val.to_variant())
+    }
+}
+
+/// Operations allowed on non-unique typed arrays.
+impl<T: ToVariant + FromVariant, Own: NonUniqueOwnership> Array<T, Own> {
+    /// Assume that this is the only reference to this array, on which
+    /// operations that change the container size can be safely performed.
+    ///
+    /// # Safety
+    ///
+    /// See [`VariantArray::assume_unique`].
+    #[inline]
+    pub unsafe fn assume_unique(self) -> Array<T, Unique> {
+        Array::assume_typed(self.inner.assume_unique())
+    }
+}
+
+/// Operations allowed on unique typed arrays.
+impl<T: ToVariant + FromVariant> Array<T, Unique> {
+    /// Creates an empty typed array.
+    #[inline]
+    pub fn new() -> Self {
+        // An empty array has no elements to validate.
+        Self::assume_typed(VariantArray::new())
+    }
+
+    /// Put this array under the "shared" access type.
+    #[inline]
+    pub fn into_shared(self) -> Array<T, Shared> {
+        Array::assume_typed(self.inner.into_shared())
+    }
+
+    /// Put this array under the "thread-local" access type.
+    #[inline]
+    pub fn into_thread_local(self) -> Array<T, ThreadLocal> {
+        Array::assume_typed(self.inner.into_thread_local())
+    }
+}
+
+/// Operations allowed on typed arrays that might be shared between different threads.
+impl<T: ToVariant + FromVariant> Array<T, Shared> {
+    /// Create a new shared typed array.
+    #[inline]
+    pub fn new_shared() -> Self {
+        Array::<T, Unique>::new().into_shared()
+    }
+}
+
+/// Operations allowed on typed arrays that may only be shared on the current thread.
+impl<T: ToVariant + FromVariant> Array<T, ThreadLocal> {
+    /// Create a new thread-local typed array.
+    #[inline]
+    pub fn new_thread_local() -> Self {
+        Array::<T, Unique>::new().into_thread_local()
+    }
+}
+
+#[track_caller]
+fn expect_element<T>(idx: i32, result: Result<T, FromVariantError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => panic!("Invalid element at index {}: {}", idx, err),
+    }
+}
+
+impl<T: ToVariant + FromVariant> Default for Array<T, Unique> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ToVariant + FromVariant> Default for Array<T, Shared> {
+    #[inline]
+    fn default() -> Self {
+        Self::new_shared()
+    }
+}
+
+impl<T: ToVariant + FromVariant> Default for Array<T, ThreadLocal> {
+    #[inline]
+    fn default() -> Self {
+        Self::new_thread_local()
+    }
+}
+
+impl<T: ToVariant + FromVariant, Own: NonUniqueOwnership> NewRef for Array<T, Own> {
+    #[inline]
+    fn new_ref(&self) -> Self {
+        Self::assume_typed(self.inner.new_ref())
+    }
+}
+
+impl<T: ToVariant + FromVariant> From<Array<T, Unique>> for Array<T, Shared> {
+    #[inline]
+    fn from(array: Array<T, Unique>) -> Self {
+        array.into_shared()
+    }
+}
+
+impl<T: ToVariant + FromVariant> From<Array<T, Unique>> for Array<T, ThreadLocal> {
+    #[inline]
+    fn from(array: Array<T, Unique>) -> Self {
+        array.into_thread_local()
+    }
+}
+
+impl<T: ToVariant + FromVariant, Own: Ownership> From<Array<T, Own>> for VariantArray<Own> {
+    #[inline]
+    fn from(array: Array<T, Own>) -> Self {
+        array.into_variant_array()
+    }
+}
+
+impl<T: ToVariant + FromVariant, Own: Ownership> TryFrom<VariantArray<Own>> for Array<T, Own> {
+    type Error = FromVariantError;
+
+    #[inline]
+    fn try_from(array: VariantArray<Own>) -> Result<Self, Self::Error> {
+        Self::from_variant_array(array)
+    }
+}
+
+impl<T: ToVariant + FromVariant> ToVariant for Array<T, Shared> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.inner.to_variant()
+    }
+}
+
+impl<T: ToVariant + FromVariant> OwnedToVariant for Array<T, Unique> {
+    #[inline]
+    fn owned_to_variant(self) -> Variant {
+        self.inner.owned_to_variant()
+    }
+}
+
+impl<T: ToVariant + FromVariant> FromVariant for Array<T, Shared> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Self::from_variant_array(VariantArray::from_variant(variant)?)
+    }
+}
+
+impl<T: ToVariant + FromVariant, Own: Ownership> fmt::Debug for Array<T, Own> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+/// Iterator through all values in a typed [`Array`], converted to `T`.
+pub struct ArrayIter<'a, T: ToVariant + FromVariant, Own: Ownership> {
+    arr: &'a Array<T, Own>,
+    range: std::ops::Range<i32>,
+}
+
+impl<'a, T: ToVariant + FromVariant, Own: Ownership> Iterator for ArrayIter<'a, T, Own> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|idx| self.arr.get(idx))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, T: ToVariant + FromVariant, Own: Ownership> IntoIterator for &'a Array<T, Own> {
+    type Item = T;
+    type IntoIter = ArrayIter<'a, T, Own>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayIter {
+            range: 0..self.len(),
+            arr: self,
+        }
+    }
+}
+
+impl<T: ToVariant + FromVariant> FromIterator<T> for Array<T, Unique> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut arr = Self::new();
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<T: ToVariant + FromVariant, Own: LocalThreadOwnership> Extend<T> for Array<T, Own> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+godot_test!(test_typed_array {
+    let array = Array::<i64, Unique>::new();
+    assert!(array.is_empty());
+
+    array.push(2);
+    array.push(3);
+    array.push_front(1);
+    assert_eq!(3, array.len());
+    assert_eq!(2, array.get(1));
+    assert!(array.contains(&3));
+    assert_eq!(2, array.find(&3, 0));
+    assert_eq!(vec![1, 2, 3], array.to_vec());
+    assert_eq!(6, array.iter().sum::<i64>());
+
+    array.set(0, 4);
+    array.sort();
+    assert_eq!(vec![2, 3, 4], array.to_vec());
+
+    assert_eq!(Some(4), array.pop());
+    assert_eq!(Some(2), array.pop_front());
+    assert_eq!(vec![3], array.to_vec());
+
+    let copy = array.duplicate();
+    copy.push(5);
+    assert_eq!(1, array.len());
+    assert_eq!(2, copy.len());
+
+    array.clear();
+    assert_eq!(None, array.pop());
+
+    let shared: Array<String> = ["a", "b"].iter().map(|s| s.to_string()).collect::<Array<String, Unique>>().into_shared();
+    let variant = shared.to_variant();
+    let back = Array::<String>::from_variant(&variant).unwrap();
+    assert_eq!(vec!["a".to_string(), "b".to_string()], back.to_vec());
+    assert_eq!(2, shared.new_ref().len());
+});
+
+godot_test!(test_typed_array_validation {
+    let untyped = VariantArray::new();
+    untyped.push(1);
+    untyped.push("two");
+
+    let err = Array::<i64, Unique>::from_variant_array(untyped).unwrap_err();
+    assert!(matches!(err, FromVariantError::InvalidItem { index: 1, .. }));
+
+    let untyped = VariantArray::new();
+    untyped.push(1);
+    untyped.push(2);
+    let typed = Array::<i64, Unique>::try_from(untyped).unwrap();
+    assert_eq!(vec![1, 2], typed.to_vec());
+
+    let untyped = typed.into_variant_array();
+    untyped.push("three");
+    let typed = Array::<i64, Unique>::assume_typed(untyped);
+    assert!(typed.try_get(2).is_err());
+    assert_eq!(Ok(2), typed.try_get(1));
+
+    let variant = typed.into_variant_array().owned_to_variant();
+    assert!(Array::<i64>::from_variant(&variant).is_err());
+    assert!(Array::<Variant>::from_variant(&variant).is_ok());
+});
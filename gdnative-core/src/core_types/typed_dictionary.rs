@@ -0,0 +1,526 @@
+use std::fmt;
+use std::iter::{Extend, FromIterator};
+use std::marker::PhantomData;
+
+use crate::core_types::dictionary;
+use crate::core_types::variant::describe_key;
+use crate::core_types::{
+    Array, Dictionary, FromVariant, FromVariantError, OwnedToVariant, ToVariant, ToVariantEq,
+    Variant,
+};
+use crate::object::NewRef;
+
+use crate::object::ownership::*;
+
+/// A statically typed view of a [`Dictionary`] whose keys are all convertible to `K`, and whose
+/// values are all convertible to `V`.
+///
+/// `TypedDictionary` shares its storage with the underlying `Dictionary`, and follows the same
+/// ownership model. Entries are validated when a `TypedDictionary` is constructed from an
+/// untyped container, either explicitly with [`TypedDictionary::from_dictionary`], or implicitly
+/// through `FromVariant` when it is used as a method argument or property.
+///
+/// As with [`Array`], entries of the wrong type can still be inserted through other references
+/// to the same storage after validation. Typed getters panic in that case, while
+/// [`TypedDictionary::try_get`] reports the error instead.
+pub struct TypedDictionary<K, V, Own: Ownership = Shared>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    inner: Dictionary<Own>,
+
+    /// Marker for the entry types. `fn() -> (K, V)` keeps `Send` and `Sync` dependent on `Own` only.
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+/// Operations allowed on all typed dictionaries at any point in time.
+impl<K, V, Own: Ownership> TypedDictionary<K, V, Own>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    /// Creates a typed view of `dictionary`, checking that all of its keys can be converted
+    /// to `K` and all of its values to `V`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FromVariantError::InvalidKey` or `FromVariantError::InvalidEntry` with the key of
+    /// the first entry whose key or value could not be converted.
+    #[inline]
+    pub fn from_dictionary(dictionary: Dictionary<Own>) -> Result<Self, FromVariantError> {
+        for (key, value) in dictionary.iter() {
+            K::from_variant(&key).map_err(|error| FromVariantError::InvalidKey {
+                key: describe_key(&key),
+                error: Box::new(error),
+            })?;
+            V::from_variant(&value).map_err(|error| FromVariantError::InvalidEntry {
+                key: describe_key(&key),
+                error: Box::new(error),
+            })?;
+        }
+
+        // All entries have been validated above.
+        Ok(Self::assume_typed(dictionary))
+    }
+
+    /// Creates a typed view of `dictionary` without checking its entries.
+    ///
+    /// This is useful when the entries are known to be valid, e.g. when they were inserted by
+    /// the caller. If an entry cannot be converted to `K` and `V`, typed getters like
+    /// [`TypedDictionary::get`] panic when they encounter it, while [`TypedDictionary::try_get`]
+    /// reports the error instead.
+    #[inline]
+    pub fn assume_typed(dictionary: Dictionary<Own>) -> Self {
+        TypedDictionary {
+            inner: dictionary,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying untyped dictionary.
+    #[inline]
+    pub fn as_dictionary(&self) -> &Dictionary<Own> {
+        &self.inner
+    }
+
+    /// Returns the underlying untyped dictionary, consuming the typed view.
+    #[inline]
+    pub fn into_dictionary(self) -> Dictionary<Own> {
+        self.inner
+    }
+
+    /// Returns `true` if the dictionary contains no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the number of entries in the dictionary.
+    #[inline]
+    pub fn len(&self) -> i32 {
+        self.inner.len()
+    }
+
+    /// Returns true if the dictionary contains the specified key.
+    #[inline]
+    pub fn contains(&self, key: &K) -> bool {
+        self.inner.contains(key)
+    }
+
+    /// Returns a copy of the value corresponding to the key if it exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value cannot be converted to `V`.
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.try_get(key).unwrap_or_else(|err| {
+            panic!(
+                "Invalid value for key {}: {}",
+                describe_key(&key.to_variant()),
+                err
+            )
+        })
+    }
+
+    /// Returns a copy of the value corresponding to the key if it exists, or an error if it
+    /// cannot be converted to `V`.
+    #[inline]
+    pub fn try_get(&self, key: &K) -> Result<Option<V>, FromVariantError> {
+        self.inner
+            .get(key)
+            .map(|value| V::from_variant(&value))
+            .transpose()
+    }
+
+    /// Returns a typed array containing all keys of the dictionary.
+    #[inline]
+    pub fn keys(&self) -> Array<K, Unique> {
+        // All keys have been validated on construction.
+        Array::assume_typed(self.inner.keys())
+    }
+
+    /// Returns a typed array containing all values of the dictionary.
+    #[inline]
+    pub fn values(&self) -> Array<V, Unique> {
+        // All values have been validated on construction.
+        Array::assume_typed(self.inner.values())
+    }
+
+    /// Returns an iterator through all key-value pairs in the dictionary, converted to `K`
+    /// and `V`.
+    ///
+    /// See [`Dictionary::iter`] for notes on modifying the dictionary during iteration.
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics if an entry cannot be converted to `K` and `V`.
+    #[inline]
+    pub fn iter(&self) -> TypedDictionaryIter<'_, K, V, Own> {
+        self.into_iter()
+    }
+
+    /// Create a copy of the dictionary.
+    ///
+    /// This creates a new dictionary and is **not** a cheap reference count
+    /// increment.
+    #[inline]
+    pub fn duplicate(&self) -> TypedDictionary<K, V, Unique> {
+        // The copy contains the same entries as `self`.
+        TypedDictionary::assume_typed(self.inner.duplicate())
+    }
+}
+
+/// Operations allowed on typed dictionaries that can only be referenced to from the current thread.
+impl<K, V, Own: LocalThreadOwnership> TypedDictionary<K, V, Own>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    /// Inserts or updates the value of the entry corresponding to the key.
+    #[inline]
+    pub fn insert(&self, key: K, val: V) {
+        self.inner.insert(&key, val.to_variant())
+    }
+
+    /// Erase an entry in the dictionary by the specified key.
+    #[inline]
+    pub fn erase(&self, key: &K) {
+        self.inner.erase(key)
+    }
+
+    /// Clears the dictionary, removing all entries.
+    #[inline]
+    pub fn clear(&self) {
+        self.inner.clear()
+    }
+}
+
+/// Operations allowed on non-unique typed dictionaries.
+impl<K, V, Own: NonUniqueOwnership> TypedDictionary<K, V, Own>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    /// Assume that this is the only reference to this dictionary, on which
+    /// operations that change the container size can be safely performed.
+    ///
+    /// # Safety
+    ///
+    /// See [`Dictionary::assume_unique`].
+    #[inline]
+    pub unsafe fn assume_unique(self) -> TypedDictionary<K, V, Unique> {
+        TypedDictionary::assume_typed(self.inner.assume_unique())
+    }
+}
+
+/// Operations allowed on unique typed dictionaries.
+impl<K, V> TypedDictionary<K, V, Unique>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    /// Creates an empty typed dictionary.
+    #[inline]
+    pub fn new() -> Self {
+        // An empty dictionary has no entries to validate.
+        Self::assume_typed(Dictionary::new())
+    }
+
+    /// Put this dictionary under the "shared" access type.
+    #[inline]
+    pub fn into_shared(self) -> TypedDictionary<K, V, Shared> {
+        TypedDictionary::assume_typed(self.inner.into_shared())
+    }
+
+    /// Put this dictionary under the "thread-local" access type.
+    #[inline]
+    pub fn into_thread_local(self) -> TypedDictionary<K, V, ThreadLocal> {
+        TypedDictionary::assume_typed(self.inner.into_thread_local())
+    }
+}
+
+/// Operations allowed on typed dictionaries that might be shared between different threads.
+impl<K, V> TypedDictionary<K, V, Shared>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    /// Create a new shared typed dictionary.
+    #[inline]
+    pub fn new_shared() -> Self {
+        TypedDictionary::<K, V, Unique>::new().into_shared()
+    }
+}
+
+/// Operations allowed on typed dictionaries that may only be shared on the current thread.
+impl<K, V> TypedDictionary<K, V, ThreadLocal>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    /// Create a new thread-local typed dictionary.
+    #[inline]
+    pub fn new_thread_local() -> Self {
+        TypedDictionary::<K, V, Unique>::new().into_thread_local()
+    }
+}
+
+impl<K, V> Default for TypedDictionary<K, V, Unique>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Default for TypedDictionary<K, V, Shared>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new_shared()
+    }
+}
+
+impl<K, V> Default for TypedDictionary<K, V, ThreadLocal>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new_thread_local()
+    }
+}
+
+impl<K, V, Own: NonUniqueOwnership> NewRef for TypedDictionary<K, V, Own>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn new_ref(&self) -> Self {
+        Self::assume_typed(self.inner.new_ref())
+    }
+}
+
+impl<K, V> From<TypedDictionary<K, V, Unique>> for TypedDictionary<K, V, Shared>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn from(dict: TypedDictionary<K, V, Unique>) -> Self {
+        dict.into_shared()
+    }
+}
+
+impl<K, V> From<TypedDictionary<K, V, Unique>> for TypedDictionary<K, V, ThreadLocal>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn from(dict: TypedDictionary<K, V, Unique>) -> Self {
+        dict.into_thread_local()
+    }
+}
+
+impl<K, V, Own: Ownership> From<TypedDictionary<K, V, Own>> for Dictionary<Own>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn from(dict: TypedDictionary<K, V, Own>) -> Self {
+        dict.into_dictionary()
+    }
+}
+
+impl<K, V, Own: Ownership> TryFrom<Dictionary<Own>> for TypedDictionary<K, V, Own>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    type Error = FromVariantError;
+
+    #[inline]
+    fn try_from(dict: Dictionary<Own>) -> Result<Self, Self::Error> {
+        Self::from_dictionary(dict)
+    }
+}
+
+impl<K, V> ToVariant for TypedDictionary<K, V, Shared>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.inner.to_variant()
+    }
+}
+
+impl<K, V> OwnedToVariant for TypedDictionary<K, V, Unique>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn owned_to_variant(self) -> Variant {
+        self.inner.owned_to_variant()
+    }
+}
+
+impl<K, V> FromVariant for TypedDictionary<K, V, Shared>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Self::from_dictionary(Dictionary::from_variant(variant)?)
+    }
+}
+
+impl<K, V, Own: Ownership> fmt::Debug for TypedDictionary<K, V, Own>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+/// Iterator through all key-value pairs in a [`TypedDictionary`], converted to `K` and `V`.
+pub struct TypedDictionaryIter<'a, K, V, Own: Ownership> {
+    iter: dictionary::Iter<'a, Own>,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'a, K, V, Own: Ownership> Iterator for TypedDictionaryIter<'a, K, V, Own>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.iter.next()?;
+        let entry = K::from_variant(&key).and_then(|k| Ok((k, V::from_variant(&value)?)));
+        Some(
+            entry.unwrap_or_else(|err| {
+                panic!("Invalid entry for key {}: {}", describe_key(&key), err)
+            }),
+        )
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V, Own: Ownership> IntoIterator for &'a TypedDictionary<K, V, Own>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    type Item = (K, V);
+    type IntoIter = TypedDictionaryIter<'a, K, V, Own>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        TypedDictionaryIter {
+            iter: self.inner.iter(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for TypedDictionary<K, V, Unique>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut dict = Self::new();
+        dict.extend(iter);
+        dict
+    }
+}
+
+impl<K, V, Own: LocalThreadOwnership> Extend<(K, V)> for TypedDictionary<K, V, Own>
+where
+    K: ToVariant + ToVariantEq + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+godot_test!(test_typed_dictionary {
+    let dict = TypedDictionary::<String, i64, Unique>::new();
+    assert!(dict.is_empty());
+
+    dict.insert("a".to_string(), 1);
+    dict.insert("b".to_string(), 2);
+    dict.insert("a".to_string(), 3);
+    assert_eq!(2, dict.len());
+    assert!(dict.contains(&"b".to_string()));
+    assert_eq!(Some(3), dict.get(&"a".to_string()));
+    assert_eq!(None, dict.get(&"c".to_string()));
+    assert_eq!(vec!["a".to_string(), "b".to_string()], dict.keys().to_vec());
+    assert_eq!(vec![3, 2], dict.values().to_vec());
+    assert_eq!(
+        vec![("a".to_string(), 3), ("b".to_string(), 2)],
+        dict.iter().collect::<Vec<_>>()
+    );
+
+    dict.erase(&"a".to_string());
+    assert_eq!(1, dict.len());
+
+    let shared = dict.into_shared();
+    let variant = shared.to_variant();
+    let back = TypedDictionary::<String, i64>::from_variant(&variant).unwrap();
+    assert_eq!(Some(2), back.get(&"b".to_string()));
+    assert_eq!(1, shared.duplicate().len());
+});
+
+godot_test!(test_typed_dictionary_validation {
+    let untyped = Dictionary::new();
+    untyped.insert("a", 1);
+    untyped.insert("b", "two");
+
+    let err = TypedDictionary::<String, i64, Unique>::from_dictionary(untyped).unwrap_err();
+    assert!(matches!(err, FromVariantError::InvalidEntry { ref key, .. } if key == "\"b\""));
+
+    let untyped = Dictionary::new();
+    untyped.insert(1, 1);
+    let err = TypedDictionary::<String, i64, Unique>::try_from(untyped).unwrap_err();
+    assert!(matches!(err, FromVariantError::InvalidKey { ref key, .. } if key == "1"));
+
+    let untyped = Dictionary::new();
+    untyped.insert("a", 1);
+    let typed = TypedDictionary::<String, i64, Unique>::try_from(untyped).unwrap();
+    let untyped = typed.into_dictionary();
+    untyped.insert("b", "two");
+    let typed = TypedDictionary::<String, i64, Unique>::assume_typed(untyped);
+    assert_eq!(Ok(Some(1)), typed.try_get(&"a".to_string()));
+    assert!(typed.try_get(&"b".to_string()).is_err());
+});
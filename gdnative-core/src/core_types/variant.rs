@@ -1,20 +1,46 @@
+//! The `Variant` type and conversions between Rust types and Godot values.
+//!
+//! Everything in this module is also re-exported from [`crate::core_types`].
+
 use crate::*;
+use indexmap::IndexMap;
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::default::Default;
 use std::fmt;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
 use std::mem::{forget, transmute};
+use std::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::path::{Path, PathBuf};
 use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::core_types::*;
 use crate::object::ownership::*;
 use crate::object::*;
 use crate::private::{get_api, ManuallyManagedClassPlaceholder};
 
+pub mod json;
+pub mod marshal;
+pub mod text;
+
+#[cfg(feature = "serde")]
+mod de;
+#[cfg(feature = "serde")]
+mod ser;
 #[cfg(feature = "serde")]
 mod serialize;
 
+#[cfg(feature = "serde")]
+pub use de::{from_variant, VariantDeserializer};
+#[cfg(feature = "serde")]
+pub use ser::{to_variant, EnumRepr, SerdeError, VariantSerializer};
+
 /// A `Variant` can represent all Godot values (core types or `Object` class instances).
 ///
 /// The underlying data is either stored inline or reference-counted on the heap,
@@ -23,7 +49,8 @@ mod serialize;
 /// If you compile godot-rust with the `serde` feature enabled, you will have
 /// access to serialization/deserialization support: the traits `Serialize`
 /// and `Deserialize` will be automatically implemented on [`VariantDispatch`]
-/// as well as most of the types in [`core_types`].
+/// as well as most of the types in [`core_types`]. Arbitrary `serde` types can also be
+/// converted to and from `Variant`s directly, using [`to_variant`] and [`from_variant`].
 pub struct Variant(pub(crate) sys::godot_variant);
 
 macro_rules! impl_coerce_from_variant_inner {
@@ -159,7 +186,7 @@ decl_variant_type!(
         VariantArray(VariantArray) = sys::godot_variant_type_GODOT_VARIANT_TYPE_ARRAY,
         ByteArray(PoolArray<u8>) = sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_BYTE_ARRAY,
         Int32Array(PoolArray<i32>) = sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_INT_ARRAY,
-        Float32Array(PoolArray<f32>) = sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_REAL_ARRAY,
+        Float32Array(PoolArray<real>) = sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_REAL_ARRAY,
         StringArray(PoolArray<GodotString>) = sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_STRING_ARRAY,
         Vector2Array(PoolArray<Vector2>) = sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_VECTOR2_ARRAY,
         Vector3Array(PoolArray<Vector3>) = sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_VECTOR3_ARRAY,
@@ -466,8 +493,8 @@ impl Variant {
     }
 
     #[inline]
-    pub fn has_method(&self, method: impl Into<GodotString>) -> bool {
-        let method = method.into();
+    pub fn has_method(&self, method: impl NameArg) -> bool {
+        let method = method.into_name();
         unsafe { (get_api().godot_variant_has_method)(&self.0, &method.0) }
     }
 
@@ -479,10 +506,10 @@ impl Variant {
     #[inline]
     pub unsafe fn call(
         &mut self,
-        method: impl Into<GodotString>,
+        method: impl NameArg,
         args: &[Variant],
     ) -> Result<Variant, CallError> {
-        let method = method.into();
+        let method = method.into_name();
 
         let api = get_api();
         let mut err = sys::godot_variant_call_error::default();
@@ -604,7 +631,7 @@ impl_coerce_from_variant!(
     impl CoerceFromVariant for VariantArray<Shared> = from_sys(godot_variant_as_array);
     impl CoerceFromVariant for PoolArray<u8> = from_sys(godot_variant_as_pool_byte_array);
     impl CoerceFromVariant for PoolArray<i32> = from_sys(godot_variant_as_pool_int_array);
-    impl CoerceFromVariant for PoolArray<f32> = from_sys(godot_variant_as_pool_real_array);
+    impl CoerceFromVariant for PoolArray<real> = from_sys(godot_variant_as_pool_real_array);
     impl CoerceFromVariant for PoolArray<GodotString> = from_sys(godot_variant_as_pool_string_array);
     impl CoerceFromVariant for PoolArray<Vector2> = from_sys(godot_variant_as_pool_vector2_array);
     impl CoerceFromVariant for PoolArray<Vector3> = from_sys(godot_variant_as_pool_vector3_array);
@@ -739,6 +766,36 @@ godot_test!(
 /// Only applicable to field-less enums with a explicit primitive `#[repr]` type. Variants of
 /// types annotated with this attribute are represented as their primitive integral values.
 ///
+/// - `#[variant(tag = "type")]`
+///
+/// Represent variants as a `Dictionary` containing the variant name under the given key,
+/// alongside the variant's fields, i.e. `{ "type": "Variant", "a": 1, "b": 2 }`. Unit variants
+/// produce only the tag. Newtype variants are supported if their payload converts to a
+/// `Dictionary`, while tuple variants are rejected. Payloads of types that are known not to, like
+/// numbers or `Vec`s, are rejected at compile time, while others cause a panic when converted.
+/// Fields can't be named like the tag. Should a flattened or newtype payload contain the tag's
+/// key anyway, the tag takes precedence.
+///
+/// - `#[variant(tag = "t", content = "c")]`
+///
+/// Represent variants as a `Dictionary` containing the variant name under `tag`, and the
+/// payload under `content`, i.e. `{ "t": "Variant", "c": [1, 2] }`. Unit variants produce only
+/// the tag.
+///
+/// - `#[variant(untagged)]`
+///
+/// Represent variants as their payloads only, with unit variants as `Nil`. When converting from
+/// `Variant`, each variant is attempted in declaration order and the first one that succeeds is
+/// returned. If none does, the errors for all variants are reported through
+/// `FromVariantError::NoMatchingEnumVariant`.
+///
+/// - `#[variant(rename_all = "...")]`
+///
+/// Rename all fields of a struct, or all variants of an enum, according to the given case
+/// convention. The possible values are `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`,
+/// `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"` and
+/// `"SCREAMING-KEBAB-CASE"`, with the same meanings as in `serde`.
+///
 /// ### Field attributes
 ///
 /// - `#[variant(to_variant_with = "path::to::func")]`
@@ -769,6 +826,31 @@ godot_test!(
 /// - `#[variant(skip)]`
 ///
 /// Convenience attribute that sets `skip_to_variant` and `skip_from_variant`.
+///
+/// - `#[variant(rename = "name")]`
+///
+/// Use the given name as the `Dictionary` key of the field, instead of the field's own name.
+/// Takes precedence over `rename_all`. Only applicable to named fields.
+///
+/// - `#[variant(default)]`
+///
+/// When converting from `Variant`, use `Default::default()` if the key of the field is missing,
+/// instead of returning an error. A key that is present with a `Nil` value is still converted.
+///
+/// - `#[variant(default = "path::to::func")]`
+///
+/// Same as `default`, but obtain the value by calling the given function, which is expected to
+/// have the signature `fn() -> T`. Also applies to fields skipped with `skip_from_variant`.
+///
+/// - `#[variant(flatten)]`
+///
+/// Merge the entries of the field, which should be represented as a `Dictionary`, into the
+/// `Dictionary` of the containing struct, instead of nesting it under its own key. When
+/// converting from `Variant`, the field is converted from all entries that don't belong to
+/// other fields. Fields converted to `Nil` produce no entries. Fields converted to any other
+/// type that isn't a `Dictionary` can't be merged, and are stored under their own key as if
+/// they weren't flattened. Conversion from `Variant` falls back to that key when the field
+/// can't be converted from the remaining entries, so these values still round-trip.
 pub trait ToVariant {
     fn to_variant(&self) -> Variant;
 }
@@ -862,6 +944,12 @@ pub trait CoerceFromVariant: Sized + private::Sealed {
 #[derive(Clone, PartialEq, Eq, Debug)]
 #[non_exhaustive]
 /// Error type returned by `FromVariant::from_variant`.
+///
+/// Implementations for collections and derived implementations wrap errors from their elements
+/// in `InvalidItem`, `InvalidEntry`, `InvalidKey`, `InvalidField` or `InvalidEnumVariant`,
+/// recording where the conversion failed. The location can be inspected with [`path`](Self::path), and is
+/// included when the error is displayed, e.g.
+/// `[3].items["sword"].damage: invalid variant type: expected I64, got GodotString`.
 pub enum FromVariantError {
     /// An unspecified error.
     Unspecified,
@@ -914,6 +1002,13 @@ pub enum FromVariantError {
         error: Box<FromVariantError>,
     },
 
+    /// Error indicating that none of the variants of an untagged enum could be converted from
+    /// the value.
+    ///
+    /// `errors` contains an `InvalidEnumVariant` error for each variant that was attempted, in
+    /// declaration order.
+    NoMatchingEnumVariant { errors: Vec<FromVariantError> },
+
     /// Given object is not an instance of the expected NativeClass.
     InvalidInstance { expected: Cow<'static, str> },
     /// Collection contains an invalid field.
@@ -926,12 +1021,29 @@ pub enum FromVariantError {
         index: usize,
         error: Box<FromVariantError>,
     },
+    /// Dictionary contains an invalid value.
+    InvalidEntry {
+        /// Key of the entry, formatted for display. String keys are quoted.
+        key: String,
+        error: Box<FromVariantError>,
+    },
+    /// Dictionary contains an invalid key.
+    InvalidKey {
+        /// The key, formatted for display. String keys are quoted.
+        key: String,
+        error: Box<FromVariantError>,
+    },
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 #[non_exhaustive]
 pub enum VariantEnumRepr {
+    /// `{ "Variant": payload }`
     ExternallyTagged,
+    /// `{ "tag": "Variant", ...fields }`
+    InternallyTagged,
+    /// `{ "tag": "Variant", "content": payload }`
+    AdjacentlyTagged,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -942,12 +1054,114 @@ pub enum VariantStructRepr {
     Struct,
 }
 
+/// A step into a collection, struct or enum taken by a `FromVariant` implementation.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum FromVariantPathSegment {
+    /// Item of an array, displayed as `[index]`.
+    Index(usize),
+    /// Value of a dictionary entry, displayed as `[key]`.
+    Key(String),
+    /// Key of a dictionary entry itself, displayed as `{key}`.
+    KeyOf(String),
+    /// Field of a struct, displayed as `.field`.
+    Field(&'static str),
+    /// Variant of an enum, displayed as `::Variant`.
+    Variant(&'static str),
+}
+
+/// Location of the value that caused a [`FromVariantError`], relative to the `Variant` passed
+/// to `from_variant`. Displayed like `[3].items["sword"].damage`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct FromVariantPath {
+    segments: Vec<FromVariantPathSegment>,
+}
+
+impl FromVariantPath {
+    /// Returns the segments of the path, from the outermost to the innermost.
+    #[inline]
+    pub fn segments(&self) -> &[FromVariantPathSegment] {
+        &self.segments
+    }
+
+    /// Returns `true` if the error occurred at the top level.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+impl fmt::Display for FromVariantPath {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use FromVariantPathSegment as S;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                S::Index(index) => write!(f, "[{index}]")?,
+                S::Key(key) => write!(f, "[{key}]")?,
+                S::KeyOf(key) => write!(f, "{{{key}}}")?,
+                S::Field(field_name) if i == 0 => write!(f, "{field_name}")?,
+                S::Field(field_name) => write!(f, ".{field_name}")?,
+                S::Variant(variant) => write!(f, "::{variant}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
 impl FromVariantError {
     /// Returns a `FromVariantError` with a custom message.
     #[inline]
     pub fn custom<T: fmt::Display>(message: T) -> Self {
         FromVariantError::Custom(format!("{message}"))
     }
+
+    /// Returns the path to the value that caused this error.
+    #[inline]
+    pub fn path(&self) -> FromVariantPath {
+        let mut segments = Vec::new();
+        let mut error = self;
+        while let Some((segment, inner)) = error.split_segment() {
+            segments.push(segment);
+            error = inner;
+        }
+        FromVariantPath { segments }
+    }
+
+    /// Returns the error for the value at [`path`](Self::path), i.e. this error without the
+    /// wrappers recording its location.
+    #[inline]
+    pub fn root_cause(&self) -> &FromVariantError {
+        let mut error = self;
+        while let Some((_, inner)) = error.split_segment() {
+            error = inner;
+        }
+        error
+    }
+
+    fn split_segment(&self) -> Option<(FromVariantPathSegment, &FromVariantError)> {
+        use FromVariantPathSegment as S;
+
+        let split = match self {
+            FromVariantError::InvalidItem { index, error } => (S::Index(*index), error),
+            FromVariantError::InvalidEntry { key, error } => (S::Key(key.clone()), error),
+            FromVariantError::InvalidKey { key, error } => (S::KeyOf(key.clone()), error),
+            FromVariantError::InvalidField { field_name, error } => (S::Field(field_name), error),
+            FromVariantError::InvalidEnumVariant { variant, error } => (S::Variant(variant), error),
+            _ => return None,
+        };
+        Some((split.0, split.1.as_ref()))
+    }
+}
+
+/// Formats a dictionary key for [`FromVariantError::InvalidEntry`] and
+/// [`FromVariantError::InvalidKey`].
+pub(crate) fn describe_key(key: &Variant) -> String {
+    match key.dispatch() {
+        VariantDispatch::GodotString(key) => format!("{:?}", key.to_string()),
+        _ => key.to_string(),
+    }
 }
 
 impl fmt::Display for FromVariantError {
@@ -993,35 +1207,22 @@ impl fmt::Display for FromVariantError {
                 }
                 Ok(())
             }
-            E::InvalidEnumVariant { variant, error } => {
-                write!(f, "invalid value for variant {variant}: {error}")
+            E::NoMatchingEnumVariant { errors } => {
+                write!(f, "value did not match any variant of the untagged enum")?;
+                for error in errors {
+                    write!(f, "; {error}")?;
+                }
+                Ok(())
             }
             E::InvalidInstance { expected } => {
                 write!(f, "object is not an instance of `NativeClass` {expected}")
             }
-            E::InvalidField { field_name, error } => {
-                write!(f, "invalid value for field {field_name}")?;
-
-                let mut next_error = error.as_ref();
-                loop {
-                    match next_error {
-                        E::InvalidField { field_name, error } => {
-                            write!(f, ".{field_name}")?;
-                            next_error = error.as_ref();
-                        }
-                        E::InvalidItem { index, error } => {
-                            write!(f, "[{index}]")?;
-                            next_error = error.as_ref();
-                        }
-                        _ => {
-                            write!(f, ": {next_error}")?;
-                            return Ok(());
-                        }
-                    }
-                }
-            }
-            E::InvalidItem { index, error } => {
-                write!(f, "invalid value for item at index {index}: {error}")
+            E::InvalidItem { .. }
+            | E::InvalidEntry { .. }
+            | E::InvalidKey { .. }
+            | E::InvalidField { .. }
+            | E::InvalidEnumVariant { .. } => {
+                write!(f, "{}: {}", self.path(), self.root_cause())
             }
         }
     }
@@ -1428,6 +1629,22 @@ impl FromVariant for String {
     }
 }
 
+/// Godot 3 variants can't hold `StringName`s, so they are converted to `GodotString`s.
+impl ToVariant for StringName {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.to_godot_string().owned_to_variant()
+    }
+}
+impl ToVariantEq for StringName {}
+
+impl FromVariant for StringName {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        GodotString::from_variant(variant).map(|s| StringName::from_godot_string(&s))
+    }
+}
+
 impl ToVariant for Variant {
     #[inline]
     fn to_variant(&self) -> Variant {
@@ -1621,24 +1838,55 @@ impl<T: ToVariant> ToVariant for Vec<T> {
 impl<T: FromVariant> FromVariant for Vec<T> {
     #[inline]
     fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
-        let arr = VariantArray::from_variant(variant)?;
-        let len: usize = arr
-            .len()
-            .try_into()
-            .expect("variant array length should fit in usize");
-        let mut vec = Vec::with_capacity(len);
-        for idx in 0..len as i32 {
-            let item =
-                T::from_variant(&arr.get(idx)).map_err(|e| FromVariantError::InvalidItem {
-                    index: idx as usize,
-                    error: Box::new(e),
-                })?;
-            vec.push(item);
-        }
-        Ok(vec)
+        items_from_variant(variant)
     }
 }
 
+/// Converts the items of a `VariantArray` into a collection, recording the index of any item
+/// that fails to convert.
+fn items_from_variant<T, C>(variant: &Variant) -> Result<C, FromVariantError>
+where
+    T: FromVariant,
+    C: FromIterator<T>,
+{
+    let arr = VariantArray::from_variant(variant)?;
+    arr.iter()
+        .enumerate()
+        .map(|(index, item)| {
+            T::from_variant(&item).map_err(|e| FromVariantError::InvalidItem {
+                index,
+                error: Box::new(e),
+            })
+        })
+        .collect()
+}
+
+/// Converts the entries of a `Dictionary` into a collection, recording the key of any key or value
+/// that fails to convert. Entries are produced in the order of the dictionary.
+fn entries_from_variant<K, V, C>(variant: &Variant) -> Result<C, FromVariantError>
+where
+    K: FromVariant,
+    V: FromVariant,
+    C: FromIterator<(K, V)>,
+{
+    let dictionary = Dictionary::from_variant(variant)?;
+    dictionary
+        .iter()
+        .map(|(key, value)| {
+            let converted_key =
+                K::from_variant(&key).map_err(|err| FromVariantError::InvalidKey {
+                    key: describe_key(&key),
+                    error: Box::new(err),
+                })?;
+            let value = V::from_variant(&value).map_err(|err| FromVariantError::InvalidEntry {
+                key: describe_key(&key),
+                error: Box::new(err),
+            })?;
+            Ok((converted_key, value))
+        })
+        .collect()
+}
+
 /// Converts the hash map to a `Dictionary`, wrapped in a `Variant`.
 ///
 /// Note that Rust's `HashMap` is non-deterministically ordered for security reasons, meaning that
@@ -1673,17 +1921,7 @@ impl<K: ToVariant + Hash + ToVariantEq, V: ToVariant> ToVariant for HashMap<K, V
 impl<K: FromVariant + Hash + Eq, V: FromVariant> FromVariant for HashMap<K, V> {
     #[inline]
     fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
-        let dictionary = Dictionary::from_variant(variant)?;
-        let len: usize = dictionary
-            .len()
-            .try_into()
-            .expect("Dictionary length should fit in usize");
-
-        let mut hash_map = HashMap::with_capacity(len);
-        for (key, value) in dictionary.iter() {
-            hash_map.insert(K::from_variant(&key)?, V::from_variant(&value)?);
-        }
-        Ok(hash_map)
+        entries_from_variant(variant)
     }
 }
 
@@ -1712,22 +1950,280 @@ impl<T: ToVariant> ToVariant for HashSet<T> {
 impl<T: FromVariant + Eq + Hash> FromVariant for HashSet<T> {
     #[inline]
     fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
-        let arr = VariantArray::from_variant(variant)?;
-        let len: usize = arr
-            .len()
-            .try_into()
-            .expect("VariantArray length should fit in usize");
-
-        let mut set = HashSet::with_capacity(len);
-        for idx in 0..len as i32 {
-            let item =
-                T::from_variant(&arr.get(idx)).map_err(|e| FromVariantError::InvalidItem {
-                    index: idx as usize,
-                    error: Box::new(e),
-                })?;
-            set.insert(item);
+        items_from_variant(variant)
+    }
+}
+
+/// Converts the map to a `Dictionary`, wrapped in a `Variant`, with entries in key order.
+impl<K: ToVariant + ToVariantEq, V: ToVariant> ToVariant for BTreeMap<K, V> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let dict = Dictionary::new();
+        for (key, value) in self {
+            dict.insert(key.to_variant(), value.to_variant());
+        }
+        dict.owned_to_variant()
+    }
+}
+
+impl<K: FromVariant + Ord, V: FromVariant> FromVariant for BTreeMap<K, V> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        entries_from_variant(variant)
+    }
+}
+
+/// Converts the set to a `VariantArray`, wrapped in a `Variant`, with elements in order.
+impl<T: ToVariant> ToVariant for BTreeSet<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.iter()
+            .collect::<VariantArray<Unique>>()
+            .owned_to_variant()
+    }
+}
+
+impl<T: FromVariant + Ord> FromVariant for BTreeSet<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        items_from_variant(variant)
+    }
+}
+
+/// Converts the map to a `Dictionary`, wrapped in a `Variant`, preserving the order of entries.
+impl<K, V, S> ToVariant for IndexMap<K, V, S>
+where
+    K: ToVariant + ToVariantEq,
+    V: ToVariant,
+{
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let dict = Dictionary::new();
+        for (key, value) in self {
+            dict.insert(key.to_variant(), value.to_variant());
+        }
+        dict.owned_to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `Dictionary`, and preserves the order of its entries.
+impl<K, V, S> FromVariant for IndexMap<K, V, S>
+where
+    K: FromVariant + Hash + Eq,
+    V: FromVariant,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        entries_from_variant(variant)
+    }
+}
+
+impl<T: ToVariant> ToVariant for VecDeque<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.iter()
+            .collect::<VariantArray<Unique>>()
+            .owned_to_variant()
+    }
+}
+
+impl<T: FromVariant> FromVariant for VecDeque<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        items_from_variant(variant)
+    }
+}
+
+impl<T: ToVariant, const N: usize> ToVariant for [T; N] {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_slice().to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `VariantArray` of exactly `N` elements.
+impl<T: FromVariant, const N: usize> FromVariant for [T; N] {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let len = VariantArray::from_variant(variant)?.len() as usize;
+        if len != N {
+            return Err(FromVariantError::InvalidLength { len, expected: N });
+        }
+
+        let vec: Vec<T> = items_from_variant(variant)?;
+        match vec.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => unreachable!("length is checked above"),
+        }
+    }
+}
+
+macro_rules! impl_variant_for_smart_pointer {
+    ($($ptr:ident),*) => {
+        $(
+            impl<T: ToVariant + ?Sized> ToVariant for $ptr<T> {
+                #[inline]
+                fn to_variant(&self) -> Variant {
+                    T::to_variant(self)
+                }
+            }
+            impl<T: ToVariantEq + ?Sized> ToVariantEq for $ptr<T> {}
+
+            impl<T: FromVariant> FromVariant for $ptr<T> {
+                #[inline]
+                fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                    T::from_variant(variant).map($ptr::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_for_smart_pointer!(Box, Rc, Arc);
+
+impl<'a, T> ToVariant for Cow<'a, T>
+where
+    T: ToVariant + ToOwned + ?Sized,
+{
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        T::to_variant(self)
+    }
+}
+impl<'a, T> ToVariantEq for Cow<'a, T> where T: ToVariantEq + ToOwned + ?Sized {}
+
+/// Always produces `Cow::Owned`.
+impl<'a, T> FromVariant for Cow<'a, T>
+where
+    T: ToOwned + ?Sized,
+    T::Owned: FromVariant,
+{
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        T::Owned::from_variant(variant).map(Cow::Owned)
+    }
+}
+
+/// Converts the character to a `GodotString` of length 1, wrapped in a `Variant`.
+impl ToVariant for char {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.encode_utf8(&mut [0; 4]).to_variant()
+    }
+}
+impl ToVariantEq for char {}
+
+/// Expects a `Variant` populated with a `GodotString` of exactly one character.
+impl FromVariant for char {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let s = String::from_variant(variant)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(FromVariantError::InvalidLength {
+                len: s.chars().count(),
+                expected: 1,
+            }),
         }
-        Ok(set)
+    }
+}
+
+macro_rules! impl_variant_for_non_zero {
+    ($($ty:ident : $int:ty),*) => {
+        $(
+            impl ToVariant for $ty {
+                #[inline]
+                fn to_variant(&self) -> Variant {
+                    self.get().to_variant()
+                }
+            }
+            impl ToVariantEq for $ty {}
+
+            impl FromVariant for $ty {
+                #[inline]
+                fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                    let value = <$int>::from_variant(variant)?;
+                    $ty::new(value).ok_or_else(|| {
+                        FromVariantError::custom("expected a non-zero integer, got 0")
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_for_non_zero!(
+    NonZeroI8: i8,
+    NonZeroI16: i16,
+    NonZeroI32: i32,
+    NonZeroI64: i64,
+    NonZeroIsize: isize,
+    NonZeroU8: u8,
+    NonZeroU16: u16,
+    NonZeroU32: u32,
+    NonZeroU64: u64,
+    NonZeroUsize: usize
+);
+
+/// Converts the duration to a `f64` number of seconds, the unit used by Godot, wrapped in a
+/// `Variant`.
+impl ToVariant for Duration {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_secs_f64().to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a non-negative number of seconds, either as a `f64` or an
+/// `i64`.
+impl FromVariant for Duration {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let secs = match variant.dispatch() {
+            VariantDispatch::F64(secs) => secs,
+            VariantDispatch::I64(secs) => secs as f64,
+            _ => {
+                return Err(FromVariantError::InvalidVariantType {
+                    variant_type: variant.get_type(),
+                    expected: VariantType::F64,
+                })
+            }
+        };
+
+        if secs.is_finite() && secs >= 0.0 && secs < u64::MAX as f64 {
+            Ok(Duration::from_secs_f64(secs))
+        } else {
+            Err(FromVariantError::custom(format!(
+                "expected a non-negative number of seconds, got {secs}"
+            )))
+        }
+    }
+}
+
+/// Converts the path to a `GodotString`, wrapped in a `Variant`. Paths that are not valid
+/// Unicode are converted lossily.
+impl ToVariant for Path {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.to_string_lossy().to_variant()
+    }
+}
+impl ToVariantEq for Path {}
+
+impl ToVariant for PathBuf {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_path().to_variant()
+    }
+}
+impl ToVariantEq for PathBuf {}
+
+impl FromVariant for PathBuf {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        String::from_variant(variant).map(PathBuf::from)
     }
 }
 
@@ -1917,20 +2413,37 @@ godot_test!(
         non_homogenous_key_dictonary.insert(7, 2u32);
         assert_eq!(
             non_homogenous_key_dictonary.owned_to_variant().try_to::<HashMap<String, u32>>(),
-            Err(FromVariantError::InvalidVariantType {
-                variant_type: VariantType::I64,
-                expected: VariantType::GodotString
+            Err(FromVariantError::InvalidKey {
+                key: "7".to_string(),
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    variant_type: VariantType::I64,
+                    expected: VariantType::GodotString
+                })
             }),
         );
+        let string_key_dictionary = Dictionary::new();
+        string_key_dictionary.insert(1, 1u32);
+        string_key_dictionary.insert("two", 2u32);
+        let err = string_key_dictionary
+            .owned_to_variant()
+            .try_to::<HashMap<i64, u32>>()
+            .unwrap_err();
+        assert_eq!(
+            "{\"two\"}: invalid variant type: expected I64, got GodotString",
+            err.to_string(),
+        );
         // Check conversion of heterogeneous dictionary value types
         let non_homogenous_value_dictonary = Dictionary::new();
         non_homogenous_value_dictonary.insert("Foo".to_string(), 4u32);
         non_homogenous_value_dictonary.insert("Bar".to_string(), "Unexpected".to_string());
         assert_eq!(
             non_homogenous_value_dictonary.owned_to_variant().try_to::<HashMap<String, u32>>(),
-            Err(FromVariantError::InvalidVariantType {
-                variant_type: VariantType::GodotString,
-                expected: VariantType::I64
+            Err(FromVariantError::InvalidEntry {
+                key: "\"Bar\"".to_string(),
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    variant_type: VariantType::GodotString,
+                    expected: VariantType::I64
+                })
             }),
         );
     }
@@ -1992,6 +2505,84 @@ godot_test!(
         );
     }
 
+    test_variant_ordered_collections {
+        let map = BTreeMap::from([(3, "c".to_string()), (1, "a".to_string()), (2, "b".to_string())]);
+        let dict = map.to_variant().try_to::<Dictionary>().expect("should be dictionary");
+        let keys: Vec<i64> = dict.keys().iter().map(|k| k.try_to().unwrap()).collect();
+        assert_eq!(vec![1, 2, 3], keys);
+        assert_eq!(Ok(map.clone()), BTreeMap::from_variant(&map.to_variant()));
+
+        let dict = Dictionary::new();
+        dict.insert("z", 1);
+        dict.insert("a", 2);
+        dict.insert("m", 3);
+        let index_map = IndexMap::<String, i64>::from_variant(&dict.owned_to_variant())
+            .expect("should be index map");
+        let keys: Vec<&str> = index_map.keys().map(String::as_str).collect();
+        assert_eq!(vec!["z", "a", "m"], keys);
+        let dict = index_map.to_variant().try_to::<Dictionary>().expect("should be dictionary");
+        let keys: Vec<String> = dict.keys().iter().map(|k| k.try_to().unwrap()).collect();
+        assert_eq!(vec!["z", "a", "m"], keys);
+
+        let set = BTreeSet::from(["b".to_string(), "a".to_string()]);
+        let arr = set.to_variant().try_to::<VariantArray>().expect("should be array");
+        assert_eq!(Ok("a".to_string()), arr.get(0).try_to::<String>());
+        assert_eq!(Ok(set.clone()), BTreeSet::from_variant(&set.to_variant()));
+
+        let deque = VecDeque::from([1, 2, 3]);
+        assert_eq!(Ok(deque.clone()), VecDeque::from_variant(&deque.to_variant()));
+    }
+
+    test_variant_fixed_arrays {
+        let array = [1_i64, 2, 3];
+        assert_eq!(Ok(array), <[i64; 3]>::from_variant(&array.to_variant()));
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { len: 3, expected: 2 }),
+            <[i64; 2]>::from_variant(&array.to_variant()),
+        );
+        assert_eq!(
+            Err(FromVariantError::InvalidItem {
+                index: 1,
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    variant_type: VariantType::GodotString,
+                    expected: VariantType::I64,
+                }),
+            }),
+            <[i64; 2]>::from_variant(&(1, "two").to_variant()),
+        );
+    }
+
+    test_variant_std_wrappers {
+        assert_eq!(Ok(Box::new(42)), Box::<i64>::from_variant(&Box::new(42).to_variant()));
+        assert_eq!(Ok(Rc::new(42)), Rc::<i64>::from_variant(&Rc::new(42).to_variant()));
+        assert_eq!(Ok(Arc::new(42)), Arc::<i64>::from_variant(&Arc::new(42).to_variant()));
+        let boxed: Box<str> = "boxed".into();
+        assert_eq!(Ok("boxed".to_string()), boxed.to_variant().try_to::<String>());
+
+        let cow: Cow<str> = Cow::Borrowed("cow");
+        assert_eq!(Ok(cow.clone()), Cow::<str>::from_variant(&cow.to_variant()));
+
+        assert_eq!(Ok('ä'), char::from_variant(&'ä'.to_variant()));
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { len: 2, expected: 1 }),
+            char::from_variant(&"ab".to_variant()),
+        );
+
+        let non_zero = NonZeroU32::new(7).unwrap();
+        assert_eq!(Ok(non_zero), NonZeroU32::from_variant(&non_zero.to_variant()));
+        assert!(NonZeroU32::from_variant(&0.to_variant()).is_err());
+
+        let duration = Duration::from_millis(1500);
+        assert_eq!(Ok(1.5), duration.to_variant().try_to::<f64>());
+        assert_eq!(Ok(duration), Duration::from_variant(&duration.to_variant()));
+        assert_eq!(Ok(Duration::from_secs(2)), Duration::from_variant(&2.to_variant()));
+        assert!(Duration::from_variant(&(-1.0).to_variant()).is_err());
+
+        let path = PathBuf::from("res://icon.png");
+        assert_eq!(Ok("res://icon.png".to_string()), path.to_variant().try_to::<String>());
+        assert_eq!(Ok(path.clone()), PathBuf::from_variant(&path.to_variant()));
+    }
+
     test_variant_tuple {
         let variant = (42i64, 54i64).to_variant();
         let arr = variant.try_to::<VariantArray>().expect("should be array");
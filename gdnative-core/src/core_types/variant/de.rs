@@ -0,0 +1,310 @@
+use serde::de::value::{MapDeserializer, SeqDeserializer, StringDeserializer, U64Deserializer};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, Unexpected,
+    VariantAccess, Visitor,
+};
+use serde::Serialize;
+
+use super::ser::{SerdeError, VariantSerializer};
+use crate::core_types::*;
+
+/// Deserializes a value implementing `Deserialize` directly from a [`Variant`].
+///
+/// This accepts the representation produced by [`to_variant`], regardless of the configured
+/// [`EnumRepr`]. Integers are converted to floats where necessary, but not the other way around.
+///
+/// Core types with `serde` support are presented to `Deserialize` implementations the same way
+/// they would be serialized by their `Serialize` implementations, so a [`Vector2`] can be read
+/// into either a `Vector2` field or a user-defined struct with `x` and `y` fields.
+///
+/// `Rid`s and `Object`s cannot be deserialized.
+///
+/// See [`to_variant`] for an example.
+#[inline]
+pub fn from_variant<T>(variant: &Variant) -> Result<T, SerdeError>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(VariantDeserializer::new(variant.clone()))
+}
+
+/// Serde deserializer reading from a [`Variant`]. See [`from_variant`] for details.
+#[derive(Debug)]
+pub struct VariantDeserializer {
+    variant: Variant,
+}
+
+impl VariantDeserializer {
+    /// Creates a deserializer reading from `variant`.
+    #[inline]
+    pub fn new(variant: Variant) -> Self {
+        VariantDeserializer { variant }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, SerdeError> for Variant {
+    type Deserializer = VariantDeserializer;
+
+    #[inline]
+    fn into_deserializer(self) -> VariantDeserializer {
+        VariantDeserializer::new(self)
+    }
+}
+
+fn unexpected(variant: &Variant) -> Unexpected<'static> {
+    match variant.dispatch() {
+        VariantDispatch::Nil => Unexpected::Unit,
+        VariantDispatch::Bool(v) => Unexpected::Bool(v),
+        VariantDispatch::I64(v) => Unexpected::Signed(v),
+        VariantDispatch::F64(v) => Unexpected::Float(v),
+        _ => Unexpected::Other(variant.get_type().name()),
+    }
+}
+
+fn visit_seq<'de, V>(elements: Vec<Variant>, visitor: V) -> Result<V::Value, SerdeError>
+where
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(elements.into_iter());
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+fn pool_elements<T>(array: &PoolArray<T>) -> Vec<Variant>
+where
+    T: PoolElement + ToVariant,
+{
+    array.read().iter().map(ToVariant::to_variant).collect()
+}
+
+/// Presents a core type to a visitor in the form produced by its `Serialize` implementation.
+fn visit_core_type<'de, T, V>(value: &T, visitor: V) -> Result<V::Value, SerdeError>
+where
+    T: Serialize,
+    V: Visitor<'de>,
+{
+    let expanded = value.serialize(VariantSerializer::expanded())?;
+    de::Deserializer::deserialize_any(VariantDeserializer::new(expanded), visitor)
+}
+
+impl<'de> de::Deserializer<'de> for VariantDeserializer {
+    type Error = SerdeError;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.variant.dispatch() {
+            VariantDispatch::Nil => visitor.visit_unit(),
+            VariantDispatch::Bool(v) => visitor.visit_bool(v),
+            VariantDispatch::I64(v) => visitor.visit_i64(v),
+            VariantDispatch::F64(v) => visitor.visit_f64(v),
+            VariantDispatch::GodotString(v) => visitor.visit_string(v.to_string()),
+            VariantDispatch::NodePath(v) => visitor.visit_string(v.to_string()),
+            VariantDispatch::Vector2(v) => visit_core_type(&v, visitor),
+            VariantDispatch::Rect2(v) => visit_core_type(&v, visitor),
+            VariantDispatch::Vector3(v) => visit_core_type(&v, visitor),
+            VariantDispatch::Transform2D(v) => visit_core_type(&v, visitor),
+            VariantDispatch::Plane(v) => visit_core_type(&v, visitor),
+            VariantDispatch::Quat(v) => visit_core_type(&v, visitor),
+            VariantDispatch::Aabb(v) => visit_core_type(&v, visitor),
+            VariantDispatch::Basis(v) => visit_core_type(&v, visitor),
+            VariantDispatch::Transform(v) => visit_core_type(&v, visitor),
+            VariantDispatch::Color(v) => visit_core_type(&v, visitor),
+            VariantDispatch::Rid(_) => Err(de::Error::custom("RIDs cannot be deserialized")),
+            VariantDispatch::Object(_) => Err(de::Error::custom("Objects cannot be deserialized")),
+            VariantDispatch::Dictionary(dict) => {
+                let mut map = MapDeserializer::new(dict.iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            VariantDispatch::VariantArray(v) => visit_seq(v.iter().collect(), visitor),
+            VariantDispatch::ByteArray(v) => visit_seq(pool_elements(&v), visitor),
+            VariantDispatch::Int32Array(v) => visit_seq(pool_elements(&v), visitor),
+            VariantDispatch::Float32Array(v) => visit_seq(pool_elements(&v), visitor),
+            VariantDispatch::StringArray(v) => visit_seq(pool_elements(&v), visitor),
+            VariantDispatch::Vector2Array(v) => visit_seq(pool_elements(&v), visitor),
+            VariantDispatch::Vector3Array(v) => visit_seq(pool_elements(&v), visitor),
+            VariantDispatch::ColorArray(v) => visit_seq(pool_elements(&v), visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        if self.variant.is_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    #[inline]
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.variant.try_to::<PoolArray<u8>>() {
+            Ok(bytes) => visitor.visit_byte_buf(bytes.to_vec()),
+            Err(_) => self.deserialize_any(visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.variant.dispatch() {
+            VariantDispatch::GodotString(_) | VariantDispatch::I64(_) => {
+                visitor.visit_enum(EnumDeserializer {
+                    key: self.variant,
+                    value: None,
+                })
+            }
+            VariantDispatch::Dictionary(dict) => {
+                let mut entries = dict.iter();
+                match (entries.next(), entries.next()) {
+                    (Some((key, value)), None) => visitor.visit_enum(EnumDeserializer {
+                        key,
+                        value: Some(value),
+                    }),
+                    _ => Err(de::Error::invalid_length(
+                        dict.len() as usize,
+                        &"a Dictionary with a single entry",
+                    )),
+                }
+            }
+            _ => Err(de::Error::invalid_type(
+                unexpected(&self.variant),
+                &"an enum variant",
+            )),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Reads an enum variant, identified by `key`, with optional contents in `value`.
+struct EnumDeserializer {
+    key: Variant,
+    value: Option<Variant>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+    type Error = SerdeError;
+    type Variant = VariantContents;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, VariantContents), SerdeError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        // Derived implementations only accept unsigned variant indices
+        let variant = match self.key.dispatch() {
+            VariantDispatch::I64(index) => {
+                let index = u64::try_from(index).map_err(|_| {
+                    de::Error::invalid_value(Unexpected::Signed(index), &"a variant index")
+                })?;
+                let key: U64Deserializer<SerdeError> = index.into_deserializer();
+                seed.deserialize(key)?
+            }
+            VariantDispatch::GodotString(name) => {
+                let key: StringDeserializer<SerdeError> = name.to_string().into_deserializer();
+                seed.deserialize(key)?
+            }
+            _ => {
+                return Err(de::Error::invalid_type(
+                    unexpected(&self.key),
+                    &"a variant name or index",
+                ))
+            }
+        };
+        Ok((variant, VariantContents { value: self.value }))
+    }
+}
+
+/// Contents of an enum variant, which are missing for unit variants.
+struct VariantContents {
+    value: Option<Variant>,
+}
+
+impl VariantContents {
+    fn deserializer(self, expected: &str) -> Result<VariantDeserializer, SerdeError> {
+        match self.value {
+            Some(value) => Ok(VariantDeserializer::new(value)),
+            None => Err(de::Error::invalid_type(Unexpected::UnitVariant, &expected)),
+        }
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantContents {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        match self.value {
+            Some(value) if !value.is_nil() => Err(de::Error::invalid_type(
+                unexpected(&value),
+                &"a unit variant",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, SerdeError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.deserializer("a newtype variant")?)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.deserializer("a tuple variant")?, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self.deserializer("a struct variant")?, visitor)
+    }
+}
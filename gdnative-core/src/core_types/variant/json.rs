@@ -0,0 +1,919 @@
+//! JSON reading and writing compatible with Godot's `JSON` singleton.
+//!
+//! Unlike `JSON.parse` and `JSON.print`, the functions in this module do not go through the
+//! scripting API, so they can be used from any thread, and in tests using the `mock-api`
+//! feature.
+//!
+//! Values are mapped like in the engine: JSON objects become [`Dictionary`]s, JSON arrays
+//! become [`VariantArray`]s, and so on. The only difference is in the handling of numbers:
+//!
+//! - The engine parses all numbers as floats. Here, numbers without a fraction or exponent that
+//!   fit into an `i64` are parsed as integers, and all other numbers as floats.
+//! - The engine prints whole floats like integers. Here, floats are always printed with a
+//!   decimal point or exponent, so that they are read back as floats.
+//!
+//! With the `serde_json` feature, `serde_json::Value` implements [`ToVariant`] and
+//! [`FromVariant`] with the same mapping.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use gdnative::core_types::json;
+//! use gdnative::core_types::Dictionary;
+//!
+//! let variant = json::parse(r#"{ "name": "Player", "level": 3, "speed": 1.5 }"#).unwrap();
+//! let dict = variant.to::<Dictionary>().unwrap();
+//! assert_eq!(Some(3), dict.get("level").and_then(|v| v.to::<i64>()));
+//!
+//! assert_eq!(
+//!     r#"{"name":"Player","level":3,"speed":1.5}"#,
+//!     json::to_string(&variant).unwrap(),
+//! );
+//! ```
+
+use std::fmt::{self, Write};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::core_types::*;
+
+use super::marshal::MAX_DEPTH;
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Printing
+
+/// Prints a value as compact JSON, like `JSON.print(value)`.
+///
+/// See [`PrintOptions`] for details on how values are printed.
+///
+/// # Errors
+///
+/// See [`PrintOptions::print`].
+#[inline]
+pub fn to_string(variant: &Variant) -> Result<String, PrintError> {
+    PrintOptions::new().print(variant)
+}
+
+/// Prints a value as JSON with each element on its own line, indented with `indent`, like
+/// `JSON.print(value, indent)`.
+///
+/// # Errors
+///
+/// See [`PrintOptions::print`].
+#[inline]
+pub fn to_string_pretty(variant: &Variant, indent: &str) -> Result<String, PrintError> {
+    PrintOptions::new().indent(indent).print(variant)
+}
+
+/// Options for printing JSON, corresponding to the arguments of `JSON.print`.
+///
+/// Values are printed like in the engine:
+///
+/// - Dictionary keys are converted to strings.
+/// - `PoolIntArray`, `PoolRealArray` and `PoolStringArray` are printed as arrays.
+/// - Other values without a JSON equivalent, like vectors or objects, are printed as the
+///   string returned by `str()`.
+#[derive(Clone, Debug, Default)]
+pub struct PrintOptions {
+    indent: String,
+    sort_keys: bool,
+}
+
+impl PrintOptions {
+    /// Returns options for compact output with dictionary keys in insertion order.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Puts each element on its own line, indented with `indent` once per level of nesting.
+    /// Compact output is used if `indent` is empty.
+    #[inline]
+    pub fn indent(mut self, indent: &str) -> Self {
+        self.indent = indent.to_owned();
+        self
+    }
+
+    /// Sets whether dictionary keys are sorted, using the ordering of [`Variant`].
+    #[inline]
+    pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
+    /// Prints `variant` as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value contains floats that are infinite or NaN, which cannot be
+    /// represented in JSON, or if it is nested deeper than
+    /// [`MAX_DEPTH`](super::marshal::MAX_DEPTH), which is usually caused by a collection
+    /// containing itself.
+    #[inline]
+    pub fn print(&self, variant: &Variant) -> Result<String, PrintError> {
+        let mut out = String::new();
+        self.write_value(&mut out, variant, 0)?;
+        Ok(out)
+    }
+
+    fn write_value(
+        &self,
+        out: &mut String,
+        variant: &Variant,
+        depth: usize,
+    ) -> Result<(), PrintError> {
+        if depth >= MAX_DEPTH {
+            return Err(PrintError::TooDeep);
+        }
+
+        match variant.dispatch() {
+            VariantDispatch::Nil => out.push_str("null"),
+            VariantDispatch::Bool(b) => out.push_str(if b { "true" } else { "false" }),
+            VariantDispatch::I64(i) => write!(out, "{i}").unwrap(),
+            VariantDispatch::F64(f) => write_float(out, f)?,
+            VariantDispatch::GodotString(s) => write_string(out, &s.to_string()),
+            VariantDispatch::VariantArray(arr) => {
+                self.write_array(out, arr.iter(), depth)?;
+            }
+            VariantDispatch::Int32Array(arr) => {
+                self.write_array(out, arr.read().iter().map(ToVariant::to_variant), depth)?;
+            }
+            VariantDispatch::Float32Array(arr) => {
+                self.write_array(out, arr.read().iter().map(ToVariant::to_variant), depth)?;
+            }
+            VariantDispatch::StringArray(arr) => {
+                self.write_array(out, arr.read().iter().map(ToVariant::to_variant), depth)?;
+            }
+            VariantDispatch::Dictionary(dict) => {
+                let mut entries: Vec<(Variant, Variant)> = dict.iter().collect();
+                if self.sort_keys {
+                    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                }
+
+                out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    self.write_newline(out, depth + 1);
+                    write_string(out, &key_to_string(key));
+                    out.push(':');
+                    if !self.indent.is_empty() {
+                        out.push(' ');
+                    }
+                    self.write_value(out, value, depth + 1)?;
+                }
+                if !entries.is_empty() {
+                    self.write_newline(out, depth);
+                }
+                out.push('}');
+            }
+            _ => write_string(out, &variant.to_string()),
+        }
+
+        Ok(())
+    }
+
+    fn write_array(
+        &self,
+        out: &mut String,
+        elements: impl Iterator<Item = Variant>,
+        depth: usize,
+    ) -> Result<(), PrintError> {
+        out.push('[');
+        let mut empty = true;
+        for element in elements {
+            if !empty {
+                out.push(',');
+            }
+            empty = false;
+            self.write_newline(out, depth + 1);
+            self.write_value(out, &element, depth + 1)?;
+        }
+        if !empty {
+            self.write_newline(out, depth);
+        }
+        out.push(']');
+        Ok(())
+    }
+
+    fn write_newline(&self, out: &mut String, depth: usize) {
+        if !self.indent.is_empty() {
+            out.push('\n');
+            for _ in 0..depth {
+                out.push_str(&self.indent);
+            }
+        }
+    }
+}
+
+/// Converts a dictionary key to the string used as its name in JSON objects.
+fn key_to_string(key: &Variant) -> String {
+    match key.dispatch() {
+        VariantDispatch::GodotString(s) => s.to_string(),
+        _ => key.to_string(),
+    }
+}
+
+fn write_float(out: &mut String, f: f64) -> Result<(), PrintError> {
+    if !f.is_finite() {
+        return Err(PrintError::NonFiniteNumber(f));
+    }
+
+    // `Debug` prints the shortest representation that round-trips, and always includes a
+    // decimal point or exponent.
+    write!(out, "{f:?}").unwrap();
+    Ok(())
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\x08' => out.push_str("\\b"),
+            '\x0c' => out.push_str("\\f"),
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Error returned when printing a value as JSON.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PrintError {
+    /// A float that is infinite or NaN.
+    NonFiniteNumber(f64),
+    /// The value is nested deeper than [`MAX_DEPTH`](super::marshal::MAX_DEPTH).
+    TooDeep,
+}
+
+impl fmt::Display for PrintError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrintError::NonFiniteNumber(n) => {
+                write!(f, "{n} cannot be represented in JSON")
+            }
+            PrintError::TooDeep => write!(f, "value is nested deeper than {MAX_DEPTH} levels"),
+        }
+    }
+}
+
+impl std::error::Error for PrintError {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Parsing
+
+/// Parses a JSON document, like `JSON.parse`.
+///
+/// The input must follow [RFC 8259](https://www.rfc-editor.org/rfc/rfc8259): comments and
+/// trailing commas are rejected. Duplicate keys in objects are allowed, with the last value
+/// taking precedence. See the [module documentation](self) for how numbers are parsed.
+///
+/// # Errors
+///
+/// Returns an error with the line and column of the first invalid character if the input is
+/// not valid JSON, or if it is nested deeper than [`MAX_DEPTH`](super::marshal::MAX_DEPTH).
+#[inline]
+pub fn parse(json: &str) -> Result<Variant, ParseError> {
+    let mut parser = Parser {
+        chars: json.chars().peekable(),
+        line: 1,
+        column: 1,
+    };
+
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(value),
+        Some(_) => Err(parser.error(ParseErrorKind::TrailingCharacters)),
+    }
+}
+
+/// Error returned when parsing JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// Returns the line of the error, starting at 1.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column of the error in characters, starting at 1.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the kind of the error.
+    #[inline]
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Kinds of [`ParseError`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// A character other than the expected one.
+    UnexpectedCharacter { expected: &'static str, found: char },
+    /// The input ended before the value was complete.
+    UnexpectedEnd { expected: &'static str },
+    /// An invalid escape sequence in a string.
+    InvalidEscape,
+    /// A number that does not follow the JSON syntax, or is too large to be represented.
+    InvalidNumber(String),
+    /// The value is nested deeper than [`MAX_DEPTH`](super::marshal::MAX_DEPTH).
+    TooDeep,
+    /// Additional characters after a complete value.
+    TrailingCharacters,
+}
+
+impl fmt::Display for ParseErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ParseErrorKind as E;
+
+        match self {
+            E::UnexpectedCharacter { expected, found } => {
+                write!(f, "expected {expected}, found {found:?}")
+            }
+            E::UnexpectedEnd { expected } => write!(f, "expected {expected}, found end of input"),
+            E::InvalidEscape => write!(f, "invalid escape sequence"),
+            E::InvalidNumber(num) => write!(f, "invalid number `{num}`"),
+            E::TooDeep => write!(f, "value is nested deeper than {MAX_DEPTH} levels"),
+            E::TrailingCharacters => write!(f, "unexpected characters after value"),
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+
+    /// Returns an error for the next character, or the end of input.
+    fn unexpected(&mut self, expected: &'static str) -> ParseError {
+        match self.peek() {
+            Some(found) => self.error(ParseErrorKind::UnexpectedCharacter { expected, found }),
+            None => self.error(ParseErrorKind::UnexpectedEnd { expected }),
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.bump();
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Variant, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(depth),
+            Some('[') => self.array(depth),
+            Some('"') => Ok(self.string()?.owned_to_variant()),
+            Some('-' | '0'..='9') => self.number(),
+            Some('a'..='z') => self.keyword(),
+            _ => Err(self.unexpected("value")),
+        }
+    }
+
+    fn keyword(&mut self) -> Result<Variant, ParseError> {
+        for (keyword, value) in [
+            ("null", Variant::nil()),
+            ("true", true.to_variant()),
+            ("false", false.to_variant()),
+        ] {
+            if self.peek() == keyword.chars().next() {
+                for expected in keyword.chars() {
+                    if !self.eat(expected) {
+                        return Err(self.unexpected(keyword));
+                    }
+                }
+                return Ok(value);
+            }
+        }
+        Err(self.unexpected("value"))
+    }
+
+    fn enter(&self, depth: usize) -> Result<usize, ParseError> {
+        if depth + 1 >= MAX_DEPTH {
+            Err(self.error(ParseErrorKind::TooDeep))
+        } else {
+            Ok(depth + 1)
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Variant, ParseError> {
+        let depth = self.enter(depth)?;
+        self.bump();
+
+        let arr = VariantArray::new();
+        self.skip_whitespace();
+        if !self.eat(']') {
+            loop {
+                arr.push(self.value(depth)?);
+                self.skip_whitespace();
+                if self.eat(']') {
+                    break;
+                }
+                if !self.eat(',') {
+                    return Err(self.unexpected("',' or ']'"));
+                }
+            }
+        }
+
+        Ok(arr.owned_to_variant())
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Variant, ParseError> {
+        let depth = self.enter(depth)?;
+        self.bump();
+
+        let dict = Dictionary::new();
+        self.skip_whitespace();
+        if !self.eat('}') {
+            loop {
+                self.skip_whitespace();
+                if self.peek() != Some('"') {
+                    return Err(self.unexpected("string key"));
+                }
+                let key = self.string()?;
+
+                self.skip_whitespace();
+                if !self.eat(':') {
+                    return Err(self.unexpected("':'"));
+                }
+                let value = self.value(depth)?;
+                dict.insert(key, value);
+
+                self.skip_whitespace();
+                if self.eat('}') {
+                    break;
+                }
+                if !self.eat(',') {
+                    return Err(self.unexpected("',' or '}'"));
+                }
+            }
+        }
+
+        Ok(dict.owned_to_variant())
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.bump();
+
+        let mut s = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.bump();
+                    return Ok(s);
+                }
+                Some('\\') => {
+                    let escape = self.error(ParseErrorKind::InvalidEscape);
+                    self.bump();
+                    let c = match self.bump() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\x08',
+                        Some('f') => '\x0c',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.unicode_escape().ok_or(escape)?,
+                        _ => return Err(escape),
+                    };
+                    s.push(c);
+                }
+                Some(c) if c >= ' ' => {
+                    self.bump();
+                    s.push(c);
+                }
+                _ => return Err(self.unexpected("'\"'")),
+            }
+        }
+    }
+
+    /// Reads the hex digits of a `\u` escape, including a following low surrogate if needed.
+    fn unicode_escape(&mut self) -> Option<char> {
+        let high = self.hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high);
+        }
+
+        if !(self.eat('\\') && self.eat('u')) {
+            return None;
+        }
+        let low = self.hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return None;
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let mut value = 0;
+        for _ in 0..4 {
+            let digit = self.peek()?.to_digit(16)?;
+            self.bump();
+            value = value * 16 + digit;
+        }
+        Some(value)
+    }
+
+    fn number(&mut self) -> Result<Variant, ParseError> {
+        let start = self.error(ParseErrorKind::InvalidNumber(String::new()));
+
+        let mut text = String::new();
+        while let Some(c @ ('0'..='9' | '-' | '+' | '.' | 'e' | 'E')) = self.peek() {
+            self.bump();
+            text.push(c);
+        }
+
+        let invalid = || ParseError {
+            kind: ParseErrorKind::InvalidNumber(text.clone()),
+            ..start.clone()
+        };
+
+        let is_integer = match json_number_kind(&text) {
+            Some(is_integer) => is_integer,
+            None => return Err(invalid()),
+        };
+
+        if is_integer {
+            if let Ok(i) = text.parse::<i64>() {
+                return Ok(i.to_variant());
+            }
+        }
+
+        match text.parse::<f64>() {
+            Ok(f) if f.is_finite() => Ok(f.to_variant()),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Checks `text` against the JSON number grammar. Returns whether the number is an integer, or
+/// `None` if it is invalid.
+fn json_number_kind(text: &str) -> Option<bool> {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    let int_len = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    let (int, rest) = digits.split_at(int_len);
+    if int.is_empty() || (int.len() > 1 && int.starts_with('0')) {
+        return None;
+    }
+
+    let rest = match rest.strip_prefix('.') {
+        Some(fraction) => {
+            let len = fraction
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(fraction.len());
+            if len == 0 {
+                return None;
+            }
+            &fraction[len..]
+        }
+        None => rest,
+    };
+
+    let exponent = match rest.strip_prefix(|c| c == 'e' || c == 'E') {
+        Some(exponent) => {
+            let exponent = exponent
+                .strip_prefix(|c| c == '+' || c == '-')
+                .unwrap_or(exponent);
+            if exponent.is_empty() || !exponent.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            true
+        }
+        None if rest.is_empty() => false,
+        None => return None,
+    };
+
+    Some(int_len == digits.len() && !exponent)
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// serde_json
+
+#[cfg(feature = "serde_json")]
+mod bridge {
+    use super::*;
+    use crate::object::ownership::Unique;
+
+    /// Converts a JSON value into a `Variant`, with the same mapping as [`parse`].
+    ///
+    /// Unsigned integers that don't fit into an `i64` are converted to floats.
+    impl ToVariant for serde_json::Value {
+        #[inline]
+        fn to_variant(&self) -> Variant {
+            use serde_json::Value as J;
+
+            match self {
+                J::Null => Variant::nil(),
+                J::Bool(b) => b.to_variant(),
+                J::Number(n) => match n.as_i64() {
+                    Some(i) => i.to_variant(),
+                    None => n.as_f64().unwrap_or(f64::NAN).to_variant(),
+                },
+                J::String(s) => s.to_variant(),
+                J::Array(elements) => elements
+                    .iter()
+                    .map(ToVariant::to_variant)
+                    .collect::<VariantArray<Unique>>()
+                    .owned_to_variant(),
+                J::Object(entries) => entries
+                    .iter()
+                    .map(|(key, value)| (key.to_variant(), value.to_variant()))
+                    .collect::<Dictionary<Unique>>()
+                    .owned_to_variant(),
+            }
+        }
+    }
+
+    /// Converts a `Variant` into a JSON value, with the same mapping as [`to_string`].
+    ///
+    /// Conversion fails if the value contains floats that are infinite or NaN, or if it is
+    /// nested too deeply.
+    impl FromVariant for serde_json::Value {
+        #[inline]
+        fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+            to_json_value(variant, 0).map_err(|err| FromVariantError::Custom(err.to_string()))
+        }
+    }
+
+    fn to_json_value(variant: &Variant, depth: usize) -> Result<serde_json::Value, PrintError> {
+        use serde_json::Value as J;
+
+        if depth >= MAX_DEPTH {
+            return Err(PrintError::TooDeep);
+        }
+
+        let array = |elements: Vec<Variant>| {
+            elements
+                .iter()
+                .map(|element| to_json_value(element, depth + 1))
+                .collect::<Result<_, _>>()
+                .map(J::Array)
+        };
+
+        let value = match variant.dispatch() {
+            VariantDispatch::Nil => J::Null,
+            VariantDispatch::Bool(b) => J::Bool(b),
+            VariantDispatch::I64(i) => J::from(i),
+            VariantDispatch::F64(f) => serde_json::Number::from_f64(f)
+                .map(J::Number)
+                .ok_or(PrintError::NonFiniteNumber(f))?,
+            VariantDispatch::GodotString(s) => J::String(s.to_string()),
+            VariantDispatch::VariantArray(arr) => array(arr.iter().collect())?,
+            VariantDispatch::Int32Array(arr) => array(variants(&arr))?,
+            VariantDispatch::Float32Array(arr) => array(variants(&arr))?,
+            VariantDispatch::StringArray(arr) => array(variants(&arr))?,
+            VariantDispatch::Dictionary(dict) => J::Object(
+                dict.iter()
+                    .map(|(key, value)| {
+                        Ok((key_to_string(&key), to_json_value(&value, depth + 1)?))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => J::String(variant.to_string()),
+        };
+
+        Ok(value)
+    }
+
+    fn variants<T: PoolElement + ToVariant>(arr: &PoolArray<T>) -> Vec<Variant> {
+        arr.read().iter().map(ToVariant::to_variant).collect()
+    }
+}
+
+godot_test!(
+    test_json_round_trip {
+        let json = r#"{"name":"Player","level":3,"speed":1.5,"items":[null,true,"a\"b"],"empty":{}}"#;
+        let variant = parse(json).unwrap();
+
+        let dict = variant.to::<Dictionary>().unwrap();
+        assert_eq!(Some(VariantType::I64), dict.get("level").map(|v| v.get_type()));
+        assert_eq!(Some(VariantType::F64), dict.get("speed").map(|v| v.get_type()));
+        assert_eq!(Ok(json.to_string()), to_string(&variant));
+
+        let pretty = to_string_pretty(&variant, "  ").unwrap();
+        assert_eq!(Ok(json.to_string()), to_string(&parse(&pretty).unwrap()));
+        assert!(pretty.starts_with("{\n  \"name\": \"Player\",\n"));
+
+        let sorted = PrintOptions::new().sort_keys(true).print(&variant).unwrap();
+        assert!(sorted.starts_with(r#"{"empty":{},"items""#));
+
+        assert_eq!("2.0", to_string(&2.0.to_variant()).unwrap());
+        assert_eq!(Err(PrintError::NonFiniteNumber(f64::INFINITY)), to_string(&f64::INFINITY.to_variant()));
+        assert_eq!("\"(1, 2)\"", to_string(&Vector2::new(1.0, 2.0).to_variant()).unwrap());
+    }
+);
+
+godot_test!(
+    test_json_parse_errors {
+        let err = parse("{\n  \"a\": [1, 2,]\n}").unwrap_err();
+        assert_eq!((2, 14), (err.line(), err.column()));
+        assert_eq!(&ParseErrorKind::UnexpectedCharacter { expected: "value", found: ']' }, err.kind());
+
+        let err = parse("[01]").unwrap_err();
+        assert_eq!(&ParseErrorKind::InvalidNumber("01".into()), err.kind());
+        assert_eq!(2, err.column());
+
+        let err = parse("\"\\x\"").unwrap_err();
+        assert_eq!(&ParseErrorKind::InvalidEscape, err.kind());
+
+        let err = parse("[1] 2").unwrap_err();
+        assert_eq!(&ParseErrorKind::TrailingCharacters, err.kind());
+
+        let err = parse("nul").unwrap_err();
+        assert_eq!(&ParseErrorKind::UnexpectedEnd { expected: "null" }, err.kind());
+
+        assert_eq!(Ok("\u{1F600}".to_variant()), parse(r#""\ud83d\ude00""#));
+        assert_eq!(Ok(1e20.to_variant()), parse("100000000000000000000"));
+        assert_eq!(Ok((-0.5).to_variant()), parse("-5e-1"));
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parser(json: &str) -> Parser<'_> {
+        Parser {
+            chars: json.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn parse_string(json: &str) -> Result<String, ParseError> {
+        let mut parser = parser(json);
+        parser.skip_whitespace();
+        parser.string()
+    }
+
+    fn printed_string(s: &str) -> String {
+        let mut out = String::new();
+        write_string(&mut out, s);
+        out
+    }
+
+    fn printed_float(f: f64) -> Result<String, PrintError> {
+        let mut out = String::new();
+        write_float(&mut out, f).map(|()| out)
+    }
+
+    #[test]
+    fn number_grammar() {
+        for integer in ["0", "-0", "7", "-12", "9223372036854775808"] {
+            assert_eq!(Some(true), json_number_kind(integer), "{integer}");
+        }
+        for float in ["0.5", "-1.25", "1e5", "1E+5", "-2.5e-3", "0e0"] {
+            assert_eq!(Some(false), json_number_kind(float), "{float}");
+        }
+        for invalid in [
+            "", "-", "01", "-01", "+1", ".5", "1.", "1.e5", "1e", "1e+", "1e5.0", "1-2", "--1",
+        ] {
+            assert_eq!(None, json_number_kind(invalid), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn parse_strings() {
+        assert_eq!(Ok(String::new()), parse_string(r#""""#));
+        assert_eq!(
+            Ok("a\"b\\c/\x08\x0c\n\r\t".to_string()),
+            parse_string(r#""a\"b\\c\/\b\f\n\r\t""#)
+        );
+        assert_eq!(Ok("é\u{1F600}".to_string()), parse_string(r#""é😀""#));
+    }
+
+    #[test]
+    fn parse_string_errors() {
+        let err = parse_string("  \n  \"ab\\q\"").unwrap_err();
+        assert_eq!((2, 6), (err.line(), err.column()));
+        assert_eq!(&ParseErrorKind::InvalidEscape, err.kind());
+
+        let lone_surrogate = parse_string(r#""\ud83d""#).unwrap_err();
+        assert_eq!(&ParseErrorKind::InvalidEscape, lone_surrogate.kind());
+        assert_eq!(2, lone_surrogate.column());
+
+        let short_escape = parse_string(r#""\u12""#).unwrap_err();
+        assert_eq!(&ParseErrorKind::InvalidEscape, short_escape.kind());
+
+        let control = parse_string("\"a\tb\"").unwrap_err();
+        assert_eq!(
+            &ParseErrorKind::UnexpectedCharacter {
+                expected: "'\"'",
+                found: '\t'
+            },
+            control.kind()
+        );
+        assert_eq!(3, control.column());
+
+        let unterminated = parse_string("\"abc").unwrap_err();
+        assert_eq!(
+            &ParseErrorKind::UnexpectedEnd { expected: "'\"'" },
+            unterminated.kind()
+        );
+        assert_eq!(
+            "line 1, column 5: expected '\"', found end of input",
+            unterminated.to_string()
+        );
+    }
+
+    #[test]
+    fn print_strings() {
+        assert_eq!(r#""""#, printed_string(""));
+        assert_eq!(
+            r#""a\"b\\c\n\r\t\b\f\u0001é/""#,
+            printed_string("a\"b\\c\n\r\t\x08\x0c\x01é/")
+        );
+
+        let s = "tab\t \"quote\" \u{1F600} \x1f";
+        assert_eq!(Ok(s.to_string()), parse_string(&printed_string(s)));
+    }
+
+    #[test]
+    fn print_floats() {
+        assert_eq!(Ok("2.0".to_string()), printed_float(2.0));
+        assert_eq!(Ok("0.1".to_string()), printed_float(0.1));
+        assert_eq!(Ok("-0.0".to_string()), printed_float(-0.0));
+        assert_eq!(Ok("1e20".to_string()), printed_float(1e20));
+        assert_eq!(Ok("1.5e-7".to_string()), printed_float(1.5e-7));
+        assert_eq!(
+            Err(PrintError::NonFiniteNumber(f64::INFINITY)),
+            printed_float(f64::INFINITY)
+        );
+        assert!(matches!(
+            printed_float(f64::NAN),
+            Err(PrintError::NonFiniteNumber(f)) if f.is_nan()
+        ));
+    }
+}
@@ -0,0 +1,1385 @@
+//! Binary serialization compatible with Godot's `var2bytes` and `bytes2var`.
+//!
+//! The functions in this module implement the format used by Godot 3's `encode_variant` and
+//! `decode_variant` (see `core/io/marshalls.cpp` in the engine source), which is also used
+//! by `PacketPeer.put_var`, `File.store_var` and the high-level multiplayer API. Data produced
+//! by GDScript's `var2bytes` can be read with [`decode`], and data produced by [`encode`] can be
+//! read with `bytes2var`.
+//!
+//! Input is parsed in pure Rust before any engine types are created, so malformed or malicious
+//! input is always reported as a [`DecodeError`] without touching the engine. This also means
+//! that the decoder can be fuzzed without a running Godot instance, as long as the input does
+//! not actually contain engine-allocated types like strings or collections.
+//!
+//! # Objects
+//!
+//! Objects are encoded either as their instance ID (the default, like `var2bytes(value)`), or
+//! as "full objects" containing their class name and all stored properties (like
+//! `var2bytes(value, true)`). Decoding full objects instantiates arbitrary classes and sets
+//! arbitrary properties on them, which may run arbitrary code. As such, it is only available
+//! through the `unsafe` [`decode_with_objects`] function, mirroring the `allow_objects`
+//! parameter of `bytes2var`.
+//!
+//! Objects encoded as IDs are decoded into `EncodedObjectAsID` instances, as in GDScript.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use gdnative::core_types::marshal;
+//! use gdnative::prelude::*;
+//!
+//! let bytes = marshal::encode(&Vector2::new(1.0, 2.0).to_variant().dispatch()).unwrap();
+//! assert_eq!(bytes, [5, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 64]);
+//!
+//! let (value, len) = marshal::decode(&bytes).unwrap();
+//! assert_eq!(len, bytes.len());
+//! assert_eq!(Variant::from(&value), Vector2::new(1.0, 2.0).to_variant());
+//! ```
+
+use std::ffi::CString;
+use std::fmt;
+use std::ptr;
+
+use crate::core_types::*;
+use crate::export::PropertyUsage;
+use crate::object::ownership::Unique;
+use crate::private::get_api;
+
+/// Mask for the type part of a header.
+const HEADER_TYPE_MASK: u32 = 0xFF;
+
+/// Header flag indicating a 64-bit integer or float payload.
+const HEADER_FLAG_64: u32 = 1 << 16;
+
+/// Header flag indicating that an object is encoded as its instance ID.
+const HEADER_FLAG_OBJECT_AS_ID: u32 = 1 << 16;
+
+/// Flag set on the first word of a `NodePath` in the current format.
+const NODE_PATH_NEW_FORMAT: u32 = 0x8000_0000;
+
+/// `NodePath` flag for absolute paths.
+const NODE_PATH_FLAG_ABSOLUTE: u32 = 1;
+
+/// `NodePath` flag for the legacy property name, which is stored as an additional subname.
+const NODE_PATH_FLAG_PROPERTY: u32 = 2;
+
+/// Mask for the element count of arrays and dictionaries. The highest bit was used as a
+/// "shared" flag by older engine versions.
+const COLLECTION_LEN_MASK: u32 = 0x7FFF_FFFF;
+
+/// Maximum nesting depth of collections and objects, same as `Variant::MAX_RECURSION_DEPTH`
+/// in the engine.
+pub const MAX_DEPTH: usize = 1024;
+
+/// Encodes a value in the format of `var2bytes(value)`, with objects encoded as their
+/// instance IDs.
+///
+/// # Errors
+///
+/// Returns an error if the value is nested deeper than [`MAX_DEPTH`], or if a string or a
+/// collection is too long to be represented.
+#[inline]
+pub fn encode(value: &VariantDispatch) -> Result<Vec<u8>, EncodeError> {
+    let mut buf = Vec::new();
+    Encoder {
+        full_objects: false,
+    }
+    .encode(&mut buf, value, 0)?;
+    Ok(buf)
+}
+
+/// Encodes a value in the format of `var2bytes(value, true)`, with objects encoded as their
+/// class names and all their stored properties.
+///
+/// # Errors
+///
+/// Returns an error if the value is nested deeper than [`MAX_DEPTH`], if a string or a
+/// collection is too long to be represented, or if the property list of an object can't be
+/// retrieved.
+///
+/// # Safety
+///
+/// Encoding full objects calls `get_property_list` and `get` on them, which may execute
+/// arbitrary code, including user-defined code in GDScript or unsafe Rust.
+#[inline]
+pub unsafe fn encode_with_objects(value: &VariantDispatch) -> Result<Vec<u8>, EncodeError> {
+    let mut buf = Vec::new();
+    Encoder { full_objects: true }.encode(&mut buf, value, 0)?;
+    Ok(buf)
+}
+
+/// Decodes a value in the format of `bytes2var(bytes)`. Returns the value and the number of
+/// bytes read. Trailing data is ignored, like in Godot.
+///
+/// Objects encoded as instance IDs are decoded into `EncodedObjectAsID` instances. Full
+/// objects are rejected with [`DecodeError::ObjectsNotAllowed`].
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid encoding of a value.
+#[inline]
+pub fn decode(bytes: &[u8]) -> Result<(VariantDispatch, usize), DecodeError> {
+    let mut reader = Reader::new(bytes);
+    let node = reader.read_node(false, 0)?;
+    let len = reader.pos;
+    // SAFETY: Full objects are rejected by the reader when `allow_objects` is false.
+    let value = unsafe { node.into_dispatch()? };
+    Ok((value, len))
+}
+
+/// Decodes a value in the format of `bytes2var(bytes, true)`. Returns the value and the
+/// number of bytes read. Trailing data is ignored, like in Godot.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid encoding of a value, or if an encoded object
+/// has a class that cannot be instantiated.
+///
+/// # Safety
+///
+/// Decoding full objects instantiates arbitrary classes and sets arbitrary properties on
+/// them, which may execute arbitrary code, including user-defined code in GDScript or unsafe
+/// Rust. Non-`Reference` objects created this way are not freed automatically. Do not use
+/// this function on untrusted input.
+#[inline]
+pub unsafe fn decode_with_objects(bytes: &[u8]) -> Result<(VariantDispatch, usize), DecodeError> {
+    let mut reader = Reader::new(bytes);
+    let node = reader.read_node(true, 0)?;
+    let len = reader.pos;
+    let value = node.into_dispatch()?;
+    Ok((value, len))
+}
+
+/// Error returned by [`encode`] and [`encode_with_objects`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EncodeError {
+    /// The value is nested deeper than [`MAX_DEPTH`].
+    TooDeep,
+    /// A string or collection is too long to be represented in the format.
+    TooLong { len: usize },
+    /// An engine call needed to encode an object failed.
+    ObjectCall(CallError),
+}
+
+impl fmt::Display for EncodeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::TooDeep => write!(f, "value is nested deeper than {MAX_DEPTH} levels"),
+            EncodeError::TooLong { len } => {
+                write!(f, "length {len} is too long to be encoded")
+            }
+            EncodeError::ObjectCall(err) => write!(f, "cannot encode object: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Error returned by [`decode`] and [`decode_with_objects`].
+///
+/// Offsets are positions in the input, in bytes, of the part that failed to decode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input ends before the value is complete. `needed` is the number of bytes that were
+    /// expected at `offset`.
+    UnexpectedEof { offset: usize, needed: usize },
+    /// The type part of a header does not correspond to any `VariantType`.
+    InvalidType { offset: usize, type_id: u32 },
+    /// A length prefix is negative, or larger than what the remaining input can hold.
+    InvalidLength { offset: usize, len: u32 },
+    /// A string is not valid UTF-8.
+    InvalidUtf8 { offset: usize },
+    /// A `NodePath` uses the legacy string format, which Godot 3 also rejects.
+    LegacyNodePath { offset: usize },
+    /// The value is nested deeper than [`MAX_DEPTH`].
+    TooDeep { offset: usize },
+    /// The input contains a full object, which is only allowed by [`decode_with_objects`].
+    ObjectsNotAllowed { offset: usize },
+    /// The class of an encoded object cannot be instantiated.
+    UnknownClass { class: String },
+}
+
+impl fmt::Display for DecodeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DecodeError as E;
+
+        match self {
+            E::UnexpectedEof { offset, needed } => {
+                write!(
+                    f,
+                    "unexpected end of input at {offset}, expected {needed} more bytes"
+                )
+            }
+            E::InvalidType { offset, type_id } => {
+                write!(f, "invalid variant type {type_id} at {offset}")
+            }
+            E::InvalidLength { offset, len } => write!(f, "invalid length {len} at {offset}"),
+            E::InvalidUtf8 { offset } => write!(f, "invalid UTF-8 in string at {offset}"),
+            E::LegacyNodePath { offset } => {
+                write!(f, "unsupported legacy NodePath format at {offset}")
+            }
+            E::TooDeep { offset } => {
+                write!(
+                    f,
+                    "value at {offset} is nested deeper than {MAX_DEPTH} levels"
+                )
+            }
+            E::ObjectsNotAllowed { offset } => {
+                write!(f, "encoded object at {offset}, but objects are not allowed")
+            }
+            E::UnknownClass { class } => write!(f, "cannot instantiate class {class}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Encoding
+
+struct Encoder {
+    full_objects: bool,
+}
+
+impl Encoder {
+    fn encode(
+        &self,
+        buf: &mut Vec<u8>,
+        value: &VariantDispatch,
+        depth: usize,
+    ) -> Result<(), EncodeError> {
+        use VariantDispatch as V;
+
+        if depth > MAX_DEPTH {
+            return Err(EncodeError::TooDeep);
+        }
+
+        let header = |ty: VariantType| ty as u32;
+
+        match value {
+            V::Nil => put_u32(buf, header(VariantType::Nil)),
+            V::Bool(b) => {
+                put_u32(buf, header(VariantType::Bool));
+                put_u32(buf, *b as u32);
+            }
+            V::I64(i) => match i32::try_from(*i) {
+                Ok(i) => {
+                    put_u32(buf, header(VariantType::I64));
+                    put_u32(buf, i as u32);
+                }
+                Err(_) => {
+                    put_u32(buf, header(VariantType::I64) | HEADER_FLAG_64);
+                    buf.extend_from_slice(&i.to_le_bytes());
+                }
+            },
+            V::F64(f) => {
+                // Same check as the engine, which is also true for NaN
+                #[allow(clippy::float_cmp)]
+                if f64::from(*f as f32) != *f {
+                    put_u32(buf, header(VariantType::F64) | HEADER_FLAG_64);
+                    buf.extend_from_slice(&f.to_le_bytes());
+                } else {
+                    put_u32(buf, header(VariantType::F64));
+                    put_f32(buf, *f as f32);
+                }
+            }
+            V::GodotString(s) => {
+                put_u32(buf, header(VariantType::GodotString));
+                put_string(buf, s.to_utf8().as_bytes())?;
+            }
+            V::Vector2(v) => {
+                put_u32(buf, header(VariantType::Vector2));
+                put_vector2(buf, v);
+            }
+            V::Rect2(r) => {
+                put_u32(buf, header(VariantType::Rect2));
+                put_vector2(buf, &r.position);
+                put_vector2(buf, &r.size);
+            }
+            V::Vector3(v) => {
+                put_u32(buf, header(VariantType::Vector3));
+                put_vector3(buf, v);
+            }
+            V::Transform2D(t) => {
+                put_u32(buf, header(VariantType::Transform2D));
+                put_vector2(buf, &t.a);
+                put_vector2(buf, &t.b);
+                put_vector2(buf, &t.origin);
+            }
+            V::Plane(p) => {
+                put_u32(buf, header(VariantType::Plane));
+                put_vector3(buf, &p.normal);
+                put_real(buf, p.d);
+            }
+            V::Quat(q) => {
+                put_u32(buf, header(VariantType::Quat));
+                for c in [q.x, q.y, q.z, q.w] {
+                    put_real(buf, c);
+                }
+            }
+            V::Aabb(a) => {
+                put_u32(buf, header(VariantType::Aabb));
+                put_vector3(buf, &a.position);
+                put_vector3(buf, &a.size);
+            }
+            V::Basis(b) => {
+                put_u32(buf, header(VariantType::Basis));
+                for row in &b.elements {
+                    put_vector3(buf, row);
+                }
+            }
+            V::Transform(t) => {
+                put_u32(buf, header(VariantType::Transform));
+                for row in &t.basis.elements {
+                    put_vector3(buf, row);
+                }
+                put_vector3(buf, &t.origin);
+            }
+            V::Color(c) => {
+                put_u32(buf, header(VariantType::Color));
+                put_color(buf, c);
+            }
+            V::NodePath(path) => {
+                put_u32(buf, header(VariantType::NodePath));
+
+                let name_count = path.name_count();
+                let subname_count = path.get_subname_count();
+                let flags = if path.is_absolute() {
+                    NODE_PATH_FLAG_ABSOLUTE
+                } else {
+                    0
+                };
+
+                put_u32(buf, name_count as u32 | NODE_PATH_NEW_FORMAT);
+                put_u32(buf, subname_count as u32);
+                put_u32(buf, flags);
+
+                for i in 0..name_count {
+                    put_string(buf, path.get_name(i).to_utf8().as_bytes())?;
+                }
+                for i in 0..subname_count {
+                    put_string(buf, path.get_subname(i).to_utf8().as_bytes())?;
+                }
+            }
+            V::Rid(_) => {
+                // RIDs are meaningless outside of the process, so the engine doesn't encode them
+                put_u32(buf, header(VariantType::Rid));
+            }
+            V::Object(variant) => self.encode_object(buf, variant, depth)?,
+            V::Dictionary(dict) => {
+                put_u32(buf, header(VariantType::Dictionary));
+                put_len(buf, dict.len() as usize)?;
+                for (key, value) in dict.iter() {
+                    self.encode(buf, &key.dispatch(), depth + 1)?;
+                    self.encode(buf, &value.dispatch(), depth + 1)?;
+                }
+            }
+            V::VariantArray(arr) => {
+                put_u32(buf, header(VariantType::VariantArray));
+                put_len(buf, arr.len() as usize)?;
+                for value in arr.iter() {
+                    self.encode(buf, &value.dispatch(), depth + 1)?;
+                }
+            }
+            V::ByteArray(arr) => {
+                put_u32(buf, header(VariantType::ByteArray));
+                let read = arr.read();
+                put_len(buf, read.len())?;
+                buf.extend_from_slice(&read);
+                pad(buf);
+            }
+            V::Int32Array(arr) => {
+                put_u32(buf, header(VariantType::Int32Array));
+                let read = arr.read();
+                put_len(buf, read.len())?;
+                for &i in read.iter() {
+                    put_u32(buf, i as u32);
+                }
+            }
+            V::Float32Array(arr) => {
+                put_u32(buf, header(VariantType::Float32Array));
+                let read = arr.read();
+                put_len(buf, read.len())?;
+                for &f in read.iter() {
+                    put_real(buf, f);
+                }
+            }
+            V::StringArray(arr) => {
+                put_u32(buf, header(VariantType::StringArray));
+                let read = arr.read();
+                put_len(buf, read.len())?;
+                for s in read.iter() {
+                    // Unlike other strings, pool string array elements include the NUL terminator
+                    let utf8 = s.to_utf8();
+                    put_len(buf, utf8.as_bytes().len() + 1)?;
+                    buf.extend_from_slice(utf8.as_bytes());
+                    buf.push(0);
+                    pad(buf);
+                }
+            }
+            V::Vector2Array(arr) => {
+                put_u32(buf, header(VariantType::Vector2Array));
+                let read = arr.read();
+                put_len(buf, read.len())?;
+                for v in read.iter() {
+                    put_vector2(buf, v);
+                }
+            }
+            V::Vector3Array(arr) => {
+                put_u32(buf, header(VariantType::Vector3Array));
+                let read = arr.read();
+                put_len(buf, read.len())?;
+                for v in read.iter() {
+                    put_vector3(buf, v);
+                }
+            }
+            V::ColorArray(arr) => {
+                put_u32(buf, header(VariantType::ColorArray));
+                let read = arr.read();
+                put_len(buf, read.len())?;
+                for c in read.iter() {
+                    put_color(buf, c);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encode_object(
+        &self,
+        buf: &mut Vec<u8>,
+        variant: &Variant,
+        depth: usize,
+    ) -> Result<(), EncodeError> {
+        let obj = unsafe { (get_api().godot_variant_as_object)(variant.sys()) };
+        let obj = ptr::NonNull::new(obj).map(|obj| unsafe {
+            crate::object::RawObject::<crate::private::ManuallyManagedClassPlaceholder>::from_sys_ref_unchecked(obj)
+        });
+
+        if !self.full_objects {
+            put_u32(buf, VariantType::Object as u32 | HEADER_FLAG_OBJECT_AS_ID);
+            let id = obj.map_or(0, |obj| obj.instance_id());
+            buf.extend_from_slice(&(id as u64).to_le_bytes());
+            return Ok(());
+        }
+
+        put_u32(buf, VariantType::Object as u32);
+
+        let obj = match obj {
+            Some(obj) => obj,
+            None => {
+                // Null objects are encoded with an empty class name
+                put_u32(buf, 0);
+                return Ok(());
+            }
+        };
+
+        put_string(buf, obj.class_name().as_bytes())?;
+
+        // SAFETY: Caller of `encode_with_objects` accepts that arbitrary code may be executed.
+        let mut variant = variant.clone();
+        let property_list = unsafe { variant.call(string_name!("get_property_list"), &[]) }
+            .map_err(EncodeError::ObjectCall)?;
+        let property_list = VariantArray::from_variant(&property_list).unwrap_or_default();
+
+        let mut properties = Vec::new();
+        for info in property_list.iter() {
+            let info = match Dictionary::from_variant(&info) {
+                Ok(info) => info,
+                Err(_) => continue,
+            };
+            let usage = info.get_or_nil("usage").coerce_to::<i64>() as u32;
+            if usage & PropertyUsage::STORAGE.bits() == 0 {
+                continue;
+            }
+            properties.push(info.get_or_nil("name"));
+        }
+
+        put_len(buf, properties.len())?;
+        for name in properties {
+            let value = unsafe { variant.call(string_name!("get"), std::slice::from_ref(&name)) }
+                .map_err(EncodeError::ObjectCall)?;
+            put_string(buf, name.coerce_to::<GodotString>().to_utf8().as_bytes())?;
+            self.encode(buf, &value.dispatch(), depth + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_f32(buf: &mut Vec<u8>, value: f32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Writes a `real` as 32 bits, which is how the engine encodes them even with `float=64`.
+fn put_real(buf: &mut Vec<u8>, value: real) {
+    put_f32(buf, value.as_f32());
+}
+
+fn put_len(buf: &mut Vec<u8>, len: usize) -> Result<(), EncodeError> {
+    // The engine reads lengths as signed 32-bit integers
+    let len32 = i32::try_from(len).map_err(|_| EncodeError::TooLong { len })?;
+    put_u32(buf, len32 as u32);
+    Ok(())
+}
+
+fn put_string(buf: &mut Vec<u8>, utf8: &[u8]) -> Result<(), EncodeError> {
+    put_len(buf, utf8.len())?;
+    buf.extend_from_slice(utf8);
+    pad(buf);
+    Ok(())
+}
+
+/// Pads the buffer with zeroes to a multiple of 4 bytes.
+fn pad(buf: &mut Vec<u8>) {
+    while buf.len() % 4 != 0 {
+        buf.push(0);
+    }
+}
+
+fn put_vector2(buf: &mut Vec<u8>, v: &Vector2) {
+    put_real(buf, v.x);
+    put_real(buf, v.y);
+}
+
+fn put_vector3(buf: &mut Vec<u8>, v: &Vector3) {
+    put_real(buf, v.x);
+    put_real(buf, v.y);
+    put_real(buf, v.z);
+}
+
+fn put_color(buf: &mut Vec<u8>, c: &Color) {
+    for c in [c.r, c.g, c.b, c.a] {
+        put_f32(buf, c);
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Decoding
+
+/// Pure-Rust representation of decoded data, before any engine types are created.
+enum Parsed {
+    Nil,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    String(String),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    NodePath(String),
+    Rid,
+    NullObject,
+    ObjectId(u64),
+    Object {
+        class: String,
+        properties: Vec<(String, Parsed)>,
+    },
+    Dictionary(Vec<(Parsed, Parsed)>),
+    Array(Vec<Parsed>),
+    ByteArray(Vec<u8>),
+    Int32Array(Vec<i32>),
+    Float32Array(Vec<real>),
+    StringArray(Vec<String>),
+    Vector2Array(Vec<Vector2>),
+    Vector3Array(Vec<Vector3>),
+    ColorArray(Vec<Color>),
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let remaining = self.bytes.len() - self.pos;
+        if len > remaining {
+            return Err(DecodeError::UnexpectedEof {
+                offset: self.pos,
+                needed: len,
+            });
+        }
+
+        let slice = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64, DecodeError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn f32(&mut self) -> Result<f32, DecodeError> {
+        self.u32().map(f32::from_bits)
+    }
+
+    fn real(&mut self) -> Result<real, DecodeError> {
+        self.f32().map(real::from_f32)
+    }
+
+    fn vector2(&mut self) -> Result<Vector2, DecodeError> {
+        Ok(Vector2::new(self.real()?, self.real()?))
+    }
+
+    fn vector3(&mut self) -> Result<Vector3, DecodeError> {
+        Ok(Vector3::new(self.real()?, self.real()?, self.real()?))
+    }
+
+    fn color(&mut self) -> Result<Color, DecodeError> {
+        Ok(Color::from_rgba(
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+        ))
+    }
+
+    /// Reads a length prefix for `elem_size`-byte elements, checking that the remaining input
+    /// can hold that many elements. This prevents huge allocations from corrupt input.
+    fn len(&mut self, mask: u32, elem_size: usize) -> Result<usize, DecodeError> {
+        let offset = self.pos;
+        let len = self.u32()?;
+        let masked = len & mask;
+
+        let remaining = self.bytes.len() - self.pos;
+        match (masked as usize).checked_mul(elem_size) {
+            Some(size) if masked <= i32::MAX as u32 && size <= remaining => Ok(masked as usize),
+            _ => Err(DecodeError::InvalidLength { offset, len }),
+        }
+    }
+
+    /// Reads padding after `len` bytes of data, so the position is aligned to 4 bytes again.
+    fn padding(&mut self, len: usize) -> Result<(), DecodeError> {
+        if len % 4 != 0 {
+            self.take(4 - len % 4)?;
+        }
+        Ok(())
+    }
+
+    fn utf8(&mut self, len: usize) -> Result<String, DecodeError> {
+        let offset = self.pos;
+        let bytes = self.take(len)?;
+
+        // The engine stops parsing at the first NUL byte
+        let bytes = match bytes.iter().position(|&b| b == 0) {
+            Some(nul) => &bytes[..nul],
+            None => bytes,
+        };
+
+        let s = std::str::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8 { offset })?;
+        Ok(s.to_owned())
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let len = self.len(u32::MAX, 1)?;
+        let s = self.utf8(len)?;
+        self.padding(len)?;
+        Ok(s)
+    }
+
+    fn read_node(&mut self, allow_objects: bool, depth: usize) -> Result<Parsed, DecodeError> {
+        let offset = self.pos;
+
+        if depth > MAX_DEPTH {
+            return Err(DecodeError::TooDeep { offset });
+        }
+
+        let header = self.u32()?;
+        let type_id = header & HEADER_TYPE_MASK;
+        if type_id as usize >= VariantType::NAMES.len() {
+            return Err(DecodeError::InvalidType { offset, type_id });
+        }
+
+        #[allow(clippy::unnecessary_cast)] // False positives: casts necessary for cross-platform
+        let ty = VariantType::from_sys(type_id as sys::godot_variant_type);
+
+        match ty {
+            VariantType::Object => self.object(header, offset, allow_objects, depth),
+            VariantType::Dictionary => self.dictionary(allow_objects, depth),
+            VariantType::VariantArray => self.array(allow_objects, depth),
+            _ => self.leaf(ty, header),
+        }
+    }
+
+    // The functions below are split from `read_node` to keep its stack frame small, since it
+    // is called recursively up to `MAX_DEPTH` times.
+
+    fn object(
+        &mut self,
+        header: u32,
+        offset: usize,
+        allow_objects: bool,
+        depth: usize,
+    ) -> Result<Parsed, DecodeError> {
+        if header & HEADER_FLAG_OBJECT_AS_ID != 0 {
+            return match self.u64()? {
+                0 => Ok(Parsed::NullObject),
+                id => Ok(Parsed::ObjectId(id)),
+            };
+        }
+
+        if !allow_objects {
+            return Err(DecodeError::ObjectsNotAllowed { offset });
+        }
+
+        let class = self.string()?;
+        if class.is_empty() {
+            return Ok(Parsed::NullObject);
+        }
+
+        let count = self.len(u32::MAX, 8)?;
+        let mut properties = Vec::with_capacity(count);
+        for _ in 0..count {
+            let name = self.string()?;
+            let value = self.read_node(allow_objects, depth + 1)?;
+            properties.push((name, value));
+        }
+        Ok(Parsed::Object { class, properties })
+    }
+
+    fn dictionary(&mut self, allow_objects: bool, depth: usize) -> Result<Parsed, DecodeError> {
+        let count = self.len(COLLECTION_LEN_MASK, 8)?;
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            let key = self.read_node(allow_objects, depth + 1)?;
+            let value = self.read_node(allow_objects, depth + 1)?;
+            entries.push((key, value));
+        }
+        Ok(Parsed::Dictionary(entries))
+    }
+
+    fn array(&mut self, allow_objects: bool, depth: usize) -> Result<Parsed, DecodeError> {
+        let count = self.len(COLLECTION_LEN_MASK, 4)?;
+        let mut elements = Vec::with_capacity(count);
+        for _ in 0..count {
+            elements.push(self.read_node(allow_objects, depth + 1)?);
+        }
+        Ok(Parsed::Array(elements))
+    }
+
+    /// Reads a value of a non-recursive type.
+    #[inline(never)]
+    fn leaf(&mut self, ty: VariantType, header: u32) -> Result<Parsed, DecodeError> {
+        let node = match ty {
+            VariantType::Object | VariantType::Dictionary | VariantType::VariantArray => {
+                unreachable!("recursive types are handled by read_node")
+            }
+            VariantType::Nil => Parsed::Nil,
+            VariantType::Bool => Parsed::Bool(self.u32()? != 0),
+            VariantType::I64 => {
+                if header & HEADER_FLAG_64 != 0 {
+                    Parsed::I64(self.u64()? as i64)
+                } else {
+                    Parsed::I64(i64::from(self.u32()? as i32))
+                }
+            }
+            VariantType::F64 => {
+                if header & HEADER_FLAG_64 != 0 {
+                    Parsed::F64(f64::from_bits(self.u64()?))
+                } else {
+                    Parsed::F64(f64::from(self.f32()?))
+                }
+            }
+            VariantType::GodotString => Parsed::String(self.string()?),
+            VariantType::Vector2 => Parsed::Vector2(self.vector2()?),
+            VariantType::Rect2 => Parsed::Rect2(Rect2::new(self.vector2()?, self.vector2()?)),
+            VariantType::Vector3 => Parsed::Vector3(self.vector3()?),
+            VariantType::Transform2D => Parsed::Transform2D(Transform2D {
+                a: self.vector2()?,
+                b: self.vector2()?,
+                origin: self.vector2()?,
+            }),
+            VariantType::Plane => Parsed::Plane(Plane {
+                normal: self.vector3()?,
+                d: self.real()?,
+            }),
+            VariantType::Quat => Parsed::Quat(Quat::new(
+                self.real()?,
+                self.real()?,
+                self.real()?,
+                self.real()?,
+            )),
+            VariantType::Aabb => Parsed::Aabb(Aabb::new(self.vector3()?, self.vector3()?)),
+            VariantType::Basis => Parsed::Basis(Basis {
+                elements: [self.vector3()?, self.vector3()?, self.vector3()?],
+            }),
+            VariantType::Transform => Parsed::Transform(Transform {
+                basis: Basis {
+                    elements: [self.vector3()?, self.vector3()?, self.vector3()?],
+                },
+                origin: self.vector3()?,
+            }),
+            VariantType::Color => Parsed::Color(self.color()?),
+            VariantType::NodePath => self.node_path()?,
+            VariantType::Rid => Parsed::Rid,
+            VariantType::ByteArray => {
+                let len = self.len(u32::MAX, 1)?;
+                let bytes = self.take(len)?.to_vec();
+                self.padding(len)?;
+                Parsed::ByteArray(bytes)
+            }
+            VariantType::Int32Array => {
+                let len = self.len(u32::MAX, 4)?;
+                let mut vec = Vec::with_capacity(len);
+                for _ in 0..len {
+                    vec.push(self.u32()? as i32);
+                }
+                Parsed::Int32Array(vec)
+            }
+            VariantType::Float32Array => {
+                let len = self.len(u32::MAX, 4)?;
+                let mut vec = Vec::with_capacity(len);
+                for _ in 0..len {
+                    vec.push(self.real()?);
+                }
+                Parsed::Float32Array(vec)
+            }
+            VariantType::StringArray => {
+                let len = self.len(u32::MAX, 4)?;
+                let mut vec = Vec::with_capacity(len);
+                for _ in 0..len {
+                    vec.push(self.string()?);
+                }
+                Parsed::StringArray(vec)
+            }
+            VariantType::Vector2Array => {
+                let len = self.len(u32::MAX, 8)?;
+                let mut vec = Vec::with_capacity(len);
+                for _ in 0..len {
+                    vec.push(self.vector2()?);
+                }
+                Parsed::Vector2Array(vec)
+            }
+            VariantType::Vector3Array => {
+                let len = self.len(u32::MAX, 12)?;
+                let mut vec = Vec::with_capacity(len);
+                for _ in 0..len {
+                    vec.push(self.vector3()?);
+                }
+                Parsed::Vector3Array(vec)
+            }
+            VariantType::ColorArray => {
+                let len = self.len(u32::MAX, 16)?;
+                let mut vec = Vec::with_capacity(len);
+                for _ in 0..len {
+                    vec.push(self.color()?);
+                }
+                Parsed::ColorArray(vec)
+            }
+        };
+
+        Ok(node)
+    }
+
+    fn node_path(&mut self) -> Result<Parsed, DecodeError> {
+        let offset = self.pos;
+        let first = self.u32()?;
+        if first & NODE_PATH_NEW_FORMAT == 0 {
+            return Err(DecodeError::LegacyNodePath { offset });
+        }
+
+        // The first word is the name count, so read it again as a length
+        self.pos = offset;
+        let name_count = self.len(!NODE_PATH_NEW_FORMAT, 4)?;
+        let mut subname_count = self.len(u32::MAX, 4)?;
+        let flags = self.u32()?;
+        if flags & NODE_PATH_FLAG_PROPERTY != 0 {
+            subname_count += 1;
+        }
+
+        // Reconstruct the textual form, since GDNative has no constructor taking the parts
+        let mut path = String::new();
+        if flags & NODE_PATH_FLAG_ABSOLUTE != 0 {
+            path.push('/');
+        }
+        for i in 0..name_count {
+            if i > 0 {
+                path.push('/');
+            }
+            path.push_str(&self.string()?);
+        }
+        for _ in 0..subname_count {
+            path.push(':');
+            path.push_str(&self.string()?);
+        }
+
+        Ok(Parsed::NodePath(path))
+    }
+}
+
+impl Parsed {
+    /// Creates engine values from the decoded data.
+    ///
+    /// # Safety
+    ///
+    /// If `self` contains full objects, the same safety requirements as `decode_with_objects`
+    /// apply.
+    unsafe fn into_dispatch(self) -> Result<VariantDispatch, DecodeError> {
+        use VariantDispatch as V;
+
+        let value = match self {
+            Parsed::Nil => V::Nil,
+            Parsed::Bool(b) => V::Bool(b),
+            Parsed::I64(i) => V::I64(i),
+            Parsed::F64(f) => V::F64(f),
+            Parsed::String(s) => V::GodotString(GodotString::from_str(s)),
+            Parsed::Vector2(v) => V::Vector2(v),
+            Parsed::Rect2(r) => V::Rect2(r),
+            Parsed::Vector3(v) => V::Vector3(v),
+            Parsed::Transform2D(t) => V::Transform2D(t),
+            Parsed::Plane(p) => V::Plane(p),
+            Parsed::Quat(q) => V::Quat(q),
+            Parsed::Aabb(a) => V::Aabb(a),
+            Parsed::Basis(b) => V::Basis(b),
+            Parsed::Transform(t) => V::Transform(t),
+            Parsed::Color(c) => V::Color(c),
+            Parsed::NodePath(path) => V::NodePath(NodePath::from_str(&path)),
+            Parsed::Rid => V::Rid(Rid::new()),
+            Parsed::NullObject => V::Object(Variant::from_object_ptr(ptr::null_mut())),
+            Parsed::ObjectId(id) => {
+                let mut obj = instantiate("EncodedObjectAsID")?;
+                // `EncodedObjectAsID` is a plain engine class with no user code
+                let _ = obj.call("set_object_id", &[Variant::new(id as i64)]);
+                V::Object(obj)
+            }
+            Parsed::Object { class, properties } => {
+                let mut obj = instantiate(&class)?;
+                for (name, value) in properties {
+                    let value = Variant::from(&value.into_dispatch()?);
+                    // Like in the engine, properties that can't be set are silently ignored
+                    let _ = obj.call("set", &[Variant::new(name), value]);
+                }
+                V::Object(obj)
+            }
+            Parsed::Dictionary(entries) => {
+                let dict = Dictionary::<Unique>::new();
+                for (key, value) in entries {
+                    let key = Variant::from(&key.into_dispatch()?);
+                    let value = Variant::from(&value.into_dispatch()?);
+                    dict.insert(key, value);
+                }
+                V::Dictionary(dict.into_shared())
+            }
+            Parsed::Array(elements) => {
+                let arr = VariantArray::<Unique>::new();
+                for element in elements {
+                    arr.push(Variant::from(&element.into_dispatch()?));
+                }
+                V::VariantArray(arr.into_shared())
+            }
+            Parsed::ByteArray(vec) => V::ByteArray(PoolArray::from_vec(vec)),
+            Parsed::Int32Array(vec) => V::Int32Array(PoolArray::from_vec(vec)),
+            Parsed::Float32Array(vec) => V::Float32Array(PoolArray::from_vec(vec)),
+            Parsed::StringArray(vec) => V::StringArray(
+                vec.into_iter()
+                    .map(GodotString::from_str)
+                    .collect::<PoolArray<_>>(),
+            ),
+            Parsed::Vector2Array(vec) => V::Vector2Array(PoolArray::from_vec(vec)),
+            Parsed::Vector3Array(vec) => V::Vector3Array(PoolArray::from_vec(vec)),
+            Parsed::ColorArray(vec) => V::ColorArray(PoolArray::from_vec(vec)),
+        };
+
+        Ok(value)
+    }
+}
+
+/// Instantiates an engine class by name, returning the new object in a `Variant`.
+unsafe fn instantiate(class: &str) -> Result<Variant, DecodeError> {
+    let unknown = || DecodeError::UnknownClass {
+        class: class.to_owned(),
+    };
+
+    let class_name = CString::new(class).map_err(|_| unknown())?;
+    let ctor = (get_api().godot_get_class_constructor)(class_name.as_ptr()).ok_or_else(unknown)?;
+    let obj = ctor();
+    if obj.is_null() {
+        return Err(unknown());
+    }
+
+    // `Reference`s are initialized by the variant constructor, like `REF(obj)` in the engine
+    Ok(Variant::from_object_ptr(obj))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_ok(bytes: &[u8]) -> VariantDispatch {
+        let (value, len) = decode(bytes).expect("should decode");
+        assert_eq!(bytes.len(), len);
+        value
+    }
+
+    fn f32_bytes(values: &[f32]) -> Vec<u8> {
+        values.iter().flat_map(|f| f.to_le_bytes()).collect()
+    }
+
+    fn with_header(header: [u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut bytes = header.to_vec();
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    #[test]
+    fn golden_primitives() {
+        assert_eq!(encode(&VariantDispatch::Nil).unwrap(), [0, 0, 0, 0]);
+        assert_eq!(
+            encode(&VariantDispatch::Bool(true)).unwrap(),
+            [1, 0, 0, 0, 1, 0, 0, 0]
+        );
+        assert_eq!(
+            encode(&VariantDispatch::I64(42)).unwrap(),
+            [2, 0, 0, 0, 42, 0, 0, 0]
+        );
+        assert_eq!(
+            encode(&VariantDispatch::I64(-2)).unwrap(),
+            [2, 0, 0, 0, 254, 255, 255, 255]
+        );
+        assert_eq!(
+            encode(&VariantDispatch::I64(1 << 40)).unwrap(),
+            [2, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0]
+        );
+        assert_eq!(
+            encode(&VariantDispatch::F64(0.5)).unwrap(),
+            [3, 0, 0, 0, 0, 0, 0, 63]
+        );
+        assert_eq!(
+            encode(&VariantDispatch::F64(0.1)).unwrap(),
+            [3, 0, 1, 0, 0x9a, 0x99, 0x99, 0x99, 0x99, 0x99, 0xb9, 0x3f]
+        );
+
+        assert!(matches!(decode_ok(&[0, 0, 0, 0]), VariantDispatch::Nil));
+        assert!(matches!(
+            decode_ok(&[1, 0, 0, 0, 1, 0, 0, 0]),
+            VariantDispatch::Bool(true)
+        ));
+        assert!(matches!(
+            decode_ok(&[2, 0, 0, 0, 254, 255, 255, 255]),
+            VariantDispatch::I64(-2)
+        ));
+        assert!(matches!(
+            decode_ok(&[2, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0]),
+            VariantDispatch::I64(0x100_0000_0000)
+        ));
+        assert!(matches!(
+            decode_ok(&[3, 0, 1, 0, 0x9a, 0x99, 0x99, 0x99, 0x99, 0x99, 0xb9, 0x3f]),
+            VariantDispatch::F64(f) if f == 0.1
+        ));
+    }
+
+    #[test]
+    fn golden_math_types() {
+        let vector2 = with_header([5, 0, 0, 0], &f32_bytes(&[1.0, 2.0]));
+        assert_eq!(
+            encode(&VariantDispatch::Vector2(Vector2::new(1.0, 2.0))).unwrap(),
+            vector2
+        );
+        assert!(matches!(
+            decode_ok(&vector2),
+            VariantDispatch::Vector2(v) if v == Vector2::new(1.0, 2.0)
+        ));
+
+        let rect2 = Rect2::new(Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0));
+        let bytes = with_header([6, 0, 0, 0], &f32_bytes(&[1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(encode(&VariantDispatch::Rect2(rect2)).unwrap(), bytes);
+        assert!(matches!(decode_ok(&bytes), VariantDispatch::Rect2(r) if r == rect2));
+
+        let transform2d = Transform2D {
+            a: Vector2::new(1.0, 2.0),
+            b: Vector2::new(3.0, 4.0),
+            origin: Vector2::new(5.0, 6.0),
+        };
+        let bytes = with_header([8, 0, 0, 0], &f32_bytes(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+        assert_eq!(
+            encode(&VariantDispatch::Transform2D(transform2d)).unwrap(),
+            bytes
+        );
+        assert!(matches!(decode_ok(&bytes), VariantDispatch::Transform2D(t) if t == transform2d));
+
+        let plane = Plane {
+            normal: Vector3::new(0.0, 1.0, 0.0),
+            d: 2.5,
+        };
+        let bytes = with_header([9, 0, 0, 0], &f32_bytes(&[0.0, 1.0, 0.0, 2.5]));
+        assert_eq!(encode(&VariantDispatch::Plane(plane)).unwrap(), bytes);
+        assert!(matches!(decode_ok(&bytes), VariantDispatch::Plane(p) if p == plane));
+
+        let quat = Quat::new(0.0, 0.0, 0.0, 1.0);
+        let bytes = with_header([10, 0, 0, 0], &f32_bytes(&[0.0, 0.0, 0.0, 1.0]));
+        assert_eq!(encode(&VariantDispatch::Quat(quat)).unwrap(), bytes);
+        assert!(matches!(decode_ok(&bytes), VariantDispatch::Quat(q) if q == quat));
+
+        let basis = Basis {
+            elements: [
+                Vector3::new(1.0, 2.0, 3.0),
+                Vector3::new(4.0, 5.0, 6.0),
+                Vector3::new(7.0, 8.0, 9.0),
+            ],
+        };
+        let transform = Transform {
+            basis,
+            origin: Vector3::new(10.0, 11.0, 12.0),
+        };
+        let bytes = with_header(
+            [13, 0, 0, 0],
+            &f32_bytes(&[
+                1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0,
+            ]),
+        );
+        assert_eq!(
+            encode(&VariantDispatch::Transform(transform)).unwrap(),
+            bytes
+        );
+        assert!(matches!(decode_ok(&bytes), VariantDispatch::Transform(t) if t == transform));
+
+        let color = Color::from_rgba(1.0, 0.5, 0.25, 1.0);
+        let bytes = with_header([14, 0, 0, 0], &f32_bytes(&[1.0, 0.5, 0.25, 1.0]));
+        assert_eq!(encode(&VariantDispatch::Color(color)).unwrap(), bytes);
+        assert!(matches!(decode_ok(&bytes), VariantDispatch::Color(c) if c == color));
+    }
+
+    #[test]
+    fn golden_node_path() {
+        // var2bytes(NodePath("/root/Player:position:x"))
+        let bytes = [
+            15, 0, 0, 0, // header
+            2, 0, 0, 0x80, // name count, with the new format flag
+            2, 0, 0, 0, // subname count
+            1, 0, 0, 0, // flags: absolute
+            4, 0, 0, 0, b'r', b'o', b'o', b't', //
+            6, 0, 0, 0, b'P', b'l', b'a', b'y', b'e', b'r', 0, 0, //
+            8, 0, 0, 0, b'p', b'o', b's', b'i', b't', b'i', b'o', b'n', //
+            1, 0, 0, 0, b'x', 0, 0, 0,
+        ];
+
+        // Only the parsed form can be checked without the engine.
+        let mut reader = Reader::new(&bytes);
+        match reader.read_node(false, 0) {
+            Ok(Parsed::NodePath(path)) => assert_eq!("/root/Player:position:x", path),
+            _ => panic!("should decode a NodePath"),
+        }
+        assert_eq!(bytes.len(), reader.pos);
+
+        // Relative, with the legacy property flag adding a subname.
+        let bytes = [
+            15, 0, 0, 0, // header
+            1, 0, 0, 0x80, // name count, with the new format flag
+            0, 0, 0, 0, // subname count
+            2, 0, 0, 0, // flags: property
+            1, 0, 0, 0, b'a', 0, 0, 0, //
+            1, 0, 0, 0, b'b', 0, 0, 0,
+        ];
+        let mut reader = Reader::new(&bytes);
+        match reader.read_node(false, 0) {
+            Ok(Parsed::NodePath(path)) => assert_eq!("a:b", path),
+            _ => panic!("should decode a NodePath"),
+        }
+        assert_eq!(bytes.len(), reader.pos);
+    }
+
+    #[test]
+    fn trailing_data_is_ignored() {
+        let (value, len) = decode(&[2, 0, 0, 0, 7, 0, 0, 0, 0xff, 0xff]).unwrap();
+        assert!(matches!(value, VariantDispatch::I64(7)));
+        assert_eq!(8, len);
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(
+            Err(DecodeError::UnexpectedEof {
+                offset: 0,
+                needed: 4
+            }),
+            decode(&[]).map(|_| ())
+        );
+        assert_eq!(
+            Err(DecodeError::UnexpectedEof {
+                offset: 4,
+                needed: 8
+            }),
+            decode(&[2, 0, 1, 0, 1, 2, 3, 4]).map(|_| ())
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidType {
+                offset: 0,
+                type_id: 27
+            }),
+            decode(&[27, 0, 0, 0]).map(|_| ())
+        );
+
+        // String with a length larger than the input
+        assert_eq!(
+            Err(DecodeError::InvalidLength {
+                offset: 4,
+                len: 0x7fff_ffff
+            }),
+            decode(&[4, 0, 0, 0, 0xff, 0xff, 0xff, 0x7f, b'a', 0, 0, 0]).map(|_| ())
+        );
+
+        // String without padding
+        assert_eq!(
+            Err(DecodeError::UnexpectedEof {
+                offset: 11,
+                needed: 1
+            }),
+            decode(&[4, 0, 0, 0, 3, 0, 0, 0, b'a', b'b', b'c']).map(|_| ())
+        );
+
+        assert_eq!(
+            Err(DecodeError::InvalidUtf8 { offset: 8 }),
+            decode(&[4, 0, 0, 0, 2, 0, 0, 0, 0xc3, 0x28, 0, 0]).map(|_| ())
+        );
+
+        // Huge pool array lengths must not cause allocations
+        assert_eq!(
+            Err(DecodeError::InvalidLength {
+                offset: 4,
+                len: 0x4000_0000
+            }),
+            decode(&[25, 0, 0, 0, 0, 0, 0, 0x40]).map(|_| ())
+        );
+
+        assert_eq!(
+            Err(DecodeError::LegacyNodePath { offset: 4 }),
+            decode(&[15, 0, 0, 0, 0, 0, 0, 0]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn full_objects_are_rejected() {
+        let bytes = [17, 0, 0, 0, 4, 0, 0, 0, b'N', b'o', b'd', b'e', 0, 0, 0, 0];
+        assert_eq!(
+            Err(DecodeError::ObjectsNotAllowed { offset: 0 }),
+            decode(&bytes).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        // Arrays containing a single array, nested too deep
+        let mut bytes = Vec::new();
+        for _ in 0..=MAX_DEPTH + 1 {
+            bytes.extend_from_slice(&[19, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        bytes.extend_from_slice(&[0, 0, 0, 0]);
+
+        assert_eq!(
+            Err(DecodeError::TooDeep {
+                offset: (MAX_DEPTH + 1) * 8
+            }),
+            decode(&bytes).map(|_| ())
+        );
+    }
+}
+
+godot_test!(
+    test_marshal_strings_and_collections {
+        let s = VariantDispatch::GodotString(GodotString::from("héllo"));
+        let bytes = encode(&s).unwrap();
+        assert_eq!(bytes, [4, 0, 0, 0, 6, 0, 0, 0, b'h', 0xc3, 0xa9, b'l', b'l', b'o', 0, 0]);
+
+        let (value, _) = decode(&bytes).unwrap();
+        assert_eq!(Variant::from(&value), "héllo".to_variant());
+
+        let arr = VariantArray::new();
+        arr.push(1);
+        arr.push("a");
+        let bytes = encode(&arr.into_shared().to_variant().dispatch()).unwrap();
+        assert_eq!(bytes, [
+            19, 0, 0, 0, 2, 0, 0, 0,
+            2, 0, 0, 0, 1, 0, 0, 0,
+            4, 0, 0, 0, 1, 0, 0, 0, b'a', 0, 0, 0,
+        ]);
+
+        let (value, _) = decode(&bytes).unwrap();
+        assert_eq!(Ok(vec![Variant::new(1), Variant::new("a")]), Variant::from(&value).try_to::<Vec<Variant>>());
+
+        let dict = Dictionary::new();
+        dict.insert("k", 1.5);
+        let bytes = encode(&dict.into_shared().to_variant().dispatch()).unwrap();
+        assert_eq!(bytes, [
+            18, 0, 0, 0, 1, 0, 0, 0,
+            4, 0, 0, 0, 1, 0, 0, 0, b'k', 0, 0, 0,
+            3, 0, 0, 0, 0, 0, 0xc0, 0x3f,
+        ]);
+
+        let (value, _) = decode(&bytes).unwrap();
+        let dict = Variant::from(&value).try_to::<Dictionary>().unwrap();
+        assert_eq!(Some(1.5), dict.get("k").and_then(|v| v.try_to::<f64>().ok()));
+    }
+
+    test_marshal_pool_arrays {
+        let bytes = encode(&VariantDispatch::ByteArray(PoolArray::from_vec(vec![1, 2, 3]))).unwrap();
+        assert_eq!(bytes, [20, 0, 0, 0, 3, 0, 0, 0, 1, 2, 3, 0]);
+        let (value, _) = decode(&bytes).unwrap();
+        assert_eq!(Ok(PoolArray::from_vec(vec![1u8, 2, 3])), Variant::from(&value).try_to::<PoolArray<u8>>());
+
+        let strings = vec![GodotString::from("ab"), GodotString::from("cde")];
+        let bytes = encode(&VariantDispatch::StringArray(PoolArray::from_vec(strings))).unwrap();
+        assert_eq!(bytes, [
+            23, 0, 0, 0, 2, 0, 0, 0,
+            3, 0, 0, 0, b'a', b'b', 0, 0,
+            4, 0, 0, 0, b'c', b'd', b'e', 0,
+        ]);
+        let (value, _) = decode(&bytes).unwrap();
+        let strings = Variant::from(&value).try_to::<PoolArray<GodotString>>().unwrap().to_vec();
+        assert_eq!(vec![GodotString::from("ab"), GodotString::from("cde")], strings);
+
+        let vectors = vec![Vector3::new(1.0, 2.0, 3.0)];
+        let bytes = encode(&VariantDispatch::Vector3Array(PoolArray::from_vec(vectors))).unwrap();
+        let (value, len) = decode(&bytes).unwrap();
+        assert_eq!(bytes.len(), len);
+        assert_eq!(
+            vec![Vector3::new(1.0, 2.0, 3.0)],
+            Variant::from(&value).try_to::<PoolArray<Vector3>>().unwrap().to_vec(),
+        );
+    }
+
+    test_marshal_node_path {
+        let path = NodePath::from_str("/root/Player:position:x");
+        let bytes = encode(&VariantDispatch::NodePath(path)).unwrap();
+        assert_eq!(bytes, [
+            15, 0, 0, 0, 2, 0, 0, 0x80, 2, 0, 0, 0, 1, 0, 0, 0,
+            4, 0, 0, 0, b'r', b'o', b'o', b't',
+            6, 0, 0, 0, b'P', b'l', b'a', b'y', b'e', b'r', 0, 0,
+            8, 0, 0, 0, b'p', b'o', b's', b'i', b't', b'i', b'o', b'n',
+            1, 0, 0, 0, b'x', 0, 0, 0,
+        ]);
+
+        let (value, _) = decode(&bytes).unwrap();
+        assert_eq!(
+            Ok(GodotString::from("/root/Player:position:x")),
+            Variant::from(&value).try_to::<NodePath>().map(|p| p.to_godot_string()),
+        );
+    }
+);
@@ -0,0 +1,621 @@
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+use super::de::from_variant;
+use crate::core_types::*;
+use crate::object::ownership::Unique;
+
+/// Serializes a value implementing `Serialize` directly into a [`Variant`].
+///
+/// Values are mapped to the types GDScript works with:
+///
+/// - Structs and maps become [`Dictionary`]s. Struct fields are keyed by their names.
+/// - Sequences, tuples and tuple structs become [`VariantArray`]s.
+/// - Byte strings (e.g. through `serde_bytes`) become `PoolByteArray`s.
+/// - `None`, `()` and unit structs become `Nil`, while `Some` and newtype structs are transparent.
+/// - Enums are represented as described by [`EnumRepr`], by name unless configured otherwise
+///   with [`VariantSerializer`].
+///
+/// Core types with `serde` support, like [`Vector2`] or [`NodePath`], are converted to their
+/// native `Variant` representation instead of `Dictionary`s. User structs with the same name as a
+/// core type are only converted if they have exactly the same fields.
+///
+/// Integers that don't fit in an `i64` cause an error, as do types that are rejected by their
+/// own `Serialize` implementation.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gdnative::core_types::variant::{from_variant, to_variant};
+/// use gdnative::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Item {
+///     name: String,
+///     position: Vector2,
+///     tags: Vec<String>,
+/// }
+///
+/// let item = Item {
+///     name: "sword".to_string(),
+///     position: Vector2::new(1.0, 2.0),
+///     tags: vec!["weapon".to_string()],
+/// };
+///
+/// let variant = to_variant(&item).unwrap();
+/// let dict = variant.try_to::<Dictionary>().unwrap();
+/// assert_eq!(dict.get("position"), Some(Vector2::new(1.0, 2.0).to_variant()));
+///
+/// let back: Item = from_variant(&variant).unwrap();
+/// assert_eq!(item, back);
+/// ```
+#[inline]
+pub fn to_variant<T>(value: &T) -> Result<Variant, SerdeError>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(VariantSerializer::new())
+}
+
+/// Error returned by [`to_variant`] and [`from_variant`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SerdeError {
+    message: String,
+}
+
+impl fmt::Display for SerdeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for SerdeError {}
+
+impl ser::Error for SerdeError {
+    #[inline]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerdeError {
+            message: msg.to_string(),
+        }
+    }
+}
+
+impl serde::de::Error for SerdeError {
+    #[inline]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerdeError {
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// Representation of enum variants used by [`VariantSerializer`].
+///
+/// Regardless of this setting, [`from_variant`] accepts both representations.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum EnumRepr {
+    /// Variants are identified by name, like serde's default "externally tagged" representation.
+    ///
+    /// Unit variants become `String`s. Other variants become `Dictionary`s with a single entry,
+    /// mapping the variant name to its contents.
+    #[default]
+    Name,
+
+    /// Variants are identified by their index, like the values of GDScript enums.
+    ///
+    /// Unit variants become `int`s. Other variants become `Dictionary`s with a single entry,
+    /// mapping the variant index to its contents.
+    Index,
+}
+
+/// Serde data format producing [`Variant`]s. See [`to_variant`] for the mapping of values.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gdnative::core_types::variant::{EnumRepr, VariantSerializer};
+/// use gdnative::prelude::*;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// enum Direction {
+///     Left,
+///     Right,
+/// }
+///
+/// let serializer = VariantSerializer::new().with_enum_repr(EnumRepr::Index);
+/// let variant = Direction::Right.serialize(serializer).unwrap();
+/// assert_eq!(variant, 1.to_variant());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct VariantSerializer {
+    enum_repr: EnumRepr,
+    core_types: bool,
+}
+
+impl VariantSerializer {
+    /// Creates a serializer using the default [`EnumRepr`].
+    #[inline]
+    pub fn new() -> Self {
+        VariantSerializer {
+            enum_repr: EnumRepr::default(),
+            core_types: true,
+        }
+    }
+
+    /// Sets the representation used for enum variants.
+    #[inline]
+    pub fn with_enum_repr(mut self, enum_repr: EnumRepr) -> Self {
+        self.enum_repr = enum_repr;
+        self
+    }
+
+    /// Serializer that keeps core types in their `Dictionary` form. Used to present core types
+    /// to their `Deserialize` implementations.
+    pub(super) fn expanded() -> Self {
+        VariantSerializer {
+            enum_repr: EnumRepr::default(),
+            core_types: false,
+        }
+    }
+
+    fn variant_key(&self, variant_index: u32, variant: &'static str) -> Variant {
+        match self.enum_repr {
+            EnumRepr::Name => variant.to_variant(),
+            EnumRepr::Index => variant_index.to_variant(),
+        }
+    }
+}
+
+impl Default for VariantSerializer {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wraps the contents of a non-unit enum variant.
+fn wrap_variant(key: Variant, value: Variant) -> Variant {
+    let dict = Dictionary::new();
+    dict.insert(key, value);
+    dict.owned_to_variant()
+}
+
+/// Converts a `Dictionary` produced for a core type back into the native `Variant` type.
+///
+/// User structs may share the name of a core type, so only dictionaries with exactly the fields
+/// of the core type are converted. Others are kept as-is, without losing any fields.
+fn core_type(name: &str, dict: &Variant) -> Option<Variant> {
+    fn convert<T>(dict: &Variant, fields: &[&str]) -> Option<Variant>
+    where
+        T: serde::de::DeserializeOwned + ToVariant,
+    {
+        let keys = dict.to::<Dictionary>()?;
+        let exact_fields =
+            keys.len() as usize == fields.len() && fields.iter().all(|&field| keys.contains(field));
+        if !exact_fields {
+            return None;
+        }
+
+        from_variant::<T>(dict).ok().map(|value| value.to_variant())
+    }
+
+    match name {
+        "Vector2" => convert::<Vector2>(dict, &["x", "y"]),
+        "Vector3" => convert::<Vector3>(dict, &["x", "y", "z"]),
+        "Rect2" => convert::<Rect2>(dict, &["position", "size"]),
+        "Transform2D" => convert::<Transform2D>(dict, &["a", "b", "origin"]),
+        "Plane" => convert::<Plane>(dict, &["normal", "d"]),
+        "Quat" => convert::<Quat>(dict, &["x", "y", "z", "w"]),
+        "Aabb" => convert::<Aabb>(dict, &["position", "size"]),
+        "Basis" => convert::<Basis>(dict, &["elements"]),
+        "Transform" => convert::<Transform>(dict, &["basis", "origin"]),
+        "Color" => convert::<Color>(dict, &["r", "g", "b", "a"]),
+        _ => None,
+    }
+}
+
+impl ser::Serializer for VariantSerializer {
+    type Ok = Variant;
+    type Error = SerdeError;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeEnumVariant<SerializeArray>;
+    type SerializeMap = SerializeDictionary;
+    type SerializeStruct = SerializeStruct;
+    type SerializeStructVariant = SerializeEnumVariant<SerializeStruct>;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<Variant, SerdeError> {
+        Ok(v.to_variant())
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<Variant, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<Variant, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<Variant, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<Variant, SerdeError> {
+        Ok(v.to_variant())
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<Variant, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<Variant, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<Variant, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<Variant, SerdeError> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            Err(_) => Err(ser::Error::custom(format!(
+                "integer {v} is out of range for Variant"
+            ))),
+        }
+    }
+
+    #[inline]
+    fn serialize_f32(self, v: f32) -> Result<Variant, SerdeError> {
+        self.serialize_f64(v.into())
+    }
+
+    #[inline]
+    fn serialize_f64(self, v: f64) -> Result<Variant, SerdeError> {
+        Ok(v.to_variant())
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<Variant, SerdeError> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Variant, SerdeError> {
+        Ok(v.to_variant())
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<Variant, SerdeError> {
+        Ok(PoolArray::from_slice(v).to_variant())
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Variant, SerdeError> {
+        Ok(Variant::nil())
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<Variant, SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Variant, SerdeError> {
+        Ok(Variant::nil())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Variant, SerdeError> {
+        Ok(Variant::nil())
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Variant, SerdeError> {
+        Ok(self.variant_key(variant_index, variant))
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Variant, SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let variant = value.serialize(self)?;
+        if self.core_types && name == "NodePath" {
+            if let Ok(path) = variant.try_to::<GodotString>() {
+                return Ok(NodePath::new(&path).to_variant());
+            }
+        }
+        Ok(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Variant, SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self.variant_key(variant_index, variant);
+        Ok(wrap_variant(key, value.serialize(self)?))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SerializeArray, SerdeError> {
+        Ok(SerializeArray {
+            ser: self,
+            array: VariantArray::new(),
+        })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeEnumVariant<SerializeArray>, SerdeError> {
+        Ok(SerializeEnumVariant {
+            key: self.variant_key(variant_index, variant),
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeDictionary, SerdeError> {
+        Ok(SerializeDictionary {
+            ser: self,
+            dict: Dictionary::new(),
+            key: None,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<SerializeStruct, SerdeError> {
+        Ok(SerializeStruct {
+            ser: self,
+            name,
+            dict: Dictionary::new(),
+        })
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeEnumVariant<SerializeStruct>, SerdeError> {
+        Ok(SerializeEnumVariant {
+            key: self.variant_key(variant_index, variant),
+            inner: self.serialize_struct(name, len)?,
+        })
+    }
+}
+
+/// Serializes sequences, tuples and tuple structs into a [`VariantArray`].
+pub struct SerializeArray {
+    ser: VariantSerializer,
+    array: VariantArray<Unique>,
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Variant;
+    type Error = SerdeError;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.array.push(value.serialize(self.ser)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerdeError> {
+        Ok(self.array.owned_to_variant())
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Variant;
+    type Error = SerdeError;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Variant;
+    type Error = SerdeError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Serializes maps into a [`Dictionary`].
+pub struct SerializeDictionary {
+    ser: VariantSerializer,
+    dict: Dictionary<Unique>,
+    key: Option<Variant>,
+}
+
+impl ser::SerializeMap for SerializeDictionary {
+    type Ok = Variant;
+    type Error = SerdeError;
+
+    #[inline]
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(self.ser)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("serialize_value called before serialize_key"))?;
+        self.dict.insert(key, value.serialize(self.ser)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerdeError> {
+        Ok(self.dict.owned_to_variant())
+    }
+}
+
+/// Serializes structs into a [`Dictionary`] keyed by field names, or into core types.
+pub struct SerializeStruct {
+    ser: VariantSerializer,
+    name: &'static str,
+    dict: Dictionary<Unique>,
+}
+
+impl ser::SerializeStruct for SerializeStruct {
+    type Ok = Variant;
+    type Error = SerdeError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.dict.insert(key, value.serialize(self.ser)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerdeError> {
+        let dict = self.dict.owned_to_variant();
+        if self.ser.core_types {
+            if let Some(variant) = core_type(self.name, &dict) {
+                return Ok(variant);
+            }
+        }
+        Ok(dict)
+    }
+}
+
+/// Serializes the contents of a tuple or struct enum variant, wrapping them in a single-entry
+/// [`Dictionary`] when done.
+pub struct SerializeEnumVariant<S> {
+    key: Variant,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for SerializeEnumVariant<SerializeArray> {
+    type Ok = Variant;
+    type Error = SerdeError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerdeError> {
+        Ok(wrap_variant(self.key, ser::SerializeSeq::end(self.inner)?))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeEnumVariant<SerializeStruct> {
+    type Ok = Variant;
+    type Error = SerdeError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerdeError> {
+        // Enum variants are never core types, even if they share a name
+        Ok(wrap_variant(self.key, self.inner.dict.owned_to_variant()))
+    }
+}
@@ -0,0 +1,1618 @@
+//! Text serialization compatible with Godot's `var2str` and `str2var`.
+//!
+//! This module implements the text syntax used by Godot 3's `VariantWriter` and `VariantParser`
+//! (see `core/variant_parser.cpp` in the engine source), which is used by `var2str`, `str2var`,
+//! `ConfigFile` and the values in `.tscn` and `.tres` files. Examples of this syntax include:
+//!
+//! ```text
+//! Vector2( 1, 2 )
+//! { "a": [ 1, Color( 1, 0, 0, 1 ) ] }
+//! PoolStringArray( "foo", "bar" )
+//! ```
+//!
+//! Values are represented by [`Value`], a pure-Rust mirror of [`VariantDispatch`]. Printing and
+//! parsing do not require a running engine. Conversion from and to `VariantDispatch` is
+//! available through [`Value::from_dispatch`] and [`Value::to_dispatch`].
+//!
+//! # Examples
+//!
+//! ```
+//! use gdnative::core_types::text::Value;
+//! use gdnative::core_types::Vector2;
+//!
+//! let value: Value = "[ 1, Vector2( 0.5, 2 ) ]".parse().unwrap();
+//! assert_eq!(
+//!     value,
+//!     Value::VariantArray(vec![Value::I64(1), Value::Vector2(Vector2::new(0.5, 2.0))]),
+//! );
+//! assert_eq!(value.to_string(), "[ 1, Vector2( 0.5, 2 ) ]");
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+use std::iter::Peekable;
+use std::ptr;
+use std::str::{Chars, FromStr};
+
+use crate::core_types::*;
+use crate::object::ownership::Unique;
+use crate::private::get_api;
+
+use super::marshal::MAX_DEPTH;
+
+/// A value in Godot's text syntax. This is a pure-Rust equivalent of [`VariantDispatch`].
+///
+/// Non-null objects are represented by [`Value::Object`]. Null objects are written as `null`,
+/// and parsed as [`Value::Nil`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    GodotString(String),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    NodePath(String),
+    Rid,
+    Object {
+        class: String,
+        properties: Vec<(String, Value)>,
+    },
+    /// Dictionary entries, in insertion order.
+    Dictionary(Vec<(Value, Value)>),
+    VariantArray(Vec<Value>),
+    ByteArray(Vec<u8>),
+    Int32Array(Vec<i32>),
+    Float32Array(Vec<real>),
+    StringArray(Vec<String>),
+    Vector2Array(Vec<Vector2>),
+    Vector3Array(Vec<Vector3>),
+    ColorArray(Vec<Color>),
+}
+
+impl Value {
+    /// Returns the `VariantType` corresponding to this value.
+    #[inline]
+    pub fn get_type(&self) -> VariantType {
+        match self {
+            Value::Nil => VariantType::Nil,
+            Value::Bool(_) => VariantType::Bool,
+            Value::I64(_) => VariantType::I64,
+            Value::F64(_) => VariantType::F64,
+            Value::GodotString(_) => VariantType::GodotString,
+            Value::Vector2(_) => VariantType::Vector2,
+            Value::Rect2(_) => VariantType::Rect2,
+            Value::Vector3(_) => VariantType::Vector3,
+            Value::Transform2D(_) => VariantType::Transform2D,
+            Value::Plane(_) => VariantType::Plane,
+            Value::Quat(_) => VariantType::Quat,
+            Value::Aabb(_) => VariantType::Aabb,
+            Value::Basis(_) => VariantType::Basis,
+            Value::Transform(_) => VariantType::Transform,
+            Value::Color(_) => VariantType::Color,
+            Value::NodePath(_) => VariantType::NodePath,
+            Value::Rid => VariantType::Rid,
+            Value::Object { .. } => VariantType::Object,
+            Value::Dictionary(_) => VariantType::Dictionary,
+            Value::VariantArray(_) => VariantType::VariantArray,
+            Value::ByteArray(_) => VariantType::ByteArray,
+            Value::Int32Array(_) => VariantType::Int32Array,
+            Value::Float32Array(_) => VariantType::Float32Array,
+            Value::StringArray(_) => VariantType::StringArray,
+            Value::Vector2Array(_) => VariantType::Vector2Array,
+            Value::Vector3Array(_) => VariantType::Vector3Array,
+            Value::ColorArray(_) => VariantType::ColorArray,
+        }
+    }
+
+    /// Converts an engine value into a `Value`.
+    ///
+    /// RIDs are converted into [`Value::Rid`], losing their identity, since they have no text
+    /// representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value contains non-null objects, since reading their properties
+    /// may execute arbitrary code.
+    #[inline]
+    pub fn from_dispatch(value: &VariantDispatch) -> Result<Self, UnsupportedObject> {
+        use VariantDispatch as V;
+
+        let value = match value {
+            V::Nil => Value::Nil,
+            V::Bool(b) => Value::Bool(*b),
+            V::I64(i) => Value::I64(*i),
+            V::F64(f) => Value::F64(*f),
+            V::GodotString(s) => Value::GodotString(s.to_string()),
+            V::Vector2(v) => Value::Vector2(*v),
+            V::Rect2(r) => Value::Rect2(*r),
+            V::Vector3(v) => Value::Vector3(*v),
+            V::Transform2D(t) => Value::Transform2D(*t),
+            V::Plane(p) => Value::Plane(*p),
+            V::Quat(q) => Value::Quat(*q),
+            V::Aabb(a) => Value::Aabb(*a),
+            V::Basis(b) => Value::Basis(*b),
+            V::Transform(t) => Value::Transform(*t),
+            V::Color(c) => Value::Color(*c),
+            V::NodePath(path) => Value::NodePath(path.to_string()),
+            V::Rid(_) => Value::Rid,
+            V::Object(variant) => {
+                let obj = unsafe { (get_api().godot_variant_as_object)(variant.sys()) };
+                match ptr::NonNull::new(obj) {
+                    None => Value::Nil,
+                    Some(obj) => {
+                        let obj = unsafe {
+                            crate::object::RawObject::<
+                                crate::private::ManuallyManagedClassPlaceholder,
+                            >::from_sys_ref_unchecked(obj)
+                        };
+                        return Err(UnsupportedObject {
+                            class: obj.class_name(),
+                        });
+                    }
+                }
+            }
+            V::Dictionary(dict) => Value::Dictionary(
+                dict.iter()
+                    .map(|(key, value)| {
+                        Ok((
+                            Value::from_dispatch(&key.dispatch())?,
+                            Value::from_dispatch(&value.dispatch())?,
+                        ))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            V::VariantArray(arr) => Value::VariantArray(
+                arr.iter()
+                    .map(|value| Value::from_dispatch(&value.dispatch()))
+                    .collect::<Result<_, _>>()?,
+            ),
+            V::ByteArray(arr) => Value::ByteArray(arr.to_vec()),
+            V::Int32Array(arr) => Value::Int32Array(arr.to_vec()),
+            V::Float32Array(arr) => Value::Float32Array(arr.to_vec()),
+            V::StringArray(arr) => {
+                Value::StringArray(arr.read().iter().map(GodotString::to_string).collect())
+            }
+            V::Vector2Array(arr) => Value::Vector2Array(arr.to_vec()),
+            V::Vector3Array(arr) => Value::Vector3Array(arr.to_vec()),
+            V::ColorArray(arr) => Value::ColorArray(arr.to_vec()),
+        };
+
+        Ok(value)
+    }
+
+    /// Converts this value into an engine value.
+    ///
+    /// [`Value::Rid`] is converted into an empty RID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value contains [`Value::Object`], since instantiating classes and
+    /// setting their properties may execute arbitrary code.
+    #[inline]
+    pub fn to_dispatch(&self) -> Result<VariantDispatch, UnsupportedObject> {
+        use VariantDispatch as V;
+
+        let value = match self {
+            Value::Nil => V::Nil,
+            Value::Bool(b) => V::Bool(*b),
+            Value::I64(i) => V::I64(*i),
+            Value::F64(f) => V::F64(*f),
+            Value::GodotString(s) => V::GodotString(GodotString::from_str(s)),
+            Value::Vector2(v) => V::Vector2(*v),
+            Value::Rect2(r) => V::Rect2(*r),
+            Value::Vector3(v) => V::Vector3(*v),
+            Value::Transform2D(t) => V::Transform2D(*t),
+            Value::Plane(p) => V::Plane(*p),
+            Value::Quat(q) => V::Quat(*q),
+            Value::Aabb(a) => V::Aabb(*a),
+            Value::Basis(b) => V::Basis(*b),
+            Value::Transform(t) => V::Transform(*t),
+            Value::Color(c) => V::Color(*c),
+            Value::NodePath(path) => V::NodePath(NodePath::from_str(path)),
+            Value::Rid => V::Rid(Rid::new()),
+            Value::Object { class, .. } => {
+                return Err(UnsupportedObject {
+                    class: class.clone(),
+                })
+            }
+            Value::Dictionary(entries) => {
+                let dict = Dictionary::<Unique>::new();
+                for (key, value) in entries {
+                    let key = Variant::from(&key.to_dispatch()?);
+                    let value = Variant::from(&value.to_dispatch()?);
+                    dict.insert(key, value);
+                }
+                V::Dictionary(dict.into_shared())
+            }
+            Value::VariantArray(elements) => {
+                let arr = VariantArray::<Unique>::new();
+                for element in elements {
+                    arr.push(Variant::from(&element.to_dispatch()?));
+                }
+                V::VariantArray(arr.into_shared())
+            }
+            Value::ByteArray(vec) => V::ByteArray(PoolArray::from_slice(vec)),
+            Value::Int32Array(vec) => V::Int32Array(PoolArray::from_slice(vec)),
+            Value::Float32Array(vec) => V::Float32Array(PoolArray::from_slice(vec)),
+            Value::StringArray(vec) => V::StringArray(
+                vec.iter()
+                    .map(GodotString::from_str)
+                    .collect::<PoolArray<_>>(),
+            ),
+            Value::Vector2Array(vec) => V::Vector2Array(PoolArray::from_slice(vec)),
+            Value::Vector3Array(vec) => V::Vector3Array(PoolArray::from_slice(vec)),
+            Value::ColorArray(vec) => V::ColorArray(PoolArray::from_slice(vec)),
+        };
+
+        Ok(value)
+    }
+}
+
+/// Error returned when converting values containing objects between [`Value`] and
+/// [`VariantDispatch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedObject {
+    class: String,
+}
+
+impl UnsupportedObject {
+    /// Returns the class name of the object that could not be converted.
+    #[inline]
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+}
+
+impl fmt::Display for UnsupportedObject {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot convert object of class {}", self.class)
+    }
+}
+
+impl std::error::Error for UnsupportedObject {}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Printing
+
+/// Prints the value in the format of `var2str`.
+///
+/// Like in Godot 3, floating-point numbers are printed with 6 significant digits, and
+/// dictionary keys are sorted.
+impl fmt::Display for Value {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_value(f, self)
+    }
+}
+
+fn write_value<W: Write>(w: &mut W, value: &Value) -> fmt::Result {
+    match value {
+        Value::Object { class, properties } => {
+            write!(w, "Object({class},")?;
+            for (i, (name, value)) in properties.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                w.write_char('"')?;
+                write_escaped(w, name, true)?;
+                w.write_str("\":")?;
+                write_value(w, value)?;
+            }
+            w.write_str(")\n")
+        }
+        Value::Dictionary(entries) => {
+            let mut sorted = entries.iter().collect::<Vec<_>>();
+            sorted.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+
+            w.write_str("{\n")?;
+            for (i, (key, value)) in sorted.into_iter().enumerate() {
+                if i > 0 {
+                    w.write_str(",\n")?;
+                }
+                write_value(w, key)?;
+                w.write_str(": ")?;
+                write_value(w, value)?;
+            }
+            w.write_str("\n}")
+        }
+        Value::VariantArray(elements) => {
+            w.write_str("[ ")?;
+            for (i, element) in elements.iter().enumerate() {
+                if i > 0 {
+                    w.write_str(", ")?;
+                }
+                write_value(w, element)?;
+            }
+            w.write_str(" ]")
+        }
+        _ => write_leaf(w, value),
+    }
+}
+
+/// Writes values of non-recursive types. This is split from `write_value` to keep its stack
+/// frame small for deeply nested values.
+#[inline(never)]
+fn write_leaf<W: Write>(w: &mut W, value: &Value) -> fmt::Result {
+    match value {
+        Value::Object { .. } | Value::Dictionary(_) | Value::VariantArray(_) => {
+            unreachable!("recursive types are handled by write_value")
+        }
+        Value::Nil => w.write_str("null"),
+        Value::Bool(b) => write!(w, "{b}"),
+        Value::I64(i) => write!(w, "{i}"),
+        Value::F64(f) => {
+            // Godot 3 converts to `real_t` before printing
+            let s = real_to_string(real::from_f64(*f).as_f64());
+            w.write_str(&s)?;
+            if s.bytes().all(|b| b == b'-' || b.is_ascii_digit()) {
+                w.write_str(".0")?;
+            }
+            Ok(())
+        }
+        Value::GodotString(s) => {
+            w.write_char('"')?;
+            write_escaped(w, s, false)?;
+            w.write_char('"')
+        }
+        Value::Vector2(v) => write_reals(w, "Vector2", &[v.x, v.y]),
+        Value::Rect2(r) => write_reals(
+            w,
+            "Rect2",
+            &[r.position.x, r.position.y, r.size.x, r.size.y],
+        ),
+        Value::Vector3(v) => write_reals(w, "Vector3", &[v.x, v.y, v.z]),
+        Value::Transform2D(t) => write_reals(
+            w,
+            "Transform2D",
+            &[t.a.x, t.a.y, t.b.x, t.b.y, t.origin.x, t.origin.y],
+        ),
+        Value::Plane(p) => write_reals(w, "Plane", &[p.normal.x, p.normal.y, p.normal.z, p.d]),
+        Value::Quat(q) => write_reals(w, "Quat", &[q.x, q.y, q.z, q.w]),
+        Value::Aabb(a) => write_reals(
+            w,
+            "AABB",
+            &[
+                a.position.x,
+                a.position.y,
+                a.position.z,
+                a.size.x,
+                a.size.y,
+                a.size.z,
+            ],
+        ),
+        Value::Basis(b) => write_reals(w, "Basis", &basis_to_reals(b)),
+        Value::Transform(t) => {
+            let mut reals = basis_to_reals(&t.basis).to_vec();
+            reals.extend_from_slice(&[t.origin.x, t.origin.y, t.origin.z]);
+            write_reals(w, "Transform", &reals)
+        }
+        Value::Color(c) => write_reals(w, "Color", &[c.r, c.g, c.b, c.a]),
+        Value::NodePath(path) => {
+            w.write_str("NodePath(\"")?;
+            write_escaped(w, path, true)?;
+            w.write_str("\")")
+        }
+        Value::Rid => w.write_str("RID()"),
+        Value::ByteArray(vec) => write_list(w, "PoolByteArray", vec, |w, b| write!(w, "{b}")),
+        Value::Int32Array(vec) => write_list(w, "PoolIntArray", vec, |w, i| write!(w, "{i}")),
+        Value::Float32Array(vec) => write_list(w, "PoolRealArray", vec, |w, f| {
+            w.write_str(&real_to_string(f.as_f64()))
+        }),
+        Value::StringArray(vec) => write_list(w, "PoolStringArray", vec, |w, s| {
+            w.write_char('"')?;
+            write_escaped(w, s, true)?;
+            w.write_char('"')
+        }),
+        Value::Vector2Array(vec) => {
+            let reals = vec.iter().flat_map(|v| [v.x, v.y]).collect::<Vec<_>>();
+            write_reals(w, "PoolVector2Array", &reals)
+        }
+        Value::Vector3Array(vec) => {
+            let reals = vec.iter().flat_map(|v| [v.x, v.y, v.z]).collect::<Vec<_>>();
+            write_reals(w, "PoolVector3Array", &reals)
+        }
+        Value::ColorArray(vec) => {
+            let reals = vec
+                .iter()
+                .flat_map(|c| [c.r, c.g, c.b, c.a])
+                .collect::<Vec<_>>();
+            write_reals(w, "PoolColorArray", &reals)
+        }
+    }
+}
+
+fn basis_to_reals(b: &Basis) -> [real; 9] {
+    let [x, y, z] = b.elements;
+    [x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z]
+}
+
+fn write_list<W: Write, T>(
+    w: &mut W,
+    constructor: &str,
+    items: &[T],
+    mut write_item: impl FnMut(&mut W, &T) -> fmt::Result,
+) -> fmt::Result {
+    write!(w, "{constructor}( ")?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            w.write_str(", ")?;
+        }
+        write_item(w, item)?;
+    }
+    w.write_str(" )")
+}
+
+fn write_reals<W: Write, R: RealConv>(w: &mut W, constructor: &str, reals: &[R]) -> fmt::Result {
+    write_list(w, constructor, reals, |w, f| {
+        w.write_str(&real_to_string(f.as_f64()))
+    })
+}
+
+/// Escapes a string like `String::c_escape` (if `all` is true), or
+/// `String::c_escape_multiline` otherwise.
+fn write_escaped<W: Write>(w: &mut W, s: &str, all: bool) -> fmt::Result {
+    for c in s.chars() {
+        let escaped = match c {
+            '\\' => "\\\\",
+            '"' => "\\\"",
+            _ if !all => {
+                w.write_char(c)?;
+                continue;
+            }
+            '\x07' => "\\a",
+            '\x08' => "\\b",
+            '\x0c' => "\\f",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            '\x0b' => "\\v",
+            '\'' => "\\'",
+            '?' => "\\?",
+            _ => {
+                w.write_char(c)?;
+                continue;
+            }
+        };
+        w.write_str(escaped)?;
+    }
+    Ok(())
+}
+
+/// Formats a number like `rtosfix` in the engine, which is `printf("%lg")` with special cases
+/// for zero and non-finite values.
+fn real_to_string(f: f64) -> String {
+    if f == 0.0 {
+        // Avoids printing negative zero, like the engine
+        return "0".into();
+    }
+    if f.is_nan() {
+        return "nan".into();
+    }
+    if f.is_infinite() {
+        return if f > 0.0 { "inf" } else { "inf_neg" }.into();
+    }
+
+    fn strip_zeros(s: &str) -> &str {
+        if s.contains('.') {
+            s.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            s
+        }
+    }
+
+    // `%g` uses 6 significant digits, and scientific notation for exponents below -4 or above 5
+    let scientific = format!("{f:.5e}");
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("scientific notation should contain an exponent");
+    let exponent: i32 = exponent.parse().expect("exponent should be an integer");
+
+    if !(-4..6).contains(&exponent) {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", strip_zeros(mantissa), sign, exponent.abs())
+    } else {
+        let precision = (5 - exponent) as usize;
+        strip_zeros(&format!("{f:.precision$}")).to_owned()
+    }
+}
+
+/// Orders dictionary keys for printing, like the engine's `Variant::operator<`: by type first, so
+/// that e.g. all integer keys come before all float keys. Numbers, strings, booleans and vectors
+/// of the same type are then compared by value. Other values are ordered by type only.
+fn compare_keys(a: &Value, b: &Value) -> Ordering {
+    let by_value = match (a, b) {
+        (Value::I64(a), Value::I64(b)) => a.cmp(b),
+        (Value::F64(a), Value::F64(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::GodotString(a), Value::GodotString(b)) => a.cmp(b),
+        (Value::Vector2(a), Value::Vector2(b)) => [a.x, a.y]
+            .partial_cmp(&[b.x, b.y])
+            .unwrap_or(Ordering::Equal),
+        (Value::Vector3(a), Value::Vector3(b)) => [a.x, a.y, a.z]
+            .partial_cmp(&[b.x, b.y, b.z])
+            .unwrap_or(Ordering::Equal),
+        _ => Ordering::Equal,
+    };
+
+    (a.get_type() as u32)
+        .cmp(&(b.get_type() as u32))
+        .then(by_value)
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Parsing
+
+/// Parses a value in the format of `str2var`.
+///
+/// In addition to the output of `var2str`, this accepts the legacy constructor names of
+/// Godot 2 (like `Matrix3` or `IntArray`), HTML colors like `#ff8000`, `;` line comments and
+/// trailing commas, like the engine. Unlike the engine, trailing characters after the value
+/// are rejected.
+impl FromStr for Value {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let value = parser.value()?;
+        let (token, pos) = parser.next()?;
+        if token != Token::Eof {
+            return Err(pos.error(ParseErrorKind::TrailingCharacters));
+        }
+        Ok(value)
+    }
+}
+
+/// Error returned when parsing a [`Value`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// Returns the line of the error, starting at 1.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column of the error in characters, starting at 1.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the kind of the error.
+    #[inline]
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Kinds of [`ParseError`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// A character that cannot start a token.
+    UnexpectedCharacter(char),
+    /// A string without a closing quote.
+    UnterminatedString,
+    /// An invalid `\u` escape sequence in a string.
+    InvalidEscape,
+    /// A number that cannot be represented.
+    InvalidNumber(String),
+    /// An HTML color that is not in the `#RRGGBB` or `#AARRGGBB` format.
+    InvalidColor(String),
+    /// A token other than the expected one.
+    UnexpectedToken {
+        expected: &'static str,
+        found: String,
+    },
+    /// An identifier that is not a keyword or a known constructor.
+    UnknownIdentifier(String),
+    /// A constructor with the wrong number of arguments.
+    InvalidArgumentCount { constructor: String, count: usize },
+    /// The value is nested deeper than [`MAX_DEPTH`](super::marshal::MAX_DEPTH).
+    TooDeep,
+    /// Additional characters after a complete value.
+    TrailingCharacters,
+}
+
+impl fmt::Display for ParseErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ParseErrorKind as E;
+
+        match self {
+            E::UnexpectedCharacter(c) => write!(f, "unexpected character {c:?}"),
+            E::UnterminatedString => write!(f, "unterminated string"),
+            E::InvalidEscape => write!(f, "invalid escape sequence"),
+            E::InvalidNumber(num) => write!(f, "invalid number `{num}`"),
+            E::InvalidColor(color) => write!(f, "invalid color `{color}`"),
+            E::UnexpectedToken { expected, found } => {
+                write!(f, "expected {expected}, found {found}")
+            }
+            E::UnknownIdentifier(ident) => write!(f, "unknown identifier `{ident}`"),
+            E::InvalidArgumentCount { constructor, count } => {
+                write!(f, "invalid number of arguments for {constructor}: {count}")
+            }
+            E::TooDeep => write!(f, "value is nested deeper than {MAX_DEPTH} levels"),
+            E::TrailingCharacters => write!(f, "unexpected characters after value"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Pos {
+    line: usize,
+    column: usize,
+}
+
+impl Pos {
+    fn error(self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    CurlyOpen,
+    CurlyClose,
+    BracketOpen,
+    BracketClose,
+    ParenOpen,
+    ParenClose,
+    Colon,
+    Comma,
+    Ident(String),
+    String(String),
+    Int(i64),
+    Real(f64),
+    Color(Color),
+    Eof,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::CurlyOpen => "'{'".into(),
+            Token::CurlyClose => "'}'".into(),
+            Token::BracketOpen => "'['".into(),
+            Token::BracketClose => "']'".into(),
+            Token::ParenOpen => "'('".into(),
+            Token::ParenClose => "')'".into(),
+            Token::Colon => "':'".into(),
+            Token::Comma => "','".into(),
+            Token::Ident(ident) => format!("identifier `{ident}`"),
+            Token::String(_) => "string".into(),
+            Token::Int(_) | Token::Real(_) => "number".into(),
+            Token::Color(_) => "color".into(),
+            Token::Eof => "end of input".into(),
+        }
+    }
+}
+
+/// A container being parsed.
+enum Frame {
+    Array(Vec<Value>),
+    Dictionary {
+        entries: Vec<(Value, Value)>,
+        key: Option<Value>,
+    },
+    Object {
+        class: String,
+        properties: Vec<(String, Value)>,
+        name: Option<String>,
+    },
+}
+
+impl Frame {
+    fn into_value(self) -> Value {
+        match self {
+            Frame::Array(elements) => Value::VariantArray(elements),
+            Frame::Dictionary { entries, .. } => Value::Dictionary(entries),
+            Frame::Object {
+                class, properties, ..
+            } => Value::Object { class, properties },
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Parser {
+            chars: s.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn pos(&self) -> Pos {
+        Pos {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn bump_while(&mut self, buf: &mut String, mut pred: impl FnMut(char) -> bool) {
+        while let Some(&c) = self.chars.peek() {
+            if !pred(c) {
+                break;
+            }
+            buf.push(c);
+            self.bump();
+        }
+    }
+
+    /// Reads the next token and its position.
+    fn next(&mut self) -> Result<(Token, Pos), ParseError> {
+        loop {
+            let pos = self.pos();
+            let c = match self.chars.peek() {
+                Some(&c) => c,
+                None => return Ok((Token::Eof, pos)),
+            };
+
+            let token = match c {
+                _ if c <= ' ' => {
+                    self.bump();
+                    continue;
+                }
+                ';' => {
+                    // Comment until the end of the line
+                    while self.bump().map_or(false, |c| c != '\n') {}
+                    continue;
+                }
+                '{' | '}' | '[' | ']' | '(' | ')' | ':' | ',' => {
+                    self.bump();
+                    match c {
+                        '{' => Token::CurlyOpen,
+                        '}' => Token::CurlyClose,
+                        '[' => Token::BracketOpen,
+                        ']' => Token::BracketClose,
+                        '(' => Token::ParenOpen,
+                        ')' => Token::ParenClose,
+                        ':' => Token::Colon,
+                        _ => Token::Comma,
+                    }
+                }
+                '"' => {
+                    self.bump();
+                    Token::String(self.string_contents(pos)?)
+                }
+                '#' => {
+                    self.bump();
+                    let mut html = String::from("#");
+                    self.bump_while(&mut html, |c| c.is_ascii_hexdigit());
+                    match Color::from_html(&html) {
+                        Some(color) => Token::Color(color),
+                        None => return Err(pos.error(ParseErrorKind::InvalidColor(html))),
+                    }
+                }
+                '-' | '0'..='9' => self.number(pos)?,
+                '_' | 'a'..='z' | 'A'..='Z' => {
+                    let mut ident = String::new();
+                    self.bump_while(&mut ident, |c| c == '_' || c.is_ascii_alphanumeric());
+                    Token::Ident(ident)
+                }
+                _ => return Err(pos.error(ParseErrorKind::UnexpectedCharacter(c))),
+            };
+
+            return Ok((token, pos));
+        }
+    }
+
+    fn string_contents(&mut self, pos: Pos) -> Result<String, ParseError> {
+        let mut s = String::new();
+        loop {
+            let c = self
+                .bump()
+                .ok_or_else(|| pos.error(ParseErrorKind::UnterminatedString))?;
+
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let escape_pos = self.pos();
+                    let next = self
+                        .bump()
+                        .ok_or_else(|| pos.error(ParseErrorKind::UnterminatedString))?;
+                    let c = match next {
+                        'a' => '\x07',
+                        'b' => '\x08',
+                        't' => '\t',
+                        'n' => '\n',
+                        'v' => '\x0b',
+                        'f' => '\x0c',
+                        'r' => '\r',
+                        'u' => {
+                            let mut hex = String::new();
+                            for _ in 0..4 {
+                                match self.bump() {
+                                    Some(c) if c.is_ascii_hexdigit() => hex.push(c),
+                                    _ => {
+                                        return Err(escape_pos.error(ParseErrorKind::InvalidEscape))
+                                    }
+                                }
+                            }
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| escape_pos.error(ParseErrorKind::InvalidEscape))?
+                        }
+                        // Includes quotes and backslashes
+                        other => other,
+                    };
+                    s.push(c);
+                }
+                _ => s.push(c),
+            }
+        }
+    }
+
+    /// Reads a number with the same rules as the engine's tokenizer.
+    fn number(&mut self, pos: Pos) -> Result<Token, ParseError> {
+        let mut num = String::new();
+        if self.chars.peek() == Some(&'-') {
+            num.push('-');
+            self.bump();
+        }
+
+        let mut is_float = false;
+        self.bump_while(&mut num, |c| c.is_ascii_digit());
+        if self.chars.peek() == Some(&'.') {
+            is_float = true;
+            num.push('.');
+            self.bump();
+            self.bump_while(&mut num, |c| c.is_ascii_digit());
+        }
+        if self.chars.peek() == Some(&'e') {
+            is_float = true;
+            num.push('e');
+            self.bump();
+            if let Some(&sign @ ('+' | '-')) = self.chars.peek() {
+                num.push(sign);
+                self.bump();
+            }
+            self.bump_while(&mut num, |c| c.is_ascii_digit());
+        }
+
+        let token = if is_float {
+            num.parse().ok().map(Token::Real)
+        } else {
+            num.parse().ok().map(Token::Int)
+        };
+
+        token.ok_or_else(|| pos.error(ParseErrorKind::InvalidNumber(num)))
+    }
+
+    fn expect(&mut self, expected_token: Token, expected: &'static str) -> Result<(), ParseError> {
+        let (token, pos) = self.next()?;
+        if token == expected_token {
+            Ok(())
+        } else {
+            Err(unexpected(&token, pos, expected))
+        }
+    }
+
+    /// Parses a complete value. Containers are tracked on an explicit stack instead of
+    /// recursion, so deeply nested input cannot overflow the call stack.
+    fn value(&mut self) -> Result<Value, ParseError> {
+        let mut stack = Vec::new();
+        let (mut token, mut pos) = self.next()?;
+
+        loop {
+            let mut value = match token {
+                Token::CurlyOpen | Token::BracketOpen => {
+                    if stack.len() >= MAX_DEPTH {
+                        return Err(pos.error(ParseErrorKind::TooDeep));
+                    }
+                    stack.push(if token == Token::CurlyOpen {
+                        Frame::Dictionary {
+                            entries: Vec::new(),
+                            key: None,
+                        }
+                    } else {
+                        Frame::Array(Vec::new())
+                    });
+                    None
+                }
+                Token::Ident(ident) if ident == "Object" => {
+                    if stack.len() >= MAX_DEPTH {
+                        return Err(pos.error(ParseErrorKind::TooDeep));
+                    }
+                    self.expect(Token::ParenOpen, "'('")?;
+                    let class = match self.next()? {
+                        (Token::Ident(class), _) => class,
+                        (token, pos) => return Err(unexpected(&token, pos, "class name")),
+                    };
+                    stack.push(Frame::Object {
+                        class,
+                        properties: Vec::new(),
+                        name: None,
+                    });
+                    None
+                }
+                Token::Ident(ident) => Some(self.constructor(ident, pos)?),
+                Token::String(s) => Some(Value::GodotString(s)),
+                Token::Int(i) => Some(Value::I64(i)),
+                Token::Real(f) => Some(Value::F64(f)),
+                Token::Color(c) => Some(Value::Color(c)),
+                token => return Err(unexpected(&token, pos, "value")),
+            };
+
+            // Add complete values to their containers, until another value needs to be parsed
+            loop {
+                let frame = match stack.last_mut() {
+                    Some(frame) => frame,
+                    None => return Ok(value.expect("value should be complete")),
+                };
+
+                match self.step(frame, value.take())? {
+                    Some((next_token, next_pos)) => {
+                        token = next_token;
+                        pos = next_pos;
+                        break;
+                    }
+                    None => {
+                        let frame = stack.pop().expect("stack should not be empty");
+                        value = Some(frame.into_value());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Adds `value` to the container in `frame` (if any), then reads the next token. Returns
+    /// the first token of the next value in the container, or `None` if the container is
+    /// closed.
+    fn step(
+        &mut self,
+        frame: &mut Frame,
+        value: Option<Value>,
+    ) -> Result<Option<(Token, Pos)>, ParseError> {
+        match frame {
+            Frame::Array(elements) => {
+                let is_first = value.is_none();
+                elements.extend(value);
+                self.separator(Token::BracketClose, "']' or ','", is_first)
+            }
+            Frame::Dictionary { entries, key } => {
+                let is_first = value.is_none();
+                match (value, key.take()) {
+                    (Some(value), Some(key)) => entries.push((key, value)),
+                    (Some(value), None) => {
+                        *key = Some(value);
+                        self.expect(Token::Colon, "':'")?;
+                        return self.next().map(Some);
+                    }
+                    (None, _) => {}
+                }
+                self.separator(Token::CurlyClose, "'}' or ','", is_first)
+            }
+            Frame::Object {
+                properties, name, ..
+            } => {
+                if let (Some(value), Some(name)) = (value, name.take()) {
+                    properties.push((name, value));
+                }
+
+                // The first property is also preceded by a comma, after the class name
+                match self.separator(Token::ParenClose, "')' or ','", false)? {
+                    None => Ok(None),
+                    Some((Token::String(property), _)) => {
+                        *name = Some(property);
+                        self.expect(Token::Colon, "':'")?;
+                        self.next().map(Some)
+                    }
+                    Some((token, pos)) => Err(unexpected(&token, pos, "property name")),
+                }
+            }
+        }
+    }
+
+    /// Reads the separator before the next element of a container. Returns the first token of
+    /// the next element, or `None` if the container is closed. Trailing commas are allowed.
+    fn separator(
+        &mut self,
+        close: Token,
+        expected: &'static str,
+        is_first: bool,
+    ) -> Result<Option<(Token, Pos)>, ParseError> {
+        let (token, pos) = self.next()?;
+        if token == close {
+            return Ok(None);
+        }
+        if is_first {
+            return Ok(Some((token, pos)));
+        }
+        if token != Token::Comma {
+            return Err(unexpected(&token, pos, expected));
+        }
+
+        let (token, pos) = self.next()?;
+        if token == close {
+            Ok(None)
+        } else {
+            Ok(Some((token, pos)))
+        }
+    }
+
+    /// Parses keywords and constructors of non-recursive types.
+    fn constructor(&mut self, ident: String, pos: Pos) -> Result<Value, ParseError> {
+        let value = match ident.as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            "null" | "nil" => Value::Nil,
+            "inf" | "inf_neg" | "nan" => Value::F64(keyword_real(&ident).unwrap()),
+            "Vector2" => {
+                let [x, y] = self.reals(&ident, pos)?;
+                Value::Vector2(Vector2::new(x, y))
+            }
+            "Rect2" => {
+                let [x, y, w, h] = self.reals(&ident, pos)?;
+                Value::Rect2(Rect2::new(Vector2::new(x, y), Vector2::new(w, h)))
+            }
+            "Vector3" => {
+                let [x, y, z] = self.reals(&ident, pos)?;
+                Value::Vector3(Vector3::new(x, y, z))
+            }
+            "Transform2D" | "Matrix32" => {
+                let [ax, ay, bx, by, ox, oy] = self.reals(&ident, pos)?;
+                Value::Transform2D(Transform2D {
+                    a: Vector2::new(ax, ay),
+                    b: Vector2::new(bx, by),
+                    origin: Vector2::new(ox, oy),
+                })
+            }
+            "Plane" => {
+                let [x, y, z, d] = self.reals(&ident, pos)?;
+                Value::Plane(Plane::new(Vector3::new(x, y, z), d))
+            }
+            "Quat" => {
+                let [x, y, z, w] = self.reals(&ident, pos)?;
+                Value::Quat(Quat::new(x, y, z, w))
+            }
+            "AABB" | "Rect3" => {
+                let [px, py, pz, sx, sy, sz] = self.reals(&ident, pos)?;
+                Value::Aabb(Aabb::new(
+                    Vector3::new(px, py, pz),
+                    Vector3::new(sx, sy, sz),
+                ))
+            }
+            "Basis" | "Matrix3" => {
+                let r: [real; 9] = self.reals(&ident, pos)?;
+                Value::Basis(basis_from_reals(&r))
+            }
+            "Transform" => {
+                let r: [real; 12] = self.reals(&ident, pos)?;
+                Value::Transform(Transform {
+                    basis: basis_from_reals(&r[..9]),
+                    origin: Vector3::new(r[9], r[10], r[11]),
+                })
+            }
+            "Color" => {
+                let [r, g, b, a] = self.reals(&ident, pos)?;
+                Value::Color(Color::from_rgba(r, g, b, a))
+            }
+            "NodePath" => {
+                let [path] = self.fixed_args(&ident, pos, |token| match token {
+                    Token::String(s) => Some(s),
+                    _ => None,
+                })?;
+                Value::NodePath(path)
+            }
+            "RID" => {
+                let [] = self.fixed_args(&ident, pos, |_| None::<()>)?;
+                Value::Rid
+            }
+            "PoolByteArray" | "ByteArray" => {
+                // Out-of-range values are truncated, like in the engine
+                let ints = self.args(number_arg)?;
+                Value::ByteArray(ints.into_iter().map(|n| n as i64 as u8).collect())
+            }
+            "PoolIntArray" | "IntArray" => {
+                let ints = self.args(number_arg)?;
+                Value::Int32Array(ints.into_iter().map(|n| n as i64 as i32).collect())
+            }
+            "PoolRealArray" | "RealArray" | "FloatArray" => {
+                let reals = self.args(number_arg)?;
+                Value::Float32Array(reals.into_iter().map(real::from_f64).collect())
+            }
+            "PoolStringArray" | "StringArray" => {
+                Value::StringArray(self.args(|token| match token {
+                    Token::String(s) => Some(s),
+                    _ => None,
+                })?)
+            }
+            "PoolVector2Array" | "Vector2Array" => {
+                let reals = self.real_groups(&ident, pos, 2)?;
+                Value::Vector2Array(reals.chunks(2).map(|r| Vector2::new(r[0], r[1])).collect())
+            }
+            "PoolVector3Array" | "Vector3Array" => {
+                let reals = self.real_groups(&ident, pos, 3)?;
+                Value::Vector3Array(
+                    reals
+                        .chunks(3)
+                        .map(|r| Vector3::new(r[0], r[1], r[2]))
+                        .collect(),
+                )
+            }
+            "PoolColorArray" | "ColorArray" => {
+                let reals = self.real_groups(&ident, pos, 4)?;
+                Value::ColorArray(
+                    reals
+                        .chunks(4)
+                        .map(|r| Color::from_rgba(r[0], r[1], r[2], r[3]))
+                        .collect(),
+                )
+            }
+            _ => return Err(pos.error(ParseErrorKind::UnknownIdentifier(ident))),
+        };
+
+        Ok(value)
+    }
+
+    /// Parses a parenthesized, comma-separated argument list. `arg` converts each token into an
+    /// argument, or returns `None` if the token is not valid in the list.
+    fn args<T>(&mut self, mut arg: impl FnMut(Token) -> Option<T>) -> Result<Vec<T>, ParseError> {
+        self.expect(Token::ParenOpen, "'('")?;
+
+        let mut args = Vec::new();
+        loop {
+            let (token, pos) = self.next()?;
+            let (token, pos) = match token {
+                Token::ParenClose => break,
+                Token::Comma if !args.is_empty() => match self.next()? {
+                    (Token::ParenClose, _) => break,
+                    next => next,
+                },
+                token if args.is_empty() => (token, pos),
+                token => return Err(unexpected(&token, pos, "')' or ','")),
+            };
+
+            let description = token.describe();
+            match arg(token) {
+                Some(value) => args.push(value),
+                None => {
+                    return Err(pos.error(ParseErrorKind::UnexpectedToken {
+                        expected: "constructor argument",
+                        found: description,
+                    }))
+                }
+            }
+        }
+
+        Ok(args)
+    }
+
+    fn fixed_args<T, const N: usize>(
+        &mut self,
+        constructor: &str,
+        pos: Pos,
+        arg: impl FnMut(Token) -> Option<T>,
+    ) -> Result<[T; N], ParseError> {
+        let args = self.args(arg)?;
+        let count = args.len();
+        args.try_into().map_err(|_| {
+            pos.error(ParseErrorKind::InvalidArgumentCount {
+                constructor: constructor.to_owned(),
+                count,
+            })
+        })
+    }
+
+    fn reals<const N: usize, R: RealConv>(
+        &mut self,
+        constructor: &str,
+        pos: Pos,
+    ) -> Result<[R; N], ParseError> {
+        let reals: [f64; N] = self.fixed_args(constructor, pos, number_arg)?;
+        Ok(reals.map(R::from_f64))
+    }
+
+    fn real_groups<R: RealConv>(
+        &mut self,
+        constructor: &str,
+        pos: Pos,
+        group: usize,
+    ) -> Result<Vec<R>, ParseError> {
+        let reals = self.args(number_arg)?;
+        if reals.len() % group != 0 {
+            return Err(pos.error(ParseErrorKind::InvalidArgumentCount {
+                constructor: constructor.to_owned(),
+                count: reals.len(),
+            }));
+        }
+        Ok(reals.into_iter().map(R::from_f64).collect())
+    }
+}
+
+fn unexpected(token: &Token, pos: Pos, expected: &'static str) -> ParseError {
+    pos.error(ParseErrorKind::UnexpectedToken {
+        expected,
+        found: token.describe(),
+    })
+}
+
+fn keyword_real(ident: &str) -> Option<f64> {
+    match ident {
+        "inf" => Some(f64::INFINITY),
+        "inf_neg" => Some(f64::NEG_INFINITY),
+        "nan" => Some(f64::NAN),
+        _ => None,
+    }
+}
+
+/// Accepts numbers, and the `inf`, `inf_neg` and `nan` keywords, as constructor arguments.
+fn number_arg(token: Token) -> Option<f64> {
+    match token {
+        Token::Int(i) => Some(i as f64),
+        Token::Real(f) => Some(f),
+        Token::Ident(ident) => keyword_real(&ident),
+        _ => None,
+    }
+}
+
+fn basis_from_reals(r: &[real]) -> Basis {
+    Basis::from_rows(
+        Vector3::new(r[0], r[1], r[2]),
+        Vector3::new(r[3], r[4], r[5]),
+        Vector3::new(r[6], r[7], r[8]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Value {
+        s.parse()
+            .unwrap_or_else(|err| panic!("{s:?} should parse: {err}"))
+    }
+
+    fn parse_err(s: &str) -> (usize, usize, ParseErrorKind) {
+        let err = s.parse::<Value>().expect_err("should fail");
+        (err.line(), err.column(), err.kind().clone())
+    }
+
+    #[test]
+    fn print_numbers() {
+        assert_eq!("1.0", Value::F64(1.0).to_string());
+        assert_eq!("0.1", Value::F64(0.1).to_string());
+        assert_eq!("-2.5", Value::F64(-2.5).to_string());
+        assert_eq!("0.333333", Value::F64(1.0 / 3.0).to_string());
+        assert_eq!("123457.0", Value::F64(123456.7).to_string());
+        assert_eq!("1.23457e+06", Value::F64(1234567.0).to_string());
+        assert_eq!("1e-05", Value::F64(0.00001).to_string());
+        assert_eq!("0.0", Value::F64(-0.0).to_string());
+        assert_eq!("inf", Value::F64(f64::INFINITY).to_string());
+        assert_eq!("inf_neg", Value::F64(f64::NEG_INFINITY).to_string());
+        assert_eq!("nan", Value::F64(f64::NAN).to_string());
+        assert_eq!("-42", Value::I64(-42).to_string());
+    }
+
+    #[test]
+    fn print_golden() {
+        assert_eq!("null", Value::Nil.to_string());
+        assert_eq!("true", Value::Bool(true).to_string());
+        assert_eq!(
+            r#""a \"quoted\" \\ string
+with newline""#,
+            Value::GodotString("a \"quoted\" \\ string\nwith newline".into()).to_string()
+        );
+        assert_eq!(
+            "Vector2( 1, 2.5 )",
+            Value::Vector2(Vector2::new(1.0, 2.5)).to_string()
+        );
+        assert_eq!(
+            "Transform( 1, 0, 0, 0, 1, 0, 0, 0, 1, 1, 2, 3 )",
+            Value::Transform(Transform {
+                basis: Basis::IDENTITY,
+                origin: Vector3::new(1.0, 2.0, 3.0),
+            })
+            .to_string()
+        );
+        assert_eq!(
+            "Color( 1, 0, 0, 1 )",
+            Value::Color(Color::from_rgb(1.0, 0.0, 0.0)).to_string()
+        );
+        assert_eq!(
+            r#"NodePath("a/b:c")"#,
+            Value::NodePath("a/b:c".into()).to_string()
+        );
+        assert_eq!("[  ]", Value::VariantArray(vec![]).to_string());
+        assert_eq!(
+            r#"[ 1, "a", null ]"#,
+            Value::VariantArray(vec![
+                Value::I64(1),
+                Value::GodotString("a".into()),
+                Value::Nil
+            ])
+            .to_string()
+        );
+        assert_eq!("{\n\n}", Value::Dictionary(vec![]).to_string());
+        assert_eq!(
+            "{\n1: 2.0,\n\"a\": [  ],\n\"b\": true\n}",
+            Value::Dictionary(vec![
+                (Value::GodotString("b".into()), Value::Bool(true)),
+                (Value::GodotString("a".into()), Value::VariantArray(vec![])),
+                (Value::I64(1), Value::F64(2.0)),
+            ])
+            .to_string()
+        );
+        assert_eq!("PoolByteArray(  )", Value::ByteArray(vec![]).to_string());
+        assert_eq!(
+            "PoolIntArray( 1, -2 )",
+            Value::Int32Array(vec![1, -2]).to_string()
+        );
+        assert_eq!(
+            r#"PoolStringArray( "a\tb", "c" )"#,
+            Value::StringArray(vec!["a\tb".into(), "c".into()]).to_string()
+        );
+        assert_eq!(
+            "PoolVector2Array( 1, 2, 3, 4 )",
+            Value::Vector2Array(vec![Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0)]).to_string()
+        );
+        assert_eq!(
+            "Object(Node,\"name\":\"Player\",\"visible\":false)\n",
+            Value::Object {
+                class: "Node".into(),
+                properties: vec![
+                    ("name".into(), Value::GodotString("Player".into())),
+                    ("visible".into(), Value::Bool(false)),
+                ],
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn print_mixed_keys() {
+        // Like the engine, keys are ordered by type first, so all ints come before all floats
+        let value = Value::Dictionary(vec![
+            (Value::F64(0.5), Value::I64(0)),
+            (Value::I64(3), Value::I64(1)),
+            (Value::F64(-1.0), Value::I64(2)),
+            (Value::I64(-2), Value::I64(3)),
+            (Value::Bool(true), Value::I64(4)),
+        ]);
+        assert_eq!(
+            "{\ntrue: 4,\n-2: 3,\n3: 1,\n-1.0: 2,\n0.5: 0\n}",
+            value.to_string()
+        );
+    }
+
+    #[test]
+    fn parse_values() {
+        assert_eq!(Value::Nil, parse("null"));
+        assert_eq!(Value::Bool(false), parse(" false "));
+        assert_eq!(Value::I64(-7), parse("-7"));
+        assert_eq!(Value::F64(1.5e3), parse("1.5e+3"));
+        assert_eq!(Value::F64(2.0), parse("2."));
+        assert_eq!(
+            Value::GodotString("tab\t\"é\"".into()),
+            parse(r#""tab\t\"é\"""#)
+        );
+        assert_eq!(
+            Value::Vector2(Vector2::new(1.0, 2.0)),
+            parse("Vector2( 1, 2 )")
+        );
+        assert_eq!(
+            Value::Vector2(Vector2::new(1.0, 2.0)),
+            parse("Vector2(1,2,)")
+        );
+        assert_eq!(
+            Value::Color(Color::from_rgba_u8(0xff, 0x80, 0x00, 0xff)),
+            parse("#ff8000")
+        );
+        assert_eq!(
+            Value::Basis(Basis::IDENTITY),
+            parse("Matrix3( 1, 0, 0, 0, 1, 0, 0, 0, 1 )")
+        );
+        assert_eq!(Value::NodePath("../a".into()), parse(r#"NodePath("../a")"#));
+        assert_eq!(Value::Rid, parse("RID()"));
+        assert_eq!(
+            Value::Vector3Array(vec![Vector3::new(1.0, 2.0, 3.0)]),
+            parse("PoolVector3Array( 1, 2, 3 )")
+        );
+        assert_eq!(
+            Value::Dictionary(vec![(
+                Value::GodotString("a".into()),
+                Value::VariantArray(vec![
+                    Value::I64(1),
+                    Value::Color(Color::from_rgba(1.0, 0.0, 0.0, 1.0))
+                ])
+            )]),
+            parse(r#"{"a": [1, Color( 1, 0, 0, 1 )]}"#)
+        );
+        assert_eq!(
+            Value::VariantArray(vec![Value::I64(1), Value::I64(2)]),
+            parse("[ 1, ; comment\n 2, ]")
+        );
+        assert_eq!(
+            Value::Object {
+                class: "Node".into(),
+                properties: vec![("name".into(), Value::GodotString("Player".into()))],
+            },
+            parse("Object(Node,\"name\":\"Player\")\n")
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        // Keys are sorted when printing
+        let value = Value::Dictionary(vec![
+            (
+                Value::GodotString("list".into()),
+                Value::VariantArray(vec![
+                    Value::Aabb(Aabb::new(Vector3::ZERO, Vector3::ONE)),
+                    Value::Float32Array(vec![0.25, -1.0]),
+                    Value::Dictionary(vec![]),
+                    Value::StringArray(vec!["quote\"d?".into()]),
+                ]),
+            ),
+            (
+                Value::GodotString("transform".into()),
+                Value::Transform2D(Transform2D {
+                    a: Vector2::new(0.5, 0.0),
+                    b: Vector2::new(0.0, 0.5),
+                    origin: Vector2::new(-3.0, 100.25),
+                }),
+            ),
+        ]);
+
+        assert_eq!(value, parse(&value.to_string()));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            (
+                2,
+                4,
+                ParseErrorKind::UnexpectedToken {
+                    expected: "':'",
+                    found: "','".into()
+                }
+            ),
+            parse_err("{\n\"a\", 1}")
+        );
+        assert_eq!(
+            (1, 1, ParseErrorKind::UnknownIdentifier("Vector4".into())),
+            parse_err("Vector4( 1, 2, 3, 4 )")
+        );
+        assert_eq!(
+            (
+                1,
+                3,
+                ParseErrorKind::InvalidArgumentCount {
+                    constructor: "Vector2".into(),
+                    count: 3
+                }
+            ),
+            parse_err("[ Vector2( 1, 2, 3 ) ]")
+        );
+        assert_eq!(
+            (1, 9, ParseErrorKind::UnterminatedString),
+            parse_err("[ 1, 2,\t\"abc")
+        );
+        assert_eq!(
+            (
+                1,
+                7,
+                ParseErrorKind::UnexpectedToken {
+                    expected: "']' or ','",
+                    found: "end of input".into()
+                }
+            ),
+            parse_err("[ 1, 2")
+        );
+        assert_eq!(
+            (1, 3, ParseErrorKind::UnexpectedCharacter('@')),
+            parse_err("[ @ ]")
+        );
+        assert_eq!(
+            (1, 5, ParseErrorKind::TrailingCharacters),
+            parse_err("1.0 2.0")
+        );
+        assert_eq!(
+            (
+                1,
+                1,
+                ParseErrorKind::InvalidNumber("99999999999999999999".into())
+            ),
+            parse_err("99999999999999999999")
+        );
+        assert_eq!(
+            (1, 1, ParseErrorKind::InvalidColor("#12345".into())),
+            parse_err("#12345")
+        );
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        let nested = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+        let value = parse(&nested);
+        assert_eq!(nested.len() + 2 * MAX_DEPTH, value.to_string().len());
+
+        let too_deep = "[".repeat(MAX_DEPTH + 1) + &"]".repeat(MAX_DEPTH + 1);
+        assert_eq!(
+            (1, MAX_DEPTH + 1, ParseErrorKind::TooDeep),
+            parse_err(&too_deep)
+        );
+    }
+}
+
+godot_test!(
+    test_text_dispatch_round_trip {
+        // Keys are sorted when printing
+        let dict = Dictionary::new();
+        let arr = VariantArray::new();
+        arr.push(1);
+        arr.push(NodePath::from_str("../Enemy"));
+        dict.insert("items", arr.into_shared());
+        dict.insert("name", "Player");
+        dict.insert("position", Vector2::new(1.0, 2.0));
+        dict.insert("tags", PoolArray::from_vec(vec![GodotString::from("a")]));
+        let variant = dict.into_shared().to_variant();
+
+        let value = Value::from_dispatch(&variant.dispatch()).unwrap();
+        let parsed: Value = value.to_string().parse().unwrap();
+        assert_eq!(value, parsed);
+
+        let dispatch = parsed.to_dispatch().unwrap();
+        assert_eq!(Ok(value), Value::from_dispatch(&dispatch));
+
+        let object = Value::Object { class: "Node".into(), properties: vec![] };
+        let err = object.to_dispatch().map(|_| ()).unwrap_err();
+        assert_eq!("Node", err.class());
+    }
+);
@@ -1,5 +1,5 @@
-use super::IsEqualApprox;
-use glam::Vec2;
+use super::glam_real::Vec2;
+use super::{real, IsEqualApprox};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 /// 2D vector class.
@@ -9,8 +9,8 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssi
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Vector2 {
-    pub x: f32,
-    pub y: f32,
+    pub x: real,
+    pub y: real,
 }
 
 /// Helper methods for `Vector2`.
@@ -24,7 +24,7 @@ impl Vector2 {
     pub const ONE: Vector2 = Vector2::new(1.0, 1.0);
 
     /// A vector with all components set to +infinity.
-    pub const INF: Vector2 = Vector2::new(f32::INFINITY, f32::INFINITY);
+    pub const INF: Vector2 = Vector2::new(real::INFINITY, real::INFINITY);
 
     /// Unit vector in -X direction.
     pub const LEFT: Vector2 = Vector2::new(-1.0, 0.0);
@@ -40,7 +40,7 @@ impl Vector2 {
 
     /// Constructs a new Vector2 from the given x and y.
     #[inline]
-    pub const fn new(x: f32, y: f32) -> Self {
+    pub const fn new(x: real, y: real) -> Self {
         Self { x, y }
     }
 
@@ -60,25 +60,25 @@ impl Vector2 {
     /// Equivalent to the result of @GDScript.atan2 when called with the vector's y and x as
     /// parameters: atan2(y, x).
     #[inline]
-    pub fn angle(self) -> f32 {
+    pub fn angle(self) -> real {
         self.glam().angle_between(Vec2::X)
     }
 
     /// Returns the angle to the given vector, in radians.
     #[inline]
-    pub fn angle_to(self, to: Self) -> f32 {
+    pub fn angle_to(self, to: Self) -> real {
         self.glam().angle_between(to.glam())
     }
 
     /// Returns the angle between the line connecting the two points and the X axis, in radians
     #[inline]
-    pub fn angle_to_point(self, to: Self) -> f32 {
+    pub fn angle_to_point(self, to: Self) -> real {
         self.glam().angle_between(to.glam() - self.glam())
     }
 
     /// Returns the aspect ratio of this vector, the ratio of x to y.
     #[inline]
-    pub fn aspect(self) -> f32 {
+    pub fn aspect(self) -> real {
         self.x / self.y
     }
 
@@ -96,13 +96,13 @@ impl Vector2 {
 
     /// Returns the vector with a maximum length by limiting its length to `length`.
     #[inline]
-    pub fn clamped(self, length: f32) -> Self {
+    pub fn clamped(self, length: real) -> Self {
         Self::gd(self.glam().clamp_length_max(length))
     }
 
     /// Returns the cross product of this vector and `with`.
     #[inline]
-    pub fn cross(self, with: Self) -> f32 {
+    pub fn cross(self, with: Self) -> real {
         self.x * with.y - self.y * with.x
     }
 
@@ -110,7 +110,7 @@ impl Vector2 {
     /// and returns the result at position `t`. `t` is in the range of 0.0 - 1.0, representing
     /// the amount of interpolation.
     #[inline]
-    pub fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self {
+    pub fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: real) -> Self {
         let v0 = pre_a;
         let v1 = self;
         let v2 = b;
@@ -137,13 +137,13 @@ impl Vector2 {
     /// This method runs faster than distance_to, so prefer it if you need to compare vectors or
     /// need the squared distance for some formula.
     #[inline]
-    pub fn distance_squared_to(self, other: Self) -> f32 {
+    pub fn distance_squared_to(self, other: Self) -> real {
         self.glam().distance_squared(other.glam())
     }
 
     /// Returns the distance to `other`.
     #[inline]
-    pub fn distance_to(self, other: Self) -> f32 {
+    pub fn distance_to(self, other: Self) -> real {
         self.glam().distance(other.glam())
     }
 
@@ -160,7 +160,7 @@ impl Vector2 {
     ///
     /// Note: `a.dot(b)` is equivalent to `b.dot(a)`.
     #[inline]
-    pub fn dot(self, with: Self) -> f32 {
+    pub fn dot(self, with: Self) -> real {
         self.glam().dot(with.glam())
     }
 
@@ -185,7 +185,7 @@ impl Vector2 {
 
     /// Returns the length (magnitude) of this vector.
     #[inline]
-    pub fn length(self) -> f32 {
+    pub fn length(self) -> real {
         self.glam().length()
     }
 
@@ -194,20 +194,20 @@ impl Vector2 {
     /// This method runs faster than length, so prefer it if you need to compare vectors or need
     /// the squared distance for some formula.
     #[inline]
-    pub fn length_squared(self) -> f32 {
+    pub fn length_squared(self) -> real {
         self.glam().length_squared()
     }
 
     /// Returns the result of the linear interpolation between this vector and b by amount t. t is
     /// on the range of 0.0 to 1.0, representing the amount of interpolation.
     #[inline]
-    pub fn linear_interpolate(self, b: Self, t: f32) -> Self {
+    pub fn linear_interpolate(self, b: Self, t: real) -> Self {
         Self::gd(self.glam().lerp(b.glam(), t))
     }
 
     /// Returns `self` moved towards `to` by the distance `delta`, clamped by `to`.
     #[inline]
-    pub fn move_toward(self, to: Vector2, delta: f32) -> Self {
+    pub fn move_toward(self, to: Vector2, delta: real) -> Self {
         let vd = to - self;
         let len = vd.length();
         if len <= delta || approx::abs_diff_eq!(0.0, len) {
@@ -225,7 +225,7 @@ impl Vector2 {
 
     /// Returns a vector composed of the @GDScript.fposmod of this vector's components and `rem`.
     #[inline]
-    pub fn posmod(self, rem: f32) -> Self {
+    pub fn posmod(self, rem: real) -> Self {
         self.posmodv(Self::new(rem, rem))
     }
 
@@ -249,7 +249,7 @@ impl Vector2 {
 
     /// Returns the vector rotated by `angle` radians.
     #[inline]
-    pub fn rotated(self, angle: f32) -> Self {
+    pub fn rotated(self, angle: real) -> Self {
         let (cos, sin) = (angle.cos(), angle.sin());
         Self::new(cos * self.x - sin * self.y, sin * self.x + cos * self.y)
     }
@@ -274,7 +274,7 @@ impl Vector2 {
     ///
     /// Note: Both vectors must be normalized.
     #[inline]
-    pub fn slerp(self, b: Self, t: f32) -> Self {
+    pub fn slerp(self, b: Self, t: real) -> Self {
         let theta = self.angle_to(b);
         self.rotated(theta * t)
     }
@@ -388,14 +388,14 @@ derive_op_impl!(Add, add);
 derive_op_impl!(Sub, sub);
 derive_op_impl!(Mul, mul);
 derive_op_impl!(Div, div);
-derive_op_impl!(Mul, mul, f32);
-derive_op_impl!(Div, div, f32);
+derive_op_impl!(Mul, mul, real);
+derive_op_impl!(Div, div, real);
 derive_assign_op_impl!(AddAssign, add_assign, add);
 derive_assign_op_impl!(SubAssign, sub_assign, sub);
 derive_assign_op_impl!(MulAssign, mul_assign, mul);
 derive_assign_op_impl!(DivAssign, div_assign, div);
-derive_assign_op_impl!(MulAssign, mul_assign, mul, f32);
-derive_assign_op_impl!(DivAssign, div_assign, div, f32);
+derive_assign_op_impl!(MulAssign, mul_assign, mul, real);
+derive_assign_op_impl!(DivAssign, div_assign, div, real);
 
 impl Neg for Vector2 {
     type Output = Self;
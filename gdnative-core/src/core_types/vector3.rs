@@ -1,6 +1,6 @@
 use super::geom::Basis;
-use super::IsEqualApprox;
-use glam::Vec3A;
+use super::glam_real::Vec3A;
+use super::{real, IsEqualApprox};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 /// 3D vector class.
@@ -10,9 +10,9 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssi
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Vector3 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
+    pub x: real,
+    pub y: real,
+    pub z: real,
 }
 
 #[allow(
@@ -50,7 +50,7 @@ impl Vector3 {
     pub const ONE: Self = Self::new(1.0, 1.0, 1.0);
 
     /// A vector with all components set to +infinity.
-    pub const INF: Self = Self::new(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+    pub const INF: Self = Self::new(real::INFINITY, real::INFINITY, real::INFINITY);
 
     /// Unit vector in -X direction.
     pub const LEFT: Self = Self::new(-1.0, 0.0, 0.0);
@@ -72,7 +72,7 @@ impl Vector3 {
 
     /// Returns a Vector3 with the given components.
     #[inline]
-    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+    pub const fn new(x: real, y: real, z: real) -> Self {
         Self { x, y, z }
     }
 
@@ -84,7 +84,7 @@ impl Vector3 {
 
     /// Returns the minimum angle to the given vector, in radians.
     #[inline]
-    pub fn angle_to(self, to: Self) -> f32 {
+    pub fn angle_to(self, to: Self) -> real {
         self.glam().angle_between(to.glam())
     }
 
@@ -109,7 +109,7 @@ impl Vector3 {
     /// Performs a cubic interpolation between vectors pre_a, a, b, post_b (a is current), by the
     /// given amount t. t is on the range of 0.0 to 1.0, representing the amount of interpolation.
     #[inline]
-    pub fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self {
+    pub fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: real) -> Self {
         let mut p = (pre_a, self, b, post_b);
 
         {
@@ -144,13 +144,13 @@ impl Vector3 {
     /// This method runs faster than distance_to, so prefer it if you need to compare vectors or
     /// need the squared distance for some formula.
     #[inline]
-    pub fn distance_squared_to(self, other: Vector3) -> f32 {
+    pub fn distance_squared_to(self, other: Vector3) -> real {
         other.glam().distance_squared(self.glam())
     }
 
     /// Returns the distance to `other`.
     #[inline]
-    pub fn distance_to(self, other: Vector3) -> f32 {
+    pub fn distance_to(self, other: Vector3) -> real {
         other.glam().distance(self.glam())
     }
 
@@ -166,7 +166,7 @@ impl Vector3 {
     ///
     /// Note: a.dot(b) is equivalent to b.dot(a).
     #[inline]
-    pub fn dot(self, other: Self) -> f32 {
+    pub fn dot(self, other: Self) -> real {
         self.glam().dot(other.glam())
     }
 
@@ -198,7 +198,7 @@ impl Vector3 {
 
     /// Returns the length (magnitude) of this vector.
     #[inline]
-    pub fn length(self) -> f32 {
+    pub fn length(self) -> real {
         self.glam().length()
     }
 
@@ -207,14 +207,14 @@ impl Vector3 {
     /// This method runs faster than length, so prefer it if you need to compare vectors or need
     /// the squared distance for some formula.
     #[inline]
-    pub fn length_squared(self) -> f32 {
+    pub fn length_squared(self) -> real {
         self.glam().length_squared()
     }
 
     /// Returns the result of the linear interpolation between this vector and b by amount t. t is
     /// on the range of 0.0 to 1.0, representing the amount of interpolation.
     #[inline]
-    pub fn linear_interpolate(self, b: Self, t: f32) -> Self {
+    pub fn linear_interpolate(self, b: Self, t: real) -> Self {
         Self::gd(self.glam().lerp(b.glam(), t))
     }
 
@@ -262,7 +262,7 @@ impl Vector3 {
 
     /// Moves this vector toward `to` by the fixed `delta` amount.
     #[inline]
-    pub fn move_toward(self, to: Self, delta: f32) -> Self {
+    pub fn move_toward(self, to: Self, delta: real) -> Self {
         let vd = to - self;
         let len = vd.length();
         if len <= delta || approx::abs_diff_eq!(0.0, len) {
@@ -286,7 +286,7 @@ impl Vector3 {
 
     /// Returns a vector composed of the `rem_euclid` of this vector's components and `mod`.
     #[inline]
-    pub fn posmod(self, rem: f32) -> Self {
+    pub fn posmod(self, rem: real) -> Self {
         self.posmodv(Self::new(rem, rem, rem))
     }
 
@@ -316,7 +316,7 @@ impl Vector3 {
     /// Rotates this vector around a given axis by `phi` radians. The axis must be a normalized
     /// vector.
     #[inline]
-    pub fn rotated(self, axis: Self, phi: f32) -> Self {
+    pub fn rotated(self, axis: Self, phi: real) -> Self {
         Basis::from_axis_angle(axis, phi) * self
     }
 
@@ -340,7 +340,7 @@ impl Vector3 {
     ///
     /// **Note**: Both vectors must be normalized.
     #[inline]
-    pub fn slerp(self, b: Self, t: f32) -> Self {
+    pub fn slerp(self, b: Self, t: real) -> Self {
         let theta = self.angle_to(b);
         self.rotated(self.cross(b).normalized(), theta * t)
     }
@@ -355,7 +355,7 @@ impl Vector3 {
     /// This can also be used to round to an arbitrary number of decimals.
     #[inline]
     pub fn snapped(self, by: Self) -> Self {
-        let stepify = |v: f32, s: f32| {
+        let stepify = |v: real, s: real| {
             if by.x != 0.0 {
                 (v / s + 0.5).floor() * s
             } else {
@@ -412,11 +412,11 @@ impl Vector3 {
     }
 }
 
-impl AsRef<[f32; 3]> for Vector3 {
+impl AsRef<[real; 3]> for Vector3 {
     #[inline]
-    fn as_ref(&self) -> &[f32; 3] {
+    fn as_ref(&self) -> &[real; 3] {
         // SAFETY: Vector3 is repr(C)
-        unsafe { &*(self as *const Vector3 as *const [f32; 3]) }
+        unsafe { &*(self as *const Vector3 as *const [real; 3]) }
     }
 }
 
@@ -479,15 +479,15 @@ derive_op_impl!(Add, add);
 derive_op_impl!(Sub, sub);
 derive_op_impl!(Mul, mul);
 derive_op_impl!(Div, div);
-derive_op_impl!(Mul, mul, f32);
-derive_op_impl!(Div, div, f32);
-derive_op_impl_rev!(Mul, mul, f32);
+derive_op_impl!(Mul, mul, real);
+derive_op_impl!(Div, div, real);
+derive_op_impl_rev!(Mul, mul, real);
 derive_assign_op_impl!(AddAssign, add_assign, add);
 derive_assign_op_impl!(SubAssign, sub_assign, sub);
 derive_assign_op_impl!(MulAssign, mul_assign, mul);
 derive_assign_op_impl!(DivAssign, div_assign, div);
-derive_assign_op_impl!(MulAssign, mul_assign, mul, f32);
-derive_assign_op_impl!(DivAssign, div_assign, div, f32);
+derive_assign_op_impl!(MulAssign, mul_assign, mul, real);
+derive_assign_op_impl!(DivAssign, div_assign, div, real);
 
 impl Neg for Vector3 {
     type Output = Self;
@@ -586,7 +586,7 @@ mod impl_export {
     impl_export_for_core_type_without_hint!(Dictionary);
     impl_export_for_core_type_without_hint!(PoolArray<u8>: ByteArray);
     impl_export_for_core_type_without_hint!(PoolArray<i32>: Int32Array);
-    impl_export_for_core_type_without_hint!(PoolArray<f32>: Float32Array);
+    impl_export_for_core_type_without_hint!(PoolArray<real>: Float32Array);
     impl_export_for_core_type_without_hint!(PoolArray<GodotString>: StringArray);
     impl_export_for_core_type_without_hint!(PoolArray<Vector2>: Vector2Array);
     impl_export_for_core_type_without_hint!(PoolArray<Vector3>: Vector3Array);
@@ -645,4 +645,29 @@ mod impl_export {
             hint.unwrap_or_default().export_info()
         }
     }
+
+    impl<T> Export for Array<T, Shared>
+    where
+        T: Export + FromVariant,
+    {
+        type Hint = hint::ArrayHint;
+
+        #[inline]
+        fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+            hint.unwrap_or_else(hint::ArrayHint::with_element::<T>)
+                .export_info()
+        }
+    }
+
+    impl<K, V> Export for TypedDictionary<K, V, Shared>
+    where
+        K: ToVariant + ToVariantEq + FromVariant,
+        V: ToVariant + FromVariant,
+    {
+        type Hint = NoHint;
+        #[inline]
+        fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
+            ExportInfo::new(VariantType::Dictionary)
+        }
+    }
 }
@@ -1,8 +1,12 @@
-use std::f32::consts::TAU;
+use crate::core_types::{real, real_consts::TAU};
 use std::ops::Rem;
 use std::ops::{Range, RangeInclusive};
 
-const CMP_EPSILON: f32 = 0.00001;
+mod random;
+
+pub use random::*;
+
+const CMP_EPSILON: real = 0.00001;
 
 /// Coordinate system conversion: polar -> cartesian
 ///
@@ -19,7 +23,7 @@ const CMP_EPSILON: f32 = 0.00001;
 /// assert_eq!(y, -5.0);
 /// ```
 #[inline]
-pub fn polar2cartesian(r: f32, th: f32) -> (f32, f32) {
+pub fn polar2cartesian(r: real, th: real) -> (real, real) {
     let x = r * th.cos();
     let y = r * th.sin();
 
@@ -41,7 +45,7 @@ pub fn polar2cartesian(r: f32, th: f32) -> (f32, f32) {
 /// assert!(is_equal_approx(th, -0.394791119699));
 /// ```
 #[inline]
-pub fn cartesian2polar(x: f32, y: f32) -> (f32, f32) {
+pub fn cartesian2polar(x: real, y: real) -> (real, real) {
     let r = x.hypot(y);
     let th = y.atan2(x);
 
@@ -50,15 +54,15 @@ pub fn cartesian2polar(x: f32, y: f32) -> (f32, f32) {
 
 /// Converts from decibels to linear energy (audio).
 #[inline]
-pub fn db2linear(decibels: f32) -> f32 {
-    f32::exp(decibels * 0.115_129_255)
+pub fn db2linear(decibels: real) -> real {
+    real::exp(decibels * 0.115_129_255)
 }
 
 /// Converts from linear energy to decibels (audio).
 ///
 /// This can be used to implement volume sliders that behave as expected (since volume isn't linear).
 #[inline]
-pub fn linear2db(linear_energy: f32) -> f32 {
+pub fn linear2db(linear_energy: real) -> real {
     linear_energy.ln() * 0.115_129_255
 }
 
@@ -75,9 +79,9 @@ pub fn linear2db(linear_energy: f32) -> f32 {
 /// assert_eq!(step_decimals(0.000000004), 9);
 /// ```
 #[inline]
-pub fn step_decimals(step: f32) -> i32 {
+pub fn step_decimals(step: real) -> i32 {
     const MAXN: usize = 10;
-    const SD: [f32; MAXN] = [
+    const SD: [real; MAXN] = [
         0.9999, // somehow compensate for floating point error
         0.09999,
         0.009999,
@@ -92,7 +96,7 @@ pub fn step_decimals(step: f32) -> i32 {
 
     let abs = step.abs();
     let int_abs: i32 = step as i32;
-    let decs: f32 = abs - (int_abs as f32); // strip away integer part;
+    let decs: real = abs - (int_abs as real); // strip away integer part;
     for (i, item) in SD.iter().enumerate().take(MAXN) {
         if decs >= *item {
             return i.try_into().unwrap();
@@ -116,7 +120,7 @@ pub fn step_decimals(step: f32) -> i32 {
 /// assert_eq!(move_toward(8.0..=4.0, 5.0), 4.0);
 /// ```
 #[inline]
-pub fn move_toward(range: RangeInclusive<f32>, delta: f32) -> f32 {
+pub fn move_toward(range: RangeInclusive<real>, delta: real) -> real {
     if (range.end() - range.start()).abs() <= delta {
         *range.end()
     } else {
@@ -144,7 +148,7 @@ pub fn move_toward(range: RangeInclusive<f32>, delta: f32) -> f32 {
 /// Curve values cheatsheet:  
 /// ![Image](https://raw.githubusercontent.com/godotengine/godot-docs/3.4/img/ease_cheatsheet.png)
 #[inline]
-pub fn ease(s: f32, curve: f32) -> f32 {
+pub fn ease(s: real, curve: real) -> real {
     let s = s.clamp(0.0, 1.0);
     if curve > 0.0 {
         if curve < 1.0 {
@@ -174,7 +178,7 @@ pub fn ease(s: f32, curve: f32) -> f32 {
 /// assert_eq!(lerp(0.0..=4.0, 0.75), 3.0);
 /// ```
 #[inline]
-pub fn lerp(range: RangeInclusive<f32>, weight: f32) -> f32 {
+pub fn lerp(range: RangeInclusive<real>, weight: real) -> real {
     range.start() + (range.end() - range.start()) * weight
 }
 
@@ -183,7 +187,7 @@ pub fn lerp(range: RangeInclusive<f32>, weight: f32) -> f32 {
 /// Similar to lerp, but interpolates correctly when the angles wrap around `TAU`.
 /// To perform eased interpolation with `lerp_angle`, combine it with `ease` or `smoothstep`.
 /// ```
-/// use std::f32::consts::{PI, TAU};
+/// use gdnative::core_types::real_consts::{PI, TAU};
 /// use gdnative::globalscope::lerp_angle;
 ///
 /// assert_eq!(lerp_angle(-PI..PI, 0.0), -PI);
@@ -196,9 +200,9 @@ pub fn lerp(range: RangeInclusive<f32>, weight: f32) -> f32 {
 /// assert_eq!(lerp_angle(TAU..0.0, 1.0), TAU);
 /// ```
 #[inline]
-pub fn lerp_angle(range: Range<f32>, amount: f32) -> f32 {
-    let difference = f32::rem(range.end - range.start, TAU);
-    let distance = f32::rem(2.0 * difference, TAU) - difference;
+pub fn lerp_angle(range: Range<real>, amount: real) -> real {
+    let difference = real::rem(range.end - range.start, TAU);
+    let distance = real::rem(2.0 * difference, TAU) - difference;
 
     range.start + distance * amount
 }
@@ -222,7 +226,7 @@ pub fn lerp_angle(range: Range<f32>, amount: f32) -> f32 {
 /// assert_eq!(fposmod(-6.0, -3.0), 0.0);
 /// ```
 #[inline]
-pub fn fposmod(a: f32, b: f32) -> f32 {
+pub fn fposmod(a: real, b: real) -> real {
     let mut value = a % b;
     if value < 0.0 && b > 0.0 || value > 0.0 && b < 0.0 {
         value += b;
@@ -247,7 +251,7 @@ pub fn fposmod(a: f32, b: f32) -> f32 {
 /// assert_eq!(inverse_lerp(20.0..=30.0, 27.5), 0.75);
 /// ```
 #[inline]
-pub fn inverse_lerp(range: RangeInclusive<f32>, value: f32) -> f32 {
+pub fn inverse_lerp(range: RangeInclusive<real>, value: real) -> real {
     (value - range.start()) / (range.end() - range.start())
 }
 
@@ -275,7 +279,7 @@ pub fn inverse_lerp(range: RangeInclusive<f32>, value: f32) -> f32 {
 /// assert_eq!(smoothstep(0.0, 2.0, 2.0), 1.0);
 /// ```
 #[inline]
-pub fn smoothstep(from: f32, to: f32, s: f32) -> f32 {
+pub fn smoothstep(from: real, to: real, s: real) -> real {
     if is_equal_approx(from, to) {
         return from;
     }
@@ -290,7 +294,7 @@ pub fn smoothstep(from: f32, to: f32, s: f32) -> f32 {
 ///
 /// Infinity values of the same sign are considered equal.
 #[inline]
-pub fn is_equal_approx(a: f32, b: f32) -> bool {
+pub fn is_equal_approx(a: real, b: real) -> bool {
     if a == b {
         return true;
     }
@@ -305,7 +309,7 @@ pub fn is_equal_approx(a: f32, b: f32) -> bool {
 ///
 /// This method is faster than using is_equal_approx with one value as zero.
 #[inline]
-pub fn is_zero_approx(s: f32) -> bool {
+pub fn is_zero_approx(s: real) -> bool {
     s.abs() < CMP_EPSILON
 }
 
@@ -373,10 +377,10 @@ pub fn posmod(a: i32, b: i32) -> i32 {
 /// ```
 #[inline]
 pub fn range_lerp(
-    value: f32,
-    range_from: RangeInclusive<f32>,
-    range_to: RangeInclusive<f32>,
-) -> f32 {
+    value: real,
+    range_from: RangeInclusive<real>,
+    range_to: RangeInclusive<real>,
+) -> real {
     lerp(range_to, inverse_lerp(range_from, value))
 }
 
@@ -385,13 +389,13 @@ pub fn range_lerp(
 /// This can also be used to round a floating point number to an arbitrary number of decimals.
 /// ```
 /// use gdnative::globalscope::*;
-/// use std::f32::consts::E; // Euler constant, 2.71828
+/// use gdnative::core_types::real_consts::E; // Euler constant, 2.71828
 ///
 /// assert_eq!(stepify(100.0, 32.0), 96.0);
 /// assert_eq!(stepify(E, 0.01), 2.72);
 /// ```
 #[inline]
-pub fn stepify(mut value: f32, step: f32) -> f32 {
+pub fn stepify(mut value: real, step: real) -> real {
     if step != 0.0 {
         value = (value / step + 0.5).floor() * step;
     }
@@ -405,7 +409,7 @@ pub fn stepify(mut value: f32, step: f32) -> f32 {
 /// # Examples:
 /// ```
 /// use gdnative::globalscope::*;
-/// use std::f32::consts::{TAU, PI};
+/// use gdnative::core_types::real_consts::{TAU, PI};
 ///
 /// // Custom range
 /// assert_eq!(wrapf(3.2, 0.5..2.5), 1.2);
@@ -422,7 +426,7 @@ pub fn stepify(mut value: f32, step: f32) -> f32 {
 /// # Panics
 /// If the range is empty, i.e. `range.start` >= `range.end`.
 #[inline]
-pub fn wrapf(value: f32, range: Range<f32>) -> f32 {
+pub fn wrapf(value: real, range: Range<real>) -> real {
     assert!(
         !range.is_empty(),
         "wrapf expects non-empty, non-inverted range; passed {}..{}",
@@ -0,0 +1,363 @@
+use crate::core_types::{real, RealConv};
+use std::f64::consts::TAU;
+
+/// Pseudo-random number generator producing the same sequences as the engine's
+/// `RandomNumberGenerator` class.
+///
+/// This is a pure-Rust port of Godot 3's implementation, based on the PCG32 algorithm. Given the
+/// same seed or state, it produces the same numbers as GDScript code using `RandomNumberGenerator`,
+/// which allows procedural generation to be split between Rust and GDScript. Like the engine's,
+/// the generator is not suitable for cryptographic purposes.
+///
+/// The integer methods and [`randf`][Self::randf] match the engine bit-for-bit. The remaining
+/// methods use floating-point functions like `cos` and `ln` from the platform's math library, and
+/// match to the last bit when the engine uses the same one.
+///
+/// ```
+/// use gdnative::globalscope::RandomNumberGenerator;
+///
+/// let mut rng = RandomNumberGenerator::new();
+/// rng.set_seed(1234);
+/// let roll = rng.randi_range(1, 6);
+/// assert!((1..=6).contains(&roll));
+///
+/// // Restoring the state repeats the sequence.
+/// let state = rng.state();
+/// let value = rng.randf();
+/// rng.set_state(state);
+/// assert_eq!(value, rng.randf());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RandomNumberGenerator {
+    state: u64,
+    inc: u64,
+    seed: u64,
+}
+
+impl RandomNumberGenerator {
+    /// Seed of newly constructed generators, like in the engine.
+    pub const DEFAULT_SEED: u64 = 12_047_754_176_567_800_795;
+
+    /// Increment (stream selector) used by the engine.
+    const DEFAULT_INC: u64 = 1_442_695_040_888_963_407;
+
+    /// Creates a generator seeded with [`DEFAULT_SEED`][Self::DEFAULT_SEED], which is also what
+    /// `RandomNumberGenerator.new()` does in GDScript.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_seed(Self::DEFAULT_SEED)
+    }
+
+    /// Creates a generator with the given seed. See [`set_seed`][Self::set_seed].
+    #[inline]
+    pub fn from_seed(seed: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            inc: Self::DEFAULT_INC,
+            seed: 0,
+        };
+        rng.set_seed(seed);
+        rng
+    }
+
+    /// Reinitializes the generator from a seed. The same seed produces the same sequence.
+    ///
+    /// Seeds are `int` values in GDScript, so negative seeds from there correspond to their
+    /// two's complement here, e.g. `seed as u64` for `seed: i64`.
+    #[inline]
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.state = 0;
+        self.inc = (Self::DEFAULT_INC << 1) | 1;
+        self.next_u32();
+        self.state = self.state.wrapping_add(seed);
+        self.next_u32();
+    }
+
+    /// Returns the seed last passed to [`set_seed`][Self::set_seed].
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the current state of the generator, which can be passed to
+    /// [`set_state`][Self::set_state] to resume the sequence from this point.
+    #[inline]
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// Restores a state previously returned by [`state`][Self::state].
+    ///
+    /// Arbitrary values are allowed, but are not recommended, as they may lead to a lower
+    /// quality of the generated numbers.
+    #[inline]
+    pub fn set_state(&mut self, state: u64) {
+        self.state = state;
+    }
+
+    /// Seeds the generator with a time-based value, like the engine's `randomize()`.
+    #[inline]
+    pub fn randomize(&mut self) {
+        let time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |duration| {
+                duration
+                    .as_secs()
+                    .wrapping_add(duration.subsec_micros().into())
+            });
+
+        self.set_seed(
+            time.wrapping_mul(self.state)
+                .wrapping_add(Self::DEFAULT_INC),
+        );
+    }
+
+    /// Returns a pseudo-random 32-bit unsigned integer.
+    #[inline]
+    pub fn randi(&mut self) -> u32 {
+        self.next_u32()
+    }
+
+    /// Returns a pseudo-random number between 0.0 and 1.0, inclusive.
+    ///
+    /// This is computed with single precision, also with the `double-precision` feature.
+    #[inline]
+    pub fn randf(&mut self) -> real {
+        real::from_f32(self.next_f32())
+    }
+
+    /// Returns a pseudo-random number between `from` and `to`, inclusive.
+    #[inline]
+    pub fn randf_range(&mut self, from: real, to: real) -> real {
+        self.next_real() * (to - from) + from
+    }
+
+    /// Returns a normally distributed pseudo-random number, with the given mean and standard
+    /// deviation. The engine's defaults are `randfn(0.0, 1.0)`.
+    #[inline]
+    pub fn randfn(&mut self, mean: real, deviation: real) -> real {
+        self.next_normal(mean, deviation)
+    }
+
+    /// Returns a pseudo-random integer between `from` and `to`, inclusive. The bounds may be given
+    /// in any order.
+    #[inline]
+    pub fn randi_range(&mut self, from: i32, to: i32) -> i32 {
+        if from == to {
+            return from;
+        }
+
+        let offset = self.next_bounded(from.abs_diff(to).wrapping_add(1));
+        (offset as i32).wrapping_add(from.min(to))
+    }
+
+    /// `pcg32_random_r` of the PCG reference implementation.
+    fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(self.inc);
+
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xorshifted.rotate_right(rot)
+    }
+
+    /// `pcg32_boundedrand_r` of the PCG reference implementation, returning a number below
+    /// `bound` without modulo bias. A `bound` of 0 stands for the full range.
+    fn next_bounded(&mut self, bound: u32) -> u32 {
+        if bound == 0 {
+            return self.next_u32();
+        }
+
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let r = self.next_u32();
+            if r >= threshold {
+                return r % bound;
+            }
+        }
+    }
+
+    // The floating-point numbers use the bits of one random number as the fraction of a binary
+    // number between 0.5 and 1, and scale it down by the number of leading zeros of another.
+
+    fn next_f32(&mut self) -> f32 {
+        let proto_exp_offset = self.next_u32();
+        if proto_exp_offset == 0 {
+            return 0.0;
+        }
+
+        let significand = (self.next_u32() | 0x8000_0001) as f32;
+        significand * exp2_f32(-32 - proto_exp_offset.leading_zeros() as i32)
+    }
+
+    #[cfg(feature = "double-precision")]
+    fn next_f64(&mut self) -> f64 {
+        let proto_exp_offset = self.next_u32();
+        if proto_exp_offset == 0 {
+            return 0.0;
+        }
+
+        // The engine's compilers evaluate the high bits first.
+        let high = u64::from(self.next_u32()) << 32;
+        let low = u64::from(self.next_u32());
+        let significand = (high | low | 0x8000_0000_0000_0001) as f64;
+        significand * exp2_f64(-64 - proto_exp_offset.leading_zeros() as i32)
+    }
+
+    /// Box-Muller transform, with the engine's mix of single and double precision.
+    #[cfg(not(feature = "double-precision"))]
+    fn next_normal(&mut self, mean: f32, deviation: f32) -> f32 {
+        let angle = TAU * f64::from(self.next_f32());
+        let radius = (-2.0 * f64::from(self.next_f32().ln())).sqrt();
+        (f64::from(mean) + f64::from(deviation) * (angle.cos() * radius)) as f32
+    }
+
+    /// Box-Muller transform.
+    #[cfg(feature = "double-precision")]
+    fn next_normal(&mut self, mean: f64, deviation: f64) -> f64 {
+        let angle = TAU * self.next_f64();
+        let radius = (-2.0 * self.next_f64().ln()).sqrt();
+        mean + deviation * (angle.cos() * radius)
+    }
+
+    /// The engine uses the overload for `real_t`.
+    #[cfg(not(feature = "double-precision"))]
+    fn next_real(&mut self) -> f32 {
+        self.next_f32()
+    }
+
+    /// The engine uses the overload for `real_t`.
+    #[cfg(feature = "double-precision")]
+    fn next_real(&mut self) -> f64 {
+        self.next_f64()
+    }
+}
+
+impl Default for RandomNumberGenerator {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns 2 to the power of `exp`, which must be in the range of normal numbers.
+fn exp2_f32(exp: i32) -> f32 {
+    f32::from_bits(((exp + 127) as u32) << 23)
+}
+
+/// Returns 2 to the power of `exp`, which must be in the range of normal numbers.
+#[cfg(feature = "double-precision")]
+fn exp2_f64(exp: i32) -> f64 {
+    f64::from_bits(((exp + 1023) as u64) << 52)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pcg32_reference() {
+        // Output of the PCG reference implementation's demo, seeded with state 42 and sequence 54.
+        let mut rng = RandomNumberGenerator {
+            state: 0,
+            inc: (54 << 1) | 1,
+            seed: 0,
+        };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(42);
+        rng.next_u32();
+
+        let expected = [
+            0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e,
+        ];
+        for value in expected {
+            assert_eq!(value, rng.randi());
+        }
+    }
+
+    #[test]
+    fn seed_and_state() {
+        let mut rng = RandomNumberGenerator::new();
+        assert_eq!(RandomNumberGenerator::DEFAULT_SEED, rng.seed());
+        assert_eq!(
+            [3161026589, 2668139190, 4134715227],
+            [(); 3].map(|_| rng.randi())
+        );
+
+        rng.set_seed(1234);
+        assert_eq!(1234, rng.seed());
+        assert_eq!(5147283058137325692, rng.state());
+        assert_eq!(RandomNumberGenerator::from_seed(1234), rng);
+
+        let values = [435017838, 3680626977, 1396107827, 1732080859];
+        assert_eq!(values, [(); 4].map(|_| rng.randi()));
+        assert_eq!(1234, rng.seed());
+
+        rng.set_state(5147283058137325692);
+        assert_eq!(values, [(); 4].map(|_| rng.randi()));
+
+        // GDScript seed -5.
+        let mut rng = RandomNumberGenerator::from_seed(-5_i64 as u64);
+        assert_eq!([1375664790, 3233470852], [(); 2].map(|_| rng.randi()));
+    }
+
+    #[test]
+    fn randf() {
+        let mut rng = RandomNumberGenerator::from_seed(1234);
+        let expected: [f32; 3] = [0.107120343, 0.451640755, 0.0208016299];
+        assert_eq!(expected, [(); 3].map(|_| rng.randf().as_f32()));
+
+        let mut rng = RandomNumberGenerator::from_seed(1234);
+        #[cfg(not(feature = "double-precision"))]
+        let expected = [2.32136106, 3.35492229, 2.06240487];
+        #[cfg(feature = "double-precision")]
+        let expected = [2.321361030567646, 2.781218343414012, 3.9651924189476144];
+        assert_eq!(expected, [(); 3].map(|_| rng.randf_range(2.0, 5.0)));
+
+        let mut rng = RandomNumberGenerator::from_seed(99);
+        for _ in 0..1000 {
+            assert!((0.0..=1.0).contains(&rng.randf()));
+            assert!((-3.0..=-1.0).contains(&rng.randf_range(-1.0, -3.0)));
+        }
+    }
+
+    #[test]
+    fn randfn() {
+        let mut rng = RandomNumberGenerator::from_seed(7);
+        let samples: Vec<real> = (0..10_000).map(|_| rng.randfn(5.0, 2.0)).collect();
+
+        let mean = samples.iter().sum::<real>() / samples.len() as real;
+        let variance = samples
+            .iter()
+            .map(|x| (x - mean) * (x - mean))
+            .sum::<real>()
+            / samples.len() as real;
+        assert!((mean - 5.0).abs() < 0.1, "{mean}");
+        assert!((variance.sqrt() - 2.0).abs() < 0.1, "{variance}");
+    }
+
+    #[test]
+    fn randi_range() {
+        let mut rng = RandomNumberGenerator::from_seed(1234);
+        let expected = [-7, -4, -8, 9, 9, 1, 3, -9];
+        assert_eq!(expected, [(); 8].map(|_| rng.randi_range(-10, 10)));
+
+        let mut rng = RandomNumberGenerator::from_seed(1234);
+        assert_eq!(expected, [(); 8].map(|_| rng.randi_range(10, -10)));
+
+        assert_eq!(3, rng.randi_range(3, 3));
+
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let roll = rng.randi_range(1, 6);
+            seen[roll as usize - 1] = true;
+        }
+        assert_eq!([true; 6], seen);
+
+        // Doesn't overflow on the full range.
+        rng.randi_range(i32::MIN, i32::MAX);
+    }
+}
@@ -50,12 +50,16 @@ pub use gdnative_derive as derive;
 // Macros have to be processed before they are used.
 mod macros;
 
+mod digest;
+
 pub mod core_types;
 
 pub mod export;
 pub mod globalscope;
 pub mod init;
 pub mod log;
+#[cfg(feature = "mock-api")]
+pub mod mock;
 pub mod object;
 pub mod profiler;
 
@@ -113,6 +113,107 @@ macro_rules! godot_error {
     });
 }
 
+/// Formats a [`GodotString`][crate::core_types::GodotString] with named placeholders, like
+/// GDScript's `"...".format({...})`.
+///
+/// The first argument is the template, which can be anything convertible into a `GodotString`.
+/// It is followed by `name = value` pairs, where each value implements
+/// [`ToVariant`][crate::core_types::ToVariant]. A single `name` is short for `name = name`.
+/// Every `{name}` in the template is replaced by the value's string representation through
+/// [`GodotString::format()`][crate::core_types::GodotString::format]. Placeholders without a
+/// value are left as they are.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use gdnative::prelude::*;
+/// let player = "Ferris";
+/// let greeting = godot_format!("Hello {player}, you have {count} new messages", player, count = 3);
+/// godot_print!("{}", greeting); // "Hello Ferris, you have 3 new messages"
+/// ```
+#[macro_export]
+macro_rules! godot_format {
+    (@insert $values:ident) => {};
+    (@insert $values:ident $name:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $values.insert(
+            ::std::stringify!($name),
+            $crate::core_types::ToVariant::to_variant(&$value),
+        );
+        $crate::godot_format!(@insert $values $($($rest)*)?);
+    };
+    (@insert $values:ident $name:ident $(, $($rest:tt)*)?) => {
+        $crate::godot_format!(@insert $values $name = $name $(, $($rest)*)?);
+    };
+    ($template:expr $(, $($args:tt)*)?) => {{
+        let values = $crate::core_types::Dictionary::new();
+        $crate::godot_format!(@insert values $($($args)*)?);
+        $crate::core_types::GodotString::from($template)
+            .format(&$crate::core_types::OwnedToVariant::owned_to_variant(values))
+    }};
+}
+
+/// Returns a `&'static` [`StringName`][crate::core_types::StringName] for a string literal.
+///
+/// The name is interned the first time the expression is evaluated, and reused afterwards. This
+/// makes it the cheapest way to pass a fixed method, signal or property name to the engine
+/// repeatedly, for example on every frame. See [`NameArg`][crate::core_types::NameArg].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use gdnative::prelude::*;
+/// # fn process(owner: TRef<Node>) {
+/// owner.emit_signal(string_name!("moved"), &[]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! string_name {
+    ($name:expr) => {{
+        static NAME: $crate::private::StaticStringName =
+            $crate::private::StaticStringName::new($name);
+        NAME.get()
+    }};
+}
+
+/// Parses a [`NodePathBuf`][crate::core_types::NodePathBuf] from a string literal, which is
+/// checked at compile time.
+///
+/// Malformed paths, like `"Player//Sprite"` or `"Sprite::modulate"`, fail to compile instead of
+/// panicking at runtime.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use gdnative::prelude::*;
+/// # fn ready(owner: TRef<Node>) {
+/// let sprite = owner.get_node(node_path!("Player/Sprite"));
+/// # }
+/// ```
+///
+/// Empty node names are rejected:
+///
+/// ```compile_fail
+/// # use gdnative::prelude::*;
+/// let path = node_path!("Player//Sprite");
+/// ```
+///
+/// As are empty subnames:
+///
+/// ```compile_fail
+/// # use gdnative::prelude::*;
+/// let path = node_path!("Sprite::modulate");
+/// ```
+#[macro_export]
+macro_rules! node_path {
+    ($path:expr) => {{
+        const _: () = $crate::private::assert_valid_node_path($path);
+        match $crate::core_types::NodePathBuf::parse($path) {
+            ::std::result::Result::Ok(path) => path,
+            ::std::result::Result::Err(_) => ::std::unreachable!(),
+        }
+    }};
+}
+
 macro_rules! impl_basic_trait_as_sys {
     (
         Drop for $Type:ty as $GdType:ident : $gd_method:ident
@@ -0,0 +1,515 @@
+//! Variant arrays.
+//!
+//! A `godot_array` handle points to a reference-counted `ArrayData`, shared by all copies of the
+//! handle. A null handle is an empty array that has not been written to yet.
+
+use std::cell::UnsafeCell;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use super::variant::{self, PoolValue, Value};
+use super::{check_index, error, load, pool_array, store};
+use crate::core_types::{real, Color, GodotString, Variant, VariantOperator, Vector2, Vector3};
+use crate::sys;
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    register!(api:
+        godot_array_new,
+        godot_array_new_copy,
+        godot_array_set,
+        godot_array_get,
+        godot_array_operator_index,
+        godot_array_operator_index_const,
+        godot_array_append,
+        godot_array_clear,
+        godot_array_count,
+        godot_array_empty,
+        godot_array_erase,
+        godot_array_front,
+        godot_array_back,
+        godot_array_find,
+        godot_array_find_last,
+        godot_array_has,
+        godot_array_hash,
+        godot_array_insert,
+        godot_array_invert,
+        godot_array_pop_back,
+        godot_array_pop_front,
+        godot_array_push_back,
+        godot_array_push_front,
+        godot_array_remove,
+        godot_array_resize,
+        godot_array_rfind,
+        godot_array_size,
+        godot_array_sort,
+        godot_array_bsearch,
+        godot_array_destroy,
+        godot_array_duplicate,
+        godot_array_max,
+        godot_array_min,
+        godot_array_slice,
+    );
+
+    api.godot_array_new_pool_byte_array = godot_array_new_pool_array::<u8>;
+    api.godot_array_new_pool_int_array = godot_array_new_pool_array::<i32>;
+    api.godot_array_new_pool_real_array = godot_array_new_pool_array::<real>;
+    api.godot_array_new_pool_string_array = godot_array_new_pool_array::<GodotString>;
+    api.godot_array_new_pool_vector2_array = godot_array_new_pool_array::<Vector2>;
+    api.godot_array_new_pool_vector3_array = godot_array_new_pool_array::<Vector3>;
+    api.godot_array_new_pool_color_array = godot_array_new_pool_array::<Color>;
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Storage
+
+struct ArrayData {
+    // Like the engine's arrays, these perform no synchronization.
+    items: UnsafeCell<Vec<Variant>>,
+}
+
+// Thread safety is left to the ownership typestates of the core types, as in the engine.
+unsafe impl Send for ArrayData {}
+unsafe impl Sync for ArrayData {}
+
+/// Creates a new array handle with the given contents.
+pub(super) fn new(items: Vec<Variant>) -> sys::godot_array {
+    let mut handle = sys::godot_array::default();
+    let data = ArrayData {
+        items: UnsafeCell::new(items),
+    };
+    unsafe { store(&mut handle, Arc::into_raw(Arc::new(data))) };
+    handle
+}
+
+/// Returns the contents of an array handle.
+pub(super) unsafe fn items<'a>(a: *const sys::godot_array) -> &'a [Variant] {
+    match load::<_, ArrayData>(a).as_ref() {
+        Some(data) => &*data.items.get(),
+        None => &[],
+    }
+}
+
+/// Returns the contents of an array handle for writing, allocating it if necessary.
+unsafe fn items_mut<'a>(a: *mut sys::godot_array) -> &'a mut Vec<Variant> {
+    if load::<_, ArrayData>(a).is_null() {
+        *a = new(Vec::new());
+    }
+    &mut *(*load::<_, ArrayData>(a)).items.get()
+}
+
+/// Element-wise comparison of two arrays.
+pub(super) unsafe fn equals(
+    a: *const sys::godot_array,
+    b: *const sys::godot_array,
+    eq: fn(&Value, &Value) -> bool,
+) -> bool {
+    let (a, b) = (items(a), items(b));
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| eq(variant::value_of(a), variant::value_of(b)))
+}
+
+/// Lexicographic comparison of two arrays.
+pub(super) unsafe fn compare(a: *const sys::godot_array, b: *const sys::godot_array) -> Ordering {
+    let (a, b) = (items(a), items(b));
+    for (a, b) in a.iter().zip(b) {
+        let ordering = variant::compare(variant::value_of(a), variant::value_of(b));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// Equivalent of `Array::duplicate`.
+pub(super) unsafe fn duplicate(a: *const sys::godot_array, deep: bool) -> sys::godot_array {
+    let items = items(a)
+        .iter()
+        .map(|v| {
+            if deep {
+                variant::deep_copy(v)
+            } else {
+                v.clone()
+            }
+        })
+        .collect();
+    new(items)
+}
+
+fn position(items: &[Variant], what: &Value) -> Option<usize> {
+    items
+        .iter()
+        .position(|v| variant::equals(variant::value_of(v), what))
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Functions
+
+unsafe extern "C-unwind" fn godot_array_new(r_dest: *mut sys::godot_array) {
+    *r_dest = new(Vec::new());
+}
+
+unsafe extern "C-unwind" fn godot_array_new_copy(
+    r_dest: *mut sys::godot_array,
+    src: *const sys::godot_array,
+) {
+    let data = load::<_, ArrayData>(src);
+    if data.is_null() {
+        *r_dest = new(Vec::new());
+    } else {
+        Arc::increment_strong_count(data);
+        *r_dest = *src;
+    }
+}
+
+unsafe extern "C-unwind" fn godot_array_new_pool_array<T: PoolValue>(
+    r_dest: *mut sys::godot_array,
+    src: *const T::SysArray,
+) {
+    let items = pool_array::items::<T>(src)
+        .iter()
+        .map(|v| variant::variant(v.to_value()))
+        .collect();
+    *r_dest = new(items);
+}
+
+unsafe extern "C-unwind" fn godot_array_destroy(self_: *mut sys::godot_array) {
+    let data = load::<_, ArrayData>(self_);
+    *self_ = sys::godot_array::default();
+    if !data.is_null() {
+        drop(Arc::from_raw(data));
+    }
+}
+
+unsafe extern "C-unwind" fn godot_array_set(
+    self_: *mut sys::godot_array,
+    idx: sys::godot_int,
+    value: *const sys::godot_variant,
+) {
+    let value = Variant::cast_ref(value).clone();
+    let items = items_mut(self_);
+    check_index("array", idx, items.len());
+    items[idx as usize] = value;
+}
+
+unsafe extern "C-unwind" fn godot_array_get(
+    self_: *const sys::godot_array,
+    idx: sys::godot_int,
+) -> sys::godot_variant {
+    Variant::cast_ref(godot_array_operator_index_const(self_, idx))
+        .clone()
+        .leak()
+}
+
+unsafe extern "C-unwind" fn godot_array_operator_index(
+    self_: *mut sys::godot_array,
+    idx: sys::godot_int,
+) -> *mut sys::godot_variant {
+    let items = items_mut(self_);
+    check_index("array", idx, items.len());
+    items[idx as usize].sys_mut()
+}
+
+unsafe extern "C-unwind" fn godot_array_operator_index_const(
+    self_: *const sys::godot_array,
+    idx: sys::godot_int,
+) -> *const sys::godot_variant {
+    let items = items(self_);
+    check_index("array", idx, items.len());
+    items[idx as usize].sys()
+}
+
+unsafe extern "C-unwind" fn godot_array_append(
+    self_: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+) {
+    godot_array_push_back(self_, value)
+}
+
+unsafe extern "C-unwind" fn godot_array_push_back(
+    self_: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+) {
+    let value = Variant::cast_ref(value).clone();
+    items_mut(self_).push(value);
+}
+
+unsafe extern "C-unwind" fn godot_array_push_front(
+    self_: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+) {
+    let value = Variant::cast_ref(value).clone();
+    items_mut(self_).insert(0, value);
+}
+
+unsafe extern "C-unwind" fn godot_array_insert(
+    self_: *mut sys::godot_array,
+    pos: sys::godot_int,
+    value: *const sys::godot_variant,
+) {
+    let value = Variant::cast_ref(value).clone();
+    let items = items_mut(self_);
+    if pos < 0 || pos as usize > items.len() {
+        error(&format!(
+            "Index p_pos = {pos} is out of bounds (size() = {}).",
+            items.len()
+        ));
+        return;
+    }
+    items.insert(pos as usize, value);
+}
+
+unsafe extern "C-unwind" fn godot_array_clear(self_: *mut sys::godot_array) {
+    let removed = std::mem::take(items_mut(self_));
+    drop(removed);
+}
+
+unsafe extern "C-unwind" fn godot_array_count(
+    self_: *const sys::godot_array,
+    value: *const sys::godot_variant,
+) -> sys::godot_int {
+    let value = variant::value(value);
+    items(self_)
+        .iter()
+        .filter(|v| variant::equals(variant::value_of(v), value))
+        .count() as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_array_empty(self_: *const sys::godot_array) -> sys::godot_bool {
+    items(self_).is_empty()
+}
+
+unsafe extern "C-unwind" fn godot_array_size(self_: *const sys::godot_array) -> sys::godot_int {
+    items(self_).len() as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_array_erase(
+    self_: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+) {
+    let value = variant::value(value).clone();
+    let items = items_mut(self_);
+    if let Some(i) = position(items, &value) {
+        let removed = items.remove(i);
+        drop(removed);
+    }
+}
+
+unsafe extern "C-unwind" fn godot_array_remove(self_: *mut sys::godot_array, idx: sys::godot_int) {
+    let items = items_mut(self_);
+    if idx < 0 || idx as usize >= items.len() {
+        error(&format!(
+            "Index p_idx = {idx} is out of bounds (size() = {}).",
+            items.len()
+        ));
+        return;
+    }
+    let removed = items.remove(idx as usize);
+    drop(removed);
+}
+
+unsafe extern "C-unwind" fn godot_array_front(
+    self_: *const sys::godot_array,
+) -> sys::godot_variant {
+    match items(self_).first() {
+        Some(v) => v.clone().leak(),
+        None => {
+            error("Can't take value from empty array.");
+            variant::new(Value::Nil)
+        }
+    }
+}
+
+unsafe extern "C-unwind" fn godot_array_back(self_: *const sys::godot_array) -> sys::godot_variant {
+    match items(self_).last() {
+        Some(v) => v.clone().leak(),
+        None => {
+            error("Can't take value from empty array.");
+            variant::new(Value::Nil)
+        }
+    }
+}
+
+unsafe extern "C-unwind" fn godot_array_pop_back(
+    self_: *mut sys::godot_array,
+) -> sys::godot_variant {
+    items_mut(self_)
+        .pop()
+        .map_or_else(|| variant::new(Value::Nil), Variant::leak)
+}
+
+unsafe extern "C-unwind" fn godot_array_pop_front(
+    self_: *mut sys::godot_array,
+) -> sys::godot_variant {
+    let items = items_mut(self_);
+    if items.is_empty() {
+        variant::new(Value::Nil)
+    } else {
+        items.remove(0).leak()
+    }
+}
+
+unsafe extern "C-unwind" fn godot_array_find(
+    self_: *const sys::godot_array,
+    what: *const sys::godot_variant,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    let items = items(self_);
+    let from = from.max(0) as usize;
+    if from >= items.len() {
+        return -1;
+    }
+    position(&items[from..], variant::value(what)).map_or(-1, |i| (from + i) as sys::godot_int)
+}
+
+unsafe extern "C-unwind" fn godot_array_rfind(
+    self_: *const sys::godot_array,
+    what: *const sys::godot_variant,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    let items = items(self_);
+    let len = items.len() as sys::godot_int;
+    let from = if from < 0 { len + from } else { from };
+    if from < 0 || from >= len {
+        return -1;
+    }
+    let what = variant::value(what);
+    items[..=from as usize]
+        .iter()
+        .rposition(|v| variant::equals(variant::value_of(v), what))
+        .map_or(-1, |i| i as sys::godot_int)
+}
+
+unsafe extern "C-unwind" fn godot_array_find_last(
+    self_: *const sys::godot_array,
+    what: *const sys::godot_variant,
+) -> sys::godot_int {
+    godot_array_rfind(self_, what, -1)
+}
+
+unsafe extern "C-unwind" fn godot_array_has(
+    self_: *const sys::godot_array,
+    value: *const sys::godot_variant,
+) -> sys::godot_bool {
+    position(items(self_), variant::value(value)).is_some()
+}
+
+unsafe extern "C-unwind" fn godot_array_hash(self_: *const sys::godot_array) -> sys::godot_int {
+    let hash = items(self_)
+        .iter()
+        .fold(5381_u32.wrapping_mul(33), |hash, v| {
+            hash.wrapping_mul(33)
+                .wrapping_add(variant::hash(variant::value_of(v)))
+        });
+    hash as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_array_invert(self_: *mut sys::godot_array) {
+    items_mut(self_).reverse();
+}
+
+unsafe extern "C-unwind" fn godot_array_resize(self_: *mut sys::godot_array, size: sys::godot_int) {
+    let size = size.max(0) as usize;
+    let items = items_mut(self_);
+    if size < items.len() {
+        let removed = items.split_off(size);
+        drop(removed);
+    } else {
+        items.resize_with(size, Variant::nil);
+    }
+}
+
+unsafe extern "C-unwind" fn godot_array_sort(self_: *mut sys::godot_array) {
+    let items = items_mut(self_);
+    let mut sorted = std::mem::take(items);
+    sorted.sort_by(|a, b| variant::compare(variant::value_of(a), variant::value_of(b)));
+    *items_mut(self_) = sorted;
+}
+
+unsafe extern "C-unwind" fn godot_array_bsearch(
+    self_: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+    before: sys::godot_bool,
+) -> sys::godot_int {
+    let value = variant::value(value);
+    items(self_).partition_point(|v| {
+        let ordering = variant::compare(variant::value_of(v), value);
+        ordering == Ordering::Less || (!before && ordering == Ordering::Equal)
+    }) as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_array_duplicate(
+    self_: *const sys::godot_array,
+    deep: sys::godot_bool,
+) -> sys::godot_array {
+    duplicate(self_, deep)
+}
+
+/// Equivalent of `Array::max` and `Array::min`, which return nil for incomparable elements.
+unsafe fn extreme(a: *const sys::godot_array, op: VariantOperator) -> sys::godot_variant {
+    let mut items = items(a).iter();
+    let mut result = match items.next() {
+        Some(first) => variant::value_of(first),
+        None => return variant::new(Value::Nil),
+    };
+    for item in items {
+        let item = variant::value_of(item);
+        match variant::evaluate(op, item, result) {
+            Some(Value::Bool(true)) => result = item,
+            Some(_) => {}
+            None => return variant::new(Value::Nil),
+        }
+    }
+    variant::new(result.clone())
+}
+
+unsafe extern "C-unwind" fn godot_array_max(self_: *const sys::godot_array) -> sys::godot_variant {
+    extreme(self_, VariantOperator::Greater)
+}
+
+unsafe extern "C-unwind" fn godot_array_min(self_: *const sys::godot_array) -> sys::godot_variant {
+    extreme(self_, VariantOperator::Less)
+}
+
+unsafe extern "C-unwind" fn godot_array_slice(
+    self_: *const sys::godot_array,
+    begin: sys::godot_int,
+    end: sys::godot_int,
+    step: sys::godot_int,
+    deep: sys::godot_bool,
+) -> sys::godot_array {
+    let items = items(self_);
+    if step == 0 {
+        error("Array slice step size cannot be zero.");
+        return new(Vec::new());
+    }
+    if items.is_empty() {
+        return new(Vec::new());
+    }
+
+    // Both ends are inclusive in Godot 3.
+    let len = items.len() as i64;
+    let clamp = |index: sys::godot_int| {
+        let index = (index as i64).clamp(-len, len - 1);
+        if index < 0 {
+            len + index
+        } else {
+            index
+        }
+    };
+    let (begin, end, step) = (clamp(begin), clamp(end), step as i64);
+
+    let mut result = Vec::new();
+    let mut i = begin;
+    while (step > 0 && i <= end) || (step < 0 && i >= end) {
+        let item = &items[i as usize];
+        result.push(if deep {
+            variant::deep_copy(item)
+        } else {
+            item.clone()
+        });
+        i += step;
+    }
+    new(result)
+}
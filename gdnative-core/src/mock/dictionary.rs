@@ -0,0 +1,325 @@
+//! Dictionaries.
+//!
+//! A `godot_dictionary` handle points to a reference-counted `DictionaryData`, shared by all
+//! copies of the handle. Entries keep their insertion order, like in the engine. A null handle is
+//! an empty dictionary that has not been written to yet.
+
+use std::cell::UnsafeCell;
+use std::sync::Arc;
+
+use super::variant::{self, Value};
+use super::{array, error, load, store, string};
+use crate::core_types::{Dictionary, Variant};
+use crate::object::NewRef;
+use crate::sys;
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    register!(api:
+        godot_dictionary_new,
+        godot_dictionary_new_copy,
+        godot_dictionary_destroy,
+        godot_dictionary_size,
+        godot_dictionary_empty,
+        godot_dictionary_clear,
+        godot_dictionary_has,
+        godot_dictionary_has_all,
+        godot_dictionary_erase,
+        godot_dictionary_erase_with_return,
+        godot_dictionary_hash,
+        godot_dictionary_keys,
+        godot_dictionary_values,
+        godot_dictionary_get,
+        godot_dictionary_get_with_default,
+        godot_dictionary_set,
+        godot_dictionary_operator_index,
+        godot_dictionary_operator_index_const,
+        godot_dictionary_next,
+        godot_dictionary_operator_equal,
+        godot_dictionary_to_json,
+        godot_dictionary_duplicate,
+    );
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Storage
+
+/// Boxed, so that pointers to keys and values stay valid while other entries are added.
+type Entry = Box<(Variant, Variant)>;
+
+struct DictionaryData {
+    // Like the engine's dictionaries, these perform no synchronization.
+    entries: UnsafeCell<Vec<Entry>>,
+}
+
+// Thread safety is left to the ownership typestates of the core types, as in the engine.
+unsafe impl Send for DictionaryData {}
+unsafe impl Sync for DictionaryData {}
+
+/// Returned by `operator_index_const` for missing keys.
+static NIL: [u8; std::mem::size_of::<sys::godot_variant>()] =
+    [0; std::mem::size_of::<sys::godot_variant>()];
+
+fn new(entries: Vec<Entry>) -> sys::godot_dictionary {
+    let mut handle = sys::godot_dictionary::default();
+    let data = DictionaryData {
+        entries: UnsafeCell::new(entries),
+    };
+    unsafe { store(&mut handle, Arc::into_raw(Arc::new(data))) };
+    handle
+}
+
+unsafe fn entries<'a>(d: *const sys::godot_dictionary) -> &'a [Entry] {
+    match load::<_, DictionaryData>(d).as_ref() {
+        Some(data) => &*data.entries.get(),
+        None => &[],
+    }
+}
+
+unsafe fn entries_mut<'a>(d: *mut sys::godot_dictionary) -> &'a mut Vec<Entry> {
+    if load::<_, DictionaryData>(d).is_null() {
+        *d = new(Vec::new());
+    }
+    &mut *(*load::<_, DictionaryData>(d)).entries.get()
+}
+
+/// Returns the index of the entry with the given key.
+pub(super) unsafe fn find(d: *const sys::godot_dictionary, key: &Value) -> Option<usize> {
+    entries(d)
+        .iter()
+        .position(|entry| variant::key_equals(variant::value_of(&entry.0), key))
+}
+
+/// Returns whether two handles refer to the same dictionary.
+pub(super) unsafe fn same(
+    a: *const sys::godot_dictionary,
+    b: *const sys::godot_dictionary,
+) -> bool {
+    let (a, b) = (load::<_, DictionaryData>(a), load::<_, DictionaryData>(b));
+    a == b && !a.is_null()
+}
+
+/// Equivalent of `Dictionary::duplicate`.
+pub(super) unsafe fn duplicate(
+    d: *const sys::godot_dictionary,
+    deep: bool,
+) -> sys::godot_dictionary {
+    let copy = |v: &Variant| {
+        if deep {
+            variant::deep_copy(v)
+        } else {
+            v.clone()
+        }
+    };
+    let entries = entries(d)
+        .iter()
+        .map(|entry| Box::new((copy(&entry.0), copy(&entry.1))))
+        .collect();
+    new(entries)
+}
+
+/// Returns the value for `key`, inserting nil if it doesn't exist.
+unsafe fn value_mut<'a>(
+    d: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> &'a mut Variant {
+    let index = match find(d, variant::value(key)) {
+        Some(index) => index,
+        None => {
+            let key = Variant::cast_ref(key).clone();
+            let entries = entries_mut(d);
+            entries.push(Box::new((key, Variant::nil())));
+            entries.len() - 1
+        }
+    };
+    &mut entries_mut(d)[index].1
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Functions
+
+unsafe extern "C-unwind" fn godot_dictionary_new(r_dest: *mut sys::godot_dictionary) {
+    *r_dest = new(Vec::new());
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_new_copy(
+    r_dest: *mut sys::godot_dictionary,
+    src: *const sys::godot_dictionary,
+) {
+    let data = load::<_, DictionaryData>(src);
+    if data.is_null() {
+        *r_dest = new(Vec::new());
+    } else {
+        Arc::increment_strong_count(data);
+        *r_dest = *src;
+    }
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_destroy(self_: *mut sys::godot_dictionary) {
+    let data = load::<_, DictionaryData>(self_);
+    *self_ = sys::godot_dictionary::default();
+    if !data.is_null() {
+        drop(Arc::from_raw(data));
+    }
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_size(
+    self_: *const sys::godot_dictionary,
+) -> sys::godot_int {
+    entries(self_).len() as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_empty(
+    self_: *const sys::godot_dictionary,
+) -> sys::godot_bool {
+    entries(self_).is_empty()
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_clear(self_: *mut sys::godot_dictionary) {
+    let removed = std::mem::take(entries_mut(self_));
+    drop(removed);
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_has(
+    self_: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> sys::godot_bool {
+    find(self_, variant::value(key)).is_some()
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_has_all(
+    self_: *const sys::godot_dictionary,
+    keys: *const sys::godot_array,
+) -> sys::godot_bool {
+    array::items(keys)
+        .iter()
+        .all(|key| find(self_, variant::value_of(key)).is_some())
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_erase(
+    self_: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) {
+    godot_dictionary_erase_with_return(self_, key);
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_erase_with_return(
+    self_: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> bool {
+    match find(self_, variant::value(key)) {
+        Some(index) => {
+            let removed = entries_mut(self_).remove(index);
+            drop(removed);
+            true
+        }
+        None => false,
+    }
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_hash(
+    self_: *const sys::godot_dictionary,
+) -> sys::godot_int {
+    load::<_, DictionaryData>(self_) as usize as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_keys(
+    self_: *const sys::godot_dictionary,
+) -> sys::godot_array {
+    array::new(entries(self_).iter().map(|entry| entry.0.clone()).collect())
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_values(
+    self_: *const sys::godot_dictionary,
+) -> sys::godot_array {
+    array::new(entries(self_).iter().map(|entry| entry.1.clone()).collect())
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_get(
+    self_: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> sys::godot_variant {
+    match find(self_, variant::value(key)) {
+        Some(index) => entries(self_)[index].1.clone().leak(),
+        None => {
+            error("Bug: Dictionary::operator[] used when there was no value for the given key, please report.");
+            variant::new(Value::Nil)
+        }
+    }
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_get_with_default(
+    self_: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+    default: *const sys::godot_variant,
+) -> sys::godot_variant {
+    match find(self_, variant::value(key)) {
+        Some(index) => entries(self_)[index].1.clone().leak(),
+        None => Variant::cast_ref(default).clone().leak(),
+    }
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_set(
+    self_: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+    value: *const sys::godot_variant,
+) {
+    let value = Variant::cast_ref(value).clone();
+    *value_mut(self_, key) = value;
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_operator_index(
+    self_: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> *mut sys::godot_variant {
+    value_mut(self_, key).sys_mut()
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_operator_index_const(
+    self_: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> *const sys::godot_variant {
+    match find(self_, variant::value(key)) {
+        Some(index) => entries(self_)[index].1.sys(),
+        None => NIL.as_ptr() as *const sys::godot_variant,
+    }
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_next(
+    self_: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> *mut sys::godot_variant {
+    let entries = entries(self_);
+    let next = if key.is_null() {
+        0
+    } else {
+        match find(self_, variant::value(key)) {
+            Some(index) => index + 1,
+            None => return std::ptr::null_mut(),
+        }
+    };
+    match entries.get(next) {
+        Some(entry) => entry.0.sys() as *mut _,
+        None => std::ptr::null_mut(),
+    }
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_operator_equal(
+    self_: *const sys::godot_dictionary,
+    b: *const sys::godot_dictionary,
+) -> sys::godot_bool {
+    same(self_, b)
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_to_json(
+    self_: *const sys::godot_dictionary,
+) -> sys::godot_string {
+    let dict = variant::borrow(self_, Dictionary::from_sys);
+    string::new(variant::json(&Value::Dictionary(dict.new_ref())))
+}
+
+unsafe extern "C-unwind" fn godot_dictionary_duplicate(
+    self_: *const sys::godot_dictionary,
+    deep: sys::godot_bool,
+) -> sys::godot_dictionary {
+    duplicate(self_, deep)
+}
@@ -0,0 +1,119 @@
+//! Engine-free implementation of the core GDNative API, for use in ordinary unit tests.
+//!
+//! Most core types call into the engine through the GDNative API table, so they can normally
+//! only be used while Godot is running. With the `mock-api` feature, [`install`] binds a table
+//! that implements the functions behind [`GodotString`], [`Variant`], [`VariantArray`],
+//! [`Dictionary`], [`PoolArray`] and [`NodePath`] in pure Rust:
+//!
+//! ```
+//! # #[cfg(feature = "mock-api")] {
+//! use gdnative::core_types::Dictionary;
+//!
+//! gdnative_core::mock::install();
+//!
+//! let dict = Dictionary::new();
+//! dict.insert("answer", 42);
+//! assert_eq!(Some(42), dict.get("answer").and_then(|v| v.to::<i64>()));
+//! # }
+//! ```
+//!
+//! The implementation follows the observable behavior of Godot 3: strings are immutable and
+//! shared, arrays and dictionaries are reference-counted, and pool arrays have copy semantics.
+//! Like the engine, collections perform no synchronization of their own.
+//!
+//! Objects cannot be created without the engine. Method binds are reported as missing, class
+//! constructors as unavailable, and calls through `Variant::call` fail with an invalid method
+//! error. Any other API function that is not implemented panics, naming the function.
+//!
+//! [`GodotString`]: crate::core_types::GodotString
+//! [`Variant`]: crate::core_types::Variant
+//! [`VariantArray`]: crate::core_types::VariantArray
+//! [`Dictionary`]: crate::core_types::Dictionary
+//! [`PoolArray`]: crate::core_types::PoolArray
+//! [`NodePath`]: crate::core_types::NodePath
+
+use std::ptr;
+use std::sync::Once;
+
+use crate::private;
+use crate::sys;
+
+/// Assigns mock functions to the API table fields of the same name.
+macro_rules! register {
+    ($api:ident: $($function:ident),* $(,)?) => {
+        $($api.$function = $function;)*
+    };
+}
+
+mod array;
+mod dictionary;
+mod digest;
+mod node_path;
+mod object;
+mod pool_array;
+mod string;
+mod variant;
+
+#[cfg(test)]
+mod tests;
+
+/// Binds the mock API table, unless an API table is already bound.
+///
+/// This is safe to call from multiple tests concurrently. When the engine's own API is bound,
+/// e.g. in integration tests running inside Godot, this does nothing.
+#[inline]
+pub fn install() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        if !private::is_api_bound() {
+            unsafe { private::bind_mock_api(api()) }
+        }
+    });
+}
+
+fn api() -> sys::GodotApi {
+    let mut api = sys::GodotApi::unsupported();
+    string::register(&mut api);
+    variant::register(&mut api);
+    array::register(&mut api);
+    dictionary::register(&mut api);
+    pool_array::register(&mut api);
+    node_path::register(&mut api);
+    object::register(&mut api);
+    api
+}
+
+/// Panics on an out-of-bounds index, where the engine would crash with `CRASH_BAD_INDEX`.
+fn check_index(what: &str, index: impl TryInto<usize> + Copy + std::fmt::Display, len: usize) {
+    match index.try_into() {
+        Ok(i) if i < len => {}
+        _ => fail(&format!(
+            "{what} index {index} out of bounds (length {len})"
+        )),
+    }
+}
+
+/// Reports an unrecoverable error by panicking.
+///
+/// The mock functions are declared `extern "C-unwind"`, so the panic reaches the calling test.
+#[cold]
+fn fail(message: &str) -> ! {
+    panic!("gdnative-core mock API: {message}")
+}
+
+/// Reports a recoverable error, like the engine's `ERR_FAIL_*` macros.
+fn error(message: &str) {
+    eprintln!("ERROR: {message}");
+}
+
+/// Reads the data pointer stored in an opaque, pointer-sized handle type.
+///
+/// Handles in the sys layer are plain byte arrays without alignment requirements.
+unsafe fn load<H, T>(handle: *const H) -> *const T {
+    ptr::read_unaligned(handle as *const *const T)
+}
+
+/// Stores a data pointer in an opaque, pointer-sized handle type.
+unsafe fn store<H, T>(handle: *mut H, data: *const T) {
+    ptr::write_unaligned(handle as *mut *const T, data)
+}
@@ -0,0 +1,198 @@
+//! Node paths.
+//!
+//! A `godot_node_path` handle points to reference-counted, immutable `NodePathData`. A null
+//! handle is the empty path.
+
+use std::sync::Arc;
+
+use super::{load, store, string};
+use crate::sys;
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    register!(api:
+        godot_node_path_new,
+        godot_node_path_new_copy,
+        godot_node_path_destroy,
+        godot_node_path_as_string,
+        godot_node_path_is_absolute,
+        godot_node_path_get_name_count,
+        godot_node_path_get_name,
+        godot_node_path_get_subname_count,
+        godot_node_path_get_subname,
+        godot_node_path_get_concatenated_subnames,
+        godot_node_path_is_empty,
+        godot_node_path_operator_equal,
+        godot_node_path_get_as_property_path,
+    );
+}
+
+#[derive(PartialEq, Eq)]
+struct NodePathData {
+    absolute: bool,
+    names: Vec<String>,
+    subnames: Vec<String>,
+}
+
+fn new(data: NodePathData) -> sys::godot_node_path {
+    let mut handle = sys::godot_node_path::default();
+    if data.absolute || !data.names.is_empty() || !data.subnames.is_empty() {
+        unsafe { store(&mut handle, Arc::into_raw(Arc::new(data))) };
+    }
+    handle
+}
+
+/// Returns an empty node path handle.
+pub(super) fn empty() -> sys::godot_node_path {
+    sys::godot_node_path::default()
+}
+
+unsafe fn data<'a>(np: *const sys::godot_node_path) -> Option<&'a NodePathData> {
+    load::<_, NodePathData>(np).as_ref()
+}
+
+/// Equivalent of the `NodePath(const String &)` constructor.
+fn parse(path: &str) -> NodePathData {
+    let absolute = path.starts_with('/');
+    let (names, subnames) = match path.find(':') {
+        Some(colon) => (&path[..colon], Some(&path[colon + 1..])),
+        None => (path, None),
+    };
+
+    let segments = |text: &str, separator: char| -> Vec<String> {
+        text.split(separator)
+            .filter(|segment| !segment.is_empty())
+            .map(String::from)
+            .collect()
+    };
+
+    NodePathData {
+        absolute,
+        names: segments(names, '/'),
+        subnames: subnames.map_or_else(Vec::new, |subnames| segments(subnames, ':')),
+    }
+}
+
+/// Equivalent of `NodePath::operator String`.
+fn to_string(data: &NodePathData) -> String {
+    let mut result = String::new();
+    if data.absolute {
+        result.push('/');
+    }
+    result.push_str(&data.names.join("/"));
+    for subname in &data.subnames {
+        result.push(':');
+        result.push_str(subname);
+    }
+    result
+}
+
+unsafe extern "C-unwind" fn godot_node_path_new(
+    r_dest: *mut sys::godot_node_path,
+    from: *const sys::godot_string,
+) {
+    *r_dest = new(parse(string::text(from)));
+}
+
+unsafe extern "C-unwind" fn godot_node_path_new_copy(
+    r_dest: *mut sys::godot_node_path,
+    src: *const sys::godot_node_path,
+) {
+    let data = load::<_, NodePathData>(src);
+    if !data.is_null() {
+        Arc::increment_strong_count(data);
+    }
+    *r_dest = *src;
+}
+
+unsafe extern "C-unwind" fn godot_node_path_destroy(self_: *mut sys::godot_node_path) {
+    let data = load::<_, NodePathData>(self_);
+    *self_ = sys::godot_node_path::default();
+    if !data.is_null() {
+        drop(Arc::from_raw(data));
+    }
+}
+
+unsafe extern "C-unwind" fn godot_node_path_as_string(
+    self_: *const sys::godot_node_path,
+) -> sys::godot_string {
+    string::new(data(self_).map_or_else(String::new, to_string))
+}
+
+unsafe extern "C-unwind" fn godot_node_path_is_absolute(
+    self_: *const sys::godot_node_path,
+) -> sys::godot_bool {
+    data(self_).map_or(false, |data| data.absolute)
+}
+
+unsafe extern "C-unwind" fn godot_node_path_get_name_count(
+    self_: *const sys::godot_node_path,
+) -> sys::godot_int {
+    data(self_).map_or(0, |data| data.names.len() as sys::godot_int)
+}
+
+unsafe extern "C-unwind" fn godot_node_path_get_name(
+    self_: *const sys::godot_node_path,
+    idx: sys::godot_int,
+) -> sys::godot_string {
+    let name = data(self_)
+        .and_then(|data| data.names.get(usize::try_from(idx).ok()?))
+        .map_or("", String::as_str);
+    string::new(name)
+}
+
+unsafe extern "C-unwind" fn godot_node_path_get_subname_count(
+    self_: *const sys::godot_node_path,
+) -> sys::godot_int {
+    data(self_).map_or(0, |data| data.subnames.len() as sys::godot_int)
+}
+
+unsafe extern "C-unwind" fn godot_node_path_get_subname(
+    self_: *const sys::godot_node_path,
+    idx: sys::godot_int,
+) -> sys::godot_string {
+    let subname = data(self_)
+        .and_then(|data| data.subnames.get(usize::try_from(idx).ok()?))
+        .map_or("", String::as_str);
+    string::new(subname)
+}
+
+unsafe extern "C-unwind" fn godot_node_path_get_concatenated_subnames(
+    self_: *const sys::godot_node_path,
+) -> sys::godot_string {
+    string::new(data(self_).map_or_else(String::new, |data| data.subnames.join(":")))
+}
+
+unsafe extern "C-unwind" fn godot_node_path_is_empty(
+    self_: *const sys::godot_node_path,
+) -> sys::godot_bool {
+    data(self_).is_none()
+}
+
+unsafe extern "C-unwind" fn godot_node_path_operator_equal(
+    self_: *const sys::godot_node_path,
+    b: *const sys::godot_node_path,
+) -> sys::godot_bool {
+    data(self_) == data(b)
+}
+
+unsafe extern "C-unwind" fn godot_node_path_get_as_property_path(
+    self_: *const sys::godot_node_path,
+) -> sys::godot_node_path {
+    match data(self_) {
+        Some(data) if !data.names.is_empty() => {
+            let mut subnames = vec![data.names.join("/")];
+            subnames.extend(data.subnames.iter().cloned());
+            new(NodePathData {
+                absolute: false,
+                names: Vec::new(),
+                subnames,
+            })
+        }
+        Some(data) => new(NodePathData {
+            absolute: false,
+            names: Vec::new(),
+            subnames: data.subnames.clone(),
+        }),
+        None => empty(),
+    }
+}
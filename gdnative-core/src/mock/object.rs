@@ -0,0 +1,158 @@
+//! Objects, method binds, RIDs, memory and printing.
+//!
+//! Objects only exist inside the engine, so there are no instances to look up or call. Queries
+//! for classes and method binds report them as missing, which the core types handle gracefully.
+
+use std::ffi::{c_void, CStr};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use super::{load, store, string, variant};
+use crate::sys;
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    register!(api:
+        godot_method_bind_get_method,
+        godot_method_bind_call,
+        godot_get_class_constructor,
+        godot_global_get_singleton,
+        godot_is_instance_valid,
+        godot_instance_from_id,
+        godot_rid_new,
+        godot_rid_get_id,
+        godot_rid_operator_equal,
+        godot_rid_operator_less,
+        godot_alloc,
+        godot_realloc,
+        godot_free,
+        godot_print,
+        godot_print_warning,
+        godot_print_error,
+    );
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Objects
+
+unsafe extern "C-unwind" fn godot_method_bind_get_method(
+    _classname: *const c_char,
+    _methodname: *const c_char,
+) -> *mut sys::godot_method_bind {
+    ptr::null_mut()
+}
+
+unsafe extern "C-unwind" fn godot_method_bind_call(
+    _method_bind: *mut sys::godot_method_bind,
+    _instance: *mut sys::godot_object,
+    _args: *mut *const sys::godot_variant,
+    _arg_count: c_int,
+    call_error: *mut sys::godot_variant_call_error,
+) -> sys::godot_variant {
+    if !call_error.is_null() {
+        (*call_error).error =
+            sys::godot_variant_call_error_error_GODOT_CALL_ERROR_CALL_ERROR_INVALID_METHOD;
+    }
+    variant::new(variant::Value::Nil)
+}
+
+unsafe extern "C-unwind" fn godot_get_class_constructor(
+    _classname: *const c_char,
+) -> sys::godot_class_constructor {
+    None
+}
+
+unsafe extern "C-unwind" fn godot_global_get_singleton(
+    _name: *mut c_char,
+) -> *mut sys::godot_object {
+    ptr::null_mut()
+}
+
+unsafe extern "C-unwind" fn godot_is_instance_valid(_object: *const sys::godot_object) -> bool {
+    false
+}
+
+unsafe extern "C-unwind" fn godot_instance_from_id(
+    _instance_id: sys::godot_int,
+) -> *mut sys::godot_object {
+    ptr::null_mut()
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// RIDs, which are never occupied without resources
+
+unsafe extern "C-unwind" fn godot_rid_new(r_dest: *mut sys::godot_rid) {
+    store(r_dest, ptr::null::<()>());
+}
+
+unsafe extern "C-unwind" fn godot_rid_get_id(self_: *const sys::godot_rid) -> sys::godot_int {
+    load::<_, ()>(self_) as usize as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_rid_operator_equal(
+    self_: *const sys::godot_rid,
+    b: *const sys::godot_rid,
+) -> sys::godot_bool {
+    variant::rid_bits(self_) == variant::rid_bits(b)
+}
+
+unsafe extern "C-unwind" fn godot_rid_operator_less(
+    self_: *const sys::godot_rid,
+    b: *const sys::godot_rid,
+) -> sys::godot_bool {
+    variant::rid_bits(self_) < variant::rid_bits(b)
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Memory
+
+unsafe extern "C-unwind" fn godot_alloc(bytes: c_int) -> *mut c_void {
+    libc::malloc(bytes.max(0) as usize)
+}
+
+unsafe extern "C-unwind" fn godot_realloc(ptr: *mut c_void, bytes: c_int) -> *mut c_void {
+    libc::realloc(ptr, bytes.max(0) as usize)
+}
+
+unsafe extern "C-unwind" fn godot_free(ptr: *mut c_void) {
+    libc::free(ptr)
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Printing
+
+unsafe extern "C-unwind" fn godot_print(message: *const sys::godot_string) {
+    println!("{}", string::text(message));
+}
+
+unsafe fn location(function: *const c_char, file: *const c_char, line: c_int) -> String {
+    let text = |s: *const c_char| {
+        if s.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(s).to_string_lossy().into_owned()
+        }
+    };
+    format!("{} ({}:{line})", text(function), text(file))
+}
+
+unsafe extern "C-unwind" fn godot_print_warning(
+    description: *const c_char,
+    function: *const c_char,
+    file: *const c_char,
+    line: c_int,
+) {
+    let description = CStr::from_ptr(description).to_string_lossy();
+    eprintln!("WARNING: {description}");
+    eprintln!("   At: {}", location(function, file, line));
+}
+
+unsafe extern "C-unwind" fn godot_print_error(
+    description: *const c_char,
+    function: *const c_char,
+    file: *const c_char,
+    line: c_int,
+) {
+    let description = CStr::from_ptr(description).to_string_lossy();
+    eprintln!("ERROR: {description}");
+    eprintln!("   At: {}", location(function, file, line));
+}
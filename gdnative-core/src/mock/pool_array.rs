@@ -0,0 +1,438 @@
+//! Pool arrays of all element types.
+//!
+//! A pool array handle points to a boxed `Vec` of core type elements, which share their layout
+//! with the sys types. Copies are deep, which is indistinguishable from the engine's
+//! copy-on-write behavior. A null handle is an empty array that has not been written to yet.
+
+use std::mem::ManuallyDrop;
+use std::ptr;
+
+use super::variant::{self, PoolValue};
+use super::{array, error, load, store};
+use crate::core_types::{real, Color, GodotString, Vector2, Vector3};
+use crate::sys;
+
+/// Assigns generic mock functions, instantiated for one element type, to API table fields.
+macro_rules! register_pool {
+    ($api:ident, $T:ty: $($field:ident => $function:ident,)*) => {
+        $($api.$field = $function::<$T>;)*
+    };
+}
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    register_pool!(api, u8:
+        godot_pool_byte_array_new => new,
+        godot_pool_byte_array_new_copy => new_copy,
+        godot_pool_byte_array_new_with_array => new_with_array,
+        godot_pool_byte_array_append => append,
+        godot_pool_byte_array_append_array => append_array,
+        godot_pool_byte_array_insert => insert,
+        godot_pool_byte_array_invert => invert,
+        godot_pool_byte_array_push_back => push_back,
+        godot_pool_byte_array_remove => remove,
+        godot_pool_byte_array_resize => resize,
+        godot_pool_byte_array_read => read,
+        godot_pool_byte_array_write => write,
+        godot_pool_byte_array_set => set,
+        godot_pool_byte_array_get => get,
+        godot_pool_byte_array_size => size,
+        godot_pool_byte_array_empty => empty,
+        godot_pool_byte_array_destroy => destroy,
+        godot_pool_byte_array_read_access_copy => read_access_copy,
+        godot_pool_byte_array_read_access_ptr => read_access_ptr,
+        godot_pool_byte_array_read_access_operator_assign => read_access_operator_assign,
+        godot_pool_byte_array_read_access_destroy => read_access_destroy,
+        godot_pool_byte_array_write_access_copy => write_access_copy,
+        godot_pool_byte_array_write_access_ptr => write_access_ptr,
+        godot_pool_byte_array_write_access_operator_assign => write_access_operator_assign,
+        godot_pool_byte_array_write_access_destroy => write_access_destroy,
+    );
+    register_pool!(api, i32:
+        godot_pool_int_array_new => new,
+        godot_pool_int_array_new_copy => new_copy,
+        godot_pool_int_array_new_with_array => new_with_array,
+        godot_pool_int_array_append => append,
+        godot_pool_int_array_append_array => append_array,
+        godot_pool_int_array_insert => insert,
+        godot_pool_int_array_invert => invert,
+        godot_pool_int_array_push_back => push_back,
+        godot_pool_int_array_remove => remove,
+        godot_pool_int_array_resize => resize,
+        godot_pool_int_array_read => read,
+        godot_pool_int_array_write => write,
+        godot_pool_int_array_set => set,
+        godot_pool_int_array_get => get,
+        godot_pool_int_array_size => size,
+        godot_pool_int_array_empty => empty,
+        godot_pool_int_array_destroy => destroy,
+        godot_pool_int_array_read_access_copy => read_access_copy,
+        godot_pool_int_array_read_access_ptr => read_access_ptr,
+        godot_pool_int_array_read_access_operator_assign => read_access_operator_assign,
+        godot_pool_int_array_read_access_destroy => read_access_destroy,
+        godot_pool_int_array_write_access_copy => write_access_copy,
+        godot_pool_int_array_write_access_ptr => write_access_ptr,
+        godot_pool_int_array_write_access_operator_assign => write_access_operator_assign,
+        godot_pool_int_array_write_access_destroy => write_access_destroy,
+    );
+    register_pool!(api, real:
+        godot_pool_real_array_new => new,
+        godot_pool_real_array_new_copy => new_copy,
+        godot_pool_real_array_new_with_array => new_with_array,
+        godot_pool_real_array_append => append,
+        godot_pool_real_array_append_array => append_array,
+        godot_pool_real_array_insert => insert,
+        godot_pool_real_array_invert => invert,
+        godot_pool_real_array_push_back => push_back,
+        godot_pool_real_array_remove => remove,
+        godot_pool_real_array_resize => resize,
+        godot_pool_real_array_read => read,
+        godot_pool_real_array_write => write,
+        godot_pool_real_array_set => set,
+        godot_pool_real_array_get => get,
+        godot_pool_real_array_size => size,
+        godot_pool_real_array_empty => empty,
+        godot_pool_real_array_destroy => destroy,
+        godot_pool_real_array_read_access_copy => read_access_copy,
+        godot_pool_real_array_read_access_ptr => read_access_ptr,
+        godot_pool_real_array_read_access_operator_assign => read_access_operator_assign,
+        godot_pool_real_array_read_access_destroy => read_access_destroy,
+        godot_pool_real_array_write_access_copy => write_access_copy,
+        godot_pool_real_array_write_access_ptr => write_access_ptr,
+        godot_pool_real_array_write_access_operator_assign => write_access_operator_assign,
+        godot_pool_real_array_write_access_destroy => write_access_destroy,
+    );
+    register_pool!(api, GodotString:
+        godot_pool_string_array_new => new,
+        godot_pool_string_array_new_copy => new_copy,
+        godot_pool_string_array_new_with_array => new_with_array,
+        godot_pool_string_array_append => append,
+        godot_pool_string_array_append_array => append_array,
+        godot_pool_string_array_insert => insert,
+        godot_pool_string_array_invert => invert,
+        godot_pool_string_array_push_back => push_back,
+        godot_pool_string_array_remove => remove,
+        godot_pool_string_array_resize => resize,
+        godot_pool_string_array_read => read,
+        godot_pool_string_array_write => write,
+        godot_pool_string_array_set => set,
+        godot_pool_string_array_get => get,
+        godot_pool_string_array_size => size,
+        godot_pool_string_array_empty => empty,
+        godot_pool_string_array_destroy => destroy,
+        godot_pool_string_array_read_access_copy => read_access_copy,
+        godot_pool_string_array_read_access_ptr => read_access_ptr,
+        godot_pool_string_array_read_access_operator_assign => read_access_operator_assign,
+        godot_pool_string_array_read_access_destroy => read_access_destroy,
+        godot_pool_string_array_write_access_copy => write_access_copy,
+        godot_pool_string_array_write_access_ptr => write_access_ptr,
+        godot_pool_string_array_write_access_operator_assign => write_access_operator_assign,
+        godot_pool_string_array_write_access_destroy => write_access_destroy,
+    );
+    register_pool!(api, Vector2:
+        godot_pool_vector2_array_new => new,
+        godot_pool_vector2_array_new_copy => new_copy,
+        godot_pool_vector2_array_new_with_array => new_with_array,
+        godot_pool_vector2_array_append => append,
+        godot_pool_vector2_array_append_array => append_array,
+        godot_pool_vector2_array_insert => insert,
+        godot_pool_vector2_array_invert => invert,
+        godot_pool_vector2_array_push_back => push_back,
+        godot_pool_vector2_array_remove => remove,
+        godot_pool_vector2_array_resize => resize,
+        godot_pool_vector2_array_read => read,
+        godot_pool_vector2_array_write => write,
+        godot_pool_vector2_array_set => set,
+        godot_pool_vector2_array_get => get,
+        godot_pool_vector2_array_size => size,
+        godot_pool_vector2_array_empty => empty,
+        godot_pool_vector2_array_destroy => destroy,
+        godot_pool_vector2_array_read_access_copy => read_access_copy,
+        godot_pool_vector2_array_read_access_ptr => read_access_ptr,
+        godot_pool_vector2_array_read_access_operator_assign => read_access_operator_assign,
+        godot_pool_vector2_array_read_access_destroy => read_access_destroy,
+        godot_pool_vector2_array_write_access_copy => write_access_copy,
+        godot_pool_vector2_array_write_access_ptr => write_access_ptr,
+        godot_pool_vector2_array_write_access_operator_assign => write_access_operator_assign,
+        godot_pool_vector2_array_write_access_destroy => write_access_destroy,
+    );
+    register_pool!(api, Vector3:
+        godot_pool_vector3_array_new => new,
+        godot_pool_vector3_array_new_copy => new_copy,
+        godot_pool_vector3_array_new_with_array => new_with_array,
+        godot_pool_vector3_array_append => append,
+        godot_pool_vector3_array_append_array => append_array,
+        godot_pool_vector3_array_insert => insert,
+        godot_pool_vector3_array_invert => invert,
+        godot_pool_vector3_array_push_back => push_back,
+        godot_pool_vector3_array_remove => remove,
+        godot_pool_vector3_array_resize => resize,
+        godot_pool_vector3_array_read => read,
+        godot_pool_vector3_array_write => write,
+        godot_pool_vector3_array_set => set,
+        godot_pool_vector3_array_get => get,
+        godot_pool_vector3_array_size => size,
+        godot_pool_vector3_array_empty => empty,
+        godot_pool_vector3_array_destroy => destroy,
+        godot_pool_vector3_array_read_access_copy => read_access_copy,
+        godot_pool_vector3_array_read_access_ptr => read_access_ptr,
+        godot_pool_vector3_array_read_access_operator_assign => read_access_operator_assign,
+        godot_pool_vector3_array_read_access_destroy => read_access_destroy,
+        godot_pool_vector3_array_write_access_copy => write_access_copy,
+        godot_pool_vector3_array_write_access_ptr => write_access_ptr,
+        godot_pool_vector3_array_write_access_operator_assign => write_access_operator_assign,
+        godot_pool_vector3_array_write_access_destroy => write_access_destroy,
+    );
+    register_pool!(api, Color:
+        godot_pool_color_array_new => new,
+        godot_pool_color_array_new_copy => new_copy,
+        godot_pool_color_array_new_with_array => new_with_array,
+        godot_pool_color_array_append => append,
+        godot_pool_color_array_append_array => append_array,
+        godot_pool_color_array_insert => insert,
+        godot_pool_color_array_invert => invert,
+        godot_pool_color_array_push_back => push_back,
+        godot_pool_color_array_remove => remove,
+        godot_pool_color_array_resize => resize,
+        godot_pool_color_array_read => read,
+        godot_pool_color_array_write => write,
+        godot_pool_color_array_set => set,
+        godot_pool_color_array_get => get,
+        godot_pool_color_array_size => size,
+        godot_pool_color_array_empty => empty,
+        godot_pool_color_array_destroy => destroy,
+        godot_pool_color_array_read_access_copy => read_access_copy,
+        godot_pool_color_array_read_access_ptr => read_access_ptr,
+        godot_pool_color_array_read_access_operator_assign => read_access_operator_assign,
+        godot_pool_color_array_read_access_destroy => read_access_destroy,
+        godot_pool_color_array_write_access_copy => write_access_copy,
+        godot_pool_color_array_write_access_ptr => write_access_ptr,
+        godot_pool_color_array_write_access_operator_assign => write_access_operator_assign,
+        godot_pool_color_array_write_access_destroy => write_access_destroy,
+    );
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Storage
+
+/// Returns the contents of a pool array handle.
+pub(super) unsafe fn items<'a, T: PoolValue>(a: *const T::SysArray) -> &'a [T] {
+    match load::<_, Vec<T>>(a).as_ref() {
+        Some(items) => items,
+        None => &[],
+    }
+}
+
+unsafe fn items_mut<'a, T: PoolValue>(a: *mut T::SysArray) -> &'a mut Vec<T> {
+    if load::<_, Vec<T>>(a).is_null() {
+        store(a, Box::into_raw(Box::<Vec<T>>::default()));
+    }
+    &mut *(load::<_, Vec<T>>(a) as *mut Vec<T>)
+}
+
+/// Stores elements in a new pool array handle.
+unsafe fn create<T: PoolValue>(r_dest: *mut T::SysArray, items: Vec<T>) {
+    store(r_dest, Box::into_raw(Box::new(items)));
+}
+
+/// Read or write access, which only holds a pointer to the elements.
+struct Access<T> {
+    ptr: *mut T,
+}
+
+impl<T> Clone for Access<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Access<T> {}
+
+unsafe fn access<T, A>(ptr: *mut T) -> *mut A {
+    Box::into_raw(Box::new(Access { ptr })).cast()
+}
+
+unsafe fn access_ptr<T, A>(access: *const A) -> *mut T {
+    (*access.cast::<Access<T>>()).ptr
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Functions
+
+unsafe extern "C-unwind" fn new<T: PoolValue>(r_dest: *mut T::SysArray) {
+    store(r_dest, ptr::null::<Vec<T>>());
+}
+
+unsafe extern "C-unwind" fn new_copy<T: PoolValue>(
+    r_dest: *mut T::SysArray,
+    src: *const T::SysArray,
+) {
+    create(r_dest, items::<T>(src).to_vec());
+}
+
+unsafe extern "C-unwind" fn new_with_array<T: PoolValue>(
+    r_dest: *mut T::SysArray,
+    a: *const sys::godot_array,
+) {
+    let items = array::items(a)
+        .iter()
+        .map(|v| T::from_value(variant::value_of(v)))
+        .collect();
+    create(r_dest, items);
+}
+
+unsafe extern "C-unwind" fn destroy<T: PoolValue>(self_: *mut T::SysArray) {
+    let items = load::<_, Vec<T>>(self_) as *mut Vec<T>;
+    store(self_, ptr::null::<Vec<T>>());
+    if !items.is_null() {
+        drop(Box::from_raw(items));
+    }
+}
+
+unsafe extern "C-unwind" fn append<T: PoolValue>(self_: *mut T::SysArray, data: T::SysRefTy) {
+    push_back::<T>(self_, data)
+}
+
+unsafe extern "C-unwind" fn push_back<T: PoolValue>(self_: *mut T::SysArray, data: T::SysRefTy) {
+    let value = T::element_from_sys_ref(data);
+    items_mut::<T>(self_).push(value);
+}
+
+unsafe extern "C-unwind" fn append_array<T: PoolValue>(
+    self_: *mut T::SysArray,
+    array: *const T::SysArray,
+) {
+    let other = items::<T>(array).to_vec();
+    items_mut::<T>(self_).extend(other);
+}
+
+unsafe extern "C-unwind" fn insert<T: PoolValue>(
+    self_: *mut T::SysArray,
+    idx: sys::godot_int,
+    data: T::SysRefTy,
+) -> sys::godot_error {
+    let value = T::element_from_sys_ref(data);
+    let items = items_mut::<T>(self_);
+    if idx < 0 || idx as usize > items.len() {
+        return sys::godot_error_GODOT_ERR_INVALID_PARAMETER;
+    }
+    items.insert(idx as usize, value);
+    sys::godot_error_GODOT_OK
+}
+
+unsafe extern "C-unwind" fn invert<T: PoolValue>(self_: *mut T::SysArray) {
+    items_mut::<T>(self_).reverse();
+}
+
+unsafe extern "C-unwind" fn remove<T: PoolValue>(self_: *mut T::SysArray, idx: sys::godot_int) {
+    let items = items_mut::<T>(self_);
+    if idx < 0 || idx as usize >= items.len() {
+        error(&format!(
+            "Index p_index = {idx} is out of bounds (size() = {}).",
+            items.len()
+        ));
+        return;
+    }
+    items.remove(idx as usize);
+}
+
+unsafe extern "C-unwind" fn resize<T: PoolValue>(self_: *mut T::SysArray, size: sys::godot_int) {
+    let default = T::from_value(&variant::Value::Nil);
+    items_mut::<T>(self_).resize(size.max(0) as usize, default);
+}
+
+unsafe extern "C-unwind" fn set<T: PoolValue>(
+    self_: *mut T::SysArray,
+    idx: sys::godot_int,
+    data: T::SysRefTy,
+) {
+    let value = T::element_from_sys_ref(data);
+    let items = items_mut::<T>(self_);
+    if idx < 0 || idx as usize >= items.len() {
+        error(&format!(
+            "Index p_index = {idx} is out of bounds (size() = {}).",
+            items.len()
+        ));
+        return;
+    }
+    items[idx as usize] = value;
+}
+
+unsafe extern "C-unwind" fn get<T: PoolValue>(
+    self_: *const T::SysArray,
+    idx: sys::godot_int,
+) -> T::SysTy {
+    let items = items::<T>(self_);
+    match usize::try_from(idx).ok().and_then(|i| items.get(i)) {
+        // Elements share their layout with the sys type, and ownership moves to the caller.
+        Some(value) => ptr::read((&*ManuallyDrop::new(value.clone()) as *const T).cast()),
+        None => {
+            error(&format!(
+                "Index p_index = {idx} is out of bounds (size() = {}).",
+                items.len()
+            ));
+            T::SysTy::default()
+        }
+    }
+}
+
+unsafe extern "C-unwind" fn size<T: PoolValue>(self_: *const T::SysArray) -> sys::godot_int {
+    items::<T>(self_).len() as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn empty<T: PoolValue>(self_: *const T::SysArray) -> sys::godot_bool {
+    items::<T>(self_).is_empty()
+}
+
+unsafe extern "C-unwind" fn read<T: PoolValue>(self_: *const T::SysArray) -> *mut T::SysReadAccess {
+    access(items::<T>(self_).as_ptr() as *mut T)
+}
+
+unsafe extern "C-unwind" fn write<T: PoolValue>(self_: *mut T::SysArray) -> *mut T::SysWriteAccess {
+    access(items_mut::<T>(self_).as_mut_ptr())
+}
+
+unsafe extern "C-unwind" fn read_access_copy<T: PoolValue>(
+    read: *const T::SysReadAccess,
+) -> *mut T::SysReadAccess {
+    access(access_ptr::<T, _>(read))
+}
+
+unsafe extern "C-unwind" fn read_access_ptr<T: PoolValue>(
+    read: *const T::SysReadAccess,
+) -> *const T::SysTy {
+    access_ptr::<T, _>(read).cast()
+}
+
+unsafe extern "C-unwind" fn read_access_operator_assign<T: PoolValue>(
+    read: *mut T::SysReadAccess,
+    other: *mut T::SysReadAccess,
+) {
+    *read.cast::<Access<T>>() = *other.cast::<Access<T>>();
+}
+
+unsafe extern "C-unwind" fn read_access_destroy<T: PoolValue>(read: *mut T::SysReadAccess) {
+    drop(Box::from_raw(read.cast::<Access<T>>()));
+}
+
+unsafe extern "C-unwind" fn write_access_copy<T: PoolValue>(
+    write: *const T::SysWriteAccess,
+) -> *mut T::SysWriteAccess {
+    access(access_ptr::<T, _>(write))
+}
+
+unsafe extern "C-unwind" fn write_access_ptr<T: PoolValue>(
+    write: *const T::SysWriteAccess,
+) -> *mut T::SysTy {
+    access_ptr::<T, _>(write).cast()
+}
+
+unsafe extern "C-unwind" fn write_access_operator_assign<T: PoolValue>(
+    write: *mut T::SysWriteAccess,
+    other: *mut T::SysWriteAccess,
+) {
+    *write.cast::<Access<T>>() = *other.cast::<Access<T>>();
+}
+
+unsafe extern "C-unwind" fn write_access_destroy<T: PoolValue>(write: *mut T::SysWriteAccess) {
+    drop(Box::from_raw(write.cast::<Access<T>>()));
+}
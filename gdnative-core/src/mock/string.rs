@@ -0,0 +1,1865 @@
+//! Strings, char strings and string names.
+//!
+//! A `godot_string` handle points to a reference-counted, immutable `StringData`. A null handle
+//! is the empty string, matching a zero-initialized `String` in the engine.
+
+use std::collections::HashMap;
+use std::ffi::{c_void, CStr};
+use std::os::raw::{c_char, c_double};
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use super::{digest, load, store, variant};
+use crate::core_types::{
+    Dictionary, GodotString, PoolArray, RealConv, ToVariant, Variant, VariantArray, VariantType,
+};
+use crate::digest::{hex, sha256};
+use crate::sys;
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    register!(api:
+        godot_string_new,
+        godot_string_new_copy,
+        godot_string_new_with_wide_string,
+        godot_string_destroy,
+        godot_string_wide_str,
+        godot_string_operator_index,
+        godot_string_operator_index_const,
+        godot_string_operator_equal,
+        godot_string_operator_less,
+        godot_string_operator_plus,
+        godot_string_length,
+        godot_string_empty,
+        godot_string_chars_to_utf8,
+        godot_string_chars_to_utf8_with_len,
+        godot_string_parse_utf8,
+        godot_string_parse_utf8_with_len,
+        godot_string_utf8,
+        godot_string_ascii,
+        godot_string_begins_with,
+        godot_string_begins_with_char_array,
+        godot_string_ends_with,
+        godot_string_find,
+        godot_string_find_from,
+        godot_string_find_last,
+        godot_string_findn,
+        godot_string_rfind,
+        godot_string_substr,
+        godot_string_left,
+        godot_string_right,
+        godot_string_count,
+        godot_string_countn,
+        godot_string_match,
+        godot_string_matchn,
+        godot_string_similarity,
+        godot_string_split,
+        godot_string_split_allow_empty,
+        godot_string_rsplit,
+        godot_string_replace,
+        godot_string_replace_first,
+        godot_string_replacen,
+        godot_string_insert,
+        godot_string_strip_edges,
+        godot_string_strip_escapes,
+        godot_string_rstrip,
+        godot_string_trim_prefix,
+        godot_string_trim_suffix,
+        godot_string_pad_zeros,
+        godot_string_pad_decimals,
+        godot_string_lpad_with_custom_character,
+        godot_string_rpad_with_custom_character,
+        godot_string_ord_at,
+        godot_string_format,
+        godot_string_hash,
+        godot_string_hash64,
+        godot_string_to_float,
+        godot_string_to_double,
+        godot_string_to_int,
+        godot_string_to_int64,
+        godot_string_hex_to_int,
+        godot_string_hex_to_int_without_prefix,
+        godot_string_is_numeric,
+        godot_string_is_valid_float,
+        godot_string_is_valid_hex_number,
+        godot_string_is_valid_html_color,
+        godot_string_is_valid_identifier,
+        godot_string_is_valid_integer,
+        godot_string_is_valid_ip_address,
+        godot_string_is_abs_path,
+        godot_string_is_rel_path,
+        godot_string_is_resource_file,
+        godot_string_camelcase_to_underscore,
+        godot_string_camelcase_to_underscore_lowercased,
+        godot_string_capitalize,
+        godot_string_to_lower,
+        godot_string_to_upper,
+        godot_string_char_lowercase,
+        godot_string_char_uppercase,
+        godot_string_get_file,
+        godot_string_get_base_dir,
+        godot_string_get_basename,
+        godot_string_get_extension,
+        godot_string_plus_file,
+        godot_string_simplify_path,
+        godot_string_sha256_text,
+        godot_string_md5_text,
+        godot_string_c_escape,
+        godot_string_c_escape_multiline,
+        godot_string_c_unescape,
+        godot_string_http_escape,
+        godot_string_http_unescape,
+        godot_string_json_escape,
+        godot_string_xml_escape,
+        godot_string_xml_escape_with_quotes,
+        godot_string_xml_unescape,
+        godot_string_percent_decode,
+        godot_string_percent_encode,
+        godot_char_string_length,
+        godot_char_string_get_data,
+        godot_char_string_destroy,
+        godot_string_name_new,
+        godot_string_name_new_data,
+        godot_string_name_get_name,
+        godot_string_name_get_hash,
+        godot_string_name_get_data_unique_pointer,
+        godot_string_name_operator_equal,
+        godot_string_name_operator_less,
+        godot_string_name_destroy,
+    );
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Storage
+
+struct StringData {
+    text: String,
+    /// Nul-terminated copy of `text` in the platform's `wchar_t` encoding.
+    wide: Vec<sys::wchar_t>,
+}
+
+const EMPTY_WIDE: &[sys::wchar_t] = &[0];
+
+/// Creates a new string handle with the given contents.
+pub(super) fn new(text: impl Into<String>) -> sys::godot_string {
+    let text = text.into();
+    let mut handle = sys::godot_string::default();
+    if !text.is_empty() {
+        let wide = encode_wide(&text);
+        unsafe {
+            store(
+                &mut handle,
+                Arc::into_raw(Arc::new(StringData { text, wide })),
+            )
+        };
+    }
+    handle
+}
+
+/// Creates a new `GodotString` with the given contents.
+pub(super) fn godot(text: impl Into<String>) -> GodotString {
+    GodotString::from_sys(new(text))
+}
+
+unsafe fn data<'a>(s: *const sys::godot_string) -> Option<&'a StringData> {
+    load::<_, StringData>(s).as_ref()
+}
+
+/// Returns the contents of a string handle.
+pub(super) unsafe fn text<'a>(s: *const sys::godot_string) -> &'a str {
+    data(s).map_or("", |data| data.text.as_str())
+}
+
+/// Returns the contents of a string handle in `wchar_t` units, without the nul terminator.
+unsafe fn units<'a>(s: *const sys::godot_string) -> &'a [sys::wchar_t] {
+    let wide = data(s).map_or(EMPTY_WIDE, |data| data.wide.as_slice());
+    &wide[..wide.len() - 1]
+}
+
+#[allow(clippy::unnecessary_cast)] // False positives: casts necessary for cross-platform
+pub(super) fn encode_wide(text: &str) -> Vec<sys::wchar_t> {
+    let mut wide: Vec<sys::wchar_t> = if std::mem::size_of::<sys::wchar_t>() == 2 {
+        text.encode_utf16().map(|u| u as sys::wchar_t).collect()
+    } else {
+        text.chars().map(|c| c as u32 as sys::wchar_t).collect()
+    };
+    wide.push(0);
+    wide
+}
+
+#[allow(clippy::unnecessary_cast)] // False positives: casts necessary for cross-platform
+fn decode_wide(units: &[sys::wchar_t]) -> String {
+    if std::mem::size_of::<sys::wchar_t>() == 2 {
+        char::decode_utf16(units.iter().map(|&u| u as u16))
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    } else {
+        units
+            .iter()
+            .map(|&c| char::from_u32(c as u32).unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
+}
+
+#[allow(clippy::unnecessary_cast)] // False positives: casts necessary for cross-platform
+fn unit_to_char(unit: sys::wchar_t) -> char {
+    char::from_u32(unit as u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// Equivalent of `String::substr` on `wchar_t` units.
+fn substr(units: &[sys::wchar_t], from: i32, chars: i32) -> String {
+    let len = units.len() as i32;
+    if from < 0 || from >= len || chars <= 0 {
+        return String::new();
+    }
+    let end = if chars > len - from {
+        len
+    } else {
+        from + chars
+    };
+    decode_wide(&units[from as usize..end as usize])
+}
+
+fn find_units(units: &[sys::wchar_t], what: &[sys::wchar_t], from: i32) -> i32 {
+    if from < 0 || what.is_empty() || what.len() > units.len() {
+        return -1;
+    }
+    (from as usize..=units.len() - what.len())
+        .find(|&i| &units[i..i + what.len()] == what)
+        .map_or(-1, |i| i as i32)
+}
+
+fn rfind_units(units: &[sys::wchar_t], what: &[sys::wchar_t], from: i32) -> i32 {
+    if what.is_empty() || what.len() > units.len() {
+        return -1;
+    }
+    let last = units.len() - what.len();
+    let start = if from < 0 {
+        last
+    } else {
+        (from as usize).min(last)
+    };
+    (0..=start)
+        .rev()
+        .find(|&i| &units[i..i + what.len()] == what)
+        .map_or(-1, |i| i as i32)
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Construction and basic access
+
+unsafe extern "C-unwind" fn godot_string_new(r_dest: *mut sys::godot_string) {
+    *r_dest = sys::godot_string::default();
+}
+
+unsafe extern "C-unwind" fn godot_string_new_copy(
+    r_dest: *mut sys::godot_string,
+    src: *const sys::godot_string,
+) {
+    let ptr = load::<_, StringData>(src);
+    if !ptr.is_null() {
+        Arc::increment_strong_count(ptr);
+    }
+    *r_dest = sys::godot_string::default();
+    store(r_dest, ptr);
+}
+
+unsafe extern "C-unwind" fn godot_string_new_with_wide_string(
+    r_dest: *mut sys::godot_string,
+    contents: *const sys::wchar_t,
+    size: std::os::raw::c_int,
+) {
+    let units = if contents.is_null() {
+        &[][..]
+    } else if size < 0 {
+        let mut len = 0;
+        while *contents.add(len) != 0 {
+            len += 1;
+        }
+        std::slice::from_raw_parts(contents, len)
+    } else {
+        std::slice::from_raw_parts(contents, size as usize)
+    };
+    *r_dest = new(decode_wide(units));
+}
+
+unsafe extern "C-unwind" fn godot_string_destroy(self_: *mut sys::godot_string) {
+    let ptr = load::<_, StringData>(self_);
+    if !ptr.is_null() {
+        drop(Arc::from_raw(ptr));
+    }
+    *self_ = sys::godot_string::default();
+}
+
+unsafe extern "C-unwind" fn godot_string_wide_str(
+    self_: *const sys::godot_string,
+) -> *const sys::wchar_t {
+    data(self_).map_or(EMPTY_WIDE.as_ptr(), |data| data.wide.as_ptr())
+}
+
+unsafe extern "C-unwind" fn godot_string_operator_index(
+    self_: *mut sys::godot_string,
+    idx: sys::godot_int,
+) -> *const sys::wchar_t {
+    let units = units(self_);
+    super::check_index("string", idx, units.len());
+    units.as_ptr().add(idx as usize)
+}
+
+unsafe extern "C-unwind" fn godot_string_operator_index_const(
+    self_: *const sys::godot_string,
+    idx: sys::godot_int,
+) -> sys::wchar_t {
+    let units = units(self_);
+    super::check_index("string", idx, units.len());
+    units[idx as usize]
+}
+
+unsafe extern "C-unwind" fn godot_string_ord_at(
+    self_: *const sys::godot_string,
+    idx: sys::godot_int,
+) -> sys::wchar_t {
+    godot_string_operator_index_const(self_, idx)
+}
+
+unsafe extern "C-unwind" fn godot_string_operator_equal(
+    self_: *const sys::godot_string,
+    b: *const sys::godot_string,
+) -> sys::godot_bool {
+    text(self_) == text(b)
+}
+
+unsafe extern "C-unwind" fn godot_string_operator_less(
+    self_: *const sys::godot_string,
+    b: *const sys::godot_string,
+) -> sys::godot_bool {
+    units(self_) < units(b)
+}
+
+unsafe extern "C-unwind" fn godot_string_operator_plus(
+    self_: *const sys::godot_string,
+    b: *const sys::godot_string,
+) -> sys::godot_string {
+    new([text(self_), text(b)].concat())
+}
+
+unsafe extern "C-unwind" fn godot_string_length(self_: *const sys::godot_string) -> sys::godot_int {
+    units(self_).len() as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_string_empty(self_: *const sys::godot_string) -> sys::godot_bool {
+    text(self_).is_empty()
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Encodings
+
+unsafe fn utf8_bytes<'a>(utf8: *const c_char, len: Option<sys::godot_int>) -> &'a [u8] {
+    if utf8.is_null() {
+        return &[];
+    }
+    match len {
+        Some(len) if len >= 0 => std::slice::from_raw_parts(utf8 as *const u8, len as usize),
+        _ => CStr::from_ptr(utf8).to_bytes(),
+    }
+}
+
+unsafe extern "C-unwind" fn godot_string_chars_to_utf8(utf8: *const c_char) -> sys::godot_string {
+    new(String::from_utf8_lossy(utf8_bytes(utf8, None)))
+}
+
+unsafe extern "C-unwind" fn godot_string_chars_to_utf8_with_len(
+    utf8: *const c_char,
+    len: sys::godot_int,
+) -> sys::godot_string {
+    new(String::from_utf8_lossy(utf8_bytes(utf8, Some(len))))
+}
+
+unsafe fn parse_utf8(self_: *mut sys::godot_string, bytes: &[u8]) -> sys::godot_bool {
+    godot_string_destroy(self_);
+    match std::str::from_utf8(bytes) {
+        Ok(text) => {
+            *self_ = new(text);
+            false
+        }
+        // Like the engine, report the error and leave the string empty
+        Err(_) => true,
+    }
+}
+
+unsafe extern "C-unwind" fn godot_string_parse_utf8(
+    self_: *mut sys::godot_string,
+    utf8: *const c_char,
+) -> sys::godot_bool {
+    parse_utf8(self_, utf8_bytes(utf8, None))
+}
+
+unsafe extern "C-unwind" fn godot_string_parse_utf8_with_len(
+    self_: *mut sys::godot_string,
+    utf8: *const c_char,
+    len: sys::godot_int,
+) -> sys::godot_bool {
+    parse_utf8(self_, utf8_bytes(utf8, Some(len)))
+}
+
+fn new_char_string(mut bytes: Vec<u8>) -> sys::godot_char_string {
+    bytes.push(0);
+    let mut handle = sys::godot_char_string::default();
+    unsafe { store(&mut handle, Box::into_raw(Box::new(bytes))) };
+    handle
+}
+
+unsafe extern "C-unwind" fn godot_string_utf8(
+    self_: *const sys::godot_string,
+) -> sys::godot_char_string {
+    new_char_string(text(self_).as_bytes().to_vec())
+}
+
+#[allow(clippy::unnecessary_cast)] // False positives: casts necessary for cross-platform
+unsafe extern "C-unwind" fn godot_string_ascii(
+    self_: *const sys::godot_string,
+) -> sys::godot_char_string {
+    new_char_string(units(self_).iter().map(|&c| c as u8).collect())
+}
+
+unsafe fn char_string_bytes<'a>(cs: *const sys::godot_char_string) -> &'a [u8] {
+    load::<_, Vec<u8>>(cs)
+        .as_ref()
+        .map_or(&[0][..], Vec::as_slice)
+}
+
+unsafe extern "C-unwind" fn godot_char_string_length(
+    cs: *const sys::godot_char_string,
+) -> sys::godot_int {
+    (char_string_bytes(cs).len() - 1) as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_char_string_get_data(
+    cs: *const sys::godot_char_string,
+) -> *const c_char {
+    char_string_bytes(cs).as_ptr() as *const c_char
+}
+
+unsafe extern "C-unwind" fn godot_char_string_destroy(cs: *mut sys::godot_char_string) {
+    let ptr = load::<_, Vec<u8>>(cs) as *mut Vec<u8>;
+    if !ptr.is_null() {
+        drop(Box::from_raw(ptr));
+    }
+    *cs = sys::godot_char_string::default();
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Searching and slicing
+
+unsafe extern "C-unwind" fn godot_string_begins_with(
+    self_: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> sys::godot_bool {
+    text(self_).starts_with(text(string))
+}
+
+unsafe extern "C-unwind" fn godot_string_begins_with_char_array(
+    self_: *const sys::godot_string,
+    char_array: *const c_char,
+) -> sys::godot_bool {
+    let prefix = CStr::from_ptr(char_array).to_bytes();
+    let units = units(self_);
+    units.len() >= prefix.len()
+        && units
+            .iter()
+            .zip(prefix)
+            .all(|(&c, &p)| c == p as sys::wchar_t)
+}
+
+unsafe extern "C-unwind" fn godot_string_ends_with(
+    self_: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> sys::godot_bool {
+    text(self_).ends_with(text(string))
+}
+
+unsafe extern "C-unwind" fn godot_string_find(
+    self_: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    find_units(units(self_), units(&what), 0)
+}
+
+unsafe extern "C-unwind" fn godot_string_find_from(
+    self_: *const sys::godot_string,
+    what: sys::godot_string,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    find_units(units(self_), units(&what), from)
+}
+
+unsafe extern "C-unwind" fn godot_string_find_last(
+    self_: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    rfind_units(units(self_), units(&what), -1)
+}
+
+unsafe extern "C-unwind" fn godot_string_findn(
+    self_: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    let this = encode_wide(&to_lower(text(self_)));
+    let what = encode_wide(&to_lower(text(&what)));
+    find_units(&this[..this.len() - 1], &what[..what.len() - 1], 0)
+}
+
+unsafe extern "C-unwind" fn godot_string_rfind(
+    self_: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    rfind_units(units(self_), units(&what), -1)
+}
+
+unsafe extern "C-unwind" fn godot_string_substr(
+    self_: *const sys::godot_string,
+    from: sys::godot_int,
+    chars: sys::godot_int,
+) -> sys::godot_string {
+    new(substr(units(self_), from, chars))
+}
+
+unsafe extern "C-unwind" fn godot_string_left(
+    self_: *const sys::godot_string,
+    pos: sys::godot_int,
+) -> sys::godot_string {
+    new(substr(units(self_), 0, pos))
+}
+
+unsafe extern "C-unwind" fn godot_string_right(
+    self_: *const sys::godot_string,
+    pos: sys::godot_int,
+) -> sys::godot_string {
+    let units = units(self_);
+    new(substr(units, pos, units.len() as i32 - pos))
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Matching, splitting and editing
+
+/// Equivalent of `String::_count`, counting non-overlapping occurrences in `[from, to)`.
+fn count(units: &[sys::wchar_t], what: &[sys::wchar_t], from: i32, to: i32) -> sys::godot_int {
+    let len = units.len() as i32;
+    if what.is_empty() || units.len() < what.len() || from < 0 || to < 0 {
+        return 0;
+    }
+    let range = if to == 0 {
+        from.min(len) as usize..units.len()
+    } else if from >= to {
+        return 0;
+    } else {
+        from.min(len) as usize..to.min(len) as usize
+    };
+
+    let units = &units[range];
+    let mut count = 0;
+    let mut pos = 0;
+    loop {
+        let found = find_units(&units[pos..], what, 0);
+        if found < 0 {
+            return count;
+        }
+        pos += found as usize + what.len();
+        count += 1;
+    }
+}
+
+/// Equivalent of the engine's `_wildcard_match`, where `?` does not match `.`.
+fn wildcard_match(pattern: &[char], string: &[char]) -> bool {
+    match pattern.first() {
+        None => string.is_empty(),
+        Some('*') => {
+            wildcard_match(&pattern[1..], string)
+                || (!string.is_empty() && wildcard_match(pattern, &string[1..]))
+        }
+        Some('?') => {
+            !string.is_empty() && string[0] != '.' && wildcard_match(&pattern[1..], &string[1..])
+        }
+        Some(&c) => string.first() == Some(&c) && wildcard_match(&pattern[1..], &string[1..]),
+    }
+}
+
+fn matches(text: &str, wildcard: &str) -> bool {
+    if text.is_empty() || wildcard.is_empty() {
+        return false;
+    }
+    let text: Vec<char> = text.chars().collect();
+    let wildcard: Vec<char> = wildcard.chars().collect();
+    wildcard_match(&wildcard, &text)
+}
+
+/// Equivalent of `String::similarity`, the Sørensen–Dice coefficient of character pairs.
+fn similarity(a: &str, b: &str) -> f32 {
+    if a == b {
+        return 1.0;
+    }
+    let bigrams = |s: &str| {
+        let chars: Vec<char> = s.chars().collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect::<Vec<_>>()
+    };
+    let (a, b) = (bigrams(a), bigrams(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let inter = a.iter().filter(|bigram| b.contains(bigram)).count();
+    2.0 * inter as f32 / (a.len() + b.len()) as f32
+}
+
+/// Equivalent of `String::split` without a split limit.
+fn split(units: &[sys::wchar_t], splitter: &[sys::wchar_t], allow_empty: bool) -> Vec<String> {
+    let len = units.len() as i32;
+    let mut parts = Vec::new();
+    let mut from = 0;
+    loop {
+        let end = match find_units(units, splitter, from) {
+            -1 => len,
+            end => end,
+        };
+        if allow_empty || end > from {
+            parts.push(substr(units, from, end - from));
+        }
+        if end == len {
+            return parts;
+        }
+        from = end + splitter.len() as i32;
+    }
+}
+
+/// Equivalent of `String::rsplit`.
+fn rsplit(
+    units: &[sys::wchar_t],
+    splitter: &[sys::wchar_t],
+    allow_empty: bool,
+    max_split: i32,
+) -> Vec<String> {
+    let splitter_len = splitter.len() as i32;
+    let mut parts = Vec::new();
+    let mut remaining = units.len() as i32;
+    loop {
+        if remaining < splitter_len || (max_split > 0 && max_split as usize == parts.len()) {
+            if allow_empty || remaining > 0 {
+                parts.push(substr(units, 0, remaining));
+            }
+            break;
+        }
+        let left_edge = rfind_units(&units[..remaining as usize], splitter, -1);
+        if left_edge < 0 {
+            parts.push(substr(units, 0, remaining));
+            break;
+        }
+        let start = left_edge + splitter_len;
+        if allow_empty || start < remaining {
+            parts.push(substr(units, start, remaining - start));
+        }
+        remaining = left_edge;
+    }
+    parts.reverse();
+    parts
+}
+
+unsafe extern "C-unwind" fn godot_string_count(
+    self_: *const sys::godot_string,
+    what: sys::godot_string,
+    from: sys::godot_int,
+    to: sys::godot_int,
+) -> sys::godot_int {
+    count(units(self_), units(&what), from, to)
+}
+
+unsafe extern "C-unwind" fn godot_string_countn(
+    self_: *const sys::godot_string,
+    what: sys::godot_string,
+    from: sys::godot_int,
+    to: sys::godot_int,
+) -> sys::godot_int {
+    let this = encode_wide(&to_lower(text(self_)));
+    let what = encode_wide(&to_lower(text(&what)));
+    count(&this[..this.len() - 1], &what[..what.len() - 1], from, to)
+}
+
+unsafe extern "C-unwind" fn godot_string_match(
+    self_: *const sys::godot_string,
+    wildcard: *const sys::godot_string,
+) -> sys::godot_bool {
+    matches(text(self_), text(wildcard))
+}
+
+unsafe extern "C-unwind" fn godot_string_matchn(
+    self_: *const sys::godot_string,
+    wildcard: *const sys::godot_string,
+) -> sys::godot_bool {
+    matches(&to_upper(text(self_)), &to_upper(text(wildcard)))
+}
+
+unsafe extern "C-unwind" fn godot_string_similarity(
+    self_: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> sys::godot_real {
+    sys::godot_real::from_f32(similarity(text(self_), text(string)))
+}
+
+unsafe fn split_array(
+    self_: *const sys::godot_string,
+    splitter: *const sys::godot_string,
+    allow_empty: bool,
+) -> sys::godot_array {
+    let parts = split(units(self_), units(splitter), allow_empty);
+    super::array::new(parts.into_iter().map(|s| godot(s).to_variant()).collect())
+}
+
+unsafe extern "C-unwind" fn godot_string_split(
+    self_: *const sys::godot_string,
+    splitter: *const sys::godot_string,
+) -> sys::godot_array {
+    split_array(self_, splitter, false)
+}
+
+unsafe extern "C-unwind" fn godot_string_split_allow_empty(
+    self_: *const sys::godot_string,
+    splitter: *const sys::godot_string,
+) -> sys::godot_array {
+    split_array(self_, splitter, true)
+}
+
+unsafe extern "C-unwind" fn godot_string_rsplit(
+    self_: *const sys::godot_string,
+    divisor: *const sys::godot_string,
+    allow_empty: sys::godot_bool,
+    max_split: sys::godot_int,
+) -> sys::godot_pool_string_array {
+    let parts = rsplit(units(self_), units(divisor), allow_empty, max_split);
+    let parts = PoolArray::from_vec(parts.into_iter().map(godot).collect());
+    variant::leak(parts, PoolArray::sys)
+}
+
+unsafe extern "C-unwind" fn godot_string_replace(
+    self_: *const sys::godot_string,
+    key: sys::godot_string,
+    with: sys::godot_string,
+) -> sys::godot_string {
+    let key = text(&key);
+    if key.is_empty() {
+        return new(text(self_));
+    }
+    new(text(self_).replace(key, text(&with)))
+}
+
+unsafe extern "C-unwind" fn godot_string_replace_first(
+    self_: *const sys::godot_string,
+    key: sys::godot_string,
+    with: sys::godot_string,
+) -> sys::godot_string {
+    let key = text(&key);
+    if key.is_empty() {
+        return new(text(self_));
+    }
+    new(text(self_).replacen(key, text(&with), 1))
+}
+
+unsafe extern "C-unwind" fn godot_string_replacen(
+    self_: *const sys::godot_string,
+    key: sys::godot_string,
+    with: sys::godot_string,
+) -> sys::godot_string {
+    let units = units(self_);
+    let lower = encode_wide(&to_lower(text(self_)));
+    let key = encode_wide(&to_lower(text(&key)));
+    let (lower, key) = (&lower[..lower.len() - 1], &key[..key.len() - 1]);
+
+    // Case mappings in the engine keep one character per character, so positions match
+    let mut result = String::new();
+    let mut from = 0;
+    loop {
+        let found = find_units(lower, key, from as i32);
+        if found < 0 {
+            result.push_str(&decode_wide(&units[from..]));
+            return new(result);
+        }
+        result.push_str(&decode_wide(&units[from..found as usize]));
+        result.push_str(text(&with));
+        from = found as usize + key.len();
+    }
+}
+
+unsafe extern "C-unwind" fn godot_string_insert(
+    self_: *const sys::godot_string,
+    at_pos: sys::godot_int,
+    string: sys::godot_string,
+) -> sys::godot_string {
+    let units = units(self_);
+    if at_pos < 0 {
+        return new(decode_wide(units));
+    }
+    let at_pos = (at_pos as usize).min(units.len());
+    new([
+        decode_wide(&units[..at_pos]),
+        text(&string).to_string(),
+        decode_wide(&units[at_pos..]),
+    ]
+    .concat())
+}
+
+unsafe extern "C-unwind" fn godot_string_strip_edges(
+    self_: *const sys::godot_string,
+    left: sys::godot_bool,
+    right: sys::godot_bool,
+) -> sys::godot_string {
+    let is_edge = |c: char| c as u32 <= 32;
+    let mut text = text(self_);
+    if left {
+        text = text.trim_start_matches(is_edge);
+    }
+    if right {
+        text = text.trim_end_matches(is_edge);
+    }
+    new(text)
+}
+
+unsafe extern "C-unwind" fn godot_string_strip_escapes(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(text(self_).replace(|c: char| (c as u32) < 32, ""))
+}
+
+unsafe extern "C-unwind" fn godot_string_rstrip(
+    self_: *const sys::godot_string,
+    chars: *const sys::godot_string,
+) -> sys::godot_string {
+    let chars = text(chars);
+    new(text(self_).trim_end_matches(|c| chars.contains(c)))
+}
+
+unsafe extern "C-unwind" fn godot_string_trim_prefix(
+    self_: *const sys::godot_string,
+    prefix: *const sys::godot_string,
+) -> sys::godot_string {
+    let text = text(self_);
+    new(text.strip_prefix(self::text(prefix)).unwrap_or(text))
+}
+
+unsafe extern "C-unwind" fn godot_string_trim_suffix(
+    self_: *const sys::godot_string,
+    suffix: *const sys::godot_string,
+) -> sys::godot_string {
+    let text = text(self_);
+    new(text.strip_suffix(self::text(suffix)).unwrap_or(text))
+}
+
+/// Equivalent of `String::pad_zeros`.
+fn pad_zeros(text: &str, digits: i32) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    let mut end = chars.iter().position(|&c| c == '.').unwrap_or(chars.len());
+    if end == 0 {
+        return text.to_string();
+    }
+    let begin = chars[..end]
+        .iter()
+        .position(char::is_ascii_digit)
+        .unwrap_or(end);
+    if begin >= end {
+        return text.to_string();
+    }
+    while ((end - begin) as i32) < digits {
+        chars.insert(begin, '0');
+        end += 1;
+    }
+    chars.into_iter().collect()
+}
+
+/// Equivalent of `String::pad_decimals`.
+fn pad_decimals(text: &str, digits: i32) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    let dot = match chars.iter().position(|&c| c == '.') {
+        Some(dot) if digits <= 0 => return chars[..dot].iter().collect(),
+        Some(dot) => dot,
+        None if digits <= 0 => return text.to_string(),
+        None => {
+            chars.push('.');
+            chars.len() - 1
+        }
+    };
+    chars.resize(dot + 1 + digits as usize, '0');
+    chars.into_iter().collect()
+}
+
+unsafe extern "C-unwind" fn godot_string_pad_zeros(
+    self_: *const sys::godot_string,
+    digits: sys::godot_int,
+) -> sys::godot_string {
+    new(pad_zeros(text(self_), digits))
+}
+
+unsafe extern "C-unwind" fn godot_string_pad_decimals(
+    self_: *const sys::godot_string,
+    digits: sys::godot_int,
+) -> sys::godot_string {
+    new(pad_decimals(text(self_), digits))
+}
+
+/// Equivalent of `String::lpad` and `String::rpad`, which add `character` once per missing
+/// character, even if it is longer than one character.
+unsafe fn pad(
+    self_: *const sys::godot_string,
+    min_length: sys::godot_int,
+    character: *const sys::godot_string,
+    left: bool,
+) -> sys::godot_string {
+    let padding = (min_length - units(self_).len() as i32).max(0) as usize;
+    let padding = text(character).repeat(padding);
+    if left {
+        new(padding + text(self_))
+    } else {
+        new([text(self_), &padding].concat())
+    }
+}
+
+unsafe extern "C-unwind" fn godot_string_lpad_with_custom_character(
+    self_: *const sys::godot_string,
+    min_length: sys::godot_int,
+    character: *const sys::godot_string,
+) -> sys::godot_string {
+    pad(self_, min_length, character, true)
+}
+
+unsafe extern "C-unwind" fn godot_string_rpad_with_custom_character(
+    self_: *const sys::godot_string,
+    min_length: sys::godot_int,
+    character: *const sys::godot_string,
+) -> sys::godot_string {
+    pad(self_, min_length, character, false)
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Formatting and hashing
+
+/// Removes one pair of surrounding double quotes, like `String::format` does for values.
+fn unquote(s: String) -> String {
+    if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+        s[1..s.len() - 1].to_string()
+    } else {
+        s
+    }
+}
+
+unsafe extern "C-unwind" fn godot_string_format(
+    self_: *const sys::godot_string,
+    values: *const sys::godot_variant,
+) -> sys::godot_string {
+    let values = Variant::cast_ref(values);
+    let mut result = text(self_).to_string();
+
+    match values.get_type() {
+        VariantType::VariantArray => {
+            let values = values.to::<VariantArray>().unwrap();
+            for (i, value) in values.iter().enumerate() {
+                if let Some(pair) = value.to::<VariantArray>() {
+                    if pair.len() == 2 {
+                        let key = unquote(variant::to_string(&pair.get(0)));
+                        let value = unquote(variant::to_string(&pair.get(1)));
+                        result = result.replace(&format!("{{{key}}}"), &value);
+                    }
+                } else {
+                    let value = unquote(variant::to_string(&value));
+                    result = result.replace(&format!("{{{i}}}"), &value);
+                }
+            }
+        }
+        VariantType::Dictionary => {
+            let values = values.to::<Dictionary>().unwrap();
+            for (key, value) in values.iter() {
+                let key = unquote(variant::to_string(&key));
+                let value = unquote(variant::to_string(&value));
+                result = result.replace(&format!("{{{key}}}"), &value);
+            }
+        }
+        _ => {}
+    }
+
+    new(result)
+}
+
+#[allow(clippy::unnecessary_cast)] // False positives: casts necessary for cross-platform
+pub(super) fn hash_units(units: &[sys::wchar_t]) -> u32 {
+    units.iter().fold(5381_u32, |hash, &c| {
+        (hash << 5).wrapping_add(hash).wrapping_add(c as u32)
+    })
+}
+
+#[allow(clippy::unnecessary_cast)] // False positives: casts necessary for cross-platform
+unsafe extern "C-unwind" fn godot_string_hash64(self_: *const sys::godot_string) -> u64 {
+    units(self_).iter().fold(5381_u64, |hash, &c| {
+        (hash << 5).wrapping_add(hash).wrapping_add(c as u32 as u64)
+    })
+}
+
+unsafe extern "C-unwind" fn godot_string_hash(self_: *const sys::godot_string) -> u32 {
+    hash_units(units(self_))
+}
+
+unsafe extern "C-unwind" fn godot_string_sha256_text(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(hex(&sha256(text(self_).as_bytes())))
+}
+
+unsafe extern "C-unwind" fn godot_string_md5_text(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(hex(&digest::md5(text(self_).as_bytes())))
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Number parsing
+
+/// Equivalent of `String::to_double`: parses the longest numeric prefix, ignoring leading
+/// whitespace.
+pub(super) fn to_double(text: &str) -> f64 {
+    let text = text.trim_start();
+    let bytes = text.as_bytes();
+    let mut end = 0;
+    let digits = |mut i: usize| {
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        i
+    };
+
+    if end < bytes.len() && (bytes[end] == b'+' || bytes[end] == b'-') {
+        end += 1;
+    }
+    end = digits(end);
+    if end < bytes.len() && bytes[end] == b'.' {
+        end = digits(end + 1);
+    }
+    if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+        let mut exp = end + 1;
+        if exp < bytes.len() && (bytes[exp] == b'+' || bytes[exp] == b'-') {
+            exp += 1;
+        }
+        let exp_end = digits(exp);
+        if exp_end > exp {
+            end = exp_end;
+        }
+    }
+
+    let number = &text[..end];
+    number
+        .parse::<f64>()
+        .or_else(|_| format!("{number}0").parse::<f64>())
+        .unwrap_or(0.0)
+}
+
+/// Equivalent of `String::to_int` and `String::to_int64`: reads all digits before the first `.`,
+/// ignoring any other characters. Overflowing values are clamped.
+pub(super) fn to_int(text: &str, min: i64, max: i64) -> i64 {
+    let mut integer: i128 = 0;
+    let mut sign = 1;
+    for c in text.chars().take_while(|&c| c != '.') {
+        if let Some(digit) = c.to_digit(10) {
+            integer = integer * 10 + digit as i128;
+            if integer > max as i128 + 1 {
+                return if sign > 0 { max } else { min };
+            }
+        } else if integer == 0 && c == '-' {
+            sign = -sign;
+        }
+    }
+    (integer * sign).clamp(min as i128, max as i128) as i64
+}
+
+fn hex_to_int(text: &str, with_prefix: bool) -> i64 {
+    let (sign, mut rest) = match text.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, text),
+    };
+    if with_prefix {
+        match rest.strip_prefix("0x") {
+            Some(digits) => rest = digits,
+            None => return 0,
+        }
+    }
+
+    let mut hex: i64 = 0;
+    for c in rest.chars() {
+        let digit = match c.to_digit(16) {
+            Some(digit) => digit as i64,
+            None => return 0,
+        };
+        if hex > i32::MAX as i64 / 16 {
+            return if sign > 0 {
+                i32::MAX as i64
+            } else {
+                i32::MIN as i64
+            };
+        }
+        hex = hex * 16 + digit;
+    }
+    hex * sign
+}
+
+unsafe extern "C-unwind" fn godot_string_to_double(self_: *const sys::godot_string) -> c_double {
+    to_double(text(self_))
+}
+
+unsafe extern "C-unwind" fn godot_string_to_float(
+    self_: *const sys::godot_string,
+) -> sys::godot_real {
+    sys::godot_real::from_f64(to_double(text(self_)))
+}
+
+unsafe extern "C-unwind" fn godot_string_to_int(self_: *const sys::godot_string) -> sys::godot_int {
+    to_int(text(self_), i32::MIN as i64, i32::MAX as i64) as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_string_to_int64(self_: *const sys::godot_string) -> i64 {
+    to_int(text(self_), i64::MIN, i64::MAX)
+}
+
+unsafe extern "C-unwind" fn godot_string_hex_to_int(
+    self_: *const sys::godot_string,
+) -> sys::godot_int {
+    hex_to_int(text(self_), true) as sys::godot_int
+}
+
+unsafe extern "C-unwind" fn godot_string_hex_to_int_without_prefix(
+    self_: *const sys::godot_string,
+) -> sys::godot_int {
+    hex_to_int(text(self_), false) as sys::godot_int
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Validation
+
+unsafe extern "C-unwind" fn godot_string_is_numeric(
+    self_: *const sys::godot_string,
+) -> sys::godot_bool {
+    let text = text(self_);
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    !text.is_empty()
+        && digits.chars().filter(|&c| c == '.').count() <= 1
+        && digits.chars().all(|c| c == '.' || c.is_ascii_digit())
+}
+
+fn is_valid_integer(text: &str) -> bool {
+    let digits = match text.strip_prefix(|c| c == '+' || c == '-') {
+        Some(digits) if text.len() > 1 => digits,
+        _ => text,
+    };
+    !text.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_valid_float(text: &str) -> bool {
+    let digits = text.strip_prefix(|c| c == '+' || c == '-').unwrap_or(text);
+    let mut exponent_found = false;
+    let mut period_found = false;
+    let mut sign_found = false;
+    let mut exponent_values_found = false;
+    let mut numbers_found = false;
+
+    for c in digits.chars() {
+        if c.is_ascii_digit() {
+            if exponent_found {
+                exponent_values_found = true;
+            } else {
+                numbers_found = true;
+            }
+        } else if numbers_found && !exponent_found && c == 'e' {
+            exponent_found = true;
+        } else if !period_found && !exponent_found && c == '.' {
+            period_found = true;
+        } else if (c == '-' || c == '+') && exponent_found && !exponent_values_found && !sign_found
+        {
+            sign_found = true;
+        } else {
+            return false;
+        }
+    }
+
+    numbers_found
+}
+
+fn is_valid_hex_number(text: &str, with_prefix: bool) -> bool {
+    let mut digits = match text.strip_prefix(|c| c == '+' || c == '-') {
+        Some(digits) if text.len() > 1 => digits,
+        _ => text,
+    };
+    if with_prefix {
+        match digits.strip_prefix("0x") {
+            Some(rest) if text.len() >= 3 => digits = rest,
+            _ => return false,
+        }
+    }
+    !text.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+unsafe extern "C-unwind" fn godot_string_is_valid_float(
+    self_: *const sys::godot_string,
+) -> sys::godot_bool {
+    is_valid_float(text(self_))
+}
+
+unsafe extern "C-unwind" fn godot_string_is_valid_integer(
+    self_: *const sys::godot_string,
+) -> sys::godot_bool {
+    is_valid_integer(text(self_))
+}
+
+unsafe extern "C-unwind" fn godot_string_is_valid_hex_number(
+    self_: *const sys::godot_string,
+    with_prefix: sys::godot_bool,
+) -> sys::godot_bool {
+    is_valid_hex_number(text(self_), with_prefix)
+}
+
+unsafe extern "C-unwind" fn godot_string_is_valid_html_color(
+    self_: *const sys::godot_string,
+) -> sys::godot_bool {
+    let text = text(self_);
+    let color = text.strip_prefix('#').unwrap_or(text);
+    (color.len() == 6 || color.len() == 8) && color.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+unsafe extern "C-unwind" fn godot_string_is_valid_identifier(
+    self_: *const sys::godot_string,
+) -> sys::godot_bool {
+    let text = text(self_);
+    !text.is_empty()
+        && !text.starts_with(|c: char| c.is_ascii_digit())
+        && text.chars().all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+fn is_valid_ip_address(text: &str) -> bool {
+    if text.contains(':') {
+        text.split(':').all(|n| {
+            if n.is_empty() {
+                true
+            } else if is_valid_hex_number(n, false) {
+                (0..=0xffff).contains(&hex_to_int(n, false))
+            } else {
+                is_valid_ip_address(n)
+            }
+        })
+    } else {
+        let parts: Vec<&str> = text.split('.').collect();
+        parts.len() == 4
+            && parts
+                .iter()
+                .all(|n| is_valid_integer(n) && (0..=255).contains(&to_int(n, i64::MIN, i64::MAX)))
+    }
+}
+
+unsafe extern "C-unwind" fn godot_string_is_valid_ip_address(
+    self_: *const sys::godot_string,
+) -> sys::godot_bool {
+    is_valid_ip_address(text(self_))
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Paths
+
+fn is_abs_path(text: &str) -> bool {
+    text.starts_with('/')
+        || text.starts_with('\\')
+        || (text.len() > 1 && (text.contains(":/") || text.contains(":\\")))
+}
+
+/// Returns the byte offset of the last path separator.
+fn last_separator(text: &str) -> Option<usize> {
+    match (text.rfind('/'), text.rfind('\\')) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Returns the byte offset of the extension dot, if it is part of the file name.
+fn extension_dot(text: &str) -> Option<usize> {
+    let dot = text.rfind('.')?;
+    match last_separator(text) {
+        Some(sep) if dot < sep => None,
+        _ => Some(dot),
+    }
+}
+
+fn get_base_dir(text: &str) -> String {
+    let drive_end = text
+        .find(":/")
+        .or_else(|| text.find(":\\"))
+        .map(|pos| (pos + 3).min(text.len()));
+
+    let (base, rest) = match drive_end {
+        Some(end) => (&text[..end], &text[end..]),
+        None if text.starts_with('/') => ("/", &text[1..]),
+        None => ("", text),
+    };
+
+    match last_separator(rest) {
+        Some(sep) => format!("{base}{}", &rest[..sep]),
+        None => base.to_string(),
+    }
+}
+
+fn simplify_path(text: &str) -> String {
+    let (drive, path) = if let Some(rest) = text.strip_prefix("local://") {
+        ("local://".to_string(), rest)
+    } else if let Some(rest) = text.strip_prefix("res://") {
+        ("res://".to_string(), rest)
+    } else if let Some(rest) = text.strip_prefix("user://") {
+        ("user://".to_string(), rest)
+    } else if text.starts_with('/') || text.starts_with('\\') {
+        (text[..1].to_string(), &text[1..])
+    } else {
+        let drive = text.find(":/").or_else(|| text.find(":\\"));
+        match (drive, text.find('/')) {
+            (Some(p), Some(slash)) if p < slash => (text[..p + 2].to_string(), &text[p + 2..]),
+            _ => (String::new(), text),
+        }
+    };
+
+    let path = path.replace('\\', "/");
+    let mut dirs: Vec<&str> = Vec::new();
+    for dir in path.split('/').filter(|d| !d.is_empty()) {
+        match dir {
+            "." => {}
+            ".." => {
+                dirs.pop();
+            }
+            _ => dirs.push(dir),
+        }
+    }
+
+    drive + &dirs.join("/")
+}
+
+unsafe extern "C-unwind" fn godot_string_is_abs_path(
+    self_: *const sys::godot_string,
+) -> sys::godot_bool {
+    is_abs_path(text(self_))
+}
+
+unsafe extern "C-unwind" fn godot_string_is_rel_path(
+    self_: *const sys::godot_string,
+) -> sys::godot_bool {
+    !is_abs_path(text(self_))
+}
+
+unsafe extern "C-unwind" fn godot_string_is_resource_file(
+    self_: *const sys::godot_string,
+) -> sys::godot_bool {
+    let text = text(self_);
+    text.starts_with("res://") && !text.contains("::")
+}
+
+unsafe extern "C-unwind" fn godot_string_get_file(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    let text = text(self_);
+    new(last_separator(text).map_or(text, |sep| &text[sep + 1..]))
+}
+
+unsafe extern "C-unwind" fn godot_string_get_base_dir(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(get_base_dir(text(self_)))
+}
+
+unsafe extern "C-unwind" fn godot_string_get_basename(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    let text = text(self_);
+    new(extension_dot(text).map_or(text, |dot| &text[..dot]))
+}
+
+unsafe extern "C-unwind" fn godot_string_get_extension(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    let text = text(self_);
+    new(extension_dot(text).map_or("", |dot| &text[dot + 1..]))
+}
+
+unsafe extern "C-unwind" fn godot_string_plus_file(
+    self_: *const sys::godot_string,
+    file: *const sys::godot_string,
+) -> sys::godot_string {
+    let (text, file) = (text(self_), text(file));
+    if text.is_empty() || text.ends_with('/') {
+        new(format!("{text}{file}"))
+    } else {
+        new(format!("{text}/{file}"))
+    }
+}
+
+unsafe extern "C-unwind" fn godot_string_simplify_path(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(simplify_path(text(self_)))
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Case conversion
+
+/// Maps a character that has a single-character case mapping, like the engine's case tables.
+fn map_case<I: Iterator<Item = char>>(c: char, mapping: impl Fn(char) -> I) -> char {
+    let mut mapped = mapping(c);
+    match (mapped.next(), mapped.next()) {
+        (Some(m), None) => m,
+        _ => c,
+    }
+}
+
+fn to_lower(text: &str) -> String {
+    text.chars()
+        .map(|c| map_case(c, char::to_lowercase))
+        .collect()
+}
+
+fn to_upper(text: &str) -> String {
+    text.chars()
+        .map(|c| map_case(c, char::to_uppercase))
+        .collect()
+}
+
+fn camelcase_to_underscore(text: &str, lowercase: bool) -> String {
+    let chars: Vec<char> = text.chars().chain(std::iter::once('\0')).collect();
+    let is_upper = |c: char| c.is_ascii_uppercase();
+    let is_lower = |c: char| c.is_ascii_lowercase();
+    let is_number = |c: char| c.is_ascii_digit();
+
+    let mut result = String::new();
+    let mut start = 0;
+    for i in 1..chars.len() {
+        let upper = is_upper(chars[i]);
+        let number = is_number(chars[i]);
+        let prev_upper = is_upper(chars[i - 1]);
+        let prev_number = is_number(chars[i - 1]);
+        let next_2_lower = i + 2 < chars.len() && is_lower(chars[i + 1]) && is_lower(chars[i + 2]);
+        let next_lower = i + 1 < chars.len() && is_lower(chars[i + 1]);
+        let next_number = i + 1 < chars.len() && is_number(chars[i + 1]);
+
+        let should_split = (upper
+            && (if prev_upper {
+                next_2_lower
+            } else {
+                !prev_number
+            }))
+            || (number && !prev_number && next_lower)
+            || (!number && prev_number && (next_lower || next_number));
+
+        if should_split {
+            result.extend(&chars[start..i]);
+            result.push('_');
+            start = i;
+        }
+    }
+    result.extend(&chars[start..chars.len() - 1]);
+
+    if lowercase {
+        to_lower(&result)
+    } else {
+        result
+    }
+}
+
+/// Equivalent of `String::strip_edges`, which strips all control characters and spaces.
+pub(super) fn strip_edges(text: &str) -> &str {
+    text.trim_matches(|c: char| c as u32 <= 32)
+}
+
+fn capitalize(text: &str) -> String {
+    let aux = camelcase_to_underscore(text, true).replace('_', " ");
+    let mut result = String::new();
+    for (i, word) in strip_edges(&aux).split(' ').enumerate() {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if i > 0 {
+                result.push(' ');
+            }
+            result.push(map_case(first, char::to_uppercase));
+            result.extend(chars);
+        }
+    }
+    result
+}
+
+unsafe extern "C-unwind" fn godot_string_camelcase_to_underscore(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(camelcase_to_underscore(text(self_), false))
+}
+
+unsafe extern "C-unwind" fn godot_string_camelcase_to_underscore_lowercased(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(camelcase_to_underscore(text(self_), true))
+}
+
+unsafe extern "C-unwind" fn godot_string_capitalize(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(capitalize(text(self_)))
+}
+
+unsafe extern "C-unwind" fn godot_string_to_lower(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(to_lower(text(self_)))
+}
+
+unsafe extern "C-unwind" fn godot_string_to_upper(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(to_upper(text(self_)))
+}
+
+#[allow(clippy::unnecessary_cast)] // False positives: casts necessary for cross-platform
+unsafe extern "C-unwind" fn godot_string_char_lowercase(char: sys::wchar_t) -> sys::wchar_t {
+    map_case(unit_to_char(char), char::to_lowercase) as u32 as sys::wchar_t
+}
+
+#[allow(clippy::unnecessary_cast)] // False positives: casts necessary for cross-platform
+unsafe extern "C-unwind" fn godot_string_char_uppercase(char: sys::wchar_t) -> sys::wchar_t {
+    map_case(unit_to_char(char), char::to_uppercase) as u32 as sys::wchar_t
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Escaping
+
+fn replace_all(text: &str, replacements: &[(&str, &str)]) -> String {
+    replacements
+        .iter()
+        .fold(text.to_string(), |text, (from, to)| text.replace(from, to))
+}
+
+/// Equivalent of `String::json_escape`.
+pub(super) fn json_escape(text: &str) -> String {
+    replace_all(
+        text,
+        &[
+            ("\\", "\\\\"),
+            ("\x08", "\\b"),
+            ("\x0c", "\\f"),
+            ("\n", "\\n"),
+            ("\r", "\\r"),
+            ("\t", "\\t"),
+            ("\x0b", "\\v"),
+            ("\"", "\\\""),
+        ],
+    )
+}
+
+fn xml_unescape(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let rest = &chars[i..];
+        let starts_with = |s: &str| rest.iter().copied().take(s.len()).eq(s.chars());
+        if rest[0] != '&' {
+            result.push(rest[0]);
+            i += 1;
+        } else if rest.len() >= 4 && rest[1] == '#' {
+            // Numeric references are always read as hexadecimal, like the engine does
+            let mut code: u32 = 0;
+            let mut eat = 2;
+            for &c in &rest[2..] {
+                eat += 1;
+                if c == ';' {
+                    break;
+                }
+                if let Some(digit) = c.to_digit(16) {
+                    code = (code << 4) | digit;
+                }
+            }
+            result.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            i += eat;
+        } else if starts_with("&gt;") {
+            result.push('>');
+            i += 4;
+        } else if starts_with("&lt;") {
+            result.push('<');
+            i += 4;
+        } else if starts_with("&amp;") {
+            result.push('&');
+            i += 5;
+        } else if starts_with("&quot;") {
+            result.push('"');
+            i += 6;
+        } else if starts_with("&apos;") {
+            result.push('\'');
+            i += 6;
+        } else {
+            result.push('&');
+            i += 1;
+        }
+    }
+    result
+}
+
+fn http_unescape(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let is_digit = |c: char| c.is_ascii_digit() || c.is_ascii_uppercase();
+    let mut bytes = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '%' && i + 2 < chars.len() {
+            if is_digit(chars[i + 1]) {
+                if is_digit(chars[i + 2]) {
+                    let hex: String = chars[i + 1..i + 3].iter().collect();
+                    bytes.push(u8::from_str_radix(&hex, 16).unwrap_or(0));
+                    i += 2;
+                }
+            } else {
+                bytes.push(b'%');
+            }
+        } else {
+            bytes.push(chars[i] as u32 as u8);
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let char_len = text.chars().count() as i64;
+    let hex = |c: u8| {
+        (c as char)
+            .to_ascii_lowercase()
+            .to_digit(16)
+            .map(|d| d as u8)
+    };
+    let mut decoded = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c == b'%' && (i as i64) < char_len - 2 {
+            if let (Some(a), Some(b)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                decoded.push((a << 4) | b);
+                i += 2;
+            }
+        } else {
+            decoded.push(c);
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn percent_encode(text: &str, allowed: impl Fn(u8) -> bool) -> String {
+    let mut encoded = String::new();
+    for &b in text.as_bytes() {
+        if allowed(b) {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{b:02X}"));
+        }
+    }
+    encoded
+}
+
+unsafe extern "C-unwind" fn godot_string_c_escape(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(replace_all(
+        text(self_),
+        &[
+            ("\\", "\\\\"),
+            ("\x07", "\\a"),
+            ("\x08", "\\b"),
+            ("\x0c", "\\f"),
+            ("\n", "\\n"),
+            ("\r", "\\r"),
+            ("\t", "\\t"),
+            ("\x0b", "\\v"),
+            ("'", "\\'"),
+            ("?", "\\?"),
+            ("\"", "\\\""),
+        ],
+    ))
+}
+
+unsafe extern "C-unwind" fn godot_string_c_escape_multiline(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(replace_all(text(self_), &[("\\", "\\\\"), ("\"", "\\\"")]))
+}
+
+unsafe extern "C-unwind" fn godot_string_c_unescape(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(replace_all(
+        text(self_),
+        &[
+            ("\\a", "\x07"),
+            ("\\b", "\x08"),
+            ("\\f", "\x0c"),
+            ("\\n", "\n"),
+            ("\\r", "\r"),
+            ("\\t", "\t"),
+            ("\\v", "\x0b"),
+            ("\\'", "'"),
+            ("\\\"", "\""),
+            ("\\?", "?"),
+            ("\\\\", "\\"),
+        ],
+    ))
+}
+
+unsafe extern "C-unwind" fn godot_string_http_escape(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    // The engine formats bytes with `%hhX`, which does not pad single digits
+    let mut escaped = String::new();
+    for &b in text(self_).as_bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_' | b'~') {
+            escaped.push(b as char);
+        } else {
+            escaped.push_str(&format!("%{b:X}"));
+        }
+    }
+    new(escaped)
+}
+
+unsafe extern "C-unwind" fn godot_string_http_unescape(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(http_unescape(text(self_)))
+}
+
+unsafe extern "C-unwind" fn godot_string_json_escape(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(json_escape(text(self_)))
+}
+
+unsafe extern "C-unwind" fn godot_string_xml_escape(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(replace_all(
+        text(self_),
+        &[("&", "&amp;"), ("<", "&lt;"), (">", "&gt;")],
+    ))
+}
+
+unsafe extern "C-unwind" fn godot_string_xml_escape_with_quotes(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(replace_all(
+        text(self_),
+        &[
+            ("&", "&amp;"),
+            ("<", "&lt;"),
+            (">", "&gt;"),
+            ("'", "&apos;"),
+            ("\"", "&quot;"),
+        ],
+    ))
+}
+
+unsafe extern "C-unwind" fn godot_string_xml_unescape(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(xml_unescape(text(self_)))
+}
+
+unsafe extern "C-unwind" fn godot_string_percent_decode(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(percent_decode(text(self_)))
+}
+
+unsafe extern "C-unwind" fn godot_string_percent_encode(
+    self_: *const sys::godot_string,
+) -> sys::godot_string {
+    new(percent_encode(text(self_), |b| {
+        b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'~' | b'.')
+    }))
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// String names
+
+/// Interned name. Entries are never freed, so the data pointer is unique for each name.
+struct NameData {
+    name: String,
+    hash: u32,
+}
+
+static NAMES: Lazy<Mutex<HashMap<String, &'static NameData>>> = Lazy::new(Default::default);
+
+fn intern(name: &str) -> *const NameData {
+    let mut names = NAMES.lock();
+    if let Some(&data) = names.get(name) {
+        return data;
+    }
+
+    let wide = encode_wide(name);
+    let data: &'static NameData = Box::leak(Box::new(NameData {
+        name: name.to_string(),
+        hash: hash_units(&wide[..wide.len() - 1]),
+    }));
+    names.insert(name.to_string(), data);
+    data
+}
+
+unsafe fn name_data<'a>(name: *const sys::godot_string_name) -> Option<&'a NameData> {
+    load::<_, NameData>(name).as_ref()
+}
+
+unsafe extern "C-unwind" fn godot_string_name_new(
+    r_dest: *mut sys::godot_string_name,
+    name: *const sys::godot_string,
+) {
+    *r_dest = sys::godot_string_name::default();
+    store(r_dest, intern(text(name)));
+}
+
+unsafe extern "C-unwind" fn godot_string_name_new_data(
+    r_dest: *mut sys::godot_string_name,
+    name: *const c_char,
+) {
+    *r_dest = sys::godot_string_name::default();
+    store(r_dest, intern(&CStr::from_ptr(name).to_string_lossy()));
+}
+
+unsafe extern "C-unwind" fn godot_string_name_get_name(
+    self_: *const sys::godot_string_name,
+) -> sys::godot_string {
+    new(name_data(self_).map_or("", |data| data.name.as_str()))
+}
+
+unsafe extern "C-unwind" fn godot_string_name_get_hash(
+    self_: *const sys::godot_string_name,
+) -> u32 {
+    name_data(self_).map_or(0, |data| data.hash)
+}
+
+unsafe extern "C-unwind" fn godot_string_name_get_data_unique_pointer(
+    self_: *const sys::godot_string_name,
+) -> *const c_void {
+    load::<_, c_void>(self_)
+}
+
+unsafe extern "C-unwind" fn godot_string_name_operator_equal(
+    self_: *const sys::godot_string_name,
+    other: *const sys::godot_string_name,
+) -> sys::godot_bool {
+    load::<_, NameData>(self_) == load::<_, NameData>(other)
+}
+
+unsafe extern "C-unwind" fn godot_string_name_operator_less(
+    self_: *const sys::godot_string_name,
+    other: *const sys::godot_string_name,
+) -> sys::godot_bool {
+    // Like the engine, names are ordered by their data pointers
+    load::<_, NameData>(self_) < load::<_, NameData>(other)
+}
+
+unsafe extern "C-unwind" fn godot_string_name_destroy(self_: *mut sys::godot_string_name) {
+    *self_ = sys::godot_string_name::default();
+}
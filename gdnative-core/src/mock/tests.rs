@@ -0,0 +1,447 @@
+use super::install;
+use crate::core_types::*;
+use crate::object::NewRef;
+use std::collections::HashMap;
+
+#[test]
+fn string_basics() {
+    install();
+
+    let s = GodotString::from("Hello, Wörld!");
+    assert_eq!(13, s.len());
+    assert_eq!("Hello, Wörld!", s.to_string());
+    assert_eq!(s, GodotString::from_str("Hello, Wörld!"));
+    assert!(GodotString::new().is_empty());
+
+    assert!(s.begins_with(&"Hello".into()));
+    assert!(s.ends_with(&"!".into()));
+    assert_eq!(7, s.find(&"W".into()));
+    assert_eq!(-1, s.find(&"x".into()));
+    assert_eq!("Wörld", s.sub_string(7..12).to_string());
+    assert_eq!("hello, wörld!", s.to_lowercase().to_string());
+    assert_eq!("HELLO, WÖRLD!", s.to_uppercase().to_string());
+
+    let joined = GodotString::from("a") + GodotString::from("b");
+    assert_eq!("ab", joined.to_string());
+    assert!(GodotString::from("a") < GodotString::from("b"));
+}
+
+#[test]
+fn string_parsing() {
+    install();
+
+    assert_eq!(42, GodotString::from("42").to_i32());
+    assert_eq!(-7, GodotString::from("-7abc").to_i32());
+    assert!((GodotString::from("1.5").to_f64() - 1.5).abs() < f64::EPSILON);
+    assert!(GodotString::from("12").is_valid_integer());
+    assert!(!GodotString::from("1x2").is_valid_integer());
+    assert!(GodotString::from("0x1F").is_valid_hex_number(true));
+    assert_eq!(31, GodotString::from("0x1F").hex_to_int());
+}
+
+#[test]
+fn string_format() {
+    install();
+
+    let values = Dictionary::new();
+    values.insert("name", "Godot");
+    values.insert("version", 3);
+    let template = GodotString::from("{name} {version}");
+    assert_eq!(
+        "Godot 3",
+        template
+            .format(&values.into_shared().to_variant())
+            .to_string()
+    );
+}
+
+#[test]
+fn string_hashes() {
+    install();
+
+    let s = GodotString::from("abc");
+    assert_eq!("900150983cd24fb0d6963f7d28e17f72", s.md5_text().to_string());
+    assert_eq!(
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        s.sha256_text().to_string()
+    );
+    assert_eq!(s.u32_hash(), GodotString::from("abc").u32_hash());
+}
+
+#[test]
+fn string_name() {
+    install();
+
+    let a = StringName::from_str("name");
+    let b = StringName::from_str("name");
+    assert_eq!(a, b);
+    assert_eq!("name", a.to_godot_string().to_string());
+    assert_ne!(a, StringName::from_str("other"));
+}
+
+#[test]
+fn variant_conversions() {
+    install();
+
+    assert_eq!(VariantType::Nil, Variant::nil().get_type());
+    assert_eq!(Some(42), 42.to_variant().to::<i64>());
+    assert_eq!(Some(true), true.to_variant().to::<bool>());
+    assert_eq!(Some(1.5), 1.5.to_variant().to::<f64>());
+    assert_eq!(Some("text".to_string()), "text".to_variant().to::<String>());
+    assert_eq!(
+        Some(Vector3::new(1.0, 2.0, 3.0)),
+        Vector3::new(1.0, 2.0, 3.0).to_variant().to::<Vector3>()
+    );
+    assert_eq!(
+        Some(Color::from_rgba(0.1, 0.2, 0.3, 0.4)),
+        Color::from_rgba(0.1, 0.2, 0.3, 0.4)
+            .to_variant()
+            .to::<Color>()
+    );
+    assert_eq!(None, "text".to_variant().to::<i64>());
+
+    let v = Vector2::new(1.0, 2.0).to_variant();
+    assert_eq!(v, v.clone());
+    assert_eq!(VariantType::Vector2, v.clone().get_type());
+}
+
+#[test]
+fn variant_display() {
+    install();
+
+    assert_eq!("Null", Variant::nil().to_string());
+    assert_eq!("True", true.to_variant().to_string());
+    assert_eq!("1.5", 1.5.to_variant().to_string());
+    assert_eq!("3", 3.0.to_variant().to_string());
+    assert_eq!("(1, 2.5)", Vector2::new(1.0, 2.5).to_variant().to_string());
+
+    let array = VariantArray::new();
+    array.push(1);
+    array.push("two");
+    assert_eq!("[1, two]", array.into_shared().to_variant().to_string());
+}
+
+#[test]
+fn variant_evaluate() {
+    install();
+
+    let result = 2
+        .to_variant()
+        .evaluate(VariantOperator::Add, &3.to_variant());
+    assert_eq!(Ok(5.to_variant()), result);
+
+    let result = 2
+        .to_variant()
+        .evaluate(VariantOperator::Multiply, &1.5.to_variant());
+    assert_eq!(Ok(3.0.to_variant()), result);
+
+    let result = "a"
+        .to_variant()
+        .evaluate(VariantOperator::Add, &"b".to_variant());
+    assert_eq!(Ok("ab".to_variant()), result);
+
+    let result = 1
+        .to_variant()
+        .evaluate(VariantOperator::Less, &2.5.to_variant());
+    assert_eq!(Ok(true.to_variant()), result);
+
+    assert!(1
+        .to_variant()
+        .evaluate(VariantOperator::Divide, &0.to_variant())
+        .is_err());
+    assert!(1
+        .to_variant()
+        .evaluate(VariantOperator::Add, &"a".to_variant())
+        .is_err());
+
+    assert_eq!(1.to_variant(), 1.0.to_variant());
+    assert!(1.to_variant() < 2.to_variant());
+}
+
+#[test]
+fn variant_call_unsupported() {
+    install();
+
+    let mut v = 1.to_variant();
+    assert!(!v.has_method("get_class"));
+    assert!(unsafe { v.call("get_class", &[]) }.is_err());
+}
+
+#[test]
+fn array() {
+    install();
+
+    let array = VariantArray::new();
+    for i in 0..5 {
+        array.push(i);
+    }
+    assert_eq!(5, array.len());
+    assert_eq!(3.to_variant(), array.get(3));
+    assert!(array.contains(4));
+    assert_eq!(2, array.find(2, 0));
+    assert_eq!(-1, array.find(7, 0));
+
+    array.set(0, "zero");
+    array.erase(1);
+    assert_eq!(4, array.len());
+    assert_eq!("zero".to_variant(), array.get(0));
+
+    let shared = array.into_thread_local();
+    let other = shared.new_ref();
+    other.push(5);
+    assert_eq!(5, shared.len());
+
+    let copy = shared.duplicate();
+    copy.push(6);
+    assert_eq!(5, shared.len());
+    assert_eq!(6, copy.len());
+
+    let values: Vec<Variant> = shared.iter().collect();
+    assert_eq!(5.to_variant(), values[4]);
+}
+
+#[test]
+fn array_sort() {
+    install();
+
+    let array = VariantArray::new();
+    array.push(3);
+    array.push(1);
+    array.push(2);
+    array.sort();
+    let values: Vec<i64> = array.iter().map(|v| v.to::<i64>().unwrap()).collect();
+    assert_eq!(vec![1, 2, 3], values);
+}
+
+#[test]
+#[should_panic(expected = "array index 3 out of bounds (length 3)")]
+fn array_index_out_of_bounds() {
+    install();
+
+    let array = VariantArray::new();
+    array.push(1);
+    array.push(2);
+    array.push(3);
+    unsafe { (crate::private::get_api().godot_array_get)(array.sys(), 3) };
+}
+
+#[test]
+#[should_panic(expected = "`godot_arvr_get_worldscale` is not supported")]
+fn unsupported_function() {
+    install();
+
+    unsafe { (crate::private::get_api().godot_arvr_get_worldscale)() };
+}
+
+#[test]
+fn dictionary() {
+    install();
+
+    let dict = Dictionary::new();
+    dict.insert("a", 1);
+    dict.insert(2, "b");
+    dict.insert("a", 3);
+
+    assert_eq!(2, dict.len());
+    assert!(dict.contains("a"));
+    assert!(!dict.contains("c"));
+    assert_eq!(Some(3.to_variant()), dict.get("a"));
+    assert_eq!(Variant::nil(), dict.get_or_nil("c"));
+
+    let keys: Vec<Variant> = dict.iter().map(|(k, _)| k).collect();
+    assert_eq!(vec!["a".to_variant(), 2.to_variant()], keys);
+
+    assert_eq!(r#"{"2":"b","a":3}"#, dict.to_json().to_string());
+
+    dict.erase("a");
+    assert_eq!(1, dict.len());
+
+    let shared = dict.into_thread_local();
+    let other = shared.new_ref();
+    other.insert("c", 4);
+    assert_eq!(2, shared.len());
+
+    // Dictionaries compare by identity
+    let a = Dictionary::new_shared();
+    let b = a.new_ref();
+    assert_eq!(a.to_variant(), b.to_variant());
+    assert_ne!(a.to_variant(), a.duplicate().into_shared().to_variant());
+}
+
+#[test]
+fn dictionary_keys_are_strict() {
+    install();
+
+    let dict = Dictionary::new();
+    dict.insert(1, "int");
+    dict.insert(1.0.to_variant(), "float");
+    assert_eq!(2, dict.len());
+}
+
+#[test]
+fn pool_array() {
+    install();
+
+    let mut array = PoolArray::from_vec(vec![1_i32, 2, 3]);
+    array.push(4);
+    assert_eq!(4, array.len());
+    assert_eq!(3, array.get(2));
+
+    {
+        let mut write = array.write();
+        write[0] = 10;
+    }
+    assert_eq!(vec![10, 2, 3, 4], array.to_vec());
+
+    let copy = array.clone();
+    array.push(5);
+    assert_eq!(4, copy.len());
+    assert_eq!(5, array.len());
+
+    let strings = PoolArray::from_vec(vec![GodotString::from("a"), GodotString::from("b")]);
+    assert_eq!("b", strings.get(1).to_string());
+
+    let variant = strings.to_variant();
+    assert_eq!(VariantType::StringArray, variant.get_type());
+    assert_eq!(
+        Some(strings.to_vec()),
+        variant.to::<PoolArray<GodotString>>().map(|a| a.to_vec())
+    );
+}
+
+#[test]
+fn byte_array_compression() {
+    install();
+
+    let mut modes = vec![CompressionMode::FastLz];
+    #[cfg(feature = "compression")]
+    modes.extend([
+        CompressionMode::Deflate,
+        CompressionMode::Zstd,
+        CompressionMode::Gzip,
+    ]);
+
+    let bytes = PoolArray::from_vec("abcabcabc ".repeat(50).into_bytes());
+    for mode in modes {
+        let compressed = bytes.compress(mode);
+        assert!(compressed.len() < bytes.len(), "{mode:?}");
+        assert_eq!(
+            Ok(bytes.to_vec()),
+            compressed
+                .decompress(bytes.len() as usize, mode)
+                .map(|a| a.to_vec()),
+            "{mode:?}"
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    {
+        let compressed = bytes.compress(CompressionMode::Gzip);
+        assert_eq!(
+            Ok(bytes.to_vec()),
+            compressed
+                .decompress_dynamic(None, CompressionMode::Gzip)
+                .map(|a| a.to_vec())
+        );
+    }
+}
+
+#[test]
+fn node_path() {
+    install();
+
+    let path = NodePath::from_str("/root/Node:position:x");
+    assert!(path.is_absolute());
+    assert_eq!(2, path.name_count());
+    assert_eq!("Node", path.get_name(1).to_string());
+    assert_eq!(2, path.get_subname_count());
+    assert_eq!("x", path.get_subname(1).to_string());
+    assert_eq!("position:x", path.get_concatenated_subnames().to_string());
+    assert_eq!("/root/Node:position:x", path.to_string());
+    assert!(NodePath::default().is_empty());
+    assert_eq!(path, NodePath::from_str("/root/Node:position:x"));
+}
+
+#[test]
+fn rid() {
+    install();
+
+    let rid = Rid::new();
+    assert!(!rid.is_occupied());
+    assert_eq!(rid, Rid::default());
+}
+
+#[test]
+fn from_variant_error_path() {
+    install();
+
+    let inventory = Dictionary::new();
+    inventory.insert("shield", VariantArray::new().into_shared());
+    let items = VariantArray::new();
+    items.push(1);
+    items.push("two");
+    inventory.insert("sword", items.into_shared());
+
+    let array = VariantArray::new();
+    array.push(Dictionary::new().into_shared());
+    array.push(inventory.into_shared());
+    let variant = array.into_shared().to_variant();
+
+    let err = Vec::<HashMap<String, Vec<i64>>>::from_variant(&variant).unwrap_err();
+    assert_eq!(
+        &[
+            FromVariantPathSegment::Index(1),
+            FromVariantPathSegment::Key("\"sword\"".into()),
+            FromVariantPathSegment::Index(1),
+        ],
+        err.path().segments()
+    );
+    assert_eq!(
+        &FromVariantError::InvalidVariantType {
+            variant_type: VariantType::GodotString,
+            expected: VariantType::I64,
+        },
+        err.root_cause()
+    );
+    assert_eq!(
+        "[1][\"sword\"][1]: invalid variant type: expected I64, got GodotString",
+        err.to_string()
+    );
+
+    let err = i64::from_variant(&"text".to_variant()).unwrap_err();
+    assert!(err.path().is_empty());
+    assert_eq!(&err, err.root_cause());
+}
+
+#[test]
+fn json() {
+    install();
+
+    let variant = json::parse(r#"{"a": [1, 2.0, "three"], "b": null}"#).unwrap();
+    let dict = variant.to::<Dictionary>().unwrap();
+    let items = dict.get("a").unwrap().to::<VariantArray>().unwrap();
+    assert_eq!(VariantType::I64, items.get(0).get_type());
+    assert_eq!(VariantType::F64, items.get(1).get_type());
+    assert_eq!(
+        Ok(r#"{"a":[1,2.0,"three"],"b":null}"#.to_string()),
+        json::to_string(&variant)
+    );
+
+    let err = json::parse("[1,\n 2").unwrap_err();
+    assert_eq!((2, 3), (err.line(), err.column()));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn json_serde_bridge() {
+    install();
+
+    let value: serde_json::Value = serde_json::json!({ "a": [1, 2.5, "x", null, true] });
+    let variant = value.to_variant();
+    assert_eq!(
+        Ok(r#"{"a":[1,2.5,"x",null,true]}"#.to_string()),
+        json::to_string(&variant)
+    );
+    assert_eq!(Ok(value), serde_json::Value::from_variant(&variant));
+    assert!(serde_json::Value::from_variant(&f64::NAN.to_variant()).is_err());
+}
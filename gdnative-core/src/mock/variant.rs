@@ -0,0 +1,1208 @@
+//! Variants, including their conversions, comparisons and operators.
+//!
+//! A `godot_variant` stores a pointer to a boxed [`Value`] in its first bytes. A null pointer is
+//! `Nil`, matching a zero-initialized `Variant` in the engine.
+
+use std::cmp::Ordering;
+use std::mem::{self, ManuallyDrop};
+use std::os::raw::c_double;
+use std::ptr;
+
+use super::{array, dictionary, load, node_path, store, string};
+use crate::core_types::{
+    real, Aabb, Basis, Color, Dictionary, GodotString, NodePath, Plane, PoolArray, PoolElement,
+    Quat, RealConv, Rect2, Rid, Transform, Transform2D, Variant, VariantArray, VariantOperator,
+    VariantType, Vector2, Vector3,
+};
+use crate::object::NewRef;
+use crate::sys;
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    register!(api:
+        godot_variant_new_copy,
+        godot_variant_new_nil,
+        godot_variant_new_bool,
+        godot_variant_new_uint,
+        godot_variant_new_int,
+        godot_variant_new_real,
+        godot_variant_new_string,
+        godot_variant_new_vector2,
+        godot_variant_new_rect2,
+        godot_variant_new_vector3,
+        godot_variant_new_transform2d,
+        godot_variant_new_plane,
+        godot_variant_new_quat,
+        godot_variant_new_aabb,
+        godot_variant_new_basis,
+        godot_variant_new_transform,
+        godot_variant_new_color,
+        godot_variant_new_node_path,
+        godot_variant_new_rid,
+        godot_variant_new_object,
+        godot_variant_new_dictionary,
+        godot_variant_new_array,
+        godot_variant_new_pool_byte_array,
+        godot_variant_new_pool_int_array,
+        godot_variant_new_pool_real_array,
+        godot_variant_new_pool_string_array,
+        godot_variant_new_pool_vector2_array,
+        godot_variant_new_pool_vector3_array,
+        godot_variant_new_pool_color_array,
+        godot_variant_as_bool,
+        godot_variant_as_uint,
+        godot_variant_as_int,
+        godot_variant_as_real,
+        godot_variant_as_string,
+        godot_variant_as_vector2,
+        godot_variant_as_rect2,
+        godot_variant_as_vector3,
+        godot_variant_as_transform2d,
+        godot_variant_as_plane,
+        godot_variant_as_quat,
+        godot_variant_as_aabb,
+        godot_variant_as_basis,
+        godot_variant_as_transform,
+        godot_variant_as_color,
+        godot_variant_as_node_path,
+        godot_variant_as_rid,
+        godot_variant_as_object,
+        godot_variant_as_dictionary,
+        godot_variant_as_array,
+        godot_variant_as_pool_byte_array,
+        godot_variant_as_pool_int_array,
+        godot_variant_as_pool_real_array,
+        godot_variant_as_pool_string_array,
+        godot_variant_as_pool_vector2_array,
+        godot_variant_as_pool_vector3_array,
+        godot_variant_as_pool_color_array,
+        godot_variant_call,
+        godot_variant_has_method,
+        godot_variant_operator_equal,
+        godot_variant_operator_less,
+        godot_variant_hash_compare,
+        godot_variant_booleanize,
+        godot_variant_destroy,
+        godot_variant_get_type,
+        godot_variant_evaluate,
+    );
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Storage
+
+/// Contents of a variant. Reference types hold their own handles, which are copied and destroyed
+/// through the (mock) API like any other core type.
+pub(super) enum Value {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Real(f64),
+    String(GodotString),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    NodePath(NodePath),
+    Rid(Rid),
+    Object(*mut sys::godot_object),
+    Dictionary(Dictionary),
+    Array(VariantArray),
+    ByteArray(PoolArray<u8>),
+    Int32Array(PoolArray<i32>),
+    Float32Array(PoolArray<real>),
+    StringArray(PoolArray<GodotString>),
+    Vector2Array(PoolArray<Vector2>),
+    Vector3Array(PoolArray<Vector3>),
+    ColorArray(PoolArray<Color>),
+}
+
+const NIL: &Value = &Value::Nil;
+
+impl Clone for Value {
+    fn clone(&self) -> Self {
+        match self {
+            Value::Nil => Value::Nil,
+            Value::Bool(v) => Value::Bool(*v),
+            Value::Int(v) => Value::Int(*v),
+            Value::Real(v) => Value::Real(*v),
+            Value::String(v) => Value::String(v.clone()),
+            Value::Vector2(v) => Value::Vector2(*v),
+            Value::Rect2(v) => Value::Rect2(*v),
+            Value::Vector3(v) => Value::Vector3(*v),
+            Value::Transform2D(v) => Value::Transform2D(*v),
+            Value::Plane(v) => Value::Plane(*v),
+            Value::Quat(v) => Value::Quat(*v),
+            Value::Aabb(v) => Value::Aabb(*v),
+            Value::Basis(v) => Value::Basis(*v),
+            Value::Transform(v) => Value::Transform(*v),
+            Value::Color(v) => Value::Color(*v),
+            Value::NodePath(v) => Value::NodePath(v.new_ref()),
+            Value::Rid(v) => Value::Rid(*v),
+            Value::Object(v) => Value::Object(*v),
+            Value::Dictionary(v) => Value::Dictionary(v.new_ref()),
+            Value::Array(v) => Value::Array(v.new_ref()),
+            Value::ByteArray(v) => Value::ByteArray(v.clone()),
+            Value::Int32Array(v) => Value::Int32Array(v.clone()),
+            Value::Float32Array(v) => Value::Float32Array(v.clone()),
+            Value::StringArray(v) => Value::StringArray(v.clone()),
+            Value::Vector2Array(v) => Value::Vector2Array(v.clone()),
+            Value::Vector3Array(v) => Value::Vector3Array(v.clone()),
+            Value::ColorArray(v) => Value::ColorArray(v.clone()),
+        }
+    }
+}
+
+impl Value {
+    pub(super) fn get_type(&self) -> VariantType {
+        match self {
+            Value::Nil => VariantType::Nil,
+            Value::Bool(_) => VariantType::Bool,
+            Value::Int(_) => VariantType::I64,
+            Value::Real(_) => VariantType::F64,
+            Value::String(_) => VariantType::GodotString,
+            Value::Vector2(_) => VariantType::Vector2,
+            Value::Rect2(_) => VariantType::Rect2,
+            Value::Vector3(_) => VariantType::Vector3,
+            Value::Transform2D(_) => VariantType::Transform2D,
+            Value::Plane(_) => VariantType::Plane,
+            Value::Quat(_) => VariantType::Quat,
+            Value::Aabb(_) => VariantType::Aabb,
+            Value::Basis(_) => VariantType::Basis,
+            Value::Transform(_) => VariantType::Transform,
+            Value::Color(_) => VariantType::Color,
+            Value::NodePath(_) => VariantType::NodePath,
+            Value::Rid(_) => VariantType::Rid,
+            Value::Object(_) => VariantType::Object,
+            Value::Dictionary(_) => VariantType::Dictionary,
+            Value::Array(_) => VariantType::VariantArray,
+            Value::ByteArray(_) => VariantType::ByteArray,
+            Value::Int32Array(_) => VariantType::Int32Array,
+            Value::Float32Array(_) => VariantType::Float32Array,
+            Value::StringArray(_) => VariantType::StringArray,
+            Value::Vector2Array(_) => VariantType::Vector2Array,
+            Value::Vector3Array(_) => VariantType::Vector3Array,
+            Value::ColorArray(_) => VariantType::ColorArray,
+        }
+    }
+
+    fn is_number(&self) -> bool {
+        matches!(self, Value::Int(_) | Value::Real(_))
+    }
+}
+
+/// Creates a new variant handle holding `value`.
+pub(super) fn new(value: Value) -> sys::godot_variant {
+    let mut handle = sys::godot_variant::default();
+    if !matches!(value, Value::Nil) {
+        unsafe { store(&mut handle, Box::into_raw(Box::new(value))) };
+    }
+    handle
+}
+
+/// Creates a new `Variant` holding `value`.
+pub(super) fn variant(value: Value) -> Variant {
+    Variant::from_sys(new(value))
+}
+
+/// Returns the contents of a variant handle.
+pub(super) unsafe fn value<'a>(v: *const sys::godot_variant) -> &'a Value {
+    load::<_, Value>(v).as_ref().unwrap_or(NIL)
+}
+
+/// Returns the contents of a `Variant`.
+pub(super) fn value_of(v: &Variant) -> &Value {
+    unsafe { value(v.sys()) }
+}
+
+/// Borrows a core type from a sys handle without taking ownership.
+pub(super) unsafe fn borrow<S: Copy, T>(sys: *const S, from_sys: fn(S) -> T) -> ManuallyDrop<T> {
+    ManuallyDrop::new(from_sys(*sys))
+}
+
+/// Transfers ownership of a core type to its sys handle.
+pub(super) fn leak<S: Copy, T>(value: T, sys: fn(&T) -> *const S) -> S {
+    let handle = unsafe { *sys(&value) };
+    mem::forget(value);
+    handle
+}
+
+/// Equivalent of `Variant::duplicate(true)` for arrays and dictionaries.
+pub(super) fn deep_copy(v: &Variant) -> Variant {
+    unsafe {
+        match value_of(v) {
+            Value::Array(a) => variant(Value::Array(VariantArray::from_sys(array::duplicate(
+                a.sys(),
+                true,
+            )))),
+            Value::Dictionary(d) => variant(Value::Dictionary(Dictionary::from_sys(
+                dictionary::duplicate(d.sys(), true),
+            ))),
+            _ => v.clone(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Construction
+
+unsafe extern "C-unwind" fn godot_variant_new_copy(
+    r_dest: *mut sys::godot_variant,
+    src: *const sys::godot_variant,
+) {
+    *r_dest = new(value(src).clone());
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_nil(r_dest: *mut sys::godot_variant) {
+    *r_dest = new(Value::Nil);
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_bool(
+    r_dest: *mut sys::godot_variant,
+    b: sys::godot_bool,
+) {
+    *r_dest = new(Value::Bool(b));
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_uint(r_dest: *mut sys::godot_variant, i: u64) {
+    *r_dest = new(Value::Int(i as i64));
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_int(r_dest: *mut sys::godot_variant, i: i64) {
+    *r_dest = new(Value::Int(i));
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_real(r_dest: *mut sys::godot_variant, r: c_double) {
+    *r_dest = new(Value::Real(r));
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_string(
+    r_dest: *mut sys::godot_variant,
+    s: *const sys::godot_string,
+) {
+    let s = borrow(s, GodotString::from_sys);
+    *r_dest = new(Value::String((*s).clone()));
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_node_path(
+    r_dest: *mut sys::godot_variant,
+    np: *const sys::godot_node_path,
+) {
+    let np = borrow(np, NodePath::from_sys);
+    *r_dest = new(Value::NodePath(np.new_ref()));
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_rid(
+    r_dest: *mut sys::godot_variant,
+    rid: *const sys::godot_rid,
+) {
+    *r_dest = new(Value::Rid(Rid::from_sys(*rid)));
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_object(
+    r_dest: *mut sys::godot_variant,
+    obj: *const sys::godot_object,
+) {
+    *r_dest = new(Value::Object(obj as *mut _));
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_dictionary(
+    r_dest: *mut sys::godot_variant,
+    dict: *const sys::godot_dictionary,
+) {
+    let dict = borrow(dict, Dictionary::from_sys);
+    *r_dest = new(Value::Dictionary(dict.new_ref()));
+}
+
+unsafe extern "C-unwind" fn godot_variant_new_array(
+    r_dest: *mut sys::godot_variant,
+    arr: *const sys::godot_array,
+) {
+    let arr = borrow(arr, VariantArray::from_sys);
+    *r_dest = new(Value::Array(arr.new_ref()));
+}
+
+/// Math types are plain data with the same layout in sys and core types.
+macro_rules! math_conversions {
+    ($($new:ident, $as:ident: $Variant:ident($T:ident, $sys:ident);)*) => {
+        $(
+            unsafe extern "C-unwind" fn $new(r_dest: *mut sys::godot_variant, src: *const sys::$sys) {
+                *r_dest = new(Value::$Variant(mem::transmute::<sys::$sys, $T>(*src)));
+            }
+
+            unsafe extern "C-unwind" fn $as(self_: *const sys::godot_variant) -> sys::$sys {
+                mem::transmute::<$T, sys::$sys>(to_math::<$T>(value(self_)))
+            }
+        )*
+    };
+}
+
+math_conversions!(
+    godot_variant_new_vector2, godot_variant_as_vector2: Vector2(Vector2, godot_vector2);
+    godot_variant_new_rect2, godot_variant_as_rect2: Rect2(Rect2, godot_rect2);
+    godot_variant_new_vector3, godot_variant_as_vector3: Vector3(Vector3, godot_vector3);
+    godot_variant_new_transform2d, godot_variant_as_transform2d: Transform2D(Transform2D, godot_transform2d);
+    godot_variant_new_plane, godot_variant_as_plane: Plane(Plane, godot_plane);
+    godot_variant_new_quat, godot_variant_as_quat: Quat(Quat, godot_quat);
+    godot_variant_new_aabb, godot_variant_as_aabb: Aabb(Aabb, godot_aabb);
+    godot_variant_new_basis, godot_variant_as_basis: Basis(Basis, godot_basis);
+    godot_variant_new_transform, godot_variant_as_transform: Transform(Transform, godot_transform);
+    godot_variant_new_color, godot_variant_as_color: Color(Color, godot_color);
+);
+
+macro_rules! pool_conversions {
+    ($($new:ident, $as:ident: $T:ty, $sys:ident;)*) => {
+        $(
+            unsafe extern "C-unwind" fn $new(r_dest: *mut sys::godot_variant, src: *const sys::$sys) {
+                let src = borrow(src, PoolArray::<$T>::from_sys);
+                *r_dest = new(<$T as PoolValue>::into_value((*src).clone()));
+            }
+
+            unsafe extern "C-unwind" fn $as(self_: *const sys::godot_variant) -> sys::$sys {
+                leak(to_pool::<$T>(value(self_)), PoolArray::sys)
+            }
+        )*
+    };
+}
+
+pool_conversions!(
+    godot_variant_new_pool_byte_array, godot_variant_as_pool_byte_array: u8, godot_pool_byte_array;
+    godot_variant_new_pool_int_array, godot_variant_as_pool_int_array: i32, godot_pool_int_array;
+    godot_variant_new_pool_real_array, godot_variant_as_pool_real_array: real, godot_pool_real_array;
+    godot_variant_new_pool_string_array, godot_variant_as_pool_string_array: GodotString, godot_pool_string_array;
+    godot_variant_new_pool_vector2_array, godot_variant_as_pool_vector2_array: Vector2, godot_pool_vector2_array;
+    godot_variant_new_pool_vector3_array, godot_variant_as_pool_vector3_array: Vector3, godot_pool_vector3_array;
+    godot_variant_new_pool_color_array, godot_variant_as_pool_color_array: Color, godot_pool_color_array;
+);
+
+unsafe extern "C-unwind" fn godot_variant_destroy(self_: *mut sys::godot_variant) {
+    let ptr = load::<_, Value>(self_) as *mut Value;
+    *self_ = sys::godot_variant::default();
+    if !ptr.is_null() {
+        drop(Box::from_raw(ptr));
+    }
+}
+
+#[allow(clippy::unnecessary_cast)] // False positives: casts necessary for cross-platform
+unsafe extern "C-unwind" fn godot_variant_get_type(
+    v: *const sys::godot_variant,
+) -> sys::godot_variant_type {
+    value(v).get_type() as u32 as sys::godot_variant_type
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Conversions, following the engine's `Variant::operator T()`
+
+/// Equivalent of `Variant::booleanize`: whether the value is not the default of its type.
+pub(super) fn to_bool(value: &Value) -> bool {
+    match value {
+        Value::Nil => false,
+        Value::Bool(v) => *v,
+        Value::Int(v) => *v != 0,
+        Value::Real(v) => *v != 0.0,
+        Value::String(v) => !v.is_empty(),
+        Value::Vector2(v) => *v != Vector2::ZERO,
+        Value::Rect2(v) => v.position != Vector2::ZERO || v.size != Vector2::ZERO,
+        Value::Vector3(v) => *v != Vector3::ZERO,
+        Value::Transform2D(v) => *v != Transform2D::IDENTITY,
+        Value::Plane(v) => v.normal != Vector3::ZERO || v.d != 0.0,
+        Value::Quat(v) => *v != Quat::IDENTITY,
+        Value::Aabb(v) => v.position != Vector3::ZERO || v.size != Vector3::ZERO,
+        Value::Basis(v) => *v != Basis::IDENTITY,
+        Value::Transform(v) => *v != Transform::IDENTITY,
+        Value::Color(v) => *v != Color::from_rgba(0.0, 0.0, 0.0, 1.0),
+        Value::NodePath(v) => !v.is_empty(),
+        Value::Rid(v) => v.is_occupied(),
+        Value::Object(v) => !v.is_null(),
+        Value::Dictionary(v) => !v.is_empty(),
+        Value::Array(v) => !v.is_empty(),
+        Value::ByteArray(v) => !v.is_empty(),
+        Value::Int32Array(v) => !v.is_empty(),
+        Value::Float32Array(v) => !v.is_empty(),
+        Value::StringArray(v) => !v.is_empty(),
+        Value::Vector2Array(v) => !v.is_empty(),
+        Value::Vector3Array(v) => !v.is_empty(),
+        Value::ColorArray(v) => !v.is_empty(),
+    }
+}
+
+pub(super) fn to_int(value: &Value) -> i64 {
+    match value {
+        Value::Bool(v) => *v as i64,
+        Value::Int(v) => *v,
+        Value::Real(v) => *v as i64,
+        Value::String(v) => string::to_int(&v.to_string(), i64::MIN, i64::MAX),
+        _ => 0,
+    }
+}
+
+pub(super) fn to_real(value: &Value) -> f64 {
+    match value {
+        Value::Bool(v) => *v as i64 as f64,
+        Value::Int(v) => *v as f64,
+        Value::Real(v) => *v,
+        Value::String(v) => string::to_double(&v.to_string()),
+        _ => 0.0,
+    }
+}
+
+/// Conversions between plain data types.
+pub(super) trait MathValue: Copy {
+    fn from_value(value: &Value) -> Option<Self>;
+    fn default_value() -> Self;
+}
+
+macro_rules! impl_math_value {
+    ($($T:ident: $default:expr, { $($pattern:pat => $converted:expr,)* })*) => {
+        $(
+            impl MathValue for $T {
+                fn from_value(value: &Value) -> Option<Self> {
+                    match value {
+                        Value::$T(v) => Some(*v),
+                        $($pattern => Some($converted),)*
+                        _ => None,
+                    }
+                }
+
+                fn default_value() -> Self {
+                    $default
+                }
+            }
+        )*
+    };
+}
+
+impl_math_value!(
+    Vector2: Vector2::ZERO, {
+        Value::Vector3(v) => Vector2::new(v.x, v.y),
+    }
+    Rect2: Rect2::new(Vector2::ZERO, Vector2::ZERO), {}
+    Vector3: Vector3::ZERO, {
+        Value::Vector2(v) => Vector3::new(v.x, v.y, 0.0),
+    }
+    Transform2D: Transform2D::IDENTITY, {}
+    Plane: Plane::new(Vector3::ZERO, 0.0), {}
+    Quat: Quat::IDENTITY, {}
+    Aabb: Aabb::new(Vector3::ZERO, Vector3::ZERO), {}
+    Basis: Basis::IDENTITY, {
+        Value::Transform(v) => v.basis,
+    }
+    Transform: Transform::IDENTITY, {
+        Value::Basis(v) => Transform { basis: *v, origin: Vector3::ZERO },
+    }
+    Color: Color::from_rgba(0.0, 0.0, 0.0, 1.0), {}
+);
+
+pub(super) fn to_math<T: MathValue>(value: &Value) -> T {
+    T::from_value(value).unwrap_or_else(T::default_value)
+}
+
+/// Conversions between pool arrays, their elements and variants.
+pub(super) trait PoolValue: PoolElement + Clone {
+    fn pool(value: &Value) -> Option<&PoolArray<Self>>;
+    fn into_value(array: PoolArray<Self>) -> Value;
+    fn to_value(&self) -> Value;
+    fn from_value(value: &Value) -> Self;
+}
+
+macro_rules! impl_pool_value {
+    ($($T:ty: $Variant:ident, $Element:ident, |$value:ident| $from_value:expr;)*) => {
+        $(
+            impl PoolValue for $T {
+                fn pool(value: &Value) -> Option<&PoolArray<Self>> {
+                    match value {
+                        Value::$Variant(array) => Some(array),
+                        _ => None,
+                    }
+                }
+
+                fn into_value(array: PoolArray<Self>) -> Value {
+                    Value::$Variant(array)
+                }
+
+                #[allow(clippy::clone_on_copy)]
+                fn to_value(&self) -> Value {
+                    Value::$Element(self.clone().into())
+                }
+
+                fn from_value($value: &Value) -> Self {
+                    $from_value
+                }
+            }
+        )*
+    };
+}
+
+impl_pool_value!(
+    u8: ByteArray, Int, |value| to_int(value) as u8;
+    i32: Int32Array, Int, |value| to_int(value) as i32;
+    real: Float32Array, Real, |value| real::from_f64(to_real(value));
+    GodotString: StringArray, String, |value| string::godot(stringify(value));
+    Vector2: Vector2Array, Vector2, |value| to_math(value);
+    Vector3: Vector3Array, Vector3, |value| to_math(value);
+    Color: ColorArray, Color, |value| to_math(value);
+);
+
+/// Equivalent of the engine's pool array conversions, which also accept generic arrays.
+pub(super) fn to_pool<T: PoolValue>(value: &Value) -> PoolArray<T> {
+    if let Some(array) = T::pool(value) {
+        array.clone()
+    } else if let Value::Array(array) = value {
+        PoolArray::from_variant_array(array)
+    } else {
+        PoolArray::new()
+    }
+}
+
+/// Equivalent of the engine's array conversion, which also accepts pool arrays.
+pub(super) fn to_array(value: &Value) -> VariantArray {
+    fn from_pool<T: PoolValue>(array: &PoolArray<T>) -> VariantArray {
+        let result = VariantArray::new();
+        for element in array.read().iter() {
+            result.push(variant(element.to_value()));
+        }
+        result.into_shared()
+    }
+
+    match value {
+        Value::Array(array) => array.new_ref(),
+        Value::ByteArray(array) => from_pool(array),
+        Value::Int32Array(array) => from_pool(array),
+        Value::Float32Array(array) => from_pool(array),
+        Value::StringArray(array) => from_pool(array),
+        Value::Vector2Array(array) => from_pool(array),
+        Value::Vector3Array(array) => from_pool(array),
+        Value::ColorArray(array) => from_pool(array),
+        _ => VariantArray::new_shared(),
+    }
+}
+
+unsafe extern "C-unwind" fn godot_variant_as_bool(
+    self_: *const sys::godot_variant,
+) -> sys::godot_bool {
+    to_bool(value(self_))
+}
+
+unsafe extern "C-unwind" fn godot_variant_booleanize(
+    self_: *const sys::godot_variant,
+) -> sys::godot_bool {
+    to_bool(value(self_))
+}
+
+unsafe extern "C-unwind" fn godot_variant_as_uint(self_: *const sys::godot_variant) -> u64 {
+    to_int(value(self_)) as u64
+}
+
+unsafe extern "C-unwind" fn godot_variant_as_int(self_: *const sys::godot_variant) -> i64 {
+    to_int(value(self_))
+}
+
+unsafe extern "C-unwind" fn godot_variant_as_real(self_: *const sys::godot_variant) -> c_double {
+    to_real(value(self_))
+}
+
+unsafe extern "C-unwind" fn godot_variant_as_string(
+    self_: *const sys::godot_variant,
+) -> sys::godot_string {
+    match value(self_) {
+        Value::String(s) => leak(s.clone(), GodotString::sys),
+        other => string::new(stringify(other)),
+    }
+}
+
+unsafe extern "C-unwind" fn godot_variant_as_node_path(
+    self_: *const sys::godot_variant,
+) -> sys::godot_node_path {
+    match value(self_) {
+        Value::NodePath(path) => leak(path.new_ref(), NodePath::sys),
+        Value::String(path) => leak(NodePath::new(path), NodePath::sys),
+        _ => node_path::empty(),
+    }
+}
+
+unsafe extern "C-unwind" fn godot_variant_as_rid(
+    self_: *const sys::godot_variant,
+) -> sys::godot_rid {
+    match value(self_) {
+        Value::Rid(rid) => *rid.sys(),
+        _ => sys::godot_rid::default(),
+    }
+}
+
+unsafe extern "C-unwind" fn godot_variant_as_object(
+    self_: *const sys::godot_variant,
+) -> *mut sys::godot_object {
+    match value(self_) {
+        Value::Object(object) => *object,
+        _ => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C-unwind" fn godot_variant_as_dictionary(
+    self_: *const sys::godot_variant,
+) -> sys::godot_dictionary {
+    match value(self_) {
+        Value::Dictionary(dict) => leak(dict.new_ref(), Dictionary::sys),
+        _ => leak(Dictionary::new_shared(), Dictionary::sys),
+    }
+}
+
+unsafe extern "C-unwind" fn godot_variant_as_array(
+    self_: *const sys::godot_variant,
+) -> sys::godot_array {
+    leak(to_array(value(self_)), VariantArray::sys)
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Calls (unsupported without objects)
+
+unsafe extern "C-unwind" fn godot_variant_call(
+    _self: *mut sys::godot_variant,
+    _method: *const sys::godot_string,
+    _args: *mut *const sys::godot_variant,
+    _argcount: sys::godot_int,
+    r_error: *mut sys::godot_variant_call_error,
+) -> sys::godot_variant {
+    if !r_error.is_null() {
+        (*r_error).error =
+            sys::godot_variant_call_error_error_GODOT_CALL_ERROR_CALL_ERROR_INVALID_METHOD;
+    }
+    new(Value::Nil)
+}
+
+unsafe extern "C-unwind" fn godot_variant_has_method(
+    _self: *const sys::godot_variant,
+    _method: *const sys::godot_string,
+) -> sys::godot_bool {
+    false
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Comparison
+
+/// Equivalent of `Variant::operator==`. Numbers compare across `int` and `float`, arrays by
+/// their elements and dictionaries by identity, like in Godot 3.
+pub(super) fn equals(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Nil, Value::Nil) => true,
+        (Value::Bool(a), Value::Bool(b)) => a == b,
+        (Value::Int(a), Value::Int(b)) => a == b,
+        (Value::Int(_) | Value::Real(_), Value::Int(_) | Value::Real(_)) => {
+            to_real(a) == to_real(b)
+        }
+        (Value::String(a), Value::String(b)) => a == b,
+        (Value::Vector2(a), Value::Vector2(b)) => a == b,
+        (Value::Rect2(a), Value::Rect2(b)) => a == b,
+        (Value::Vector3(a), Value::Vector3(b)) => a == b,
+        (Value::Transform2D(a), Value::Transform2D(b)) => a == b,
+        (Value::Plane(a), Value::Plane(b)) => a == b,
+        (Value::Quat(a), Value::Quat(b)) => a == b,
+        (Value::Aabb(a), Value::Aabb(b)) => a == b,
+        (Value::Basis(a), Value::Basis(b)) => a == b,
+        (Value::Transform(a), Value::Transform(b)) => a == b,
+        (Value::Color(a), Value::Color(b)) => a == b,
+        (Value::NodePath(a), Value::NodePath(b)) => a == b,
+        (Value::Rid(a), Value::Rid(b)) => unsafe { rid_bits(a.sys()) == rid_bits(b.sys()) },
+        (Value::Object(a), Value::Object(b)) => a == b,
+        (Value::Dictionary(a), Value::Dictionary(b)) => unsafe {
+            dictionary::same(a.sys(), b.sys())
+        },
+        (Value::Array(a), Value::Array(b)) => unsafe { array::equals(a.sys(), b.sys(), equals) },
+        (Value::ByteArray(a), Value::ByteArray(b)) => pool_equals(a, b),
+        (Value::Int32Array(a), Value::Int32Array(b)) => pool_equals(a, b),
+        (Value::Float32Array(a), Value::Float32Array(b)) => pool_equals(a, b),
+        (Value::StringArray(a), Value::StringArray(b)) => pool_equals(a, b),
+        (Value::Vector2Array(a), Value::Vector2Array(b)) => pool_equals(a, b),
+        (Value::Vector3Array(a), Value::Vector3Array(b)) => pool_equals(a, b),
+        (Value::ColorArray(a), Value::ColorArray(b)) => pool_equals(a, b),
+        _ => false,
+    }
+}
+
+fn pool_equals<T: PoolElement + PartialEq>(a: &PoolArray<T>, b: &PoolArray<T>) -> bool {
+    *a.read() == *b.read()
+}
+
+/// Equivalent of `Variant::hash_compare`, used for dictionary keys: types must match exactly,
+/// and `NaN` is equal to itself.
+pub(super) fn key_equals(a: &Value, b: &Value) -> bool {
+    if a.get_type() != b.get_type() {
+        return false;
+    }
+    match (a, b) {
+        (Value::Real(a), Value::Real(b)) => a == b || (a.is_nan() && b.is_nan()),
+        (Value::Array(a), Value::Array(b)) => unsafe {
+            array::equals(a.sys(), b.sys(), key_equals)
+        },
+        _ => equals(a, b),
+    }
+}
+
+pub(super) unsafe fn rid_bits(rid: *const sys::godot_rid) -> usize {
+    load::<_, ()>(rid) as usize
+}
+
+/// Total order used for sorting and `Variant::operator<`: values of different types are ordered
+/// by type, and types without an order in the engine compare as equal.
+pub(super) fn compare(a: &Value, b: &Value) -> Ordering {
+    fn vector2(a: &Vector2, b: &Vector2) -> Ordering {
+        a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y))
+    }
+    fn vector3(a: &Vector3, b: &Vector3) -> Ordering {
+        a.x.total_cmp(&b.x)
+            .then(a.y.total_cmp(&b.y))
+            .then(a.z.total_cmp(&b.z))
+    }
+
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Int(a), Value::Int(b)) => a.cmp(b),
+        (Value::Real(a), Value::Real(b)) => a.total_cmp(b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Vector2(a), Value::Vector2(b)) => vector2(a, b),
+        (Value::Rect2(a), Value::Rect2(b)) => {
+            vector2(&a.position, &b.position).then(vector2(&a.size, &b.size))
+        }
+        (Value::Vector3(a), Value::Vector3(b)) => vector3(a, b),
+        (Value::Rid(a), Value::Rid(b)) => unsafe { rid_bits(a.sys()).cmp(&rid_bits(b.sys())) },
+        (Value::Object(a), Value::Object(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => unsafe { array::compare(a.sys(), b.sys()) },
+        _ => (a.get_type() as u32).cmp(&(b.get_type() as u32)),
+    }
+}
+
+unsafe extern "C-unwind" fn godot_variant_operator_equal(
+    self_: *const sys::godot_variant,
+    other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    equals(value(self_), value(other))
+}
+
+unsafe extern "C-unwind" fn godot_variant_operator_less(
+    self_: *const sys::godot_variant,
+    other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    compare(value(self_), value(other)) == Ordering::Less
+}
+
+unsafe extern "C-unwind" fn godot_variant_hash_compare(
+    self_: *const sys::godot_variant,
+    other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    key_equals(value(self_), value(other))
+}
+
+/// Equivalent of `Variant::hash`. Values that are equal as dictionary keys have equal hashes.
+pub(super) fn hash(value: &Value) -> u32 {
+    fn combine(hash: u32, value: u32) -> u32 {
+        hash.wrapping_mul(33).wrapping_add(value)
+    }
+    fn floats<R: RealConv>(values: &[R]) -> u32 {
+        values
+            .iter()
+            .fold(5381, |hash, v| combine(hash, float_bits(v.as_f64())))
+    }
+    fn float_bits(v: f64) -> u32 {
+        let bits = if v == 0.0 {
+            0
+        } else if v.is_nan() {
+            f64::NAN.to_bits()
+        } else {
+            v.to_bits()
+        };
+        (bits ^ (bits >> 32)) as u32
+    }
+
+    match value {
+        Value::Nil => 0,
+        Value::Bool(v) => *v as u32,
+        Value::Int(v) => (*v ^ (*v >> 32)) as u32,
+        Value::Real(v) => float_bits(*v),
+        Value::String(v) => string::hash_units(&string::encode_wide(&v.to_string())),
+        Value::Vector2(v) => floats(&[v.x, v.y]),
+        Value::Rect2(v) => floats(&[v.position.x, v.position.y, v.size.x, v.size.y]),
+        Value::Vector3(v) => floats(&[v.x, v.y, v.z]),
+        Value::Quat(v) => floats(&[v.x, v.y, v.z, v.w]),
+        Value::Color(v) => floats(&[v.r, v.g, v.b, v.a]),
+        Value::Rid(v) => unsafe { rid_bits(v.sys()) as u32 },
+        Value::Object(v) => *v as usize as u32,
+        Value::Dictionary(v) => unsafe { load::<_, ()>(v.sys()) as usize as u32 },
+        Value::Array(v) => v
+            .iter()
+            .fold(5381, |hash, v| combine(hash, self::hash(value_of(&v)))),
+        other => string::hash_units(&string::encode_wide(&stringify(other))),
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Operators
+
+fn arithmetic(op: VariantOperator, a: &Value, b: &Value) -> Option<Value> {
+    use VariantOperator as Op;
+
+    let value = match (a, b) {
+        (Value::Int(x), Value::Int(y)) => Value::Int(match op {
+            Op::Add => x.wrapping_add(*y),
+            Op::Subtract => x.wrapping_sub(*y),
+            Op::Multiply => x.wrapping_mul(*y),
+            Op::Divide if *y != 0 => x.wrapping_div(*y),
+            Op::Module if *y != 0 => x.wrapping_rem(*y),
+            Op::ShiftLeft => x.wrapping_shl(*y as u32),
+            Op::ShiftRight => x.wrapping_shr(*y as u32),
+            Op::BitAnd => x & y,
+            Op::BitOr => x | y,
+            Op::BitXor => x ^ y,
+            _ => return None,
+        }),
+        (Value::Int(_) | Value::Real(_), Value::Int(_) | Value::Real(_)) => {
+            let (x, y) = (to_real(a), to_real(b));
+            Value::Real(match op {
+                Op::Add => x + y,
+                Op::Subtract => x - y,
+                Op::Multiply => x * y,
+                Op::Divide => x / y,
+                Op::Module => x % y,
+                _ => return None,
+            })
+        }
+        (Value::String(x), Value::String(y)) if op == Op::Add => {
+            Value::String(string::godot(format!("{x}{y}")))
+        }
+        (Value::Vector2(x), Value::Vector2(y)) => Value::Vector2(match op {
+            Op::Add => *x + *y,
+            Op::Subtract => *x - *y,
+            Op::Multiply => *x * *y,
+            Op::Divide => *x / *y,
+            _ => return None,
+        }),
+        (Value::Vector2(x), Value::Int(_) | Value::Real(_)) => {
+            let y = real::from_f64(to_real(b));
+            Value::Vector2(match op {
+                Op::Multiply => *x * y,
+                Op::Divide => *x / y,
+                _ => return None,
+            })
+        }
+        (Value::Int(_) | Value::Real(_), Value::Vector2(y)) if op == Op::Multiply => {
+            Value::Vector2(*y * real::from_f64(to_real(a)))
+        }
+        (Value::Vector3(x), Value::Vector3(y)) => Value::Vector3(match op {
+            Op::Add => *x + *y,
+            Op::Subtract => *x - *y,
+            Op::Multiply => *x * *y,
+            Op::Divide => *x / *y,
+            _ => return None,
+        }),
+        (Value::Vector3(x), Value::Int(_) | Value::Real(_)) => {
+            let y = real::from_f64(to_real(b));
+            Value::Vector3(match op {
+                Op::Multiply => *x * y,
+                Op::Divide => *x / y,
+                _ => return None,
+            })
+        }
+        (Value::Int(_) | Value::Real(_), Value::Vector3(y)) if op == Op::Multiply => {
+            Value::Vector3(*y * real::from_f64(to_real(a)))
+        }
+        (Value::Color(x), Value::Color(y)) => {
+            let f: fn(f32, f32) -> f32 = match op {
+                Op::Add => |x, y| x + y,
+                Op::Subtract => |x, y| x - y,
+                Op::Multiply => |x, y| x * y,
+                Op::Divide => |x, y| x / y,
+                _ => return None,
+            };
+            Value::Color(Color::from_rgba(
+                f(x.r, y.r),
+                f(x.g, y.g),
+                f(x.b, y.b),
+                f(x.a, y.a),
+            ))
+        }
+        (Value::Array(x), Value::Array(y)) if op == Op::Add => {
+            let result = VariantArray::new();
+            for v in x.iter().chain(y.iter()) {
+                result.push(v);
+            }
+            Value::Array(result.into_shared())
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn contains(container: &Value, item: &Value) -> Option<bool> {
+    let found = match container {
+        Value::String(s) => match item {
+            Value::String(item) => s.to_string().contains(&item.to_string()),
+            _ => return None,
+        },
+        Value::Array(array) => array.iter().any(|v| equals(value_of(&v), item)),
+        Value::Dictionary(dict) => unsafe { dictionary::find(dict.sys(), item).is_some() },
+        _ => {
+            let array = to_array(container);
+            if array.is_empty() && !to_bool(container) && !matches!(container, Value::Array(_)) {
+                if T_POOL_TYPES.contains(&container.get_type()) {
+                    false
+                } else {
+                    return None;
+                }
+            } else {
+                array.iter().any(|v| equals(value_of(&v), item))
+            }
+        }
+    };
+    Some(found)
+}
+
+const T_POOL_TYPES: [VariantType; 7] = [
+    VariantType::ByteArray,
+    VariantType::Int32Array,
+    VariantType::Float32Array,
+    VariantType::StringArray,
+    VariantType::Vector2Array,
+    VariantType::Vector3Array,
+    VariantType::ColorArray,
+];
+
+/// Equivalent of `Variant::evaluate`. Returns `None` for invalid operands.
+pub(super) fn evaluate(op: VariantOperator, a: &Value, b: &Value) -> Option<Value> {
+    use VariantOperator as Op;
+
+    let comparable = a.get_type() == b.get_type()
+        || (a.is_number() && b.is_number())
+        || matches!(a, Value::Nil)
+        || matches!(b, Value::Nil);
+
+    let result = match op {
+        Op::Equal | Op::NotEqual => {
+            if !comparable {
+                return None;
+            }
+            Value::Bool(equals(a, b) == (op == Op::Equal))
+        }
+        Op::Less | Op::LessEqual | Op::Greater | Op::GreaterEqual => {
+            let ordered = (a.get_type() == b.get_type()
+                && matches!(
+                    a,
+                    Value::Bool(_)
+                        | Value::Int(_)
+                        | Value::Real(_)
+                        | Value::String(_)
+                        | Value::Vector2(_)
+                        | Value::Vector3(_)
+                        | Value::Rid(_)
+                        | Value::Object(_)
+                        | Value::Array(_)
+                ))
+                || (a.is_number() && b.is_number());
+            if !ordered {
+                return None;
+            }
+            let ordering = if a.is_number() && b.is_number() && a.get_type() != b.get_type() {
+                to_real(a).partial_cmp(&to_real(b))?
+            } else {
+                compare(a, b)
+            };
+            Value::Bool(match op {
+                Op::Less => ordering == Ordering::Less,
+                Op::LessEqual => ordering != Ordering::Greater,
+                Op::Greater => ordering == Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            })
+        }
+        Op::Negate | Op::Positive => {
+            let negate = op == Op::Negate;
+            match a {
+                Value::Int(v) => Value::Int(if negate { v.wrapping_neg() } else { *v }),
+                Value::Real(v) => Value::Real(if negate { -v } else { *v }),
+                Value::Vector2(v) => Value::Vector2(if negate { -*v } else { *v }),
+                Value::Vector3(v) => Value::Vector3(if negate { -*v } else { *v }),
+                _ => return None,
+            }
+        }
+        Op::BitNegate => match a {
+            Value::Int(v) => Value::Int(!v),
+            _ => return None,
+        },
+        Op::And => Value::Bool(to_bool(a) && to_bool(b)),
+        Op::Or => Value::Bool(to_bool(a) || to_bool(b)),
+        Op::Xor => Value::Bool(to_bool(a) != to_bool(b)),
+        Op::Not => Value::Bool(!to_bool(a)),
+        Op::In => Value::Bool(contains(b, a)?),
+        Op::StringConcat => Value::String(string::godot(stringify(a) + &stringify(b))),
+        _ => arithmetic(op, a, b)?,
+    };
+    Some(result)
+}
+
+unsafe extern "C-unwind" fn godot_variant_evaluate(
+    op: sys::godot_variant_operator,
+    a: *const sys::godot_variant,
+    b: *const sys::godot_variant,
+    r_ret: *mut sys::godot_variant,
+    r_valid: *mut sys::godot_bool,
+) {
+    let result = VariantOperator::try_from_sys(op).and_then(|op| evaluate(op, value(a), value(b)));
+    *r_valid = result.is_some();
+    *r_ret = new(result.unwrap_or(Value::Nil));
+}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Printing
+
+/// Equivalent of `String::num` without a fixed number of decimals, also used for `rtos`.
+pub(super) fn num(value: f64) -> String {
+    if value.is_nan() {
+        return "nan".into();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "inf" } else { "-inf" }.into();
+    }
+    let formatted = format!("{value:.6}");
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn vector2_string(v: &Vector2) -> String {
+    format!("{}, {}", num(v.x.as_f64()), num(v.y.as_f64()))
+}
+
+fn vector3_string(v: &Vector3) -> String {
+    format!(
+        "{}, {}, {}",
+        num(v.x.as_f64()),
+        num(v.y.as_f64()),
+        num(v.z.as_f64())
+    )
+}
+
+fn join<T>(items: impl Iterator<Item = T>, f: impl Fn(T) -> String) -> String {
+    items.map(f).collect::<Vec<_>>().join(", ")
+}
+
+/// Equivalent of `Variant::operator String`.
+pub(super) fn stringify(value: &Value) -> String {
+    match value {
+        Value::Nil => "Null".into(),
+        Value::Bool(v) => if *v { "True" } else { "False" }.into(),
+        Value::Int(v) => v.to_string(),
+        Value::Real(v) => num(*v),
+        Value::String(v) => v.to_string(),
+        Value::Vector2(v) => format!("({})", vector2_string(v)),
+        Value::Rect2(v) => format!(
+            "({}, {})",
+            vector2_string(&v.position),
+            vector2_string(&v.size)
+        ),
+        Value::Vector3(v) => format!("({})", vector3_string(v)),
+        Value::Transform2D(v) => format!(
+            "(({}), ({}), ({}))",
+            vector2_string(&v.a),
+            vector2_string(&v.b),
+            vector2_string(&v.origin)
+        ),
+        Value::Plane(v) => format!("{}, {}", vector3_string(&v.normal), num(v.d.as_f64())),
+        Value::Quat(v) => format!(
+            "({}, {}, {}, {})",
+            num(v.x.as_f64()),
+            num(v.y.as_f64()),
+            num(v.z.as_f64()),
+            num(v.w.as_f64())
+        ),
+        Value::Aabb(v) => format!(
+            "{} - {}",
+            vector3_string(&v.position),
+            vector3_string(&v.size)
+        ),
+        Value::Basis(v) => format!(
+            "({})",
+            join(v.elements.iter(), |row| format!(
+                "({})",
+                vector3_string(row)
+            ))
+        ),
+        Value::Transform(v) => format!(
+            "{} - {}",
+            join(v.basis.elements.iter(), vector3_string),
+            vector3_string(&v.origin)
+        ),
+        Value::Color(v) => format!(
+            "{},{},{},{}",
+            num(v.r.as_f64()),
+            num(v.g.as_f64()),
+            num(v.b.as_f64()),
+            num(v.a.as_f64())
+        ),
+        Value::NodePath(v) => v.to_string(),
+        Value::Object(v) if v.is_null() => "[Object:null]".into(),
+        Value::Object(_) => "[Object]".into(),
+        Value::Dictionary(dict) => {
+            let mut pairs: Vec<(String, String)> = dict
+                .iter()
+                .map(|(k, v)| (stringify(value_of(&k)), stringify(value_of(&v))))
+                .collect();
+            pairs.sort();
+            format!("{{{}}}", join(pairs.iter(), |(k, v)| format!("{k}:{v}")))
+        }
+        Value::Array(array) => format!("[{}]", join(array.iter(), |v| stringify(value_of(&v)))),
+        Value::Int32Array(array) => format!("[{}]", join(array.read().iter(), i32::to_string)),
+        Value::Float32Array(array) => {
+            format!("[{}]", join(array.read().iter(), |v| num(v.as_f64())))
+        }
+        Value::StringArray(array) => {
+            format!("[{}]", join(array.read().iter(), GodotString::to_string))
+        }
+        Value::Vector2Array(array) => format!(
+            "[{}]",
+            join(array.read().iter(), |v| format!("({})", vector2_string(v)))
+        ),
+        Value::Vector3Array(array) => format!(
+            "[{}]",
+            join(array.read().iter(), |v| format!("({})", vector3_string(v)))
+        ),
+        Value::Rid(_) => "[RID]".into(),
+        Value::ByteArray(_) => "[PoolByteArray]".into(),
+        Value::ColorArray(_) => "[PoolColorArray]".into(),
+    }
+}
+
+/// Equivalent of `Variant::operator String` for a `Variant`.
+pub(super) fn to_string(v: &Variant) -> String {
+    stringify(value_of(v))
+}
+
+/// Equivalent of `JSON::print` without indentation, with sorted keys.
+pub(super) fn json(value: &Value) -> String {
+    match value {
+        Value::Nil => "null".into(),
+        Value::Bool(v) => v.to_string(),
+        Value::Int(v) => v.to_string(),
+        Value::Real(v) => num(*v),
+        Value::Array(_) | Value::Int32Array(_) | Value::Float32Array(_) | Value::StringArray(_) => {
+            let array = to_array(value);
+            let items: Vec<String> = array.iter().map(|v| json(value_of(&v))).collect();
+            format!("[{}]", items.join(","))
+        }
+        Value::Dictionary(dict) => {
+            let mut entries: Vec<(Variant, Variant)> = dict.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| compare(value_of(a), value_of(b)));
+            let items: Vec<String> = entries
+                .iter()
+                .map(|(k, v)| {
+                    let key = Value::String(string::godot(stringify(value_of(k))));
+                    format!("{}:{}", json(&key), json(value_of(v)))
+                })
+                .collect();
+            format!("{{{}}}", items.join(","))
+        }
+        other => format!("\"{}\"", string::json_escape(&stringify(other))),
+    }
+}
@@ -87,6 +87,27 @@ impl<T: GodotObject> RawObject<T> {
         string.to_string()
     }
 
+    /// Returns the instance ID of this object using `Object::get_instance_id`.
+    #[inline]
+    pub fn instance_id(&self) -> i64 {
+        let api = crate::private::get_api();
+        let get_instance_id_method = crate::private::ObjectMethodTable::get(api).get_instance_id;
+        let mut argument_buffer = [ptr::null() as *const libc::c_void; 0];
+        let mut instance_id: i64 = 0;
+        let ret_ptr = &mut instance_id as *mut i64;
+
+        unsafe {
+            (api.godot_method_bind_ptrcall)(
+                get_instance_id_method,
+                self.sys().as_ptr(),
+                argument_buffer.as_mut_ptr() as *mut _,
+                ret_ptr as *mut _,
+            );
+        }
+
+        instance_id
+    }
+
     /// Attempt to cast a Godot object to a different class type.
     #[inline]
     pub fn cast<U>(&self) -> Option<&RawObject<U>>
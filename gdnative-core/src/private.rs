@@ -39,6 +39,16 @@ pub unsafe fn bind_api(options: *mut sys::godot_gdnative_init_options) -> bool {
     true
 }
 
+/// Binds an API struct that was constructed without the engine.
+///
+/// # Safety
+///
+/// Must not be called while other threads access the API.
+#[cfg(feature = "mock-api")]
+pub(crate) unsafe fn bind_mock_api(api: sys::GodotApi) {
+    GODOT_API = Some(api);
+}
+
 unsafe fn check_api_compatibility(
     options: *const sys::godot_gdnative_init_options,
 ) -> Result<(), sys::InitError> {
@@ -152,6 +162,7 @@ pub fn get_gdnative_library_sys() -> *mut sys::godot_object {
 pub unsafe fn cleanup_internal_state() {
     crate::export::type_tag::cleanup();
     crate::export::class_registry::cleanup();
+    crate::core_types::cleanup_names();
 
     GODOT_API = None;
 }
@@ -208,6 +219,40 @@ pub(crate) fn print_panic_error(err: Box<dyn std::any::Any + Send>) {
     }
 }
 
+/// Lazily interned `StringName` to be used by the `string_name!` macro.
+pub struct StaticStringName {
+    name: &'static str,
+    cell: once_cell::sync::OnceCell<crate::core_types::StringName>,
+}
+
+impl StaticStringName {
+    #[inline]
+    pub const fn new(name: &'static str) -> Self {
+        StaticStringName {
+            name,
+            cell: once_cell::sync::OnceCell::new(),
+        }
+    }
+
+    #[inline]
+    pub fn get(&'static self) -> &'static crate::core_types::StringName {
+        self.cell
+            .get_or_init(|| crate::core_types::StringName::from_str(self.name))
+    }
+}
+
+/// Fails const evaluation for malformed paths, to be used by the `node_path!` macro.
+#[inline]
+pub const fn assert_valid_node_path(path: &str) {
+    use crate::core_types::{NodePathBuf, NodePathError};
+
+    match NodePathBuf::validate(path) {
+        Ok(()) => {}
+        Err(NodePathError::EmptyName { .. }) => panic!("invalid node path: empty node name"),
+        Err(NodePathError::EmptySubname { .. }) => panic!("invalid node path: empty subname"),
+    }
+}
+
 /// Plugin type to be used by macros for auto class registration.
 pub struct AutoInitPlugin {
     pub f: fn(init_handle: crate::init::InitHandle),
@@ -310,6 +355,7 @@ macro_rules! make_method_table {
 
 make_method_table!(struct ObjectMethodTable for Object {
     get_class,
+    get_instance_id,
     is_class,
 });
 
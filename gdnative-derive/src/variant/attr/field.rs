@@ -13,6 +13,9 @@ pub struct FieldAttr {
     pub skip_from_variant: bool,
     pub to_variant_with: Option<syn::Path>,
     pub from_variant_with: Option<syn::Path>,
+    pub rename: Option<syn::LitStr>,
+    pub default: Option<syn::Path>,
+    pub flatten: bool,
 }
 
 impl FieldAttr {
@@ -30,6 +33,9 @@ pub struct FieldAttrBuilder {
     skip_from_variant: bool,
     to_variant_with: Option<syn::Path>,
     from_variant_with: Option<syn::Path>,
+    rename: Option<syn::LitStr>,
+    default: Option<syn::Path>,
+    flatten: bool,
     errors: Vec<syn::Error>,
 }
 
@@ -77,16 +83,20 @@ impl FieldAttrBuilder {
             match name.as_str() {
                 skip_to_variant,
                 skip_from_variant,
+                flatten,
             }
         }
 
-        #[allow(clippy::single_match)]
         match name.as_str() {
             "skip" => {
                 self.skip_to_variant = true;
                 self.skip_from_variant = true;
                 return Ok(());
             }
+            "default" => {
+                let path = parse_quote_spanned!(flag.span()=> ::std::default::Default::default);
+                return self.set_default(flag.span(), path);
+            }
             _ => {}
         }
 
@@ -96,6 +106,13 @@ impl FieldAttrBuilder {
         ))
     }
 
+    fn set_default(&mut self, span: Span, path: syn::Path) -> Result<(), syn::Error> {
+        if self.default.replace(path).is_some() {
+            return Err(syn::Error::new(span, "the argument default is already set"));
+        }
+        Ok(())
+    }
+
     fn set_pair(&mut self, pair: &syn::MetaNameValue) {
         let err = self.try_set_pair(pair).err();
         self.errors.extend(err);
@@ -105,8 +122,7 @@ impl FieldAttrBuilder {
     fn try_set_pair(&mut self, pair: &syn::MetaNameValue) -> Result<(), syn::Error> {
         let syn::MetaNameValue { path, lit, .. } = pair;
 
-        const VALID_KEYS: &str =
-            "to_variant_with, from_variant_with, with, skip_to_variant, skip_from_variant, skip";
+        const VALID_KEYS: &str = "to_variant_with, from_variant_with, with, skip_to_variant, skip_from_variant, skip, rename, default, flatten";
 
         let name = path
             .get_ident()
@@ -172,6 +188,39 @@ impl FieldAttrBuilder {
 
                 return Ok(());
             }
+            "rename" => {
+                let name = match lit {
+                    syn::Lit::Str(lit_str) => lit_str.clone(),
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "expecting a name in double quotes: #[variant(rename = \"name\")]",
+                        ))
+                    }
+                };
+
+                if self.rename.replace(name).is_some() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "the argument rename is already set",
+                    ));
+                }
+
+                return Ok(());
+            }
+            "default" => {
+                let path = match lit {
+                    syn::Lit::Str(lit_str) => lit_str.parse::<syn::Path>()?,
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "expecting a path to a function in double quotes: #[variant(default = \"path::to::func\")]",
+                        ))
+                    }
+                };
+
+                return self.set_default(lit.span(), path);
+            }
             _ => {}
         }
 
@@ -198,12 +247,30 @@ impl FromIterator<syn::Meta> for FieldAttrBuilder {
 impl AttrBuilder for FieldAttrBuilder {
     type Attr = FieldAttr;
     fn done(mut self) -> Result<FieldAttr, syn::Error> {
+        if self.flatten {
+            if let Some(rename) = &self.rename {
+                self.errors.push(syn::Error::new(
+                    rename.span(),
+                    "`rename` cannot be used on flattened fields",
+                ));
+            }
+            if let Some(default) = &self.default {
+                self.errors.push(syn::Error::new(
+                    default.span(),
+                    "`default` cannot be used on flattened fields",
+                ));
+            }
+        }
+
         if self.errors.is_empty() {
             Ok(FieldAttr {
                 skip_to_variant: self.skip_to_variant,
                 skip_from_variant: self.skip_from_variant,
                 to_variant_with: self.to_variant_with,
                 from_variant_with: self.from_variant_with,
+                rename: self.rename,
+                default: self.default,
+                flatten: self.flatten,
             })
         } else {
             let first_error = self.errors.remove(0);
@@ -3,18 +3,23 @@ use std::iter::FromIterator;
 use proc_macro2::Span;
 use syn::spanned::Spanned;
 
-use crate::variant::{attr::generate_error_with_docs, repr::EnumReprKind};
+use crate::variant::{attr::generate_error_with_docs, rename::RenameRule, repr::EnumReprKind};
 
 use super::AttrBuilder;
 
 #[derive(Clone, Debug)]
 pub struct ItemAttr {
     pub enum_repr_kind: Option<(EnumReprKind, Span)>,
+    pub rename_all: Option<RenameRule>,
 }
 
 #[derive(Debug, Default)]
 pub struct ItemAttrBuilder {
     enum_repr_kind: Option<syn::Ident>,
+    rename_all: Option<syn::LitStr>,
+    tag: Option<syn::LitStr>,
+    content: Option<syn::LitStr>,
+    untagged: Option<Span>,
 
     errors: Vec<syn::Error>,
 }
@@ -44,6 +49,17 @@ impl ItemAttrBuilder {
     }
 
     fn try_set_flag(&mut self, flag: &syn::Path) -> Result<(), syn::Error> {
+        if flag.is_ident("untagged") {
+            if self.untagged.replace(flag.span()).is_some() {
+                return Err(syn::Error::new(
+                    flag.span(),
+                    "the argument untagged is already set",
+                ));
+            }
+
+            return Ok(());
+        }
+
         Err(generate_error_with_docs(
             flag.span(),
             "Unknown flag, or missing macro arguments",
@@ -59,7 +75,7 @@ impl ItemAttrBuilder {
     fn try_set_pair(&mut self, pair: &syn::MetaNameValue) -> Result<(), syn::Error> {
         let syn::MetaNameValue { path, lit, .. } = pair;
 
-        const VALID_KEYS: &str = "enum";
+        const VALID_KEYS: &str = "enum, rename_all, tag, content, untagged";
 
         let name = path
             .get_ident()
@@ -88,10 +104,31 @@ impl ItemAttrBuilder {
             }
         }
 
-        Err(syn::Error::new(
-            path.span(),
-            format!("unknown argument, expected one of:\n\t{VALID_KEYS}"),
-        ))
+        let slot = match name.as_str() {
+            "rename_all" => &mut self.rename_all,
+            "tag" => &mut self.tag,
+            "content" => &mut self.content,
+            _ => {
+                return Err(syn::Error::new(
+                    path.span(),
+                    format!("unknown argument, expected one of:\n\t{VALID_KEYS}"),
+                ))
+            }
+        };
+
+        let value = match lit {
+            syn::Lit::Str(lit_str) => lit_str.clone(),
+            _ => return Err(syn::Error::new(lit.span(), "expected string literal")),
+        };
+
+        if slot.replace(value).is_some() {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("the argument {name} is already set"),
+            ));
+        }
+
+        Ok(())
     }
 }
 
@@ -124,7 +161,67 @@ impl AttrBuilder for ItemAttrBuilder {
                 })
                 .transpose()?;
 
-            Ok(ItemAttr { enum_repr_kind })
+            let enum_repr_kind = match (enum_repr_kind, self.tag, self.content, self.untagged) {
+                (kind, None, None, None) => kind,
+                (Some((_, span)), _, _, _) => {
+                    return Err(syn::Error::new(
+                        span,
+                        "`enum` cannot be combined with `tag`, `content` or `untagged`",
+                    ))
+                }
+                (None, None, None, Some(span)) => Some((EnumReprKind::Untagged, span)),
+                (None, _, _, Some(span)) => {
+                    return Err(syn::Error::new(
+                        span,
+                        "`untagged` cannot be combined with `tag` or `content`",
+                    ))
+                }
+                (None, None, Some(content), None) => {
+                    return Err(syn::Error::new(
+                        content.span(),
+                        "`content` can only be used together with `tag`",
+                    ))
+                }
+                (None, Some(tag), None, None) => {
+                    Some((EnumReprKind::Internal { tag: tag.value() }, tag.span()))
+                }
+                (None, Some(tag), Some(content), None) => {
+                    if tag.value() == content.value() {
+                        return Err(syn::Error::new(
+                            content.span(),
+                            "`tag` and `content` must be different keys",
+                        ));
+                    }
+
+                    Some((
+                        EnumReprKind::Adjacent {
+                            tag: tag.value(),
+                            content: content.value(),
+                        },
+                        tag.span(),
+                    ))
+                }
+            };
+
+            let rename_all = self
+                .rename_all
+                .map(|rule| {
+                    RenameRule::parse(&rule.value()).ok_or_else(|| {
+                        syn::Error::new(
+                            rule.span(),
+                            format!(
+                                "unknown rename rule, expected values: {}",
+                                RenameRule::VALID_VALUES
+                            ),
+                        )
+                    })
+                })
+                .transpose()?;
+
+            Ok(ItemAttr {
+                enum_repr_kind,
+                rename_all,
+            })
         } else {
             let first_error = self.errors.remove(0);
             let errors = self
@@ -4,7 +4,7 @@ use crate::variant::bounds;
 use crate::variant::repr::VariantRepr;
 use syn::Ident;
 
-use super::repr::{EnumRepr, EnumReprKind, Repr, StructRepr};
+use super::repr::{EnumRepr, EnumReprKind, EnumVariant, Repr, StructRepr};
 use super::DeriveData;
 
 pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream2, syn::Error> {
@@ -37,30 +37,32 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
             primitive_repr,
         }) => match kind {
             EnumReprKind::External => expand_external(&ident, &input_ident, variants)?,
+            EnumReprKind::Internal { tag } => {
+                expand_tagged(&ident, &input_ident, variants, &tag, None)?
+            }
+            EnumReprKind::Adjacent { tag, content } => {
+                expand_tagged(&ident, &input_ident, variants, &tag, Some(&content))?
+            }
+            EnumReprKind::Untagged => expand_untagged(&ident, &input_ident, variants)?,
             EnumReprKind::Str => {
-                if let Some((var_ident, _)) = variants
+                if let Some(variant) = variants
                     .iter()
-                    .find(|(_, var_repr)| !matches!(var_repr, VariantRepr::Unit(_)))
+                    .find(|variant| !matches!(variant.repr, VariantRepr::Unit(_)))
                 {
                     return Err(syn::Error::new(
-                        var_ident.span(),
+                        variant.ident.span(),
                         "`str` representation can only be used for fieldless enums",
                     ));
                 }
 
-                let var_ident_strings: Vec<String> = variants
-                    .iter()
-                    .map(|(var_ident, _)| format!("{var_ident}"))
-                    .collect();
-
-                let var_ident_string_literals = var_ident_strings
+                let var_ident_string_literals = variants
                     .iter()
-                    .map(|string| Literal::string(string))
+                    .map(|variant| Literal::string(&variant.name))
                     .collect::<Vec<_>>();
 
                 let ref_var_ident_string_literals = &var_ident_string_literals;
 
-                let variant_idents = variants.iter().map(|(var_ident, _)| var_ident);
+                let variant_idents = variants.iter().map(|variant| &variant.ident);
 
                 let early_return = variants.is_empty().then(|| {
                     quote! {
@@ -100,7 +102,12 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
                 let mut clauses = Vec::new();
                 let mut hints = Vec::new();
                 let mut discriminant = quote! { 0 };
-                for (var_ident, var_repr) in variants.iter() {
+                for EnumVariant {
+                    ident: var_ident,
+                    repr: var_repr,
+                    ..
+                } in variants.iter()
+                {
                     if let VariantRepr::Unit(expr) = var_repr {
                         if let Some(expr) = expr {
                             discriminant = quote!(#expr);
@@ -163,26 +170,24 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
 fn expand_external(
     ident: &syn::Ident,
     input_ident: &syn::Ident,
-    variants: Vec<(Ident, super::repr::VariantRepr)>,
+    variants: Vec<EnumVariant>,
 ) -> Result<TokenStream2, syn::Error> {
     let var_input_ident = Ident::new("__enum_variant", Span::call_site());
 
-    let var_ident_strings: Vec<String> = variants
-        .iter()
-        .map(|(var_ident, _)| format!("{var_ident}"))
-        .collect();
-
-    let var_ident_string_literals = var_ident_strings
+    let var_ident_string_literals = variants
         .iter()
-        .map(|string| Literal::string(string))
+        .map(|variant| Literal::string(&variant.name))
         .collect::<Vec<_>>();
 
     let ref_var_ident_string_literals = &var_ident_string_literals;
 
     let var_from_variants = variants
         .iter()
-        .map(|(var_ident, var_repr)| {
-            var_repr.make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident })
+        .map(|variant| {
+            let var_ident = &variant.ident;
+            variant
+                .repr
+                .make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident })
         })
         .collect::<Result<Vec<_>, _>>()?;
 
@@ -238,3 +243,177 @@ fn expand_external(
         }
     })
 }
+
+/// Expands internally tagged enums if `content` is `None`, and adjacently tagged enums otherwise.
+fn expand_tagged(
+    ident: &syn::Ident,
+    input_ident: &syn::Ident,
+    variants: Vec<EnumVariant>,
+    tag: &str,
+    content: Option<&str>,
+) -> Result<TokenStream2, syn::Error> {
+    let var_input_ident = Ident::new("__enum_variant", Span::call_site());
+
+    let expected_repr = if content.is_some() {
+        quote! { VariantEnumRepr::AdjacentlyTagged }
+    } else {
+        quote! { VariantEnumRepr::InternallyTagged }
+    };
+
+    let tag_string_literal = Literal::string(tag);
+
+    // The payload of an internally tagged variant is everything except the tag itself
+    let var_input = match content {
+        Some(content) => {
+            let content_string_literal = Literal::string(content);
+            quote! {
+                &__dict.get_or_nil(
+                    &::gdnative::core_types::GodotString::from(#content_string_literal).to_variant()
+                )
+            }
+        }
+        None => quote! {
+            &{
+                let __content = __dict.duplicate();
+                __content.erase(&__tag_key);
+                __content.into_shared().to_variant()
+            }
+        },
+    };
+
+    let var_ident_string_literals = variants
+        .iter()
+        .map(|variant| Literal::string(&variant.name))
+        .collect::<Vec<_>>();
+
+    let ref_var_ident_string_literals = &var_ident_string_literals;
+
+    let var_from_variants = variants
+        .iter()
+        .zip(ref_var_ident_string_literals)
+        .map(|(variant, var_ident_string_literal)| {
+            let var_ident = &variant.ident;
+            if let VariantRepr::Unit(_) = variant.repr {
+                return Ok(quote! { Ok(#ident::#var_ident) });
+            }
+
+            let from_variant = variant
+                .repr
+                .make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident })?;
+
+            Ok(quote! {
+                {
+                    let #var_input_ident = #var_input;
+                    (#from_variant).map_err(|err| FVE::InvalidEnumVariant {
+                        variant: #var_ident_string_literal,
+                        error: std::boxed::Box::new(err),
+                    })
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, syn::Error>>()?;
+
+    let early_return = variants.is_empty().then(|| {
+        quote! {
+            return Err(FVE::UnknownEnumVariant {
+                variant: __tag,
+                expected: &[],
+            });
+        }
+    });
+
+    Ok(quote! {
+        let __dict = ::gdnative::core_types::Dictionary::from_variant(#input_ident)
+            .map_err(|__err| FVE::InvalidEnumRepr {
+                expected: #expected_repr,
+                error: std::boxed::Box::new(__err),
+            })?;
+
+        let __tag_key = ::gdnative::core_types::GodotString::from(#tag_string_literal).to_variant();
+        let __tag = String::from_variant(&__dict.get_or_nil(&__tag_key))
+            .map_err(|__err| FVE::InvalidEnumRepr {
+                expected: #expected_repr,
+                error: std::boxed::Box::new(FVE::InvalidField {
+                    field_name: #tag_string_literal,
+                    error: std::boxed::Box::new(__err),
+                }),
+            })?;
+
+        #early_return
+
+        match __tag.as_str() {
+            #(
+                #ref_var_ident_string_literals => #var_from_variants,
+            )*
+            variant => Err(FVE::UnknownEnumVariant {
+                variant: variant.to_string(),
+                expected: &[#(#ref_var_ident_string_literals),*],
+            }),
+        }
+    })
+}
+
+fn expand_untagged(
+    ident: &syn::Ident,
+    input_ident: &syn::Ident,
+    variants: Vec<EnumVariant>,
+) -> Result<TokenStream2, syn::Error> {
+    if variants.is_empty() {
+        return Ok(quote! {
+            Err(FVE::NoMatchingEnumVariant {
+                errors: std::vec::Vec::new(),
+            })
+        });
+    }
+
+    let var_input_ident = Ident::new("__enum_variant", Span::call_site());
+
+    // Variants are attempted in declaration order, and the first one to succeed is returned
+    let attempts = variants
+        .iter()
+        .map(|variant| {
+            let var_ident = &variant.ident;
+            let var_ident_string_literal = Literal::string(&variant.name);
+
+            let from_variant = match variant.repr {
+                VariantRepr::Unit(_) => quote! {
+                    if #var_input_ident.is_nil() {
+                        Ok(#ident::#var_ident)
+                    } else {
+                        Err(FVE::InvalidStructRepr {
+                            expected: VariantStructRepr::Unit,
+                            error: std::boxed::Box::new(FVE::InvalidVariantType {
+                                variant_type: #var_input_ident.get_type(),
+                                expected: ::gdnative::core_types::VariantType::Nil,
+                            }),
+                        })
+                    }
+                },
+                _ => variant
+                    .repr
+                    .make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident })?,
+            };
+
+            Ok(quote! {
+                {
+                    let #var_input_ident = #input_ident;
+                    match #from_variant {
+                        Ok(__value) => return Ok(__value),
+                        Err(err) => __errors.push(FVE::InvalidEnumVariant {
+                            variant: #var_ident_string_literal,
+                            error: std::boxed::Box::new(err),
+                        }),
+                    }
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, syn::Error>>()?;
+
+    Ok(quote! {
+        let mut __errors = std::vec::Vec::new();
+
+        #( #attempts )*
+
+        Err(FVE::NoMatchingEnumVariant { errors: __errors })
+    })
+}
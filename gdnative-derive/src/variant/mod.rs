@@ -4,6 +4,7 @@ use syn::{spanned::Spanned, Data, DeriveInput, Generics, Ident, Meta};
 mod attr;
 mod bounds;
 mod from;
+mod rename;
 mod repr;
 mod to;
 
@@ -0,0 +1,148 @@
+/// Case conventions that can be applied to field and variant names with `rename_all`. These
+/// follow the behavior of the attribute of the same name in `serde`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum RenameRule {
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub(crate) const VALID_VALUES: &'static str = "lowercase, UPPERCASE, PascalCase, camelCase, \
+        snake_case, SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE";
+
+    pub(crate) fn parse(rule: &str) -> Option<Self> {
+        let rule = match rule {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => return None,
+        };
+        Some(rule)
+    }
+
+    /// Applies the rule to a field name, which is assumed to be in `snake_case`.
+    pub(crate) fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply_to_field(field);
+                lowercase_first(&pascal)
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+
+    /// Applies the rule to a variant name, which is assumed to be in `Pascal`.
+    pub(crate) fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::Pascal => variant.to_owned(),
+            RenameRule::Lower => variant.to_ascii_lowercase(),
+            RenameRule::Upper => variant.to_ascii_uppercase(),
+            RenameRule::Camel => lowercase_first(variant),
+            RenameRule::Snake => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnake => RenameRule::Snake
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            RenameRule::Kebab => RenameRule::Snake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            RenameRule::ScreamingKebab => RenameRule::ScreamingSnake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+}
+
+fn lowercase_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename_fields() {
+        let cases = [
+            (RenameRule::Lower, "max_hit_points"),
+            (RenameRule::Upper, "MAX_HIT_POINTS"),
+            (RenameRule::Pascal, "MaxHitPoints"),
+            (RenameRule::Camel, "maxHitPoints"),
+            (RenameRule::Snake, "max_hit_points"),
+            (RenameRule::ScreamingSnake, "MAX_HIT_POINTS"),
+            (RenameRule::Kebab, "max-hit-points"),
+            (RenameRule::ScreamingKebab, "MAX-HIT-POINTS"),
+        ];
+
+        for (rule, expected) in cases {
+            assert_eq!(expected, rule.apply_to_field("max_hit_points"), "{rule:?}");
+        }
+    }
+
+    #[test]
+    fn rename_variants() {
+        let cases = [
+            (RenameRule::Lower, "verylongname"),
+            (RenameRule::Upper, "VERYLONGNAME"),
+            (RenameRule::Pascal, "VeryLongName"),
+            (RenameRule::Camel, "veryLongName"),
+            (RenameRule::Snake, "very_long_name"),
+            (RenameRule::ScreamingSnake, "VERY_LONG_NAME"),
+            (RenameRule::Kebab, "very-long-name"),
+            (RenameRule::ScreamingKebab, "VERY-LONG-NAME"),
+        ];
+
+        for (rule, expected) in cases {
+            assert_eq!(expected, rule.apply_to_variant("VeryLongName"), "{rule:?}");
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use syn::spanned::Spanned;
 use syn::{DataEnum, Fields, Ident, Type};
 
 use super::attr::{FieldAttr, FieldAttrBuilder, ItemAttr};
+use super::rename::RenameRule;
 use super::{parse_attrs, ToVariantTrait};
 
 // Shouldn't matter since this is immediately unpacked anyway.
@@ -24,6 +26,8 @@ pub(crate) enum VariantRepr {
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub(crate) struct Field {
     pub ident: Ident,
+    /// Key of the field in `Dictionary` representations.
+    pub name: String,
     pub ty: Type,
     pub attr: FieldAttr,
 }
@@ -35,10 +39,18 @@ pub(crate) struct StructRepr(pub VariantRepr);
 pub(crate) struct EnumRepr {
     pub kind: EnumReprKind,
     pub primitive_repr: Option<Type>,
-    pub variants: Vec<(Ident, VariantRepr)>,
+    pub variants: Vec<EnumVariant>,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct EnumVariant {
+    pub ident: Ident,
+    /// Name of the variant in representations that use names.
+    pub name: String,
+    pub repr: VariantRepr,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub enum EnumReprKind {
     /// Externally-tagged objects, i.e. the original behavior.
     External,
@@ -46,6 +58,12 @@ pub enum EnumReprKind {
     Repr,
     /// Represent as strings.
     Str,
+    /// Objects with the variant name under `tag`, alongside the fields.
+    Internal { tag: String },
+    /// Objects with the variant name under `tag` and the payload under `content`.
+    Adjacent { tag: String, content: String },
+    /// Payloads without any variant names.
+    Untagged,
 }
 
 impl EnumRepr {
@@ -54,21 +72,72 @@ impl EnumRepr {
         primitive_repr: Option<syn::Type>,
         enum_data: &DataEnum,
     ) -> Result<Self, syn::Error> {
-        let variants = enum_data
+        let variants: Vec<EnumVariant> = enum_data
             .variants
             .iter()
             .map(|variant| {
-                let mut repr = VariantRepr::repr_for(&variant.fields)?;
+                let mut repr = VariantRepr::repr_for(&variant.fields, None)?;
                 if let VariantRepr::Unit(discriminant) = &mut repr {
                     if let Some((_, expr)) = &variant.discriminant {
                         *discriminant = Some(expr.clone());
                     }
                 }
 
-                Ok((variant.ident.clone(), repr))
+                let name = variant.ident.to_string();
+                let name = match attr.rename_all {
+                    Some(rule) => rule.apply_to_variant(&name),
+                    None => name,
+                };
+
+                Ok(EnumVariant {
+                    ident: variant.ident.clone(),
+                    name,
+                    repr,
+                })
             })
             .collect::<Result<_, syn::Error>>()?;
 
+        if let Some((EnumReprKind::Internal { tag }, _)) = &attr.enum_repr_kind {
+            // Only `Dictionary` payloads can share an object with the tag
+            if let Some(variant) = variants.iter().find(
+                |variant| matches!(&variant.repr, VariantRepr::Tuple(fields) if fields.len() != 1),
+            ) {
+                return Err(syn::Error::new(
+                    variant.ident.span(),
+                    "tuple variants cannot be used with internally tagged enums",
+                ));
+            }
+
+            for variant in &variants {
+                match &variant.repr {
+                    VariantRepr::Struct(fields) => {
+                        let conflicting = fields.iter().find(|f| {
+                            f.name == *tag && !(f.attr.skip_to_variant && f.attr.skip_from_variant)
+                        });
+                        if let Some(field) = conflicting {
+                            return Err(syn::Error::new(
+                                field.ident.span(),
+                                format!("field name `{tag}` conflicts with the tag of the enum"),
+                            ));
+                        }
+                    }
+                    VariantRepr::Tuple(fields) => {
+                        let field = &fields[0];
+                        if field.attr.to_variant_with.is_none()
+                            && field.attr.from_variant_with.is_none()
+                            && is_non_dictionary_type(&field.ty)
+                        {
+                            return Err(syn::Error::new(
+                                field.ty.span(),
+                                "newtype variants of internally tagged enums must contain a type that converts to a `Dictionary`",
+                            ));
+                        }
+                    }
+                    VariantRepr::Unit(_) => {}
+                }
+            }
+        }
+
         Ok(EnumRepr {
             kind: attr
                 .enum_repr_kind
@@ -81,19 +150,26 @@ impl EnumRepr {
 
 impl StructRepr {
     pub(crate) fn repr_for(attr: ItemAttr, fields: &Fields) -> Result<Self, syn::Error> {
-        if let Some((_, span)) = attr.enum_repr_kind {
-            return Err(syn::Error::new(
-                span,
-                "`enum` representation can only be set for enums",
-            ));
+        if let Some((kind, span)) = attr.enum_repr_kind {
+            let message = match kind {
+                EnumReprKind::Internal { .. } | EnumReprKind::Adjacent { .. } => {
+                    "`tag` can only be set for enums"
+                }
+                EnumReprKind::Untagged => "`untagged` can only be set for enums",
+                _ => "`enum` representation can only be set for enums",
+            };
+            return Err(syn::Error::new(span, message));
         }
 
-        VariantRepr::repr_for(fields).map(StructRepr)
+        VariantRepr::repr_for(fields, attr.rename_all).map(StructRepr)
     }
 }
 
 impl VariantRepr {
-    pub(crate) fn repr_for(fields: &Fields) -> Result<Self, syn::Error> {
+    pub(crate) fn repr_for(
+        fields: &Fields,
+        rename_all: Option<RenameRule>,
+    ) -> Result<Self, syn::Error> {
         let this = match fields {
             Fields::Named(fields) => VariantRepr::Struct(
                 fields
@@ -103,7 +179,17 @@ impl VariantRepr {
                         let ident = f.ident.clone().expect("fields should be named");
                         let ty = f.ty.clone();
                         let attr = parse_attrs::<FieldAttrBuilder, _>(&f.attrs)?;
-                        Ok(Field { ident, ty, attr })
+                        let name = match (&attr.rename, rename_all) {
+                            (Some(rename), _) => rename.value(),
+                            (None, Some(rule)) => rule.apply_to_field(&ident.to_string()),
+                            (None, None) => ident.to_string(),
+                        };
+                        Ok(Field {
+                            ident,
+                            name,
+                            ty,
+                            attr,
+                        })
                     })
                     .collect::<Result<Vec<_>, syn::Error>>()?,
             ),
@@ -116,7 +202,13 @@ impl VariantRepr {
                         let ident = Ident::new(&format!("__field_{n}"), Span::call_site());
                         let ty = f.ty.clone();
                         let attr = parse_attrs::<FieldAttrBuilder, _>(&f.attrs)?;
-                        Ok(Field { ident, ty, attr })
+                        check_unnamed_field_attr(f, &attr)?;
+                        Ok(Field {
+                            ident,
+                            name: n.to_string(),
+                            ty,
+                            attr,
+                        })
                     })
                     .collect::<Result<_, syn::Error>>()?,
             ),
@@ -184,26 +276,44 @@ impl VariantRepr {
                 }
             }
             VariantRepr::Struct(fields) => {
-                let fields: Vec<&Field> =
-                    fields.iter().filter(|f| !f.attr.skip_to_variant).collect();
-
-                let name_strings: Vec<String> =
-                    fields.iter().map(|f| format!("{}", &f.ident)).collect();
-
-                let name_string_literals =
-                    name_strings.iter().map(|string| Literal::string(string));
+                let inserts = fields.iter().filter(|f| !f.attr.skip_to_variant).map(|f| {
+                    let name_string_literal = Literal::string(&f.name);
+                    let expr = f.make_to_variant_expr(trait_kind);
 
-                let exprs = fields.iter().map(|f| f.make_to_variant_expr(trait_kind));
+                    if f.attr.flatten {
+                        quote! {
+                            {
+                                let __value = #expr;
+                                match ::gdnative::core_types::Dictionary::from_variant(&__value) {
+                                    Ok(__flattened) => {
+                                        for (__key, __value) in __flattened.iter() {
+                                            __dict.insert(&__key, &__value);
+                                        }
+                                    }
+                                    Err(_) if __value.is_nil() => {}
+                                    // Values that aren't `Dictionary`s can't be merged, so they are
+                                    // kept under the field name, which `FromVariant` also accepts
+                                    Err(_) => {
+                                        let __key = ::gdnative::core_types::GodotString::from(#name_string_literal).to_variant();
+                                        __dict.insert(&__key, &__value);
+                                    }
+                                }
+                            }
+                        }
+                    } else {
+                        quote! {
+                            {
+                                let __key = ::gdnative::core_types::GodotString::from(#name_string_literal).to_variant();
+                                __dict.insert(&__key, &#expr);
+                            }
+                        }
+                    }
+                });
 
                 quote! {
                     {
                         let __dict = ::gdnative::core_types::Dictionary::new();
-                        #(
-                            {
-                                let __key = ::gdnative::core_types::GodotString::from(#name_string_literals).to_variant();
-                                __dict.insert(&__key, &#exprs);
-                            }
-                        )*
+                        #( #inserts )*
                         __dict.into_shared().to_variant()
                     }
                 }
@@ -258,6 +368,7 @@ impl VariantRepr {
                         .collect();
 
                     let skipped_idents = skipped_fields.iter().map(|f| &f.ident);
+                    let skipped_defaults = skipped_fields.iter().map(|f| f.default_path());
                     let non_skipped_idents = non_skipped_fields.iter().map(|f| &f.ident);
                     let ctor_idents = fields.iter().map(|f| &f.ident);
 
@@ -292,7 +403,7 @@ impl VariantRepr {
                                                 })?;
                                         )*
                                         #(
-                                            let #skipped_idents = std::default::Default::default();
+                                            let #skipped_idents = #skipped_defaults();
                                         )*
                                         Ok(#ctor( #(#ctor_idents),* ))
                                     }
@@ -302,31 +413,84 @@ impl VariantRepr {
                 }
             }
             VariantRepr::Struct(fields) => {
-                let skipped_fields: Vec<&Field> =
-                    fields.iter().filter(|f| f.attr.skip_from_variant).collect();
-
-                let non_skipped_fields: Vec<&Field> = fields
+                let has_flattened = fields
                     .iter()
-                    .filter(|f| !f.attr.skip_from_variant)
-                    .collect();
+                    .any(|f| f.attr.flatten && !f.attr.skip_from_variant);
 
-                let skipped_idents = skipped_fields.iter().map(|f| &f.ident);
-                let non_skipped_idents: Vec<&Ident> =
-                    non_skipped_fields.iter().map(|f| &f.ident).collect();
-                let ctor_idents = fields.iter().map(|f| &f.ident);
+                // Flattened fields are converted from the entries not claimed by other fields
+                let rest = has_flattened.then(|| {
+                    let claimed_names = fields
+                        .iter()
+                        .filter(|f| !f.attr.flatten)
+                        .map(|f| Literal::string(&f.name));
 
-                let name_strings: Vec<String> = non_skipped_idents
-                    .iter()
-                    .map(|ident| format!("{ident}"))
-                    .collect();
+                    quote! {
+                        let __rest = __dict.duplicate();
+                        #(
+                            __rest.erase(::gdnative::core_types::GodotString::from(#claimed_names).to_variant());
+                        )*
+                        let __rest = __rest.into_shared().to_variant();
+                    }
+                });
 
-                let name_string_literals =
-                    name_strings.iter().map(|string| Literal::string(string));
+                let lets = fields.iter().map(|f| {
+                    let ident = &f.ident;
 
-                let expr_variant = &quote!(&__dict.get_or_nil(&__key));
-                let exprs = non_skipped_fields
-                    .iter()
-                    .map(|f| f.make_from_variant_expr(expr_variant));
+                    if f.attr.skip_from_variant {
+                        let default = f.default_path();
+                        return quote! {
+                            let #ident = #default();
+                        };
+                    }
+
+                    if f.attr.flatten {
+                        let ident_string_literal = Literal::string(&ident.to_string());
+                        let name_string_literal = Literal::string(&f.name);
+                        let expr = f.make_from_variant_expr(&quote!(&__rest));
+                        let expr_by_key = f.make_from_variant_expr(&quote!(&__dict.get_or_nil(&__key)));
+                        return quote! {
+                            let #ident = #expr
+                                .or_else(|err| {
+                                    // Values that aren't `Dictionary`s are stored under the field name
+                                    let __key = ::gdnative::core_types::GodotString::from(#name_string_literal).to_variant();
+                                    #expr_by_key.map_err(|_| err)
+                                })
+                                .map_err(|err| FVE::InvalidField {
+                                    field_name: #ident_string_literal,
+                                    error: std::boxed::Box::new(err),
+                                })?;
+                        };
+                    }
+
+                    let name_string_literal = Literal::string(&f.name);
+                    let expr = f.make_from_variant_expr(&quote!(&__dict.get_or_nil(&__key)));
+                    let convert = quote! {
+                        #expr
+                            .map_err(|err| FVE::InvalidField {
+                                field_name: __field_name,
+                                error: std::boxed::Box::new(err),
+                            })?
+                    };
+
+                    let value = match &f.attr.default {
+                        Some(default) => quote! {
+                            if __dict.contains(&__key) {
+                                #convert
+                            } else {
+                                #default()
+                            }
+                        },
+                        None => convert,
+                    };
+
+                    quote! {
+                        let __field_name = #name_string_literal;
+                        let __key = ::gdnative::core_types::GodotString::from(__field_name).to_variant();
+                        let #ident = #value;
+                    }
+                });
+
+                let ctor_idents = fields.iter().map(|f| &f.ident);
 
                 quote! {
                     {
@@ -336,18 +500,8 @@ impl VariantRepr {
                                 error: std::boxed::Box::new(__err),
                             })
                             .and_then(|__dict| {
-                                #(
-                                    let __field_name = #name_string_literals;
-                                    let __key = ::gdnative::core_types::GodotString::from(__field_name).to_variant();
-                                    let #non_skipped_idents = #exprs
-                                        .map_err(|err| FVE::InvalidField {
-                                            field_name: __field_name,
-                                            error: std::boxed::Box::new(err),
-                                        })?;
-                                )*
-                                #(
-                                    let #skipped_idents = std::default::Default::default();
-                                )*
+                                #rest
+                                #( #lets )*
                                 Ok(#ctor { #( #ctor_idents ),* })
                             })
                     }
@@ -359,6 +513,76 @@ impl VariantRepr {
     }
 }
 
+/// Returns `true` for types that are known not to convert to a `Dictionary`. Other types can only
+/// be checked when converting.
+fn is_non_dictionary_type(ty: &Type) -> bool {
+    const NON_DICTIONARY_TYPES: &[&str] = &[
+        "bool",
+        "i8",
+        "i16",
+        "i32",
+        "i64",
+        "isize",
+        "u8",
+        "u16",
+        "u32",
+        "u64",
+        "usize",
+        "f32",
+        "f64",
+        "char",
+        "str",
+        "String",
+        "GodotString",
+        "Vec",
+        "VariantArray",
+        "PoolArray",
+    ];
+
+    match ty {
+        Type::Array(_) | Type::Slice(_) | Type::Tuple(_) => true,
+        Type::Group(group) => is_non_dictionary_type(&group.elem),
+        Type::Paren(paren) => is_non_dictionary_type(&paren.elem),
+        Type::Reference(reference) => is_non_dictionary_type(&reference.elem),
+        Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().map_or(false, |segment| {
+                NON_DICTIONARY_TYPES
+                    .iter()
+                    .any(|name| segment.ident == name)
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Rejects attributes that only make sense for `Dictionary` keys.
+fn check_unnamed_field_attr(field: &syn::Field, attr: &FieldAttr) -> Result<(), syn::Error> {
+    if attr.flatten {
+        return Err(syn::Error::new(
+            field.ty.span(),
+            "`flatten` can only be used on named fields",
+        ));
+    }
+
+    if let Some(rename) = &attr.rename {
+        return Err(syn::Error::new(
+            rename.span(),
+            "`rename` can only be used on named fields",
+        ));
+    }
+
+    if let Some(default) = &attr.default {
+        if !attr.skip_from_variant {
+            return Err(syn::Error::new(
+                default.span(),
+                "`default` can only be used on named or skipped fields",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 impl Field {
     fn make_to_variant_expr(&self, trait_kind: ToVariantTrait) -> TokenStream2 {
         let Field { ident, attr, .. } = self;
@@ -370,6 +594,14 @@ impl Field {
         }
     }
 
+    /// Path to the function providing values for skipped or missing fields.
+    fn default_path(&self) -> syn::Path {
+        self.attr
+            .default
+            .clone()
+            .unwrap_or_else(|| parse_quote!(::std::default::Default::default))
+    }
+
     fn make_from_variant_expr(&self, variant: &TokenStream2) -> TokenStream2 {
         if let Some(from_variant_with) = &self.attr.from_variant_with {
             quote!(#from_variant_with(#variant))
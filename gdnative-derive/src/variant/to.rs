@@ -1,7 +1,7 @@
 use crate::variant::bounds;
 use proc_macro2::{Literal, TokenStream as TokenStream2};
 
-use crate::variant::repr::{EnumReprKind, VariantRepr};
+use crate::variant::repr::{EnumReprKind, EnumVariant, VariantRepr};
 
 use super::repr::{EnumRepr, Repr, StructRepr};
 use super::{DeriveData, ToVariantTrait};
@@ -50,11 +50,10 @@ pub(crate) fn expand_to_variant(
                     EnumReprKind::External => {
                         let match_arms = variants
                             .iter()
-                            .map(|(var_ident, var_repr)| {
+                            .map(|EnumVariant { ident: var_ident, name, repr: var_repr }| {
                                 let destructure_pattern = var_repr.destructure_pattern();
                                 let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
-                                let var_ident_string = format!("{var_ident}");
-                                let var_ident_string_literal = Literal::string(&var_ident_string);
+                                let var_ident_string_literal = Literal::string(name);
                                 let tokens = quote! {
                                     #ident::#var_ident #destructure_pattern => {
                                         let __dict = ::gdnative::core_types::Dictionary::new();
@@ -76,16 +75,131 @@ pub(crate) fn expand_to_variant(
                             }
                         }
                     }
+                    EnumReprKind::Internal { tag } => {
+                        let match_arms = variants
+                            .iter()
+                            .map(|EnumVariant { ident: var_ident, name, repr: var_repr }| {
+                                let destructure_pattern = var_repr.destructure_pattern();
+                                let insert_tag = insert_string(&tag, name);
+                                let merge_content = if matches!(var_repr, VariantRepr::Unit(_)) {
+                                    None
+                                } else {
+                                    let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
+                                    let var_ident_string = var_ident.to_string();
+                                    Some(quote! {
+                                        let __value = #to_variant;
+                                        match ::gdnative::core_types::Dictionary::from_variant(&__value) {
+                                            Ok(__content) => {
+                                                for (__key, __value) in __content.iter() {
+                                                    __dict.insert(&__key, &__value);
+                                                }
+                                            }
+                                            Err(_) if __value.is_nil() => {}
+                                            Err(_) => panic!(
+                                                "internally tagged variant `{}::{}` should contain a Dictionary, got {:?}",
+                                                stringify!(#ident),
+                                                #var_ident_string,
+                                                __value.get_type(),
+                                            ),
+                                        }
+                                    })
+                                };
+                                // The tag is inserted last, so that it can't be replaced by the content
+                                let tokens = quote! {
+                                    #ident::#var_ident #destructure_pattern => {
+                                        let __dict = ::gdnative::core_types::Dictionary::new();
+                                        #merge_content
+                                        #insert_tag
+                                        ::gdnative::core_types::ToVariant::to_variant(&__dict.into_shared())
+                                    }
+                                };
+                                Ok(tokens)
+                            })
+                            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                        quote! {
+                            match #to_variant_receiver {
+                                #( #match_arms ),*
+                            }
+                        }
+                    }
+                    EnumReprKind::Adjacent { tag, content } => {
+                        let content_string_literal = Literal::string(&content);
+                        let match_arms = variants
+                            .iter()
+                            .map(|EnumVariant { ident: var_ident, name, repr: var_repr }| {
+                                let destructure_pattern = var_repr.destructure_pattern();
+                                let insert_tag = insert_string(&tag, name);
+                                let insert_content = if matches!(var_repr, VariantRepr::Unit(_)) {
+                                    None
+                                } else {
+                                    let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
+                                    Some(quote! {
+                                        let __key = ::gdnative::core_types::ToVariant::to_variant(
+                                            &::gdnative::core_types::GodotString::from(#content_string_literal)
+                                        );
+                                        let __value = #to_variant;
+                                        __dict.insert(&__key, &__value);
+                                    })
+                                };
+                                let tokens = quote! {
+                                    #ident::#var_ident #destructure_pattern => {
+                                        let __dict = ::gdnative::core_types::Dictionary::new();
+                                        #insert_tag
+                                        #insert_content
+                                        ::gdnative::core_types::ToVariant::to_variant(&__dict.into_shared())
+                                    }
+                                };
+                                Ok(tokens)
+                            })
+                            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                        quote! {
+                            match #to_variant_receiver {
+                                #( #match_arms ),*
+                            }
+                        }
+                    }
+                    EnumReprKind::Untagged => {
+                        let match_arms = variants
+                            .iter()
+                            .map(
+                                |EnumVariant {
+                                     ident: var_ident,
+                                     repr: var_repr,
+                                     ..
+                                 }| {
+                                    let destructure_pattern = var_repr.destructure_pattern();
+                                    let to_variant = if matches!(var_repr, VariantRepr::Unit(_)) {
+                                        quote! { ::gdnative::core_types::Variant::nil() }
+                                    } else {
+                                        var_repr.make_to_variant_expr(trait_kind)?
+                                    };
+                                    let tokens = quote! {
+                                        #ident::#var_ident #destructure_pattern => {
+                                            #to_variant
+                                        }
+                                    };
+                                    Ok(tokens)
+                                },
+                            )
+                            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                        quote! {
+                            match #to_variant_receiver {
+                                #( #match_arms ),*
+                            }
+                        }
+                    }
                     EnumReprKind::Str => {
                         let match_arms = variants
                             .iter()
-                            .map(|(var_ident, var_repr)| {
+                            .map(|EnumVariant { ident: var_ident, name, repr: var_repr }| {
                                 if !matches!(var_repr, VariantRepr::Unit(_)) {
                                     return Err(syn::Error::new(var_ident.span(), "`str` representation can only be used for fieldless enums"));
                                 }
 
-                                let var_ident_string = format!("{var_ident}");
-                                let var_ident_string_literal = Literal::string(&var_ident_string);
+                                let var_ident_string_literal = Literal::string(name);
                                 let tokens = quote! {
                                     #ident::#var_ident => {
                                         ::gdnative::core_types::ToVariant::to_variant(#var_ident_string_literal)
@@ -110,12 +224,12 @@ pub(crate) fn expand_to_variant(
                             )
                         })?;
 
-                        if let Some((var_ident, _)) = variants
+                        if let Some(variant) = variants
                             .iter()
-                            .find(|(_, var_repr)| !matches!(var_repr, VariantRepr::Unit(_)))
+                            .find(|variant| !matches!(variant.repr, VariantRepr::Unit(_)))
                         {
                             return Err(syn::Error::new(
-                                var_ident.span(),
+                                variant.ident.span(),
                                 "`repr` representation can only be used for fieldless enums",
                             ));
                         }
@@ -155,3 +269,20 @@ pub(crate) fn expand_to_variant(
 
     Ok(result)
 }
+
+/// Inserts a `key: value` entry of two strings into `__dict`.
+fn insert_string(key: &str, value: &str) -> TokenStream2 {
+    let key_string_literal = Literal::string(key);
+    let value_string_literal = Literal::string(value);
+    quote! {
+        {
+            let __key = ::gdnative::core_types::ToVariant::to_variant(
+                &::gdnative::core_types::GodotString::from(#key_string_literal)
+            );
+            let __value = ::gdnative::core_types::ToVariant::to_variant(
+                &::gdnative::core_types::GodotString::from(#value_string_literal)
+            );
+            __dict.insert(&__key, &__value);
+        }
+    }
+}
@@ -239,6 +239,12 @@ mod header_binding {
             builder = add_android_include_paths(builder);
         }
 
+        // Engine builds with `float=64` define `real_t` as `double`, which changes `godot_real`
+        // and the size of the math types.
+        if std::env::var_os("CARGO_FEATURE_DOUBLE_PRECISION").is_some() {
+            builder = builder.clang_arg("-DREAL_T_IS_DOUBLE");
+        }
+
         let bindings = builder.generate().expect("Unable to generate bindings");
 
         let out_path = PathBuf::from(out_dir);
@@ -364,7 +370,20 @@ mod api_wrapper {
         fn to_tokens(&self, tokens: &mut TokenStream) {
             let args = self.rust_args();
             let return_type = self.rust_return_type();
-            tokens.extend(quote!(unsafe extern "C" fn(#args) -> #return_type));
+            let abi = function_abi();
+            tokens.extend(quote!(unsafe extern #abi fn(#args) -> #return_type));
+        }
+    }
+
+    /// ABI of the functions in `GodotApi`.
+    ///
+    /// With the `mock-api` feature, the functions may unwind, so that mock implementations can
+    /// report errors by panicking.
+    fn function_abi() -> &'static str {
+        if std::env::var_os("CARGO_FEATURE_MOCK_API").is_some() {
+            "C-unwind"
+        } else {
+            "C"
         }
     }
 
@@ -497,6 +516,7 @@ mod api_wrapper {
 
         let struct_fields = godot_api_functions(&api_root);
         let impl_constructor = api_constructor(&api_root);
+        let unsupported = unsupported_api(&api_root);
         let wrapper = quote! {
             pub struct GodotApi{
                 #struct_fields
@@ -504,6 +524,7 @@ mod api_wrapper {
             impl GodotApi {
                 #impl_constructor
             }
+            #unsupported
         };
         let mut wrapper_file = File::create(to.join(file_name))
             .unwrap_or_else(|_| panic!("Couldn't create output file: {:?}", to.join(file_name)));
@@ -548,6 +569,12 @@ mod api_wrapper {
                 struct_field_bindings.extend(quote! {
                     let #function_name = map_option_to_init_error((*#i).#function_name, #message)?;
                 });
+                if function_abi() != "C" {
+                    // Engine functions never unwind, so they can be called through either ABI.
+                    struct_field_bindings.extend(quote! {
+                        let #function_name = std::mem::transmute::<_, #function>(#function_name);
+                    });
+                }
                 constructed_struct_fields.extend(quote! {
                     #function_name,
                 });
@@ -564,6 +591,46 @@ mod api_wrapper {
         }
     }
 
+    /// Generates `GodotApi::unsupported`, which fills every field with a function that panics
+    /// with the name of the API function. Used as a base table by the `mock-api` feature.
+    fn unsupported_api(api: &ApiRoot) -> TokenStream {
+        let mut stub_functions = TokenStream::new();
+        let mut constructed_struct_fields = TokenStream::new();
+        for api in api.all_apis() {
+            for function in &api.functions {
+                let function_name = function.rust_name();
+                let name = &function.name;
+                let arg_types = function.arguments.iter().map(Argument::rust_type);
+                let return_type = function.rust_return_type();
+                stub_functions.extend(quote! {
+                    pub unsafe extern "C-unwind" fn #function_name(#(_: #arg_types),*) -> #return_type {
+                        crate::unsupported_api_function(#name)
+                    }
+                });
+                constructed_struct_fields.extend(quote! {
+                    #function_name: unsupported_api::#function_name,
+                });
+            }
+        }
+        quote! {
+            #[cfg(feature = "mock-api")]
+            impl GodotApi {
+                /// Creates an API table in which every function panics, naming the function that
+                /// was called. Individual fields can then be replaced.
+                pub fn unsupported() -> Self {
+                    GodotApi {
+                        #constructed_struct_fields
+                    }
+                }
+            }
+            #[cfg(feature = "mock-api")]
+            mod unsupported_api {
+                use super::*;
+                #stub_functions
+            }
+        }
+    }
+
     fn parse_c_type(mut c_type: &str) -> (bool, i8, &str) {
         c_type = c_type.trim();
         let is_const = c_type.starts_with("const ");
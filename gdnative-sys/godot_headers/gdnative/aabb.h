@@ -37,7 +37,11 @@ extern "C" {
 
 #include <stdint.h>
 
+#ifdef REAL_T_IS_DOUBLE
+#define GODOT_AABB_SIZE 48
+#else
 #define GODOT_AABB_SIZE 24
+#endif
 
 #ifndef GODOT_CORE_API_GODOT_AABB_TYPE_DEFINED
 #define GODOT_CORE_API_GODOT_AABB_TYPE_DEFINED
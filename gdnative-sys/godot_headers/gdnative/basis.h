@@ -37,7 +37,11 @@ extern "C" {
 
 #include <stdint.h>
 
+#ifdef REAL_T_IS_DOUBLE
+#define GODOT_BASIS_SIZE 72
+#else
 #define GODOT_BASIS_SIZE 36
+#endif
 
 #ifndef GODOT_CORE_API_GODOT_BASIS_TYPE_DEFINED
 #define GODOT_CORE_API_GODOT_BASIS_TYPE_DEFINED
@@ -131,7 +131,11 @@ typedef int godot_int;
 
 /////// real
 
+#ifdef REAL_T_IS_DOUBLE
+typedef double godot_real;
+#else
 typedef float godot_real;
+#endif
 
 /////// Object (forward declared)
 typedef void godot_object;
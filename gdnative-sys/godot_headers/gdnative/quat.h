@@ -37,7 +37,11 @@ extern "C" {
 
 #include <stdint.h>
 
+#ifdef REAL_T_IS_DOUBLE
+#define GODOT_QUAT_SIZE 32
+#else
 #define GODOT_QUAT_SIZE 16
+#endif
 
 #ifndef GODOT_CORE_API_GODOT_QUAT_TYPE_DEFINED
 #define GODOT_CORE_API_GODOT_QUAT_TYPE_DEFINED
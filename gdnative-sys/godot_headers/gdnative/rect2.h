@@ -40,7 +40,11 @@ extern "C" {
 #ifndef GODOT_CORE_API_GODOT_RECT2_TYPE_DEFINED
 #define GODOT_CORE_API_GODOT_RECT2_TYPE_DEFINED
 typedef struct godot_rect2 {
+#ifdef REAL_T_IS_DOUBLE
+	uint8_t _dont_touch_that[32];
+#else
 	uint8_t _dont_touch_that[16];
+#endif
 } godot_rect2;
 #endif
 
@@ -37,7 +37,11 @@ extern "C" {
 
 #include <stdint.h>
 
+#ifdef REAL_T_IS_DOUBLE
+#define GODOT_TRANSFORM_SIZE 96
+#else
 #define GODOT_TRANSFORM_SIZE 48
+#endif
 
 #ifndef GODOT_CORE_API_GODOT_TRANSFORM_TYPE_DEFINED
 #define GODOT_CORE_API_GODOT_TRANSFORM_TYPE_DEFINED
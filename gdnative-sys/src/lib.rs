@@ -28,6 +28,34 @@ pub enum InitError {
     },
 }
 
+/// Expands to the type of an API function with the given signature, in the ABI used by
+/// `GodotApi`: `extern "C"`, or `extern "C-unwind"` with the `mock-api` feature.
+#[cfg(not(feature = "mock-api"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! godot_api_fn {
+    (unsafe fn($($arg:ty),* $(,)?) $(-> $ret:ty)?) => {
+        unsafe extern "C" fn($($arg),*) $(-> $ret)?
+    };
+}
+
+#[cfg(feature = "mock-api")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! godot_api_fn {
+    (unsafe fn($($arg:ty),* $(,)?) $(-> $ret:ty)?) => {
+        unsafe extern "C-unwind" fn($($arg),*) $(-> $ret)?
+    };
+}
+
+/// Reports a call to an API function that has no implementation in `GodotApi::unsupported`.
+#[cfg(feature = "mock-api")]
+#[doc(hidden)]
+#[cold]
+pub fn unsupported_api_function(name: &str) -> ! {
+    panic!("gdnative-sys: API function `{name}` is not supported without the Godot engine")
+}
+
 fn map_option_to_init_error<T>(t: Option<T>, message: &'static str) -> Result<T, InitError> {
     match t {
         Some(t) => Ok(t),
@@ -9,7 +9,9 @@
 //!   * `instance_from_id` -> [`GodotObject::from_instance_id()`][crate::object::GodotObject::from_instance_id]
 //!   * ...
 //! * they have a private implementation, i.e. a Rust port would have different semantics
-//!   * `randi`, `randf` etc. -- users should use `rand` crate
+//!   * `randi`, `randf` etc. -- these use a global generator with a private state. Use
+//!     [`RandomNumberGenerator`] instead, which reproduces the engine's sequences for a given seed,
+//!     or the `rand` crate if that is not needed.
 //!   * `str2var`, `bytes2var`, `hash` etc -- to be verified
 //!
 //! This above list is not a definitive inclusion/exclusion criterion, just a rough guideline.
@@ -51,6 +51,20 @@
 //! * **`serde`**<br>
 //!   Enable for `serde` support of several core types. See also [`Variant`](core_types::Variant).
 //!
+//! * **`compression`**<br>
+//!   Enables the Deflate, Zstandard and gzip modes of [`PoolArray::<u8>::compress`](core_types::PoolArray::compress),
+//!   by linking the zlib and Zstandard C libraries. FastLZ is available without this feature.
+//!
+//! * **`double-precision`**<br>
+//!   Makes [`real`](core_types::real) an alias for `f64` instead of `f32`, for use with engine builds
+//!   compiled with `float=64`. This changes the components of vectors and all geometry types, the
+//!   element type of `PoolRealArray`, and the math functions in [`globalscope`].
+//!
+//! * **`glam`**, **`mint`**<br>
+//!   Enable `From`/`Into` conversions between the math types in [`core_types`] and those of the
+//!   `glam` and `mint` crates, as well as `ToVariant`/`FromVariant` for the latter. The supported
+//!   versions of both crates are re-exported as `core_types::glam` and `core_types::mint`.
+//!
 //! * **`inventory`**<br>
 //!   Enables automatic class registration via `inventory`.
 //!
@@ -61,6 +75,12 @@
 //!   Please refer to [the `rust-ctor` README][ctor-repo] for an up-to-date listing of platforms
 //!   that *do* support automatic registration.
 //!
+//! * **`mock-api`**<br>
+//!   Provides [`mock::install`], a pure-Rust implementation of the core GDNative API for
+//!   strings, variants, collections, pool arrays and node paths. This allows core types to be used in
+//!   ordinary `cargo test` runs without the engine. Object and method-bind functions are unsupported.
+//!   Requires Rust 1.71, since the mock functions report errors by unwinding.
+//!
 //! Bindings generation:
 //!
 //! * **`custom-godot`**<br>
@@ -103,8 +123,8 @@
 // their hidden status. Re-exporting them manually and hiding the wildcard solves this.
 #[doc(inline)]
 pub use gdnative_core::{
-    core_types, derive, export, godot_dbg, godot_error, godot_print, godot_site, init, log, object,
-    profiler,
+    core_types, derive, export, godot_dbg, godot_error, godot_format, godot_print, godot_site,
+    init, log, node_path, object, profiler, string_name,
 };
 
 pub mod globalscope;
@@ -124,3 +144,7 @@ pub use gdnative_bindings as api;
 #[doc(inline)]
 #[cfg(feature = "async")]
 pub use gdnative_async as tasks;
+
+#[doc(inline)]
+#[cfg(feature = "mock-api")]
+pub use gdnative_core::mock;
@@ -5,9 +5,9 @@ pub use gdnative_bindings::{
     ResourceLoader, SceneTree, Shader, Spatial, Sprite, Texture, Timer, Tween, Viewport,
 };
 pub use gdnative_core::core_types::{
-    Aabb, Basis, Color, Dictionary, GodotError, GodotString, NodePath, Plane, PoolArray, Quat,
-    Rect2, Rid, StringName, Transform, Transform2D, Variant, VariantArray, VariantDispatch,
-    VariantOperator, VariantType, Vector2, Vector3,
+    Aabb, Array, Basis, Color, Dictionary, GodotError, GodotString, NodePath, Plane, PoolArray,
+    Quat, Rect2, Rid, StringName, Transform, Transform2D, TypedDictionary, Variant, VariantArray,
+    VariantDispatch, VariantOperator, VariantType, Vector2, Vector3,
 };
 pub use gdnative_core::core_types::{
     FromVariant, FromVariantError, OwnedToVariant, ToVariant, ToVariantEq,
@@ -23,7 +23,10 @@ pub use gdnative_core::object::{
     AsArg, GodotObject, Instance, Instanciable, NewRef, Null, QueueFree, Ref, SubClass, TInstance,
     TRef,
 };
-pub use gdnative_core::{godot_dbg, godot_error, godot_init, godot_print, godot_warn};
+pub use gdnative_core::{
+    godot_dbg, godot_error, godot_format, godot_init, godot_print, godot_warn, node_path,
+    string_name,
+};
 pub use gdnative_derive::*;
 
 /// User-data attributes from [`export::user_data`][crate::export::user_data] module.
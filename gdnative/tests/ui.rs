@@ -20,6 +20,9 @@ fn ui_tests() {
     // Variants
     t.pass("tests/ui/variant_pass.rs");
 
+    // NameArg
+    t.pass("tests/ui/name_arg_pass.rs");
+
     // ToVariant
     t.compile_fail("tests/ui/to_variant_fail_01.rs");
     to_variant_ui_path(&t);
@@ -39,6 +42,9 @@ fn ui_tests() {
     t.compile_fail("tests/ui/from_variant_fail_07.rs");
     t.compile_fail("tests/ui/from_variant_fail_08.rs");
     t.compile_fail("tests/ui/from_variant_fail_09.rs");
+    t.compile_fail("tests/ui/from_variant_fail_10.rs");
+    t.compile_fail("tests/ui/from_variant_fail_11.rs");
+    t.compile_fail("tests/ui/from_variant_fail_12.rs");
 }
 
 // FIXME(rust/issues/54725): Full path spans are only available on nightly as of now
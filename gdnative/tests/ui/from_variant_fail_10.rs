@@ -0,0 +1,23 @@
+use gdnative::prelude::*;
+
+#[derive(FromVariant)]
+// Only the case conventions supported by serde are allowed
+#[variant(rename_all = "Title Case")]
+pub struct Foo {
+    bar: String,
+}
+
+#[derive(FromVariant)]
+pub struct Bar {
+    // Flattened fields take all remaining entries, so they can't have names
+    #[variant(flatten, rename = "baz")]
+    baz: Foo,
+}
+
+#[derive(FromVariant)]
+pub struct Baz {
+    #[variant(default, default = "Default::default")]
+    quux: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use gdnative::prelude::*;
+
+#[derive(FromVariant)]
+// Tuple structs are represented as arrays, so fields can't be renamed
+pub struct Foo(#[variant(rename = "bar")] String, i64);
+
+#[derive(FromVariant)]
+pub struct Bar(#[variant(flatten)] Foo, i64);
+
+#[derive(FromVariant)]
+// `default` only applies to missing keys, which don't exist for tuples unless skipped
+pub struct Baz(#[variant(default)] String, i64);
+
+fn main() {}
@@ -0,0 +1,45 @@
+use gdnative::prelude::*;
+
+#[derive(FromVariant)]
+// Tuple payloads can't share a `Dictionary` with the tag
+#[variant(tag = "type")]
+pub enum Foo {
+    Bar(i64, i64),
+}
+
+#[derive(FromVariant)]
+#[variant(content = "c")]
+pub enum Baz {
+    Quux(i64),
+}
+
+#[derive(FromVariant)]
+#[variant(untagged, tag = "type")]
+pub enum Corge {
+    Grault(i64),
+}
+
+#[derive(FromVariant)]
+#[variant(tag = "type")]
+pub struct Garply {
+    waldo: i64,
+}
+
+#[derive(FromVariant)]
+// Fields can't share a key with the tag
+#[variant(tag = "type")]
+pub enum Fred {
+    Plugh {
+        #[variant(rename = "type")]
+        kind: i64,
+    },
+}
+
+#[derive(FromVariant)]
+// Newtype payloads must be represented as `Dictionary`s
+#[variant(tag = "type")]
+pub enum Xyzzy {
+    Thud(u32),
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use gdnative::prelude::*;
+
+// Method, signal and property names accept every type that converted into `GodotString` before
+// they were declared `impl NameArg`.
+#[allow(dead_code)]
+fn call_sites(object: &Object, name: String, godot_name: GodotString) {
+    object.has_method("method");
+    object.has_method(String::from("method"));
+    object.has_method(&name);
+    object.has_method(name.as_str());
+    object.has_method(std::borrow::Cow::Borrowed("method"));
+    object.has_method(&godot_name);
+    object.has_method(godot_name.clone());
+    object.has_method(NodePath::from_str("method"));
+    object.has_method(string_name!("method"));
+
+    object.has_signal(&name);
+    object.has_signal(godot_name.clone());
+
+    object.set(&name, 1);
+    object.set(godot_name.clone(), 1);
+    object.get(name);
+    object.get(godot_name);
+}
+
+fn main() {}
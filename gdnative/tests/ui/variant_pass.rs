@@ -44,4 +44,74 @@ pub struct Bar {
     owned: Owned,
 }
 
+#[derive(ToVariant, FromVariant)]
+#[variant(rename_all = "camelCase")]
+pub struct Renamed {
+    hit_points: i64,
+
+    #[variant(rename = "MaxHP")]
+    max_hit_points: i64,
+
+    #[variant(default)]
+    name: String,
+
+    #[variant(default = "default_speed")]
+    speed: f64,
+
+    #[variant(flatten)]
+    stats: Stats,
+}
+
+#[derive(ToVariant, FromVariant)]
+pub struct Stats {
+    level: i64,
+}
+
+fn default_speed() -> f64 {
+    1.0
+}
+
+#[derive(ToVariant, FromVariant)]
+pub struct SkippedWithDefault(#[variant(skip, default = "default_speed")] f64, i64);
+
+#[derive(ToVariant, FromVariant)]
+#[variant(rename_all = "snake_case")]
+pub enum Command {
+    MoveTo { target_x: f64 },
+    StandStill,
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(enum = "str", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Mode {
+    FreeLook,
+    Follow,
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    Hit { damage: i64 },
+    Heal(Stats),
+    Died,
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(tag = "t", content = "c")]
+pub enum Adjacent {
+    Unit,
+    Newtype(i64),
+    Tuple(i64, String),
+    Struct { x: f64 },
+}
+
+#[derive(OwnedToVariant, FromVariant)]
+#[variant(untagged)]
+pub enum Untagged {
+    Unit,
+    Newtype(i64),
+    Tuple(i64, String),
+    Struct { x: f64 },
+}
+
 fn main() {}
@@ -100,63 +100,64 @@ fn fn_symbol(godot_type: &str, method: &str) -> Ident {
 }
 
 fn fn_ty(method: &str) -> Type {
+    let signature = fn_signature(method);
+    parse_quote!(sys::godot_api_fn!(#signature))
+}
+
+fn fn_signature(method: &str) -> Type {
     match method {
-        "new" => parse_quote!(unsafe extern "C" fn(*mut Self::SysArray)),
+        "new" => parse_quote!(unsafe fn(*mut Self::SysArray)),
         "new_copy" => {
-            parse_quote!(unsafe extern "C" fn(*mut Self::SysArray, *const Self::SysArray))
+            parse_quote!(unsafe fn(*mut Self::SysArray, *const Self::SysArray))
         }
         "new_with_array" => {
-            parse_quote!(unsafe extern "C" fn(*mut Self::SysArray, *const sys::godot_array))
+            parse_quote!(unsafe fn(*mut Self::SysArray, *const sys::godot_array))
         }
-        "append" => parse_quote!(unsafe extern "C" fn(*mut Self::SysArray, Self::SysRefTy)),
+        "append" => parse_quote!(unsafe fn(*mut Self::SysArray, Self::SysRefTy)),
         "append_array" => {
-            parse_quote!(unsafe extern "C" fn(*mut Self::SysArray, *const Self::SysArray))
+            parse_quote!(unsafe fn(*mut Self::SysArray, *const Self::SysArray))
         }
         "insert" => parse_quote!(
-            unsafe extern "C" fn(
-                *mut Self::SysArray,
-                sys::godot_int,
-                Self::SysRefTy,
-            ) -> sys::godot_error
+            unsafe fn(*mut Self::SysArray, sys::godot_int, Self::SysRefTy) -> sys::godot_error
         ),
-        "invert" => parse_quote!(unsafe extern "C" fn(*mut Self::SysArray)),
-        "push_back" => parse_quote!(unsafe extern "C" fn(*mut Self::SysArray, Self::SysRefTy)),
-        "remove" => parse_quote!(unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int)),
-        "resize" => parse_quote!(unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int)),
+        "invert" => parse_quote!(unsafe fn(*mut Self::SysArray)),
+        "push_back" => parse_quote!(unsafe fn(*mut Self::SysArray, Self::SysRefTy)),
+        "remove" => parse_quote!(unsafe fn(*mut Self::SysArray, sys::godot_int)),
+        "resize" => parse_quote!(unsafe fn(*mut Self::SysArray, sys::godot_int)),
         "read" => {
-            parse_quote!(unsafe extern "C" fn(*const Self::SysArray) -> *mut Self::SysReadAccess)
+            parse_quote!(unsafe fn(*const Self::SysArray) -> *mut Self::SysReadAccess)
         }
         "write" => {
-            parse_quote!(unsafe extern "C" fn(*mut Self::SysArray) -> *mut Self::SysWriteAccess)
+            parse_quote!(unsafe fn(*mut Self::SysArray) -> *mut Self::SysWriteAccess)
         }
         "set" => {
-            parse_quote!(unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int, Self::SysRefTy))
+            parse_quote!(unsafe fn(*mut Self::SysArray, sys::godot_int, Self::SysRefTy))
         }
         "get" => {
-            parse_quote!(unsafe extern "C" fn(*const Self::SysArray, sys::godot_int) -> Self::SysTy)
+            parse_quote!(unsafe fn(*const Self::SysArray, sys::godot_int) -> Self::SysTy)
+        }
+        "size" => parse_quote!(unsafe fn(*const Self::SysArray) -> sys::godot_int),
+        "destroy" => parse_quote!(unsafe fn(*mut Self::SysArray)),
+        "read_access_copy" => {
+            parse_quote!(unsafe fn(*const Self::SysReadAccess) -> *mut Self::SysReadAccess)
         }
-        "size" => parse_quote!(unsafe extern "C" fn(*const Self::SysArray) -> sys::godot_int),
-        "destroy" => parse_quote!(unsafe extern "C" fn(*mut Self::SysArray)),
-        "read_access_copy" => parse_quote!(
-            unsafe extern "C" fn(*const Self::SysReadAccess) -> *mut Self::SysReadAccess
-        ),
         "read_access_ptr" => {
-            parse_quote!(unsafe extern "C" fn(*const Self::SysReadAccess) -> *const Self::SysTy)
+            parse_quote!(unsafe fn(*const Self::SysReadAccess) -> *const Self::SysTy)
         }
         "read_access_operator_assign" => {
-            parse_quote!(unsafe extern "C" fn(*mut Self::SysReadAccess, *mut Self::SysReadAccess))
+            parse_quote!(unsafe fn(*mut Self::SysReadAccess, *mut Self::SysReadAccess))
+        }
+        "read_access_destroy" => parse_quote!(unsafe fn(*mut Self::SysReadAccess)),
+        "write_access_copy" => {
+            parse_quote!(unsafe fn(*const Self::SysWriteAccess) -> *mut Self::SysWriteAccess)
         }
-        "read_access_destroy" => parse_quote!(unsafe extern "C" fn(*mut Self::SysReadAccess)),
-        "write_access_copy" => parse_quote!(
-            unsafe extern "C" fn(*const Self::SysWriteAccess) -> *mut Self::SysWriteAccess
-        ),
         "write_access_ptr" => {
-            parse_quote!(unsafe extern "C" fn(*const Self::SysWriteAccess) -> *mut Self::SysTy)
+            parse_quote!(unsafe fn(*const Self::SysWriteAccess) -> *mut Self::SysTy)
         }
         "write_access_operator_assign" => {
-            parse_quote!(unsafe extern "C" fn(*mut Self::SysWriteAccess, *mut Self::SysWriteAccess))
+            parse_quote!(unsafe fn(*mut Self::SysWriteAccess, *mut Self::SysWriteAccess))
         }
-        "write_access_destroy" => parse_quote!(unsafe extern "C" fn(*mut Self::SysWriteAccess)),
+        "write_access_destroy" => parse_quote!(unsafe fn(*mut Self::SysWriteAccess)),
         _ => panic!("unknown method: {method}"),
     }
 }
@@ -310,14 +311,14 @@ fn expand(input: Args) -> Result<TokenStream, syn::Error> {
             #[inline(always)]
             fn array_to_variant_fn(
                 api: &sys::GodotApi,
-            ) -> unsafe extern "C" fn (*mut sys::godot_variant, *const Self::SysArray) {
+            ) -> sys::godot_api_fn!(unsafe fn(*mut sys::godot_variant, *const Self::SysArray)) {
                 api.#array_to_variant_fn_symbol
             }
 
             #[inline(always)]
             fn array_from_variant_fn(
                 api: &sys::GodotApi,
-            ) -> unsafe extern "C" fn (*const sys::godot_variant) -> Self::SysArray {
+            ) -> sys::godot_api_fn!(unsafe fn(*const sys::godot_variant) -> Self::SysArray) {
                 api.#array_from_variant_fn_symbol
             }
 
@@ -418,12 +419,12 @@ pub fn decl_element(input: proc_macro::TokenStream) -> Result<TokenStream, syn::
             #[doc(hidden)]
             fn array_to_variant_fn(
                 api: &sys::GodotApi,
-            ) -> unsafe extern "C" fn (*mut sys::godot_variant, *const Self::SysArray);
+            ) -> sys::godot_api_fn!(unsafe fn(*mut sys::godot_variant, *const Self::SysArray));
 
             #[doc(hidden)]
             fn array_from_variant_fn(
                 api: &sys::GodotApi,
-            ) -> unsafe extern "C" fn (*const sys::godot_variant) -> Self::SysArray;
+            ) -> sys::godot_api_fn!(unsafe fn(*const sys::godot_variant) -> Self::SysArray);
 
             #(#functions)*
         }
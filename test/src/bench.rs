@@ -0,0 +1,178 @@
+//! Micro-benchmarks running against the real engine API.
+//!
+//! Timings are printed for comparison, but never cause a failure. The benchmarks still check
+//! that the compared implementations produce the same results.
+
+use std::time::{Duration, Instant};
+
+use gdnative::core_types::{pool_vec, real, Aabb, PoolVec};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= bench_pool_vec_conversions();
+    status &= bench_xform_slice();
+    status &= bench_aabb_from_points();
+
+    status
+}
+
+pub(crate) fn register(_handle: InitHandle) {}
+
+/// Runs `f` for `iterations` times after a warm-up run, and prints the average duration.
+fn measure<R>(name: &str, iterations: u32, mut f: impl FnMut() -> R) -> Duration {
+    drop(f());
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        drop(f());
+    }
+    let average = start.elapsed() / iterations;
+
+    godot_print!("   {:<40} {:>12?}", name, average);
+    average
+}
+
+crate::godot_itest! { bench_pool_vec_conversions {
+    const LEN: usize = 100_000;
+    const ITERATIONS: u32 = 20;
+
+    let floats: Vec<real> = (0..LEN).map(|i| i as real * 0.5).collect();
+    let vertices: Vec<Vector3> = (0..LEN)
+        .map(|i| Vector3::new(i as real, 1.0, -(i as real)))
+        .collect();
+
+    measure("Vec<real>::to_variant", ITERATIONS, || floats.to_variant());
+    measure("pool_vec::to_variant::<real>", ITERATIONS, || pool_vec::to_variant(&floats));
+    measure("Vec<Vector3>::to_variant", ITERATIONS, || vertices.to_variant());
+    measure("pool_vec::to_variant::<Vector3>", ITERATIONS, || pool_vec::to_variant(&vertices));
+
+    let array = vertices.to_variant();
+    let pool = PoolVec(vertices.clone()).to_variant();
+    assert_eq!(VariantType::VariantArray, array.get_type());
+    assert_eq!(VariantType::Vector3Array, pool.get_type());
+
+    measure("Vec<Vector3>::from_variant(Array)", ITERATIONS, || {
+        Vec::<Vector3>::from_variant(&array).unwrap()
+    });
+    measure("PoolVec<Vector3>::from_variant(Array)", ITERATIONS, || {
+        PoolVec::<Vector3>::from_variant(&array).unwrap()
+    });
+    measure("PoolVec<Vector3>::from_variant(Pool)", ITERATIONS, || {
+        PoolVec::<Vector3>::from_variant(&pool).unwrap()
+    });
+
+    assert_eq!(Ok(vertices.clone()), Vec::<Vector3>::from_variant(&array));
+    assert_eq!(Ok(PoolVec(vertices)), PoolVec::<Vector3>::from_variant(&pool));
+}}
+
+/// Returns `true` if `a` and `b` are equal up to rounding errors relative to their magnitude.
+fn nearly_equal(a: Vector3, b: Vector3) -> bool {
+    (a - b).length() <= 1e-4 * a.length().max(1.0)
+}
+
+crate::godot_itest! { bench_xform_slice {
+    const LEN: usize = 100_000;
+    const ITERATIONS: u32 = 20;
+
+    let vertices: Vec<Vector3> = (0..LEN)
+        .map(|i| Vector3::new(i as real, 1.0, -(i as real)))
+        .collect();
+
+    // A rigid transform, so the vertices don't grow when it is applied repeatedly.
+    let transform = Transform {
+        basis: Basis::from_euler(Vector3::new(0.3, -0.2, 1.1)),
+        origin: Vector3::new(4.0, 5.0, 6.0),
+    };
+
+    let mut single = vertices.clone();
+    measure("Transform::xform (per vector)", ITERATIONS, || {
+        for v in single.iter_mut() {
+            *v = transform.xform(*v);
+        }
+    });
+
+    let mut batch = vertices.clone();
+    measure("Transform::xform_slice", ITERATIONS, || {
+        transform.xform_slice(&mut batch)
+    });
+
+    let mut pool = PoolArray::from_vec(vertices.clone());
+    measure("Transform::xform_slice (PoolArray)", ITERATIONS, || {
+        transform.xform_slice(&mut pool.write())
+    });
+
+    assert!(single.iter().zip(&batch).all(|(&a, &b)| nearly_equal(a, b)));
+    assert!(single.iter().zip(pool.read().iter()).all(|(&a, &b)| nearly_equal(a, b)));
+
+    let mut single = vertices.clone();
+    measure("Basis::xform (per vector)", ITERATIONS, || {
+        for v in single.iter_mut() {
+            *v = transform.basis.xform(*v);
+        }
+    });
+
+    let mut batch = vertices;
+    measure("Basis::xform_slice", ITERATIONS, || {
+        transform.basis.xform_slice(&mut batch)
+    });
+
+    assert!(single.iter().zip(&batch).all(|(&a, &b)| nearly_equal(a, b)));
+
+    let vertices: Vec<Vector2> = (0..LEN).map(|i| Vector2::new(i as real, 1.0)).collect();
+    let transform = Transform2D::from_scale_rotation_origin(Vector2::ONE, 0.7, Vector2::new(4.0, 5.0));
+
+    let mut single = vertices.clone();
+    measure("Transform2D::xform (per vector)", ITERATIONS, || {
+        for v in single.iter_mut() {
+            *v = transform.xform(*v);
+        }
+    });
+
+    let mut batch = vertices;
+    measure("Transform2D::xform_slice", ITERATIONS, || {
+        transform.xform_slice(&mut batch)
+    });
+
+    assert_eq!(single, batch);
+}}
+
+crate::godot_itest! { bench_aabb_from_points {
+    const LEN: usize = 100_000;
+    const ITERATIONS: u32 = 20;
+
+    let points: Vec<Vector3> = (0..LEN)
+        .map(|i| {
+            let angle = i as real * 0.01;
+            Vector3::new(angle.cos(), angle.sin(), i as real) * (i % 100) as real
+        })
+        .collect();
+
+    let merge = |points: &[Vector3]| {
+        points[1..]
+            .iter()
+            .fold(Aabb::new(points[0], Vector3::ZERO), |aabb, &point| {
+                aabb.merge(Aabb::new(point, Vector3::ZERO))
+            })
+    };
+
+    // Keep the results, so the computations can't be optimized away.
+    let mut merged = Vec::new();
+    let mut from_points = Vec::new();
+    let mut from_pool = Vec::new();
+
+    measure("Aabb::merge (per point)", ITERATIONS, || merged.push(merge(&points)));
+    measure("Aabb::from_points", ITERATIONS, || {
+        from_points.push(Aabb::from_points(&points).unwrap())
+    });
+
+    let pool = PoolArray::from_vec(points);
+    measure("Aabb::from_points (PoolArray)", ITERATIONS, || {
+        from_pool.push(Aabb::from_points(&pool.read()).unwrap())
+    });
+
+    let (merged, from_points) = (merged[0], from_points[0]);
+    assert!(from_pool.iter().all(|&aabb| aabb == from_points));
+    assert!(merged.is_equal_approx(from_points));
+}}
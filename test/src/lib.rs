@@ -4,12 +4,14 @@
 use gdnative::prelude::*;
 use gdnative_core::godot_itest;
 
+mod bench;
 mod test_as_arg;
 mod test_async;
 mod test_constructor;
 mod test_derive;
 mod test_free_ub;
 mod test_generic_class;
+mod test_geometry2d;
 mod test_indexed_props;
 mod test_map_owned;
 mod test_register;
@@ -26,8 +28,11 @@ pub extern "C" fn run_tests(
 ) -> gdnative::sys::godot_variant {
     let mut status = true;
     status &= gdnative::core_types::test_string();
+    status &= gdnative::core_types::test_string_ops();
     status &= gdnative::core_types::test_string_name_eq();
     status &= gdnative::core_types::test_string_name_ord();
+    status &= gdnative::core_types::test_name_arg();
+    status &= gdnative::core_types::test_node_path_buf_conversion();
 
     status &= gdnative::core_types::test_dictionary();
     status &= gdnative::core_types::test_dictionary_clone_clear();
@@ -35,6 +40,10 @@ pub extern "C" fn run_tests(
     status &= gdnative::core_types::test_array();
     status &= gdnative::core_types::test_array_debug();
     status &= gdnative::core_types::test_array_clone_clear();
+    status &= gdnative::core_types::test_typed_array();
+    status &= gdnative::core_types::test_typed_array_validation();
+    status &= gdnative::core_types::test_typed_dictionary();
+    status &= gdnative::core_types::test_typed_dictionary_validation();
 
     status &= gdnative::core_types::test_variant_nil();
     status &= gdnative::core_types::test_variant_i64();
@@ -50,10 +59,23 @@ pub extern "C" fn run_tests(
     status &= gdnative::core_types::test_variant_hash_set();
     status &= gdnative::core_types::test_variant_vec();
     status &= gdnative::core_types::test_to_variant_iter();
+    status &= gdnative::core_types::test_variant_ordered_collections();
+    status &= gdnative::core_types::test_variant_fixed_arrays();
+    status &= gdnative::core_types::test_variant_std_wrappers();
     status &= gdnative::core_types::test_variant_tuple();
     status &= gdnative::core_types::test_variant_dispatch();
-
+    status &= gdnative::core_types::marshal::test_marshal_strings_and_collections();
+    status &= gdnative::core_types::marshal::test_marshal_pool_arrays();
+    status &= gdnative::core_types::marshal::test_marshal_node_path();
+    status &= gdnative::core_types::text::test_text_dispatch_round_trip();
+    status &= gdnative::core_types::json::test_json_round_trip();
+    status &= gdnative::core_types::json::test_json_parse_errors();
+
+    status &= gdnative::core_types::pool_vec::test_pool_vec();
     status &= gdnative::core_types::test_byte_array_access();
+    status &= gdnative::core_types::test_byte_array_compression();
+    status &= gdnative::core_types::test_byte_array_encoding();
+    status &= gdnative::core_types::test_byte_array_matches_engine();
     status &= gdnative::core_types::test_int32_array_access();
     status &= gdnative::core_types::test_float32_array_access();
     status &= gdnative::core_types::test_color_array_access();
@@ -66,6 +88,8 @@ pub extern "C" fn run_tests(
     status &= test_nil_object_return_value();
     status &= test_rust_class_construction();
     status &= test_underscore_method_binding();
+    status &= test_color_named_matches_engine();
+    status &= test_random_number_generator_matches_engine();
 
     status &= test_as_arg::run_tests();
     status &= test_async::run_tests();
@@ -73,6 +97,7 @@ pub extern "C" fn run_tests(
     status &= test_derive::run_tests();
     status &= test_free_ub::run_tests();
     status &= test_generic_class::run_tests();
+    status &= test_geometry2d::run_tests();
     status &= test_indexed_props::run_tests();
     status &= test_map_owned::run_tests();
     status &= test_register::run_tests();
@@ -81,6 +106,7 @@ pub extern "C" fn run_tests(
     status &= test_vararray_return::run_tests();
     status &= test_variant_call_args::run_tests();
     status &= test_variant_ops::run_tests();
+    status &= bench::run_tests();
 
     Variant::new(status).leak()
 }
@@ -99,6 +125,45 @@ godot_itest! {
         assert!(option.is_none());
         node.free();
     }
+
+    test_color_named_matches_engine {
+        let expression = gdnative::api::Expression::new();
+        for &(name, color) in Color::named_colors() {
+            expression
+                .parse(format!("ColorN(\"{name}\")"), PoolArray::new())
+                .unwrap();
+            let engine_color = expression.execute(VariantArray::new_shared(), Null::null(), true);
+            assert_eq!(Some(color), engine_color.to::<Color>(), "{name}");
+        }
+    }
+
+    test_random_number_generator_matches_engine {
+        use gdnative::core_types::RealConv;
+        use gdnative::globalscope::RandomNumberGenerator;
+
+        for seed in [0, 1234, -5, i64::MAX] {
+            let engine_rng = gdnative::api::RandomNumberGenerator::new();
+            engine_rng.set_seed(seed);
+            let mut rng = RandomNumberGenerator::from_seed(seed as u64);
+            assert_eq!(engine_rng.state() as u64, rng.state(), "seed {seed}");
+
+            for _ in 0..100 {
+                assert_eq!(engine_rng.randi(), i64::from(rng.randi()), "seed {seed}");
+                assert_eq!(engine_rng.randf(), rng.randf().as_f64(), "seed {seed}");
+                assert_eq!(
+                    engine_rng.randi_range(-10, 1000),
+                    i64::from(rng.randi_range(-10, 1000)),
+                    "seed {seed}"
+                );
+
+                // Depends on the platform's math library, so only compare approximately.
+                let engine_value = engine_rng.randfn(2.0, 3.0);
+                let value = rng.randfn(2.0, 3.0).as_f64();
+                assert!((engine_value - value).abs() < 1e-4, "seed {seed}");
+            }
+            assert_eq!(engine_rng.state() as u64, rng.state(), "seed {seed}");
+        }
+    }
 }
 
 #[derive(NativeClass)]
@@ -259,6 +324,7 @@ fn delegate_init(handle: InitHandle) {
     test_derive::register(handle);
     test_free_ub::register(handle);
     test_generic_class::register(handle);
+    test_geometry2d::register(handle);
     test_indexed_props::register(handle);
     test_map_owned::register(handle);
     test_register::register(handle);
@@ -266,6 +332,7 @@ fn delegate_init(handle: InitHandle) {
     test_vararray_return::register(handle);
     test_variant_call_args::register(handle);
     test_variant_ops::register(handle);
+    bench::register(handle);
 }
 
 fn terminate(_term_info: &gdnative::init::TerminateInfo) {
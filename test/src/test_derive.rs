@@ -11,6 +11,9 @@ pub(crate) fn run_tests() -> bool {
     status &= test_derive_to_variant();
     status &= test_derive_to_variant_repr();
     status &= test_derive_to_variant_str();
+    status &= test_derive_to_variant_tagged();
+    status &= test_derive_to_variant_flatten();
+    status &= test_derive_from_variant_error_path();
     status &= test_derive_owned_to_variant();
     status &= test_derive_nativeclass();
     status &= test_derive_nativeclass_without_constructor();
@@ -271,6 +274,221 @@ crate::godot_itest! { test_derive_to_variant_str {
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
+crate::godot_itest! { test_derive_to_variant_tagged {
+    use gdnative::core_types::VariantEnumRepr;
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    struct Heal {
+        amount: i64,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(tag = "type", rename_all = "snake_case")]
+    enum Internal {
+        Hit { damage: i64 },
+        Heal(Heal),
+        Died,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(tag = "t", content = "c")]
+    enum Adjacent {
+        Unit,
+        Newtype(i64),
+        Tuple(i64, bool),
+        Struct { x: f64 },
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(untagged)]
+    enum Untagged {
+        Unit,
+        Int(i64),
+        Struct { name: String },
+    }
+
+    let variant = Internal::Hit { damage: 3 }.to_variant();
+    let dict = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(2, dict.len());
+    assert_eq!(Some("hit".into()), dict.get("type").and_then(|v| v.to::<String>()));
+    assert_eq!(Some(3), dict.get("damage").and_then(|v| v.to::<i64>()));
+
+    for value in [Internal::Hit { damage: 3 }, Internal::Heal(Heal { amount: 2 }), Internal::Died] {
+        assert_eq!(Ok(value.clone()), Internal::from_variant(&value.to_variant()));
+    }
+
+    // The tag takes precedence over entries of the payload with the same key
+    #[derive(Clone, PartialEq, Debug, ToVariant)]
+    #[variant(tag = "type")]
+    enum Wrapped {
+        Map(HashMap<String, i64>),
+        Newtype(Amount),
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant)]
+    struct Amount(i64);
+
+    let payload = HashMap::from_iter([("type".to_string(), 1), ("amount".to_string(), 2)]);
+    let dict = Wrapped::Map(payload).to_variant().to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(Some("Map".into()), dict.get("type").and_then(|v| v.to::<String>()));
+    assert_eq!(Some(2), dict.get("amount").and_then(|v| v.to::<i64>()));
+
+    // Payloads that aren't `Dictionary`s can't be represented
+    let result = std::panic::catch_unwind(|| Wrapped::Newtype(Amount(3)).to_variant());
+    assert!(result.is_err());
+
+    let input = HashMap::from_iter([("type", "hit"), ("damage", "lots")]).to_variant();
+    assert_eq!(
+        Err(FromVariantError::InvalidEnumVariant {
+            variant: "hit",
+            error: Box::new(FromVariantError::InvalidField {
+                field_name: "damage",
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    variant_type: VariantType::GodotString,
+                    expected: VariantType::I64,
+                }),
+            }),
+        }),
+        Internal::from_variant(&input),
+    );
+
+    let input = HashMap::from_iter([("damage", 3)]).to_variant();
+    assert_eq!(
+        Err(FromVariantError::InvalidEnumRepr {
+            expected: VariantEnumRepr::InternallyTagged,
+            error: Box::new(FromVariantError::InvalidField {
+                field_name: "type",
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    variant_type: VariantType::Nil,
+                    expected: VariantType::GodotString,
+                }),
+            }),
+        }),
+        Internal::from_variant(&input),
+    );
+
+    let variant = Adjacent::Newtype(42).to_variant();
+    let dict = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(Some("Newtype".into()), dict.get("t").and_then(|v| v.to::<String>()));
+    assert_eq!(Some(42), dict.get("c").and_then(|v| v.to::<i64>()));
+    assert!(!Adjacent::Unit.to_variant().to::<Dictionary>().unwrap().contains("c"));
+
+    for value in [
+        Adjacent::Unit,
+        Adjacent::Newtype(42),
+        Adjacent::Tuple(1, true),
+        Adjacent::Struct { x: 1.5 },
+    ] {
+        assert_eq!(Ok(value.clone()), Adjacent::from_variant(&value.to_variant()));
+    }
+
+    assert!(Untagged::Unit.to_variant().is_nil());
+    assert_eq!(Some(7), Untagged::Int(7).to_variant().to::<i64>());
+    for value in [Untagged::Unit, Untagged::Int(7), Untagged::Struct { name: "foo".into() }] {
+        assert_eq!(Ok(value.clone()), Untagged::from_variant(&value.to_variant()));
+    }
+
+    match Untagged::from_variant(&true.to_variant()) {
+        Err(FromVariantError::NoMatchingEnumVariant { errors }) => {
+            let attempted: Vec<_> = errors
+                .iter()
+                .map(|error| match error {
+                    FromVariantError::InvalidEnumVariant { variant, .. } => *variant,
+                    _ => panic!("unexpected error: {error:?}"),
+                })
+                .collect();
+            assert_eq!(vec!["Unit", "Int", "Struct"], attempted);
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+crate::godot_itest! { test_derive_to_variant_flatten {
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    struct Stats {
+        level: i64,
+        hp: i64,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    struct Player {
+        name: String,
+        #[variant(flatten)]
+        stats: Stats,
+        #[variant(flatten)]
+        score: i64,
+        #[variant(flatten)]
+        title: Option<String>,
+    }
+
+    let player = Player {
+        name: "foo".into(),
+        stats: Stats { level: 3, hp: 20 },
+        score: 100,
+        title: None,
+    };
+
+    // Dictionaries are merged, other values are kept under their own key, and `Nil` is omitted
+    let dict = player.to_variant().to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(4, dict.len());
+    assert_eq!(Some(3), dict.get("level").and_then(|v| v.to::<i64>()));
+    assert_eq!(Some(100), dict.get("score").and_then(|v| v.to::<i64>()));
+    assert!(!dict.contains("stats"));
+    assert!(!dict.contains("title"));
+
+    let titled = Player { title: Some("hero".into()), ..player.clone() };
+    for value in [player, titled] {
+        assert_eq!(Ok(value.clone()), Player::from_variant(&value.to_variant()));
+    }
+
+    let input = HashMap::from_iter([
+        ("name", "foo".to_variant()),
+        ("level", 3.to_variant()),
+        ("hp", 20.to_variant()),
+    ]);
+    match Player::from_variant(&input.to_variant()) {
+        Err(FromVariantError::InvalidField { field_name: "score", .. }) => {}
+        other => panic!("unexpected result: {other:?}"),
+    }
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+crate::godot_itest! { test_derive_from_variant_error_path {
+    #[derive(Debug, FromVariant)]
+    struct Weapon {
+        #[allow(dead_code)]
+        damage: i64,
+    }
+
+    #[derive(Debug, FromVariant)]
+    struct Inventory {
+        #[allow(dead_code)]
+        items: HashMap<String, Weapon>,
+    }
+
+    let sword = HashMap::from_iter([("damage", "lots")]);
+    let inventory = HashMap::from_iter([("items", HashMap::from_iter([("sword", sword)]))]);
+    let input = vec![inventory].to_variant();
+
+    let err = Vec::<Inventory>::from_variant(&input).unwrap_err();
+    assert_eq!(
+        "[0].items[\"sword\"].damage: invalid variant type: expected I64, got GodotString",
+        err.to_string(),
+    );
+    assert_eq!(
+        &FromVariantError::InvalidVariantType {
+            variant_type: VariantType::GodotString,
+            expected: VariantType::I64,
+        },
+        err.root_cause(),
+    );
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
 crate::godot_itest! { test_derive_owned_to_variant {
     #[derive(OwnedToVariant)]
     struct ToVar {
@@ -0,0 +1,308 @@
+use gdnative::api::Geometry;
+use gdnative::core_types::geometry2d::{self, PolyJoinType};
+use gdnative::core_types::{real, RealConv};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_geometry2d_queries_match_engine();
+    status &= test_geometry2d_triangulation_matches_engine();
+    status &= test_geometry2d_boolean_operations_match_engine();
+    status &= test_geometry2d_offset_matches_engine();
+
+    status
+}
+
+pub(crate) fn register(_handle: InitHandle) {}
+
+fn v(x: real, y: real) -> Vector2 {
+    Vector2::new(x, y)
+}
+
+/// Polygons covering the corner cases of the polygon operations: shared edges and vertices,
+/// concave and self-intersecting outlines, collinear and duplicate vertices, holes in the results,
+/// and coordinates off the fixed-point grid.
+fn polygons() -> Vec<Vec<Vector2>> {
+    vec![
+        vec![v(0.0, 0.0), v(10.0, 0.0), v(10.0, 10.0), v(0.0, 10.0)],
+        vec![v(5.0, 5.0), v(15.0, 5.0), v(15.0, 15.0), v(5.0, 15.0)],
+        vec![v(10.0, 0.0), v(20.0, 0.0), v(20.0, 10.0), v(10.0, 10.0)],
+        vec![v(3.0, 3.0), v(7.0, 3.0), v(7.0, 7.0), v(3.0, 7.0)],
+        vec![v(0.0, 0.0), v(0.0, 10.0), v(10.0, 10.0), v(10.0, 0.0)],
+        vec![
+            v(0.0, 0.0),
+            v(12.0, 0.0),
+            v(12.0, 4.0),
+            v(4.0, 4.0),
+            v(4.0, 12.0),
+            v(0.0, 12.0),
+        ],
+        vec![
+            v(-2.0, 5.0),
+            v(12.0, 5.0),
+            v(12.0, 8.0),
+            v(-2.0, 8.0),
+            v(-2.0, 6.0),
+        ],
+        vec![
+            v(5.0, -3.0),
+            v(6.5, 3.2),
+            v(13.0, 3.5),
+            v(7.5, 6.8),
+            v(9.5, 13.0),
+            v(5.0, 9.0),
+            v(0.5, 13.0),
+            v(2.5, 6.8),
+            v(-3.0, 3.5),
+            v(3.5, 3.2),
+        ],
+        vec![v(0.0, 0.0), v(10.0, 10.0), v(10.0, 0.0), v(0.0, 10.0)],
+        vec![
+            v(1.0, 1.0),
+            v(1.0, 1.0),
+            v(6.0, 1.0),
+            v(11.0, 1.0),
+            v(11.0, 6.0),
+            v(1.0, 6.0),
+            v(1.0, 1.0),
+        ],
+        vec![
+            v(0.123_456, 0.654_321),
+            v(9.876_543, 1.234_567),
+            v(8.765_432, 9.876_543),
+            v(1.357_913, 8.642_097),
+        ],
+        vec![v(0.0, 0.0), v(5.0, 5.0)],
+        vec![],
+    ]
+}
+
+fn pool(polygon: &[Vector2]) -> Variant {
+    PoolArray::from_slice(polygon).to_variant()
+}
+
+fn engine_polygons(result: Variant) -> Vec<Vec<Vector2>> {
+    result
+        .to::<Vec<PoolArray<Vector2>>>()
+        .expect("should be an array of polygons")
+        .iter()
+        .map(|polygon| polygon.to_vec())
+        .collect()
+}
+
+crate::godot_itest! { test_geometry2d_queries_match_engine {
+    let geometry = Geometry::godot_singleton();
+    let polygons = polygons();
+
+    let points = [
+        v(5.0, 5.0),
+        v(0.0, 0.0),
+        v(10.0, 5.0),
+        v(-1.0, 5.0),
+        v(2.0, 11.0),
+        v(6.0, 0.5),
+        v(13.0, 3.0),
+    ];
+
+    for polygon in &polygons {
+        let engine_clockwise = unsafe { geometry.call("is_polygon_clockwise", &[pool(polygon)]) };
+        assert_eq!(
+            Some(geometry2d::is_polygon_clockwise(polygon)),
+            engine_clockwise.to::<bool>(),
+            "{polygon:?}"
+        );
+
+        for &point in &points {
+            let engine_inside = unsafe {
+                geometry.call("is_point_in_polygon", &[point.to_variant(), pool(polygon)])
+            };
+            assert_eq!(
+                Some(geometry2d::is_point_in_polygon(point, polygon)),
+                engine_inside.to::<bool>(),
+                "{point:?} in {polygon:?}"
+            );
+        }
+
+        let engine_hull = unsafe { geometry.call("convex_hull_2d", &[pool(polygon)]) };
+        assert_eq!(
+            geometry2d::convex_hull(polygon),
+            engine_hull
+                .to::<PoolArray<Vector2>>()
+                .expect("should be a polygon")
+                .to_vec(),
+            "{polygon:?}"
+        );
+    }
+
+    let segments = [
+        (v(0.0, 0.0), v(10.0, 10.0)),
+        (v(0.0, 10.0), v(10.0, 0.0)),
+        (v(5.0, 0.0), v(5.0, 20.0)),
+        (v(-3.0, 2.0), v(12.0, 2.0)),
+        (v(1.0, 1.0), v(4.0, 4.0)),
+        (v(2.0, 2.0), v(2.0, 2.0)),
+    ];
+    let radii: [real; 3] = [0.5, 2.0, 8.0];
+
+    for &(from_a, to_a) in &segments {
+        for &(from_b, to_b) in &segments {
+            let args = [
+                from_a.to_variant(),
+                to_a.to_variant(),
+                from_b.to_variant(),
+                to_b.to_variant(),
+            ];
+
+            let engine_intersection =
+                unsafe { geometry.call("segment_intersects_segment_2d", &args) };
+            let intersection = geometry2d::segment_intersects_segment(from_a, to_a, from_b, to_b);
+            match (intersection, engine_intersection.to::<Vector2>()) {
+                (Some(point), Some(engine_point)) => {
+                    assert!(point.is_equal_approx(engine_point), "{args:?}")
+                }
+                (None, None) => {}
+                (point, engine_point) => panic!("{args:?}: {point:?} != {engine_point:?}"),
+            }
+
+            let engine_closest = unsafe {
+                geometry
+                    .call("get_closest_points_between_segments_2d", &args)
+                    .to::<PoolArray<Vector2>>()
+                    .expect("should be two points")
+                    .to_vec()
+            };
+            let (closest_a, closest_b) =
+                geometry2d::closest_points_between_segments(from_a, to_a, from_b, to_b);
+            assert!(closest_a.is_equal_approx(engine_closest[0]), "{args:?}");
+            assert!(closest_b.is_equal_approx(engine_closest[1]), "{args:?}");
+        }
+
+        for &point in &points {
+            let args = [point.to_variant(), from_a.to_variant(), to_a.to_variant()];
+
+            let engine_closest = unsafe {
+                geometry
+                    .call("get_closest_point_to_segment_2d", &args)
+                    .to::<Vector2>()
+                    .expect("should be a point")
+            };
+            let closest = geometry2d::closest_point_to_segment(point, from_a, to_a);
+            assert!(closest.is_equal_approx(engine_closest), "{args:?}");
+
+            let engine_closest = unsafe {
+                geometry
+                    .call("get_closest_point_to_segment_uncapped_2d", &args)
+                    .to::<Vector2>()
+                    .expect("should be a point")
+            };
+            let closest = geometry2d::closest_point_to_segment_uncapped(point, from_a, to_a);
+            assert!(closest.is_equal_approx(engine_closest), "{args:?}");
+
+            for radius in radii {
+                let engine_fraction = unsafe {
+                    geometry
+                        .call(
+                            "segment_intersects_circle",
+                            &[
+                                from_a.to_variant(),
+                                to_a.to_variant(),
+                                point.to_variant(),
+                                radius.to_variant(),
+                            ],
+                        )
+                        .to::<f64>()
+                        .expect("should be a number")
+                };
+                let fraction =
+                    geometry2d::segment_intersects_circle(from_a, to_a, point, radius)
+                        .map_or(-1.0, RealConv::as_f64);
+                assert!(
+                    (fraction - engine_fraction).abs() < 1e-5,
+                    "{from_a:?} {to_a:?} {point:?} {radius}"
+                );
+            }
+        }
+    }
+}}
+
+crate::godot_itest! { test_geometry2d_triangulation_matches_engine {
+    let geometry = Geometry::godot_singleton();
+
+    for polygon in &polygons() {
+        let engine_triangles = unsafe {
+            geometry
+                .call("triangulate_polygon", &[pool(polygon)])
+                .to::<PoolArray<i32>>()
+                .expect("should be an array of indices")
+                .to_vec()
+        };
+        let triangles: Vec<i32> = geometry2d::triangulate_polygon(polygon)
+            .map(|triangles| triangles.into_iter().map(|i| i as i32).collect())
+            .unwrap_or_default();
+        assert_eq!(engine_triangles, triangles, "{polygon:?}");
+    }
+}}
+
+crate::godot_itest! { test_geometry2d_boolean_operations_match_engine {
+    let geometry = Geometry::godot_singleton();
+    let polygons = polygons();
+
+    type Operation = fn(&[Vector2], &[Vector2]) -> Vec<Vec<Vector2>>;
+    let operations: [(&str, Operation); 4] = [
+        ("merge_polygons_2d", geometry2d::merge_polygons),
+        ("clip_polygons_2d", geometry2d::clip_polygons),
+        ("intersect_polygons_2d", geometry2d::intersect_polygons),
+        ("exclude_polygons_2d", geometry2d::exclude_polygons),
+    ];
+
+    for (method, operation) in operations {
+        for polygon_a in &polygons {
+            for polygon_b in &polygons {
+                let engine_result = unsafe {
+                    geometry.call(method, &[pool(polygon_a), pool(polygon_b)])
+                };
+                assert_eq!(
+                    engine_polygons(engine_result),
+                    operation(polygon_a, polygon_b),
+                    "{method}({polygon_a:?}, {polygon_b:?})"
+                );
+            }
+        }
+    }
+}}
+
+crate::godot_itest! { test_geometry2d_offset_matches_engine {
+    let geometry = Geometry::godot_singleton();
+
+    // The engine's `JOIN_SQUARE`, `JOIN_ROUND` and `JOIN_MITER`.
+    let join_types = [
+        (0, PolyJoinType::Square),
+        (1, PolyJoinType::Round),
+        (2, PolyJoinType::Miter),
+    ];
+
+    let deltas: [real; 7] = [-6.0, -1.5, -0.25, 0.0, 0.5, 2.0, 7.5];
+
+    for polygon in &polygons() {
+        for delta in deltas {
+            for (engine_join_type, join_type) in join_types {
+                let engine_result = unsafe {
+                    geometry.call(
+                        "offset_polygon_2d",
+                        &[
+                            pool(polygon),
+                            delta.to_variant(),
+                            engine_join_type.to_variant(),
+                        ],
+                    )
+                };
+                assert_eq!(
+                    engine_polygons(engine_result),
+                    geometry2d::offset_polygon(polygon, delta, join_type),
+                    "offset_polygon_2d({polygon:?}, {delta}, {join_type:?})"
+                );
+            }
+        }
+    }
+}}
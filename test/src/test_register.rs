@@ -11,6 +11,7 @@ pub(crate) fn run_tests() -> bool {
     status &= test_advanced_methods();
     status &= test_varargs_gets();
     status &= test_varargs_to_tuple();
+    status &= test_typed_containers();
 
     status
 }
@@ -22,6 +23,7 @@ pub(crate) fn register(handle: InitHandle) {
     handle.add_class::<AdvancedMethods>();
     handle.add_class::<VarargsGets>();
     handle.add_class::<VarargsToTuple>();
+    handle.add_class::<TypedContainers>();
 }
 
 #[cfg(feature = "no-manual-register")]
@@ -306,3 +308,58 @@ crate::godot_itest! { test_varargs_to_tuple {
     let args = [3_i64.to_variant(), 4_i64.to_variant(), 5_i64.to_variant()];
     assert_eq!(unsafe { base.call("calc", &args).to() }, Some(7));
 }}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct TypedContainers {
+    #[property]
+    scores: Array<i64>,
+}
+
+#[methods]
+impl TypedContainers {
+    fn new(_owner: TRef<Reference>) -> Self {
+        TypedContainers {
+            scores: Array::new_shared(),
+        }
+    }
+
+    #[method]
+    fn doubled(&self, values: Array<i64>) -> Array<i64, Unique> {
+        values.iter().map(|v| v * 2).collect()
+    }
+
+    #[method]
+    fn total(&self, values: TypedDictionary<String, i64>) -> i64 {
+        values.iter().map(|(_, v)| v).sum()
+    }
+}
+
+crate::godot_itest! { test_typed_containers {
+    let thing = Instance::<TypedContainers, _>::new();
+    let base = thing.base();
+
+    let values = VariantArray::new();
+    values.push(1);
+    values.push(2);
+    let doubled = unsafe { base.call("doubled", &[values.into_shared().to_variant()]) };
+    assert_eq!(Some(vec![2, 4]), doubled.to::<Array<i64>>().map(|a| a.to_vec()));
+
+    // Elements of the wrong type are rejected before the method is called
+    let values = VariantArray::new();
+    values.push("one");
+    let result = unsafe { base.call("doubled", &[values.into_shared().to_variant()]) };
+    assert!(result.is_nil());
+
+    let values = Dictionary::new();
+    values.insert("a", 3);
+    values.insert("b", 4);
+    let total = unsafe { base.call("total", &[values.into_shared().to_variant()]) };
+    assert_eq!(Some(7), total.to::<i64>());
+
+    let scores = Array::<i64, Unique>::new();
+    scores.push(10);
+    base.set("scores", scores.owned_to_variant());
+    let scores = base.get("scores").to::<Array<i64>>().unwrap();
+    assert_eq!(vec![10], scores.to_vec());
+}}
@@ -1,3 +1,4 @@
+use gdnative::core_types::{real, real_consts};
 use gdnative::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +14,7 @@ pub(crate) fn run_tests() -> bool {
         return false;
     }
 
+    status &= test_variant_format();
     status &= test_ron();
     status &= test_json();
     status &= test_yaml();
@@ -45,7 +47,7 @@ struct Foo {
     // v_arr: VariantArray, //TODO(#990): PartialEq
     byte_arr: PoolArray<u8>,
     int_arr: PoolArray<i32>,
-    float_arr: PoolArray<f32>,
+    float_arr: PoolArray<real>,
     str_arr: PoolArray<GodotString>,
     vec2_arr: PoolArray<Vector2>,
     vec3_arr: PoolArray<Vector3>,
@@ -81,7 +83,7 @@ impl Foo {
                 position: Vector3::new(8.2, 9.8, 10.11),
                 size: Vector3::new(12.13, 14.15, 16.17),
             },
-            basis: Basis::IDENTITY.rotated(Vector3::UP, std::f32::consts::TAU / 3.0),
+            basis: Basis::IDENTITY.rotated(Vector3::UP, real_consts::TAU / 3.0),
             xform: Transform {
                 basis: Basis::from_euler(Vector3::new(18.19, -20.21, 22.23)),
                 origin: Vector3::new(24.25, 26.27, 28.29),
@@ -128,6 +130,72 @@ crate::godot_itest! { test_dispatch_eq {
     assert_eq!(foo, result);
 }}
 
+crate::godot_itest! { test_variant_format {
+    use gdnative::core_types::variant::{from_variant, to_variant, EnumRepr, VariantSerializer};
+
+    let foo = Foo::new();
+    let variant = to_variant(&foo).expect("Foo to Variant");
+    let dict = variant.try_to::<Dictionary>().expect("Foo as Dictionary");
+    assert_eq!(Some(foo.vec2.to_variant()), dict.get("vec2"));
+    assert_eq!(Some(foo.xform.to_variant()), dict.get("xform"));
+    assert_eq!(Some(foo.path.to_variant()), dict.get("path"));
+    assert_eq!(Some(Variant::nil()), dict.get("none"));
+    let result = from_variant::<Foo>(&variant).expect("Foo from Variant");
+    assert_eq!(foo, result);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect { w: i64, h: i64 },
+    }
+
+    let shapes = vec![Shape::Empty, Shape::Circle(0.5), Shape::Rect { w: 2, h: 3 }];
+    let variant = to_variant(&shapes).expect("shapes to Variant");
+    let array = variant.try_to::<VariantArray>().expect("shapes as VariantArray");
+    assert_eq!("Empty".to_variant(), array.get(0));
+    assert_eq!(shapes, from_variant::<Vec<Shape>>(&variant).expect("shapes from Variant"));
+
+    let ser = VariantSerializer::new().with_enum_repr(EnumRepr::Index);
+    let variant = shapes.serialize(ser).expect("shapes to Variant by index");
+    let array = variant.try_to::<VariantArray>().expect("shapes as VariantArray");
+    assert_eq!(0.to_variant(), array.get(0));
+    assert_eq!(shapes, from_variant::<Vec<Shape>>(&variant).expect("shapes from Variant by index"));
+
+    assert!(to_variant(&u64::MAX).is_err());
+    assert!(from_variant::<Foo>(&42.to_variant()).is_err());
+
+    // User structs named like core types are only converted when they have the same fields
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Rect2 {
+        position: Vector2,
+        size: Vector2,
+        label: String,
+    }
+
+    let rect = Rect2 {
+        position: Vector2::new(1.0, 2.0),
+        size: Vector2::new(3.0, 4.0),
+        label: "area".to_string(),
+    };
+    let variant = to_variant(&rect).expect("Rect2 to Variant");
+    let dict = variant.try_to::<Dictionary>().expect("user Rect2 as Dictionary");
+    assert_eq!(Some("area".to_variant()), dict.get("label"));
+    assert_eq!(rect, from_variant::<Rect2>(&variant).expect("Rect2 from Variant"));
+
+    #[derive(Serialize)]
+    struct Color {
+        r: f32,
+        g: f32,
+        b: f32,
+        a: f32,
+    }
+
+    let color = Color { r: 1.0, g: 0.5, b: 0.0, a: 1.0 };
+    let variant = to_variant(&color).expect("Color to Variant");
+    assert_eq!(Some(gdnative::core_types::Color::from_rgb(1.0, 0.5, 0.0)), variant.to());
+}}
+
 crate::godot_itest! { test_ron {
     let foo = Foo::new();
 